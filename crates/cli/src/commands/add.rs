@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use changepacks_core::{ChangePackLog, Project, UpdateType, path_key};
+use changepacks_utils::{
+    changepack_log_file, get_changepacks_dir, get_relative_path, render_changepack_log,
+};
+use clap::Args;
+
+use super::{changepacks::collect_template_fields, from_pr::resolve_changes};
+use crate::{
+    CommandContext, GlobalOptions, notes_from_messages, parse_field, parse_preid,
+    prompter::InquirePrompter, read_message_file,
+};
+
+#[derive(Args, Debug)]
+#[command(about = "Write a changepack for the named packages without prompting")]
+pub struct AddArgs {
+    /// Package and its bump as `NAME=major|minor|patch|prerelease`; the package may also be
+    /// given by manifest path. Repeat for several packages.
+    #[arg(short, long = "package", value_name = "NAME=BUMP", required = true, value_parser = parse_package_bump)]
+    pub packages: Vec<(String, UpdateType)>,
+
+    /// Changepack notes (markdown). Repeat to write one bullet per message.
+    #[arg(short, long, conflicts_with = "message_file")]
+    pub message: Vec<String>,
+
+    /// Read changepack notes from a file (`-` for stdin)
+    #[arg(short = 'F', long)]
+    pub message_file: Option<PathBuf>,
+
+    /// Prerelease identifier for `prerelease` bumps (e.g. `beta` for `1.2.0-beta.3`)
+    #[arg(long, value_name = "ID", value_parser = parse_preid)]
+    pub preid: Option<String>,
+
+    /// Answer a changepack template prompt (`key=value`). Can be repeated.
+    #[arg(long = "field", value_name = "KEY=VALUE", value_parser = parse_field)]
+    pub fields: Vec<(String, String)>,
+}
+
+/// Parse a `--package name=bump` pair
+fn parse_package_bump(raw: &str) -> Result<(String, UpdateType), String> {
+    let parsed = raw.rsplit_once('=').and_then(|(package, update_type)| {
+        let update_type = match update_type.trim().to_ascii_lowercase().as_str() {
+            "major" => UpdateType::Major,
+            "minor" => UpdateType::Minor,
+            "patch" => UpdateType::Patch,
+            "prerelease" => UpdateType::Prerelease,
+            _ => return None,
+        };
+        let package = package.trim();
+        (!package.is_empty()).then(|| (package.to_string(), update_type))
+    });
+    parsed.ok_or_else(|| {
+        format!("expected NAME=BUMP with BUMP one of major, minor, patch, prerelease; got '{raw}'")
+    })
+}
+
+/// Write a changepack for the packages given on the command line
+///
+/// # Errors
+/// Returns error if a package is unknown, the notes are missing, a required template field
+/// is not given, or the changepack cannot be written.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new` and file writes; the argument
+/// parsing is unit tested and the command is covered by the cli integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_add(args: &AddArgs, global: &GlobalOptions) -> Result<()> {
+    let has_prerelease = args
+        .packages
+        .iter()
+        .any(|(_, update_type)| *update_type == UpdateType::Prerelease);
+    if args.preid.is_some() && !has_prerelease {
+        bail!("--preid only applies to prerelease bumps");
+    }
+    let notes = match &args.message_file {
+        Some(path) => Some(read_message_file(path)?),
+        None => notes_from_messages(args.message.clone()),
+    };
+    let Some(notes) = notes.filter(|notes| !notes.trim().is_empty()) else {
+        bail!("Pass the changepack notes with --message or --message-file");
+    };
+
+    let ctx = CommandContext::new(global, false).await?;
+    let projects: Vec<&Project> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .collect();
+    let changes = resolve_changes(&args.packages, &projects, "--package")?;
+    let fields = collect_template_fields(
+        &ctx.config.template.prompts,
+        &args.fields,
+        true,
+        &InquirePrompter,
+    )?;
+    let log = ChangePackLog::new(changes, notes)
+        .with_fields(fields)
+        .with_preid(args.preid.clone());
+
+    let changepacks_dir = get_changepacks_dir(&ctx.repo_root_path)?;
+    tokio::fs::create_dir_all(&changepacks_dir).await?;
+    let format = ctx.config.changepack_format;
+    let log_file = changepack_log_file(&changepacks_dir, &nanoid::nanoid!(), format);
+    tokio::fs::write(&log_file, render_changepack_log(&log, format)?).await?;
+    println!(
+        "Wrote {}",
+        path_key(&get_relative_path(&ctx.repo_root_path, &log_file)?)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use rstest::rstest;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        add: AddArgs,
+    }
+
+    #[rstest]
+    #[case("core=minor", "core", UpdateType::Minor)]
+    #[case("@scope/web = Major", "@scope/web", UpdateType::Major)]
+    #[case(
+        "packages/core/package.json=patch",
+        "packages/core/package.json",
+        UpdateType::Patch
+    )]
+    #[case("core=prerelease", "core", UpdateType::Prerelease)]
+    fn test_parse_package_bump(
+        #[case] raw: &str,
+        #[case] package: &str,
+        #[case] update_type: UpdateType,
+    ) {
+        assert_eq!(
+            parse_package_bump(raw).unwrap(),
+            (package.to_string(), update_type)
+        );
+    }
+
+    #[rstest]
+    #[case("core")]
+    #[case("core=huge")]
+    #[case("=minor")]
+    fn test_parse_package_bump_invalid(#[case] raw: &str) {
+        assert!(
+            parse_package_bump(raw)
+                .unwrap_err()
+                .contains("expected NAME=BUMP")
+        );
+    }
+
+    #[test]
+    fn test_add_args() {
+        let cli = TestCli::parse_from([
+            "add",
+            "-p",
+            "core=minor",
+            "--package",
+            "web=patch",
+            "-m",
+            "Add X",
+            "-m",
+            "Fix Y",
+            "--field",
+            "issue=ABC-1",
+        ]);
+        assert_eq!(
+            cli.add.packages,
+            [
+                ("core".to_string(), UpdateType::Minor),
+                ("web".to_string(), UpdateType::Patch)
+            ]
+        );
+        assert_eq!(cli.add.message, ["Add X", "Fix Y"]);
+        assert_eq!(cli.add.fields, [("issue".to_string(), "ABC-1".to_string())]);
+        assert!(TestCli::try_parse_from(["add", "-m", "Add X"]).is_err());
+        assert!(
+            TestCli::try_parse_from(["add", "-p", "core=minor", "-m", "x", "-F", "notes.md"])
+                .is_err()
+        );
+    }
+}
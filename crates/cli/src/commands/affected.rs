@@ -0,0 +1,335 @@
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use changepacks_core::{Project, path_key};
+use changepacks_utils::{ChangeFilter, changed_files};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{CommandContext, GlobalOptions, options::FormatOptions};
+
+#[derive(Args, Debug)]
+#[command(about = "List projects affected by changes, directly or through their dependencies")]
+pub struct AffectedArgs {
+    /// Git diff range, e.g. `main...HEAD` (default: diff against the base branch plus
+    /// uncommitted changes)
+    pub range: Option<String>,
+
+    /// Compare with the remote base branch when no range is given
+    #[arg(short, long)]
+    pub remote: bool,
+
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+
+    /// Print only the affected project directories, one per line (for `xargs` or task runners)
+    #[arg(long)]
+    pub paths: bool,
+
+    /// Like `--paths`, but terminate each directory with NUL (for `xargs -0`)
+    #[arg(long)]
+    pub print0: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct AffectedProject {
+    name: Option<String>,
+    path: PathBuf,
+    /// Affected dependency that pulls this project in (`None` when changed directly)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    via: Option<String>,
+}
+
+/// List projects affected by a diff
+///
+/// # Errors
+/// Returns error if project discovery or the git diff fails.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new` and `git diff`;
+/// `affected_projects` is unit tested and the command is covered by the cli
+/// integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_affected(args: &AffectedArgs, global: &GlobalOptions) -> Result<()> {
+    let mut ctx = CommandContext::new(global, args.remote).await?;
+    if let Some(range) = &args.range {
+        // Replace the base branch change detection with the files in the range
+        let files = changed_files(&ctx.repo_root_path, range).await?;
+        let change_filter = ChangeFilter::new(&ctx.repo_root_path, &ctx.config)?;
+        for finder in &mut ctx.project_finders {
+            for project in finder.projects_mut() {
+                project.set_changed(false);
+            }
+        }
+        for file in &files {
+            change_filter.mark_changed(&mut ctx.project_finders, &ctx.repo_root_path.join(file))?;
+        }
+    }
+    let mut projects: Vec<&Project> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .collect();
+    projects.sort();
+
+    let affected: Vec<AffectedProject> = affected_projects(&projects)
+        .into_iter()
+        .map(|(project, via)| AffectedProject {
+            name: project.name().map(str::to_string),
+            path: project.relative_path().to_path_buf(),
+            via,
+        })
+        .collect();
+    if args.paths || args.print0 {
+        let terminator = if args.print0 { '\0' } else { '\n' };
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(format_paths(&affected, terminator).as_bytes())?;
+        stdout.flush()?;
+        return Ok(());
+    }
+    match args.format {
+        FormatOptions::Stdout => print!("{}", format_affected(&affected)),
+        FormatOptions::Json => println!("{}", serde_json::to_string_pretty(&affected)?),
+    }
+    Ok(())
+}
+
+/// Changed projects, followed by every project that depends on one of them (transitively)
+///
+/// Each dependent is paired with the name of the affected dependency that reached it first.
+fn affected_projects<'a>(projects: &[&'a Project]) -> Vec<(&'a Project, Option<String>)> {
+    let mut affected: Vec<(&Project, Option<String>)> = projects
+        .iter()
+        .filter(|project| project.is_changed())
+        .map(|project| (*project, None))
+        .collect();
+    let mut seen: HashSet<usize> = projects
+        .iter()
+        .enumerate()
+        .filter(|(_, project)| project.is_changed())
+        .map(|(index, _)| index)
+        .collect();
+    let mut queue: VecDeque<String> = affected
+        .iter()
+        .filter_map(|(project, _)| project.name().map(str::to_string))
+        .collect();
+    while let Some(name) = queue.pop_front() {
+        for (index, project) in projects.iter().enumerate() {
+            if !seen.contains(&index) && project.dependencies().contains(&name) {
+                seen.insert(index);
+                affected.push((*project, Some(name.clone())));
+                if let Some(dependent) = project.name() {
+                    queue.push_back(dependent.to_string());
+                }
+            }
+        }
+    }
+    affected
+}
+
+/// Affected project directories (`.` for the repository root), each followed by `terminator`
+///
+/// Directories shared by several projects are listed once.
+fn format_paths(affected: &[AffectedProject], terminator: char) -> String {
+    let mut seen = HashSet::new();
+    let mut out = String::new();
+    for project in affected {
+        let dir = project.path.parent().unwrap_or(Path::new(""));
+        let dir = if dir.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            path_key(dir)
+        };
+        if seen.insert(dir.clone()) {
+            out.push_str(&dir);
+            out.push(terminator);
+        }
+    }
+    out
+}
+
+fn format_affected(affected: &[AffectedProject]) -> String {
+    if affected.is_empty() {
+        return "No affected projects\n".to_string();
+    }
+    let mut out = String::new();
+    for project in affected {
+        out.push_str(&format!(
+            "{} {}",
+            project.name.as_deref().unwrap_or("noname").bold(),
+            project.path.display().to_string().bright_black()
+        ));
+        if let Some(via) = &project.via {
+            out.push_str(&format!(" {}", format!("(via {via})").bright_yellow()));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use changepacks_core::{Language, Package, UpdateType};
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        affected: AffectedArgs,
+    }
+
+    #[derive(Debug)]
+    struct MockPackage {
+        name: String,
+        relative_path: PathBuf,
+        dependencies: HashSet<String>,
+        changed: bool,
+    }
+
+    #[async_trait]
+    impl Package for MockPackage {
+        fn name(&self) -> Option<&str> {
+            Some(&self.name)
+        }
+        fn version(&self) -> Option<&str> {
+            Some("1.0.0")
+        }
+        fn path(&self) -> &Path {
+            &self.relative_path
+        }
+        fn relative_path(&self) -> &Path {
+            &self.relative_path
+        }
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
+            Ok(())
+        }
+        fn is_changed(&self) -> bool {
+            self.changed
+        }
+        fn language(&self) -> Language {
+            Language::Node
+        }
+        fn dependencies(&self) -> &HashSet<String> {
+            &self.dependencies
+        }
+        fn add_dependency(&mut self, dependency: &str) {
+            self.dependencies.insert(dependency.to_string());
+        }
+        fn set_changed(&mut self, changed: bool) {
+            self.changed = changed;
+        }
+        fn default_publish_command(&self) -> String {
+            "echo publish".to_string()
+        }
+        fn default_dry_run_publish_command(&self) -> Option<String> {
+            None
+        }
+    }
+
+    fn package(name: &str, dependencies: &[&str], changed: bool) -> Project {
+        Project::Package(Box::new(MockPackage {
+            name: name.to_string(),
+            relative_path: PathBuf::from(format!("{name}/package.json")),
+            dependencies: dependencies.iter().map(ToString::to_string).collect(),
+            changed,
+        }))
+    }
+
+    #[test]
+    fn test_affected_args() {
+        let cli = TestCli::parse_from(["affected"]);
+        assert!(cli.affected.range.is_none());
+        assert!(!cli.affected.remote);
+        let cli = TestCli::parse_from(["affected", "main...HEAD", "--format", "json"]);
+        assert_eq!(cli.affected.range.as_deref(), Some("main...HEAD"));
+        assert!(matches!(cli.affected.format, FormatOptions::Json));
+    }
+
+    #[test]
+    fn test_affected_projects_transitive() {
+        // app -> lib -> core (changed); docs is unrelated
+        let projects = [
+            package("app", &["lib"], false),
+            package("core", &[], true),
+            package("docs", &[], false),
+            package("lib", &["core"], false),
+        ];
+        let refs: Vec<&Project> = projects.iter().collect();
+        let affected: Vec<(&str, Option<String>)> = affected_projects(&refs)
+            .into_iter()
+            .map(|(project, via)| (project.name().unwrap(), via))
+            .collect();
+        assert_eq!(
+            affected,
+            [
+                ("core", None),
+                ("lib", Some("core".to_string())),
+                ("app", Some("lib".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_affected_projects_none_changed() {
+        let projects = [package("app", &["lib"], false), package("lib", &[], false)];
+        let refs: Vec<&Project> = projects.iter().collect();
+        assert!(affected_projects(&refs).is_empty());
+    }
+
+    #[test]
+    fn test_affected_args_paths() {
+        let cli = TestCli::parse_from(["affected"]);
+        assert!(!cli.affected.paths);
+        assert!(!cli.affected.print0);
+        let cli = TestCli::parse_from(["affected", "--paths", "--print0"]);
+        assert!(cli.affected.paths);
+        assert!(cli.affected.print0);
+    }
+
+    #[test]
+    fn test_format_paths() {
+        let project = |path: &str| AffectedProject {
+            name: None,
+            path: PathBuf::from(path),
+            via: None,
+        };
+        let affected = [
+            project("package.json"),
+            project("packages/a/package.json"),
+            project("packages/a/Cargo.toml"),
+            project("crates/b/Cargo.toml"),
+        ];
+        assert_eq!(format_paths(&affected, '\n'), ".\npackages/a\ncrates/b\n");
+        assert_eq!(format_paths(&affected, '\0'), ".\0packages/a\0crates/b\0");
+        assert_eq!(format_paths(&[], '\n'), "");
+    }
+
+    #[test]
+    fn test_format_affected() {
+        assert_eq!(format_affected(&[]), "No affected projects\n");
+        let output = format_affected(&[
+            AffectedProject {
+                name: Some("core".to_string()),
+                path: PathBuf::from("core/package.json"),
+                via: None,
+            },
+            AffectedProject {
+                name: Some("app".to_string()),
+                path: PathBuf::from("app/package.json"),
+                via: Some("core".to_string()),
+            },
+        ]);
+        assert!(output.contains("core"));
+        assert!(output.contains("(via core)"));
+        assert_eq!(output.lines().count(), 2);
+    }
+}
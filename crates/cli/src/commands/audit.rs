@@ -0,0 +1,502 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use changepacks_core::{ChangePackLog, Config, Project, RELEASE_MANIFEST_FILE};
+use changepacks_registry::{Registry, RegistryClient};
+use changepacks_utils::{
+    first_unreleased_change, get_changepacks_dir, get_relative_path, read_changepack_logs,
+    read_release_manifest,
+};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{CommandContext, GlobalOptions, options::FormatOptions, registries::registry_client};
+
+#[derive(Args, Debug)]
+#[command(about = "Report stale unreleased changes, never-released packages, and old changepacks")]
+pub struct AuditArgs {
+    /// Report packages whose oldest unreleased change is older than this many days
+    #[arg(long, default_value_t = 30)]
+    pub days: i64,
+
+    /// Report changepacks pending for longer than this many days
+    #[arg(long, default_value_t = 14)]
+    pub pending_days: i64,
+
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+
+    /// Also report packages whose local version is not published on their registry
+    #[arg(long)]
+    pub registry: bool,
+}
+
+/// Package identified by manifest name and relative path
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct AuditPackage {
+    name: Option<String>,
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct StaleChange {
+    #[serde(flatten)]
+    package: AuditPackage,
+    /// Commit time of the oldest change not yet released
+    since: DateTime<Utc>,
+    days: i64,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct StaleChangepack {
+    file: String,
+    date: DateTime<Utc>,
+    days: i64,
+    packages: Vec<PathBuf>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditReport {
+    stale_changes: Vec<StaleChange>,
+    never_released: Vec<AuditPackage>,
+    stale_changepacks: Vec<StaleChangepack>,
+    /// Only present with `--registry`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unpublished: Option<Vec<UnpublishedPackage>>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct UnpublishedPackage {
+    #[serde(flatten)]
+    package: AuditPackage,
+    version: String,
+    registry: String,
+}
+
+impl AuditReport {
+    fn is_empty(&self) -> bool {
+        self.stale_changes.is_empty()
+            && self.never_released.is_empty()
+            && self.stale_changepacks.is_empty()
+            && self.unpublished.as_ref().is_none_or(Vec::is_empty)
+    }
+}
+
+/// Report release hygiene issues
+///
+/// # Errors
+/// Returns error if project discovery, reading `.changepacks`, or querying git fails.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new` and per-package
+/// git queries; the report helpers are unit tested and the command is covered
+/// by the cli integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_audit(args: &AuditArgs, global: &GlobalOptions) -> Result<()> {
+    let ctx = CommandContext::new(global, false).await?;
+    let changepacks_dir = get_changepacks_dir(&ctx.repo_root_path)?;
+    let manifest = read_release_manifest(&changepacks_dir).await?;
+    let manifest_path = get_relative_path(
+        &ctx.repo_root_path,
+        &changepacks_dir.join(RELEASE_MANIFEST_FILE),
+    )?;
+    let now = Utc::now();
+
+    let mut projects: Vec<_> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .filter(|project| project.version().is_some())
+        .collect();
+    projects.sort();
+
+    let mut report = AuditReport::default();
+    if args.registry {
+        // Packages with `publish: false` are never expected on a registry
+        let published: Vec<&Project> = projects
+            .iter()
+            .copied()
+            .filter(|project| {
+                !ctx.config.is_publish_disabled(
+                    project.relative_path(),
+                    project.name(),
+                    project.language(),
+                )
+            })
+            .collect();
+        report.unpublished = Some(
+            unpublished_packages(&published, |registry| {
+                registry_client(&ctx.config, registry)
+            })
+            .await,
+        );
+    }
+    for project in projects {
+        let package = AuditPackage {
+            name: project.name().map(str::to_string),
+            path: project.relative_path().to_path_buf(),
+        };
+        let Some(released_at) = manifest.last_released_at(project.relative_path()) else {
+            report.never_released.push(package);
+            continue;
+        };
+        let package_dir = project.relative_path().parent().unwrap_or(Path::new(""));
+        if let Some(since) = first_unreleased_change(
+            &ctx.repo_root_path,
+            package_dir,
+            &manifest_path,
+            released_at,
+        )
+        .await?
+        {
+            let days = (now - since).num_days();
+            if days > args.days {
+                report.stale_changes.push(StaleChange {
+                    package,
+                    since,
+                    days,
+                });
+            }
+        }
+    }
+    report.stale_changepacks = stale_changepacks(
+        &read_changepack_logs(&changepacks_dir).await?,
+        now,
+        args.pending_days,
+    );
+
+    match args.format {
+        FormatOptions::Stdout => print!("{}", format_audit_report(&report, args, &ctx.config)),
+        FormatOptions::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(())
+}
+
+/// Packages whose local version is missing from their registry
+///
+/// Packages without a supported registry are skipped; lookup failures are reported on stderr.
+async fn unpublished_packages(
+    projects: &[&Project],
+    client: impl Fn(Registry) -> RegistryClient,
+) -> Vec<UnpublishedPackage> {
+    let lookups = projects.iter().filter_map(|project| {
+        let registry = Registry::from_name(project.registry())?;
+        let name = project.name()?;
+        let version = project.version()?;
+        let client = client(registry);
+        Some(async move {
+            match client.version_exists(name, version).await {
+                Ok(true) => None,
+                Ok(false) => Some(UnpublishedPackage {
+                    package: AuditPackage {
+                        name: Some(name.to_string()),
+                        path: project.relative_path().to_path_buf(),
+                    },
+                    version: version.to_string(),
+                    registry: registry.to_string(),
+                }),
+                Err(e) => {
+                    eprintln!("Could not check {project} on {registry}: {e:#}");
+                    None
+                }
+            }
+        })
+    });
+    futures::future::join_all(lookups)
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Changepack logs created more than `pending_days` before `now`, oldest first
+fn stale_changepacks(
+    logs: &[(PathBuf, ChangePackLog)],
+    now: DateTime<Utc>,
+    pending_days: i64,
+) -> Vec<StaleChangepack> {
+    let mut stale: Vec<StaleChangepack> = logs
+        .iter()
+        .filter_map(|(path, log)| {
+            let days = (now - *log.date()).num_days();
+            if days <= pending_days {
+                return None;
+            }
+            let mut packages: Vec<PathBuf> = log.changes().keys().cloned().collect();
+            packages.sort();
+            Some(StaleChangepack {
+                file: path
+                    .file_name()
+                    .map_or_else(String::new, |name| name.to_string_lossy().into_owned()),
+                date: *log.date(),
+                days,
+                packages,
+            })
+        })
+        .collect();
+    stale.sort_by_key(|changepack| changepack.date);
+    stale
+}
+
+fn format_package(package: &AuditPackage) -> String {
+    format!(
+        "{} ({})",
+        package.name.as_deref().unwrap_or("noname"),
+        package.path.display()
+    )
+}
+
+fn format_audit_report(report: &AuditReport, args: &AuditArgs, config: &Config) -> String {
+    if report.is_empty() {
+        return "No release hygiene issues found\n".to_string();
+    }
+    let mut out = String::new();
+    if !report.stale_changes.is_empty() {
+        out.push_str(&format!(
+            "{}\n",
+            format!("Unreleased changes older than {} days:", args.days).bold()
+        ));
+        for stale in &report.stale_changes {
+            out.push_str(&format!(
+                "  {} - oldest unreleased change {} days ago ({})\n",
+                format_package(&stale.package),
+                stale.days,
+                config.format_date(&stale.since)
+            ));
+        }
+    }
+    if !report.never_released.is_empty() {
+        out.push_str(&format!("{}\n", "Never released:".bold()));
+        for package in &report.never_released {
+            out.push_str(&format!("  {}\n", format_package(package)));
+        }
+    }
+    if !report.stale_changepacks.is_empty() {
+        out.push_str(&format!(
+            "{}\n",
+            format!(
+                "Changepacks pending longer than {} days:",
+                args.pending_days
+            )
+            .bold()
+        ));
+        for changepack in &report.stale_changepacks {
+            let packages: Vec<String> = changepack
+                .packages
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            out.push_str(&format!(
+                "  {} - {} days ({})\n",
+                changepack.file,
+                changepack.days,
+                packages.join(", ")
+            ));
+        }
+    }
+    if let Some(unpublished) = report.unpublished.as_ref().filter(|list| !list.is_empty()) {
+        out.push_str(&format!("{}\n", "Not published on their registry:".bold()));
+        for unpublished in unpublished {
+            out.push_str(&format!(
+                "  {} - v{} missing on {}\n",
+                format_package(&unpublished.package),
+                unpublished.version,
+                unpublished.registry
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::UpdateType;
+    use chrono::Duration;
+    use clap::Parser;
+    use std::collections::HashMap;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        audit: AuditArgs,
+    }
+
+    fn args() -> AuditArgs {
+        TestCli::parse_from(["audit"]).audit
+    }
+
+    #[test]
+    fn test_audit_args_defaults() {
+        let args = args();
+        assert_eq!(args.days, 30);
+        assert_eq!(args.pending_days, 14);
+        let args = TestCli::parse_from(["audit", "--days", "7", "--pending-days", "3"]).audit;
+        assert_eq!(args.days, 7);
+        assert_eq!(args.pending_days, 3);
+    }
+
+    #[test]
+    fn test_stale_changepacks() {
+        let log = |path: &str| {
+            ChangePackLog::new(
+                HashMap::from([(PathBuf::from(path), UpdateType::Patch)]),
+                "note".to_string(),
+            )
+        };
+        let logs = vec![
+            (
+                PathBuf::from(".changepacks/changepack_log_a.json"),
+                log("a/package.json"),
+            ),
+            (
+                PathBuf::from(".changepacks/changepack_log_b.json"),
+                log("b/package.json"),
+            ),
+        ];
+        let created = *logs[0].1.date();
+
+        assert!(stale_changepacks(&logs, created + Duration::days(14), 14).is_empty());
+        let stale = stale_changepacks(&logs, created + Duration::days(20), 14);
+        assert_eq!(stale.len(), 2);
+        assert_eq!(stale[0].file, "changepack_log_a.json");
+        assert_eq!(stale[0].days, 20);
+        assert_eq!(stale[0].packages, [PathBuf::from("a/package.json")]);
+    }
+
+    #[test]
+    fn test_format_audit_report() {
+        assert_eq!(
+            format_audit_report(&AuditReport::default(), &args(), &Config::default()),
+            "No release hygiene issues found\n"
+        );
+
+        let date = DateTime::parse_from_rfc3339("2025-01-02T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let report = AuditReport {
+            stale_changes: vec![StaleChange {
+                package: AuditPackage {
+                    name: Some("core".to_string()),
+                    path: PathBuf::from("crates/core/Cargo.toml"),
+                },
+                since: date,
+                days: 45,
+            }],
+            never_released: vec![AuditPackage {
+                name: None,
+                path: PathBuf::from("tools/package.json"),
+            }],
+            stale_changepacks: vec![StaleChangepack {
+                file: "changepack_log_x.json".to_string(),
+                date,
+                days: 20,
+                packages: vec![
+                    PathBuf::from("a/package.json"),
+                    PathBuf::from("b/package.json"),
+                ],
+            }],
+            unpublished: Some(vec![UnpublishedPackage {
+                package: AuditPackage {
+                    name: Some("web".to_string()),
+                    path: PathBuf::from("web/package.json"),
+                },
+                version: "1.2.0".to_string(),
+                registry: "npm".to_string(),
+            }]),
+        };
+        let output = format_audit_report(&report, &args(), &Config::default());
+        for expected in [
+            "Unreleased changes older than 30 days:",
+            "  core (crates/core/Cargo.toml) - oldest unreleased change 45 days ago (2025-01-02)\n",
+            "Never released:",
+            "  noname (tools/package.json)\n",
+            "Changepacks pending longer than 14 days:",
+            "  changepack_log_x.json - 20 days (a/package.json, b/package.json)\n",
+            "Not published on their registry:",
+            "  web (web/package.json) - v1.2.0 missing on npm\n",
+        ] {
+            assert!(
+                output.contains(expected),
+                "missing {expected:?} in {output}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_audit_report_registry_section_is_optional() {
+        let report = AuditReport::default();
+        assert!(
+            !serde_json::to_string(&report)
+                .unwrap()
+                .contains("unpublished")
+        );
+        let report = AuditReport {
+            unpublished: Some(Vec::new()),
+            ..AuditReport::default()
+        };
+        assert!(report.is_empty());
+        assert!(
+            serde_json::to_string(&report)
+                .unwrap()
+                .contains(r#""unpublished":[]"#)
+        );
+    }
+
+    /// Serve one response with `status` and `body`, returning the base URL
+    fn serve(status: u16, body: &'static str) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_unpublished_packages() {
+        let project = Project::Package(Box::new(changepacks_node::package::NodePackage::new(
+            Some("web".to_string()),
+            Some("1.2.0".to_string()),
+            PathBuf::from("/nonexistent/web/package.json"),
+            PathBuf::from("web/package.json"),
+        )));
+
+        let url = serve(200, r#"{"versions":{"1.2.0":{}}}"#);
+        let client = |registry| RegistryClient::with_base_url(registry, &url);
+        assert!(unpublished_packages(&[&project], client).await.is_empty());
+
+        let url = serve(200, r#"{"versions":{"1.1.0":{}}}"#);
+        let client = |registry| RegistryClient::with_base_url(registry, &url);
+        assert_eq!(
+            unpublished_packages(&[&project], client).await,
+            [UnpublishedPackage {
+                package: AuditPackage {
+                    name: Some("web".to_string()),
+                    path: PathBuf::from("web/package.json"),
+                },
+                version: "1.2.0".to_string(),
+                registry: "npm".to_string(),
+            }]
+        );
+
+        let url = serve(500, "oops");
+        let client = |registry| RegistryClient::with_base_url(registry, &url);
+        assert!(unpublished_packages(&[&project], client).await.is_empty());
+    }
+}
@@ -0,0 +1,421 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use changepacks_core::{
+    ChangePackResultLog, Config, Project, ReleaseManifest, UpdateType, path_key,
+};
+use changepacks_utils::{
+    apply_reverse_dependencies, compare_versions, gen_update_map, get_changepacks_dir,
+    next_version_with_id, read_release_manifest, render_changelog_section,
+};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use serde::Serialize;
+
+use crate::{
+    CommandContext, GlobalOptions, completion::package_candidates, options::FormatOptions,
+};
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Render release notes for pending changepacks or past releases without bumping versions"
+)]
+pub struct ChangelogArgs {
+    /// Package name or manifest path to render notes for (all packages if omitted)
+    #[arg(add = ArgValueCandidates::new(package_candidates))]
+    pub package: Option<String>,
+
+    /// Render the releases after this version from the release manifest instead of pending
+    /// changepacks
+    #[arg(long, value_name = "VERSION")]
+    pub from: Option<String>,
+
+    /// Render the releases up to and including this version from the release manifest
+    #[arg(long, value_name = "VERSION")]
+    pub to: Option<String>,
+
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+}
+
+/// One version of one package: the next release of its pending changepacks, or a past release
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct ChangelogEntry {
+    name: Option<String>,
+    path: PathBuf,
+    version: String,
+    date: String,
+    /// Whether the version comes from pending changepacks rather than the release manifest
+    pending: bool,
+    changes: Vec<ChangelogNote>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct ChangelogNote {
+    #[serde(rename = "type")]
+    update_type: UpdateType,
+    note: String,
+}
+
+/// Print the changelog sections pending changepacks would add, or those of past releases
+///
+/// # Errors
+/// Returns error if project discovery, reading `.changepacks`, or a version bump fails, or
+/// when `--from`/`--to` is not a version.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new`; `pending_entries`,
+/// `released_entries` and `format_changelog` are unit tested and the command is covered by
+/// the cli integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_changelog(args: &ChangelogArgs, global: &GlobalOptions) -> Result<()> {
+    let ctx = CommandContext::new(global, false).await?;
+    let entries = if args.from.is_some() || args.to.is_some() {
+        let manifest = read_release_manifest(&get_changepacks_dir(&ctx.repo_root_path)?).await?;
+        released_entries(
+            &manifest,
+            args.package.as_deref(),
+            args.from.as_deref(),
+            args.to.as_deref(),
+            &ctx.config,
+        )?
+    } else {
+        let mut projects: Vec<&Project> = ctx
+            .project_finders
+            .iter()
+            .flat_map(|finder| finder.projects())
+            .collect();
+        projects.sort();
+        let (mut update_map, prerelease_id) =
+            gen_update_map(&ctx.repo_root_path, &ctx.config, &projects, &ctx.diff_refs).await?;
+        apply_reverse_dependencies(
+            &mut update_map,
+            &projects,
+            &ctx.repo_root_path,
+            prerelease_id.as_deref(),
+        );
+        let date = ctx.config.format_date(&chrono::Utc::now());
+        pending_entries(
+            &projects,
+            &update_map,
+            prerelease_id.as_deref(),
+            args.package.as_deref(),
+            &date,
+        )?
+    };
+
+    match args.format {
+        FormatOptions::Stdout => print!("{}", format_changelog(&entries)),
+        FormatOptions::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+    }
+    Ok(())
+}
+
+/// Whether `package` (a name or manifest path) selects the project `name` at `path`
+fn selects(package: Option<&str>, name: Option<&str>, path: &Path) -> bool {
+    package.is_none_or(|query| name == Some(query) || path_key(path) == path_key(Path::new(query)))
+}
+
+/// The next version of every selected project with pending changepacks, dated `date`, with
+/// prereleases bumped to `prerelease_id`
+fn pending_entries(
+    projects: &[&Project],
+    update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    prerelease_id: Option<&str>,
+    package: Option<&str>,
+    date: &str,
+) -> Result<Vec<ChangelogEntry>> {
+    let mut entries = Vec::new();
+    for project in projects {
+        let path = project.relative_path();
+        if !selects(package, project.name(), path) {
+            continue;
+        }
+        let Some((update_type, logs)) = update_map.get(path) else {
+            continue;
+        };
+        entries.push(ChangelogEntry {
+            name: project.name().map(str::to_string),
+            path: path.to_path_buf(),
+            version: next_version_with_id(
+                project.version().unwrap_or("0.0.0"),
+                *update_type,
+                prerelease_id,
+            )?,
+            date: date.to_string(),
+            pending: true,
+            changes: logs
+                .iter()
+                .map(|log| ChangelogNote {
+                    update_type: log.update_type(),
+                    note: log.rendered_note(),
+                })
+                .collect(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Releases of the selected packages after `from` and up to `to`, newest first
+///
+/// # Errors
+/// Returns error if `from` or `to` is not a version.
+fn released_entries(
+    manifest: &ReleaseManifest,
+    package: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    config: &Config,
+) -> Result<Vec<ChangelogEntry>> {
+    for bound in [from, to].into_iter().flatten() {
+        if compare_versions(bound, bound).is_none() {
+            anyhow::bail!("Invalid version: {bound}");
+        }
+    }
+    let in_range = |version: &str| {
+        from.is_none_or(|from| compare_versions(version, from) == Some(Ordering::Greater))
+            && to.is_none_or(|to| {
+                matches!(
+                    compare_versions(version, to),
+                    Some(Ordering::Less | Ordering::Equal)
+                )
+            })
+    };
+    Ok(manifest
+        .releases()
+        .iter()
+        .rev()
+        .flat_map(|record| {
+            record
+                .packages()
+                .iter()
+                .filter(|released| selects(package, released.name(), released.path()))
+                .filter(|released| in_range(released.version()))
+                .map(|released| ChangelogEntry {
+                    name: released.name().map(str::to_string),
+                    path: released.path().to_path_buf(),
+                    version: released.version().to_string(),
+                    date: config.format_date(record.date()),
+                    pending: false,
+                    changes: released
+                        .notes()
+                        .iter()
+                        .map(|note| ChangelogNote {
+                            update_type: released.update_type(),
+                            note: note.clone(),
+                        })
+                        .collect(),
+                })
+        })
+        .collect())
+}
+
+/// Markdown changelog sections, under a `# package` heading per package when there are several
+fn format_changelog(entries: &[ChangelogEntry]) -> String {
+    if entries.is_empty() {
+        return "No changes found\n".to_string();
+    }
+    let mut packages: Vec<&Path> = Vec::new();
+    for entry in entries {
+        if !packages.contains(&entry.path.as_path()) {
+            packages.push(&entry.path);
+        }
+    }
+    let mut out = String::new();
+    for path in &packages {
+        let sections: Vec<&ChangelogEntry> =
+            entries.iter().filter(|entry| entry.path == *path).collect();
+        if packages.len() > 1 {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!(
+                "# {}\n\n",
+                sections[0].name.clone().unwrap_or_else(|| path_key(path))
+            ));
+        }
+        for (index, entry) in sections.iter().enumerate() {
+            if index > 0 {
+                out.push('\n');
+            }
+            let logs: Vec<ChangePackResultLog> = entry
+                .changes
+                .iter()
+                .map(|change| ChangePackResultLog::new(change.update_type, change.note.clone()))
+                .collect();
+            out.push_str(&render_changelog_section(
+                &entry.version,
+                &entry.date,
+                &logs,
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::{ReleaseRecord, ReleasedPackage};
+    use changepacks_node::package::NodePackage;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        changelog: ChangelogArgs,
+    }
+
+    fn manifest() -> ReleaseManifest {
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(ReleaseRecord::new(vec![ReleasedPackage::new(
+            Some("web".to_string()),
+            PathBuf::from("packages/web/package.json"),
+            Some("0.1.0".to_string()),
+            "0.2.0".to_string(),
+            UpdateType::Minor,
+            vec!["Add routing".to_string()],
+        )]));
+        for (previous, version, note) in [
+            ("1.0.0", "1.1.0", "Add streaming"),
+            ("1.1.0", "1.2.0", "Add retries"),
+            ("1.2.0", "1.3.0", "Add tracing"),
+        ] {
+            manifest.push(ReleaseRecord::new(vec![ReleasedPackage::new(
+                Some("core".to_string()),
+                PathBuf::from("packages/core/package.json"),
+                Some(previous.to_string()),
+                version.to_string(),
+                UpdateType::Minor,
+                vec![note.to_string()],
+            )]));
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_changelog_args() {
+        let cli = TestCli::parse_from(["changelog"]);
+        assert!(cli.changelog.package.is_none());
+        assert!(cli.changelog.from.is_none());
+        assert!(matches!(cli.changelog.format, FormatOptions::Stdout));
+        let cli = TestCli::parse_from([
+            "changelog",
+            "core",
+            "--from",
+            "1.0.0",
+            "--to",
+            "1.2.0",
+            "--format",
+            "json",
+        ]);
+        assert_eq!(cli.changelog.package.as_deref(), Some("core"));
+        assert_eq!(cli.changelog.from.as_deref(), Some("1.0.0"));
+        assert_eq!(cli.changelog.to.as_deref(), Some("1.2.0"));
+        assert!(matches!(cli.changelog.format, FormatOptions::Json));
+    }
+
+    #[test]
+    fn test_released_entries_range() {
+        let entries = released_entries(
+            &manifest(),
+            Some("core"),
+            Some("1.1.0"),
+            None,
+            &Config::default(),
+        )
+        .unwrap();
+        let versions: Vec<&str> = entries.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(versions, ["1.3.0", "1.2.0"]);
+        assert!(!entries[0].pending);
+        assert_eq!(
+            entries[0].changes,
+            [ChangelogNote {
+                update_type: UpdateType::Minor,
+                note: "Add tracing".to_string()
+            }]
+        );
+
+        let entries = released_entries(
+            &manifest(),
+            Some("packages/core/package.json"),
+            None,
+            Some("v1.2.0"),
+            &Config::default(),
+        )
+        .unwrap();
+        let versions: Vec<&str> = entries.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(versions, ["1.2.0", "1.1.0"]);
+
+        assert!(
+            released_entries(&manifest(), None, Some("next"), None, &Config::default()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_format_changelog() {
+        assert_eq!(format_changelog(&[]), "No changes found\n");
+
+        let entries = released_entries(
+            &manifest(),
+            Some("core"),
+            Some("1.1.0"),
+            None,
+            &Config::default(),
+        )
+        .unwrap();
+        let output = format_changelog(&entries);
+        assert!(!output.starts_with("# "));
+        let newest = output.find("## 1.3.0 - ").unwrap();
+        let older = output.find("## 1.2.0 - ").unwrap();
+        assert!(newest < older);
+        assert!(output.contains("### Minor Changes\n\n- Add tracing\n"));
+
+        let entries =
+            released_entries(&manifest(), None, None, Some("9.0.0"), &Config::default()).unwrap();
+        let output = format_changelog(&entries);
+        assert!(output.starts_with("# core\n\n## 1.3.0 - "));
+        assert!(output.contains("\n# web\n\n## 0.2.0 - "));
+    }
+
+    #[test]
+    fn test_pending_entries() {
+        let node = |name: &str| {
+            Project::Package(Box::new(NodePackage::new(
+                Some(name.to_string()),
+                Some("1.2.0".to_string()),
+                PathBuf::from(format!("/repo/{name}/package.json")),
+                PathBuf::from(format!("{name}/package.json")),
+            )))
+        };
+        let (core, web) = (node("core"), node("web"));
+        let projects = [&core, &web];
+        let update_map = HashMap::from([(
+            PathBuf::from("core/package.json"),
+            (
+                UpdateType::Minor,
+                vec![
+                    ChangePackResultLog::new(UpdateType::Minor, "Add retries".to_string()),
+                    ChangePackResultLog::new(UpdateType::Patch, "Fix a crash".to_string()),
+                ],
+            ),
+        )]);
+
+        let entries = pending_entries(&projects, &update_map, None, None, "2026-10-17").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].version, "1.3.0");
+        assert!(entries[0].pending);
+        assert_eq!(entries[0].changes.len(), 2);
+        assert_eq!(
+            format_changelog(&entries),
+            "## 1.3.0 - 2026-10-17\n\n### Minor Changes\n\n- Add retries\n\n### Patch Changes\n\n- Fix a crash\n"
+        );
+        assert!(
+            pending_entries(&projects, &update_map, None, Some("web"), "2026-10-17")
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
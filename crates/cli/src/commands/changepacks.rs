@@ -1,13 +1,19 @@
-use changepacks_core::{ChangePackLog, Language, Project, UpdateType};
-use std::{collections::HashMap, path::PathBuf};
-use tokio::fs::write;
+use changepacks_core::{ChangePackLog, Language, Project, TemplatePrompt, UpdateType};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+use tokio::fs::{create_dir_all, write};
 
-use changepacks_utils::{get_changepacks_dir, get_relative_path};
+use changepacks_utils::{
+    ChangeFilter, changed_files, changepack_log_file, get_changepacks_dir, get_relative_path,
+    render_changepack_log, run_git,
+};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 
 use crate::{
-    CommandContext,
+    CommandContext, GlobalOptions,
     options::{CliLanguage, FilterOptions},
     prompter::{InquirePrompter, Prompter},
 };
@@ -17,15 +23,24 @@ pub struct ChangepackArgs {
     pub filter: Option<FilterOptions>,
     pub remote: bool,
     pub yes: bool,
+    /// Select every changed project without prompting
+    pub all: bool,
+    /// Pre-select every project changed since the base branch, not only those owning
+    /// staged or uncommitted changes
+    pub since_base: bool,
     pub message: Option<String>,
     pub update_type: Option<UpdateType>,
     pub language: Vec<CliLanguage>,
+    /// Answers to template prompts given up front as `key=value`
+    pub fields: Vec<(String, String)>,
+    /// Identifier recorded for prerelease bumps (e.g., `beta`)
+    pub preid: Option<String>,
 }
 
 /// # Errors
 /// Returns error if command context creation or changepack creation fails.
-pub async fn handle_changepack(args: &ChangepackArgs) -> Result<()> {
-    handle_changepack_with_prompter(args, &InquirePrompter).await
+pub async fn handle_changepack(args: &ChangepackArgs, global: &GlobalOptions) -> Result<()> {
+    handle_changepack_with_prompter(args, global, &InquirePrompter).await
 }
 
 /// # Errors
@@ -38,15 +53,27 @@ pub async fn handle_changepack(args: &ChangepackArgs) -> Result<()> {
 #[cfg(not(tarpaulin_include))]
 pub async fn handle_changepack_with_prompter(
     args: &ChangepackArgs,
+    global: &GlobalOptions,
     prompter: &dyn Prompter,
 ) -> Result<()> {
-    let ctx = CommandContext::new(args.remote).await?;
-
-    let mut projects = ctx
-        .project_finders
-        .iter()
-        .flat_map(|finder| finder.projects())
-        .collect::<Vec<_>>();
+    let repositories = CommandContext::new(global, args.remote)
+        .await?
+        .with_repositories(args.remote)
+        .await?;
+
+    // Remember which repository each project belongs to, so every repository gets its own log
+    let mut repo_of_project = HashMap::<PathBuf, usize>::new();
+    let mut projects = Vec::new();
+    for (index, (_, ctx)) in repositories.iter().enumerate() {
+        for project in ctx
+            .project_finders
+            .iter()
+            .flat_map(|finder| finder.projects())
+        {
+            repo_of_project.insert(project.path().to_path_buf(), index);
+            projects.push(project);
+        }
+    }
 
     // Hide packages that inherit their version from workspace root.
     // They are updated automatically when the workspace version bumps.
@@ -70,10 +97,57 @@ pub async fn handle_changepack_with_prompter(
         projects.retain(|project| allowed_languages.contains(&project.language()));
     }
 
+    if repositories.len() > 1 {
+        for (index, (label, _)) in repositories.iter().enumerate() {
+            let count = projects
+                .iter()
+                .filter(|project| repo_of_project.get(project.path()) == Some(&index))
+                .count();
+            println!("{label}: {count} projects");
+        }
+    }
     println!("Found {} projects", projects.len());
     // workspace first
     projects.sort();
 
+    // Suggest the projects owning staged or uncommitted changes; without any, or with
+    // --since-base, every project changed since the base branch is suggested
+    let mut locally_changed = HashSet::<PathBuf>::new();
+    if !args.since_base {
+        for (index, (_, ctx)) in repositories.iter().enumerate() {
+            let repo_projects: Vec<&Project> = projects
+                .iter()
+                .copied()
+                .filter(|project| repo_of_project.get(project.path()) == Some(&index))
+                .collect();
+            let change_filter = ChangeFilter::new(&ctx.repo_root_path, &ctx.config)?;
+            let mut files = local_changes(&ctx.repo_root_path).await;
+            files.retain(|file| !change_filter.is_ignored(&ctx.repo_root_path.join(file)));
+            locally_changed.extend(owning_projects(
+                &repo_projects,
+                &files,
+                &change_filter,
+                &ctx.repo_root_path,
+            ));
+        }
+    }
+    let suggested = (!locally_changed.is_empty()).then_some(&locally_changed);
+
+    // Configured per-package defaults; an explicit --update-type overrides them
+    let default_update_types: HashMap<PathBuf, UpdateType> = if args.update_type.is_some() {
+        HashMap::new()
+    } else {
+        projects
+            .iter()
+            .filter_map(|project| {
+                let (_, ctx) = &repositories[*repo_of_project.get(project.path())?];
+                ctx.config
+                    .package_default_update_type(project.relative_path())
+                    .map(|update_type| (project.path().to_path_buf(), update_type))
+            })
+            .collect()
+    };
+
     let mut update_map = HashMap::<PathBuf, UpdateType>::new();
 
     for update_type in if let Some(update_type) = &args.update_type {
@@ -85,48 +159,31 @@ pub async fn handle_changepack_with_prompter(
             break;
         }
 
-        let selected_projects = if args.yes {
-            projects.clone()
+        let selected_projects = if args.yes || args.all {
+            projects
+                .iter()
+                .copied()
+                .filter(|project| !args.all || project.is_changed())
+                .filter(|project| {
+                    default_update_types
+                        .get(project.path())
+                        .is_none_or(|default| *default == update_type)
+                })
+                .collect()
         } else if update_type == UpdateType::Patch && projects.len() == 1 {
             vec![projects[0]]
         } else {
             let message = format!("Select projects to update for {update_type}");
-            let defaults = projects
-                .iter()
-                .enumerate()
-                .filter_map(|(index, project)| {
-                    if project.is_changed() {
-                        Some(index)
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
+            let defaults =
+                preselected_projects(&projects, update_type, &default_update_types, suggested);
             prompter.multi_select(&message, projects.clone(), defaults)?
         };
 
-        // remove selected projects from projects by index
+        // remove selected projects from projects by path
         for project in selected_projects {
-            update_map.insert(
-                get_relative_path(&ctx.repo_root_path, project.path())?,
-                update_type,
-            );
+            update_map.insert(project.path().to_path_buf(), update_type);
         }
-
-        let project_with_relpath: Vec<_> = projects
-            .iter()
-            .map(|project| {
-                get_relative_path(&ctx.repo_root_path, project.path()).map(|rel| (project, rel))
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        let keep_projects: Vec<_> = project_with_relpath
-            .into_iter()
-            .filter(|(_, rel_path)| !update_map.contains_key(rel_path))
-            .map(|(project, _)| *project)
-            .collect();
-
-        projects = keep_projects;
+        projects.retain(|project| !update_map.contains_key(project.path()));
     }
 
     if update_map.is_empty() {
@@ -144,19 +201,148 @@ pub async fn handle_changepack_with_prompter(
         println!("Notes are empty");
         return Ok(());
     }
-    let changepack_log = ChangePackLog::new(update_map, notes);
-    // random uuid
+    let fields = collect_template_fields(
+        &repositories[0].1.config.template.prompts,
+        &args.fields,
+        args.yes,
+        prompter,
+    )?;
+    // random uuid, shared by the logs written to each repository
     let changepack_log_id = nanoid::nanoid!();
-    let changepack_log_file = get_changepacks_dir(&CommandContext::current_dir()?)?
-        .join(format!("changepack_log_{changepack_log_id}.json"));
-    write(changepack_log_file, serde_json::to_string(&changepack_log)?).await?;
+    for (index, (label, ctx)) in repositories.iter().enumerate() {
+        let repo_update_map = update_map
+            .iter()
+            .filter(|(path, _)| repo_of_project.get(*path) == Some(&index))
+            .map(|(path, update_type)| {
+                get_relative_path(&ctx.repo_root_path, path).map(|rel| (rel, *update_type))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        if repo_update_map.is_empty() {
+            continue;
+        }
+        let changepack_log = ChangePackLog::new(repo_update_map, notes.clone())
+            .with_fields(fields.clone())
+            .with_preid(args.preid.clone());
+        let changepacks_dir = get_changepacks_dir(&ctx.repo_root_path)?;
+        create_dir_all(&changepacks_dir).await?;
+        let format = ctx.config.changepack_format;
+        write(
+            changepack_log_file(&changepacks_dir, &changepack_log_id, format),
+            render_changepack_log(&changepack_log, format)?,
+        )
+        .await?;
+        if repositories.len() > 1 {
+            println!("Wrote changepack to {label}");
+        }
+    }
 
     Ok(())
 }
 
+/// Indices of the projects pre-selected in the prompt for `update_type`
+///
+/// Suggested projects (or, without suggestions, changed projects) are pre-selected at their
+/// configured default update type, or at the first prompt when they have none.
+fn preselected_projects(
+    projects: &[&Project],
+    update_type: UpdateType,
+    default_update_types: &HashMap<PathBuf, UpdateType>,
+    suggested: Option<&HashSet<PathBuf>>,
+) -> Vec<usize> {
+    projects
+        .iter()
+        .enumerate()
+        .filter(|(_, project)| {
+            suggested.map_or_else(
+                || project.is_changed(),
+                |suggested| suggested.contains(project.path()),
+            ) && default_update_types
+                .get(project.path())
+                .is_none_or(|default| *default == update_type)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Staged, unstaged and untracked files in the repository at `repo_root`
+///
+/// Pending changepack logs are left out. Empty when git is unavailable or the repository
+/// has no commits yet.
+async fn local_changes(repo_root: &Path) -> Vec<PathBuf> {
+    let mut files = changed_files(repo_root, "HEAD").await.unwrap_or_default();
+    if let Ok(untracked) = run_git(repo_root, &["ls-files", "--others", "--exclude-standard"]).await
+    {
+        files.extend(untracked.lines().map(PathBuf::from));
+    }
+    if let Ok(changepacks_dir) =
+        get_changepacks_dir(repo_root).and_then(|dir| get_relative_path(repo_root, &dir))
+    {
+        files.retain(|file| !file.starts_with(&changepacks_dir));
+    }
+    files
+}
+
+/// Paths of the projects owning `files`, each file going to the project nearest to it
+///
+/// A file outside the owning project's `changeFiles` globs is not counted.
+fn owning_projects(
+    projects: &[&Project],
+    files: &[PathBuf],
+    change_filter: &ChangeFilter,
+    repo_root: &Path,
+) -> HashSet<PathBuf> {
+    files
+        .iter()
+        .filter_map(|file| {
+            projects
+                .iter()
+                .filter_map(|project| {
+                    let dir = project.relative_path().parent().unwrap_or(Path::new(""));
+                    file.starts_with(dir)
+                        .then(|| (dir.components().count(), *project))
+                })
+                .max_by_key(|(depth, _)| *depth)
+                .filter(|(_, project)| change_filter.is_significant(project, &repo_root.join(file)))
+                .map(|(_, project)| project.path().to_path_buf())
+        })
+        .collect()
+}
+
+/// Answer each template prompt, preferring values passed on the command line
+///
+/// With `yes`, unanswered prompts are not asked: optional ones are left out and required
+/// ones are an error. Empty answers are likewise omitted or rejected.
+///
+/// # Errors
+/// Returns error if a required prompt is left unanswered or prompting fails.
+pub fn collect_template_fields(
+    prompts: &[TemplatePrompt],
+    provided: &[(String, String)],
+    yes: bool,
+    prompter: &dyn Prompter,
+) -> Result<BTreeMap<String, String>> {
+    let mut fields = BTreeMap::new();
+    for prompt in prompts {
+        let answer = match provided.iter().rev().find(|(key, _)| *key == prompt.key) {
+            Some((_, value)) => value.trim().to_string(),
+            None if yes => String::new(),
+            None => prompter.text(&prompt.message)?.trim().to_string(),
+        };
+        if answer.is_empty() {
+            if prompt.required {
+                bail!("Template field '{}' is required", prompt.key);
+            }
+            continue;
+        }
+        fields.insert(prompt.key.clone(), answer);
+    }
+    Ok(fields)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prompter::MockPrompter;
 
     #[test]
     fn test_changepack_args_debug() {
@@ -164,9 +350,13 @@ mod tests {
             filter: None,
             remote: false,
             yes: true,
+            all: false,
+            since_base: false,
             message: Some("Test".to_string()),
             update_type: Some(UpdateType::Patch),
             language: vec![],
+            fields: vec![],
+            preid: None,
         };
 
         // Test Debug trait
@@ -180,9 +370,13 @@ mod tests {
             filter: Some(FilterOptions::Package),
             remote: true,
             yes: false,
+            all: false,
+            since_base: false,
             message: None,
             update_type: None,
             language: vec![],
+            fields: vec![],
+            preid: None,
         };
 
         assert!(args.filter.is_some());
@@ -198,9 +392,13 @@ mod tests {
             filter: Some(FilterOptions::Workspace),
             remote: false,
             yes: true,
+            all: false,
+            since_base: false,
             message: Some("msg".to_string()),
             update_type: Some(UpdateType::Major),
             language: vec![],
+            fields: vec![],
+            preid: None,
         };
 
         assert!(matches!(args.filter, Some(FilterOptions::Workspace)));
@@ -213,9 +411,13 @@ mod tests {
             filter: None,
             remote: false,
             yes: true,
+            all: false,
+            since_base: false,
             message: Some("feature".to_string()),
             update_type: Some(UpdateType::Minor),
             language: vec![],
+            fields: vec![],
+            preid: None,
         };
 
         assert!(matches!(args.update_type, Some(UpdateType::Minor)));
@@ -227,11 +429,156 @@ mod tests {
             filter: None,
             remote: false,
             yes: true,
+            all: false,
+            since_base: false,
             message: None,
             update_type: None,
             language: vec![CliLanguage::Node, CliLanguage::Rust],
+            fields: vec![],
+            preid: None,
         };
 
         assert_eq!(args.language.len(), 2);
     }
+
+    fn prompt(key: &str, required: bool) -> TemplatePrompt {
+        TemplatePrompt {
+            key: key.to_string(),
+            message: format!("{key}?"),
+            required,
+        }
+    }
+
+    #[test]
+    fn test_collect_template_fields_prefers_provided_values() {
+        let prompter = MockPrompter {
+            text_value: "asked".to_string(),
+            ..Default::default()
+        };
+        let prompts = [prompt("issue", true), prompt("breaking", false)];
+        let provided = [("issue".to_string(), "ABC-123".to_string())];
+
+        let fields = collect_template_fields(&prompts, &provided, false, &prompter).unwrap();
+        assert_eq!(fields.get("issue").unwrap(), "ABC-123");
+        assert_eq!(fields.get("breaking").unwrap(), "asked");
+    }
+
+    #[test]
+    fn test_collect_template_fields_non_interactive() {
+        let prompter = MockPrompter::default();
+        let prompts = [prompt("issue", false)];
+        assert!(
+            collect_template_fields(&prompts, &[], true, &prompter)
+                .unwrap()
+                .is_empty()
+        );
+
+        let prompts = [prompt("issue", true)];
+        let err = collect_template_fields(&prompts, &[], true, &prompter).unwrap_err();
+        assert!(err.to_string().contains("'issue' is required"));
+    }
+
+    #[test]
+    fn test_collect_template_fields_empty_answer() {
+        let prompter = MockPrompter {
+            text_value: "  ".to_string(),
+            ..Default::default()
+        };
+        assert!(
+            collect_template_fields(&[prompt("issue", false)], &[], false, &prompter)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(collect_template_fields(&[prompt("issue", true)], &[], false, &prompter).is_err());
+    }
+
+    #[test]
+    fn test_preselected_projects_respects_default_update_type() {
+        let project = |name: &str| {
+            let mut package = changepacks_node::package::NodePackage::new(
+                Some(name.to_string()),
+                Some("1.0.0".to_string()),
+                PathBuf::from(format!("/repo/{name}/package.json")),
+                PathBuf::from(format!("{name}/package.json")),
+            );
+            changepacks_core::Package::set_changed(&mut package, true);
+            Project::Package(Box::new(package))
+        };
+        let (docs, core) = (project("docs"), project("core"));
+        let projects = vec![&docs, &core];
+        let defaults =
+            HashMap::from([(PathBuf::from("/repo/docs/package.json"), UpdateType::Patch)]);
+
+        assert_eq!(
+            preselected_projects(&projects, UpdateType::Major, &defaults, None),
+            vec![1]
+        );
+        assert_eq!(
+            preselected_projects(&projects, UpdateType::Patch, &defaults, None),
+            vec![0, 1]
+        );
+        assert_eq!(
+            preselected_projects(&projects, UpdateType::Major, &HashMap::new(), None),
+            vec![0, 1]
+        );
+        let suggested = HashSet::from([PathBuf::from("/repo/core/package.json")]);
+        assert_eq!(
+            preselected_projects(&projects, UpdateType::Patch, &defaults, Some(&suggested)),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_owning_projects() {
+        use changepacks_core::Config;
+
+        let project = |relative: &str| {
+            Project::Package(Box::new(changepacks_node::package::NodePackage::new(
+                None,
+                Some("1.0.0".to_string()),
+                PathBuf::from("/repo").join(relative),
+                PathBuf::from(relative),
+            )))
+        };
+        let (root, core, cli) = (
+            project("package.json"),
+            project("packages/core/package.json"),
+            project("packages/cli/package.json"),
+        );
+        let projects = vec![&root, &core, &cli];
+        let repo = Path::new("/repo");
+        let filter = ChangeFilter::new(repo, &Config::default()).unwrap();
+
+        let owners = owning_projects(
+            &projects,
+            &[
+                PathBuf::from("packages/core/src/index.ts"),
+                PathBuf::from("packages/core/README.md"),
+            ],
+            &filter,
+            repo,
+        );
+        assert_eq!(
+            owners,
+            HashSet::from([PathBuf::from("/repo/packages/core/package.json")])
+        );
+
+        let owners = owning_projects(&projects, &[PathBuf::from("tsconfig.json")], &filter, repo);
+        assert_eq!(owners, HashSet::from([PathBuf::from("/repo/package.json")]));
+        assert!(
+            owning_projects(&[&core], &[PathBuf::from("docs/guide.md")], &filter, repo).is_empty()
+        );
+
+        // Files outside a project's changeFiles globs don't suggest it
+        let config = Config {
+            change_files: HashMap::from([(
+                "packages/core/package.json".to_string(),
+                vec!["src/**".to_string()],
+            )]),
+            ..Config::default()
+        };
+        let filter = ChangeFilter::new(repo, &config).unwrap();
+        let files = [PathBuf::from("packages/core/README.md")];
+        assert!(owning_projects(&projects, &files, &filter, repo).is_empty());
+    }
 }
@@ -1,17 +1,24 @@
-use changepacks_core::{ChangePackResultLog, Language, Project, UpdateType};
+use changepacks_core::{
+    ChangePackResultLog, DependencyEdge, Language, Project, UpdateType, path_key,
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use changepacks_registry::{Registry, RegistryClient};
 use changepacks_utils::{
-    apply_reverse_dependencies, display_update, gen_changepack_result_map, gen_update_map,
-    get_relative_path,
+    Membership, apply_reverse_dependencies, format_update_rows, gen_changepack_result_map,
+    gen_update_map, get_changepacks_dir, get_relative_path, next_version_with_id,
+    read_changepack_logs, read_release_manifest, render_changepack_notes, workspace_membership,
 };
 use clap::Args;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::{
-    CommandContext,
-    options::{CliLanguage, FilterOptions, FormatOptions},
+    CommandContext, GlobalOptions,
+    github_actions::{Annotation, append_step_summary, workflow_command},
+    options::{CheckFormatOptions, CliLanguage, FilterOptions, SortOptions, TreeDirection},
+    registries::registry_client,
 };
 
 #[derive(Args, Debug)]
@@ -21,7 +28,7 @@ pub struct CheckArgs {
     filter: Option<FilterOptions>,
 
     #[arg(long, default_value = "stdout")]
-    format: FormatOptions,
+    format: CheckFormatOptions,
 
     #[arg(short, long, default_value = "false")]
     remote: bool,
@@ -29,9 +36,45 @@ pub struct CheckArgs {
     #[arg(long)]
     tree: bool,
 
+    /// With `--tree`, limit how many levels below each root are shown
+    #[arg(long, value_name = "N")]
+    depth: Option<usize>,
+
+    /// With `--tree`, show each project's dependencies or its dependents as children
+    #[arg(long, value_enum, default_value_t)]
+    direction: TreeDirection,
+
     /// Filter projects by language. Can be specified multiple times to include multiple languages.
     #[arg(short, long, value_enum)]
     pub language: Vec<CliLanguage>,
+
+    /// Order projects in stdout and JSON output
+    #[arg(long, value_enum)]
+    sort: Option<SortOptions>,
+
+    /// Only show projects that are changed or have pending changepacks
+    #[arg(long)]
+    only_changed: bool,
+
+    /// Compare each package's local version with the latest version on its registry
+    #[arg(long, conflicts_with = "tree")]
+    registry: bool,
+
+    /// Fail when a changed project has no pending changepack
+    #[arg(long)]
+    strict: bool,
+}
+
+/// Local version of a package next to the latest one on its registry
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct RegistryDrift {
+    name: String,
+    path: PathBuf,
+    registry: String,
+    local: String,
+    published: Option<String>,
+    drift: bool,
 }
 
 /// Check project status
@@ -40,20 +83,69 @@ pub struct CheckArgs {
 /// Returns error if command context creation or project checking fails.
 ///
 /// Excluded from coverage: orchestrates `CommandContext::new` (git I/O)
-/// and a deeply-nested multi-line `format!(...).replace(...)` expression
-/// where tarpaulin mis-attributes one branch of the inner `if let
-/// Some(update_type) = update_map.get(...)`. The underlying helpers
-/// (`display_update`, `gen_update_map`, `apply_reverse_dependencies`,
-/// `format_project_line`) are covered by their own tests.
+/// and stdout printing. The underlying helpers (`format_update_rows`,
+/// `gen_update_map`, `apply_reverse_dependencies`, `format_project_line`)
+/// are covered by their own tests.
 #[cfg(not(tarpaulin_include))]
-pub async fn handle_check(args: &CheckArgs) -> Result<()> {
-    let ctx = CommandContext::new(args.remote).await?;
+pub async fn handle_check(args: &CheckArgs, global: &GlobalOptions) -> Result<()> {
+    let repositories = CommandContext::new(global, args.remote)
+        .await?
+        .with_repositories(args.remote)
+        .await?;
+
+    // Single repository: keep the flat output; multi-repo: one section per repository
+    if let [(_, ctx)] = repositories.as_slice() {
+        if let Some(json) = check_repository(ctx, args).await? {
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        return Ok(());
+    }
+
+    let mut sections = serde_json::Map::new();
+    for (index, (label, ctx)) in repositories.iter().enumerate() {
+        if let CheckFormatOptions::Stdout = args.format {
+            use colored::Colorize;
+            if index > 0 {
+                println!();
+            }
+            println!("{}", label.bold());
+        }
+        if let Some(json) = check_repository(ctx, args).await? {
+            sections.insert(label.clone(), json);
+        }
+    }
+    if let CheckFormatOptions::Json = args.format
+        && !args.tree
+    {
+        println!("{}", serde_json::to_string_pretty(&sections)?);
+    }
+    Ok(())
+}
 
+/// Check one repository, printing stdout output directly and returning the JSON result map
+///
+/// Excluded from coverage: see [`handle_check`].
+#[cfg(not(tarpaulin_include))]
+async fn check_repository(
+    ctx: &CommandContext,
+    args: &CheckArgs,
+) -> Result<Option<serde_json::Value>> {
     let mut projects = ctx
         .project_finders
         .iter()
         .flat_map(|finder| finder.projects())
         .collect::<Vec<_>>();
+    // Trailer changepacks may name any project, so resolve them before filtering
+    let (mut update_map, prerelease_id) =
+        gen_update_map(&ctx.repo_root_path, &ctx.config, &projects, &ctx.diff_refs).await?;
+    let all_projects = projects.clone();
+    let stale = super::doctor::stale_changepacks(
+        &read_changepack_logs(&get_changepacks_dir(&ctx.repo_root_path)?).await?,
+        &projects,
+        &ctx.config,
+        &ctx.repo_root_path,
+        chrono::Utc::now(),
+    );
     if let Some(filter) = &args.filter {
         projects.retain(|p| filter.matches(p));
     }
@@ -66,57 +158,509 @@ pub async fn handle_check(args: &CheckArgs) -> Result<()> {
         projects.retain(|project| allowed_languages.contains(&project.language()));
     }
     projects.sort();
-    if let FormatOptions::Stdout = args.format {
-        println!("Found {} projects", projects.len());
-    }
-    let mut update_map = gen_update_map(&CommandContext::current_dir()?, &ctx.config).await?;
 
     // Apply reverse dependency updates (workspace:* dependencies)
-    apply_reverse_dependencies(&mut update_map, &projects, &ctx.repo_root_path);
+    apply_reverse_dependencies(
+        &mut update_map,
+        &projects,
+        &ctx.repo_root_path,
+        prerelease_id.as_deref(),
+    );
+
+    // GitHub annotations report the uncovered projects before failing
+    if args.strict && args.format != CheckFormatOptions::Github {
+        super::update::require_changepacks(
+            &projects,
+            &update_map,
+            &ctx.repo_root_path,
+            &read_release_manifest(&get_changepacks_dir(&ctx.repo_root_path)?).await?,
+        )?;
+    }
+
+    let found = projects.len();
+    if args.only_changed {
+        projects.retain(|project| {
+            project.is_changed()
+                || get_relative_path(&ctx.repo_root_path, project.path())
+                    .is_ok_and(|path| update_map.contains_key(&path))
+        });
+    }
+    if let CheckFormatOptions::Stdout = args.format {
+        if args.only_changed {
+            println!(
+                "Found {found} projects ({} changed or pending)",
+                projects.len()
+            );
+        } else {
+            println!("Found {found} projects");
+        }
+    }
+
+    if let Some(sort) = args.sort {
+        sort.sort(&mut projects, |project| {
+            get_relative_path(&ctx.repo_root_path, project.path())
+                .ok()
+                .and_then(|path| update_map.get(&path))
+                .map(|(update_type, _)| *update_type)
+        });
+    }
+
+    if args.registry {
+        let drift =
+            registry_drift(&projects, |registry| registry_client(&ctx.config, registry)).await;
+        return match args.format {
+            CheckFormatOptions::Stdout => {
+                print!("{}", format_registry_drift(&drift));
+                Ok(None)
+            }
+            CheckFormatOptions::Json => Ok(Some(serde_json::to_value(drift)?)),
+            CheckFormatOptions::Github => {
+                print!("{}", registry_drift_annotations(&drift));
+                Ok(None)
+            }
+        };
+    }
 
     if args.tree {
         // Tree mode: show dependencies as a tree
-        display_tree(&projects, &ctx.repo_root_path, &update_map)?;
+        display_tree(
+            &projects,
+            &ctx.repo_root_path,
+            &update_map,
+            prerelease_id.as_deref(),
+            args.direction,
+            args.depth,
+        )?;
     } else {
         match args.format {
-            FormatOptions::Stdout => {
+            CheckFormatOptions::Stdout => {
                 use colored::Colorize;
-                for project in projects {
-                    let changed_marker = if project.is_changed() {
-                        " (changed)".bright_yellow()
-                    } else {
-                        "".normal()
-                    };
-                    println!(
-                        "{}",
-                        format!("{project}{changed_marker}",).replace(
-                            &project
-                                .version()
-                                .map_or_else(|| "unknown".to_string(), |v| format!("v{v}"),),
-                            &if let Some(update_type) = update_map
-                                .get(&get_relative_path(&ctx.repo_root_path, project.path())?)
-                            {
-                                display_update(project.version(), update_type.0)?
-                            } else {
-                                project
-                                    .version()
-                                    .map_or_else(|| "unknown".to_string(), |v| format!("v{v}"))
-                            },
-                        ),
+                let mut rows = Vec::with_capacity(projects.len());
+                let mut notes = Vec::with_capacity(projects.len());
+                for (workspace, members) in group_by_workspace(&projects) {
+                    let first_member = rows.len() + 1;
+                    for (project, indent) in std::iter::once((workspace, 0))
+                        .chain(members.iter().map(|member| (*member, 2)))
+                    {
+                        let changed_marker = if project.is_changed() {
+                            " (changed)".bright_yellow()
+                        } else {
+                            "".normal()
+                        };
+                        let update_entry = update_map
+                            .get(&get_relative_path(&ctx.repo_root_path, project.path())?);
+                        rows.push((
+                            format!("{}{project}{changed_marker}", " ".repeat(indent)),
+                            project.version(),
+                            update_entry.map(|(update_type, _)| *update_type),
+                            update_entry.map_or(0, |(_, logs)| logs.len()),
+                        ));
+                        notes.push(
+                            update_entry.map(|(_, logs)| render_changepack_notes(logs, indent + 4)),
+                        );
+                    }
+                    if members.is_empty() {
+                        continue;
+                    }
+                    let updates = rows[first_member..]
+                        .iter()
+                        .filter(|(_, _, update_type, _)| update_type.is_some())
+                        .count();
+                    let changed = members.iter().filter(|member| member.is_changed()).count();
+                    let subtotal = format!(
+                        "{} package{}, {changed} changed, {updates} to update",
+                        members.len(),
+                        if members.len() == 1 { "" } else { "s" }
                     );
+                    rows.push((format!("  {}", subtotal.bright_black()), None, None, 0));
+                    notes.push(None);
+                }
+                for (line, notes) in format_update_rows(&rows, prerelease_id.as_deref())?
+                    .iter()
+                    .zip(notes)
+                {
+                    println!("{line}");
+                    if let Some(notes) = notes {
+                        print!("{notes}");
+                    }
+                }
+                let issues =
+                    super::doctor::version_issues(&projects, &ctx.config, &ctx.repo_root_path)
+                        .await?;
+                if !issues.is_empty() {
+                    print!("{}", super::doctor::format_version_issues(&issues));
                 }
+                if !stale.is_empty() {
+                    print!("{}", super::doctor::format_stale_changepacks(&stale, true));
+                }
+                print!("{}", format_orphans(&projects, &all_projects));
             }
-            FormatOptions::Json => {
-                let json = serde_json::to_string_pretty(&gen_changepack_result_map(
+            CheckFormatOptions::Json => {
+                let mut results = gen_changepack_result_map(
                     projects.as_slice(),
                     &ctx.repo_root_path,
                     &mut update_map,
+                    prerelease_id.as_deref(),
+                )?;
+                for project in &projects {
+                    let path = get_relative_path(&ctx.repo_root_path, project.path())?;
+                    if let Some(result) = results.remove(&path) {
+                        let edges = dependency_edges(project, &all_projects);
+                        results.insert(path, result.with_dependencies(edges));
+                    }
+                }
+                if args.sort.is_none() {
+                    return Ok(Some(serde_json::to_value(results)?));
+                }
+                // Keep the requested order (serde_json preserves insertion order)
+                let mut sorted = serde_json::Map::new();
+                for project in &projects {
+                    let path = get_relative_path(&ctx.repo_root_path, project.path())?;
+                    if let Some(result) = results.remove(&path) {
+                        sorted.insert(
+                            path.to_string_lossy().into_owned(),
+                            serde_json::to_value(result)?,
+                        );
+                    }
+                }
+                return Ok(Some(serde_json::Value::Object(sorted)));
+            }
+            CheckFormatOptions::Github => {
+                let manifest =
+                    read_release_manifest(&get_changepacks_dir(&ctx.repo_root_path)?).await?;
+                let statuses = super::status::changepack_coverage(
+                    &projects,
+                    &update_map,
+                    &ctx.repo_root_path,
+                    &manifest,
+                )?;
+                let issues =
+                    super::doctor::version_issues(&projects, &ctx.config, &ctx.repo_root_path)
+                        .await?;
+                let changepacks =
+                    |path: &Path| update_map.get(path).map_or(0, |(_, logs)| logs.len());
+                print!(
+                    "{}",
+                    github_annotations(
+                        &statuses,
+                        prerelease_id.as_deref(),
+                        &issues,
+                        &stale,
+                        args.strict,
+                        changepacks
+                    )?
+                );
+                append_step_summary(&github_summary(
+                    &statuses,
+                    prerelease_id.as_deref(),
+                    changepacks,
                 )?)?;
-                println!("{json}");
+                if args.strict {
+                    super::update::require_changepacks(
+                        &projects,
+                        &update_map,
+                        &ctx.repo_root_path,
+                        &manifest,
+                    )?;
+                }
             }
         }
     }
-    Ok(())
+    Ok(None)
+}
+
+/// Look up the latest published version of every package with a supported registry
+///
+/// Packages whose registry cannot be reached are reported on stderr and left out.
+async fn registry_drift(
+    projects: &[&Project],
+    client: impl Fn(Registry) -> RegistryClient,
+) -> Vec<RegistryDrift> {
+    let lookups = projects.iter().filter_map(|project| {
+        let registry = Registry::from_name(project.registry())?;
+        let name = project.name()?;
+        let local = project.version()?;
+        let client = client(registry);
+        Some(async move {
+            match client.latest_version(name).await {
+                Ok(published) => Some(RegistryDrift {
+                    name: name.to_string(),
+                    path: project.relative_path().to_path_buf(),
+                    registry: registry.to_string(),
+                    local: local.to_string(),
+                    drift: published.as_deref() != Some(local),
+                    published,
+                }),
+                Err(e) => {
+                    eprintln!("Could not check {project} on {registry}: {e:#}");
+                    None
+                }
+            }
+        })
+    });
+    futures::future::join_all(lookups)
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// `::warning` for every package whose local version is not the latest published one
+fn registry_drift_annotations(drift: &[RegistryDrift]) -> String {
+    drift
+        .iter()
+        .filter(|entry| entry.drift)
+        .map(|entry| {
+            let published = entry
+                .published
+                .as_deref()
+                .map_or_else(|| "nothing".to_string(), |version| format!("v{version}"));
+            format!(
+                "{}\n",
+                workflow_command(
+                    Annotation::Warning,
+                    Some(&entry.path),
+                    &format!("{} drifted from {}", entry.name, entry.registry),
+                    &format!(
+                        "Local version is v{} but {} has {published}",
+                        entry.local, entry.registry
+                    ),
+                )
+            )
+        })
+        .collect()
+}
+
+const fn bump_name(update_type: UpdateType) -> &'static str {
+    match update_type {
+        UpdateType::Major => "major",
+        UpdateType::Minor => "minor",
+        UpdateType::Patch => "patch",
+        UpdateType::Prerelease => "prerelease",
+    }
+}
+
+/// Next version of a project with a pending bump (prereleases bumped to `prerelease_id`), as
+/// `current -> next`
+fn version_transition(
+    status: &super::status::ProjectStatus,
+    prerelease_id: Option<&str>,
+) -> Result<Option<String>> {
+    let Some(update_type) = status.update_type else {
+        return Ok(None);
+    };
+    let current = status.version.as_deref();
+    let next = next_version_with_id(current.unwrap_or("0.0.0"), update_type, prerelease_id)
+        .with_context(|| format!("Cannot bump {}", status.name.as_deref().unwrap_or("noname")))?;
+    Ok(Some(format!("{} -> {next}", current.unwrap_or("unknown"))))
+}
+
+/// Workflow commands for `--format github`
+///
+/// Pending bumps become notices on the project manifest, changed projects without a
+/// changepack become warnings (errors with `--strict`), and version consistency problems
+/// and stale changepacks are reported alongside.
+fn github_annotations(
+    statuses: &[super::status::ProjectStatus],
+    prerelease_id: Option<&str>,
+    issues: &[super::doctor::VersionIssue],
+    stale: &[super::doctor::StaleChangepack],
+    strict: bool,
+    changepacks: impl Fn(&Path) -> usize,
+) -> Result<String> {
+    use super::status::Coverage;
+    let mut out = String::new();
+    for status in statuses {
+        let name = status.name.as_deref().unwrap_or("noname");
+        let line = match (status.coverage, version_transition(status, prerelease_id)?) {
+            (Coverage::Pending, Some(transition)) => {
+                let count = changepacks(&status.path);
+                let update_type = status.update_type.map_or("", bump_name);
+                workflow_command(
+                    Annotation::Notice,
+                    Some(&status.path),
+                    &format!("{name} {transition}"),
+                    &if count == 0 {
+                        format!("{update_type} bump")
+                    } else {
+                        format!(
+                            "{update_type} bump from {count} changepack{}",
+                            if count == 1 { "" } else { "s" }
+                        )
+                    },
+                )
+            }
+            (Coverage::Missing, _) => workflow_command(
+                if strict {
+                    Annotation::Error
+                } else {
+                    Annotation::Warning
+                },
+                Some(&status.path),
+                &format!("{name} has no changepack"),
+                "Changed since the base branch without a pending changepack; run `changepacks` to record one",
+            ),
+            _ => continue,
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+    for issue in issues {
+        out.push_str(&workflow_command(
+            Annotation::Error,
+            None,
+            "Version consistency",
+            &issue.to_string(),
+        ));
+        out.push('\n');
+    }
+    for changepack in stale {
+        out.push_str(&workflow_command(
+            Annotation::Warning,
+            Some(changepack.path()),
+            "Stale changepack",
+            &changepack.to_string(),
+        ));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Markdown job summary for `--format github`: a table of the changed and pending projects
+fn github_summary(
+    statuses: &[super::status::ProjectStatus],
+    prerelease_id: Option<&str>,
+    changepacks: impl Fn(&Path) -> usize,
+) -> Result<String> {
+    use super::status::Coverage;
+    let mut out = String::from("### changepacks check\n\n");
+    if statuses.is_empty() {
+        out.push_str("No changed projects.\n\n");
+        return Ok(out);
+    }
+    out.push_str("| Package | Path | Version | Bump | Changepacks |\n");
+    out.push_str("| --- | --- | --- | --- | ---: |\n");
+    for status in statuses {
+        let version = version_transition(status, prerelease_id)?
+            .or_else(|| status.version.clone())
+            .unwrap_or_default();
+        let bump = match (status.coverage, status.update_type) {
+            (Coverage::Pending, Some(update_type)) => bump_name(update_type).to_string(),
+            (Coverage::Released, _) => "released".to_string(),
+            _ => "**missing changepack**".to_string(),
+        };
+        out.push_str(&format!(
+            "| {} | `{}` | {version} | {bump} | {} |\n",
+            status.name.as_deref().unwrap_or("noname"),
+            path_key(&status.path),
+            changepacks(&status.path)
+        ));
+    }
+    out.push('\n');
+    Ok(out)
+}
+
+fn format_registry_drift(drift: &[RegistryDrift]) -> String {
+    use colored::Colorize;
+
+    if drift.is_empty() {
+        return "No packages with a supported registry\n".to_string();
+    }
+    let mut out = String::new();
+    for entry in drift {
+        let published = entry.published.as_deref().map_or_else(
+            || "unpublished".to_string(),
+            |version| format!("v{version}"),
+        );
+        let line = format!(
+            "{} ({}) v{} local, {published} on {}",
+            entry.name,
+            entry.path.display(),
+            entry.local,
+            entry.registry
+        );
+        if entry.drift {
+            out.push_str(&format!("{} {}\n", line, "(drift)".bright_yellow()));
+        } else {
+            out.push_str(&format!("{line}\n"));
+        }
+    }
+    out
+}
+
+/// Dependencies of `project`, resolved against every project in the repository
+///
+/// Filters don't apply here: a dependency on a project left out of the output is still
+/// reported as internal.
+fn dependency_edges(project: &Project, all_projects: &[&Project]) -> Vec<DependencyEdge> {
+    let mut names: Vec<&String> = project.dependencies().iter().collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let path = all_projects
+                .iter()
+                .find(|candidate| candidate.name() == Some(name.as_str()))
+                .map(|candidate| candidate.relative_path().to_path_buf());
+            DependencyEdge::new(name.clone(), path)
+        })
+        .collect()
+}
+
+/// Group packages under the workspace that owns them
+///
+/// A package belongs to the workspace whose member globs match it (see
+/// `workspace_membership`). Returns the workspaces (with their members) followed by packages
+/// that belong to no workspace, preserving input order.
+fn group_by_workspace<'a>(projects: &[&'a Project]) -> Vec<(&'a Project, Vec<&'a Project>)> {
+    let mut groups: Vec<(&Project, Vec<&Project>)> = projects
+        .iter()
+        .filter(|project| matches!(project, Project::Workspace(_)))
+        .map(|workspace| (*workspace, Vec::new()))
+        .collect();
+    let mut standalone = Vec::new();
+    for package in projects
+        .iter()
+        .filter(|project| matches!(project, Project::Package(_)))
+    {
+        let owner = match workspace_membership(package, projects) {
+            Membership::Member(owner) => groups
+                .iter_mut()
+                .find(|(workspace, _)| std::ptr::eq(*workspace, owner)),
+            Membership::Orphan(_) | Membership::Standalone => None,
+        };
+        match owner {
+            Some((_, members)) => members.push(*package),
+            None => standalone.push((*package, Vec::new())),
+        }
+    }
+    groups.extend(standalone);
+    groups
+}
+
+/// Warnings for packages inside a workspace directory that none of its member globs match
+fn format_orphans(projects: &[&Project], all_projects: &[&Project]) -> String {
+    use colored::Colorize;
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    for package in projects
+        .iter()
+        .filter(|project| matches!(project, Project::Package(_)))
+    {
+        if let Membership::Orphan(workspace) = workspace_membership(package, all_projects) {
+            let _ = writeln!(
+                out,
+                "{} {} ({}) is inside workspace {} ({}) but not one of its members",
+                "warning:".yellow(),
+                package.name().unwrap_or("noname"),
+                package.relative_path().display(),
+                workspace.name().unwrap_or("noname"),
+                workspace.relative_path().display(),
+            );
+        }
+    }
+    out
 }
 
 /// Display projects as a dependency tree
@@ -130,49 +674,16 @@ fn display_tree(
     projects: &[&Project],
     repo_root_path: &std::path::Path,
     update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    prerelease_id: Option<&str>,
+    direction: TreeDirection,
+    max_depth: Option<usize>,
 ) -> Result<()> {
     // Create a map from project relative_path to project
     let mut path_to_project: HashMap<String, &Project> = HashMap::new();
     for project in projects {
         path_to_project.insert(project.name().unwrap_or("noname").to_string(), project);
     }
-
-    // Build reverse dependency graph: graph[dep] = list of projects that depend on dep
-    // This way, dependencies appear as children in the tree
-    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
-    let mut roots: HashSet<String> = HashSet::new();
-    let mut has_dependencies: HashSet<String> = HashSet::new();
-
-    for project in projects {
-        let deps = project.dependencies();
-        // Filter dependencies to only include monorepo projects
-        let monorepo_deps: Vec<String> = deps
-            .iter()
-            .filter(|dep| path_to_project.contains_key(*dep))
-            .cloned()
-            .collect();
-
-        if !monorepo_deps.is_empty() {
-            graph.insert(
-                project.name().unwrap_or("noname").to_string(),
-                monorepo_deps.clone(),
-            );
-            for dep in &monorepo_deps {
-                has_dependencies.insert(dep.clone());
-            }
-        }
-    }
-
-    // Root nodes are projects that are not dependencies of any other project
-    for project in projects {
-        if !has_dependencies.contains(project.name().unwrap_or("noname")) {
-            roots.insert(project.name().unwrap_or("noname").to_string());
-        }
-    }
-
-    // Sort roots for consistent output
-    let mut sorted_roots: Vec<String> = roots.into_iter().collect();
-    sorted_roots.sort();
+    let (graph, sorted_roots) = build_tree_graph(projects, &path_to_project, direction);
 
     // Display tree starting from roots
     let mut visited: HashSet<String> = HashSet::new();
@@ -181,20 +692,39 @@ fn display_tree(
         path_to_project: &path_to_project,
         repo_root_path,
         update_map,
+        prerelease_id,
+        max_depth,
     };
     for (idx, root) in sorted_roots.iter().enumerate() {
         if let Some(project) = path_to_project.get(root) {
             let is_last = idx == sorted_roots.len() - 1;
-            display_tree_node(project, &mut ctx, "", is_last, &mut visited)?;
+            display_tree_node(project, &mut ctx, "", is_last, 0, &mut visited)?;
         }
     }
 
-    // Display projects that weren't part of the tree (orphaned nodes)
+    // Display projects that weren't part of the tree (orphaned nodes); nodes hidden by
+    // `--depth` are reachable from a root and stay hidden
+    let mut reachable: HashSet<&String> = sorted_roots.iter().collect();
+    let mut stack: Vec<&String> = sorted_roots.iter().collect();
+    while let Some(name) = stack.pop() {
+        for child in graph.get(name).into_iter().flatten() {
+            if reachable.insert(child) {
+                stack.push(child);
+            }
+        }
+    }
     for project in projects {
-        if !visited.contains(project.name().unwrap_or("noname")) {
+        let name = project.name().unwrap_or("noname").to_string();
+        if !visited.contains(&name) && !reachable.contains(&name) {
             println!(
                 "{}",
-                format_project_line(project, repo_root_path, update_map, &path_to_project)?
+                format_project_line(
+                    project,
+                    repo_root_path,
+                    update_map,
+                    prerelease_id,
+                    &path_to_project
+                )?
             );
         }
     }
@@ -202,12 +732,68 @@ fn display_tree(
     Ok(())
 }
 
+/// Build the tree edges (`graph[node]` = child names) and the sorted root names
+///
+/// With `Dependencies`, children are the monorepo projects a node depends on and roots are
+/// projects nothing depends on; with `Dependents` the edges are reversed, so roots are
+/// projects without monorepo dependencies. In both directions a workspace's members are
+/// listed under it.
+fn build_tree_graph(
+    projects: &[&Project],
+    path_to_project: &HashMap<String, &Project>,
+    direction: TreeDirection,
+) -> (HashMap<String, Vec<String>>, Vec<String>) {
+    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+    let mut has_parent: HashSet<String> = HashSet::new();
+
+    for project in projects {
+        let name = project.name().unwrap_or("noname").to_string();
+        // Filter dependencies to only include monorepo projects
+        for dep in project
+            .dependencies()
+            .iter()
+            .filter(|dep| path_to_project.contains_key(*dep))
+        {
+            let (parent, child) = match direction {
+                TreeDirection::Dependencies => (name.clone(), dep.clone()),
+                TreeDirection::Dependents => (dep.clone(), name.clone()),
+            };
+            graph.entry(parent).or_default().push(child.clone());
+            has_parent.insert(child);
+        }
+        if matches!(project, Project::Package(_))
+            && let Membership::Member(workspace) = workspace_membership(project, projects)
+        {
+            let members = graph
+                .entry(workspace.name().unwrap_or("noname").to_string())
+                .or_default();
+            if !members.contains(&name) {
+                members.push(name.clone());
+            }
+            has_parent.insert(name);
+        }
+    }
+
+    let mut roots: Vec<String> = projects
+        .iter()
+        .map(|project| project.name().unwrap_or("noname").to_string())
+        .filter(|name| !has_parent.contains(name))
+        .collect();
+    roots.sort();
+    roots.dedup();
+    (graph, roots)
+}
+
 /// Context for tree display operations
 struct TreeContext<'a> {
     graph: &'a HashMap<String, Vec<String>>,
     path_to_project: &'a HashMap<String, &'a Project>,
     repo_root_path: &'a Path,
     update_map: &'a HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    /// Identifier for prerelease bumps
+    prerelease_id: Option<&'a str>,
+    /// Levels shown below each root (`None` for the whole graph)
+    max_depth: Option<usize>,
 }
 
 /// Display a single node in the tree
@@ -216,6 +802,7 @@ fn display_tree_node(
     ctx: &mut TreeContext,
     prefix: &str,
     is_last: bool,
+    depth: usize,
     visited: &mut HashSet<String>,
 ) -> Result<()> {
     let project_name = project.name().unwrap_or("noname").to_string();
@@ -235,11 +822,16 @@ fn display_tree_node(
                 project,
                 ctx.repo_root_path,
                 ctx.update_map,
+                ctx.prerelease_id,
                 ctx.path_to_project
             )?
         );
     }
 
+    if ctx.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+        return Ok(());
+    }
+
     // Always display dependencies, even if the node was already visited
     // This ensures all dependencies are shown in the tree
     if let Some(deps) = ctx.graph.get(&project_name) {
@@ -267,11 +859,19 @@ fn display_tree_node(
                             dep_project,
                             ctx.repo_root_path,
                             ctx.update_map,
+                            ctx.prerelease_id,
                             ctx.path_to_project
                         )?
                     );
                 } else {
-                    display_tree_node(dep_project, ctx, &new_prefix, is_last_dep, visited)?;
+                    display_tree_node(
+                        dep_project,
+                        ctx,
+                        &new_prefix,
+                        is_last_dep,
+                        depth + 1,
+                        visited,
+                    )?;
                 }
             }
         }
@@ -292,6 +892,7 @@ fn format_project_line(
     project: &Project,
     repo_root_path: &std::path::Path,
     update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    prerelease_id: Option<&str>,
     path_to_project: &HashMap<String, &Project>,
 ) -> Result<String> {
     use changepacks_utils::get_relative_path;
@@ -299,7 +900,7 @@ fn format_project_line(
 
     let relative_path = get_relative_path(repo_root_path, project.path())?;
     let version = if let Some(update_entry) = update_map.get(&relative_path) {
-        changepacks_utils::display_update(project.version(), update_entry.0)?
+        changepacks_utils::display_update(project.version(), update_entry.0, prerelease_id)?
     } else {
         project
             .version()
@@ -371,15 +972,45 @@ mod tests {
     fn test_check_args_default() {
         let cli = TestCli::parse_from(["test"]);
         assert!(cli.check.filter.is_none());
-        assert!(matches!(cli.check.format, FormatOptions::Stdout));
+        assert!(matches!(cli.check.format, CheckFormatOptions::Stdout));
         assert!(!cli.check.remote);
         assert!(!cli.check.tree);
     }
 
+    #[test]
+    fn test_check_args_with_sort() {
+        let cli = TestCli::parse_from(["test"]);
+        assert!(cli.check.sort.is_none());
+        let cli = TestCli::parse_from(["test", "--sort", "bump"]);
+        assert!(matches!(cli.check.sort, Some(SortOptions::Bump)));
+    }
+
+    #[test]
+    fn test_check_args_only_changed() {
+        assert!(!TestCli::parse_from(["test"]).check.only_changed);
+        assert!(
+            TestCli::parse_from(["test", "--only-changed"])
+                .check
+                .only_changed
+        );
+    }
+
+    #[test]
+    fn test_check_args_strict() {
+        assert!(!TestCli::parse_from(["test"]).check.strict);
+        assert!(TestCli::parse_from(["test", "--strict"]).check.strict);
+    }
+
     #[test]
     fn test_check_args_with_json_format() {
         let cli = TestCli::parse_from(["test", "--format", "json"]);
-        assert!(matches!(cli.check.format, FormatOptions::Json));
+        assert!(matches!(cli.check.format, CheckFormatOptions::Json));
+    }
+
+    #[test]
+    fn test_check_args_with_github_format() {
+        let cli = TestCli::parse_from(["test", "--format", "github"]);
+        assert_eq!(cli.check.format, CheckFormatOptions::Github);
     }
 
     #[test]
@@ -412,7 +1043,7 @@ mod tests {
             "test", "--filter", "package", "--format", "json", "--tree", "--remote",
         ]);
         assert!(matches!(cli.check.filter, Some(FilterOptions::Package)));
-        assert!(matches!(cli.check.format, FormatOptions::Json));
+        assert!(matches!(cli.check.format, CheckFormatOptions::Json));
         assert!(cli.check.tree);
         assert!(cli.check.remote);
     }
@@ -501,6 +1132,7 @@ mod tests {
         async fn update_version(
             &mut self,
             _update_type: changepacks_core::UpdateType,
+            _prerelease_id: Option<&str>,
         ) -> anyhow::Result<()> {
             Ok(())
         }
@@ -536,6 +1168,7 @@ mod tests {
         language: Language,
         dependencies: HashSet<String>,
         changed: bool,
+        member_patterns: Option<Vec<String>>,
     }
 
     impl MockWorkspaceForCheck {
@@ -554,6 +1187,7 @@ mod tests {
                 language,
                 dependencies: HashSet::new(),
                 changed: false,
+                member_patterns: None,
             }
         }
     }
@@ -575,6 +1209,7 @@ mod tests {
         async fn update_version(
             &mut self,
             _update_type: changepacks_core::UpdateType,
+            _prerelease_id: Option<&str>,
         ) -> anyhow::Result<()> {
             Ok(())
         }
@@ -593,6 +1228,9 @@ mod tests {
         fn set_changed(&mut self, changed: bool) {
             self.changed = changed;
         }
+        fn member_patterns(&self) -> Option<&[String]> {
+            self.member_patterns.as_deref()
+        }
         fn default_publish_command(&self) -> String {
             "echo publish".to_string()
         }
@@ -601,6 +1239,206 @@ mod tests {
         }
     }
 
+    fn project_names(projects: &[&Project]) -> Vec<String> {
+        projects
+            .iter()
+            .map(|project| project.name().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_tree_graph_directions() {
+        let package = |name: &str, deps: &[&str]| {
+            let mut pkg = MockPackageForCheck::new(
+                Some(name),
+                Some("1.0.0"),
+                &format!("/repo/{name}/package.json"),
+                &format!("{name}/package.json"),
+                Language::Node,
+            );
+            for dep in deps {
+                pkg.add_dependency(dep);
+            }
+            Project::Package(Box::new(pkg))
+        };
+        // app -> lib -> utils, app -> utils, plus an external dependency
+        let projects = [
+            package("app", &["lib", "utils", "react"]),
+            package("lib", &["utils"]),
+            package("utils", &[]),
+        ];
+        let refs: Vec<&Project> = projects.iter().collect();
+        let path_to_project: HashMap<String, &Project> = refs
+            .iter()
+            .map(|project| (project.name().unwrap().to_string(), *project))
+            .collect();
+        let sorted = |graph: &HashMap<String, Vec<String>>, name: &str| {
+            let mut children = graph.get(name).cloned().unwrap_or_default();
+            children.sort();
+            children
+        };
+
+        let (graph, roots) = build_tree_graph(&refs, &path_to_project, TreeDirection::Dependencies);
+        assert_eq!(roots, ["app"]);
+        assert_eq!(sorted(&graph, "app"), ["lib", "utils"]);
+        assert_eq!(sorted(&graph, "lib"), ["utils"]);
+        assert!(sorted(&graph, "utils").is_empty());
+
+        let (graph, roots) = build_tree_graph(&refs, &path_to_project, TreeDirection::Dependents);
+        assert_eq!(roots, ["utils"]);
+        assert_eq!(sorted(&graph, "utils"), ["app", "lib"]);
+        assert_eq!(sorted(&graph, "lib"), ["app"]);
+        assert!(sorted(&graph, "app").is_empty());
+    }
+
+    #[test]
+    fn test_check_args_tree_options() {
+        let cli = TestCli::parse_from(["test", "--tree"]);
+        assert_eq!(cli.check.depth, None);
+        assert_eq!(cli.check.direction, TreeDirection::Dependencies);
+        let cli = TestCli::parse_from([
+            "test",
+            "--tree",
+            "--depth",
+            "1",
+            "--direction",
+            "dependents",
+        ]);
+        assert_eq!(cli.check.depth, Some(1));
+        assert_eq!(cli.check.direction, TreeDirection::Dependents);
+    }
+
+    #[test]
+    fn test_dependency_edges() {
+        let package = |name: &str, deps: &[&str]| {
+            let mut pkg = MockPackageForCheck::new(
+                Some(name),
+                Some("1.0.0"),
+                &format!("/repo/{name}/package.json"),
+                &format!("{name}/package.json"),
+                Language::Node,
+            );
+            for dep in deps {
+                pkg.add_dependency(dep);
+            }
+            Project::Package(Box::new(pkg))
+        };
+        let app = package("app", &["utils", "react"]);
+        let utils = package("utils", &[]);
+
+        assert_eq!(
+            dependency_edges(&app, &[&app, &utils]),
+            [
+                DependencyEdge::new("react".to_string(), None),
+                DependencyEdge::new(
+                    "utils".to_string(),
+                    Some(PathBuf::from("utils/package.json"))
+                ),
+            ]
+        );
+        assert!(dependency_edges(&utils, &[&app, &utils]).is_empty());
+    }
+
+    #[test]
+    fn test_group_by_workspace() {
+        let workspace = |name, relative_path, language| {
+            Project::Workspace(Box::new(MockWorkspaceForCheck::new(
+                Some(name),
+                Some("1.0.0"),
+                &format!("/repo/{relative_path}"),
+                relative_path,
+                language,
+            )))
+        };
+        let package = |name, relative_path, language| {
+            Project::Package(Box::new(MockPackageForCheck::new(
+                Some(name),
+                Some("1.0.0"),
+                &format!("/repo/{relative_path}"),
+                relative_path,
+                language,
+            )))
+        };
+        let projects = [
+            workspace("root", "package.json", Language::Node),
+            workspace("nested", "apps/package.json", Language::Node),
+            workspace("cargo", "Cargo.toml", Language::Rust),
+            package("web", "apps/web/package.json", Language::Node),
+            package("ui", "packages/ui/package.json", Language::Node),
+            package("core", "crates/core/Cargo.toml", Language::Rust),
+            package("tool", "tools/pyproject.toml", Language::Python),
+        ];
+        let refs: Vec<&Project> = projects.iter().collect();
+
+        let groups = group_by_workspace(&refs);
+        let summary: Vec<(String, Vec<String>)> = groups
+            .iter()
+            .map(|(project, members)| {
+                (
+                    project.name().unwrap_or_default().to_string(),
+                    project_names(members),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("root".to_string(), vec!["ui".to_string()]),
+                ("nested".to_string(), vec!["web".to_string()]),
+                ("cargo".to_string(), vec!["core".to_string()]),
+                ("tool".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_workspace_member_globs() {
+        let mut root = MockWorkspaceForCheck::new(
+            Some("root"),
+            Some("1.0.0"),
+            "/repo/package.json",
+            "package.json",
+            Language::Node,
+        );
+        root.member_patterns = Some(vec!["packages/*".to_string()]);
+        let package = |name: &str, relative_path: &str| {
+            Project::Package(Box::new(MockPackageForCheck::new(
+                Some(name),
+                Some("1.0.0"),
+                &format!("/repo/{relative_path}"),
+                relative_path,
+                Language::Node,
+            )))
+        };
+        let projects = [
+            Project::Workspace(Box::new(root)),
+            package("ui", "packages/ui/package.json"),
+            package("example", "examples/demo/package.json"),
+        ];
+        let refs: Vec<&Project> = projects.iter().collect();
+
+        let groups = group_by_workspace(&refs);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(project_names(&groups[0].1), ["ui"]);
+        assert_eq!(groups[1].0.name(), Some("example"));
+
+        let orphans = format_orphans(&refs, &refs);
+        assert_eq!(orphans.lines().count(), 1);
+        assert!(orphans.contains(
+            "example (examples/demo/package.json) is inside workspace root (package.json) but not one of its members"
+        ));
+
+        let path_to_project: HashMap<String, &Project> = refs
+            .iter()
+            .map(|project| (project.name().unwrap().to_string(), *project))
+            .collect();
+        for direction in [TreeDirection::Dependencies, TreeDirection::Dependents] {
+            let (graph, roots) = build_tree_graph(&refs, &path_to_project, direction);
+            assert_eq!(roots, ["example", "root"]);
+            assert_eq!(graph["root"], ["ui"]);
+        }
+    }
+
     #[test]
     fn test_format_project_line_package() {
         let pkg = MockPackageForCheck::new(
@@ -616,7 +1454,8 @@ mod tests {
         let mut path_to_project: HashMap<String, &Project> = HashMap::new();
         path_to_project.insert("my-lib".to_string(), &project);
 
-        let line = format_project_line(&project, repo_root, &update_map, &path_to_project).unwrap();
+        let line =
+            format_project_line(&project, repo_root, &update_map, None, &path_to_project).unwrap();
         assert!(line.contains("my-lib"));
         assert!(line.contains("v1.2.3"));
     }
@@ -636,7 +1475,8 @@ mod tests {
         let mut path_to_project: HashMap<String, &Project> = HashMap::new();
         path_to_project.insert("my-workspace".to_string(), &project);
 
-        let line = format_project_line(&project, repo_root, &update_map, &path_to_project).unwrap();
+        let line =
+            format_project_line(&project, repo_root, &update_map, None, &path_to_project).unwrap();
         assert!(line.contains("my-workspace"));
         assert!(line.contains("Workspace"));
         assert!(line.contains("v2.0.0"));
@@ -660,7 +1500,8 @@ mod tests {
         );
         let path_to_project: HashMap<String, &Project> = HashMap::new();
 
-        let line = format_project_line(&project, repo_root, &update_map, &path_to_project).unwrap();
+        let line =
+            format_project_line(&project, repo_root, &update_map, None, &path_to_project).unwrap();
         assert!(line.contains("updated-pkg"));
         // The update display should show version transition
         assert!(line.contains("1.1.0") || line.contains("1.0.0"));
@@ -681,7 +1522,8 @@ mod tests {
         let update_map = HashMap::new();
         let path_to_project: HashMap<String, &Project> = HashMap::new();
 
-        let line = format_project_line(&project, repo_root, &update_map, &path_to_project).unwrap();
+        let line =
+            format_project_line(&project, repo_root, &update_map, None, &path_to_project).unwrap();
         assert!(line.contains("changed-pkg"));
         assert!(line.contains("changed"));
     }
@@ -713,7 +1555,8 @@ mod tests {
         path_to_project.insert("app".to_string(), &project);
         path_to_project.insert("core-lib".to_string(), &dep_project);
 
-        let line = format_project_line(&project, repo_root, &update_map, &path_to_project).unwrap();
+        let line =
+            format_project_line(&project, repo_root, &update_map, None, &path_to_project).unwrap();
         assert!(line.contains("app"));
         assert!(line.contains("deps:"));
         assert!(line.contains("core-lib"));
@@ -733,8 +1576,202 @@ mod tests {
         let update_map = HashMap::new();
         let path_to_project: HashMap<String, &Project> = HashMap::new();
 
-        let line = format_project_line(&project, repo_root, &update_map, &path_to_project).unwrap();
+        let line =
+            format_project_line(&project, repo_root, &update_map, None, &path_to_project).unwrap();
         assert!(line.contains("standalone"));
         assert!(!line.contains("deps:"));
     }
+
+    #[test]
+    fn test_check_args_registry() {
+        assert!(!TestCli::parse_from(["test"]).check.registry);
+        assert!(TestCli::parse_from(["test", "--registry"]).check.registry);
+        assert!(TestCli::try_parse_from(["test", "--registry", "--tree"]).is_err());
+    }
+
+    /// Serve one response with `status` and `body`, returning the base URL
+    fn serve(status: u16, body: &'static str) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_registry_drift() {
+        let package = |version: Option<&str>, language| {
+            Project::Package(Box::new(MockPackageForCheck::new(
+                Some("pkg"),
+                version,
+                "/repo/pkg/package.json",
+                "pkg/package.json",
+                language,
+            )))
+        };
+
+        let project = package(Some("1.1.0"), Language::Node);
+        let url = serve(200, r#"{"dist-tags":{"latest":"1.0.0"}}"#);
+        let drift = registry_drift(&[&project], |registry| {
+            RegistryClient::with_base_url(registry, &url)
+        })
+        .await;
+        assert_eq!(
+            drift,
+            [RegistryDrift {
+                name: "pkg".to_string(),
+                path: PathBuf::from("pkg/package.json"),
+                registry: "npm".to_string(),
+                local: "1.1.0".to_string(),
+                published: Some("1.0.0".to_string()),
+                drift: true,
+            }]
+        );
+
+        let url = serve(404, "Not Found");
+        let drift = registry_drift(&[&project], |registry| {
+            RegistryClient::with_base_url(registry, &url)
+        })
+        .await;
+        assert_eq!(drift[0].published, None);
+        assert!(drift[0].drift);
+
+        let url = serve(500, "oops");
+        let drift = registry_drift(&[&project], |registry| {
+            RegistryClient::with_base_url(registry, &url)
+        })
+        .await;
+        assert!(drift.is_empty());
+
+        // No version or no supported registry: nothing to look up
+        let unversioned = package(None, Language::Node);
+        let maven = package(Some("1.0.0"), Language::Java);
+        let drift = registry_drift(&[&unversioned, &maven], |_| unreachable!()).await;
+        assert!(drift.is_empty());
+    }
+
+    #[test]
+    fn test_format_registry_drift() {
+        assert_eq!(
+            format_registry_drift(&[]),
+            "No packages with a supported registry\n"
+        );
+        let entry = |published: Option<&str>| RegistryDrift {
+            name: "core".to_string(),
+            path: PathBuf::from("crates/core/Cargo.toml"),
+            registry: "crates.io".to_string(),
+            local: "1.1.0".to_string(),
+            drift: published != Some("1.1.0"),
+            published: published.map(str::to_string),
+        };
+        let output =
+            format_registry_drift(&[entry(Some("1.1.0")), entry(Some("1.0.0")), entry(None)]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[0],
+            "core (crates/core/Cargo.toml) v1.1.0 local, v1.1.0 on crates.io"
+        );
+        assert!(
+            lines[1].starts_with("core (crates/core/Cargo.toml) v1.1.0 local, v1.0.0 on crates.io")
+        );
+        assert!(lines[1].contains("(drift)"));
+        assert!(lines[2].contains("unpublished on crates.io"));
+        assert!(lines[2].contains("(drift)"));
+    }
+
+    #[test]
+    fn test_registry_drift_annotations() {
+        let entry = |published: Option<&str>| RegistryDrift {
+            name: "core".to_string(),
+            path: PathBuf::from("crates/core/Cargo.toml"),
+            registry: "crates.io".to_string(),
+            local: "1.1.0".to_string(),
+            drift: published != Some("1.1.0"),
+            published: published.map(str::to_string),
+        };
+        assert_eq!(
+            registry_drift_annotations(&[entry(Some("1.1.0")), entry(Some("1.0.0")), entry(None)]),
+            "::warning file=crates/core/Cargo.toml,title=core drifted from crates.io::Local version is v1.1.0 but crates.io has v1.0.0\n\
+             ::warning file=crates/core/Cargo.toml,title=core drifted from crates.io::Local version is v1.1.0 but crates.io has nothing\n"
+        );
+    }
+
+    fn project_status(
+        name: &str,
+        coverage: super::super::status::Coverage,
+        update_type: Option<UpdateType>,
+    ) -> super::super::status::ProjectStatus {
+        super::super::status::ProjectStatus {
+            name: Some(name.to_string()),
+            path: PathBuf::from(format!("{name}/package.json")),
+            version: Some("1.2.0".to_string()),
+            coverage,
+            update_type,
+        }
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        use super::super::status::Coverage;
+        let statuses = [
+            project_status("core", Coverage::Pending, Some(UpdateType::Minor)),
+            project_status("app", Coverage::Pending, Some(UpdateType::Patch)),
+            project_status("docs", Coverage::Released, None),
+            project_status("web", Coverage::Missing, None),
+        ];
+        let changepacks = |path: &Path| usize::from(path == Path::new("core/package.json")) * 2;
+        let output = github_annotations(&statuses, None, &[], &[], false, changepacks).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "::notice file=core/package.json,title=core 1.2.0 -> 1.3.0::minor bump from 2 changepacks",
+                "::notice file=app/package.json,title=app 1.2.0 -> 1.2.1::patch bump",
+                "::warning file=web/package.json,title=web has no changepack::Changed since the base branch without a pending changepack; run `changepacks` to record one",
+            ]
+        );
+
+        let stale = [super::super::doctor::StaleChangepack::Expired {
+            path: PathBuf::from(".changepacks/changepack_log_a.json"),
+            days: 90,
+        }];
+        let output = github_annotations(&statuses[3..], None, &[], &stale, true, |_| 0).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("::error file=web/package.json,"));
+        assert_eq!(
+            lines[1],
+            "::warning file=.changepacks/changepack_log_a.json,title=Stale changepack::.changepacks/changepack_log_a.json was written 90 days ago"
+        );
+    }
+
+    #[test]
+    fn test_github_summary() {
+        use super::super::status::Coverage;
+        assert_eq!(
+            github_summary(&[], None, |_| 0).unwrap(),
+            "### changepacks check\n\nNo changed projects.\n\n"
+        );
+        let statuses = [
+            project_status("core", Coverage::Pending, Some(UpdateType::Major)),
+            project_status("web", Coverage::Missing, None),
+        ];
+        assert_eq!(
+            github_summary(&statuses, None, |_| 1).unwrap(),
+            "### changepacks check\n\n\
+             | Package | Path | Version | Bump | Changepacks |\n\
+             | --- | --- | --- | --- | ---: |\n\
+             | core | `core/package.json` | 1.2.0 -> 2.0.0 | major | 1 |\n\
+             | web | `web/package.json` | 1.2.0 | **missing changepack** | 1 |\n\n"
+        );
+    }
 }
@@ -1,22 +1,183 @@
-use anyhow::Result;
-use changepacks_utils::get_changepacks_config;
-use clap::Args;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use changepacks_core::{ConfigFile, ConfigFileFormat};
+use changepacks_utils::{
+    ConfigIssue, config_pointer, get_changepacks_config, get_config_dir, read_file,
+    set_config_setting, validate_config, write_file,
+};
+use clap::{Args, Subcommand};
+use serde_json::Value;
+
+use crate::GlobalOptions;
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Change changepacks configuration",
+    args_conflicts_with_subcommands = true
+)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: Option<ConfigAction>,
+
+    /// Validate the config file and report every problem instead of printing the config
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print one setting of the loaded config
+    Get(ConfigGetArgs),
+    /// Change one setting in the config file
+    Set(ConfigSetArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ConfigGetArgs {
+    /// Dotted path (e.g., `publish.node`) or JSON pointer (e.g., `/publish/node`) of the setting
+    pub key: String,
+}
 
 #[derive(Args, Debug)]
-#[command(about = "Change changepacks configuration")]
-pub struct ConfigArgs {}
+pub struct ConfigSetArgs {
+    /// Dotted path (e.g., `publish.node`) or JSON pointer (e.g., `/ignore/-` to append)
+    pub key: String,
+
+    /// New value as JSON (e.g., `false`, `["docs/**"]`); anything else is taken as a string
+    pub value: String,
+}
 
 /// Display changepacks configuration
 ///
 /// # Errors
-/// Returns error if reading the configuration fails.
-pub async fn handle_config(_args: &ConfigArgs) -> Result<()> {
-    let current_dir = std::env::current_dir()?;
+/// Returns error if reading the configuration fails, or with `--check`, if it has problems.
+pub async fn handle_config(args: &ConfigArgs, global: &GlobalOptions) -> Result<()> {
+    let current_dir = global.current_dir()?;
+    match &args.action {
+        Some(ConfigAction::Get(get)) => return get_setting(&current_dir, &get.key).await,
+        Some(ConfigAction::Set(set)) => {
+            return set_setting(&current_dir, &set.key, &set.value).await;
+        }
+        None => {}
+    }
+    if args.check {
+        return check_config(&current_dir).await;
+    }
     let config = get_changepacks_config(&current_dir).await?;
     println!("{}", serde_json::to_string_pretty(&config)?);
     Ok(())
 }
 
+/// Print the setting at `key` of the loaded config, strings without quotes
+async fn get_setting(current_dir: &Path, key: &str) -> Result<()> {
+    let config = serde_json::to_value(get_changepacks_config(current_dir).await?)?;
+    let setting = config
+        .pointer(&config_pointer(key))
+        .with_context(|| format!("No setting `{key}` in the config"))?;
+    match setting {
+        Value::String(setting) => println!("{setting}"),
+        setting => println!("{}", serde_json::to_string_pretty(setting)?),
+    }
+    Ok(())
+}
+
+/// Write `value` to the setting at `key` in the config file, creating `config.json` when
+/// there is none
+async fn set_setting(current_dir: &Path, key: &str, value: &str) -> Result<()> {
+    let config_dir = get_config_dir(current_dir)?;
+    let (path, format, content) = match ConfigFile::find(&config_dir)? {
+        Some(config_file) => {
+            let content = read_file(config_file.path()).await?;
+            (
+                config_file.path().to_path_buf(),
+                config_file.format(),
+                content,
+            )
+        }
+        None => {
+            tokio::fs::create_dir_all(&config_dir).await?;
+            (
+                config_dir.join("config.json"),
+                ConfigFileFormat::Json,
+                String::new(),
+            )
+        }
+    };
+    let value = parse_setting_value(value);
+    let (settings, updated) = set_config_setting(format, &content, &config_pointer(key), value)?;
+
+    // Refuse settings that would break the config, but not problems it already had
+    let existing = match format.parse(&content) {
+        Ok(previous) => validate_config(&previous, &content),
+        Err(_) => Vec::new(),
+    };
+    let introduced: Vec<ConfigIssue> = validate_config(&settings, &updated)
+        .into_iter()
+        .filter(|issue| {
+            !existing
+                .iter()
+                .any(|old| old.field == issue.field && old.message == issue.message)
+        })
+        .collect();
+    if !introduced.is_empty() {
+        let problems: Vec<String> = introduced.iter().map(ToString::to_string).collect();
+        anyhow::bail!("Not setting `{key}`: {}", problems.join("; "));
+    }
+    write_file(&path, updated).await?;
+    println!(
+        "Set `{key}` in {}",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    );
+    Ok(())
+}
+
+/// JSON value of a `config set` argument, or the argument itself as a string
+fn parse_setting_value(value: &str) -> Value {
+    serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()))
+}
+
+/// Report every problem of the config file, then make sure the full config (with any
+/// `extends` base) loads
+async fn check_config(current_dir: &Path) -> Result<()> {
+    let Some(config_file) = ConfigFile::find(&get_config_dir(current_dir)?)? else {
+        println!("No config file found, defaults apply");
+        return Ok(());
+    };
+    let issues = config_issues(&config_file)?;
+    if !issues.is_empty() {
+        for issue in &issues {
+            println!("{}: {issue}", config_file.name());
+        }
+        anyhow::bail!(
+            "Found {} problem(s) in {}",
+            issues.len(),
+            config_file.name()
+        );
+    }
+    get_changepacks_config(current_dir).await?;
+    println!("{} is valid", config_file.name());
+    Ok(())
+}
+
+/// Problems of `config_file`; a syntax error is the only problem reported for a file that
+/// does not parse
+fn config_issues(config_file: &ConfigFile) -> Result<Vec<ConfigIssue>> {
+    let content = std::fs::read_to_string(config_file.path())
+        .with_context(|| format!("Failed to read {}", config_file.name()))?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(match config_file.format().parse(&content) {
+        Ok(value) => validate_config(&value, &content),
+        Err(err) => vec![ConfigIssue {
+            field: String::new(),
+            line: None,
+            message: format!("invalid syntax: {}", err.to_string().trim_end()),
+        }],
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,13 +191,65 @@ mod tests {
 
     #[test]
     fn test_config_args_parsing() {
-        // ConfigArgs has no arguments, just verify it parses
-        let _cli = TestCli::parse_from(["test"]);
+        let cli = TestCli::parse_from(["test"]);
+        assert!(!cli.config.check);
+        let cli = TestCli::parse_from(["test", "--check"]);
+        assert!(cli.config.check);
+    }
+
+    #[test]
+    fn test_config_action_parsing() {
+        let cli = TestCli::parse_from(["test", "set", "publish.node", "pnpm publish"]);
+        assert!(matches!(
+            cli.config.action,
+            Some(ConfigAction::Set(ConfigSetArgs { ref key, ref value }))
+                if key == "publish.node" && value == "pnpm publish"
+        ));
+        let cli = TestCli::parse_from(["test", "get", "ignore"]);
+        assert!(matches!(cli.config.action, Some(ConfigAction::Get(_))));
+        assert!(TestCli::try_parse_from(["test", "--check", "get", "ignore"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_setting_value() {
+        assert_eq!(parse_setting_value("false"), Value::Bool(false));
+        assert_eq!(
+            parse_setting_value("[\"docs/**\"]"),
+            serde_json::json!(["docs/**"])
+        );
+        assert_eq!(
+            parse_setting_value("pnpm publish"),
+            Value::String("pnpm publish".to_string())
+        );
+    }
+
+    #[test]
+    fn test_config_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("config.yaml"),
+            "baseBranch: main\nignoer:\n  - docs/**\n",
+        )
+        .unwrap();
+        let config_file = ConfigFile::find(dir.path()).unwrap().unwrap();
+        let issues = config_issues(&config_file).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].to_string(), "line 2: `ignoer`: unknown setting");
+
+        std::fs::write(dir.path().join("config.yaml"), "baseBranch: [main\n").unwrap();
+        let issues = config_issues(&config_file).unwrap();
+        assert!(
+            issues[0].message.starts_with("invalid syntax"),
+            "{issues:?}"
+        );
     }
 
     #[test]
     fn test_config_args_debug() {
-        let args = ConfigArgs {};
+        let args = ConfigArgs {
+            action: None,
+            check: false,
+        };
         let debug_str = format!("{:?}", args);
         assert!(debug_str.contains("ConfigArgs"));
     }
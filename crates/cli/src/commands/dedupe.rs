@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use changepacks_core::ChangePackLog;
+use changepacks_utils::{get_changepacks_dir, read_changepack_logs};
+use clap::Args;
+
+use crate::GlobalOptions;
+
+#[derive(Args, Debug)]
+#[command(about = "Remove duplicate changepack logs, e.g. after merging branches")]
+pub struct DedupeArgs {
+    /// List the duplicates without removing them
+    #[arg(short, long)]
+    pub dry_run: bool,
+}
+
+/// Remove changepack logs that repeat an older log's changes, note and fields
+///
+/// # Errors
+/// Returns error if the changepack logs cannot be read or removed.
+pub async fn handle_dedupe(args: &DedupeArgs, global: &GlobalOptions) -> Result<()> {
+    let changepacks_dir = get_changepacks_dir(&global.current_dir()?)?;
+    let duplicates = duplicate_logs(&read_changepack_logs(&changepacks_dir).await?);
+    if duplicates.is_empty() {
+        println!("No duplicate changepacks");
+        return Ok(());
+    }
+    for path in &duplicates {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if args.dry_run {
+            println!("Would remove {name}");
+        } else {
+            tokio::fs::remove_file(path).await?;
+            println!("Removed {name}");
+        }
+    }
+    Ok(())
+}
+
+/// Paths of logs identical to an earlier log in `logs`; the earliest copy is kept
+fn duplicate_logs(logs: &[(PathBuf, ChangePackLog)]) -> Vec<PathBuf> {
+    let same = |a: &ChangePackLog, b: &ChangePackLog| {
+        a.changes() == b.changes() && a.note().trim() == b.note().trim() && a.fields() == b.fields()
+    };
+    logs.iter()
+        .enumerate()
+        .filter(|(index, (_, log))| logs[..*index].iter().any(|(_, kept)| same(kept, log)))
+        .map(|(_, (path, _))| path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::UpdateType;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn test_duplicate_logs() {
+        let log = |update_type, note: &str| {
+            ChangePackLog::new(
+                HashMap::from([(PathBuf::from("core/package.json"), update_type)]),
+                note.to_string(),
+            )
+        };
+        let logs = vec![
+            (PathBuf::from("a.json"), log(UpdateType::Patch, "Fix crash")),
+            (
+                PathBuf::from("b.json"),
+                log(UpdateType::Patch, "Fix crash\n"),
+            ),
+            (PathBuf::from("c.json"), log(UpdateType::Minor, "Fix crash")),
+            (
+                PathBuf::from("d.json"),
+                log(UpdateType::Patch, "Fix crash")
+                    .with_fields(BTreeMap::from([("issue".to_string(), "1".to_string())])),
+            ),
+            (PathBuf::from("e.json"), log(UpdateType::Patch, "Fix crash")),
+        ];
+
+        assert_eq!(
+            duplicate_logs(&logs),
+            vec![PathBuf::from("b.json"), PathBuf::from("e.json")]
+        );
+        assert!(duplicate_logs(&logs[..1]).is_empty());
+    }
+}
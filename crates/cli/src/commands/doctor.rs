@@ -0,0 +1,501 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use changepacks_core::{ChangePackLog, Config, Project, path_key};
+use changepacks_utils::{get_changepacks_dir, get_relative_path, read_changepack_logs};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{CommandContext, GlobalOptions, options::FormatOptions};
+
+#[derive(Args, Debug)]
+#[command(about = "Diagnose repository setup problems such as inconsistent versions")]
+pub struct DoctorArgs {
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+
+    /// Move stale changepacks into `.changepacks/archive` so `update` no longer applies them
+    #[arg(long)]
+    pub archive_stale: bool,
+}
+
+/// Version of one member of a `fixed` group
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub(super) struct MemberVersion {
+    path: PathBuf,
+    version: String,
+}
+
+/// A version declared in two places that disagree
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub(super) enum VersionIssue {
+    /// Members of a `fixed` (lockstep) group are on different versions
+    FixedGroup { versions: Vec<MemberVersion> },
+    /// Another file declares a different version than the project manifest
+    Duplicate {
+        path: PathBuf,
+        version: String,
+        declared_in: PathBuf,
+        declared_version: String,
+    },
+}
+
+impl std::fmt::Display for VersionIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FixedGroup { versions } => {
+                let versions: Vec<String> = versions
+                    .iter()
+                    .map(|member| format!("{} {}", path_key(&member.path), member.version))
+                    .collect();
+                write!(f, "fixed group is out of step: {}", versions.join(", "))
+            }
+            Self::Duplicate {
+                path,
+                version,
+                declared_in,
+                declared_version,
+            } => write!(
+                f,
+                "{} declares {version} but {} declares {declared_version}",
+                path_key(path),
+                path_key(declared_in)
+            ),
+        }
+    }
+}
+
+/// A pending changepack that probably should not be applied as is
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub(super) enum StaleChangepack {
+    /// Older than the configured `staleChangepackDays`
+    Expired { path: PathBuf, days: i64 },
+    /// Names packages that no longer exist, e.g. after a delete or rename
+    MissingPackages {
+        path: PathBuf,
+        packages: Vec<PathBuf>,
+    },
+}
+
+impl StaleChangepack {
+    pub(super) fn path(&self) -> &Path {
+        match self {
+            Self::Expired { path, .. } | Self::MissingPackages { path, .. } => path,
+        }
+    }
+}
+
+impl std::fmt::Display for StaleChangepack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expired { path, days } => {
+                write!(f, "{} was written {days} days ago", path_key(path))
+            }
+            Self::MissingPackages { path, packages } => {
+                let packages: Vec<String> = packages.iter().map(|path| path_key(path)).collect();
+                write!(
+                    f,
+                    "{} names missing packages: {}",
+                    path_key(path),
+                    packages.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// Diagnose repository setup problems
+///
+/// # Errors
+/// Returns error if project discovery fails, a version declaration cannot be read, or any
+/// problem is found.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new`; the lint itself
+/// is unit tested and the command is covered by the cli integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_doctor(args: &DoctorArgs, global: &GlobalOptions) -> Result<()> {
+    let ctx = CommandContext::new(global, false).await?;
+    let mut projects: Vec<&Project> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .collect();
+    projects.sort();
+    let issues = version_issues(&projects, &ctx.config, &ctx.repo_root_path).await?;
+    let changepacks_dir = get_changepacks_dir(&ctx.repo_root_path)?;
+    let stale = stale_changepacks(
+        &read_changepack_logs(&changepacks_dir).await?,
+        &projects,
+        &ctx.config,
+        &ctx.repo_root_path,
+        Utc::now(),
+    );
+
+    match args.format {
+        FormatOptions::Stdout if issues.is_empty() && stale.is_empty() => {
+            println!("No problems found");
+        }
+        FormatOptions::Stdout => {
+            if !issues.is_empty() {
+                print!("{}", format_version_issues(&issues));
+            }
+            if !stale.is_empty() {
+                print!("{}", format_stale_changepacks(&stale, !args.archive_stale));
+            }
+        }
+        FormatOptions::Json => {
+            let mut problems = serde_json::to_value(&issues)?;
+            if let (Some(problems), serde_json::Value::Array(stale)) =
+                (problems.as_array_mut(), serde_json::to_value(&stale)?)
+            {
+                problems.extend(stale);
+            }
+            println!("{}", serde_json::to_string_pretty(&problems)?);
+        }
+    }
+    if args.archive_stale && !stale.is_empty() {
+        let archive_dir = changepacks_dir.join("archive");
+        tokio::fs::create_dir_all(&archive_dir).await?;
+        let mut archived: Vec<&Path> = stale.iter().map(StaleChangepack::path).collect();
+        archived.dedup();
+        for path in &archived {
+            let from = ctx.repo_root_path.join(path);
+            if let Some(name) = from.file_name() {
+                tokio::fs::rename(&from, archive_dir.join(name)).await?;
+            }
+        }
+        eprintln!(
+            "Archived {} stale changepack(s) to {}",
+            archived.len(),
+            path_key(&relative_to(&ctx.repo_root_path, &archive_dir))
+        );
+    }
+    if !issues.is_empty() {
+        anyhow::bail!("Found {} version consistency problem(s)", issues.len());
+    }
+    Ok(())
+}
+
+/// Versions that should agree but don't
+///
+/// Flags `fixed` groups whose members are on different versions, and projects whose
+/// version is declared again elsewhere (a Python `__version__`, a C# `Directory.Build.props`)
+/// with a different value.
+///
+/// # Errors
+/// Returns error if a version declaration exists but cannot be read.
+pub(super) async fn version_issues(
+    projects: &[&Project],
+    config: &Config,
+    repo_root_path: &Path,
+) -> Result<Vec<VersionIssue>> {
+    let relative = |path: &Path| relative_to(repo_root_path, path);
+    let mut issues = Vec::new();
+
+    for group in &config.fixed {
+        let versions: Vec<MemberVersion> = group
+            .iter()
+            .filter_map(|member| {
+                projects
+                    .iter()
+                    .find(|project| {
+                        path_key(project.relative_path()) == path_key(Path::new(member))
+                    })
+                    .and_then(|project| {
+                        Some(MemberVersion {
+                            path: project.relative_path().to_path_buf(),
+                            version: project.version()?.to_string(),
+                        })
+                    })
+            })
+            .collect();
+        if versions
+            .iter()
+            .any(|member| member.version != versions[0].version)
+        {
+            issues.push(VersionIssue::FixedGroup { versions });
+        }
+    }
+
+    for project in projects {
+        let Some(version) = project.version() else {
+            continue;
+        };
+        for (declared_in, declared_version) in project.version_declarations().await? {
+            if declared_version != version {
+                issues.push(VersionIssue::Duplicate {
+                    path: project.relative_path().to_path_buf(),
+                    version: version.to_string(),
+                    declared_in: relative(&declared_in),
+                    declared_version,
+                });
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Pending changepacks older than `staleChangepackDays` or naming packages that are gone
+///
+/// A changepack naming both missing and existing packages is still reported, since applying
+/// it would silently drop part of the change.
+pub(super) fn stale_changepacks(
+    logs: &[(PathBuf, ChangePackLog)],
+    projects: &[&Project],
+    config: &Config,
+    repo_root_path: &Path,
+    now: DateTime<Utc>,
+) -> Vec<StaleChangepack> {
+    let known: Vec<String> = projects
+        .iter()
+        .map(|project| path_key(project.relative_path()))
+        .collect();
+    let mut stale = Vec::new();
+    for (path, log) in logs {
+        let path = relative_to(repo_root_path, path);
+        let mut missing: Vec<PathBuf> = log
+            .changes()
+            .keys()
+            .filter(|package| !known.contains(&path_key(package)))
+            .cloned()
+            .collect();
+        missing.sort();
+        if !missing.is_empty() {
+            stale.push(StaleChangepack::MissingPackages {
+                path: path.clone(),
+                packages: missing,
+            });
+        }
+        let days = (now - *log.date()).num_days();
+        if config
+            .stale_changepack_days
+            .is_some_and(|limit| days > i64::try_from(limit).unwrap_or(i64::MAX))
+        {
+            stale.push(StaleChangepack::Expired { path, days });
+        }
+    }
+    stale
+}
+
+fn relative_to(repo_root_path: &Path, path: &Path) -> PathBuf {
+    get_relative_path(repo_root_path, path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Warnings for stale changepacks, followed by how to archive them when `archive_hint` is set
+pub(super) fn format_stale_changepacks(stale: &[StaleChangepack], archive_hint: bool) -> String {
+    let mut out = format!("{}\n", "Stale changepacks:".bold());
+    for changepack in stale {
+        out.push_str(&format!("  {} {changepack}\n", "warning:".yellow()));
+    }
+    if archive_hint {
+        out.push_str("  Run `changepacks doctor --archive-stale` to archive them\n");
+    }
+    out
+}
+
+pub(super) fn format_version_issues(issues: &[VersionIssue]) -> String {
+    let mut out = format!("{}\n", "Version consistency:".bold());
+    for issue in issues {
+        out.push_str(&format!("  {} {issue}\n", "warning:".yellow()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_node::package::NodePackage;
+    use changepacks_python::package::PythonPackage;
+    use tempfile::TempDir;
+
+    fn node(name: &str, version: &str) -> Project {
+        Project::Package(Box::new(NodePackage::new(
+            Some(name.to_string()),
+            Some(version.to_string()),
+            PathBuf::from(format!("/nonexistent/{name}/package.json")),
+            PathBuf::from(format!("{name}/package.json")),
+        )))
+    }
+
+    fn member(path: &str, version: &str) -> MemberVersion {
+        MemberVersion {
+            path: PathBuf::from(path),
+            version: version.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_version_issues_fixed_group() {
+        let a = node("a", "1.2.0");
+        let b = node("b", "1.2.0");
+        let c = node("c", "1.3.0");
+        let config = Config {
+            fixed: vec![
+                vec!["a/package.json".to_string(), "b/package.json".to_string()],
+                vec!["a/package.json".to_string(), "c/package.json".to_string()],
+            ],
+            ..Config::default()
+        };
+        let issues = version_issues(&[&a, &b, &c], &config, Path::new("/nonexistent"))
+            .await
+            .unwrap();
+        assert_eq!(
+            issues,
+            [VersionIssue::FixedGroup {
+                versions: vec![
+                    member("a/package.json", "1.2.0"),
+                    member("c/package.json", "1.3.0")
+                ]
+            }]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "fixed group is out of step: a/package.json 1.2.0, c/package.json 1.3.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version_issues_duplicate_declaration() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("lib/src/lib")).unwrap();
+        std::fs::write(root.join("lib/pyproject.toml"), "").unwrap();
+        std::fs::write(
+            root.join("lib/src/lib/__init__.py"),
+            "__version__ = \"0.9.0\"\n",
+        )
+        .unwrap();
+        let project = |version: &str| {
+            Project::Package(Box::new(PythonPackage::new(
+                Some("lib".to_string()),
+                Some(version.to_string()),
+                root.join("lib/pyproject.toml"),
+                PathBuf::from("lib/pyproject.toml"),
+            )))
+        };
+
+        let matching = project("0.9.0");
+        assert!(
+            version_issues(&[&matching], &Config::default(), root)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let drifted = project("1.0.0");
+        let issues = version_issues(&[&drifted], &Config::default(), root)
+            .await
+            .unwrap();
+        assert_eq!(
+            issues,
+            [VersionIssue::Duplicate {
+                path: PathBuf::from("lib/pyproject.toml"),
+                version: "1.0.0".to_string(),
+                declared_in: PathBuf::from("lib/src/lib/__init__.py"),
+                declared_version: "0.9.0".to_string(),
+            }]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "lib/pyproject.toml declares 1.0.0 but lib/src/lib/__init__.py declares 0.9.0"
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_stale_changepacks() {
+        let web = node("web", "1.0.0");
+        let log = |packages: &[&str], json_date: &str| {
+            let changes: serde_json::Map<String, serde_json::Value> = packages
+                .iter()
+                .map(|package| (package.to_string(), "Patch".into()))
+                .collect();
+            serde_json::from_value::<ChangePackLog>(serde_json::json!({
+                "changes": changes,
+                "note": "Fix",
+                "date": json_date,
+            }))
+            .unwrap()
+        };
+        let logs = vec![
+            (
+                PathBuf::from("/repo/.changepacks/changepack_log_a.json"),
+                log(&["web/package.json"], "2025-01-01T00:00:00Z"),
+            ),
+            (
+                PathBuf::from("/repo/.changepacks/changepack_log_b.json"),
+                log(
+                    &["web/package.json", "old/package.json"],
+                    "2025-03-01T00:00:00Z",
+                ),
+            ),
+        ];
+        let now = DateTime::parse_from_rfc3339("2025-03-11T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            stale_changepacks(&logs, &[&web], &Config::default(), Path::new("/repo"), now),
+            [StaleChangepack::MissingPackages {
+                path: PathBuf::from(".changepacks/changepack_log_b.json"),
+                packages: vec![PathBuf::from("old/package.json")],
+            }]
+        );
+
+        let config = Config {
+            stale_changepack_days: Some(30),
+            ..Config::default()
+        };
+        let stale = stale_changepacks(&logs, &[&web], &config, Path::new("/repo"), now);
+        assert_eq!(
+            stale[0],
+            StaleChangepack::Expired {
+                path: PathBuf::from(".changepacks/changepack_log_a.json"),
+                days: 69,
+            }
+        );
+        assert_eq!(stale.len(), 2);
+        assert_eq!(
+            stale[0].to_string(),
+            ".changepacks/changepack_log_a.json was written 69 days ago"
+        );
+        assert_eq!(
+            stale[1].to_string(),
+            ".changepacks/changepack_log_b.json names missing packages: old/package.json"
+        );
+    }
+
+    #[test]
+    fn test_format_stale_changepacks() {
+        let stale = [StaleChangepack::Expired {
+            path: PathBuf::from(".changepacks/changepack_log_a.json"),
+            days: 100,
+        }];
+        let output = format_stale_changepacks(&stale, true);
+        assert!(output.contains("Stale changepacks:"));
+        assert!(output.contains(".changepacks/changepack_log_a.json was written 100 days ago"));
+        assert!(output.contains("changepacks doctor --archive-stale"));
+        // Already archiving: no hint to do what was just asked for
+        assert!(!format_stale_changepacks(&stale, false).contains("--archive-stale"));
+    }
+
+    #[test]
+    fn test_format_version_issues() {
+        let output = format_version_issues(&[VersionIssue::FixedGroup {
+            versions: vec![
+                member("a/package.json", "1.0.0"),
+                member("b/package.json", "2.0.0"),
+            ],
+        }]);
+        assert!(output.contains("Version consistency:"));
+        assert!(
+            output
+                .contains("fixed group is out of step: a/package.json 1.0.0, b/package.json 2.0.0")
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+use changepacks_core::{ChangePackLog, ChangepackFormat, Project, UpdateType, path_key};
+use changepacks_utils::{
+    changepack_log_file, fetch_pull_request, get_changepacks_dir, get_relative_path,
+    render_changepack_log,
+};
+use clap::Args;
+use serde_json::Value;
+
+use super::release_pr::github_repo;
+use crate::{CommandContext, GlobalOptions};
+
+#[derive(Args, Debug)]
+#[command(about = "Write a changepack from the `changepacks` block of a pull request description")]
+pub struct FromPrArgs {
+    /// Pull request number (default: the pull request of the current GitHub Actions run)
+    #[arg(long)]
+    pub pr: Option<u64>,
+
+    /// GitHub repository as `owner/name` (default: `GITHUB_REPOSITORY`, then the `origin` remote)
+    #[arg(long)]
+    pub repo: Option<String>,
+
+    /// Print the changepack instead of writing it
+    #[arg(short, long)]
+    pub dry_run: bool,
+}
+
+/// Bumps declared in a pull request description
+#[derive(Debug, PartialEq, Eq)]
+struct PrIntent {
+    /// Project name or manifest path, as written, with its update type
+    changes: Vec<(String, UpdateType)>,
+    /// Text after the bump lines; the pull request title is used when empty
+    note: Option<String>,
+}
+
+/// Fetch the pull request description and turn its `changepacks` block into a changepack log
+///
+/// The log is written as `changepack_log_pr-<number>.json` (`.md` with the `markdown`
+/// `changepackFormat`), so re-running for the same pull request replaces it. Requires
+/// `GITHUB_TOKEN` (or `GH_TOKEN`); `GITHUB_API_URL` points it at GitHub Enterprise Server.
+///
+/// # Errors
+/// Returns error if the pull request cannot be determined or fetched, its block is malformed,
+/// or it names an unknown project.
+///
+/// Excluded from coverage: calls the GitHub API; the block parsing, project resolution and
+/// event parsing are covered by their own tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_from_pr(args: &FromPrArgs, global: &GlobalOptions) -> Result<()> {
+    let number = match args.pr {
+        Some(number) => number,
+        None => pull_request_number_from_env()
+            .context("No pull request in the GitHub Actions environment; pass --pr <number>")?,
+    };
+    let token = std::env::var("GITHUB_TOKEN")
+        .or_else(|_| std::env::var("GH_TOKEN"))
+        .context("Set GITHUB_TOKEN (or GH_TOKEN) to read the pull request")?;
+    let ctx = CommandContext::new(global, false).await?;
+    let repo = github_repo(args.repo.as_deref(), &ctx.repo_root_path, "origin").await?;
+    let api_url =
+        std::env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".into());
+    let (title, body) = fetch_pull_request(&api_url, &token, &repo, number).await?;
+
+    let Some(intent) = parse_pr_intent(&body)? else {
+        println!("No changepacks block in pull request #{number}");
+        return Ok(());
+    };
+    let projects: Vec<&Project> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .collect();
+    let changes = resolve_changes(&intent.changes, &projects, "The changepacks block")?;
+    let log = ChangePackLog::new(changes, intent.note.unwrap_or(title));
+
+    let changepacks_dir = get_changepacks_dir(&ctx.repo_root_path)?;
+    let format = ctx.config.changepack_format;
+    let log_file = changepack_log_file(&changepacks_dir, &format!("pr-{number}"), format);
+    let label = path_key(&get_relative_path(&ctx.repo_root_path, &log_file)?);
+    if args.dry_run {
+        println!("Would write {label}:");
+        match format {
+            ChangepackFormat::Json => println!("{}", serde_json::to_string_pretty(&log)?),
+            ChangepackFormat::Markdown => print!("{}", log.to_markdown()?),
+        }
+        return Ok(());
+    }
+    tokio::fs::create_dir_all(&changepacks_dir).await?;
+    tokio::fs::write(&log_file, render_changepack_log(&log, format)?).await?;
+    println!("Wrote {label} from pull request #{number}");
+    Ok(())
+}
+
+/// Pull request number of the current GitHub Actions run, from the event payload or the ref
+fn pull_request_number_from_env() -> Option<u64> {
+    std::env::var("GITHUB_EVENT_PATH")
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|event| serde_json::from_str::<Value>(&event).ok())
+        .and_then(|event| pull_request_number(&event))
+        .or_else(|| pull_request_number_from_ref(&std::env::var("GITHUB_REF").ok()?))
+}
+
+/// `pull_request.number` of a `pull_request` (or `pull_request_target`) event payload
+fn pull_request_number(event: &Value) -> Option<u64> {
+    event["pull_request"]["number"].as_u64()
+}
+
+/// Number in a `refs/pull/<number>/merge` (or `/head`) ref
+fn pull_request_number_from_ref(git_ref: &str) -> Option<u64> {
+    git_ref
+        .strip_prefix("refs/pull/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Parse the first fenced `changepacks` block of a pull request description
+///
+/// Each line up to the first blank one is `<project>: <major|minor|patch>`, where the project
+/// is a name or a manifest path; the remaining lines form the note.
+///
+/// # Errors
+/// Returns error for an unterminated block, a malformed line, or a block without bumps.
+fn parse_pr_intent(body: &str) -> Result<Option<PrIntent>> {
+    let mut lines = body.lines();
+    if !lines
+        .by_ref()
+        .any(|line| line.trim().strip_prefix("```") == Some("changepacks"))
+    {
+        return Ok(None);
+    }
+    let mut block = Vec::new();
+    let mut closed = false;
+    for line in lines {
+        if line.trim().starts_with("```") {
+            closed = true;
+            break;
+        }
+        block.push(line);
+    }
+    if !closed {
+        bail!("The changepacks block in the pull request description is not closed");
+    }
+
+    let split = block
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .unwrap_or(block.len());
+    let mut changes = Vec::new();
+    for line in &block[..split] {
+        let parsed = line.split_once(':').and_then(|(project, update_type)| {
+            let project = project.trim().trim_matches(|c| c == '"' || c == '\'');
+            let update_type = match update_type.trim().to_ascii_lowercase().as_str() {
+                "major" => UpdateType::Major,
+                "minor" => UpdateType::Minor,
+                "patch" => UpdateType::Patch,
+                _ => return None,
+            };
+            (!project.is_empty()).then(|| (project.to_string(), update_type))
+        });
+        match parsed {
+            Some(change) => changes.push(change),
+            None => bail!(
+                "Invalid line in the changepacks block: '{}' (expected `<project>: <major|minor|patch>`)",
+                line.trim()
+            ),
+        }
+    }
+    if changes.is_empty() {
+        bail!("The changepacks block in the pull request description lists no projects");
+    }
+    let note = block[split..].join("\n").trim().to_string();
+    Ok(Some(PrIntent {
+        changes,
+        note: (!note.is_empty()).then_some(note),
+    }))
+}
+
+/// Manifest paths of the projects named in `changes`, keeping the strongest bump per project
+///
+/// # Errors
+/// Returns error listing every name or path that matches no project; `source` names where
+/// they came from.
+pub(super) fn resolve_changes(
+    changes: &[(String, UpdateType)],
+    projects: &[&Project],
+    source: &str,
+) -> Result<HashMap<PathBuf, UpdateType>> {
+    let mut resolved = HashMap::<PathBuf, UpdateType>::new();
+    let mut unknown = Vec::new();
+    for (target, update_type) in changes {
+        let Some(project) = projects.iter().find(|project| {
+            project.name() == Some(target.as_str())
+                || path_key(project.relative_path()) == path_key(std::path::Path::new(target))
+        }) else {
+            unknown.push(target.as_str());
+            continue;
+        };
+        resolved
+            .entry(project.relative_path().to_path_buf())
+            .and_modify(|existing| *existing = (*existing).min(*update_type))
+            .or_insert(*update_type);
+    }
+    if !unknown.is_empty() {
+        bail!("{source} names unknown projects: {}", unknown.join(", "));
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::{Language, Package};
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_pr_intent() {
+        let body = "Adds a parser.\r\n\r\n```changepacks\r\ncore: minor\r\n\"packages/web/package.json\": Patch\r\n\r\nAdd a streaming parser\r\nwith docs\r\n```\r\n";
+        assert_eq!(
+            parse_pr_intent(body).unwrap(),
+            Some(PrIntent {
+                changes: vec![
+                    ("core".to_string(), UpdateType::Minor),
+                    ("packages/web/package.json".to_string(), UpdateType::Patch),
+                ],
+                note: Some("Add a streaming parser\nwith docs".to_string()),
+            })
+        );
+
+        let intent = parse_pr_intent("```changepacks\ncore: major\n```")
+            .unwrap()
+            .unwrap();
+        assert_eq!(intent.note, None);
+        assert_eq!(parse_pr_intent("```rust\nlet x = 1;\n```").unwrap(), None);
+        assert_eq!(parse_pr_intent("").unwrap(), None);
+    }
+
+    #[rstest]
+    #[case("```changepacks\ncore: minor\n", "not closed")]
+    #[case(
+        "```changepacks\ncore: huge\n```",
+        "Invalid line in the changepacks block: 'core: huge'"
+    )]
+    #[case("```changepacks\ncore\n```", "Invalid line")]
+    #[case("```changepacks\n\nJust a note\n```", "lists no projects")]
+    fn test_parse_pr_intent_errors(#[case] body: &str, #[case] expected: &str) {
+        let err = parse_pr_intent(body).unwrap_err().to_string();
+        assert!(err.contains(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case("refs/pull/42/merge", Some(42))]
+    #[case("refs/pull/7/head", Some(7))]
+    #[case("refs/heads/main", None)]
+    fn test_pull_request_number_from_ref(#[case] git_ref: &str, #[case] expected: Option<u64>) {
+        assert_eq!(pull_request_number_from_ref(git_ref), expected);
+    }
+
+    #[test]
+    fn test_pull_request_number() {
+        let event = serde_json::json!({ "action": "opened", "pull_request": { "number": 12 } });
+        assert_eq!(pull_request_number(&event), Some(12));
+        assert_eq!(
+            pull_request_number(&serde_json::json!({ "ref": "main" })),
+            None
+        );
+    }
+
+    #[derive(Debug)]
+    struct MockPackage {
+        name: String,
+        path: PathBuf,
+        dependencies: HashSet<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl Package for MockPackage {
+        fn name(&self) -> Option<&str> {
+            Some(&self.name)
+        }
+        fn version(&self) -> Option<&str> {
+            Some("1.0.0")
+        }
+        fn path(&self) -> &Path {
+            &self.path
+        }
+        fn relative_path(&self) -> &Path {
+            &self.path
+        }
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
+            Ok(())
+        }
+        fn language(&self) -> Language {
+            Language::Node
+        }
+        fn dependencies(&self) -> &HashSet<String> {
+            &self.dependencies
+        }
+        fn add_dependency(&mut self, _dependency: &str) {}
+        fn set_changed(&mut self, _changed: bool) {}
+        fn is_changed(&self) -> bool {
+            false
+        }
+        fn default_publish_command(&self) -> String {
+            String::new()
+        }
+        fn default_dry_run_publish_command(&self) -> Option<String> {
+            None
+        }
+        fn set_name(&mut self, _name: String) {}
+    }
+
+    fn project(name: &str, path: &str) -> Project {
+        Project::Package(Box::new(MockPackage {
+            name: name.to_string(),
+            path: PathBuf::from(path),
+            dependencies: HashSet::new(),
+        }))
+    }
+
+    #[test]
+    fn test_resolve_changes() {
+        let core = project("core", "packages/core/package.json");
+        let web = project("web", "packages/web/package.json");
+        let projects = [&core, &web];
+        let resolved = resolve_changes(
+            &[
+                ("core".to_string(), UpdateType::Patch),
+                ("packages/core/package.json".to_string(), UpdateType::Minor),
+                ("web".to_string(), UpdateType::Patch),
+            ],
+            &projects,
+            "The changepacks block",
+        )
+        .unwrap();
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(
+            resolved[Path::new("packages/core/package.json")],
+            UpdateType::Minor
+        );
+        assert_eq!(
+            resolved[Path::new("packages/web/package.json")],
+            UpdateType::Patch
+        );
+
+        let err = resolve_changes(
+            &[
+                ("cli".to_string(), UpdateType::Patch),
+                ("docs".to_string(), UpdateType::Patch),
+            ],
+            &projects,
+            "The changepacks block",
+        )
+        .unwrap_err();
+        assert!(err.to_string().ends_with("unknown projects: cli, docs"));
+    }
+}
@@ -0,0 +1,462 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use changepacks_core::{
+    ChangePackResultLog, HookContext, HookEvent, Language, Project, ReleaseManifest, ReleaseRecord,
+    ReleasedPackage, UpdateType, path_key,
+};
+use changepacks_utils::{
+    RunLock, append_release_record, capture_writes, discover_projects, get_changepacks_dir,
+    get_relative_path, next_version, read_release_manifest,
+};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use colored::Colorize;
+use serde::Serialize;
+
+use super::update::{
+    UpdateProjectMut, WorkspaceRef, print_file_changes, run_package_hooks, write_release,
+};
+use crate::{
+    CommandContext, GlobalOptions,
+    completion::package_candidates,
+    finders::get_finders,
+    options::{CliLanguage, FormatOptions},
+    prompter::{InquirePrompter, Prompter},
+};
+
+#[derive(Args, Debug)]
+#[command(about = "Graduate prerelease versions (e.g. 1.2.0-rc.3) to stable (1.2.0)")]
+pub struct GraduateArgs {
+    /// Package names or manifest paths to graduate (default: every package on a prerelease)
+    #[arg(add = ArgValueCandidates::new(package_candidates))]
+    pub projects: Vec<String>,
+
+    #[arg(short, long)]
+    pub dry_run: bool,
+
+    #[arg(short, long)]
+    pub yes: bool,
+
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+
+    /// Filter projects by language. Can be specified multiple times to include multiple languages.
+    #[arg(short, long, value_enum)]
+    pub language: Vec<CliLanguage>,
+}
+
+/// A package moved from a prerelease to its stable version
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Graduation {
+    name: Option<String>,
+    path: PathBuf,
+    previous_version: String,
+    version: String,
+}
+
+/// Graduate prerelease versions to stable
+///
+/// # Errors
+/// Returns error if command context creation or version update fails.
+pub async fn handle_graduate(args: &GraduateArgs, global: &GlobalOptions) -> Result<()> {
+    handle_graduate_with_prompter(args, global, &InquirePrompter).await
+}
+
+/// # Errors
+/// Returns error if another run holds the lock, a requested project is not on a prerelease,
+/// a `preUpdate` hook fails, or updating versions, changelogs or the release manifest fails.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new`, a second
+/// `discover_projects` pass and an interactive confirmation; the selection and
+/// release-note helpers are unit tested and the command is covered by the cli
+/// integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_graduate_with_prompter(
+    args: &GraduateArgs,
+    global: &GlobalOptions,
+    prompter: &dyn Prompter,
+) -> Result<()> {
+    let mut ctx = CommandContext::new(global, false).await?;
+    let changepacks_dir = get_changepacks_dir(&ctx.repo_root_path)?;
+    // Held until the versions and the release record are written
+    let _lock = if args.dry_run {
+        None
+    } else {
+        Some(RunLock::acquire(&changepacks_dir, "graduate")?)
+    };
+
+    // Workspace manifests of every language, so pins on graduated packages are rewritten too
+    let mut all_finders = get_finders(&ctx.config);
+    discover_projects(
+        &ctx.repo_root_path,
+        &mut all_finders,
+        &changepacks_core::Config::default(),
+        false,
+        &ctx.diff_refs,
+    )
+    .await?;
+    let workspace_projects: Vec<WorkspaceRef> = all_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .filter_map(|project| match project {
+            Project::Workspace(workspace) => Some(workspace.as_ref()),
+            Project::Package(_) => None,
+        })
+        .collect();
+
+    let allowed_languages: Vec<Language> = args
+        .language
+        .iter()
+        .map(|&lang| Language::from(lang))
+        .collect();
+    let projects: Vec<&mut Project> = ctx
+        .project_finders
+        .iter_mut()
+        .flat_map(|finder| finder.projects_mut())
+        .filter(|project| {
+            allowed_languages.is_empty() || allowed_languages.contains(&project.language())
+        })
+        .collect();
+    let mut graduating = select_prereleases(projects, &args.projects, &ctx.repo_root_path)?;
+    if graduating.is_empty() {
+        args.format
+            .print("No prerelease versions to graduate", "[]");
+        return Ok(());
+    }
+    graduating.sort();
+
+    let manifest = read_release_manifest(&changepacks_dir).await?;
+    let previous_versions: Vec<String> = graduating
+        .iter()
+        .map(|(project, _)| project.version().unwrap_or_default().to_string())
+        .collect();
+    // The stable release notes are the merged notes of its prereleases
+    let mut update_map = HashMap::with_capacity(graduating.len());
+    for ((project, _), previous) in graduating.iter().zip(&previous_versions) {
+        let path = get_relative_path(&ctx.repo_root_path, project.path())?;
+        let (update_type, notes) = prerelease_notes(
+            &manifest,
+            &path,
+            &next_version(previous, UpdateType::Patch)?,
+        );
+        let logs = notes
+            .into_iter()
+            .map(|note| ChangePackResultLog::new(update_type, note))
+            .collect();
+        update_map.insert(path, (update_type, logs));
+    }
+    if let FormatOptions::Stdout = args.format {
+        println!("Graduating:");
+        for ((project, _), previous) in graduating.iter().zip(&previous_versions) {
+            println!(
+                "  {project}  {previous} -> {}",
+                next_version(previous, UpdateType::Patch)?
+            );
+        }
+    }
+
+    let changelog_date = ctx
+        .config
+        .changelog
+        .then(|| ctx.config.format_date(&chrono::Utc::now()));
+    let release_writes = |graduating| {
+        write_release(
+            graduating,
+            &workspace_projects,
+            &update_map,
+            None,
+            &ctx.repo_root_path,
+            changelog_date.as_deref(),
+        )
+    };
+
+    if args.dry_run {
+        let (result, changes) = capture_writes(release_writes(&mut graduating)).await;
+        result?;
+        if let FormatOptions::Stdout = args.format {
+            print_file_changes(&changes, &ctx.repo_root_path)?;
+        }
+        args.format
+            .print("Dry run, no versions will be changed", "[]");
+        return Ok(());
+    }
+
+    let confirm = if args.yes {
+        true
+    } else {
+        prompter.confirm("Are you sure you want to graduate these versions?")?
+    };
+    if !confirm {
+        args.format.print("Graduate cancelled", "[]");
+        return Ok(());
+    }
+
+    let pre_update_hooks = graduating
+        .iter()
+        .zip(&previous_versions)
+        .map(|((project, update_type), version)| {
+            Ok(HookContext {
+                package: project.name().map(str::to_string),
+                path: get_relative_path(&ctx.repo_root_path, project.path())?,
+                version: Some(version.clone()),
+                previous_version: None,
+                update_type: Some(*update_type),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    run_package_hooks(
+        &ctx.config,
+        HookEvent::PreUpdate,
+        &pre_update_hooks,
+        &ctx.repo_root_path,
+    )
+    .await?;
+    release_writes(&mut graduating).await?;
+
+    let mut released = Vec::with_capacity(graduating.len());
+    let mut graduations = Vec::with_capacity(graduating.len());
+    for ((project, _), previous_version) in graduating.iter().zip(previous_versions) {
+        let path = get_relative_path(&ctx.repo_root_path, project.path())?;
+        let version = project.version().unwrap_or_default().to_string();
+        let (update_type, logs) = &update_map[&path];
+        released.push(ReleasedPackage::new(
+            project.name().map(str::to_string),
+            path.clone(),
+            Some(previous_version.clone()),
+            version.clone(),
+            *update_type,
+            logs.iter().map(|log| log.note().to_string()).collect(),
+        ));
+        graduations.push(Graduation {
+            name: project.name().map(str::to_string),
+            path,
+            previous_version,
+            version,
+        });
+    }
+    let release = ReleaseRecord::new(released);
+    let post_update_hooks: Vec<HookContext> = release
+        .packages()
+        .iter()
+        .map(|package| HookContext {
+            package: package.name().map(str::to_string),
+            path: package.path().to_path_buf(),
+            version: Some(package.version().to_string()),
+            previous_version: package.previous_version().map(str::to_string),
+            update_type: Some(package.update_type()),
+        })
+        .collect();
+    append_release_record(&changepacks_dir, release).await?;
+
+    // The graduation is complete, so a failing hook is reported without failing the command
+    if let Err(e) = run_package_hooks(
+        &ctx.config,
+        HookEvent::PostUpdate,
+        &post_update_hooks,
+        &ctx.repo_root_path,
+    )
+    .await
+    {
+        eprintln!("{} {e:#}", "warning:".yellow());
+    }
+
+    match args.format {
+        FormatOptions::Stdout => println!("Graduated {} package(s)", graduations.len()),
+        FormatOptions::Json => println!("{}", serde_json::to_string_pretty(&graduations)?),
+    }
+    Ok(())
+}
+
+/// Whether `version` carries a prerelease tag (`1.2.0-rc.1`), ignoring build metadata
+fn is_prerelease(version: &str) -> bool {
+    version
+        .split('+')
+        .next()
+        .is_some_and(|core| core.contains('-'))
+}
+
+/// Projects on a prerelease to graduate, each paired with the patch bump that releases it
+///
+/// With no `queries`, every prerelease project is selected. Otherwise each query must name a
+/// project (by manifest name or relative path) that is on a prerelease. Packages inheriting
+/// their version from a Cargo workspace are graduated through the workspace instead.
+fn select_prereleases<'a>(
+    projects: Vec<&'a mut Project>,
+    queries: &[String],
+    repo_root_path: &Path,
+) -> Result<Vec<UpdateProjectMut<'a>>> {
+    let matches = |project: &Project, query: &str| {
+        project.name() == Some(query)
+            || get_relative_path(repo_root_path, project.path())
+                .is_ok_and(|path| path_key(&path) == path_key(Path::new(query)))
+    };
+    for query in queries {
+        match projects.iter().find(|project| matches(project, query)) {
+            None => anyhow::bail!("Project '{query}' not found"),
+            Some(project) if !project.version().is_some_and(is_prerelease) => anyhow::bail!(
+                "{project} is not on a prerelease version ({})",
+                project.version().unwrap_or("no version")
+            ),
+            Some(_) => {}
+        }
+    }
+    Ok(projects
+        .into_iter()
+        .filter(|project| {
+            project.version().is_some_and(is_prerelease)
+                && !matches!(project, Project::Package(package) if package.inherits_workspace_version())
+                && (queries.is_empty() || queries.iter().any(|query| matches(project, query)))
+        })
+        .map(|project| (project, UpdateType::Patch))
+        .collect())
+}
+
+/// Bump type and notes for the stable release of `version`, gathered from its prereleases
+///
+/// The stable release carries every note released in `version`'s prereleases (oldest first,
+/// without duplicates) and the most significant bump among them, so the release history
+/// reads as one `1.1.0 -> 1.2.0` minor release rather than a patch from `1.2.0-rc.3`.
+fn prerelease_notes(
+    manifest: &ReleaseManifest,
+    path: &Path,
+    version: &str,
+) -> (UpdateType, Vec<String>) {
+    let mut update_type = None;
+    let mut notes: Vec<String> = Vec::new();
+    for package in manifest
+        .releases()
+        .iter()
+        .flat_map(ReleaseRecord::packages)
+        .filter(|package| {
+            package.path() == path
+                && is_prerelease(package.version())
+                && package.version().split(['-', '+']).next() == Some(version)
+        })
+    {
+        update_type = Some(
+            update_type.map_or(package.update_type(), |current: UpdateType| {
+                current.min(package.update_type())
+            }),
+        );
+        for note in package.notes() {
+            if !notes.contains(note) {
+                notes.push(note.clone());
+            }
+        }
+    }
+    (update_type.unwrap_or(UpdateType::Patch), notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_node::package::NodePackage;
+    use clap::Parser;
+    use rstest::rstest;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        graduate: GraduateArgs,
+    }
+
+    #[test]
+    fn test_graduate_args() {
+        let args = TestCli::parse_from(["graduate"]).graduate;
+        assert!(args.projects.is_empty());
+        assert!(!args.dry_run);
+        assert!(!args.yes);
+        let args = TestCli::parse_from(["graduate", "core", "web/package.json", "-d"]).graduate;
+        assert_eq!(args.projects, ["core", "web/package.json"]);
+        assert!(args.dry_run);
+    }
+
+    #[rstest]
+    #[case("1.2.0-rc.1", true)]
+    #[case("1.2.0-rc.1+build.5", true)]
+    #[case("1.2.0", false)]
+    #[case("1.2.0+build-5", false)]
+    fn test_is_prerelease(#[case] version: &str, #[case] expected: bool) {
+        assert_eq!(is_prerelease(version), expected);
+    }
+
+    fn package(name: &str, version: &str) -> Project {
+        Project::Package(Box::new(NodePackage::new(
+            Some(name.to_string()),
+            Some(version.to_string()),
+            PathBuf::from(format!("/repo/{name}/package.json")),
+            PathBuf::from(format!("{name}/package.json")),
+        )))
+    }
+
+    #[test]
+    fn test_select_prereleases() {
+        let repo = Path::new("/repo");
+        let mut projects = [
+            package("core", "1.2.0-rc.1"),
+            package("web", "2.0.0"),
+            package("cli", "0.3.0-beta.2"),
+        ];
+
+        let selected = select_prereleases(projects.iter_mut().collect(), &[], repo).unwrap();
+        let names: Vec<_> = selected.iter().map(|(p, _)| p.name().unwrap()).collect();
+        assert_eq!(names, ["core", "cli"]);
+        assert!(selected.iter().all(|(_, bump)| *bump == UpdateType::Patch));
+
+        let queries = ["cli/package.json".to_string()];
+        let selected = select_prereleases(projects.iter_mut().collect(), &queries, repo).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].0.name(), Some("cli"));
+
+        let err = select_prereleases(projects.iter_mut().collect(), &["web".to_string()], repo)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("not on a prerelease version (2.0.0)")
+        );
+
+        let err = select_prereleases(projects.iter_mut().collect(), &["nope".to_string()], repo)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Project 'nope' not found");
+    }
+
+    #[test]
+    fn test_prerelease_notes() {
+        let release = |version: &str, update_type, notes: &[&str]| {
+            ReleaseRecord::new(vec![ReleasedPackage::new(
+                Some("core".to_string()),
+                PathBuf::from("core/package.json"),
+                None,
+                version.to_string(),
+                update_type,
+                notes.iter().map(|note| (*note).to_string()).collect(),
+            )])
+        };
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(release("1.1.0", UpdateType::Minor, &["Old feature"]));
+        manifest.push(release("1.2.0-rc.0", UpdateType::Minor, &["New API"]));
+        manifest.push(release(
+            "1.2.0-rc.1",
+            UpdateType::Patch,
+            &["Fix API", "New API"],
+        ));
+        manifest.push(release("1.3.0-rc.0", UpdateType::Minor, &["Later"]));
+
+        assert_eq!(
+            prerelease_notes(&manifest, Path::new("core/package.json"), "1.2.0"),
+            (
+                UpdateType::Minor,
+                vec!["New API".to_string(), "Fix API".to_string()]
+            )
+        );
+        assert_eq!(
+            prerelease_notes(&manifest, Path::new("web/package.json"), "1.2.0"),
+            (UpdateType::Patch, vec![])
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use changepacks_core::{Config, ReleaseManifest};
+use changepacks_utils::{get_changepacks_config, get_changepacks_dir, read_release_manifest};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use colored::Colorize;
+
+use crate::{GlobalOptions, completion::package_candidates, options::FormatOptions};
+
+#[derive(Args, Debug)]
+#[command(about = "Show release history from the release manifest")]
+pub struct HistoryArgs {
+    /// Package name or manifest path to show history for (all packages if omitted)
+    #[arg(add = ArgValueCandidates::new(package_candidates))]
+    pub package: Option<String>,
+
+    /// Only show the release that produced this version
+    #[arg(long)]
+    pub version: Option<String>,
+
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+}
+
+/// Show release history recorded in `.changepacks/releases.json`
+///
+/// # Errors
+/// Returns error if the release manifest cannot be read or parsed.
+pub async fn handle_history(args: &HistoryArgs, global: &GlobalOptions) -> Result<()> {
+    let current_dir = global.current_dir()?;
+    let config = get_changepacks_config(&current_dir).await?;
+    let changepacks_dir = get_changepacks_dir(&current_dir)?;
+    let manifest = read_release_manifest(&changepacks_dir)
+        .await?
+        .filter(args.package.as_deref(), args.version.as_deref());
+
+    match args.format {
+        FormatOptions::Stdout => {
+            if manifest.releases().is_empty() {
+                println!("No releases found");
+            } else {
+                print!("{}", format_history(&manifest, &config));
+            }
+        }
+        FormatOptions::Json => {
+            let releases: Vec<_> = manifest.releases().iter().rev().collect();
+            println!("{}", serde_json::to_string_pretty(&releases)?);
+        }
+    }
+    Ok(())
+}
+
+/// Render releases newest first, one block per `update` run
+fn format_history(manifest: &ReleaseManifest, config: &Config) -> String {
+    let mut out = String::new();
+    for record in manifest.releases().iter().rev() {
+        out.push_str(&format!(
+            "{}\n",
+            config.format_date_time(record.date()).bold()
+        ));
+        for package in record.packages() {
+            let previous = package
+                .previous_version()
+                .map_or_else(|| "unknown".to_string(), |v| format!("v{v}"));
+            out.push_str(&format!(
+                "  {} ({}) {previous} → v{} {}\n",
+                package.name().unwrap_or("noname"),
+                package.path().display(),
+                package.version(),
+                package.update_type(),
+            ));
+            for note in package.notes() {
+                out.push_str(&format!("    - {note}\n"));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::{ReleaseRecord, ReleasedPackage, UpdateType};
+    use clap::Parser;
+    use std::path::PathBuf;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        history: HistoryArgs,
+    }
+
+    #[test]
+    fn test_history_args_default() {
+        let cli = TestCli::parse_from(["test"]);
+        assert!(cli.history.package.is_none());
+        assert!(cli.history.version.is_none());
+        assert!(matches!(cli.history.format, FormatOptions::Stdout));
+    }
+
+    #[test]
+    fn test_history_args_with_package_and_version() {
+        let cli = TestCli::parse_from(["test", "core", "--version", "1.2.0", "--format", "json"]);
+        assert_eq!(cli.history.package.as_deref(), Some("core"));
+        assert_eq!(cli.history.version.as_deref(), Some("1.2.0"));
+        assert!(matches!(cli.history.format, FormatOptions::Json));
+    }
+
+    #[test]
+    fn test_format_history_newest_first_with_notes() {
+        let mut manifest = ReleaseManifest::default();
+        for (previous, version, note) in [("1.0.0", "1.1.0", "first"), ("1.1.0", "1.2.0", "second")]
+        {
+            manifest.push(ReleaseRecord::new(vec![ReleasedPackage::new(
+                Some("core".to_string()),
+                PathBuf::from("packages/core/package.json"),
+                Some(previous.to_string()),
+                version.to_string(),
+                UpdateType::Minor,
+                vec![note.to_string()],
+            )]));
+        }
+
+        let output = format_history(&manifest, &Config::default());
+        let second = output.find("v1.1.0 → v1.2.0").unwrap();
+        let first = output.find("v1.0.0 → v1.1.0").unwrap();
+        assert!(second < first);
+        assert!(output.contains("  core (packages/core/package.json) v1.1.0 → v1.2.0"));
+        assert!(output.contains("    - second\n"));
+    }
+}
@@ -0,0 +1,191 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use changepacks_utils::git_hooks_dir;
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::GlobalOptions;
+
+/// First line after the shebang of every hook written by changepacks
+const HOOK_MARKER: &str = "# Installed by `changepacks hook install`";
+
+#[derive(Args, Debug)]
+#[command(about = "Manage the git hook that checks for missing changepacks")]
+pub struct HookArgs {
+    #[command(subcommand)]
+    pub action: HookAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HookAction {
+    /// Write a git hook that fails when a changed project has no changepack
+    Install(HookInstallArgs),
+    /// Remove a hook written by `hook install`
+    Uninstall(HookUninstallArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct HookInstallArgs {
+    /// Git hook to write
+    #[arg(long, value_enum, default_value_t)]
+    pub kind: HookKind,
+
+    /// Command the hook runs; a non-zero exit blocks the push or commit
+    #[arg(long, default_value = "changepacks check --strict")]
+    pub command: String,
+
+    /// Replace an existing hook that was not written by changepacks
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct HookUninstallArgs {
+    #[arg(long, value_enum, default_value_t)]
+    pub kind: HookKind,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HookKind {
+    /// Check before `git push`
+    #[default]
+    PrePush,
+    /// Check while writing each commit message
+    PrepareCommitMsg,
+}
+
+impl HookKind {
+    const fn file_name(self) -> &'static str {
+        match self {
+            Self::PrePush => "pre-push",
+            Self::PrepareCommitMsg => "prepare-commit-msg",
+        }
+    }
+}
+
+/// Install or remove the changepacks git hook
+///
+/// # Errors
+/// Returns error if the hooks directory cannot be located or the hook cannot be written.
+///
+/// Excluded from coverage: resolves the hooks directory through a live git repository;
+/// the script rendering is covered by its own tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_hook(args: &HookArgs, global: &GlobalOptions) -> Result<()> {
+    let current_dir = global.current_dir()?;
+    // Honors core.hooksPath and linked worktrees
+    let hooks_dir = git_hooks_dir(&current_dir)?;
+    match &args.action {
+        HookAction::Install(install) => {
+            let path = hooks_dir.join(install.kind.file_name());
+            if !install.force && is_foreign_hook(&path).await {
+                bail!(
+                    "{} already exists and was not written by changepacks (use --force to replace it)",
+                    path.display()
+                );
+            }
+            tokio::fs::create_dir_all(&hooks_dir).await?;
+            tokio::fs::write(&path, hook_script(&install.command))
+                .await
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            make_executable(&path)?;
+            println!("Installed {}", path.display());
+        }
+        HookAction::Uninstall(uninstall) => {
+            let path = hooks_dir.join(uninstall.kind.file_name());
+            if !path.exists() {
+                println!("No {} hook installed", uninstall.kind.file_name());
+            } else if is_foreign_hook(&path).await {
+                bail!(
+                    "{} was not written by changepacks; leaving it",
+                    path.display()
+                );
+            } else {
+                tokio::fs::remove_file(&path).await?;
+                println!("Removed {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `path` holds a hook that changepacks did not write
+async fn is_foreign_hook(path: &Path) -> bool {
+    tokio::fs::read_to_string(path)
+        .await
+        .is_ok_and(|content| !content.contains(HOOK_MARKER))
+}
+
+/// Shell script running `command`, skipped with a warning when changepacks is not installed
+fn hook_script(command: &str) -> String {
+    format!(
+        "#!/bin/sh\n\
+         {HOOK_MARKER}\n\
+         if ! command -v changepacks >/dev/null 2>&1; then\n\
+         \x20 echo \"changepacks not found; skipping changepack check\" >&2\n\
+         \x20 exit 0\n\
+         fi\n\
+         {command}\n"
+    )
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use tempfile::TempDir;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        hook: HookArgs,
+    }
+
+    #[test]
+    fn test_hook_args_parsing() {
+        let cli = TestCli::parse_from(["test", "install"]);
+        assert!(matches!(
+            cli.hook.action,
+            HookAction::Install(HookInstallArgs { kind: HookKind::PrePush, ref command, force: false })
+                if command == "changepacks check --strict"
+        ));
+        let cli = TestCli::parse_from(["test", "uninstall", "--kind", "prepare-commit-msg"]);
+        assert!(matches!(
+            cli.hook.action,
+            HookAction::Uninstall(HookUninstallArgs {
+                kind: HookKind::PrepareCommitMsg
+            })
+        ));
+    }
+
+    #[test]
+    fn test_hook_script() {
+        let script = hook_script("changepacks check --strict");
+        assert!(script.starts_with("#!/bin/sh\n# Installed by `changepacks hook install`\n"));
+        assert!(script.contains("  exit 0\nfi\n"));
+        assert!(script.ends_with("\nchangepacks check --strict\n"));
+    }
+
+    #[tokio::test]
+    async fn test_is_foreign_hook() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("pre-push");
+        assert!(!is_foreign_hook(&path).await);
+        std::fs::write(&path, hook_script("true")).unwrap();
+        assert!(!is_foreign_hook(&path).await);
+        std::fs::write(&path, "#!/bin/sh\nnpm test\n").unwrap();
+        assert!(is_foreign_hook(&path).await);
+    }
+}
@@ -1,52 +1,293 @@
-use changepacks_core::Config;
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use changepacks_core::{Config, ConfigFile, Language, changepacks_dir_name};
 use tokio::fs::{create_dir_all, write};
 
 use anyhow::Result;
-use changepacks_utils::get_changepacks_dir;
+use changepacks_utils::{get_changepacks_config, get_config_dir, walk_project_dirs};
 use clap::Args;
 
+use crate::GlobalOptions;
+use crate::finders::get_finders;
+use crate::options::CiProvider;
+
 #[derive(Args, Debug)]
 #[command(about = "Initialize a new changepacks project")]
 pub struct InitArgs {
     /// If true, do not make any filesystem changes.
     #[arg(short, long, default_value = "false")]
     dry_run: bool,
+
+    /// Also write a release workflow (check on pull requests, version and publish on the base
+    /// branch) for this CI service
+    #[arg(long, value_enum)]
+    ci: Option<CiProvider>,
 }
 
 /// Initialize a new changepacks project
 ///
 /// # Errors
-/// Returns error if creating the .changepacks directory or config file fails.
+/// Returns error if creating the .changepacks directory, config file, or CI workflow fails.
 ///
 /// Excluded from coverage: filesystem I/O orchestration; the argument
-/// parsing is covered separately by `test_init_args_*` tests.
+/// parsing is covered separately by `test_init_args_*` tests and the
+/// workflow rendering by `test_ci_workflow_*`.
 #[cfg(not(tarpaulin_include))]
-pub async fn handle_init(args: &InitArgs) -> Result<()> {
+pub async fn handle_init(args: &InitArgs, global: &GlobalOptions) -> Result<()> {
     // create .changepacks directory
-    let current_dir = std::env::current_dir()?;
-    let changepacks_dir = get_changepacks_dir(&current_dir)?;
+    let current_dir = global.current_dir()?;
+    // A selected root gets its own changepacks directory instead of joining an enclosing root
+    let changepacks_dir = match &global.root {
+        Some(root) => root.join(changepacks_dir_name()),
+        None => get_config_dir(&current_dir)?,
+    };
     if !args.dry_run {
         create_dir_all(&changepacks_dir).await?;
     }
-    // create config.json file
+    // create config.json file, unless a config file in any format exists
     let config_file = changepacks_dir.join("config.json");
-    if config_file.exists() {
-        Err(anyhow::anyhow!("changepacks project already initialized"))
-    } else {
-        if !args.dry_run {
-            write(
-                config_file,
-                serde_json::to_string_pretty(&Config::default())?,
+    if ConfigFile::find(&changepacks_dir)?.is_some() {
+        // Adding CI to an existing project is fine; re-initializing is not
+        let Some(provider) = args.ci else {
+            return Err(anyhow::anyhow!("changepacks project already initialized"));
+        };
+        let config = get_changepacks_config(&current_dir).await?;
+        return init_ci(&changepacks_dir, provider, &config, args.dry_run).await;
+    }
+    if !args.dry_run {
+        write(
+            config_file,
+            serde_json::to_string_pretty(&Config::default())?,
+        )
+        .await?;
+    }
+
+    println!(
+        "changepacks project initialized in {}",
+        changepacks_dir.display()
+    );
+
+    if let Some(provider) = args.ci {
+        init_ci(&changepacks_dir, provider, &Config::default(), args.dry_run).await?;
+    }
+    Ok(())
+}
+
+/// Write the release workflow for `provider` next to the changepacks directory
+///
+/// Excluded from coverage: filesystem walk and I/O; see [`handle_init`].
+#[cfg(not(tarpaulin_include))]
+async fn init_ci(
+    changepacks_dir: &Path,
+    provider: CiProvider,
+    config: &Config,
+    dry_run: bool,
+) -> Result<()> {
+    let root = changepacks_dir.parent().unwrap_or(changepacks_dir);
+    let workflow_file = root.join(provider.workflow_path());
+    if workflow_file.exists() {
+        anyhow::bail!("{} already exists", workflow_file.display());
+    }
+    let mut project_finders = get_finders(config);
+    walk_project_dirs(root, &mut project_finders, config).await?;
+    let languages: BTreeSet<Language> = project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .map(changepacks_core::Project::language)
+        .collect();
+    if !dry_run {
+        if let Some(parent) = workflow_file.parent() {
+            create_dir_all(parent).await?;
+        }
+        write(
+            &workflow_file,
+            ci_workflow(provider, &languages, &config.base_branch),
+        )
+        .await?;
+    }
+    println!("CI workflow written to {}", workflow_file.display());
+    Ok(())
+}
+
+/// Toolchain setup steps for the GitHub Actions release job
+const fn github_setup_steps(language: Language) -> &'static str {
+    match language {
+        Language::Node => {
+            "      - uses: actions/setup-node@v4\n        with:\n          node-version: 22\n          registry-url: https://registry.npmjs.org\n"
+        }
+        // uv is always installed to run changepacks
+        Language::Python => "",
+        Language::Rust => "      - uses: dtolnay/rust-toolchain@stable\n",
+        Language::Dart => "      - uses: dart-lang/setup-dart@v1\n",
+        Language::Java => {
+            "      - uses: actions/setup-java@v4\n        with:\n          distribution: temurin\n          java-version: 21\n"
+        }
+        Language::CSharp => {
+            "      - uses: actions/setup-dotnet@v4\n        with:\n          dotnet-version: 8.0.x\n"
+        }
+        Language::Go => {
+            "      - uses: actions/setup-go@v5\n        with:\n          go-version: stable\n"
+        }
+        Language::Elixir => {
+            "      - uses: erlef/setup-beam@v1\n        with:\n          otp-version: 27\n          elixir-version: 1.17\n"
+        }
+        Language::Deno => {
+            "      - uses: denoland/setup-deno@v2\n        with:\n          deno-version: v2.x\n"
+        }
+        Language::Helm => "      - uses: azure/setup-helm@v4\n",
+    }
+}
+
+/// Registry credentials the default publish command of `language` reads from the environment
+const fn publish_secrets(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Node => &["NODE_AUTH_TOKEN"],
+        Language::Python => &["UV_PUBLISH_TOKEN"],
+        Language::Rust => &["CARGO_REGISTRY_TOKEN"],
+        Language::Elixir => &["HEX_API_KEY"],
+        // The OCI registry charts are pushed to, e.g. oci://ghcr.io/acme/charts
+        Language::Helm => &["HELM_REGISTRY"],
+        // Go modules are published by pushing a tag with the job's git credentials, and
+        // `deno publish` authenticates to JSR with the job's OIDC token
+        Language::Dart | Language::Java | Language::CSharp | Language::Go | Language::Deno => &[],
+    }
+}
+
+/// Docker image for the GitLab jobs, chosen for the first detected language
+const fn gitlab_image(language: Option<Language>) -> &'static str {
+    match language {
+        Some(Language::Node) => "node:22",
+        Some(Language::Python) | None => "python:3.12",
+        Some(Language::Rust) => "rust:latest",
+        Some(Language::Dart) => "dart:stable",
+        Some(Language::Java) => "eclipse-temurin:21",
+        Some(Language::CSharp) => "mcr.microsoft.com/dotnet/sdk:8.0",
+        Some(Language::Go) => "golang:latest",
+        Some(Language::Elixir) => "elixir:latest",
+        Some(Language::Deno) => "denoland/deno:latest",
+        Some(Language::Helm) => "alpine/helm:latest",
+    }
+}
+
+/// Render the release workflow for `provider`
+///
+/// Pull requests run `changepacks check`; pushes to `base_branch` apply pending changepacks,
+/// commit the version bumps, and publish.
+fn ci_workflow(provider: CiProvider, languages: &BTreeSet<Language>, base_branch: &str) -> String {
+    let secrets: Vec<&str> = languages
+        .iter()
+        .flat_map(|language| publish_secrets(*language))
+        .copied()
+        .collect();
+    match provider {
+        CiProvider::Github => {
+            let setup: String = languages
+                .iter()
+                .map(|language| github_setup_steps(*language))
+                .collect();
+            let mut env = String::new();
+            if !secrets.is_empty() {
+                env.push_str("        env:\n");
+                for secret in &secrets {
+                    let _ = writeln!(env, "          {secret}: ${{{{ secrets.{secret} }}}}");
+                }
+            }
+            format!(
+                r#"name: Changepacks
+
+on:
+  pull_request:
+  push:
+    branches: [{base_branch}]
+
+jobs:
+  check:
+    if: github.event_name == 'pull_request'
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v5
+        with:
+          fetch-depth: 0
+      - uses: astral-sh/setup-uv@v6
+      - run: uvx changepacks check --remote
+
+  release:
+    if: github.event_name == 'push'
+    runs-on: ubuntu-latest
+    permissions:
+      contents: write
+      id-token: write
+    steps:
+      - uses: actions/checkout@v5
+        with:
+          fetch-depth: 0
+      - uses: astral-sh/setup-uv@v6
+{setup}      - name: Version packages
+        run: |
+          uvx changepacks update --yes
+          if [ -n "$(git status --porcelain)" ]; then
+            git config user.name "github-actions[bot]"
+            git config user.email "41898282+github-actions[bot]@users.noreply.github.com"
+            git add -A
+            git commit -m "Version packages"
+            git push
+          fi
+      - name: Publish packages
+        run: uvx changepacks publish --yes
+{env}"#
             )
-            .await?;
         }
+        CiProvider::Gitlab => {
+            let mut variables = vec!["RELEASE_TOKEN"];
+            variables.extend(&secrets);
+            let npmrc = if languages.contains(&Language::Node) {
+                "    - echo \"//registry.npmjs.org/:_authToken=${NODE_AUTH_TOKEN}\" > ~/.npmrc\n"
+            } else {
+                ""
+            };
+            format!(
+                r#"# Required CI/CD variables: {variables}
 
-        println!(
-            "changepacks project initialized in {}",
-            changepacks_dir.display()
-        );
+stages:
+  - check
+  - release
+
+default:
+  image: {image}
+  before_script:
+    - curl -LsSf https://astral.sh/uv/install.sh | sh
+    - export PATH="$HOME/.local/bin:$PATH"
 
-        Ok(())
+changepacks:check:
+  stage: check
+  rules:
+    - if: $CI_PIPELINE_SOURCE == "merge_request_event"
+  script:
+    - git fetch origin {base_branch}
+    - uvx changepacks check --remote
+
+changepacks:release:
+  stage: release
+  rules:
+    - if: $CI_COMMIT_BRANCH == "{base_branch}"
+  script:
+{npmrc}    - uvx changepacks update --yes
+    - |
+      if [ -n "$(git status --porcelain)" ]; then
+        git config user.name "changepacks"
+        git config user.email "changepacks@noreply.${{CI_SERVER_HOST}}"
+        git add -A
+        git commit -m "Version packages"
+        git push "https://oauth2:${{RELEASE_TOKEN}}@${{CI_SERVER_HOST}}/${{CI_PROJECT_PATH}}.git" HEAD:{base_branch}
+      fi
+    - uvx changepacks publish --yes
+"#,
+                variables = variables.join(", "),
+                image = gitlab_image(languages.first().copied()),
+            )
+        }
     }
 }
 
@@ -78,4 +319,63 @@ mod tests {
         let cli = TestCli::parse_from(["test", "-d"]);
         assert!(cli.init.dry_run);
     }
+
+    #[test]
+    fn test_init_args_with_ci() {
+        assert!(TestCli::parse_from(["test"]).init.ci.is_none());
+        let cli = TestCli::parse_from(["test", "--ci", "github"]);
+        assert_eq!(cli.init.ci, Some(CiProvider::Github));
+        let cli = TestCli::parse_from(["test", "--ci", "gitlab"]);
+        assert_eq!(cli.init.ci, Some(CiProvider::Gitlab));
+        assert!(TestCli::try_parse_from(["test", "--ci", "jenkins"]).is_err());
+    }
+
+    #[test]
+    fn test_ci_workflow_github() {
+        let languages = BTreeSet::from([Language::Node, Language::Rust]);
+        let workflow = ci_workflow(CiProvider::Github, &languages, "main");
+        assert!(workflow.contains("branches: [main]"));
+        assert!(workflow.contains("uvx changepacks check --remote"));
+        assert!(workflow.contains("uvx changepacks update --yes"));
+        assert!(workflow.contains("uvx changepacks publish --yes"));
+        assert!(workflow.contains("actions/setup-node@v4"));
+        assert!(workflow.contains("dtolnay/rust-toolchain@stable"));
+        assert!(!workflow.contains("actions/setup-java"));
+        assert!(workflow.contains("NODE_AUTH_TOKEN: ${{ secrets.NODE_AUTH_TOKEN }}"));
+        assert!(workflow.contains("CARGO_REGISTRY_TOKEN: ${{ secrets.CARGO_REGISTRY_TOKEN }}"));
+    }
+
+    #[test]
+    fn test_ci_workflow_github_without_secrets() {
+        let languages = BTreeSet::from([Language::Dart]);
+        let workflow = ci_workflow(CiProvider::Github, &languages, "develop");
+        assert!(workflow.contains("branches: [develop]"));
+        assert!(workflow.contains("dart-lang/setup-dart@v1"));
+        assert!(!workflow.contains("env:"));
+        assert!(workflow.ends_with("run: uvx changepacks publish --yes\n"));
+    }
+
+    #[test]
+    fn test_ci_workflow_gitlab() {
+        let languages = BTreeSet::from([Language::Node, Language::Python]);
+        let workflow = ci_workflow(CiProvider::Gitlab, &languages, "main");
+        assert!(workflow.starts_with(
+            "# Required CI/CD variables: RELEASE_TOKEN, UV_PUBLISH_TOKEN, NODE_AUTH_TOKEN\n"
+        ));
+        assert!(workflow.contains("image: python:3.12"));
+        assert!(workflow.contains("$CI_COMMIT_BRANCH == \"main\""));
+        assert!(workflow.contains("${CI_SERVER_HOST}/${CI_PROJECT_PATH}.git\" HEAD:main"));
+        assert!(workflow.contains("_authToken=${NODE_AUTH_TOKEN}"));
+
+        let workflow = ci_workflow(
+            CiProvider::Gitlab,
+            &BTreeSet::from([Language::Node]),
+            "main",
+        );
+        assert!(workflow.contains("image: node:22"));
+        assert!(workflow.contains("_authToken"));
+        let workflow = ci_workflow(CiProvider::Gitlab, &BTreeSet::new(), "main");
+        assert!(workflow.contains("image: python:3.12"));
+        assert!(!workflow.contains(".npmrc"));
+    }
 }
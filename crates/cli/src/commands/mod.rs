@@ -1,10 +1,32 @@
+mod add;
+mod affected;
+mod audit;
+mod changelog;
 mod changepacks;
 mod check;
 mod config;
+mod dedupe;
+mod doctor;
+mod from_pr;
+mod graduate;
+mod history;
+mod hook;
 mod init;
 mod publish;
+mod release_pr;
+mod status;
+mod summary;
 mod update;
+mod why;
 
+pub use add::AddArgs;
+pub use add::handle_add;
+pub use affected::AffectedArgs;
+pub use affected::handle_affected;
+pub use audit::AuditArgs;
+pub use audit::handle_audit;
+pub use changelog::ChangelogArgs;
+pub use changelog::handle_changelog;
 pub use changepacks::ChangepackArgs;
 pub use changepacks::handle_changepack;
 pub use changepacks::handle_changepack_with_prompter;
@@ -12,11 +34,32 @@ pub use check::CheckArgs;
 pub use check::handle_check;
 pub use config::ConfigArgs;
 pub use config::handle_config;
+pub use dedupe::DedupeArgs;
+pub use dedupe::handle_dedupe;
+pub use doctor::DoctorArgs;
+pub use doctor::handle_doctor;
+pub use from_pr::FromPrArgs;
+pub use from_pr::handle_from_pr;
+pub use graduate::GraduateArgs;
+pub use graduate::handle_graduate;
+pub use graduate::handle_graduate_with_prompter;
+pub use history::HistoryArgs;
+pub use history::handle_history;
+pub use hook::HookArgs;
+pub use hook::handle_hook;
 pub use init::InitArgs;
 pub use init::handle_init;
 pub use publish::PublishArgs;
 pub use publish::handle_publish;
 pub use publish::handle_publish_with_prompter;
+pub use release_pr::ReleasePrArgs;
+pub use release_pr::handle_release_pr;
+pub use status::StatusArgs;
+pub use status::handle_status;
+pub use summary::SummaryArgs;
+pub use summary::handle_summary;
 pub use update::UpdateArgs;
 pub use update::handle_update;
 pub use update::handle_update_with_prompter;
+pub use why::WhyArgs;
+pub use why::handle_why;
@@ -1,14 +1,33 @@
-use std::{collections::BTreeMap, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::Result;
-use changepacks_core::{Config, Language, Project, PublishOutput, PublishResult};
-use changepacks_utils::sort_by_dependencies;
+use changepacks_core::{
+    Config, HookContext, HookEvent, Language, Project, PublishOutput, PublishRecord, PublishResult,
+    ReleaseManifest, WebhookEvent, WebhookPackage, WebhookPayload, WebhookStatus, path_key,
+    publish::stream_publish_output, run_hooks,
+};
+use changepacks_registry::{Registry, RegistryClient};
+use changepacks_utils::{
+    RunLock, append_publish_record, apply_reverse_dependencies, bump_homebrew_formulas,
+    dependency_levels, gen_update_map, get_changepacks_dir, get_relative_path, output_digest,
+    read_publish_log, read_release_manifest, send_webhooks, sort_by_dependencies,
+};
 use clap::Args;
+use clap_complete::ArgValueCandidates;
+use colored::Colorize;
+use futures::StreamExt;
 
 use crate::{
-    CommandContext,
+    CommandContext, GlobalOptions,
+    completion::project_path_candidates,
     options::FormatOptions,
     prompter::{InquirePrompter, Prompter},
+    registries::registry_client,
 };
 
 #[derive(Args, Debug)]
@@ -31,25 +50,70 @@ pub struct PublishArgs {
     pub language: Vec<crate::options::CliLanguage>,
 
     /// Filter projects by relative path (e.g., packages/foo/package.json). Can be specified multiple times.
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCandidates::new(project_path_candidates))]
     pub project: Vec<String>,
+
+    /// Only publish packages whose latest version in the release manifest has no successful publish record yet
+    /// and is not on their registry
+    #[arg(long)]
+    pub only_unpublished: bool,
+
+    /// Skip packages whose current version already exists on npm, crates.io, PyPI, NuGet, pub.dev or JSR
+    #[arg(long, visible_alias = "skip-published")]
+    pub skip_existing: bool,
+
+    /// Refuse to publish while a changed project has no pending changepack
+    #[arg(long)]
+    pub strict: bool,
+
+    /// After publishing a dependency, wait up to this many seconds for its registry
+    /// to serve the new version before publishing its dependents
+    #[arg(long, value_name = "SECONDS")]
+    pub wait_for_registry: Option<u64>,
+
+    /// Publish even on a branch listed in the config `protectedBranches`
+    #[arg(long)]
+    pub allow_protected: bool,
+
+    /// Publish up to this many packages at once; a package still waits for the packages it depends on
+    #[arg(long, value_name = "N", default_value = "1")]
+    pub concurrency: NonZeroUsize,
+
+    /// Retry a failed publish command up to this many times (default: config `publishRetries`, or 0)
+    #[arg(long, value_name = "N")]
+    pub retries: Option<u32>,
+
+    /// Seconds to wait before the first retry, doubled after each further failure
+    /// (default: config `publishRetryDelay`, or 5)
+    #[arg(long, value_name = "SECONDS")]
+    pub retry_delay: Option<u64>,
 }
 
 /// Publish packages
 ///
 /// # Errors
 /// Returns error if command context creation or publishing fails.
-pub async fn handle_publish(args: &PublishArgs) -> Result<()> {
-    handle_publish_with_prompter(args, &InquirePrompter).await
+pub async fn handle_publish(args: &PublishArgs, global: &GlobalOptions) -> Result<()> {
+    handle_publish_with_prompter(args, global, &InquirePrompter).await
 }
 
 /// # Errors
 /// Returns error if project discovery, dependency sorting, or publishing fails.
 pub async fn handle_publish_with_prompter(
     args: &PublishArgs,
+    global: &GlobalOptions,
     prompter: &dyn Prompter,
 ) -> Result<()> {
-    let ctx = CommandContext::new(args.remote).await?;
+    let ctx = CommandContext::new(global, args.remote).await?;
+    if !args.dry_run {
+        super::update::guard_protected_branch(
+            &ctx.repo_root_path,
+            &ctx.config.protected_branches,
+            args.allow_protected,
+            "publish",
+        )
+        .await?;
+    }
 
     let mut projects: Vec<_> = ctx
         .project_finders
@@ -69,15 +133,84 @@ pub async fn handle_publish_with_prompter(
 
     // Filter by project relative path if specified
     if !args.project.is_empty() {
-        let normalized_args: Vec<String> =
-            args.project.iter().map(|p| p.replace('\\', "/")).collect();
+        let normalized_args: Vec<String> = args
+            .project
+            .iter()
+            .map(|p| path_key(Path::new(p)))
+            .collect();
+        projects.retain(|project| normalized_args.contains(&path_key(project.relative_path())));
+    }
+
+    // Projects with `publish: false` in config are versioned but never published
+    projects.retain(|project| {
+        let disabled = ctx.config.is_publish_disabled(
+            project.relative_path(),
+            project.name(),
+            project.language(),
+        );
+        if disabled && let FormatOptions::Stdout = args.format {
+            println!("Skipping {project} (publishing disabled in config)");
+        }
+        !disabled
+    });
+
+    let changepacks_dir = get_changepacks_dir(&global.current_dir()?)?;
+    // Held until every publish result is logged
+    let _lock = if args.dry_run {
+        None
+    } else {
+        Some(RunLock::acquire(&changepacks_dir, "publish")?)
+    };
+
+    if args.strict || ctx.config.require_changepacks {
+        let all_projects: Vec<&Project> = ctx
+            .project_finders
+            .iter()
+            .flat_map(|finder| finder.projects())
+            .collect();
+        let (mut update_map, prerelease_id) = gen_update_map(
+            &global.current_dir()?,
+            &ctx.config,
+            &all_projects,
+            &ctx.diff_refs,
+        )
+        .await?;
+        apply_reverse_dependencies(
+            &mut update_map,
+            &projects,
+            &ctx.repo_root_path,
+            prerelease_id.as_deref(),
+        );
+        super::update::require_changepacks(
+            &projects,
+            &update_map,
+            &ctx.repo_root_path,
+            &read_release_manifest(&changepacks_dir).await?,
+        )?;
+    }
+
+    // Keep only packages whose latest released version has not gone out yet
+    if args.only_unpublished {
+        let manifest = read_release_manifest(&changepacks_dir).await?;
+        let publish_log = read_publish_log(&changepacks_dir).await?;
         projects.retain(|project| {
-            let relative_path = project.relative_path().to_string_lossy();
-            let normalized_path = relative_path.replace('\\', "/");
-            normalized_args.contains(&normalized_path)
+            manifest
+                .latest_version(project.relative_path())
+                .is_some_and(|version| !publish_log.is_published(project.relative_path(), version))
         });
     }
 
+    // A version can reach the registry without a publish record, e.g. when the run died
+    // right after the upload, so a resume also asks the registry
+    if args.only_unpublished || args.skip_existing {
+        projects = skip_existing(
+            projects,
+            |registry| registry_client(&ctx.config, registry),
+            &args.format,
+        )
+        .await;
+    }
+
     // Sort projects by dependencies (no cloning, just reordering references)
     let projects = sort_by_dependencies(projects);
 
@@ -109,6 +242,16 @@ pub async fn handle_publish_with_prompter(
         return Ok(());
     }
 
+    let missing_env = missing_required_env(&projects, &ctx.config, |name| {
+        std::env::var_os(name).is_some_and(|value| !value.is_empty())
+    });
+    if !missing_env.is_empty() {
+        anyhow::bail!(
+            "Missing required environment variables:\n  {}",
+            missing_env.join("\n  ")
+        );
+    }
+
     // confirm
     let confirm = if args.yes {
         true
@@ -120,11 +263,50 @@ pub async fn handle_publish_with_prompter(
         return Ok(());
     }
 
-    let (result_map, failed_projects) =
-        execute_publish_loop(&projects, &ctx.config, &args.format).await;
+    let (result_map, failed_projects) = execute_publish_loop(
+        &projects,
+        &ctx.config,
+        &changepacks_dir,
+        &args.format,
+        args.wait_for_registry.map(Duration::from_secs),
+        args.concurrency.get(),
+        RetryPolicy {
+            retries: args.retries.unwrap_or(ctx.config.publish_retries),
+            delay: Duration::from_secs(args.retry_delay.unwrap_or(ctx.config.publish_retry_delay)),
+        },
+    )
+    .await;
 
     print_publish_failure_summary(&failed_projects, projects.len(), &args.format);
 
+    let published: Vec<&Project> = projects
+        .iter()
+        .copied()
+        .filter(|project| !failed_projects.contains(&format!("{project}")))
+        .collect();
+    match bump_homebrew_formulas(&ctx.repo_root_path, &ctx.config.homebrew, &published).await {
+        Ok(bumped) => {
+            if let FormatOptions::Stdout = args.format {
+                for (formula, version) in bumped {
+                    let formula = get_relative_path(&ctx.repo_root_path, &formula)?;
+                    println!(
+                        "Updated Homebrew formula {} to {version}",
+                        path_key(&formula)
+                    );
+                }
+            }
+        }
+        Err(e) => eprintln!("Failed to update Homebrew formulas: {e:#}"),
+    }
+
+    if !ctx.config.webhooks.is_empty() {
+        let manifest = read_release_manifest(&changepacks_dir).await?;
+        let payload = publish_webhook_payload(&projects, &failed_projects, &manifest);
+        if let Err(e) = send_webhooks(&ctx.config.webhooks, &payload).await {
+            eprintln!("{e}");
+        }
+    }
+
     if let FormatOptions::Json = args.format {
         println!("{}", serde_json::to_string_pretty(&result_map)?);
     }
@@ -140,6 +322,109 @@ pub async fn handle_publish_with_prompter(
     Ok(())
 }
 
+/// Projects lacking environment variables listed under `requiredEnv`, one `project: VARS` line each
+fn missing_required_env(
+    projects: &[&Project],
+    config: &Config,
+    is_set: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    projects
+        .iter()
+        .filter_map(|project| {
+            let missing: Vec<&str> = config
+                .required_env_vars(
+                    project.relative_path(),
+                    project.language(),
+                    project.registry(),
+                )
+                .into_iter()
+                .filter(|name| !is_set(name))
+                .collect();
+            (!missing.is_empty()).then(|| format!("{project}: {}", missing.join(", ")))
+        })
+        .collect()
+}
+
+/// Drop projects whose current version is already on their registry
+///
+/// Registries are queried concurrently. Projects without a name, version or supported
+/// registry are kept, as are projects whose registry could not be reached.
+async fn skip_existing<'a>(
+    projects: Vec<&'a Project>,
+    client: impl Fn(Registry) -> RegistryClient,
+    format: &FormatOptions,
+) -> Vec<&'a Project> {
+    let lookups = projects.iter().map(|project| {
+        let lookup = Registry::from_name(project.registry())
+            .zip(project.name())
+            .zip(project.version())
+            .map(|((registry, name), version)| (client(registry), name, version));
+        async move {
+            match lookup {
+                Some((client, name, version)) => Some(
+                    client
+                        .version_exists(name, version)
+                        .await
+                        .map(|exists| (exists, client.registry(), version)),
+                ),
+                None => None,
+            }
+        }
+    });
+    let results = futures::future::join_all(lookups).await;
+
+    projects
+        .into_iter()
+        .zip(results)
+        .filter(|(project, result)| match result {
+            Some(Ok((true, registry, version))) => {
+                if let FormatOptions::Stdout = format {
+                    println!("Skipping {project}: {version} already exists on {registry}");
+                }
+                false
+            }
+            Some(Err(e)) => {
+                eprintln!("Could not check {project} on its registry: {e:#}");
+                true
+            }
+            _ => true,
+        })
+        .map(|(project, _)| project)
+        .collect()
+}
+
+/// Webhook payload for a publish run, with notes taken from each package's latest release
+fn publish_webhook_payload(
+    projects: &[&Project],
+    failed_projects: &[String],
+    manifest: &ReleaseManifest,
+) -> WebhookPayload {
+    let packages = projects
+        .iter()
+        .map(|project| {
+            let status = if failed_projects.contains(&format!("{project}")) {
+                WebhookStatus::Failed
+            } else {
+                WebhookStatus::Published
+            };
+            let notes = manifest
+                .latest_release(project.relative_path())
+                .filter(|release| project.version() == Some(release.version()))
+                .map(|release| release.notes().to_vec())
+                .unwrap_or_default();
+            WebhookPackage::new(
+                project.name().map(str::to_string),
+                project.relative_path().to_path_buf(),
+                project.version().map(str::to_string),
+                notes,
+                status,
+            )
+            .with_metadata(project.metadata())
+        })
+        .collect();
+    WebhookPayload::new(WebhookEvent::Publish, packages)
+}
+
 fn print_projects_to_publish(projects: &[&Project], format: &FormatOptions) {
     if let FormatOptions::Stdout = format {
         println!("Projects to publish:");
@@ -312,65 +597,383 @@ async fn execute_dry_run_publish_loop(
     (result_map, failed_projects)
 }
 
-async fn execute_publish_loop(
-    projects: &[&Project],
+/// Append one publish attempt to the publish log right away, so an interrupted
+/// run still leaves a record of what already went out
+async fn record_publish_attempt(
+    changepacks_dir: &Path,
+    project: &Project,
+    success: bool,
+    output: Option<&PublishOutput>,
+    error: Option<String>,
+) {
+    let record = output_digest(
+        output.map_or("", |o| o.stdout.as_str()),
+        output.map_or("", |o| o.stderr.as_str()),
+    )
+    .map(|digest| {
+        PublishRecord::new(
+            project.name().map(str::to_string),
+            project.relative_path().to_path_buf(),
+            project.version().map(str::to_string),
+            project.registry().to_string(),
+            success,
+            digest,
+            error,
+        )
+    });
+    let result = match record {
+        Ok(record) => append_publish_record(changepacks_dir, record).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to record publish result for {project}: {e}");
+    }
+}
+
+/// How often `--wait-for-registry` polls the registry
+const REGISTRY_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Whether a project later in the publish order depends on `project`
+fn has_pending_dependents(project: &Project, remaining: &[&Project]) -> bool {
+    let path = project.relative_path().to_string_lossy();
+    remaining.iter().any(|other| {
+        other
+            .dependencies()
+            .iter()
+            .any(|dep| project.name() == Some(dep.as_str()) || *dep == path)
+    })
+}
+
+/// Wait until the registry serves the version of `project` just published
+///
+/// Projects without a name, version or queryable registry are not waited for.
+async fn wait_for_registry(
+    project: &Project,
     config: &Config,
+    timeout: Duration,
     format: &FormatOptions,
-) -> (BTreeMap<PathBuf, PublishResult>, Vec<String>) {
-    let mut result_map = BTreeMap::new();
-    let mut failed_projects: Vec<String> = Vec::new();
+) {
+    let Some(((registry, name), version)) = Registry::from_name(project.registry())
+        .zip(project.name())
+        .zip(project.version())
+    else {
+        return;
+    };
+    if let FormatOptions::Stdout = format {
+        println!(
+            "Waiting for {version} of {project} on {}...",
+            project.registry()
+        );
+    }
+    let visible = registry_client(config, registry)
+        .wait_for_version(name, version, timeout, REGISTRY_POLL_INTERVAL)
+        .await;
+    if !visible && let FormatOptions::Stdout = format {
+        eprintln!(
+            "{project} {version} is not visible on {} after {}s; publishing dependents anyway",
+            project.registry(),
+            timeout.as_secs()
+        );
+    }
+}
 
-    for project in projects {
-        if let FormatOptions::Stdout = format {
-            println!("Publishing {project}...");
+/// How often a failed publish command is run again
+#[derive(Debug, Clone, Copy, Default)]
+struct RetryPolicy {
+    /// Extra attempts after the first failure
+    retries: u32,
+    /// Delay before the first retry; doubled after each failed attempt
+    delay: Duration,
+}
+
+/// Results collected while a publish run is in flight
+#[derive(Default)]
+struct PublishState {
+    result_map: BTreeMap<PathBuf, PublishResult>,
+    failed_projects: Vec<String>,
+    // Fixed groups are all-or-nothing: once a member fails, the rest are held back
+    halted_groups: BTreeMap<usize, String>,
+    published_in_group: BTreeMap<usize, Vec<String>>,
+}
+
+/// Settings and shared state of one publish run
+struct PublishRun<'a> {
+    config: &'a Config,
+    changepacks_dir: &'a Path,
+    format: &'a FormatOptions,
+    wait_timeout: Option<Duration>,
+    /// Print command output as it arrives, prefixed with the package, instead of afterwards
+    stream_output: bool,
+    retry: RetryPolicy,
+    state: std::sync::Mutex<PublishState>,
+    /// Serializes appends to the publish log, which rewrite the whole file
+    log_lock: futures::lock::Mutex<()>,
+}
+
+impl PublishRun<'_> {
+    fn state(&self) -> std::sync::MutexGuard<'_, PublishState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn record_result(&self, project: &Project, result: PublishResult) {
+        if let FormatOptions::Json = self.format {
+            self.state()
+                .result_map
+                .insert(project.relative_path().to_path_buf(), result);
         }
-        match project.publish(config).await {
+    }
+
+    fn record_failure(&self, project: &Project) {
+        self.state().failed_projects.push(format!("{project}"));
+    }
+
+    /// Publish `project`, whose dependents (if any) are among `later`
+    async fn publish_project(&self, project: &Project, later: &[&Project]) {
+        let group = self.config.fixed_group_index(project.relative_path());
+        let failed_member = group.and_then(|g| self.state().halted_groups.get(&g).cloned());
+        if let Some(failed_member) = failed_member {
+            let reason = format!("Not published: fixed group member {failed_member} failed");
+            if let FormatOptions::Stdout = self.format {
+                eprintln!("Skipping {project}: {reason}");
+            }
+            self.record_result(
+                project,
+                PublishResult::new(false, Some(reason), String::new(), String::new()),
+            );
+            self.record_failure(project);
+            return;
+        }
+
+        let (publish_result, attempts) = match self.run_hooks(HookEvent::PrePublish, project).await
+        {
+            Ok(()) => self.publish_with_retries(project).await,
+            Err(e) => (Err(e), 0),
+        };
+        if let Some(group) = group {
+            let mut state = self.state();
+            if publish_result.as_ref().is_ok_and(|output| output.success) {
+                state
+                    .published_in_group
+                    .entry(group)
+                    .or_default()
+                    .push(format!("{project}"));
+            } else {
+                state.halted_groups.insert(group, format!("{project}"));
+            }
+        }
+        match publish_result {
             Ok(output) if output.success => {
-                if let FormatOptions::Stdout = format {
-                    print_publish_output(&output);
+                if let FormatOptions::Stdout = self.format {
+                    if !self.stream_output {
+                        print_publish_output(&output);
+                    }
                     println!("Successfully published {project}");
                 }
-                if let FormatOptions::Json = format {
-                    result_map.insert(
-                        project.relative_path().to_path_buf(),
-                        PublishResult::new(true, None, output.stdout, output.stderr),
-                    );
+                if let Err(e) = self.run_hooks(HookEvent::PostPublish, project).await {
+                    eprintln!("{} {e:#}", "warning:".yellow());
+                }
+                self.record_result(
+                    project,
+                    PublishResult::new(true, None, output.stdout, output.stderr)
+                        .with_attempts(attempts),
+                );
+                if let Some(timeout) = self.wait_timeout
+                    && has_pending_dependents(project, later)
+                {
+                    wait_for_registry(project, self.config, timeout, self.format).await;
                 }
             }
             Ok(output) => {
-                if let FormatOptions::Stdout = format {
-                    print_publish_output(&output);
+                if let FormatOptions::Stdout = self.format {
+                    if !self.stream_output {
+                        print_publish_output(&output);
+                    }
                     eprintln!("Failed to publish {project}");
                 }
-                if let FormatOptions::Json = format {
-                    result_map.insert(
-                        project.relative_path().to_path_buf(),
-                        PublishResult::new(false, None, output.stdout, output.stderr),
-                    );
-                }
-                failed_projects.push(format!("{project}"));
+                self.record_result(
+                    project,
+                    PublishResult::new(false, None, output.stdout, output.stderr)
+                        .with_attempts(attempts),
+                );
+                self.record_failure(project);
             }
             Err(e) => {
-                if let FormatOptions::Stdout = format {
+                if let FormatOptions::Stdout = self.format {
                     eprintln!("Failed to publish {project}: {e}");
                 }
-                if let FormatOptions::Json = format {
-                    result_map.insert(
-                        project.relative_path().to_path_buf(),
-                        PublishResult::new(
-                            false,
-                            Some(e.to_string()),
-                            String::new(),
-                            String::new(),
-                        ),
+                self.record_result(
+                    project,
+                    PublishResult::new(false, Some(e.to_string()), String::new(), String::new())
+                        .with_attempts(attempts),
+                );
+                self.record_failure(project);
+            }
+        }
+    }
+
+    /// Run the `event` hooks for `project` in its directory
+    async fn run_hooks(&self, event: HookEvent, project: &Project) -> Result<()> {
+        let context = HookContext {
+            package: project.name().map(str::to_string),
+            path: project.relative_path().to_path_buf(),
+            version: project.version().map(str::to_string),
+            ..HookContext::default()
+        };
+        let working_dir = project.path().parent().unwrap_or(self.changepacks_dir);
+        run_hooks(self.config, event, &context, working_dir).await
+    }
+
+    /// Run the publish command of `project`, retrying failures with a doubling delay
+    ///
+    /// Returns the last attempt's result and the number of attempts made. Every attempt is
+    /// recorded in the publish log.
+    async fn publish_with_retries(&self, project: &Project) -> (Result<PublishOutput>, u32) {
+        let mut delay = self.retry.delay;
+        let mut attempt = 1;
+        loop {
+            if let FormatOptions::Stdout = self.format {
+                if attempt == 1 {
+                    println!("Publishing {project}...");
+                } else {
+                    println!(
+                        "Publishing {project} (attempt {attempt} of {})...",
+                        self.retry.retries + 1
                     );
                 }
-                failed_projects.push(format!("{project}"));
             }
+            let result = if self.stream_output {
+                let prefix = format!(
+                    "[{}] ",
+                    project
+                        .name()
+                        .map_or_else(|| path_key(project.relative_path()), str::to_string)
+                );
+                stream_publish_output(prefix, project.publish(self.config)).await
+            } else {
+                project.publish(self.config).await
+            };
+            self.record_attempt(project, &result).await;
+            if result.as_ref().is_ok_and(|output| output.success) || attempt > self.retry.retries {
+                return (result, attempt);
+            }
+            if let FormatOptions::Stdout = self.format {
+                match &result {
+                    Ok(output) if !self.stream_output => print_publish_output(output),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("{e}"),
+                }
+                eprintln!("Failed to publish {project}; retrying in {delay:?}");
+            }
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+            attempt += 1;
         }
     }
 
-    (result_map, failed_projects)
+    async fn record_attempt(&self, project: &Project, result: &Result<PublishOutput>) {
+        let _guard = self.log_lock.lock().await;
+        match result {
+            Ok(output) => {
+                record_publish_attempt(
+                    self.changepacks_dir,
+                    project,
+                    output.success,
+                    Some(output),
+                    None,
+                )
+                .await;
+            }
+            Err(e) => {
+                record_publish_attempt(
+                    self.changepacks_dir,
+                    project,
+                    false,
+                    None,
+                    Some(e.to_string()),
+                )
+                .await;
+            }
+        }
+    }
+}
+
+/// Split a dependency level into units publishing one after another: each fixed group's
+/// members form one unit, so a failure still holds back the rest of the group
+fn publish_units<'a>(level: &[&'a Project], config: &Config) -> Vec<Vec<&'a Project>> {
+    let mut units: Vec<Vec<&Project>> = Vec::new();
+    let mut group_units: BTreeMap<usize, usize> = BTreeMap::new();
+    for &project in level {
+        match config.fixed_group_index(project.relative_path()) {
+            Some(group) => {
+                let unit = *group_units.entry(group).or_insert_with(|| {
+                    units.push(Vec::new());
+                    units.len() - 1
+                });
+                units[unit].push(project);
+            }
+            None => units.push(vec![project]),
+        }
+    }
+    units
+}
+
+/// Publish `projects` (in dependency order), up to `concurrency` at a time
+///
+/// Projects only start once every project they depend on has finished.
+async fn execute_publish_loop(
+    projects: &[&Project],
+    config: &Config,
+    changepacks_dir: &Path,
+    format: &FormatOptions,
+    wait_timeout: Option<Duration>,
+    concurrency: usize,
+    retry: RetryPolicy,
+) -> (BTreeMap<PathBuf, PublishResult>, Vec<String>) {
+    let run = PublishRun {
+        config,
+        changepacks_dir,
+        format,
+        wait_timeout,
+        stream_output: concurrency > 1 && matches!(format, FormatOptions::Stdout),
+        retry,
+        state: std::sync::Mutex::default(),
+        log_lock: futures::lock::Mutex::new(()),
+    };
+    let levels = dependency_levels(projects);
+    let ordered: Vec<&Project> = levels.concat();
+    let mut published = 0;
+    for level in &levels {
+        published += level.len();
+        let later = &ordered[published..];
+        let run = &run;
+        futures::stream::iter(publish_units(level, config))
+            .for_each_concurrent(concurrency, |unit| async move {
+                for project in unit {
+                    run.publish_project(project, later).await;
+                }
+            })
+            .await;
+    }
+
+    let state = run
+        .state
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let FormatOptions::Stdout = format {
+        for (group, failed_member) in &state.halted_groups {
+            let published = state
+                .published_in_group
+                .get(group)
+                .map_or_else(|| "none".to_string(), |names| names.join(", "));
+            eprintln!(
+                "Fixed group halted after {failed_member} failed; already published: {published}"
+            );
+        }
+    }
+
+    (state.result_map, state.failed_projects)
 }
 
 #[cfg(test)]
@@ -391,6 +994,7 @@ mod tests {
     use super::*;
     use changepacks_core::{Package, UpdateType};
     use clap::Parser;
+    use rstest::rstest;
     use std::collections::HashSet;
 
     #[derive(Parser)]
@@ -410,6 +1014,51 @@ mod tests {
         assert!(cli.publish.project.is_empty());
     }
 
+    #[test]
+    fn test_publish_args_with_only_unpublished() {
+        let cli = TestCli::parse_from(["test"]);
+        assert!(!cli.publish.only_unpublished);
+        let cli = TestCli::parse_from(["test", "--only-unpublished"]);
+        assert!(cli.publish.only_unpublished);
+    }
+
+    #[test]
+    fn test_publish_args_with_skip_existing() {
+        let cli = TestCli::parse_from(["test"]);
+        assert!(!cli.publish.skip_existing);
+        let cli = TestCli::parse_from(["test", "--skip-existing"]);
+        assert!(cli.publish.skip_existing);
+        let cli = TestCli::parse_from(["test", "--skip-published"]);
+        assert!(cli.publish.skip_existing);
+    }
+
+    #[test]
+    fn test_publish_args_with_wait_for_registry() {
+        let cli = TestCli::parse_from(["test"]);
+        assert_eq!(cli.publish.wait_for_registry, None);
+        let cli = TestCli::parse_from(["test", "--wait-for-registry", "120"]);
+        assert_eq!(cli.publish.wait_for_registry, Some(120));
+    }
+
+    #[test]
+    fn test_publish_args_with_concurrency() {
+        let cli = TestCli::parse_from(["test"]);
+        assert_eq!(cli.publish.concurrency.get(), 1);
+        let cli = TestCli::parse_from(["test", "--concurrency", "4"]);
+        assert_eq!(cli.publish.concurrency.get(), 4);
+        assert!(TestCli::try_parse_from(["test", "--concurrency", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_publish_args_with_retries() {
+        let cli = TestCli::parse_from(["test"]);
+        assert_eq!(cli.publish.retries, None);
+        assert_eq!(cli.publish.retry_delay, None);
+        let cli = TestCli::parse_from(["test", "--retries", "3", "--retry-delay", "10"]);
+        assert_eq!(cli.publish.retries, Some(3));
+        assert_eq!(cli.publish.retry_delay, Some(10));
+    }
+
     #[test]
     fn test_publish_args_with_dry_run() {
         let cli = TestCli::parse_from(["test", "--dry-run"]);
@@ -592,7 +1241,11 @@ mod tests {
         fn relative_path(&self) -> &std::path::Path {
             &self.relative_path
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> anyhow::Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> anyhow::Result<()> {
             Ok(())
         }
         fn is_changed(&self) -> bool {
@@ -620,6 +1273,50 @@ mod tests {
         }
     }
 
+    /// Serve one response with `status` and `body`, returning the base URL
+    fn serve(status: u16, body: &'static str) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        url
+    }
+
+    #[rstest]
+    #[case(200, r#"{"versions":{"1.0.0":{}}}"#, 0)]
+    #[case(200, r#"{"versions":{"0.9.0":{}}}"#, 1)]
+    #[case(404, "Not Found", 1)]
+    #[case(500, "oops", 1)]
+    #[tokio::test]
+    async fn test_skip_existing(
+        #[case] status: u16,
+        #[case] body: &'static str,
+        #[case] kept: usize,
+    ) {
+        let project = Project::Package(Box::new(FailSpawnPackage {
+            path: PathBuf::from("/nonexistent/package.json"),
+            relative_path: PathBuf::from("package.json"),
+        }));
+        let url = serve(status, body);
+        let projects = skip_existing(
+            vec![&project],
+            |registry| RegistryClient::with_base_url(registry, &url),
+            &FormatOptions::Stdout,
+        )
+        .await;
+        assert_eq!(projects.len(), kept);
+    }
+
     static EMPTY_DEPS: std::sync::LazyLock<HashSet<String>> =
         std::sync::LazyLock::new(HashSet::new);
 
@@ -632,9 +1329,18 @@ mod tests {
         let project = Project::Package(Box::new(pkg));
         let projects: Vec<&Project> = vec![&project];
         let config = Config::default();
-
-        let (result_map, failed) =
-            execute_publish_loop(&projects, &config, &FormatOptions::Stdout).await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        let (result_map, failed) = execute_publish_loop(
+            &projects,
+            &config,
+            temp_dir.path(),
+            &FormatOptions::Stdout,
+            None,
+            1,
+            RetryPolicy::default(),
+        )
+        .await;
 
         assert!(result_map.is_empty());
         assert_eq!(failed.len(), 1);
@@ -649,12 +1355,309 @@ mod tests {
         let project = Project::Package(Box::new(pkg));
         let projects: Vec<&Project> = vec![&project];
         let config = Config::default();
-
-        let (result_map, failed) =
-            execute_publish_loop(&projects, &config, &FormatOptions::Json).await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        let (result_map, failed) = execute_publish_loop(
+            &projects,
+            &config,
+            temp_dir.path(),
+            &FormatOptions::Json,
+            None,
+            1,
+            RetryPolicy::default(),
+        )
+        .await;
 
         assert_eq!(result_map.len(), 1);
         assert_eq!(failed.len(), 1);
+
+        let log = changepacks_utils::read_publish_log(temp_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(log.records().len(), 1);
+        assert!(!log.records()[0].success());
+        assert!(log.records()[0].error().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_execute_publish_loop_halts_fixed_group() {
+        let projects: Vec<Project> = ["a/package.json", "b/package.json", "c/package.json"]
+            .into_iter()
+            .map(|rel| {
+                Project::Package(Box::new(FailSpawnPackage {
+                    path: PathBuf::from("/nonexistent").join(rel),
+                    relative_path: PathBuf::from(rel),
+                }))
+            })
+            .collect();
+        let projects: Vec<&Project> = projects.iter().collect();
+        let config = Config {
+            fixed: vec![vec![
+                "a/package.json".to_string(),
+                "b/package.json".to_string(),
+            ]],
+            ..Default::default()
+        };
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        let (result_map, failed) = execute_publish_loop(
+            &projects,
+            &config,
+            temp_dir.path(),
+            &FormatOptions::Json,
+            None,
+            1,
+            RetryPolicy::default(),
+        )
+        .await;
+
+        assert_eq!(failed.len(), 3);
+        let skipped = serde_json::to_value(&result_map[&PathBuf::from("b/package.json")]).unwrap();
+        assert!(
+            skipped.to_string().contains("fixed group member"),
+            "b should be held back: {skipped}"
+        );
+        // a and c were attempted, b was never run
+        let log = changepacks_utils::read_publish_log(temp_dir.path())
+            .await
+            .unwrap();
+        let attempted: Vec<_> = log.records().iter().map(|r| r.path()).collect();
+        assert_eq!(
+            attempted,
+            vec![
+                std::path::Path::new("a/package.json"),
+                std::path::Path::new("c/package.json")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_publish_loop_concurrent_halts_fixed_group() {
+        let projects: Vec<Project> = ["a/package.json", "b/package.json", "c/package.json"]
+            .into_iter()
+            .map(|rel| {
+                Project::Package(Box::new(FailSpawnPackage {
+                    path: PathBuf::from("/nonexistent").join(rel),
+                    relative_path: PathBuf::from(rel),
+                }))
+            })
+            .collect();
+        let projects: Vec<&Project> = projects.iter().collect();
+        let config = Config {
+            fixed: vec![vec![
+                "a/package.json".to_string(),
+                "b/package.json".to_string(),
+            ]],
+            ..Default::default()
+        };
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        let (result_map, failed) = execute_publish_loop(
+            &projects,
+            &config,
+            temp_dir.path(),
+            &FormatOptions::Json,
+            None,
+            4,
+            RetryPolicy::default(),
+        )
+        .await;
+
+        assert_eq!(failed.len(), 3);
+        assert_eq!(result_map.len(), 3);
+        // The group still publishes member by member, so b is held back after a fails
+        let log = changepacks_utils::read_publish_log(temp_dir.path())
+            .await
+            .unwrap();
+        let mut attempted: Vec<_> = log.records().iter().map(|r| r.path()).collect();
+        attempted.sort();
+        assert_eq!(
+            attempted,
+            vec![
+                std::path::Path::new("a/package.json"),
+                std::path::Path::new("c/package.json")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_publish_loop_retries_failures() {
+        let project = Project::Package(Box::new(FailSpawnPackage {
+            path: PathBuf::from("/nonexistent/package.json"),
+            relative_path: PathBuf::from("package.json"),
+        }));
+        let projects: Vec<&Project> = vec![&project];
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        let (result_map, failed) = execute_publish_loop(
+            &projects,
+            &Config::default(),
+            temp_dir.path(),
+            &FormatOptions::Json,
+            None,
+            1,
+            RetryPolicy {
+                retries: 2,
+                delay: Duration::from_millis(1),
+            },
+        )
+        .await;
+
+        assert_eq!(failed.len(), 1);
+        let result = serde_json::to_value(&result_map[&PathBuf::from("package.json")]).unwrap();
+        assert_eq!(result["attempts"], 3);
+        let log = changepacks_utils::read_publish_log(temp_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(log.records().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_execute_publish_loop_pre_publish_hook_failure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = Project::Package(Box::new(FailSpawnPackage {
+            path: temp_dir.path().join("package.json"),
+            relative_path: PathBuf::from("package.json"),
+        }));
+        let projects: Vec<&Project> = vec![&project];
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "hooks": {"prePublish": "test \"$CHANGEPACKS_PACKAGE\" != fail-spawn"}
+        }))
+        .unwrap();
+
+        let (result_map, failed) = execute_publish_loop(
+            &projects,
+            &config,
+            temp_dir.path(),
+            &FormatOptions::Json,
+            None,
+            1,
+            RetryPolicy::default(),
+        )
+        .await;
+
+        assert_eq!(failed.len(), 1);
+        let result = serde_json::to_value(&result_map[&PathBuf::from("package.json")]).unwrap();
+        assert_eq!(result["attempts"], 0);
+        assert!(
+            result["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("prePublish hook"),
+            "{result}"
+        );
+        // The publish command never ran
+        let log = changepacks_utils::read_publish_log(temp_dir.path())
+            .await
+            .unwrap();
+        assert!(log.records().is_empty());
+    }
+
+    #[test]
+    fn test_publish_units() {
+        let projects: Vec<Project> = ["a/package.json", "b/package.json", "c/package.json"]
+            .into_iter()
+            .map(|rel| {
+                Project::Package(Box::new(FailSpawnPackage {
+                    path: PathBuf::from("/nonexistent").join(rel),
+                    relative_path: PathBuf::from(rel),
+                }))
+            })
+            .collect();
+        let level: Vec<&Project> = projects.iter().collect();
+        let config = Config {
+            fixed: vec![vec![
+                "a/package.json".to_string(),
+                "c/package.json".to_string(),
+            ]],
+            ..Default::default()
+        };
+
+        let units: Vec<Vec<&Path>> = publish_units(&level, &config)
+            .iter()
+            .map(|unit| unit.iter().map(|p| p.relative_path()).collect())
+            .collect();
+        assert_eq!(
+            units,
+            [
+                vec![Path::new("a/package.json"), Path::new("c/package.json")],
+                vec![Path::new("b/package.json")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_publish_webhook_payload() {
+        let projects: Vec<Project> = ["a/package.json", "b/package.json"]
+            .into_iter()
+            .map(|rel| {
+                Project::Package(Box::new(FailSpawnPackage {
+                    path: PathBuf::from("/nonexistent").join(rel),
+                    relative_path: PathBuf::from(rel),
+                }))
+            })
+            .collect();
+        let projects: Vec<&Project> = projects.iter().collect();
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(changepacks_core::ReleaseRecord::new(vec![
+            changepacks_core::ReleasedPackage::new(
+                Some("fail-spawn".to_string()),
+                PathBuf::from("a/package.json"),
+                Some("0.9.0".to_string()),
+                "1.0.0".to_string(),
+                UpdateType::Minor,
+                vec!["Add feature".to_string()],
+            ),
+        ]));
+
+        let payload = publish_webhook_payload(&projects, &[format!("{}", projects[1])], &manifest);
+
+        assert_eq!(payload.event(), WebhookEvent::Publish);
+        let packages = payload.packages();
+        assert_eq!(packages[0].status(), WebhookStatus::Published);
+        assert_eq!(packages[0].version(), Some("1.0.0"));
+        assert_eq!(packages[0].notes(), ["Add feature".to_string()]);
+        assert_eq!(packages[1].status(), WebhookStatus::Failed);
+        assert!(packages[1].notes().is_empty());
+    }
+
+    #[test]
+    fn test_missing_required_env() {
+        let projects: Vec<Project> = ["a/package.json", "b/package.json"]
+            .into_iter()
+            .map(|rel| {
+                Project::Package(Box::new(FailSpawnPackage {
+                    path: PathBuf::from("/nonexistent").join(rel),
+                    relative_path: PathBuf::from(rel),
+                }))
+            })
+            .collect();
+        let projects: Vec<&Project> = projects.iter().collect();
+        let config = Config {
+            required_env: std::collections::HashMap::from([
+                ("node".to_string(), vec!["NPM_TOKEN".to_string()]),
+                (
+                    "b/package.json".to_string(),
+                    vec!["SIGNING_KEY".to_string()],
+                ),
+            ]),
+            ..Config::default()
+        };
+
+        assert!(missing_required_env(&projects, &config, |_| true).is_empty());
+        assert!(missing_required_env(&projects, &Config::default(), |_| false).is_empty());
+
+        let missing = missing_required_env(&projects, &config, |name| name == "NPM_TOKEN");
+        assert_eq!(missing, vec![format!("{}: SIGNING_KEY", projects[1])]);
+
+        let missing = missing_required_env(&projects, &config, |_| false);
+        assert_eq!(
+            missing,
+            vec![
+                format!("{}: NPM_TOKEN", projects[0]),
+                format!("{}: NPM_TOKEN, SIGNING_KEY", projects[1]),
+            ]
+        );
     }
 
     /// Drives the `Err(e)` branch of `execute_dry_run_publish_loop`: the
@@ -716,7 +1719,11 @@ mod tests {
         fn relative_path(&self) -> &std::path::Path {
             &self.relative_path
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> anyhow::Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> anyhow::Result<()> {
             Ok(())
         }
         fn is_changed(&self) -> bool {
@@ -803,7 +1810,11 @@ mod tests {
         fn relative_path(&self) -> &std::path::Path {
             &self.relative_path
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> anyhow::Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> anyhow::Result<()> {
             Ok(())
         }
         fn is_changed(&self) -> bool {
@@ -909,7 +1920,11 @@ mod tests {
         fn relative_path(&self) -> &std::path::Path {
             &self.relative_path
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> anyhow::Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> anyhow::Result<()> {
             Ok(())
         }
         fn is_changed(&self) -> bool {
@@ -995,6 +2010,23 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_has_pending_dependents() {
+        let leaf = make_rust_mock("crate-leaf", "crates/leaf/Cargo.toml", &[]);
+        let by_name = make_rust_mock("crate-a", "crates/a/Cargo.toml", &["crate-leaf"]);
+        let by_path = make_rust_mock(
+            "crate-b",
+            "crates/b/Cargo.toml",
+            &["crates/leaf/Cargo.toml"],
+        );
+        let other = make_rust_mock("crate-c", "crates/c/Cargo.toml", &["serde"]);
+
+        assert!(has_pending_dependents(&leaf, &[&other, &by_name]));
+        assert!(has_pending_dependents(&leaf, &[&by_path]));
+        assert!(!has_pending_dependents(&leaf, &[&other]));
+        assert!(!has_pending_dependents(&leaf, &[]));
+    }
+
     /// Integration check for stdout format: when both `parent` and `leaf`
     /// are in the publish batch and parent depends on leaf, parent must be
     /// skipped (no failure surfaced) and leaf must dry-run normally.
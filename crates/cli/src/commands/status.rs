@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use changepacks_core::{ChangePackResultLog, Project, ReleaseManifest, UpdateType, path_key};
+use changepacks_utils::{
+    apply_reverse_dependencies, gen_update_map, get_changepacks_dir, get_relative_path,
+    read_release_manifest,
+};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{CommandContext, GlobalOptions, options::FormatOptions};
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Report changed projects without a pending changepack, failing when any are missing"
+)]
+pub struct StatusArgs {
+    /// Compare with the remote base branch
+    #[arg(short, long)]
+    pub remote: bool,
+
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+}
+
+/// How a changed or scheduled project is accounted for
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(super) enum Coverage {
+    /// A changepack (or a dependency or `fixed` group rule) bumps the project
+    Pending,
+    /// The project's current version is the one last recorded in the release manifest
+    Released,
+    /// Changed without a pending changepack
+    Missing,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ProjectStatus {
+    pub name: Option<String>,
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub coverage: Coverage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<UpdateType>,
+}
+
+impl ProjectStatus {
+    fn label(&self) -> String {
+        format!(
+            "{} ({})",
+            self.name.as_deref().unwrap_or("noname"),
+            path_key(&self.path)
+        )
+    }
+}
+
+/// Report changepack coverage of the changed projects
+///
+/// # Errors
+/// Returns error if project discovery or reading `.changepacks` fails, or when a changed
+/// project has no pending changepack.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new`; `changepack_coverage` and
+/// `format_status` are unit tested and the command is covered by the cli integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_status(args: &StatusArgs, global: &GlobalOptions) -> Result<()> {
+    let ctx = CommandContext::new(global, args.remote).await?;
+    let mut projects: Vec<&Project> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .collect();
+    projects.sort();
+    let (mut update_map, prerelease_id) =
+        gen_update_map(&ctx.repo_root_path, &ctx.config, &projects, &ctx.diff_refs).await?;
+    apply_reverse_dependencies(
+        &mut update_map,
+        &projects,
+        &ctx.repo_root_path,
+        prerelease_id.as_deref(),
+    );
+    let manifest = read_release_manifest(&get_changepacks_dir(&ctx.repo_root_path)?).await?;
+
+    let statuses = changepack_coverage(&projects, &update_map, &ctx.repo_root_path, &manifest)?;
+    match args.format {
+        FormatOptions::Stdout => print!("{}", format_status(&statuses)),
+        FormatOptions::Json => println!("{}", serde_json::to_string_pretty(&statuses)?),
+    }
+    let missing = statuses
+        .iter()
+        .filter(|status| status.coverage == Coverage::Missing)
+        .count();
+    if missing > 0 {
+        anyhow::bail!(
+            "{missing} changed project{} without a changepack (run `changepacks` to record one)",
+            if missing == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
+
+/// Coverage of every project that changed or has a pending bump, in `projects` order
+///
+/// # Errors
+/// Returns error if a project path is outside the repository.
+pub(super) fn changepack_coverage(
+    projects: &[&Project],
+    update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    repo_root_path: &Path,
+    manifest: &ReleaseManifest,
+) -> Result<Vec<ProjectStatus>> {
+    let mut statuses = Vec::new();
+    for project in projects {
+        let path = get_relative_path(repo_root_path, project.path())?;
+        let update_type = update_map.get(&path).map(|(update_type, _)| *update_type);
+        let coverage = if update_type.is_some() {
+            Coverage::Pending
+        } else if !project.is_changed() {
+            continue;
+        } else {
+            match project.version() {
+                // Versionless projects are never released, so they need no changepack
+                None => continue,
+                Some(version) if manifest.latest_version(&path) == Some(version) => {
+                    Coverage::Released
+                }
+                Some(_) => Coverage::Missing,
+            }
+        };
+        statuses.push(ProjectStatus {
+            name: project.name().map(str::to_string),
+            path,
+            version: project.version().map(str::to_string),
+            coverage,
+            update_type,
+        });
+    }
+    Ok(statuses)
+}
+
+/// `name (path)` of every changed project missing a changepack
+pub(super) fn missing_changepacks(statuses: &[ProjectStatus]) -> Vec<String> {
+    statuses
+        .iter()
+        .filter(|status| status.coverage == Coverage::Missing)
+        .map(ProjectStatus::label)
+        .collect()
+}
+
+fn format_status(statuses: &[ProjectStatus]) -> String {
+    if statuses.is_empty() {
+        return "No changed projects\n".to_string();
+    }
+    let mut out = String::new();
+    for status in statuses {
+        let detail = match (status.coverage, status.update_type) {
+            (Coverage::Pending, Some(update_type)) => update_type.to_string().normal(),
+            (Coverage::Released, _) => "released".bright_black(),
+            _ => "missing changepack".red(),
+        };
+        let mark = if status.coverage == Coverage::Missing {
+            "✗".red()
+        } else {
+            "✓".green()
+        };
+        out.push_str(&format!(
+            "{mark} {} {} {detail}\n",
+            status.name.as_deref().unwrap_or("noname").bold(),
+            path_key(&status.path).bright_black()
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use changepacks_core::{Language, Package, ReleaseRecord, ReleasedPackage};
+    use clap::Parser;
+    use std::collections::HashSet;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        status: StatusArgs,
+    }
+
+    #[derive(Debug)]
+    struct MockPackage {
+        name: String,
+        version: Option<String>,
+        path: PathBuf,
+        dependencies: HashSet<String>,
+        changed: bool,
+    }
+
+    #[async_trait]
+    impl Package for MockPackage {
+        fn name(&self) -> Option<&str> {
+            Some(&self.name)
+        }
+        fn version(&self) -> Option<&str> {
+            self.version.as_deref()
+        }
+        fn path(&self) -> &Path {
+            &self.path
+        }
+        fn relative_path(&self) -> &Path {
+            &self.path
+        }
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
+            Ok(())
+        }
+        fn is_changed(&self) -> bool {
+            self.changed
+        }
+        fn language(&self) -> Language {
+            Language::Node
+        }
+        fn dependencies(&self) -> &HashSet<String> {
+            &self.dependencies
+        }
+        fn add_dependency(&mut self, dependency: &str) {
+            self.dependencies.insert(dependency.to_string());
+        }
+        fn set_changed(&mut self, changed: bool) {
+            self.changed = changed;
+        }
+        fn default_publish_command(&self) -> String {
+            "echo publish".to_string()
+        }
+        fn default_dry_run_publish_command(&self) -> Option<String> {
+            None
+        }
+    }
+
+    fn package(name: &str, version: Option<&str>, changed: bool) -> Project {
+        Project::Package(Box::new(MockPackage {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+            path: PathBuf::from(format!("/repo/{name}/package.json")),
+            dependencies: HashSet::new(),
+            changed,
+        }))
+    }
+
+    #[test]
+    fn test_status_args() {
+        let cli = TestCli::parse_from(["status"]);
+        assert!(!cli.status.remote);
+        assert!(matches!(cli.status.format, FormatOptions::Stdout));
+        let cli = TestCli::parse_from(["status", "--remote", "--format", "json"]);
+        assert!(cli.status.remote);
+        assert!(matches!(cli.status.format, FormatOptions::Json));
+    }
+
+    #[test]
+    fn test_changepack_coverage() {
+        let projects = [
+            package("covered", Some("1.0.0"), true),
+            package("missing", Some("1.0.0"), true),
+            package("released", Some("1.1.0"), true),
+            package("scheduled", Some("1.0.0"), false),
+            package("untouched", Some("1.0.0"), false),
+            package("versionless", None, true),
+        ];
+        let projects: Vec<&Project> = projects.iter().collect();
+        let update_map = HashMap::from([
+            (
+                PathBuf::from("covered/package.json"),
+                (UpdateType::Minor, vec![]),
+            ),
+            (
+                PathBuf::from("scheduled/package.json"),
+                (UpdateType::Patch, vec![]),
+            ),
+        ]);
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(ReleaseRecord::new(vec![ReleasedPackage::new(
+            Some("released".to_string()),
+            PathBuf::from("released/package.json"),
+            Some("1.0.0".to_string()),
+            "1.1.0".to_string(),
+            UpdateType::Minor,
+            vec![],
+        )]));
+
+        let statuses =
+            changepack_coverage(&projects, &update_map, Path::new("/repo"), &manifest).unwrap();
+        let summary: Vec<(&str, Coverage, Option<UpdateType>)> = statuses
+            .iter()
+            .map(|status| {
+                (
+                    status.name.as_deref().unwrap(),
+                    status.coverage,
+                    status.update_type,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("covered", Coverage::Pending, Some(UpdateType::Minor)),
+                ("missing", Coverage::Missing, None),
+                ("released", Coverage::Released, None),
+                ("scheduled", Coverage::Pending, Some(UpdateType::Patch)),
+            ]
+        );
+        assert_eq!(
+            missing_changepacks(&statuses),
+            ["missing (missing/package.json)"]
+        );
+    }
+
+    #[test]
+    fn test_format_status() {
+        assert_eq!(format_status(&[]), "No changed projects\n");
+        let statuses = [
+            ProjectStatus {
+                name: Some("core".to_string()),
+                path: PathBuf::from("core/package.json"),
+                version: Some("1.0.0".to_string()),
+                coverage: Coverage::Pending,
+                update_type: Some(UpdateType::Patch),
+            },
+            ProjectStatus {
+                name: Some("web".to_string()),
+                path: PathBuf::from("web/package.json"),
+                version: Some("1.0.0".to_string()),
+                coverage: Coverage::Missing,
+                update_type: None,
+            },
+        ];
+        let output = format_status(&statuses);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("core") && lines[0].contains("Patch"));
+        assert!(lines[1].contains("web") && lines[1].contains("missing changepack"));
+    }
+
+    #[test]
+    fn test_project_status_json() {
+        let status = ProjectStatus {
+            name: Some("web".to_string()),
+            path: PathBuf::from("web/package.json"),
+            version: None,
+            coverage: Coverage::Missing,
+            update_type: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            serde_json::json!({"name": "web", "path": "web/package.json", "coverage": "missing"})
+        );
+    }
+}
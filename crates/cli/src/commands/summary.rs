@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use changepacks_core::{ChangePackResultLog, Config, Project, ReleaseManifest, UpdateType};
+use changepacks_utils::{
+    apply_reverse_dependencies, gen_update_map, get_changepacks_dir, next_version_with_id,
+    read_release_manifest,
+};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{CommandContext, GlobalOptions, options::FormatOptions};
+
+#[derive(Args, Debug)]
+#[command(about = "Show one table of every package with its version, changes and last release")]
+pub struct SummaryArgs {
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+}
+
+/// One package in the summary table
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SummaryRow {
+    name: Option<String>,
+    path: PathBuf,
+    language: &'static str,
+    version: Option<String>,
+    changed: bool,
+    /// Bump scheduled by pending changepacks, if any
+    pending: Option<UpdateType>,
+    next_version: Option<String>,
+    /// Date of the newest release in `.changepacks/releases.json` that bumped the package
+    last_release: Option<DateTime<Utc>>,
+}
+
+/// Print every package with its version, change status, pending bump and last release
+///
+/// # Errors
+/// Returns error if project discovery, reading `.changepacks`, or a version bump fails.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new`; `summary_rows` and
+/// `format_summary` are unit tested and the command is covered by the cli integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_summary(args: &SummaryArgs, global: &GlobalOptions) -> Result<()> {
+    let ctx = CommandContext::new(global, false).await?;
+    let mut projects: Vec<&Project> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .collect();
+    projects.sort();
+    let (mut update_map, prerelease_id) =
+        gen_update_map(&ctx.repo_root_path, &ctx.config, &projects, &ctx.diff_refs).await?;
+    apply_reverse_dependencies(
+        &mut update_map,
+        &projects,
+        &ctx.repo_root_path,
+        prerelease_id.as_deref(),
+    );
+    let manifest = read_release_manifest(&get_changepacks_dir(&ctx.repo_root_path)?).await?;
+    let rows = summary_rows(&projects, &update_map, prerelease_id.as_deref(), &manifest)?;
+
+    match args.format {
+        FormatOptions::Stdout => print!("{}", format_summary(&rows, &ctx.config)),
+        FormatOptions::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+    }
+    Ok(())
+}
+
+/// Collect a summary row for each project, keyed into `update_map` by relative path, with
+/// prereleases bumped to `prerelease_id`
+fn summary_rows(
+    projects: &[&Project],
+    update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    prerelease_id: Option<&str>,
+    manifest: &ReleaseManifest,
+) -> Result<Vec<SummaryRow>> {
+    projects
+        .iter()
+        .map(|project| {
+            let path = project.relative_path().to_path_buf();
+            let pending = update_map.get(&path).map(|(update_type, _)| *update_type);
+            let next_version = pending
+                .map(|update_type| {
+                    next_version_with_id(
+                        project.version().unwrap_or("0.0.0"),
+                        update_type,
+                        prerelease_id,
+                    )
+                })
+                .transpose()?;
+            Ok(SummaryRow {
+                name: project.name().map(str::to_string),
+                language: project.language().publish_key(),
+                version: project.version().map(str::to_string),
+                changed: project.is_changed(),
+                pending,
+                next_version,
+                last_release: manifest.last_released_at(&path).copied(),
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Render the rows as an aligned table with a header line
+fn format_summary(rows: &[SummaryRow], config: &Config) -> String {
+    let header = [
+        "PACKAGE",
+        "LANGUAGE",
+        "VERSION",
+        "CHANGED",
+        "PENDING",
+        "LAST RELEASE",
+    ];
+    let cells: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.name
+                    .clone()
+                    .unwrap_or_else(|| row.path.display().to_string()),
+                row.language.to_string(),
+                row.version.clone().unwrap_or_else(|| "-".to_string()),
+                if row.changed { "yes" } else { "no" }.to_string(),
+                match (row.pending, &row.next_version) {
+                    (Some(update_type), Some(next)) => format!(
+                        "{} -> {next}",
+                        match update_type {
+                            UpdateType::Major => "major",
+                            UpdateType::Minor => "minor",
+                            UpdateType::Patch => "patch",
+                            UpdateType::Prerelease => "prerelease",
+                        }
+                    ),
+                    _ => "-".to_string(),
+                },
+                row.last_release
+                    .map_or_else(|| "never".to_string(), |date| config.format_date(&date)),
+            ]
+        })
+        .collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| row[column].chars().count())
+                .chain(std::iter::once(header[column].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |values: Vec<&str>| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{value:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = format!("{}\n", line(header.to_vec()).bold());
+    for row in &cells {
+        out.push_str(&line(row.iter().map(String::as_str).collect()));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::{ReleaseRecord, ReleasedPackage};
+    use changepacks_node::package::NodePackage;
+
+    fn node(name: &str, version: &str) -> Project {
+        Project::Package(Box::new(NodePackage::new(
+            Some(name.to_string()),
+            Some(version.to_string()),
+            PathBuf::from(format!("/repo/{name}/package.json")),
+            PathBuf::from(format!("{name}/package.json")),
+        )))
+    }
+
+    #[test]
+    fn test_summary_rows() {
+        let core = node("core", "1.2.0");
+        let web = node("web", "0.1.0");
+        let update_map = HashMap::from([(
+            PathBuf::from("core/package.json"),
+            (UpdateType::Minor, vec![]),
+        )]);
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(ReleaseRecord::new(vec![ReleasedPackage::new(
+            Some("core".to_string()),
+            PathBuf::from("core/package.json"),
+            Some("1.1.0".to_string()),
+            "1.2.0".to_string(),
+            UpdateType::Minor,
+            vec![],
+        )]));
+
+        let rows = summary_rows(&[&core, &web], &update_map, None, &manifest).unwrap();
+        assert_eq!(rows[0].pending, Some(UpdateType::Minor));
+        assert_eq!(rows[0].next_version.as_deref(), Some("1.3.0"));
+        assert!(rows[0].last_release.is_some());
+        assert_eq!(rows[0].language, "node");
+        assert_eq!(rows[1].pending, None);
+        assert_eq!(rows[1].next_version, None);
+        assert_eq!(rows[1].last_release, None);
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let rows = [
+            SummaryRow {
+                name: Some("core".to_string()),
+                path: PathBuf::from("core/package.json"),
+                language: "node",
+                version: Some("1.2.0".to_string()),
+                changed: true,
+                pending: Some(UpdateType::Minor),
+                next_version: Some("1.3.0".to_string()),
+                last_release: Some(
+                    DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+                        .unwrap()
+                        .with_timezone(&Utc),
+                ),
+            },
+            SummaryRow {
+                name: None,
+                path: PathBuf::from("tools/pyproject.toml"),
+                language: "python",
+                version: None,
+                changed: false,
+                pending: None,
+                next_version: None,
+                last_release: None,
+            },
+        ];
+        let output = format_summary(&rows, &Config::default());
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].contains(
+            "PACKAGE               LANGUAGE  VERSION  CHANGED  PENDING         LAST RELEASE"
+        ));
+        assert_eq!(
+            lines[1..],
+            [
+                "core                  node      1.2.0    yes      minor -> 1.3.0  2025-06-01",
+                "tools/pyproject.toml  python    -        no       -               never",
+            ]
+        );
+    }
+}
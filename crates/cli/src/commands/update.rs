@@ -5,23 +5,32 @@ use std::{
 
 use anyhow::Result;
 use changepacks_core::{
-    ChangePackResultLog, Language, Package, Project, ProjectFinder, UpdateType, Workspace,
+    ChangePackResultLog, Config, GitConfig, HookContext, HookEvent, Language, Package, Project,
+    ProjectFinder, PublishLog, RELEASE_MANIFEST_FILE, ReleaseManifest, ReleaseRecord,
+    ReleasedPackage, UpdateType, WebhookPayload, Workspace, path_key, run_hooks,
 };
+use changepacks_registry::{Registry, RegistryClient};
 use changepacks_utils::{
-    apply_reverse_dependencies, clear_update_logs, display_update, find_project_dirs,
-    gen_changepack_result_map, gen_update_map, get_changepacks_dir, get_relative_path,
+    FileChange, RUN_LOCK_FILE, RunLock, append_release_record, apply_file_changes,
+    apply_reverse_dependencies, capture_writes, clear_update_logs, compare_versions,
+    create_git_tag, discover_projects, format_update_rows, gen_changepack_result_map,
+    gen_update_map, get_changepacks_dir, get_relative_path, is_changepack_log_entry, list_git_tags,
+    next_version_with_id, read_publish_log, read_release_manifest, render_changepack_notes,
+    run_git, send_webhooks, unified_diff, write_changelog,
 };
 use clap::Args;
+use colored::Colorize;
 
 use crate::{
-    CommandContext,
+    CommandContext, GlobalOptions,
     finders::get_finders,
     options::{CliLanguage, FormatOptions},
     prompter::{InquirePrompter, Prompter},
+    registries::registry_client,
 };
 
-type UpdateProjectMut<'a> = (&'a mut Project, UpdateType);
-type WorkspaceRef<'a> = &'a dyn Workspace;
+pub(super) type UpdateProjectMut<'a> = (&'a mut Project, UpdateType);
+pub(super) type WorkspaceRef<'a> = &'a dyn Workspace;
 
 #[derive(Args, Debug)]
 #[command(about = "Check project status")]
@@ -41,41 +50,83 @@ pub struct UpdateArgs {
     /// Filter projects by language. Can be specified multiple times to include multiple languages.
     #[arg(short, long, value_enum)]
     pub language: Vec<CliLanguage>,
+
+    /// Apply updates even if a new version would not be above the last released, published or tagged version
+    #[arg(long)]
+    pub force: bool,
+
+    /// Refuse to update while a changed project has no pending changepack
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Also refuse new versions that are not above the latest one on the package's registry
+    /// (npm, crates.io, PyPI, NuGet, pub.dev or JSR)
+    #[arg(long)]
+    pub registry: bool,
+
+    /// Update even on a branch listed in the config `protectedBranches`
+    #[arg(long)]
+    pub allow_protected: bool,
+
+    /// Commit the release and create an annotated git tag on that commit for every updated
+    /// package (config `git.tag`)
+    #[arg(long)]
+    pub tag: bool,
+
+    /// Put this in front of the tag names instead of the config `git.tagPrefix`; implies --tag
+    #[arg(long)]
+    pub tag_prefix: Option<String>,
 }
 
 /// Update project version
 ///
 /// # Errors
 /// Returns error if command context creation or version update fails.
-pub async fn handle_update(args: &UpdateArgs) -> Result<()> {
-    handle_update_with_prompter(args, &InquirePrompter).await
+pub async fn handle_update(args: &UpdateArgs, global: &GlobalOptions) -> Result<()> {
+    handle_update_with_prompter(args, global, &InquirePrompter).await
 }
 
 /// # Errors
 /// Returns error if reading changepack logs, updating versions, or writing results fails.
 ///
 /// Excluded from coverage: orchestrates `CommandContext::new` and
-/// `find_project_dirs` (real git tree walk) plus an interactive
+/// `discover_projects` (real git tree walk) plus an interactive
 /// `prompter.confirm(...)`; underlying helpers (`apply_reverse_dependencies`,
-/// `gen_update_map`, `display_update`) are covered by their own tests.
+/// `gen_update_map`, `format_update_rows`) are covered by their own tests.
 #[cfg(not(tarpaulin_include))]
-pub async fn handle_update_with_prompter(args: &UpdateArgs, prompter: &dyn Prompter) -> Result<()> {
-    let ctx = CommandContext::new(args.remote).await?;
-    let changepacks_dir = get_changepacks_dir(&CommandContext::current_dir()?)?;
-    let mut update_map = gen_update_map(&CommandContext::current_dir()?, &ctx.config).await?;
-
+pub async fn handle_update_with_prompter(
+    args: &UpdateArgs,
+    global: &GlobalOptions,
+    prompter: &dyn Prompter,
+) -> Result<()> {
+    let ctx = CommandContext::new(global, args.remote).await?;
+    if !args.dry_run {
+        guard_protected_branch(
+            &ctx.repo_root_path,
+            &ctx.config.protected_branches,
+            args.allow_protected,
+            "update",
+        )
+        .await?;
+    }
+    let changepacks_dir = get_changepacks_dir(&global.current_dir()?)?;
+    // Held until the versions are written and the changepack logs cleared
+    let _lock = if args.dry_run {
+        None
+    } else {
+        Some(RunLock::acquire(&changepacks_dir, "update")?)
+    };
     let mut project_finders = ctx.project_finders;
-    let mut all_finders = get_finders();
+    let mut all_finders = get_finders(&ctx.config);
 
-    // Need a second git repo reference for the all_finders, but since CommandContext already called find_project_dirs
+    // Need a second discovery pass for the all_finders, but since CommandContext already called discover_projects
     // we use an empty config for all_finders which won't filter anything
-    let current_dir = CommandContext::current_dir()?;
-    let repo = changepacks_utils::find_current_git_repo(&current_dir)?;
-    find_project_dirs(
-        &repo,
+    discover_projects(
+        &global.current_dir()?,
         &mut all_finders,
         &changepacks_core::Config::default(),
         args.remote,
+        &ctx.diff_refs,
     )
     .await?;
 
@@ -84,7 +135,32 @@ pub async fn handle_update_with_prompter(args: &UpdateArgs, prompter: &dyn Promp
         .iter()
         .flat_map(|finder| finder.projects())
         .collect();
-    apply_reverse_dependencies(&mut update_map, &all_projects, &ctx.repo_root_path);
+    let (mut update_map, prerelease_id) = gen_update_map(
+        &global.current_dir()?,
+        &ctx.config,
+        &all_projects,
+        &ctx.diff_refs,
+    )
+    .await?;
+    apply_reverse_dependencies(
+        &mut update_map,
+        &all_projects,
+        &ctx.repo_root_path,
+        prerelease_id.as_deref(),
+    );
+
+    if args.strict || ctx.config.require_changepacks {
+        let projects: Vec<&Project> = project_finders
+            .iter()
+            .flat_map(|finder| finder.projects())
+            .collect();
+        require_changepacks(
+            &projects,
+            &update_map,
+            &ctx.repo_root_path,
+            &read_release_manifest(&changepacks_dir).await?,
+        )?;
+    }
 
     // Merge workspace-inherited package updates into workspace entries
     merge_workspace_inherited_updates(&mut update_map, &all_finders, &ctx.repo_root_path);
@@ -125,34 +201,206 @@ pub async fn handle_update_with_prompter(args: &UpdateArgs, prompter: &dyn Promp
     )?;
 
     if let FormatOptions::Stdout = args.format {
+        let mut rows = Vec::with_capacity(update_projects.len());
+        let mut notes = Vec::with_capacity(update_projects.len());
         for (project, update_type) in &update_projects {
-            println!(
-                "{} {}",
-                project,
-                display_update(project.version(), *update_type)?
-            );
+            let logs = update_map
+                .get(&get_relative_path(&ctx.repo_root_path, project.path())?)
+                .map_or(&[][..], |(_, logs)| logs.as_slice());
+            rows.push((
+                project.to_string(),
+                project.version(),
+                Some(*update_type),
+                logs.len(),
+            ));
+            notes.push(render_changepack_notes(logs, 4));
+        }
+        for (line, notes) in format_update_rows(&rows, prerelease_id.as_deref())?
+            .iter()
+            .zip(notes)
+        {
+            println!("{line}");
+            print!("{notes}");
         }
     }
 
-    if args.dry_run {
-        args.format.print("Dry run, no updates will be made", "{}");
-        return Ok(());
+    let tags = list_git_tags(&ctx.repo_root_path).await;
+    let registry_versions = if args.registry {
+        registry_versions(&update_projects, &ctx.repo_root_path, |registry| {
+            registry_client(&ctx.config, registry)
+        })
+        .await?
+    } else {
+        HashMap::new()
+    };
+    let non_monotonic = non_monotonic_updates(
+        &update_projects,
+        prerelease_id.as_deref(),
+        &ctx.repo_root_path,
+        &ShippedVersions {
+            manifest: &read_release_manifest(&changepacks_dir).await?,
+            publish_log: &read_publish_log(&changepacks_dir).await?,
+            tags: &tags,
+            registry_versions: &registry_versions,
+            single_project: all_projects.len() == 1,
+        },
+    )?;
+    if !non_monotonic.is_empty() {
+        if !args.force {
+            anyhow::bail!(
+                "Refusing to move versions backwards (use --force to update anyway):\n  {}",
+                non_monotonic.join("\n  ")
+            );
+        }
+        for problem in &non_monotonic {
+            eprintln!("{} {problem}", "warning:".yellow());
+        }
     }
 
-    // confirm
-    let confirm = if args.yes {
-        true
-    } else {
-        prompter.confirm("Are you sure you want to update the projects?")?
+    let tag_prefix = args
+        .tag_prefix
+        .as_deref()
+        .unwrap_or(&ctx.config.git.tag_prefix);
+    let tag_all = args.tag || args.tag_prefix.is_some() || ctx.config.git.tag;
+    let planned_tags = planned_tags(
+        &update_projects,
+        prerelease_id.as_deref(),
+        &ctx.config.git,
+        tag_prefix,
+        tag_all,
+        &tags,
+    )?;
+
+    let changelog_date = ctx
+        .config
+        .changelog
+        .then(|| ctx.config.format_date(&chrono::Utc::now()));
+    let release_writes = |update_projects| {
+        write_release(
+            update_projects,
+            &workspace_projects,
+            &update_map,
+            prerelease_id.as_deref(),
+            &ctx.repo_root_path,
+            changelog_date.as_deref(),
+        )
     };
 
-    if !confirm {
-        args.format.print("Update cancelled", "{}");
+    if args.dry_run {
+        // Run the real write pipeline with writes captured in memory to preview file changes
+        let (result, changes) = capture_writes(release_writes(&mut update_projects)).await;
+        result?;
+        match args.format {
+            FormatOptions::Stdout => {
+                print_file_changes(&changes, &ctx.repo_root_path)?;
+                print_planned_tags(&planned_tags);
+                println!("Dry run, no updates will be made");
+            }
+            FormatOptions::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&dry_run_json(
+                    &changes,
+                    &planned_tags,
+                    &ctx.repo_root_path
+                ))?
+            ),
+        }
         return Ok(());
     }
 
-    apply_updates(&mut update_projects, &workspace_projects).await?;
+    let previous_versions: Vec<Option<String>> = update_projects
+        .iter()
+        .map(|(project, _)| project.version().map(str::to_string))
+        .collect();
+    let pre_update_hooks = update_projects
+        .iter()
+        .zip(&previous_versions)
+        .map(|((project, update_type), version)| {
+            Ok(HookContext {
+                package: project.name().map(str::to_string),
+                path: get_relative_path(&ctx.repo_root_path, project.path())?,
+                version: version.clone(),
+                previous_version: None,
+                update_type: Some(*update_type),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // Stage the writes in memory so the confirmation covers the exact files touched
+    let (result, changes) = capture_writes(release_writes(&mut update_projects)).await;
+    result?;
+    if !args.yes {
+        if let FormatOptions::Stdout = args.format {
+            println!("\nFiles to be written:");
+            for line in write_set_lines(
+                &changes,
+                &changepacks_dir.join(RELEASE_MANIFEST_FILE),
+                &changepack_log_files(&changepacks_dir).await?,
+                &ctx.repo_root_path,
+            )? {
+                println!("  {line}");
+            }
+            print_planned_tags(&planned_tags);
+        }
+        if !prompter.confirm("Are you sure you want to update the projects?")? {
+            args.format.print("Update cancelled", "{}");
+            return Ok(());
+        }
+    }
+    // Hooks run once the update is confirmed, right before the staged writes land
+    run_package_hooks(
+        &ctx.config,
+        HookEvent::PreUpdate,
+        &pre_update_hooks,
+        &ctx.repo_root_path,
+    )
+    .await?;
+    apply_file_changes(&changes).await?;
+    let release = gen_release_record(
+        &update_projects,
+        previous_versions,
+        &update_map,
+        &ctx.repo_root_path,
+    )?;
+    let version_tags = update_projects
+        .iter()
+        .filter_map(|(project, _)| {
+            let tag = project.version_tag(project.version()?)?;
+            Some(get_relative_path(&ctx.repo_root_path, project.path()).map(|path| (path, tag)))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
     drop(update_projects);
+    let post_update_hooks: Vec<HookContext> = release
+        .packages()
+        .iter()
+        .map(|package| HookContext {
+            package: package.name().map(str::to_string),
+            path: package.path().to_path_buf(),
+            version: Some(package.version().to_string()),
+            previous_version: package.previous_version().map(str::to_string),
+            update_type: Some(package.update_type()),
+        })
+        .collect();
+    let release_tags = release_tags(
+        &release,
+        &version_tags,
+        &ctx.config.git,
+        tag_prefix,
+        tag_all,
+    );
+    let payload = WebhookPayload::from_release(&release).with_package_metadata(|path| {
+        project_finders
+            .iter()
+            .flat_map(|finder| finder.projects())
+            .find(|project| {
+                get_relative_path(&ctx.repo_root_path, project.path()).is_ok_and(|rel| rel == path)
+            })
+            .map(Project::metadata)
+            .unwrap_or_default()
+    });
+    append_release_record(&changepacks_dir, release).await?;
+    if let Err(e) = send_webhooks(&ctx.config.webhooks, &payload).await {
+        eprintln!("{e}");
+    }
 
     if let FormatOptions::Json = args.format {
         println!(
@@ -165,6 +413,7 @@ pub async fn handle_update_with_prompter(args: &UpdateArgs, prompter: &dyn Promp
                     .as_slice(),
                 &ctx.repo_root_path,
                 &mut update_map,
+                prerelease_id.as_deref(),
             )?)?
         );
     }
@@ -172,9 +421,243 @@ pub async fn handle_update_with_prompter(args: &UpdateArgs, prompter: &dyn Promp
     // Clear files
     clear_update_logs(&changepacks_dir).await?;
 
+    // Tags go on a commit holding the release, not on the commit it was made from
+    if !release_tags.is_empty() {
+        let tag_names: Vec<&str> = release_tags.iter().map(|(tag, _)| tag.as_str()).collect();
+        commit_release(
+            &ctx.repo_root_path,
+            &changes,
+            &changepacks_dir,
+            &format!("Release {}", tag_names.join(", ")),
+        )
+        .await?;
+    }
+    for (tag, message) in &release_tags {
+        create_git_tag(&ctx.repo_root_path, tag, message)?;
+        if let FormatOptions::Stdout = args.format {
+            println!("Tagged {}", tag.green());
+        }
+    }
+
+    // The release is complete, so a failing hook is reported without failing the update
+    if let Err(e) = run_package_hooks(
+        &ctx.config,
+        HookEvent::PostUpdate,
+        &post_update_hooks,
+        &ctx.repo_root_path,
+    )
+    .await
+    {
+        eprintln!("{} {e:#}", "warning:".yellow());
+    }
+
+    Ok(())
+}
+
+/// Run the `event` hooks for each package, in the directory of its manifest
+///
+/// # Errors
+/// Returns error at the first hook that fails.
+pub(super) async fn run_package_hooks(
+    config: &Config,
+    event: HookEvent,
+    packages: &[HookContext],
+    repo_root_path: &Path,
+) -> Result<()> {
+    for package in packages {
+        let manifest = repo_root_path.join(&package.path);
+        run_hooks(
+            config,
+            event,
+            package,
+            manifest.parent().unwrap_or(repo_root_path),
+        )
+        .await?;
+    }
     Ok(())
 }
 
+/// Commit the files the update wrote and the `.changepacks` directory (the release record
+/// and the removed changepack logs, but not the run lock) as `message`
+///
+/// Only these paths are committed, so unrelated changes staged or in the working tree stay
+/// out of the release commit.
+///
+/// # Errors
+/// Returns error if staging or committing fails, e.g. when no committer is configured.
+async fn commit_release(
+    repo_root: &Path,
+    changes: &[FileChange],
+    changepacks_dir: &Path,
+    message: &str,
+) -> Result<()> {
+    let mut paths = vec![get_relative_path(repo_root, changepacks_dir)?];
+    for change in changes {
+        paths.push(get_relative_path(repo_root, &change.path)?);
+    }
+    paths.sort();
+    paths.dedup();
+    let mut paths: Vec<String> = paths.iter().map(|path| path_key(path)).collect();
+    paths.push(format!(
+        ":(exclude){}",
+        path_key(&get_relative_path(
+            repo_root,
+            &changepacks_dir.join(RUN_LOCK_FILE)
+        )?)
+    ));
+    let pathspec = paths.iter().map(String::as_str);
+    run_git(
+        repo_root,
+        &["add", "-A", "--"]
+            .into_iter()
+            .chain(pathspec.clone())
+            .collect::<Vec<_>>(),
+    )
+    .await?;
+    run_git(
+        repo_root,
+        &["commit", "-q", "-m", message, "--"]
+            .into_iter()
+            .chain(pathspec)
+            .collect::<Vec<_>>(),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Tags `update` would create for the bumped packages
+///
+/// Packages whose version lives in git tags always get their version tag. With `tag_all`,
+/// the other named packages get a tag named by `git.tagTemplate`.
+///
+/// # Errors
+/// Returns error listing the tags that already exist, before anything is written.
+fn planned_tags(
+    update_projects: &[UpdateProjectMut<'_>],
+    prerelease_id: Option<&str>,
+    git: &GitConfig,
+    prefix: &str,
+    tag_all: bool,
+    existing_tags: &[String],
+) -> Result<Vec<String>> {
+    let planned: Vec<String> = update_projects
+        .iter()
+        .filter_map(|(project, update_type)| {
+            let next = next_version_with_id(
+                project.version().unwrap_or("0.0.0"),
+                *update_type,
+                prerelease_id,
+            )
+            .ok()?;
+            match project.version_tag(&next) {
+                Some(tag) => Some(tag),
+                None if tag_all => Some(git.tag_name(prefix, project.name()?, &next)),
+                None => None,
+            }
+        })
+        .collect();
+    let existing: Vec<&str> = planned
+        .iter()
+        .filter(|tag| existing_tags.contains(tag))
+        .map(String::as_str)
+        .collect();
+    if !existing.is_empty() {
+        anyhow::bail!(
+            "Refusing to update, these tags already exist:\n  {}",
+            existing.join("\n  ")
+        );
+    }
+    Ok(planned)
+}
+
+/// Name and message of the annotated tag for each package in `release` that gets one (see
+/// [`planned_tags`]), `version_tags` holding the version tags by package path
+///
+/// The message is the tag name followed by the package's changepack notes.
+fn release_tags(
+    release: &ReleaseRecord,
+    version_tags: &HashMap<PathBuf, String>,
+    git: &GitConfig,
+    prefix: &str,
+    tag_all: bool,
+) -> Vec<(String, String)> {
+    release
+        .packages()
+        .iter()
+        .filter_map(|package| {
+            let tag = match version_tags.get(package.path()) {
+                Some(tag) => tag.clone(),
+                None if tag_all => git.tag_name(prefix, package.name()?, package.version()),
+                None => return None,
+            };
+            let notes: Vec<String> = package
+                .notes()
+                .iter()
+                .map(|note| format!("- {note}"))
+                .collect();
+            let message = if notes.is_empty() {
+                format!("{tag}\n")
+            } else {
+                format!("{tag}\n\n{}\n", notes.join("\n"))
+            };
+            Some((tag, message))
+        })
+        .collect()
+}
+
+fn print_planned_tags(tags: &[String]) {
+    if tags.is_empty() {
+        return;
+    }
+    println!("\nTags to be created:");
+    for tag in tags {
+        println!("  {tag}");
+    }
+}
+
+/// Changepack log files `update` deletes once the versions are written
+async fn changepack_log_files(changepacks_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !changepacks_dir.exists() {
+        return Ok(files);
+    }
+    let mut entries = tokio::fs::read_dir(changepacks_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if is_changepack_log_entry(&entry).await {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// `modified  <path>` lines for every file an update writes (staged `changes` and the release
+/// manifest) or deletes (`changepack_logs`), relative to the repository root
+fn write_set_lines(
+    changes: &[FileChange],
+    release_manifest: &Path,
+    changepack_logs: &[PathBuf],
+    repo_root_path: &Path,
+) -> Result<Vec<String>> {
+    let written = changes
+        .iter()
+        .map(|change| change.path.as_path())
+        .chain(std::iter::once(release_manifest))
+        .map(|path| (if path.exists() { "modified" } else { "created" }, path));
+    let deleted = changepack_logs
+        .iter()
+        .map(|path| ("deleted", path.as_path()));
+    written
+        .chain(deleted)
+        .map(|(action, path)| {
+            Ok(format!(
+                "{action:<8}  {}",
+                path_key(&display_path(repo_root_path, path))
+            ))
+        })
+        .collect()
+}
+
 /// Excluded from coverage: private helper invoked solely by
 /// `handle_update_with_prompter`; exercised end-to-end via the cli
 /// integration tests but its internal `if let Some(...) / for project in finder.projects_mut()`
@@ -211,14 +694,17 @@ fn collect_update_projects<'a>(
     Ok((update_projects, workspace_projects))
 }
 
+/// Bump every project (prereleases to `prerelease_id`), then the workspace dependency specs
+/// on them
 async fn apply_updates(
     update_projects: &mut [UpdateProjectMut<'_>],
     workspace_projects: &[WorkspaceRef<'_>],
+    prerelease_id: Option<&str>,
 ) -> Result<()> {
     futures::future::join_all(
         update_projects
             .iter_mut()
-            .map(|(project, update_type)| project.update_version(*update_type)),
+            .map(|(project, update_type)| project.update_version(*update_type, prerelease_id)),
     )
     .await
     .into_iter()
@@ -247,6 +733,320 @@ async fn apply_updates(
     Ok(())
 }
 
+/// Apply the updates, then add each project's notes to its changelog when `changelog_date` is set
+pub(super) async fn write_release(
+    update_projects: &mut [UpdateProjectMut<'_>],
+    workspace_projects: &[WorkspaceRef<'_>],
+    update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    prerelease_id: Option<&str>,
+    repo_root_path: &Path,
+    changelog_date: Option<&str>,
+) -> Result<()> {
+    apply_updates(update_projects, workspace_projects, prerelease_id).await?;
+    let Some(date) = changelog_date else {
+        return Ok(());
+    };
+    // One at a time: projects sharing a directory share its changelog
+    for (project, _) in update_projects.iter() {
+        if let Some((_, logs)) = update_map.get(&get_relative_path(repo_root_path, project.path())?)
+        {
+            write_changelog(
+                project.path(),
+                project.version().unwrap_or_default(),
+                date,
+                logs,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Highest version of a package that already went out, with where it was seen
+///
+/// Looks at the release manifest, successful publish records, and git tags named
+/// `<name>@<version>` (or `v<version>` when the repository holds a single project).
+fn highest_released_version(
+    path: &Path,
+    name: Option<&str>,
+    manifest: &ReleaseManifest,
+    publish_log: &PublishLog,
+    tags: &[String],
+    single_project: bool,
+) -> Option<(String, &'static str)> {
+    let released = manifest
+        .releases()
+        .iter()
+        .flat_map(ReleaseRecord::packages)
+        .filter(|package| package.path() == path)
+        .map(|package| (package.version(), "released"));
+    let published = publish_log
+        .records()
+        .iter()
+        .filter(|record| record.success() && record.path() == path)
+        .filter_map(|record| record.version().map(|version| (version, "published")));
+    let tagged = tags.iter().filter_map(|tag| {
+        let version = name
+            .and_then(|name| tag.strip_prefix(name)?.strip_prefix('@'))
+            .or_else(|| single_project.then(|| tag.strip_prefix('v')).flatten())?;
+        Some((version.strip_prefix('v').unwrap_or(version), "tagged"))
+    });
+    released
+        .chain(published)
+        .chain(tagged)
+        // Versions that cannot be ordered (e.g. tags like `core@next`) say nothing about the floor
+        .filter(|(version, _)| compare_versions(version, version).is_some())
+        .max_by(|(a, _), (b, _)| compare_versions(a, b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(version, source)| (version.to_string(), source))
+}
+
+/// Latest version on its registry of every updated package with a supported registry, keyed
+/// by manifest path relative to the repository root
+///
+/// Packages never published are left out, as are packages whose registry cannot be reached
+/// (reported on stderr).
+///
+/// # Errors
+/// Returns error if a manifest path is outside the repository root.
+async fn registry_versions(
+    update_projects: &[UpdateProjectMut<'_>],
+    repo_root_path: &Path,
+    client: impl Fn(Registry) -> RegistryClient,
+) -> Result<HashMap<PathBuf, String>> {
+    let mut lookups = Vec::new();
+    for (project, _) in update_projects {
+        let (Some(registry), Some(name)) =
+            (Registry::from_name(project.registry()), project.name())
+        else {
+            continue;
+        };
+        let path = get_relative_path(repo_root_path, project.path())?;
+        let client = client(registry);
+        let project = project.to_string();
+        lookups.push(async move {
+            match client.latest_version(name).await {
+                Ok(version) => version.map(|version| (path, version)),
+                Err(e) => {
+                    eprintln!("Could not check {project} on {registry}: {e:#}");
+                    None
+                }
+            }
+        });
+    }
+    Ok(futures::future::join_all(lookups)
+        .await
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Where `update` looks for versions a package already shipped
+struct ShippedVersions<'a> {
+    manifest: &'a ReleaseManifest,
+    publish_log: &'a PublishLog,
+    tags: &'a [String],
+    /// Latest version on each package's registry, keyed by relative manifest path
+    registry_versions: &'a HashMap<PathBuf, String>,
+    /// Whether bare `v<version>` tags belong to the only project
+    single_project: bool,
+}
+
+/// Updates whose new version (prereleases bumped to `prerelease_id`) would not be above the
+/// package's highest shipped version
+///
+/// Catches changepacks applied to a stale or rebased manifest, which would otherwise try to
+/// publish a version the registry already has.
+fn non_monotonic_updates(
+    update_projects: &[UpdateProjectMut<'_>],
+    prerelease_id: Option<&str>,
+    repo_root_path: &Path,
+    shipped: &ShippedVersions<'_>,
+) -> Result<Vec<String>> {
+    let mut problems = Vec::new();
+    for (project, update_type) in update_projects {
+        let current = project.version().unwrap_or("0.0.0");
+        let Ok(next) = next_version_with_id(current, *update_type, prerelease_id) else {
+            // Reported with context when the update is applied
+            continue;
+        };
+        let path = get_relative_path(repo_root_path, project.path())?;
+        let on_registry = shipped
+            .registry_versions
+            .get(&path)
+            .filter(|version| compare_versions(version, version).is_some())
+            .map(|version| (version.clone(), "registry"));
+        if let Some((highest, source)) = highest_released_version(
+            &path,
+            project.name(),
+            shipped.manifest,
+            shipped.publish_log,
+            shipped.tags,
+            shipped.single_project,
+        )
+        .into_iter()
+        .chain(on_registry)
+        .max_by(|(a, _), (b, _)| compare_versions(a, b).unwrap_or(std::cmp::Ordering::Equal))
+            && compare_versions(&next, &highest).is_some_and(std::cmp::Ordering::is_le)
+        {
+            problems.push(format!(
+                "{} ({}): {current} -> {next} is not above {highest} ({source})",
+                project.name().unwrap_or("noname"),
+                path_key(&path)
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+/// Fail when a changed project has no pending changepack
+///
+/// A project is covered when a changepack (or a dependency or `fixed` group rule) bumps it,
+/// or when its current version is the one last recorded in the release manifest, meaning the
+/// change is the release itself.
+///
+/// # Errors
+/// Returns error listing the uncovered projects.
+pub(super) fn require_changepacks(
+    projects: &[&Project],
+    update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    repo_root_path: &Path,
+    manifest: &ReleaseManifest,
+) -> Result<()> {
+    let missing = super::status::missing_changepacks(&super::status::changepack_coverage(
+        projects,
+        update_map,
+        repo_root_path,
+        manifest,
+    )?);
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Changed projects have no changepack (run `changepacks` to record one):\n  {}",
+            missing.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+/// Fail when the current branch is protected by the config `protectedBranches`
+///
+/// Detached checkouts and directories outside git have no branch and are never protected.
+///
+/// # Errors
+/// Returns error naming the branch when it is protected and `allow_protected` is not set.
+pub(super) async fn guard_protected_branch(
+    repo_root_path: &Path,
+    protected_branches: &[String],
+    allow_protected: bool,
+    command: &str,
+) -> Result<()> {
+    if allow_protected || protected_branches.is_empty() {
+        return Ok(());
+    }
+    let Ok(branch) = run_git(repo_root_path, &["symbolic-ref", "--short", "-q", "HEAD"]).await
+    else {
+        return Ok(());
+    };
+    if let Some(pattern) = protected_branch_pattern(&branch, protected_branches) {
+        anyhow::bail!(
+            "Refusing to {command} on protected branch '{branch}' (matches \"{pattern}\" in protectedBranches); pass --allow-protected to run anyway"
+        );
+    }
+    Ok(())
+}
+
+/// First `protectedBranches` entry matching `branch`, by name or glob (`*` stops at `/`)
+fn protected_branch_pattern<'a>(branch: &str, protected_branches: &'a [String]) -> Option<&'a str> {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..glob::MatchOptions::new()
+    };
+    protected_branches
+        .iter()
+        .find(|pattern| {
+            pattern.as_str() == branch
+                || glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches_with(branch, options))
+        })
+        .map(String::as_str)
+}
+
+/// Build the release manifest record for the projects just bumped
+fn gen_release_record(
+    update_projects: &[UpdateProjectMut<'_>],
+    previous_versions: Vec<Option<String>>,
+    update_map: &HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    repo_root_path: &Path,
+) -> Result<ReleaseRecord> {
+    let mut packages = Vec::with_capacity(update_projects.len());
+    for ((project, update_type), previous_version) in update_projects.iter().zip(previous_versions)
+    {
+        let path = get_relative_path(repo_root_path, project.path())?;
+        let notes = update_map
+            .get(&path)
+            .map(|(_, logs)| {
+                logs.iter()
+                    .map(ChangePackResultLog::rendered_note)
+                    .collect()
+            })
+            .unwrap_or_default();
+        packages.push(ReleasedPackage::new(
+            project.name().map(str::to_string),
+            path,
+            previous_version,
+            project.version().unwrap_or_default().to_string(),
+            *update_type,
+            notes,
+        ));
+    }
+    Ok(ReleaseRecord::new(packages))
+}
+
+/// `path` relative to the repository root, or as given when it lies outside, like the tag refs
+/// a Go module writes to a git directory shared with other worktrees
+fn display_path(repo_root_path: &Path, path: &Path) -> PathBuf {
+    get_relative_path(repo_root_path, path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// JSON output of a dry run: a unified diff per file the update would modify, and the tags
+/// it would create
+fn dry_run_json(
+    changes: &[FileChange],
+    planned_tags: &[String],
+    repo_root_path: &Path,
+) -> serde_json::Value {
+    let files: Vec<serde_json::Value> = changes
+        .iter()
+        .map(|change| {
+            let path = path_key(&display_path(repo_root_path, &change.path));
+            let diff = unified_diff(&path, &change.original, &change.updated);
+            serde_json::json!({ "path": path, "diff": diff })
+        })
+        .collect();
+    serde_json::json!({ "files": files, "tags": planned_tags })
+}
+
+/// Print a colored unified diff for each file the update would modify
+pub(super) fn print_file_changes(changes: &[FileChange], repo_root_path: &Path) -> Result<()> {
+    for change in changes {
+        let label = display_path(repo_root_path, &change.path);
+        let diff = unified_diff(&path_key(&label), &change.original, &change.updated);
+        println!();
+        for line in diff.lines() {
+            if line.starts_with("---") || line.starts_with("+++") {
+                println!("{}", line.bold());
+            } else if line.starts_with("@@") {
+                println!("{}", line.cyan());
+            } else if line.starts_with('+') {
+                println!("{}", line.green());
+            } else if line.starts_with('-') {
+                println!("{}", line.red());
+            } else {
+                println!("{line}");
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Merge workspace-inherited package updates into workspace entries.
 /// Packages with `version.workspace = true` should have their bumps promoted
 /// to the workspace level (most significant bump wins). The packages are then
@@ -290,7 +1090,10 @@ fn merge_workspace_inherited_updates(
 
 #[cfg(test)]
 mod tests {
-    use super::{UpdateArgs, merge_workspace_inherited_updates};
+    use super::{
+        UpdateArgs, dry_run_json, merge_workspace_inherited_updates, planned_tags,
+        protected_branch_pattern, release_tags, write_set_lines,
+    };
     use anyhow::Result;
     use async_trait::async_trait;
     use changepacks_core::{
@@ -362,7 +1165,11 @@ mod tests {
             &self.relative_path
         }
 
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
 
@@ -743,4 +1550,402 @@ mod tests {
         let cli = TestCli::parse_from(["test", "-l", "rust"]);
         assert_eq!(cli.update.language.len(), 1);
     }
+
+    #[test]
+    fn test_update_args_force() {
+        assert!(!TestCli::parse_from(["test"]).update.force);
+        assert!(TestCli::parse_from(["test", "--force"]).update.force);
+    }
+
+    #[test]
+    fn test_update_args_tag() {
+        let cli = TestCli::parse_from(["test"]);
+        assert!(!cli.update.tag);
+        assert_eq!(cli.update.tag_prefix, None);
+
+        let cli = TestCli::parse_from(["test", "--tag", "--tag-prefix", "release/"]);
+        assert!(cli.update.tag);
+        assert_eq!(cli.update.tag_prefix.as_deref(), Some("release/"));
+    }
+
+    #[test]
+    fn test_planned_tags() {
+        let git = changepacks_core::GitConfig::default();
+        let mut core = Project::Package(Box::new(changepacks_node::package::NodePackage::new(
+            Some("core".to_string()),
+            Some("1.0.0".to_string()),
+            PathBuf::from("/repo/core/package.json"),
+            PathBuf::from("core/package.json"),
+        )));
+        let mut unnamed = Project::Package(Box::new(changepacks_node::package::NodePackage::new(
+            None,
+            Some("0.1.0".to_string()),
+            PathBuf::from("/repo/tools/package.json"),
+            PathBuf::from("tools/package.json"),
+        )));
+        let mut updates = [
+            (&mut core, UpdateType::Minor),
+            (&mut unnamed, UpdateType::Patch),
+        ];
+
+        assert_eq!(
+            planned_tags(&updates, None, &git, "", true, &["core@1.0.0".to_string()]).unwrap(),
+            ["core@1.1.0"]
+        );
+        assert_eq!(
+            planned_tags(&updates, None, &git, "release/", true, &[]).unwrap(),
+            ["release/core@1.1.0"]
+        );
+        let err =
+            planned_tags(&updates, None, &git, "", true, &["core@1.1.0".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("core@1.1.0"), "{err}");
+        updates[0].1 = UpdateType::Major;
+        assert!(planned_tags(&updates, None, &git, "", true, &["core@1.1.0".to_string()]).is_ok());
+        assert!(
+            planned_tags(&updates, None, &git, "", false, &[])
+                .unwrap()
+                .is_empty()
+        );
+
+        let mut module = Project::Package(Box::new(
+            changepacks_go::package::GoPackage::new(
+                Some("example.com/api".to_string()),
+                Some("1.2.0".to_string()),
+                PathBuf::from("/repo/api/go.mod"),
+                PathBuf::from("api/go.mod"),
+            )
+            .with_tag_prefix("api/".to_string()),
+        ));
+        let updates = [(&mut module, UpdateType::Patch)];
+        assert_eq!(
+            planned_tags(&updates, None, &git, "", false, &[]).unwrap(),
+            ["api/v1.2.1"]
+        );
+        assert_eq!(
+            planned_tags(&updates, None, &git, "", true, &[]).unwrap(),
+            ["api/v1.2.1"]
+        );
+    }
+
+    #[test]
+    fn test_release_tags() {
+        let release = changepacks_core::ReleaseRecord::new(vec![
+            changepacks_core::ReleasedPackage::new(
+                Some("core".to_string()),
+                PathBuf::from("core/package.json"),
+                Some("1.0.0".to_string()),
+                "1.1.0".to_string(),
+                UpdateType::Minor,
+                vec!["Add parser".to_string(), "Fix lexer".to_string()],
+            ),
+            changepacks_core::ReleasedPackage::new(
+                Some("cli".to_string()),
+                PathBuf::from("cli/package.json"),
+                Some("2.0.0".to_string()),
+                "2.0.1".to_string(),
+                UpdateType::Patch,
+                vec![],
+            ),
+        ]);
+        let git = changepacks_core::GitConfig {
+            tag_template: "{name}-v{version}".to_string(),
+            ..changepacks_core::GitConfig::default()
+        };
+
+        assert_eq!(
+            release_tags(&release, &HashMap::new(), &git, "", true),
+            [
+                (
+                    "core-v1.1.0".to_string(),
+                    "core-v1.1.0\n\n- Add parser\n- Fix lexer\n".to_string()
+                ),
+                ("cli-v2.0.1".to_string(), "cli-v2.0.1\n".to_string()),
+            ]
+        );
+        let version_tags =
+            HashMap::from([(PathBuf::from("cli/package.json"), "v2.0.1".to_string())]);
+        assert_eq!(
+            release_tags(&release, &version_tags, &git, "", false),
+            [("v2.0.1".to_string(), "v2.0.1\n".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_update_args_allow_protected() {
+        assert!(!TestCli::parse_from(["test"]).update.allow_protected);
+        assert!(
+            TestCli::parse_from(["test", "--allow-protected"])
+                .update
+                .allow_protected
+        );
+    }
+
+    #[test]
+    fn test_update_args_registry() {
+        assert!(!TestCli::parse_from(["test"]).update.registry);
+        assert!(TestCli::parse_from(["test", "--registry"]).update.registry);
+    }
+
+    #[tokio::test]
+    async fn test_registry_versions() {
+        use super::{ShippedVersions, non_monotonic_updates, registry_versions};
+        use changepacks_core::{PublishLog, ReleaseManifest};
+        use changepacks_registry::{Registry, RegistryClient};
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"dist-tags":{"latest":"1.0.1"}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+
+        let mut project = Project::Package(Box::new(changepacks_node::package::NodePackage::new(
+            Some("core".to_string()),
+            Some("1.0.0".to_string()),
+            PathBuf::from("/repo/core/package.json"),
+            PathBuf::from("core/package.json"),
+        )));
+        let repo = Path::new("/repo");
+        let update_projects = [(&mut project, UpdateType::Patch)];
+        let versions = registry_versions(&update_projects, repo, |registry| {
+            assert_eq!(registry, Registry::Npm);
+            RegistryClient::with_base_url(registry, &url)
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            versions,
+            HashMap::from([(PathBuf::from("core/package.json"), "1.0.1".to_string())])
+        );
+
+        // Nothing released locally, but the registry already has the next version
+        let problems = non_monotonic_updates(
+            &update_projects,
+            None,
+            repo,
+            &ShippedVersions {
+                manifest: &ReleaseManifest::default(),
+                publish_log: &PublishLog::default(),
+                tags: &[],
+                registry_versions: &versions,
+                single_project: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            problems,
+            ["core (core/package.json): 1.0.0 -> 1.0.1 is not above 1.0.1 (registry)"]
+        );
+    }
+
+    #[test]
+    fn test_write_set_lines() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        let changes = vec![changepacks_utils::FileChange {
+            path: root.join("package.json"),
+            original: "{}".to_string(),
+            updated: "{ }".to_string(),
+        }];
+        let lines = write_set_lines(
+            &changes,
+            &root.join(".changepacks/releases.json"),
+            &[root.join(".changepacks/changepack_log_a.json")],
+            root,
+        )
+        .unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                "modified  package.json",
+                "created   .changepacks/releases.json",
+                "deleted   .changepacks/changepack_log_a.json",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dry_run_json() {
+        let root = Path::new("/repo");
+        let changes = vec![changepacks_utils::FileChange {
+            path: root.join("crates/core/Cargo.toml"),
+            original: "[package]\nversion = \"1.0.0\"\n".to_string(),
+            updated: "[package]\nversion = \"1.0.1\"\n".to_string(),
+        }];
+        let json = dry_run_json(&changes, &["core@1.0.1".to_string()], root);
+        assert_eq!(json["files"][0]["path"], "crates/core/Cargo.toml");
+        let diff = json["files"][0]["diff"].as_str().unwrap();
+        assert!(diff.starts_with("--- a/crates/core/Cargo.toml\n+++ b/crates/core/Cargo.toml\n"));
+        assert!(diff.contains("-version = \"1.0.0\"\n+version = \"1.0.1\"\n"));
+        assert_eq!(json["tags"], serde_json::json!(["core@1.0.1"]));
+    }
+
+    #[test]
+    fn test_protected_branch_pattern() {
+        let protected = vec!["main".to_string(), "release/*".to_string()];
+        assert_eq!(protected_branch_pattern("main", &protected), Some("main"));
+        assert_eq!(
+            protected_branch_pattern("release/1.x", &protected),
+            Some("release/*")
+        );
+        assert_eq!(
+            protected_branch_pattern("release/1.x/fix", &protected),
+            None
+        );
+        assert_eq!(protected_branch_pattern("feature/main", &protected), None);
+        assert_eq!(protected_branch_pattern("main", &[]), None);
+    }
+
+    fn released(path: &str, version: &str) -> changepacks_core::ReleaseRecord {
+        changepacks_core::ReleaseRecord::new(vec![changepacks_core::ReleasedPackage::new(
+            Some("core".to_string()),
+            PathBuf::from(path),
+            None,
+            version.to_string(),
+            UpdateType::Patch,
+            vec![],
+        )])
+    }
+
+    fn published(path: &str, version: &str, success: bool) -> changepacks_core::PublishRecord {
+        changepacks_core::PublishRecord::new(
+            Some("core".to_string()),
+            PathBuf::from(path),
+            Some(version.to_string()),
+            "npm".to_string(),
+            success,
+            String::new(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_highest_released_version() {
+        use super::highest_released_version;
+        use changepacks_core::{PublishLog, ReleaseManifest};
+
+        let path = Path::new("core/package.json");
+        let mut manifest = ReleaseManifest::default();
+        let mut publish_log = PublishLog::default();
+        let none: &[String] = &[];
+        assert_eq!(
+            highest_released_version(path, Some("core"), &manifest, &publish_log, none, false),
+            None
+        );
+
+        manifest.push(released("core/package.json", "1.2.0"));
+        manifest.push(released("other/package.json", "9.0.0"));
+        assert_eq!(
+            highest_released_version(path, Some("core"), &manifest, &publish_log, none, false),
+            Some(("1.2.0".to_string(), "released"))
+        );
+
+        publish_log.push(published("core/package.json", "1.3.0", true));
+        publish_log.push(published("core/package.json", "1.9.0", false));
+        assert_eq!(
+            highest_released_version(path, Some("core"), &manifest, &publish_log, none, false),
+            Some(("1.3.0".to_string(), "published"))
+        );
+
+        let tags = [
+            "core@v1.10.0".to_string(),
+            "core@next".to_string(),
+            "web@5.0.0".to_string(),
+            "v7.0.0".to_string(),
+        ];
+        assert_eq!(
+            highest_released_version(path, Some("core"), &manifest, &publish_log, &tags, false),
+            Some(("1.10.0".to_string(), "tagged"))
+        );
+        // Bare `v` tags only count in single-project repositories
+        assert_eq!(
+            highest_released_version(path, Some("core"), &manifest, &publish_log, &tags, true),
+            Some(("7.0.0".to_string(), "tagged"))
+        );
+    }
+
+    #[test]
+    fn test_non_monotonic_updates() {
+        use super::{ShippedVersions, non_monotonic_updates};
+        use changepacks_core::{PublishLog, ReleaseManifest};
+
+        let package = |version: &str| {
+            Project::Package(Box::new(changepacks_node::package::NodePackage::new(
+                Some("core".to_string()),
+                Some(version.to_string()),
+                PathBuf::from("/repo/core/package.json"),
+                PathBuf::from("core/package.json"),
+            )))
+        };
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(released("core/package.json", "1.0.1"));
+        let publish_log = PublishLog::default();
+        let repo = Path::new("/repo");
+
+        // A rebase reverted the manifest to 1.0.0, so a patch would re-release 1.0.1
+        let mut stale = package("1.0.0");
+        let problems = non_monotonic_updates(
+            &[(&mut stale, UpdateType::Patch)],
+            None,
+            repo,
+            &ShippedVersions {
+                manifest: &manifest,
+                publish_log: &publish_log,
+                tags: &[],
+                registry_versions: &HashMap::new(),
+                single_project: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            problems,
+            ["core (core/package.json): 1.0.0 -> 1.0.1 is not above 1.0.1 (released)"]
+        );
+
+        let mut stale = package("1.0.0");
+        assert!(
+            non_monotonic_updates(
+                &[(&mut stale, UpdateType::Minor)],
+                None,
+                repo,
+                &ShippedVersions {
+                    manifest: &manifest,
+                    publish_log: &publish_log,
+                    tags: &[],
+                    registry_versions: &HashMap::new(),
+                    single_project: false,
+                },
+            )
+            .unwrap()
+            .is_empty()
+        );
+
+        let mut current = package("1.0.1");
+        assert!(
+            non_monotonic_updates(
+                &[(&mut current, UpdateType::Patch)],
+                None,
+                repo,
+                &ShippedVersions {
+                    manifest: &manifest,
+                    publish_log: &publish_log,
+                    tags: &[],
+                    registry_versions: &HashMap::new(),
+                    single_project: false,
+                },
+            )
+            .unwrap()
+            .is_empty()
+        );
+    }
 }
@@ -0,0 +1,586 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use changepacks_core::{ChangePackLog, Config, Project, UpdateType};
+use changepacks_utils::{
+    apply_reverse_dependencies, bridge_links, display_update, gen_update_map, get_changepacks_dir,
+    get_relative_path, read_changepack_logs,
+};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use colored::Colorize;
+use glob::Pattern;
+use serde::Serialize;
+
+use crate::{
+    CommandContext, GlobalOptions, completion::package_candidates, options::FormatOptions,
+};
+
+#[derive(Args, Debug)]
+#[command(about = "Explain why a package is scheduled for a version bump")]
+pub struct WhyArgs {
+    /// Package name or manifest path
+    #[arg(add = ArgValueCandidates::new(package_candidates))]
+    pub package: String,
+
+    #[arg(long, default_value = "stdout")]
+    pub format: FormatOptions,
+}
+
+/// Changepack log that targets the package directly
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct ChangepackReason {
+    file: String,
+    update_type: UpdateType,
+    note: String,
+}
+
+/// `updateOn` rule listing the package that was triggered by directly changed paths
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct UpdateOnReason {
+    pattern: String,
+    triggered_by: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Explanation {
+    name: Option<String>,
+    path: PathBuf,
+    /// Resulting bump (`None` when the package is not scheduled)
+    update_type: Option<UpdateType>,
+    changepacks: Vec<ChangepackReason>,
+    update_on: Vec<UpdateOnReason>,
+    /// Updated crates this bridge package wraps
+    bridged_crates: Vec<PathBuf>,
+    /// Members of the package's `fixed` group that are updated, when any
+    fixed_group: Vec<String>,
+    /// Package names from this package down to the updated dependency that pulls it in
+    dependency_chain: Vec<String>,
+}
+
+/// Explain why a package is scheduled for a bump
+///
+/// # Errors
+/// Returns error if project discovery or reading `.changepacks` fails, or the package is
+/// not found.
+///
+/// Excluded from coverage: orchestrates `CommandContext::new`; `explain` and
+/// `format_explanation` are unit tested and the command is covered by the cli
+/// integration tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn handle_why(args: &WhyArgs, global: &GlobalOptions) -> Result<()> {
+    let ctx = CommandContext::new(global, false).await?;
+    let projects: Vec<&Project> = ctx
+        .project_finders
+        .iter()
+        .flat_map(|finder| finder.projects())
+        .collect();
+    let target = projects
+        .iter()
+        .find(|project| {
+            project.name() == Some(args.package.as_str())
+                || get_relative_path(&ctx.repo_root_path, project.path())
+                    .is_ok_and(|path| path == Path::new(&args.package))
+        })
+        .ok_or_else(|| anyhow!("package '{}' not found", args.package))?;
+
+    let logs = read_changepack_logs(&get_changepacks_dir(&ctx.repo_root_path)?).await?;
+    let (mut update_map, prerelease_id) =
+        gen_update_map(&ctx.repo_root_path, &ctx.config, &projects, &ctx.diff_refs).await?;
+    apply_reverse_dependencies(
+        &mut update_map,
+        &projects,
+        &ctx.repo_root_path,
+        prerelease_id.as_deref(),
+    );
+    let target_path = get_relative_path(&ctx.repo_root_path, target.path())?;
+    let explanation = explain(
+        target,
+        &target_path,
+        &projects,
+        &logs,
+        &ctx.config,
+        &bridge_links(&projects, &ctx.config).await,
+        update_map
+            .get(&target_path)
+            .map(|(update_type, _)| *update_type),
+    );
+
+    match args.format {
+        FormatOptions::Stdout => print!(
+            "{}",
+            format_explanation(&explanation, target.version(), prerelease_id.as_deref())?
+        ),
+        FormatOptions::Json => println!("{}", serde_json::to_string_pretty(&explanation)?),
+    }
+    Ok(())
+}
+
+/// Collect the reasons `gen_update_map` and `apply_reverse_dependencies` schedule `target`
+fn explain(
+    target: &Project,
+    target_path: &Path,
+    projects: &[&Project],
+    logs: &[(PathBuf, ChangePackLog)],
+    config: &Config,
+    bridges: &[(PathBuf, PathBuf)],
+    update_type: Option<UpdateType>,
+) -> Explanation {
+    let changepacks: Vec<ChangepackReason> = logs
+        .iter()
+        .filter_map(|(file, log)| {
+            log.changes()
+                .get(target_path)
+                .map(|update_type| ChangepackReason {
+                    file: file
+                        .file_name()
+                        .map_or_else(String::new, |name| name.to_string_lossy().into_owned()),
+                    update_type: *update_type,
+                    note: log.note().to_string(),
+                })
+        })
+        .collect();
+
+    // Paths targeted by changepacks, then those added by updateOn rules, bridges and fixed
+    // groups
+    let direct: BTreeSet<PathBuf> = logs
+        .iter()
+        .flat_map(|(_, log)| log.changes().keys().cloned())
+        .collect();
+    let mut scheduled: HashSet<PathBuf> = direct.iter().cloned().collect();
+    let mut update_on = Vec::new();
+    let mut rules: Vec<_> = config.update_on.iter().collect();
+    rules.sort();
+    for (pattern, dependents) in rules {
+        let Ok(glob) = Pattern::new(pattern) else {
+            continue;
+        };
+        let triggered_by: Vec<PathBuf> = direct
+            .iter()
+            .filter(|path| glob.matches(&path.to_string_lossy()))
+            .cloned()
+            .collect();
+        if triggered_by.is_empty() {
+            continue;
+        }
+        scheduled.extend(dependents.iter().map(PathBuf::from));
+        if dependents
+            .iter()
+            .any(|dependent| Path::new(dependent) == target_path)
+        {
+            update_on.push(UpdateOnReason {
+                pattern: pattern.clone(),
+                triggered_by,
+            });
+        }
+    }
+    let bridged_crates: Vec<PathBuf> = bridges
+        .iter()
+        .filter(|(krate, bridge)| bridge == target_path && scheduled.contains(krate))
+        .map(|(krate, _)| krate.clone())
+        .collect();
+    scheduled.extend(
+        bridges
+            .iter()
+            .filter(|(krate, _)| scheduled.contains(krate))
+            .map(|(_, bridge)| bridge.clone())
+            .collect::<Vec<_>>(),
+    );
+    let mut fixed_group = Vec::new();
+    for group in &config.fixed {
+        if group
+            .iter()
+            .any(|member| scheduled.contains(Path::new(member)))
+        {
+            if group.iter().any(|member| Path::new(member) == target_path) {
+                fixed_group.clone_from(group);
+            }
+            scheduled.extend(group.iter().map(PathBuf::from));
+        }
+    }
+
+    let dependency_chain = if scheduled.contains(target_path) {
+        Vec::new()
+    } else {
+        dependency_chain(target, projects, &scheduled)
+    };
+
+    Explanation {
+        name: target.name().map(str::to_string),
+        path: target_path.to_path_buf(),
+        update_type,
+        changepacks,
+        update_on,
+        bridged_crates,
+        fixed_group,
+        dependency_chain,
+    }
+}
+
+/// Shortest chain of monorepo dependencies from `target` to a scheduled package
+///
+/// Mirrors `apply_reverse_dependencies`: a package is bumped when anything it depends on
+/// (transitively) is bumped. Returns an empty list when there is no such chain.
+fn dependency_chain(
+    target: &Project,
+    projects: &[&Project],
+    scheduled: &HashSet<PathBuf>,
+) -> Vec<String> {
+    let by_name: HashMap<&str, &Project> = projects
+        .iter()
+        .filter_map(|project| project.name().map(|name| (name, *project)))
+        .collect();
+    let Some(start) = target.name() else {
+        return Vec::new();
+    };
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(name) = queue.pop_front() {
+        let mut dependencies: Vec<&String> = by_name[name].dependencies().iter().collect();
+        dependencies.sort();
+        for dependency in dependencies {
+            let Some((dependency, project)) = by_name.get_key_value(dependency.as_str()) else {
+                continue;
+            };
+            if *dependency == start || parents.contains_key(dependency) {
+                continue;
+            }
+            parents.insert(dependency, name);
+            let is_scheduled = scheduled.contains(project.relative_path());
+            if is_scheduled {
+                let mut chain = vec![dependency.to_string()];
+                let mut current = *dependency;
+                while let Some(parent) = parents.get(current) {
+                    chain.push((*parent).to_string());
+                    current = parent;
+                }
+                chain.reverse();
+                return chain;
+            }
+            queue.push_back(dependency);
+        }
+    }
+    Vec::new()
+}
+
+fn format_explanation(
+    explanation: &Explanation,
+    version: Option<&str>,
+    prerelease_id: Option<&str>,
+) -> Result<String> {
+    let label = format!(
+        "{} ({})",
+        explanation.name.as_deref().unwrap_or("noname"),
+        explanation.path.display()
+    );
+    let Some(update_type) = explanation.update_type else {
+        return Ok(format!("{label} is not scheduled for a version bump\n"));
+    };
+    let mut out = format!(
+        "{} {update_type} {}\n",
+        label.bold(),
+        display_update(version, update_type, prerelease_id)?
+    );
+    if !explanation.changepacks.is_empty() {
+        out.push_str(&format!("{}\n", "Changepacks:".bold()));
+        for changepack in &explanation.changepacks {
+            out.push_str(&format!(
+                "  {} {} {}\n",
+                changepack.file,
+                changepack.update_type,
+                changepack.note.lines().next().unwrap_or_default()
+            ));
+        }
+    }
+    if !explanation.update_on.is_empty() {
+        out.push_str(&format!("{}\n", "updateOn rules:".bold()));
+        for rule in &explanation.update_on {
+            let triggered_by: Vec<String> = rule
+                .triggered_by
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            out.push_str(&format!(
+                "  {} triggered by {}\n",
+                rule.pattern,
+                triggered_by.join(", ")
+            ));
+        }
+    }
+    if !explanation.bridged_crates.is_empty() {
+        let crates: Vec<String> = explanation
+            .bridged_crates
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        out.push_str(&format!(
+            "{}\n  {}\n",
+            "Bridged crates:".bold(),
+            crates.join(", ")
+        ));
+    }
+    if !explanation.fixed_group.is_empty() {
+        out.push_str(&format!(
+            "{}\n  {}\n",
+            "Fixed group:".bold(),
+            explanation.fixed_group.join(", ")
+        ));
+    }
+    if !explanation.dependency_chain.is_empty() {
+        out.push_str(&format!(
+            "{}\n  {}\n",
+            "Dependency chain:".bold(),
+            explanation.dependency_chain.join(" → ")
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use changepacks_core::{Language, Package};
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        why: WhyArgs,
+    }
+
+    #[derive(Debug)]
+    struct MockPackage {
+        name: String,
+        path: PathBuf,
+        relative_path: PathBuf,
+        dependencies: HashSet<String>,
+    }
+
+    #[async_trait]
+    impl Package for MockPackage {
+        fn name(&self) -> Option<&str> {
+            Some(&self.name)
+        }
+        fn version(&self) -> Option<&str> {
+            Some("1.0.0")
+        }
+        fn path(&self) -> &Path {
+            &self.path
+        }
+        fn relative_path(&self) -> &Path {
+            &self.relative_path
+        }
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
+            Ok(())
+        }
+        fn is_changed(&self) -> bool {
+            false
+        }
+        fn language(&self) -> Language {
+            Language::Node
+        }
+        fn dependencies(&self) -> &HashSet<String> {
+            &self.dependencies
+        }
+        fn add_dependency(&mut self, dependency: &str) {
+            self.dependencies.insert(dependency.to_string());
+        }
+        fn set_changed(&mut self, _changed: bool) {}
+        fn default_publish_command(&self) -> String {
+            "echo publish".to_string()
+        }
+        fn default_dry_run_publish_command(&self) -> Option<String> {
+            None
+        }
+    }
+
+    fn package(name: &str, dependencies: &[&str]) -> Project {
+        Project::Package(Box::new(MockPackage {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/repo/{name}/package.json")),
+            relative_path: PathBuf::from(format!("{name}/package.json")),
+            dependencies: dependencies.iter().map(ToString::to_string).collect(),
+        }))
+    }
+
+    fn manifest(name: &str) -> PathBuf {
+        PathBuf::from(format!("{name}/package.json"))
+    }
+
+    fn log(name: &str, update_type: UpdateType, note: &str) -> (PathBuf, ChangePackLog) {
+        (
+            PathBuf::from(format!(".changepacks/changepack_log_{name}.json")),
+            ChangePackLog::new(
+                HashMap::from([(manifest(name), update_type)]),
+                note.to_string(),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_why_args() {
+        let cli = TestCli::parse_from(["why", "core", "--format", "json"]);
+        assert_eq!(cli.why.package, "core");
+        assert!(matches!(cli.why.format, FormatOptions::Json));
+    }
+
+    #[test]
+    fn test_explain_changepacks_update_on_and_fixed() {
+        let projects = [package("core", &[]), package("docs", &[])];
+        let refs: Vec<&Project> = projects.iter().collect();
+        let logs = [log("core", UpdateType::Minor, "Add streaming\n\nDetails")];
+        let mut config = Config::default();
+        config
+            .update_on
+            .insert("core/*".to_string(), vec!["docs/package.json".to_string()]);
+        config.fixed = vec![vec![
+            "core/package.json".to_string(),
+            "docs/package.json".to_string(),
+        ]];
+
+        let core = explain(
+            refs[0],
+            &manifest("core"),
+            &refs,
+            &logs,
+            &config,
+            &[],
+            Some(UpdateType::Minor),
+        );
+        assert_eq!(
+            core.changepacks,
+            [ChangepackReason {
+                file: "changepack_log_core.json".to_string(),
+                update_type: UpdateType::Minor,
+                note: "Add streaming\n\nDetails".to_string(),
+            }]
+        );
+        assert!(core.update_on.is_empty());
+        assert!(core.dependency_chain.is_empty());
+
+        let docs = explain(
+            refs[1],
+            &manifest("docs"),
+            &refs,
+            &logs,
+            &config,
+            &[],
+            Some(UpdateType::Minor),
+        );
+        assert!(docs.changepacks.is_empty());
+        assert_eq!(
+            docs.update_on,
+            [UpdateOnReason {
+                pattern: "core/*".to_string(),
+                triggered_by: vec![manifest("core")],
+            }]
+        );
+        assert_eq!(docs.fixed_group.len(), 2);
+    }
+
+    #[test]
+    fn test_explain_dependency_chain() {
+        // app -> lib -> core, where core has a changepack
+        let projects = [
+            package("app", &["lib", "react"]),
+            package("lib", &["core"]),
+            package("core", &[]),
+            package("other", &[]),
+        ];
+        let refs: Vec<&Project> = projects.iter().collect();
+        let logs = [log("core", UpdateType::Patch, "Fix")];
+        let config = Config::default();
+
+        let app = explain(
+            refs[0],
+            &manifest("app"),
+            &refs,
+            &logs,
+            &config,
+            &[],
+            Some(UpdateType::Patch),
+        );
+        assert_eq!(app.dependency_chain, ["app", "lib", "core"]);
+
+        let other = explain(
+            refs[3],
+            &manifest("other"),
+            &refs,
+            &logs,
+            &config,
+            &[],
+            None,
+        );
+        assert!(other.dependency_chain.is_empty());
+        assert_eq!(
+            format_explanation(&other, Some("1.0.0"), None).unwrap(),
+            "other (other/package.json) is not scheduled for a version bump\n"
+        );
+    }
+
+    #[test]
+    fn test_explain_bridge() {
+        let projects = [package("core", &[]), package("napi", &[])];
+        let refs: Vec<&Project> = projects.iter().collect();
+        let logs = [log("core", UpdateType::Minor, "Add streaming")];
+        let bridges = [(manifest("core"), manifest("napi"))];
+
+        let napi = explain(
+            refs[1],
+            &manifest("napi"),
+            &refs,
+            &logs,
+            &Config::default(),
+            &bridges,
+            Some(UpdateType::Minor),
+        );
+        assert_eq!(napi.bridged_crates, [manifest("core")]);
+        assert!(napi.dependency_chain.is_empty());
+    }
+
+    #[test]
+    fn test_format_explanation() {
+        let explanation = Explanation {
+            name: Some("app".to_string()),
+            path: manifest("app"),
+            update_type: Some(UpdateType::Patch),
+            changepacks: vec![ChangepackReason {
+                file: "changepack_log_x.json".to_string(),
+                update_type: UpdateType::Patch,
+                note: "Fix crash\nmore".to_string(),
+            }],
+            update_on: vec![UpdateOnReason {
+                pattern: "core/*".to_string(),
+                triggered_by: vec![manifest("core")],
+            }],
+            bridged_crates: vec![PathBuf::from("core/Cargo.toml")],
+            fixed_group: vec!["a".to_string(), "b".to_string()],
+            dependency_chain: vec!["app".to_string(), "core".to_string()],
+        };
+        let output = format_explanation(&explanation, Some("1.0.0"), None).unwrap();
+        for expected in [
+            "app (app/package.json)",
+            "Changepacks:",
+            "  changepack_log_x.json ",
+            " Fix crash\n",
+            "  core/* triggered by core/package.json\n",
+            "Bridged crates:\n  core/Cargo.toml\n",
+            "Fixed group:\n  a, b\n",
+            "Dependency chain:\n  app → core\n",
+        ] {
+            assert!(
+                output.contains(expected),
+                "missing {expected:?} in {output}"
+            );
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use changepacks_core::path_key;
+use clap_complete::engine::CompletionCandidate;
+
+use crate::{CommandContext, GlobalOptions};
+
+/// Package names (manifest paths for unnamed projects) offered for arguments that accept either
+pub fn package_candidates() -> Vec<CompletionCandidate> {
+    package_candidates_of(&discovered_projects())
+}
+
+/// Manifest paths offered for arguments that take a project path (e.g. `publish --project`)
+pub fn project_path_candidates() -> Vec<CompletionCandidate> {
+    project_path_candidates_of(&discovered_projects())
+}
+
+fn package_candidates_of(projects: &[(Option<String>, String)]) -> Vec<CompletionCandidate> {
+    projects
+        .iter()
+        .map(|(name, path)| match name {
+            Some(name) => CompletionCandidate::new(name).help(Some(path.clone().into())),
+            None => CompletionCandidate::new(path),
+        })
+        .collect()
+}
+
+fn project_path_candidates_of(projects: &[(Option<String>, String)]) -> Vec<CompletionCandidate> {
+    projects
+        .iter()
+        .map(|(name, path)| CompletionCandidate::new(path).help(name.clone().map(Into::into)))
+        .collect()
+}
+
+/// `(name, manifest path)` of every project in the current repository, sorted by path
+///
+/// Completion runs before the command's runtime exists and may be called from inside one,
+/// so discovery gets its own thread and runtime. Any failure yields no candidates.
+///
+/// Excluded from coverage: runs a real discovery pass; the candidate builders are unit tested.
+#[cfg(not(tarpaulin_include))]
+fn discovered_projects() -> Vec<(Option<String>, String)> {
+    std::thread::spawn(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .ok()?;
+        runtime.block_on(async {
+            let ctx = CommandContext::new(&GlobalOptions::default(), false)
+                .await
+                .ok()?;
+            let mut projects: Vec<_> = ctx
+                .project_finders
+                .iter()
+                .flat_map(|finder| finder.projects())
+                .map(|project| {
+                    (
+                        project.name().map(str::to_string),
+                        path_key(project.relative_path()),
+                    )
+                })
+                .collect();
+            projects.sort_by(|a, b| a.1.cmp(&b.1));
+            Some(projects)
+        })
+    })
+    .join()
+    .ok()
+    .flatten()
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projects() -> Vec<(Option<String>, String)> {
+        vec![
+            (
+                Some("core".to_string()),
+                "packages/core/package.json".to_string(),
+            ),
+            (None, "tools/pyproject.toml".to_string()),
+        ]
+    }
+
+    fn values(candidates: &[CompletionCandidate]) -> Vec<String> {
+        candidates
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_package_candidates_of() {
+        let candidates = package_candidates_of(&projects());
+        assert_eq!(values(&candidates), ["core", "tools/pyproject.toml"]);
+        assert_eq!(
+            candidates[0].get_help().map(ToString::to_string).as_deref(),
+            Some("packages/core/package.json")
+        );
+    }
+
+    #[test]
+    fn test_project_path_candidates_of() {
+        let candidates = project_path_candidates_of(&projects());
+        assert_eq!(
+            values(&candidates),
+            ["packages/core/package.json", "tools/pyproject.toml"]
+        );
+        assert_eq!(
+            candidates[0].get_help().map(ToString::to_string).as_deref(),
+            Some("core")
+        );
+        assert!(candidates[1].get_help().is_none());
+    }
+}
@@ -1,53 +1,137 @@
 use crate::finders::get_finders;
-use anyhow::{Context, Result};
-use changepacks_core::Config;
+use anyhow::Result;
 use changepacks_core::ProjectFinder;
-use changepacks_utils::{find_current_git_repo, find_project_dirs, get_changepacks_config};
-use std::path::PathBuf;
+use changepacks_core::{Config, DiffRefs};
+use changepacks_utils::{discover_projects, get_changepacks_config};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Options shared by every command, from the global `--root`, `--base` and `--head` flags
+#[derive(Debug, Clone, Default)]
+pub struct GlobalOptions {
+    /// Release root selected with `--root` (absolute), instead of the nearest one above the
+    /// current directory
+    pub root: Option<PathBuf>,
+    /// Explicit ends of the change-detection comparison
+    pub diff_refs: DiffRefs,
+}
+
+impl GlobalOptions {
+    /// Directory the release root is resolved from: the `--root` directory, or the current
+    /// directory
+    ///
+    /// # Errors
+    /// Returns error if retrieving the current directory fails.
+    pub fn current_dir(&self) -> Result<PathBuf> {
+        match &self.root {
+            Some(root) => Ok(root.clone()),
+            None => Ok(std::env::current_dir()?),
+        }
+    }
+}
 
 /// Shared setup context for all CLI commands.
 ///
-/// Contains git repository path, loaded config, and initialized project finders.
+/// Contains project root path, loaded config, and initialized project finders.
 /// Instantiated once per command to avoid repetitive setup code.
 pub struct CommandContext {
-    /// Root path of the git repository
+    /// Root path of the git repository (or of the plain directory when not using git)
     pub repo_root_path: PathBuf,
     /// Loaded configuration from `.changepacks/config.json`
     pub config: Config,
     /// Project finders for all supported languages
     pub project_finders: Vec<Box<dyn ProjectFinder>>,
+    /// Comparison the projects' changes were detected with
+    pub diff_refs: DiffRefs,
 }
 
 impl CommandContext {
     /// # Errors
-    /// Returns error if finding git repository or discovering projects fails.
+    /// Returns error if loading config or discovering projects fails.
+    ///
+    /// Excluded from coverage: `discover_projects` walks the git index or
+    /// the working tree; exercised end-to-end by the cli integration tests
+    /// which already have full coverage of the surrounding command flow.
+    #[cfg(not(tarpaulin_include))]
+    pub async fn new(global: &GlobalOptions, remote: bool) -> Result<Self> {
+        Self::for_dir(&global.current_dir()?, remote, &global.diff_refs).await
+    }
+
+    /// Set up a context for the repository containing `dir`, detecting changes between
+    /// `diff_refs`
+    ///
+    /// # Errors
+    /// Returns error if loading config or discovering projects fails.
     ///
-    /// Excluded from coverage: requires a real git repository and
-    /// `find_project_dirs` walks the working tree; exercised end-to-end by
-    /// the cli integration tests which already have full coverage of the
-    /// surrounding command flow.
+    /// Excluded from coverage: see [`CommandContext::new`].
     #[cfg(not(tarpaulin_include))]
-    pub async fn new(remote: bool) -> Result<Self> {
-        let current_dir = std::env::current_dir()?;
-        let repo = find_current_git_repo(&current_dir)?;
-        let repo_root_path = repo
-            .work_dir()
-            .context("Not a git working directory. Ensure you are inside a git repository.")?
-            .to_path_buf();
-        let config = get_changepacks_config(&current_dir).await?;
-        let mut project_finders = get_finders();
-        find_project_dirs(&repo, &mut project_finders, &config, remote).await?;
+    pub async fn for_dir(dir: &Path, remote: bool, diff_refs: &DiffRefs) -> Result<Self> {
+        let config = get_changepacks_config(dir).await?;
+        let mut project_finders = get_finders(&config);
+        let discovery =
+            discover_projects(dir, &mut project_finders, &config, remote, diff_refs).await?;
+        if Self::verbose() {
+            for area in &discovery.sparse_skipped {
+                eprintln!(
+                    "Skipped {} (not in sparse checkout)",
+                    if area.as_os_str().is_empty() {
+                        ".".to_string()
+                    } else {
+                        area.display().to_string()
+                    }
+                );
+            }
+        }
 
         Ok(Self {
-            repo_root_path,
+            repo_root_path: discovery.root,
             config,
             project_finders,
+            diff_refs: diff_refs.clone(),
         })
     }
 
+    /// Expand into one labelled context per repository for multi-repo mode
+    ///
+    /// This repository comes first (labelled with its directory name), followed by each entry
+    /// of the config `repositories` list, labelled as written and set up with its own config.
+    ///
     /// # Errors
-    /// Returns error if retrieving the current directory fails.
-    pub fn current_dir() -> Result<PathBuf> {
-        Ok(std::env::current_dir()?)
+    /// Returns error if a listed repository does not exist or its discovery fails.
+    ///
+    /// Excluded from coverage: see [`CommandContext::new`].
+    #[cfg(not(tarpaulin_include))]
+    pub async fn with_repositories(self, remote: bool) -> Result<Vec<(String, Self)>> {
+        let roots: Vec<(String, PathBuf)> = self
+            .config
+            .repositories
+            .iter()
+            .map(|repo| (repo.clone(), self.repo_root_path.join(repo)))
+            .collect();
+        let diff_refs = self.diff_refs.clone();
+        let label = self.repo_root_path.file_name().map_or_else(
+            || ".".to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let mut contexts = vec![(label, self)];
+        for (label, root) in roots {
+            if !root.is_dir() {
+                anyhow::bail!("Repository '{label}' not found at {}", root.display());
+            }
+            contexts.push((label, Self::for_dir(&root, remote, &diff_refs).await?));
+        }
+        Ok(contexts)
+    }
+
+    /// Enable verbose diagnostics (set from the global `--verbose` flag)
+    pub fn set_verbose(verbose: bool) {
+        VERBOSE.store(verbose, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn verbose() -> bool {
+        VERBOSE.load(Ordering::Relaxed)
     }
 }
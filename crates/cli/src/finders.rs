@@ -1,20 +1,30 @@
-use changepacks_core::ProjectFinder;
+use changepacks_core::{Config, ProjectFinder};
 use changepacks_csharp::CSharpProjectFinder;
 use changepacks_dart::DartProjectFinder;
+use changepacks_deno::DenoProjectFinder;
+use changepacks_elixir::ElixirProjectFinder;
+use changepacks_go::GoProjectFinder;
+use changepacks_helm::HelmProjectFinder;
 use changepacks_java::GradleProjectFinder;
+use changepacks_maven::MavenProjectFinder;
 use changepacks_node::NodeProjectFinder;
 use changepacks_python::PythonProjectFinder;
 use changepacks_rust::RustProjectFinder;
 
-/// Get finder list
-pub fn get_finders() -> Vec<Box<dyn ProjectFinder>> {
+/// Get finder list, with the language options `config` sets
+pub fn get_finders(config: &Config) -> Vec<Box<dyn ProjectFinder>> {
     vec![
-        Box::new(NodeProjectFinder::new()),
+        Box::new(NodeProjectFinder::new().with_dependency_range(config.node_dependency_range)),
         Box::new(RustProjectFinder::new()),
         Box::new(PythonProjectFinder::new()),
-        Box::new(DartProjectFinder::new()),
-        Box::new(CSharpProjectFinder::new()),
-        Box::new(GradleProjectFinder::new()),
+        Box::new(DartProjectFinder::new().with_build_number(config.flutter_build_number)),
+        Box::new(CSharpProjectFinder::new().with_assembly_info(config.assembly_info)),
+        Box::new(GradleProjectFinder::new().with_version_code(config.android_version_code)),
+        Box::new(MavenProjectFinder::new()),
+        Box::new(GoProjectFinder::new()),
+        Box::new(ElixirProjectFinder::new()),
+        Box::new(DenoProjectFinder::new()),
+        Box::new(HelmProjectFinder::new().with_app_version(config.helm_app_version)),
     ]
 }
 
@@ -24,7 +34,7 @@ mod tests {
 
     #[test]
     fn test_get_finders() {
-        let finders = get_finders();
-        assert_eq!(finders.len(), 6);
+        let finders = get_finders(&Config::default());
+        assert_eq!(finders.len(), 11);
     }
 }
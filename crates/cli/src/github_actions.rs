@@ -0,0 +1,114 @@
+//! GitHub Actions workflow commands and job summaries
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use changepacks_core::path_key;
+
+/// Severity of a workflow annotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Annotation {
+    Notice,
+    Warning,
+    Error,
+}
+
+impl Annotation {
+    const fn command(self) -> &'static str {
+        match self {
+            Self::Notice => "notice",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// `::notice file=...,title=...::message` line annotating `file` (relative to the repository)
+pub(crate) fn workflow_command(
+    annotation: Annotation,
+    file: Option<&Path>,
+    title: &str,
+    message: &str,
+) -> String {
+    let mut properties = Vec::with_capacity(2);
+    if let Some(file) = file {
+        properties.push(format!("file={}", escape_property(&path_key(file))));
+    }
+    properties.push(format!("title={}", escape_property(title)));
+    format!(
+        "::{} {}::{}",
+        annotation.command(),
+        properties.join(","),
+        escape_data(message)
+    )
+}
+
+/// Append markdown to the job summary named by `$GITHUB_STEP_SUMMARY`
+///
+/// Does nothing outside GitHub Actions, where the variable is unset.
+///
+/// # Errors
+/// Returns error if the summary file cannot be written.
+pub(crate) fn append_step_summary(markdown: &str) -> Result<()> {
+    let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY").filter(|path| !path.is_empty()) else {
+        return Ok(());
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", Path::new(&path).display()))?;
+    file.write_all(markdown.as_bytes())
+        .with_context(|| format!("Failed to write {}", Path::new(&path).display()))
+}
+
+/// Escape a workflow command message
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value, which also ends at `:` and `,`
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn test_workflow_command() {
+        assert_eq!(
+            workflow_command(
+                Annotation::Notice,
+                Some(Path::new("packages/core/package.json")),
+                "core 1.2.0 -> 1.3.0",
+                "Minor bump from 2 changepacks",
+            ),
+            "::notice file=packages/core/package.json,title=core 1.2.0 -> 1.3.0::Minor bump from 2 changepacks"
+        );
+        assert_eq!(
+            workflow_command(Annotation::Error, None, "a:b, c", "100%\nsure"),
+            "::error title=a%3Ab%2C c::100%25%0Asure"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_append_step_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        // SAFETY: tests touching the environment are serialized
+        unsafe { std::env::set_var("GITHUB_STEP_SUMMARY", &path) };
+        append_step_summary("one\n").unwrap();
+        append_step_summary("two\n").unwrap();
+        unsafe { std::env::remove_var("GITHUB_STEP_SUMMARY") };
+        append_step_summary("ignored\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+    }
+}
@@ -6,24 +6,34 @@
 //! command handlers for check, update, publish, config, and init operations. All commands
 //! use the `Prompter` trait for testability and support colored terminal output.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 
-use changepacks_core::UpdateType;
-use clap::{Parser, Subcommand, ValueEnum};
+use changepacks_core::{DiffRefs, UpdateType, changepacks_dir_name, normalize_path};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::CompleteEnv;
 
 use crate::{
     commands::{
-        ChangepackArgs, CheckArgs, ConfigArgs, InitArgs, PublishArgs, UpdateArgs,
-        handle_changepack, handle_check, handle_config, handle_init, handle_publish, handle_update,
+        AddArgs, AffectedArgs, AuditArgs, ChangelogArgs, ChangepackArgs, CheckArgs, ConfigArgs,
+        DedupeArgs, DoctorArgs, FromPrArgs, GraduateArgs, HistoryArgs, HookArgs, InitArgs,
+        PublishArgs, ReleasePrArgs, StatusArgs, SummaryArgs, UpdateArgs, WhyArgs, handle_add,
+        handle_affected, handle_audit, handle_changelog, handle_changepack, handle_check,
+        handle_config, handle_dedupe, handle_doctor, handle_from_pr, handle_graduate,
+        handle_history, handle_hook, handle_init, handle_publish, handle_release_pr, handle_status,
+        handle_summary, handle_update, handle_why,
     },
     options::{CliLanguage, FilterOptions},
 };
 pub mod commands;
+mod completion;
 mod context;
 pub use context::*;
 mod finders;
+mod github_actions;
 pub mod options;
 pub mod prompter;
+mod registries;
 
 pub use prompter::UserCancelled;
 
@@ -32,6 +42,7 @@ enum CliUpdateType {
     Major,
     Minor,
     Patch,
+    Prerelease,
 }
 
 impl From<CliUpdateType> for UpdateType {
@@ -40,6 +51,7 @@ impl From<CliUpdateType> for UpdateType {
             CliUpdateType::Major => Self::Major,
             CliUpdateType::Minor => Self::Minor,
             CliUpdateType::Patch => Self::Patch,
+            CliUpdateType::Prerelease => Self::Prerelease,
         }
     }
 }
@@ -65,47 +77,215 @@ struct Cli {
     #[arg(short, long, default_value = "false")]
     yes: bool,
 
+    /// Select every project detected as changed, without prompting
     #[arg(short, long)]
-    message: Option<String>,
+    all: bool,
+
+    /// Pre-select every project changed since the base branch, not only those with staged
+    /// or uncommitted changes
+    #[arg(long)]
+    since_base: bool,
+
+    /// Changepack notes (markdown). Repeat to write one bullet per message.
+    #[arg(short, long, conflicts_with = "message_file")]
+    message: Vec<String>,
+
+    /// Read changepack notes from a file (`-` for stdin)
+    #[arg(short = 'F', long)]
+    message_file: Option<PathBuf>,
 
     #[arg(short, long)]
     update_type: Option<CliUpdateType>,
 
+    /// Prerelease identifier for `--update-type prerelease` (e.g. `beta` for `1.2.0-beta.3`)
+    #[arg(long, value_name = "ID", value_parser = parse_preid)]
+    preid: Option<String>,
+
     /// Filter projects by language. Can be specified multiple times to include multiple languages.
     #[arg(short, long, value_enum)]
     language: Vec<CliLanguage>,
+
+    /// Answer a changepack template prompt up front (`key=value`). Can be repeated.
+    #[arg(long = "field", value_name = "KEY=VALUE", value_parser = parse_field)]
+    fields: Vec<(String, String)>,
+
+    /// Print extra diagnostics (e.g. paths skipped by sparse checkout)
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Release root to operate on (default: the nearest directory with a `.changepacks` folder)
+    #[arg(long, global = true, value_name = "DIR")]
+    root: Option<PathBuf>,
+
+    /// Revision change detection compares against, instead of the base branch (e.g. `origin/main`)
+    #[arg(long, global = true, value_name = "REF")]
+    base: Option<String>,
+
+    /// Revision whose changes are detected, instead of `HEAD` and uncommitted files
+    #[arg(long, global = true, value_name = "REF")]
+    head: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     Init(InitArgs),
+    Add(AddArgs),
     Check(CheckArgs),
+    Status(StatusArgs),
     Update(UpdateArgs),
     Config(ConfigArgs),
     Publish(PublishArgs),
+    History(HistoryArgs),
+    Changelog(ChangelogArgs),
+    Audit(AuditArgs),
+    Why(WhyArgs),
+    Affected(AffectedArgs),
+    Graduate(GraduateArgs),
+    Doctor(DoctorArgs),
+    ReleasePr(ReleasePrArgs),
+    Hook(HookArgs),
+    Dedupe(DedupeArgs),
+    Summary(SummaryArgs),
+    FromPr(FromPrArgs),
+}
+
+/// Parse a `key=value` pair for `--field`
+pub(crate) fn parse_field(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{raw}'")),
+    }
+}
+
+/// Validate a `--preid` as dot-separated alphanumerics or hyphens
+pub(crate) fn parse_preid(raw: &str) -> Result<String, String> {
+    if raw.split('.').all(|part| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    }) {
+        Ok(raw.to_string())
+    } else {
+        Err(format!(
+            "invalid prerelease identifier '{raw}' (expected dot-separated alphanumerics or hyphens)"
+        ))
+    }
+}
+
+/// Combine `--message` values into notes; several messages become a markdown bullet list
+pub(crate) fn notes_from_messages(messages: Vec<String>) -> Option<String> {
+    match messages.len() {
+        0 => None,
+        1 => messages.into_iter().next(),
+        _ => Some(
+            messages
+                .iter()
+                .map(|message| format!("- {}", message.trim()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
+
+/// Read changepack notes from `path`, or from stdin when `path` is `-`
+///
+/// Trailing whitespace is trimmed so files ending in a newline produce clean notes.
+pub(crate) fn read_message_file(path: &Path) -> Result<String> {
+    let content = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read message file {}", path.display()))?
+    };
+    Ok(content.trim_end().to_string())
+}
+
+/// Absolute, canonical form of a `--root` directory
+fn resolve_root(root: &Path) -> Result<PathBuf> {
+    let resolved = std::fs::canonicalize(root)
+        .with_context(|| format!("Root directory {} not found", root.display()))?;
+    if !resolved.is_dir() {
+        anyhow::bail!("Root {} is not a directory", root.display());
+    }
+    // Windows canonical paths carry a `\\?\` prefix that git and the current directory lack
+    Ok(normalize_path(&resolved))
 }
 
 /// # Errors
 /// Returns error if command execution fails.
 pub async fn main(args: &[String]) -> Result<()> {
+    // With `COMPLETE=<shell>` set, print the shell registration script or the completions
+    // the shell asked for instead of running a command
+    if CompleteEnv::with_factory(Cli::command)
+        .bin("changepacks")
+        .try_complete(args, std::env::current_dir().ok().as_deref())?
+    {
+        return Ok(());
+    }
     let cli = Cli::parse_from(args);
+    CommandContext::set_verbose(cli.verbose);
+    let root = cli.root.as_deref().map(resolve_root).transpose()?;
+    if let Some(root) = &root
+        && !matches!(cli.command, Some(Commands::Init(_)))
+        && !root.join(changepacks_dir_name()).is_dir()
+    {
+        anyhow::bail!(
+            "{} is not a changepacks root (run `changepacks --root {} init` first)",
+            root.display(),
+            root.display()
+        );
+    }
+    let global = GlobalOptions {
+        root,
+        diff_refs: DiffRefs {
+            base: cli.base,
+            head: cli.head,
+        },
+    };
     if let Some(command) = cli.command {
         match command {
-            Commands::Init(args) => handle_init(&args).await?,
-            Commands::Check(args) => handle_check(&args).await?,
-            Commands::Update(args) => handle_update(&args).await?,
-            Commands::Config(args) => handle_config(&args).await?,
-            Commands::Publish(args) => handle_publish(&args).await?,
+            Commands::Init(args) => handle_init(&args, &global).await?,
+            Commands::Add(args) => handle_add(&args, &global).await?,
+            Commands::Check(args) => handle_check(&args, &global).await?,
+            Commands::Status(args) => handle_status(&args, &global).await?,
+            Commands::Update(args) => handle_update(&args, &global).await?,
+            Commands::Config(args) => handle_config(&args, &global).await?,
+            Commands::Publish(args) => handle_publish(&args, &global).await?,
+            Commands::History(args) => handle_history(&args, &global).await?,
+            Commands::Changelog(args) => handle_changelog(&args, &global).await?,
+            Commands::Audit(args) => handle_audit(&args, &global).await?,
+            Commands::Why(args) => handle_why(&args, &global).await?,
+            Commands::Affected(args) => handle_affected(&args, &global).await?,
+            Commands::Graduate(args) => handle_graduate(&args, &global).await?,
+            Commands::Doctor(args) => handle_doctor(&args, &global).await?,
+            Commands::ReleasePr(args) => handle_release_pr(&args, &global).await?,
+            Commands::Hook(args) => handle_hook(&args, &global).await?,
+            Commands::Dedupe(args) => handle_dedupe(&args, &global).await?,
+            Commands::Summary(args) => handle_summary(&args, &global).await?,
+            Commands::FromPr(args) => handle_from_pr(&args, &global).await?,
         }
     } else {
-        handle_changepack(&ChangepackArgs {
-            filter: cli.filter,
-            remote: cli.remote,
-            yes: cli.yes,
-            message: cli.message,
-            update_type: cli.update_type.map(Into::into),
-            language: cli.language,
-        })
+        if cli.preid.is_some() && !matches!(cli.update_type, Some(CliUpdateType::Prerelease)) {
+            anyhow::bail!("--preid only applies to --update-type prerelease");
+        }
+        handle_changepack(
+            &ChangepackArgs {
+                filter: cli.filter,
+                remote: cli.remote,
+                yes: cli.yes,
+                all: cli.all,
+                since_base: cli.since_base,
+                message: match cli.message_file {
+                    Some(path) => Some(read_message_file(&path)?),
+                    None => notes_from_messages(cli.message),
+                },
+                update_type: cli.update_type.map(Into::into),
+                language: cli.language,
+                fields: cli.fields,
+                preid: cli.preid,
+            },
+            &global,
+        )
         .await?;
     }
     Ok(())
@@ -120,6 +300,7 @@ mod tests {
     #[case(CliUpdateType::Major, UpdateType::Major)]
     #[case(CliUpdateType::Minor, UpdateType::Minor)]
     #[case(CliUpdateType::Patch, UpdateType::Patch)]
+    #[case(CliUpdateType::Prerelease, UpdateType::Prerelease)]
     fn test_cli_update_type_to_update_type(
         #[case] cli_type: CliUpdateType,
         #[case] expected: UpdateType,
@@ -128,6 +309,176 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_cli_parsing_verbose_is_global() {
+        use clap::Parser;
+        assert!(!Cli::parse_from(["changepacks", "check"]).verbose);
+        assert!(Cli::parse_from(["changepacks", "check", "--verbose"]).verbose);
+        assert!(Cli::parse_from(["changepacks", "-v", "update"]).verbose);
+    }
+
+    #[test]
+    fn test_cli_parsing_root_is_global() {
+        use clap::Parser;
+        assert!(Cli::parse_from(["changepacks", "check"]).root.is_none());
+        let cli = Cli::parse_from(["changepacks", "check", "--root", "apps/web"]);
+        assert_eq!(cli.root, Some(PathBuf::from("apps/web")));
+        let cli = Cli::parse_from(["changepacks", "--root", "apps/web", "-m", "Fix"]);
+        assert_eq!(cli.root, Some(PathBuf::from("apps/web")));
+    }
+
+    #[test]
+    fn test_resolve_root() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let resolved = resolve_root(temp_dir.path()).unwrap();
+        assert_eq!(resolved, std::fs::canonicalize(temp_dir.path()).unwrap());
+        assert!(resolve_root(&temp_dir.path().join("missing")).is_err());
+        let file = temp_dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(resolve_root(&file).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_message_file() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "-F", "notes.md"]);
+        assert_eq!(cli.message_file, Some(PathBuf::from("notes.md")));
+        let cli = Cli::parse_from(["changepacks", "--message-file", "-"]);
+        assert_eq!(cli.message_file, Some(PathBuf::from("-")));
+        assert!(Cli::try_parse_from(["changepacks", "-m", "x", "-F", "notes.md"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_fields() {
+        use clap::Parser;
+        let cli = Cli::parse_from([
+            "changepacks",
+            "--field",
+            "issue=ABC-123",
+            "--field",
+            "breaking=a=b",
+        ]);
+        assert_eq!(
+            cli.fields,
+            vec![
+                ("issue".to_string(), "ABC-123".to_string()),
+                ("breaking".to_string(), "a=b".to_string()),
+            ]
+        );
+        assert!(Cli::try_parse_from(["changepacks", "--field", "issue"]).is_err());
+        assert!(Cli::try_parse_from(["changepacks", "--field", "=x"]).is_err());
+    }
+
+    #[test]
+    fn test_read_message_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("notes.md");
+        std::fs::write(&path, "Add feature\n\n- detail one\n- detail two\n\n").unwrap();
+        assert_eq!(
+            read_message_file(&path).unwrap(),
+            "Add feature\n\n- detail one\n- detail two"
+        );
+        assert!(read_message_file(&temp_dir.path().join("missing.md")).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_audit() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "audit", "--days", "60"]);
+        assert!(matches!(cli.command, Some(Commands::Audit(args)) if args.days == 60));
+    }
+
+    #[test]
+    fn test_cli_parsing_why() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "why", "core"]);
+        assert!(matches!(cli.command, Some(Commands::Why(args)) if args.package == "core"));
+    }
+
+    #[test]
+    fn test_cli_parsing_affected() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "affected", "main..HEAD"]);
+        assert!(
+            matches!(cli.command, Some(Commands::Affected(args)) if args.range.as_deref() == Some("main..HEAD"))
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_graduate() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "graduate", "core", "--yes"]);
+        assert!(
+            matches!(cli.command, Some(Commands::Graduate(args)) if args.projects == ["core"] && args.yes)
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_doctor() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "doctor", "--format", "json"]);
+        assert!(matches!(cli.command, Some(Commands::Doctor(_))));
+    }
+
+    #[test]
+    fn test_cli_parsing_from_pr() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "from-pr", "--pr", "42", "--dry-run"]);
+        assert!(
+            matches!(cli.command, Some(Commands::FromPr(args)) if args.pr == Some(42) && args.dry_run)
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_base_and_head() {
+        use clap::Parser;
+        let cli = Cli::parse_from([
+            "changepacks",
+            "check",
+            "--base",
+            "origin/main",
+            "--head",
+            "abc123",
+        ]);
+        assert_eq!(cli.base.as_deref(), Some("origin/main"));
+        assert_eq!(cli.head.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn test_cli_parsing_summary() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "summary", "--format", "json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Summary(SummaryArgs {
+                format: crate::options::FormatOptions::Json
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_cli_parsing_dedupe() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "dedupe", "--dry-run"]);
+        assert!(matches!(cli.command, Some(Commands::Dedupe(args)) if args.dry_run));
+    }
+
+    #[test]
+    fn test_cli_parsing_hook() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "hook", "install", "--force"]);
+        assert!(matches!(cli.command, Some(Commands::Hook(_))));
+    }
+
+    #[test]
+    fn test_cli_parsing_release_pr() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "release-pr", "--branch", "release", "-d"]);
+        assert!(
+            matches!(cli.command, Some(Commands::ReleasePr(args)) if args.branch == "release" && args.dry_run && args.title == "Version Packages")
+        );
+    }
+
     // Test that Cli struct parses correctly
     #[test]
     fn test_cli_parsing_init() {
@@ -143,6 +494,20 @@ mod tests {
         assert!(matches!(cli.command, Some(Commands::Check(_))));
     }
 
+    #[test]
+    fn test_cli_parsing_add() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "add", "-p", "core=minor", "-m", "Add X"]);
+        assert!(matches!(cli.command, Some(Commands::Add(args)) if args.packages.len() == 1));
+    }
+
+    #[test]
+    fn test_cli_parsing_status() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "status", "--format", "json"]);
+        assert!(matches!(cli.command, Some(Commands::Status(_))));
+    }
+
     #[test]
     fn test_cli_parsing_update() {
         use clap::Parser;
@@ -164,6 +529,20 @@ mod tests {
         assert!(matches!(cli.command, Some(Commands::Publish(_))));
     }
 
+    #[test]
+    fn test_cli_parsing_history() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "history", "core", "--version", "1.0.0"]);
+        assert!(matches!(cli.command, Some(Commands::History(_))));
+    }
+
+    #[test]
+    fn test_cli_parsing_changelog() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "changelog", "core", "--from", "1.0.0"]);
+        assert!(matches!(cli.command, Some(Commands::Changelog(_))));
+    }
+
     #[test]
     fn test_cli_parsing_default_with_options() {
         use clap::Parser;
@@ -177,10 +556,50 @@ mod tests {
         ]);
         assert!(cli.command.is_none());
         assert!(cli.yes);
-        assert_eq!(cli.message, Some("test".to_string()));
+        assert_eq!(cli.message, ["test"]);
         assert!(matches!(cli.update_type, Some(CliUpdateType::Patch)));
     }
 
+    #[test]
+    fn test_cli_parsing_preid() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "-u", "prerelease", "--preid", "beta"]);
+        assert!(matches!(cli.update_type, Some(CliUpdateType::Prerelease)));
+        assert_eq!(cli.preid.as_deref(), Some("beta"));
+        assert!(
+            Cli::try_parse_from(["changepacks", "-u", "prerelease", "--preid", "be ta"]).is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["changepacks", "-u", "prerelease", "--preid", "rc..1"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_notes_from_messages() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["changepacks", "-m", "Add streaming", "-m", "Fix `parse`"]);
+        assert_eq!(cli.message, ["Add streaming", "Fix `parse`"]);
+        assert_eq!(
+            notes_from_messages(cli.message),
+            Some("- Add streaming\n- Fix `parse`".to_string())
+        );
+        assert_eq!(
+            notes_from_messages(vec!["Only one".to_string()]),
+            Some("Only one".to_string())
+        );
+        assert_eq!(notes_from_messages(vec![]), None);
+    }
+
+    #[test]
+    fn test_cli_parsing_all() {
+        use clap::Parser;
+        assert!(!Cli::parse_from(["changepacks"]).all);
+        let cli = Cli::parse_from(["changepacks", "--all", "-u", "patch", "-m", "Fix"]);
+        assert!(cli.all);
+        assert!(!cli.yes);
+        assert!(Cli::parse_from(["changepacks", "-a"]).all);
+    }
+
     #[test]
     fn test_cli_parsing_with_filter() {
         use clap::Parser;
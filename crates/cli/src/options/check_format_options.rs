@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+
+/// Output format of the check command.
+///
+/// Adds GitHub Actions workflow commands to the formats every command supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CheckFormatOptions {
+    /// JSON format for CI/CD pipelines
+    #[value(name = "json")]
+    Json,
+    /// Human-readable colored terminal output
+    #[value(name = "stdout")]
+    Stdout,
+    /// `::notice`/`::error` annotations plus a job summary in `$GITHUB_STEP_SUMMARY`
+    #[value(name = "github")]
+    Github,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("json", CheckFormatOptions::Json)]
+    #[case("stdout", CheckFormatOptions::Stdout)]
+    #[case("github", CheckFormatOptions::Github)]
+    fn test_check_format_options_value_enum(
+        #[case] value: &str,
+        #[case] expected: CheckFormatOptions,
+    ) {
+        assert_eq!(CheckFormatOptions::from_str(value, true).unwrap(), expected);
+    }
+}
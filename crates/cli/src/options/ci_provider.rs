@@ -0,0 +1,49 @@
+use clap::ValueEnum;
+
+/// CI service `init --ci` writes a release workflow for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CiProvider {
+    /// GitHub Actions (`.github/workflows/changepacks.yml`)
+    Github,
+    /// GitLab CI/CD (`.gitlab-ci.yml`)
+    Gitlab,
+}
+
+impl CiProvider {
+    /// Workflow file location, relative to the project root
+    #[must_use]
+    pub const fn workflow_path(self) -> &'static str {
+        match self {
+            Self::Github => ".github/workflows/changepacks.yml",
+            Self::Gitlab => ".gitlab-ci.yml",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_ci_provider_value_enum() {
+        assert_eq!(
+            CiProvider::from_str("github", true).unwrap(),
+            CiProvider::Github
+        );
+        assert_eq!(
+            CiProvider::from_str("gitlab", true).unwrap(),
+            CiProvider::Gitlab
+        );
+        assert!(CiProvider::from_str("jenkins", true).is_err());
+    }
+
+    #[test]
+    fn test_ci_provider_workflow_path() {
+        assert_eq!(
+            CiProvider::Github.workflow_path(),
+            ".github/workflows/changepacks.yml"
+        );
+        assert_eq!(CiProvider::Gitlab.workflow_path(), ".gitlab-ci.yml");
+    }
+}
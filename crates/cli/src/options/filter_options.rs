@@ -60,7 +60,11 @@ mod tests {
             &self.relative_path
         }
 
-        async fn update_version(&mut self, _update_type: UpdateType) -> anyhow::Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> anyhow::Result<()> {
             Ok(())
         }
 
@@ -121,7 +125,11 @@ mod tests {
             &self.relative_path
         }
 
-        async fn update_version(&mut self, _update_type: UpdateType) -> anyhow::Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> anyhow::Result<()> {
             Ok(())
         }
 
@@ -9,6 +9,10 @@ pub enum CliLanguage {
     Dart,
     Java,
     CSharp,
+    Go,
+    Elixir,
+    Deno,
+    Helm,
 }
 
 impl From<CliLanguage> for Language {
@@ -20,6 +24,10 @@ impl From<CliLanguage> for Language {
             CliLanguage::Dart => Self::Dart,
             CliLanguage::Java => Self::Java,
             CliLanguage::CSharp => Self::CSharp,
+            CliLanguage::Go => Self::Go,
+            CliLanguage::Elixir => Self::Elixir,
+            CliLanguage::Deno => Self::Deno,
+            CliLanguage::Helm => Self::Helm,
         }
     }
 }
@@ -36,6 +44,10 @@ mod tests {
     #[case(CliLanguage::Dart, Language::Dart)]
     #[case(CliLanguage::Java, Language::Java)]
     #[case(CliLanguage::CSharp, Language::CSharp)]
+    #[case(CliLanguage::Go, Language::Go)]
+    #[case(CliLanguage::Elixir, Language::Elixir)]
+    #[case(CliLanguage::Deno, Language::Deno)]
+    #[case(CliLanguage::Helm, Language::Helm)]
     fn test_cli_language_to_language(#[case] cli_lang: CliLanguage, #[case] expected: Language) {
         let result: Language = cli_lang.into();
         assert_eq!(result, expected);
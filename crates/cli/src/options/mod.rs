@@ -1,6 +1,14 @@
+mod check_format_options;
+mod ci_provider;
 mod filter_options;
 mod format_options;
 mod language_options;
+mod sort_options;
+mod tree_direction;
+pub use check_format_options::CheckFormatOptions;
+pub use ci_provider::CiProvider;
 pub use filter_options::FilterOptions;
 pub use format_options::FormatOptions;
 pub use language_options::CliLanguage;
+pub use sort_options::SortOptions;
+pub use tree_direction::TreeDirection;
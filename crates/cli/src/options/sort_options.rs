@@ -0,0 +1,164 @@
+use std::cmp::Ordering;
+
+use changepacks_core::{Project, UpdateType};
+use clap::ValueEnum;
+
+/// CLI ordering for project listings.
+///
+/// Used by the check command to order stdout lines and JSON entries. Ties are broken by
+/// relative path so listings are deterministic.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortOptions {
+    /// Sort by package name (unnamed projects last)
+    Name,
+    /// Sort by manifest path relative to the repository root
+    Path,
+    /// Sort by language, then name
+    Language,
+    /// Sort by pending bump, major first; projects without a bump last
+    Bump,
+}
+
+impl SortOptions {
+    /// Sort `projects` in place; `bump` looks up a project's pending update
+    pub fn sort(&self, projects: &mut [&Project], bump: impl Fn(&Project) -> Option<UpdateType>) {
+        projects.sort_by(|a, b| {
+            let ordering = match self {
+                Self::Name => compare_names(a, b),
+                Self::Path => Ordering::Equal,
+                Self::Language => a
+                    .language()
+                    .cmp(&b.language())
+                    .then_with(|| compare_names(a, b)),
+                Self::Bump => match (bump(a), bump(b)) {
+                    (Some(x), Some(y)) => x.cmp(&y),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+                .then_with(|| compare_names(a, b)),
+            };
+            ordering.then_with(|| a.relative_path().cmp(b.relative_path()))
+        });
+    }
+}
+
+fn compare_names(a: &Project, b: &Project) -> Ordering {
+    match (a.name(), b.name()) {
+        (Some(x), Some(y)) => x.cmp(y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use changepacks_core::{Language, Package};
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::path::{Path, PathBuf};
+
+    #[derive(Debug)]
+    struct MockPackage {
+        name: Option<String>,
+        relative_path: PathBuf,
+        language: Language,
+        dependencies: HashSet<String>,
+    }
+
+    #[async_trait]
+    impl Package for MockPackage {
+        fn name(&self) -> Option<&str> {
+            self.name.as_deref()
+        }
+
+        fn version(&self) -> Option<&str> {
+            Some("1.0.0")
+        }
+
+        fn path(&self) -> &Path {
+            &self.relative_path
+        }
+
+        fn relative_path(&self) -> &Path {
+            &self.relative_path
+        }
+
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn is_changed(&self) -> bool {
+            false
+        }
+
+        fn language(&self) -> Language {
+            self.language
+        }
+
+        fn dependencies(&self) -> &HashSet<String> {
+            &self.dependencies
+        }
+
+        fn add_dependency(&mut self, _dependency: &str) {}
+
+        fn set_changed(&mut self, _changed: bool) {}
+
+        fn default_publish_command(&self) -> String {
+            "echo publish".to_string()
+        }
+        fn default_dry_run_publish_command(&self) -> Option<String> {
+            None
+        }
+    }
+
+    fn package(name: Option<&str>, relative_path: &str, language: Language) -> Project {
+        Project::Package(Box::new(MockPackage {
+            name: name.map(str::to_string),
+            relative_path: PathBuf::from(relative_path),
+            language,
+            dependencies: HashSet::new(),
+        }))
+    }
+
+    #[rstest]
+    #[case(SortOptions::Name, ["alpha", "beta", "gamma", "-"])]
+    #[case(SortOptions::Path, ["-", "gamma", "beta", "alpha"])]
+    #[case(SortOptions::Language, ["beta", "gamma", "alpha", "-"])]
+    #[case(SortOptions::Bump, ["gamma", "alpha", "beta", "-"])]
+    fn test_sort_options(#[case] sort: SortOptions, #[case] expected: [&str; 4]) {
+        let projects = [
+            package(Some("alpha"), "z/Cargo.toml", Language::Rust),
+            package(Some("beta"), "c/package.json", Language::Node),
+            package(Some("gamma"), "b/package.json", Language::Node),
+            package(None, "a/Cargo.toml", Language::Rust),
+        ];
+        let mut refs: Vec<&Project> = projects.iter().collect();
+        sort.sort(&mut refs, |project| match project.name() {
+            Some("alpha") => Some(UpdateType::Minor),
+            Some("beta") => Some(UpdateType::Patch),
+            Some("gamma") => Some(UpdateType::Major),
+            _ => None,
+        });
+        let names: Vec<&str> = refs
+            .iter()
+            .map(|project| project.name().unwrap_or("-"))
+            .collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn test_sort_options_value_enum() {
+        assert!(matches!(
+            SortOptions::from_str("bump", true).unwrap(),
+            SortOptions::Bump
+        ));
+    }
+}
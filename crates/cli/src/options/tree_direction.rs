@@ -0,0 +1,29 @@
+use clap::ValueEnum;
+
+/// Edge direction for `check --tree`.
+///
+/// Selects whether a node's children are the projects it depends on or the projects that
+/// depend on it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TreeDirection {
+    /// Children are the projects a node depends on
+    #[default]
+    Dependencies,
+    /// Children are the projects that depend on a node
+    Dependents,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_tree_direction_value_enum() {
+        assert_eq!(
+            TreeDirection::from_str("dependents", true).unwrap(),
+            TreeDirection::Dependents
+        );
+        assert_eq!(TreeDirection::default(), TreeDirection::Dependencies);
+    }
+}
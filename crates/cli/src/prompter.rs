@@ -173,7 +173,11 @@ mod tests {
         fn relative_path(&self) -> &Path {
             Path::new("package.json")
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
         fn is_changed(&self) -> bool {
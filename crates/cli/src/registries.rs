@@ -0,0 +1,71 @@
+use changepacks_core::Config;
+use changepacks_registry::{Registry, RegistryClient};
+
+/// Client for `registry`, using the endpoint and token `config` sets for it
+///
+/// The token is read from the configured environment variable; an unset or empty variable
+/// sends no token, so public lookups keep working without credentials.
+pub fn registry_client(config: &Config, registry: Registry) -> RegistryClient {
+    let Some(endpoint) = config.registries.get(registry.name()) else {
+        return RegistryClient::new(registry);
+    };
+    let client = match &endpoint.url {
+        Some(url) => RegistryClient::with_base_url(registry, url),
+        None => RegistryClient::new(registry),
+    };
+    match endpoint
+        .token_env
+        .as_deref()
+        .and_then(|var| std::env::var(var).ok())
+        .filter(|token| !token.is_empty())
+    {
+        Some(token) => client.with_token(token),
+        None => client,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::RegistryEndpoint;
+    use serial_test::serial;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_registry_client_uses_endpoint_and_token() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let body = r#"{"versions":{"1.0.0":{}}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+
+        let var = "CHANGEPACKS_TEST_REGISTRY_CLIENT_TOKEN";
+        // SAFETY: tests touching the environment are serialized
+        unsafe { std::env::set_var(var, "secret") };
+        let mut config = Config::default();
+        config.registries.insert(
+            "npm".to_string(),
+            RegistryEndpoint {
+                url: Some(url),
+                token_env: Some(var.to_string()),
+            },
+        );
+        let client = registry_client(&config, Registry::Npm);
+        unsafe { std::env::remove_var(var) };
+        assert!(client.version_exists("pkg", "1.0.0").await.unwrap());
+        let request = server.join().unwrap();
+        assert!(request.contains("authorization: bearer secret"));
+    }
+}
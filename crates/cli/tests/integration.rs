@@ -77,6 +77,53 @@ async fn test_cli_init_creates_config() {
     assert!(temp_path.join(".changepacks/config.json").exists());
 }
 
+#[tokio::test]
+#[serial]
+async fn test_cli_init_with_ci() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path();
+
+    init_git_repo(temp_path);
+    std::fs::write(
+        temp_path.join("Cargo.toml"),
+        "[package]\nname = \"crate-a\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(temp_path).unwrap();
+
+    let init = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "init".to_string(),
+        "--ci".to_string(),
+        "github".to_string(),
+    ])
+    .await;
+    // An initialized project can still add a workflow for another provider
+    let add_gitlab = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "init".to_string(),
+        "--ci".to_string(),
+        "gitlab".to_string(),
+    ])
+    .await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(init.is_ok(), "init --ci failed: {:?}", init.err());
+    assert!(
+        add_gitlab.is_ok(),
+        "init --ci failed: {:?}",
+        add_gitlab.err()
+    );
+    assert!(temp_path.join(".changepacks/config.json").exists());
+    let workflow =
+        std::fs::read_to_string(temp_path.join(".github/workflows/changepacks.yml")).unwrap();
+    assert!(workflow.contains("dtolnay/rust-toolchain@stable"));
+    assert!(temp_path.join(".gitlab-ci.yml").exists());
+}
+
 #[tokio::test]
 #[serial]
 async fn test_cli_config() {
@@ -96,6 +143,91 @@ async fn test_cli_config() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+#[serial]
+async fn test_cli_config_set() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path();
+
+    init_git_repo(temp_path);
+    std::fs::create_dir_all(temp_path.join(".changepacks")).unwrap();
+    std::fs::write(
+        temp_path.join(".changepacks/config.toml"),
+        "# Shared with the release workflow\nbaseBranch = \"develop\"\n",
+    )
+    .unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(temp_path).unwrap();
+
+    let run = |args: &[&str]| {
+        let args: Vec<String> = ["changepacks", "config"]
+            .iter()
+            .chain(args)
+            .map(ToString::to_string)
+            .collect();
+        async move { changepacks_cli::main(&args).await }
+    };
+    let set_publish = run(&["set", "publish.node", "pnpm publish"]).await;
+    let set_ignore = run(&["set", "ignore", "[\"docs/**\"]"]).await;
+    let set_unknown = run(&["set", "basebranch", "main"]).await;
+    let get = run(&["get", "publish.node"]).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(set_publish.is_ok());
+    assert!(set_ignore.is_ok());
+    assert!(get.is_ok());
+    assert!(
+        set_unknown
+            .unwrap_err()
+            .to_string()
+            .contains("did you mean `baseBranch`?")
+    );
+    assert_eq!(
+        std::fs::read_to_string(temp_path.join(".changepacks/config.toml")).unwrap(),
+        "# Shared with the release workflow\nbaseBranch = \"develop\"\nignore = [\"docs/**\"]\n\n[publish]\nnode = \"pnpm publish\"\n"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_config_check() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path();
+
+    init_git_repo(temp_path);
+    std::fs::create_dir_all(temp_path.join(".changepacks")).unwrap();
+    std::fs::write(
+        temp_path.join(".changepacks/config.toml"),
+        "baseBranch = \"develop\"\nignore = [\"docs/**\"]\n",
+    )
+    .unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(temp_path).unwrap();
+
+    let args: Vec<String> = ["changepacks", "config", "--check"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let valid = changepacks_cli::main(&args).await;
+    std::fs::write(
+        temp_path.join(".changepacks/config.toml"),
+        "baseBranch = \"a b\"\nignore = [\"src/[*\"]\n",
+    )
+    .unwrap();
+    let invalid = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(valid.is_ok());
+    assert_eq!(
+        invalid.unwrap_err().to_string(),
+        "Found 2 problem(s) in config.toml"
+    );
+}
+
 #[tokio::test]
 #[serial]
 async fn test_cli_publish_dry_run() {
@@ -444,6 +576,103 @@ async fn test_cli_check_json_format() {
     );
 }
 
+#[tokio::test]
+#[serial]
+async fn test_cli_check_sort() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    for (dir, name) in [("a", "zeta"), ("b", "alpha")] {
+        tokio::fs::create_dir_all(temp_path.join(dir))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(dir).join("package.json"),
+            format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+        )
+        .await
+        .unwrap();
+    }
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let mut results = Vec::new();
+    for sort in ["name", "path", "language", "bump"] {
+        for format in ["stdout", "json"] {
+            let args: Vec<String> = ["changepacks", "check", "--sort", sort, "--format", format]
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            results.push(changepacks_cli::main(&args).await);
+        }
+    }
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    for result in results {
+        assert!(result.is_ok(), "check --sort failed: {:?}", result.err());
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_check_only_changed() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    for dir in ["a", "b"] {
+        tokio::fs::create_dir_all(temp_path.join(dir))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(dir).join("package.json"),
+            format!(r#"{{"name": "{dir}", "version": "1.0.0"}}"#),
+        )
+        .await
+        .unwrap();
+    }
+
+    git_add_and_commit(&temp_path, "Initial commit");
+    tokio::fs::write(temp_path.join("a").join("index.js"), "export {}")
+        .await
+        .unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let mut results = Vec::new();
+    for format in ["stdout", "json"] {
+        let args: Vec<String> = ["changepacks", "check", "--only-changed", "--format", format]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        results.push(changepacks_cli::main(&args).await);
+    }
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    for result in results {
+        assert!(
+            result.is_ok(),
+            "check --only-changed failed: {:?}",
+            result.err()
+        );
+    }
+}
+
 #[tokio::test]
 #[serial]
 async fn test_cli_check_tree() {
@@ -497,6 +726,66 @@ async fn test_cli_check_tree() {
     assert!(result.is_ok(), "check tree failed: {:?}", result.err());
 }
 
+#[tokio::test]
+#[serial]
+async fn test_cli_check_tree_depth_and_direction() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "root-pkg", "version": "1.0.0", "dependencies": {"child-pkg": "workspace:*"}}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
+    )
+    .await
+    .unwrap();
+    tokio::fs::create_dir_all(temp_path.join("packages/child"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("packages/child/package.json"),
+        r#"{"name": "child-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let mut results = Vec::new();
+    for extra in [
+        &["--depth", "0"][..],
+        &["--direction", "dependents"],
+        &["--direction", "dependents", "--depth", "1"],
+    ] {
+        let mut args = vec![
+            "changepacks".to_string(),
+            "check".to_string(),
+            "--tree".to_string(),
+        ];
+        args.extend(extra.iter().map(ToString::to_string));
+        results.push(changepacks_cli::main(&args).await);
+    }
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    for result in results {
+        assert!(result.is_ok(), "check tree failed: {:?}", result.err());
+    }
+}
+
 #[tokio::test]
 #[serial]
 async fn test_cli_check_filter_package() {
@@ -714,359 +1003,554 @@ async fn test_cli_changepacks_with_yes_and_message() {
     assert!(!entries.is_empty(), "No changepack log file was created");
 }
 
+// Test `add` writing a changepack for named packages without prompting
 #[tokio::test]
 #[serial]
-async fn test_cli_changepacks_no_projects() {
+async fn test_cli_add() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
-
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    tokio::fs::write(temp_path.join("README.md"), "# Test")
+    for name in ["core", "web"] {
+        tokio::fs::create_dir_all(temp_path.join(name))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(format!("{name}/package.json")),
+            format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+        )
         .await
         .unwrap();
-
+    }
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // With --yes and no projects, it should print "No projects selected"
-    let args = vec![
-        "changepacks".to_string(),
-        "--yes".to_string(),
-        "-m".to_string(),
-        "Test message".to_string(),
-        "--update-type".to_string(),
-        "patch".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+    let to_args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let unknown = changepacks_cli::main(&to_args(&[
+        "changepacks",
+        "add",
+        "-p",
+        "core=minor",
+        "-p",
+        "docs=patch",
+        "-m",
+        "Add X",
+    ]))
+    .await;
+    let added = changepacks_cli::main(&to_args(&[
+        "changepacks",
+        "add",
+        "-p",
+        "core=minor",
+        "-p",
+        "web/package.json=patch",
+        "-m",
+        "Add X",
+    ]))
+    .await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    // Should succeed but not create any log (no projects)
-    assert!(
-        result.is_ok(),
-        "changepacks no projects failed: {:?}",
-        result.err()
+    let err = unknown.unwrap_err().to_string();
+    assert_eq!(err, "--package names unknown projects: docs");
+    assert!(added.is_ok(), "add failed: {:?}", added.err());
+    let logs: Vec<_> = std::fs::read_dir(temp_path.join(".changepacks"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| std::fs::read_to_string(e.path()).unwrap())
+        .collect();
+    assert_eq!(logs.len(), 1);
+    let log: serde_json::Value = serde_json::from_str(&logs[0]).unwrap();
+    assert_eq!(
+        log["changes"],
+        serde_json::json!({"core/package.json": "Minor", "web/package.json": "Patch"})
     );
+    assert_eq!(log["note"], "Add X");
 }
 
+// Test changepacks written and applied as Markdown with `changepackFormat`
 #[tokio::test]
 #[serial]
-async fn test_cli_changepacks_empty_notes() {
+async fn test_cli_changepacks_markdown_format() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
-
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"changepackFormat": "markdown"}"#,
+    )
+    .await
+    .unwrap();
     tokio::fs::write(
         temp_path.join("package.json"),
         r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // With empty message, should print "Notes are empty" and succeed
-    let args = vec![
-        "changepacks".to_string(),
-        "--yes".to_string(),
-        "-m".to_string(),
-        "".to_string(),
-        "--update-type".to_string(),
-        "patch".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+    let to_args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let written = changepacks_cli::main(&to_args(&[
+        "changepacks",
+        "--yes",
+        "-m",
+        "Fix the parser",
+        "--update-type",
+        "patch",
+    ]))
+    .await;
+    let logs: Vec<_> = std::fs::read_dir(temp_path.join(".changepacks"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("changepack_log_")
+        })
+        .collect();
+    let content = logs
+        .first()
+        .map(|log| std::fs::read_to_string(log).unwrap())
+        .unwrap_or_default();
+    let updated = changepacks_cli::main(&to_args(&["changepacks", "update", "--yes"])).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
+    assert!(written.is_ok(), "changepacks failed: {:?}", written.err());
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].extension().unwrap(), "md");
     assert!(
-        result.is_ok(),
-        "changepacks empty notes failed: {:?}",
-        result.err()
+        content.starts_with("---\nchanges:\n  package.json: Patch\n"),
+        "{content}"
     );
+    assert!(content.ends_with("---\n\nFix the parser\n"), "{content}");
+
+    assert!(updated.is_ok(), "update failed: {:?}", updated.err());
+    assert!(!logs[0].exists());
+    let manifest = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    assert!(manifest.contains("1.0.1"));
 }
 
+// Test changepack notes read from --message-file
 #[tokio::test]
 #[serial]
-async fn test_cli_changepacks_with_filter() {
+async fn test_cli_changepacks_message_file() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
-
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create a pnpm workspace
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "test-workspace", "version": "1.0.0"}"#,
-    )
-    .await
-    .unwrap();
-    tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
+    let notes_path = temp_path.join("notes.md");
+    tokio::fs::write(&notes_path, "Add \"quoted\" feature\n\n- first\n- second\n")
+        .await
+        .unwrap();
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    let args = vec![
+    let result = changepacks_cli::main(&[
         "changepacks".to_string(),
         "--yes".to_string(),
-        "-m".to_string(),
-        "Test filter".to_string(),
+        "-F".to_string(),
+        notes_path.to_string_lossy().into_owned(),
         "--update-type".to_string(),
         "minor".to_string(),
-        "--filter".to_string(),
-        "workspace".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+    ])
+    .await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    assert!(
-        result.is_ok(),
-        "changepacks with filter failed: {:?}",
-        result.err()
-    );
+    assert!(result.is_ok(), "changepacks -F failed: {:?}", result.err());
+    let log_entry = std::fs::read_dir(temp_path.join(".changepacks"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .find(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with("changepack_log_")
+        })
+        .expect("No changepack log file was created");
+    let log: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(log_entry.path()).unwrap()).unwrap();
+    assert_eq!(log["note"], "Add \"quoted\" feature\n\n- first\n- second");
 }
 
-// Test init error when config already exists
+// Test template prompts answered via --field are stored in the log and release notes
 #[tokio::test]
 #[serial]
-async fn test_cli_init_already_initialized() {
+async fn test_cli_changepacks_template_fields() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
-
-    // Create .changepacks/config.json first
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
     tokio::fs::write(
         temp_path.join(".changepacks/config.json"),
-        r#"{"baseBranch": "main"}"#,
+        r#"{"template": {"prompts": [
+            {"key": "issue", "message": "Issue ID?", "required": true},
+            {"key": "breaking", "message": "Breaking change?"}
+        ]}}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    let args = vec!["changepacks".to_string(), "init".to_string()];
-    let result = changepacks_cli::main(&args).await;
+    let args = |extra: &[&str]| -> Vec<String> {
+        ["changepacks", "--yes", "-m", "Add feature", "-u", "minor"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect()
+    };
+    let missing = changepacks_cli::main(&args(&[])).await;
+    let result = changepacks_cli::main(&args(&["--field", "issue=ABC-123"])).await;
+    let update = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+    ])
+    .await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    // Should fail because already initialized
-    assert!(result.is_err());
+    assert!(
+        missing.is_err(),
+        "required template field should be enforced"
+    );
+    assert!(
+        result.is_ok(),
+        "changepacks --field failed: {:?}",
+        result.err()
+    );
+    assert!(update.is_ok(), "update failed: {:?}", update.err());
+    let releases: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(temp_path.join(".changepacks/releases.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        releases["releases"][0]["packages"][0]["notes"][0],
+        "Add feature\nissue: ABC-123"
+    );
 }
 
-// Test publish with language filter
+// Test --update-type prerelease --preid records the identifier and update writes prereleases
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_with_language_filter() {
+async fn test_cli_changepacks_prerelease() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
-
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Override dry-run with `echo` so the test does not depend on a working
-    // npm/registry environment (the real `npm publish --dry-run` fails under
-    // tarpaulin / sandboxed CI because the package name conflicts with the
-    // public registry).
-    tokio::fs::write(
-        temp_path.join(".changepacks/config.json"),
-        r#"{"publishDryRun": {"node": "echo dry-run", "rust": "echo dry-run"}}"#,
-    )
-    .await
-    .unwrap();
-
-    // Create Node.js package
     tokio::fs::write(
         temp_path.join("package.json"),
         r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
-
-    // Create Rust package (should be filtered out)
-    tokio::fs::write(
-        temp_path.join("Cargo.toml"),
-        r#"[package]
-name = "test-rust"
-version = "1.0.0"
-"#,
-    )
-    .await
-    .unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Only publish Node.js packages
-    let args = vec![
+    let changepack = |extra: &[&str]| -> Vec<String> {
+        ["changepacks", "--yes", "-m", "Try it", "-u", "prerelease"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect()
+    };
+    let update_args = ["changepacks", "update", "--yes"].map(String::from);
+    let misplaced = changepacks_cli::main(&[
         "changepacks".to_string(),
-        "publish".to_string(),
-        "--dry-run".to_string(),
-        "--language".to_string(),
-        "node".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Fix".to_string(),
+        "-u".to_string(),
+        "patch".to_string(),
+        "--preid".to_string(),
+        "beta".to_string(),
+    ])
+    .await;
+    let first = changepacks_cli::main(&changepack(&["--preid", "beta"])).await;
+    let first_update = changepacks_cli::main(&update_args).await;
+    let first_version = std::fs::read_to_string(temp_path.join("package.json")).unwrap();
+    let second = changepacks_cli::main(&changepack(&[])).await;
+    let second_update = changepacks_cli::main(&update_args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
+    let err = misplaced.unwrap_err().to_string();
     assert!(
-        result.is_ok(),
-        "publish with language filter failed: {:?}",
-        result.err()
+        err.contains("--preid only applies to --update-type prerelease"),
+        "{err}"
+    );
+    assert!(
+        first.is_ok(),
+        "changepacks --preid failed: {:?}",
+        first.err()
+    );
+    assert!(
+        first_update.is_ok(),
+        "update failed: {:?}",
+        first_update.err()
+    );
+    assert!(
+        first_version.contains(r#""version": "1.0.1-beta.0""#),
+        "{first_version}"
+    );
+    // Without --preid the pre-release keeps its identifier
+    assert!(second.is_ok(), "changepacks failed: {:?}", second.err());
+    assert!(
+        second_update.is_ok(),
+        "update failed: {:?}",
+        second_update.err()
+    );
+    let content = std::fs::read_to_string(temp_path.join("package.json")).unwrap();
+    assert!(
+        content.contains(r#""version": "1.0.1-beta.1""#),
+        "{content}"
     );
 }
 
-// Test publish with project filter
+// Test --yes without --update-type applies the configured per-package default
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_with_project_filter() {
+async fn test_cli_changepacks_default_update_type() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
-
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Override dry-run with `echo` so the test does not depend on a working
-    // npm/registry environment.
     tokio::fs::write(
         temp_path.join(".changepacks/config.json"),
-        r#"{"publishDryRun": {"node": "echo dry-run"}}"#,
+        r#"{"defaultUpdateType": {"package.json": "patch"}}"#,
     )
     .await
     .unwrap();
-
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "root-pkg", "version": "1.0.0"}"#,
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
 
-    tokio::fs::create_dir_all(temp_path.join("packages/core"))
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let result = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Fix typo".to_string(),
+    ])
+    .await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(result.is_ok(), "changepacks failed: {:?}", result.err());
+    let log_entry = std::fs::read_dir(temp_path.join(".changepacks"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .find(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with("changepack_log_")
+        })
+        .expect("No changepack log file was created");
+    let log: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(log_entry.path()).unwrap()).unwrap();
+    assert_eq!(log["changes"]["package.json"], "Patch");
+}
+
+// Test --all selects only changed projects without prompting
+#[tokio::test]
+#[serial]
+async fn test_cli_changepacks_all_changed() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-    tokio::fs::write(
-        temp_path.join("packages/core/package.json"),
-        r#"{"name": "core-pkg", "version": "1.0.0"}"#,
-    )
-    .await
-    .unwrap();
-
+    for name in ["a", "b"] {
+        tokio::fs::create_dir_all(temp_path.join(name))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(name).join("package.json"),
+            format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+        )
+        .await
+        .unwrap();
+        tokio::fs::write(temp_path.join(name).join("index.js"), "// initial")
+            .await
+            .unwrap();
+    }
     git_add_and_commit(&temp_path, "Initial commit");
+    tokio::fs::write(temp_path.join("a/index.js"), "// modified")
+        .await
+        .unwrap();
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Only publish specific project
-    let args = vec![
+    let result = changepacks_cli::main(&[
         "changepacks".to_string(),
-        "publish".to_string(),
-        "--dry-run".to_string(),
-        "--project".to_string(),
-        "package.json".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+        "--all".to_string(),
+        "-u".to_string(),
+        "minor".to_string(),
+        "-m".to_string(),
+        "Add feature".to_string(),
+    ])
+    .await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
     assert!(
         result.is_ok(),
-        "publish with project filter failed: {:?}",
+        "changepacks --all failed: {:?}",
         result.err()
     );
+    let log_entry = std::fs::read_dir(temp_path.join(".changepacks"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .find(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with("changepack_log_")
+        })
+        .expect("No changepack log file was created");
+    let log: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(log_entry.path()).unwrap()).unwrap();
+    let changes = log["changes"].as_object().unwrap();
+    assert_eq!(changes.len(), 1, "only the changed project: {changes:?}");
+    assert_eq!(changes["a/package.json"], "Minor");
 }
 
-// Test update with JSON format
+// Test check and changepack across several repositories listed in config
 #[tokio::test]
 #[serial]
-async fn test_cli_update_json_format() {
+async fn test_cli_multi_repo() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
+    let main_repo = temp_path.join("main");
+    let other_repo = temp_path.join("other");
 
-    init_git_repo(&temp_path);
-
-    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+    for (repo, name) in [(&main_repo, "main-pkg"), (&other_repo, "other-pkg")] {
+        tokio::fs::create_dir_all(repo).await.unwrap();
+        init_git_repo(repo);
+        tokio::fs::write(
+            repo.join("package.json"),
+            format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+        )
+        .await
+        .unwrap();
+    }
+    tokio::fs::create_dir_all(main_repo.join(".changepacks"))
         .await
         .unwrap();
     tokio::fs::write(
-        temp_path.join(".changepacks/changepack_log_test.json"),
-        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
-    )
-    .await
-    .unwrap();
-
-    tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        main_repo.join(".changepacks/config.json"),
+        r#"{"repositories": ["../other"]}"#,
     )
     .await
     .unwrap();
-
-    git_add_and_commit(&temp_path, "Initial commit");
+    git_add_and_commit(&main_repo, "Initial commit");
+    git_add_and_commit(&other_repo, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
-    std::env::set_current_dir(&temp_path).unwrap();
+    std::env::set_current_dir(&main_repo).unwrap();
 
-    let args = vec![
+    let check = changepacks_cli::main(&[
         "changepacks".to_string(),
-        "update".to_string(),
-        "--dry-run".to_string(),
+        "check".to_string(),
         "--format".to_string(),
         "json".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+    ])
+    .await;
+    let changepack = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Cross-repo change".to_string(),
+        "--update-type".to_string(),
+        "minor".to_string(),
+    ])
+    .await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
+    assert!(check.is_ok(), "multi-repo check failed: {:?}", check.err());
     assert!(
-        result.is_ok(),
-        "update JSON format failed: {:?}",
-        result.err()
+        changepack.is_ok(),
+        "multi-repo changepack failed: {:?}",
+        changepack.err()
     );
+
+    for repo in [&main_repo, &other_repo] {
+        let logs: Vec<_> = std::fs::read_dir(repo.join(".changepacks"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with("changepack_log_")
+            })
+            .collect();
+        assert_eq!(logs.len(), 1, "expected one changepack log in {repo:?}");
+        let log: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(logs[0].path()).unwrap()).unwrap();
+        assert_eq!(log["changes"]["package.json"], "Minor");
+        assert_eq!(log["note"], "Cross-repo change");
+    }
 }
 
-// Test update with no updates found
 #[tokio::test]
 #[serial]
-async fn test_cli_update_no_updates() {
+async fn test_cli_changepacks_no_projects() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1076,38 +1560,39 @@ async fn test_cli_update_no_updates() {
         .await
         .unwrap();
 
-    tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
-    )
-    .await
-    .unwrap();
+    tokio::fs::write(temp_path.join("README.md"), "# Test")
+        .await
+        .unwrap();
 
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
+    // With --yes and no projects, it should print "No projects selected"
     let args = vec![
         "changepacks".to_string(),
-        "update".to_string(),
         "--yes".to_string(),
+        "-m".to_string(),
+        "Test message".to_string(),
+        "--update-type".to_string(),
+        "patch".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
+    // Should succeed but not create any log (no projects)
     assert!(
         result.is_ok(),
-        "update no updates failed: {:?}",
+        "changepacks no projects failed: {:?}",
         result.err()
     );
 }
 
-// Test update with JSON format and no updates
 #[tokio::test]
 #[serial]
-async fn test_cli_update_json_no_updates() {
+async fn test_cli_changepacks_empty_notes() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1119,7 +1604,7 @@ async fn test_cli_update_json_no_updates() {
 
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
@@ -1129,12 +1614,14 @@ async fn test_cli_update_json_no_updates() {
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
+    // With empty message, should print "Notes are empty" and succeed
     let args = vec![
         "changepacks".to_string(),
-        "update".to_string(),
-        "--format".to_string(),
-        "json".to_string(),
         "--yes".to_string(),
+        "-m".to_string(),
+        "".to_string(),
+        "--update-type".to_string(),
+        "patch".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -1142,15 +1629,14 @@ async fn test_cli_update_json_no_updates() {
 
     assert!(
         result.is_ok(),
-        "update JSON no updates failed: {:?}",
+        "changepacks empty notes failed: {:?}",
         result.err()
     );
 }
 
-// Test check with changed files (hit line 72 in check.rs)
 #[tokio::test]
 #[serial]
-async fn test_cli_check_with_changed_files() {
+async fn test_cli_changepacks_with_filter() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1160,86 +1646,117 @@ async fn test_cli_check_with_changed_files() {
         .await
         .unwrap();
 
+    // Create a pnpm workspace
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+        r#"{"name": "test-workspace", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
     )
     .await
     .unwrap();
-    tokio::fs::write(temp_path.join("index.js"), "console.log('hello');")
-        .await
-        .unwrap();
 
     git_add_and_commit(&temp_path, "Initial commit");
 
-    // Modify the file to make the project "changed"
-    tokio::fs::write(temp_path.join("index.js"), "console.log('modified');")
-        .await
-        .unwrap();
-
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    let args = vec!["changepacks".to_string(), "check".to_string()];
+    let args = vec![
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Test filter".to_string(),
+        "--update-type".to_string(),
+        "minor".to_string(),
+        "--filter".to_string(),
+        "workspace".to_string(),
+    ];
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
     assert!(
         result.is_ok(),
-        "check with changed files failed: {:?}",
+        "changepacks with filter failed: {:?}",
         result.err()
     );
 }
 
-// Test check tree with complex dependency graph
+// Test init error when config already exists
 #[tokio::test]
 #[serial]
-async fn test_cli_check_tree_complex_deps() {
+async fn test_cli_init_already_initialized() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
 
+    // Create .changepacks/config.json first
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create a complex dependency structure with workspace:* dependencies
-    // root -> pkg-a, pkg-b
-    // pkg-a -> pkg-c
-    // pkg-b -> pkg-c (diamond pattern)
-    tokio::fs::write(temp_path.join("package.json"), r#"{"name": "root", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*", "pkg-b": "workspace:*"}}"#).await.unwrap();
     tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
+        temp_path.join(".changepacks/config.json"),
+        r#"{"baseBranch": "main"}"#,
     )
     .await
     .unwrap();
 
-    for pkg in &["pkg-a", "pkg-b", "pkg-c"] {
-        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
-            .await
-            .unwrap();
-    }
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec!["changepacks".to_string(), "init".to_string()];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    // Should fail because already initialized
+    assert!(result.is_err());
+}
 
+// Test publish with language filter
+#[tokio::test]
+#[serial]
+async fn test_cli_publish_with_language_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Override dry-run with `echo` so the test does not depend on a working
+    // npm/registry environment (the real `npm publish --dry-run` fails under
+    // tarpaulin / sandboxed CI because the package name conflicts with the
+    // public registry).
     tokio::fs::write(
-        temp_path.join("packages/pkg-a/package.json"),
-        r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
+        temp_path.join(".changepacks/config.json"),
+        r#"{"publishDryRun": {"node": "echo dry-run", "rust": "echo dry-run"}}"#,
     )
     .await
     .unwrap();
 
+    // Create Node.js package
     tokio::fs::write(
-        temp_path.join("packages/pkg-b/package.json"),
-        r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
+    // Create Rust package (should be filtered out)
     tokio::fs::write(
-        temp_path.join("packages/pkg-c/package.json"),
-        r#"{"name": "pkg-c", "version": "1.0.0"}"#,
+        temp_path.join("Cargo.toml"),
+        r#"[package]
+name = "test-rust"
+version = "1.0.0"
+"#,
     )
     .await
     .unwrap();
@@ -1249,10 +1766,13 @@ async fn test_cli_check_tree_complex_deps() {
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
+    // Only publish Node.js packages
     let args = vec![
         "changepacks".to_string(),
-        "check".to_string(),
-        "--tree".to_string(),
+        "publish".to_string(),
+        "--dry-run".to_string(),
+        "--language".to_string(),
+        "node".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -1260,34 +1780,46 @@ async fn test_cli_check_tree_complex_deps() {
 
     assert!(
         result.is_ok(),
-        "check tree complex deps failed: {:?}",
+        "publish with language filter failed: {:?}",
         result.err()
     );
 }
 
-// Test actual publish execution (not dry-run) with echo command
+// Test publish with project filter
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_actual_execution() {
+async fn test_cli_publish_with_project_filter() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
 
-    // Create config with echo publish command
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
+
+    // Override dry-run with `echo` so the test does not depend on a working
+    // npm/registry environment.
     tokio::fs::write(
         temp_path.join(".changepacks/config.json"),
-        r#"{"publish": {"node": "echo publishing"}}"#,
+        r#"{"publishDryRun": {"node": "echo dry-run"}}"#,
     )
     .await
     .unwrap();
 
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        r#"{"name": "root-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::create_dir_all(temp_path.join("packages/core"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("packages/core/package.json"),
+        r#"{"name": "core-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
@@ -1297,12 +1829,13 @@ async fn test_cli_publish_actual_execution() {
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
+    // Only publish specific project
     let args = vec![
         "changepacks".to_string(),
         "publish".to_string(),
-        "--yes".to_string(),
-        "--format".to_string(),
-        "json".to_string(),
+        "--dry-run".to_string(),
+        "--project".to_string(),
+        "package.json".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -1310,15 +1843,15 @@ async fn test_cli_publish_actual_execution() {
 
     assert!(
         result.is_ok(),
-        "publish actual execution failed: {:?}",
+        "publish with project filter failed: {:?}",
         result.err()
     );
 }
 
-// Test actual update execution (not dry-run)
+// Test update with JSON format
 #[tokio::test]
 #[serial]
-async fn test_cli_update_actual_execution() {
+async fn test_cli_update_json_format() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1327,7 +1860,12 @@ async fn test_cli_update_actual_execution() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-    tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test update", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_test.json"),
+        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
 
     tokio::fs::write(
         temp_path.join("package.json"),
@@ -1344,7 +1882,7 @@ async fn test_cli_update_actual_execution() {
     let args = vec![
         "changepacks".to_string(),
         "update".to_string(),
-        "--yes".to_string(),
+        "--dry-run".to_string(),
         "--format".to_string(),
         "json".to_string(),
     ];
@@ -1354,30 +1892,15 @@ async fn test_cli_update_actual_execution() {
 
     assert!(
         result.is_ok(),
-        "update actual execution failed: {:?}",
+        "update JSON format failed: {:?}",
         result.err()
     );
-
-    // Verify version was updated
-    let content = tokio::fs::read_to_string(temp_path.join("package.json"))
-        .await
-        .unwrap();
-    assert!(
-        content.contains("1.0.1"),
-        "Version should be updated to 1.0.1"
-    );
-
-    // Verify changepack log was cleared
-    let log_exists = temp_path
-        .join(".changepacks/changepack_log_test.json")
-        .exists();
-    assert!(!log_exists, "Changepack log should be cleared after update");
 }
 
-// Test update with workspace dependencies
+// Test update with no updates found
 #[tokio::test]
 #[serial]
-async fn test_cli_update_with_workspace_deps() {
+async fn test_cli_update_no_updates() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1387,46 +1910,13 @@ async fn test_cli_update_with_workspace_deps() {
         .await
         .unwrap();
 
-    // Create pnpm workspace
-    tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
-    )
-    .await
-    .unwrap();
-
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "root", "version": "1.0.0"}"#,
-    )
-    .await
-    .unwrap();
-
-    // Create core package
-    tokio::fs::create_dir_all(temp_path.join("packages/core"))
-        .await
-        .unwrap();
-    tokio::fs::write(
-        temp_path.join("packages/core/package.json"),
-        r#"{"name": "core", "version": "1.0.0"}"#,
-    )
-    .await
-    .unwrap();
-
-    // Create cli package that depends on core via workspace:*
-    tokio::fs::create_dir_all(temp_path.join("packages/cli"))
-        .await
-        .unwrap();
-    tokio::fs::write(
-        temp_path.join("packages/cli/package.json"),
-        r#"{"name": "cli", "version": "1.0.0", "dependencies": {"core": "workspace:*"}}"#,
+        r#"{"name": "test", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
-    // Create changepack log for core only
-    tokio::fs::write(temp_path.join(".changepacks/changepack_log_core.json"), r#"{"changes": {"packages/core/package.json": "Minor"}, "note": "update core", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
@@ -1443,15 +1933,15 @@ async fn test_cli_update_with_workspace_deps() {
 
     assert!(
         result.is_ok(),
-        "update with workspace deps failed: {:?}",
+        "update no updates failed: {:?}",
         result.err()
     );
 }
 
-// Test check tree with pending updates and changed files
+// Test update with JSON format and no updates
 #[tokio::test]
 #[serial]
-async fn test_cli_check_tree_with_updates_and_changes() {
+async fn test_cli_update_json_no_updates() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1461,65 +1951,24 @@ async fn test_cli_check_tree_with_updates_and_changes() {
         .await
         .unwrap();
 
-    // Create changepack log for one package
-    tokio::fs::write(temp_path.join(".changepacks/changepack_log_update.json"), r#"{"changes": {"packages/pkg-a/package.json": "Minor"}, "note": "update pkg-a", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
-
-    // Create packages with workspace:* dependencies
-    // root -> pkg-a, pkg-b
-    // pkg-a -> pkg-c
-    // pkg-b -> pkg-c (diamond pattern)
-    tokio::fs::write(temp_path.join("package.json"), r#"{"name": "root", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*", "pkg-b": "workspace:*"}}"#).await.unwrap();
-    tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
-    )
-    .await
-    .unwrap();
-
-    for pkg in &["pkg-a", "pkg-b", "pkg-c"] {
-        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
-            .await
-            .unwrap();
-    }
-
-    tokio::fs::write(
-        temp_path.join("packages/pkg-a/package.json"),
-        r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
-    )
-    .await
-    .unwrap();
-    tokio::fs::write(temp_path.join("packages/pkg-a/index.js"), "// initial")
-        .await
-        .unwrap();
-
-    tokio::fs::write(
-        temp_path.join("packages/pkg-b/package.json"),
-        r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
-    )
-    .await
-    .unwrap();
-
     tokio::fs::write(
-        temp_path.join("packages/pkg-c/package.json"),
-        r#"{"name": "pkg-c", "version": "1.0.0"}"#,
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
     git_add_and_commit(&temp_path, "Initial commit");
 
-    // Modify pkg-a to make it "changed"
-    tokio::fs::write(temp_path.join("packages/pkg-a/index.js"), "// modified")
-        .await
-        .unwrap();
-
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
     let args = vec![
         "changepacks".to_string(),
-        "check".to_string(),
-        "--tree".to_string(),
+        "update".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+        "--yes".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -1527,15 +1976,15 @@ async fn test_cli_check_tree_with_updates_and_changes() {
 
     assert!(
         result.is_ok(),
-        "check tree with updates and changes failed: {:?}",
+        "update JSON no updates failed: {:?}",
         result.err()
     );
 }
 
-// Test check tree with orphaned project (no dependencies)
+// Test check with changed files (hit line 72 in check.rs)
 #[tokio::test]
 #[serial]
-async fn test_cli_check_tree_with_orphan() {
+async fn test_cli_check_with_changed_files() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1545,13 +1994,56 @@ async fn test_cli_check_tree_with_orphan() {
         .await
         .unwrap();
 
-    // Create packages - one with workspace:* deps, one orphaned
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "root", "version": "1.0.0", "dependencies": {"child": "workspace:*"}}"#,
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
+    tokio::fs::write(temp_path.join("index.js"), "console.log('hello');")
+        .await
+        .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    // Modify the file to make the project "changed"
+    tokio::fs::write(temp_path.join("index.js"), "console.log('modified');")
+        .await
+        .unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec!["changepacks".to_string(), "check".to_string()];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "check with changed files failed: {:?}",
+        result.err()
+    );
+}
+
+// Test check tree with complex dependency graph
+#[tokio::test]
+#[serial]
+async fn test_cli_check_tree_complex_deps() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Create a complex dependency structure with workspace:* dependencies
+    // root -> pkg-a, pkg-b
+    // pkg-a -> pkg-c
+    // pkg-b -> pkg-c (diamond pattern)
+    tokio::fs::write(temp_path.join("package.json"), r#"{"name": "root", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*", "pkg-b": "workspace:*"}}"#).await.unwrap();
     tokio::fs::write(
         temp_path.join("pnpm-workspace.yaml"),
         "packages:\n  - packages/*",
@@ -1559,23 +2051,29 @@ async fn test_cli_check_tree_with_orphan() {
     .await
     .unwrap();
 
-    tokio::fs::create_dir_all(temp_path.join("packages/child"))
-        .await
-        .unwrap();
+    for pkg in &["pkg-a", "pkg-b", "pkg-c"] {
+        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
+            .await
+            .unwrap();
+    }
+
     tokio::fs::write(
-        temp_path.join("packages/child/package.json"),
-        r#"{"name": "child", "version": "1.0.0"}"#,
+        temp_path.join("packages/pkg-a/package.json"),
+        r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
     )
     .await
     .unwrap();
 
-    // Create an orphaned package (not in any dependency chain)
-    tokio::fs::create_dir_all(temp_path.join("packages/orphan"))
-        .await
-        .unwrap();
     tokio::fs::write(
-        temp_path.join("packages/orphan/package.json"),
-        r#"{"name": "orphan", "version": "1.0.0"}"#,
+        temp_path.join("packages/pkg-b/package.json"),
+        r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("packages/pkg-c/package.json"),
+        r#"{"name": "pkg-c", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
@@ -1596,32 +2094,30 @@ async fn test_cli_check_tree_with_orphan() {
 
     assert!(
         result.is_ok(),
-        "check tree with orphan failed: {:?}",
+        "check tree complex deps failed: {:?}",
         result.err()
     );
 }
 
-// Test publish with failing command (to cover error path)
+// Test actual publish execution (not dry-run) with echo command
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_with_failing_command() {
+async fn test_cli_publish_actual_execution() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
 
-    // Create config with failing publish command
+    // Create config with echo publish command
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-    let fail_cmd = if cfg!(target_os = "windows") {
-        r#"{"publish": {"node": "cmd /c exit 1"}}"#
-    } else {
-        r#"{"publish": {"node": "exit 1"}}"#
-    };
-    tokio::fs::write(temp_path.join(".changepacks/config.json"), fail_cmd)
-        .await
-        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"publish": {"node": "echo publishing"}}"#,
+    )
+    .await
+    .unwrap();
 
     tokio::fs::write(
         temp_path.join("package.json"),
@@ -1646,58 +2142,101 @@ async fn test_cli_publish_with_failing_command() {
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    // Should return error since publish failed (exit code propagation)
     assert!(
-        result.is_err(),
-        "publish with failing command should return error for non-zero exit code"
+        result.is_ok(),
+        "publish actual execution failed: {:?}",
+        result.err()
     );
+
+    let log: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(temp_path.join(".changepacks/publish_log.json")).unwrap(),
+    )
+    .unwrap();
+    let record = &log["records"][0];
+    assert_eq!(record["path"], "package.json");
+    assert_eq!(record["version"], "1.0.0");
+    assert_eq!(record["registry"], "npm");
+    assert_eq!(record["success"], true);
+    assert_eq!(record["outputDigest"].as_str().unwrap().len(), 40);
 }
 
-// Test check tree with circular dependencies (covers check.rs lines 174-176 - orphan display)
-// When A depends on B and B depends on A, neither is a root, so both become orphans
+// Test publish --only-unpublished skips versions already in the publish log or on the registry
 #[tokio::test]
 #[serial]
-async fn test_cli_check_tree_circular_deps() {
+async fn test_cli_publish_only_unpublished() {
+    use std::io::{Read, Write};
+
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
 
+    // Stand-in for npm: d@3.0.0 went out without a publish record, b was never published
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let registry_url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let (status, body) = if buf[..n].starts_with(b"GET /d ") {
+                ("200 OK", r#"{"versions":{"3.0.0":{}}}"#)
+            } else {
+                ("404 Not Found", "")
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+    });
+
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create circular dependency: pkg-a -> pkg-b, pkg-b -> pkg-a
-    // Neither is a root (both are in has_dependencies), so both become orphans
-    tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
-    )
-    .await
-    .unwrap();
     tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "root", "version": "1.0.0"}"#,
+        temp_path.join(".changepacks/config.json"),
+        format!(
+            r#"{{"publish": {{"node": "echo publishing"}}, "registries": {{"npm": {{"url": "{registry_url}"}}}}}}"#
+        ),
     )
     .await
     .unwrap();
-
-    tokio::fs::create_dir_all(temp_path.join("packages/pkg-a"))
+    for (dir, version) in [
+        ("a", "1.1.0"),
+        ("b", "2.0.0"),
+        ("c", "0.1.0"),
+        ("d", "3.0.0"),
+    ] {
+        tokio::fs::create_dir_all(temp_path.join(dir))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(dir).join("package.json"),
+            format!(r#"{{"name": "{dir}", "version": "{version}"}}"#),
+        )
         .await
         .unwrap();
+    }
+    // a, b and d were released, c never was; a@1.1.0 already went out
     tokio::fs::write(
-        temp_path.join("packages/pkg-a/package.json"),
-        r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-b": "workspace:*"}}"#,
+        temp_path.join(".changepacks/releases.json"),
+        r#"{"releases": [{"date": "2025-01-01T00:00:00Z", "packages": [
+            {"name": "a", "path": "a/package.json", "previousVersion": "1.0.0", "version": "1.1.0", "updateType": "Minor", "notes": []},
+            {"name": "b", "path": "b/package.json", "previousVersion": "1.0.0", "version": "2.0.0", "updateType": "Major", "notes": []},
+            {"name": "d", "path": "d/package.json", "previousVersion": "2.0.0", "version": "3.0.0", "updateType": "Major", "notes": []}
+        ]}]}"#,
     )
     .await
     .unwrap();
-
-    tokio::fs::create_dir_all(temp_path.join("packages/pkg-b"))
-        .await
-        .unwrap();
     tokio::fs::write(
-        temp_path.join("packages/pkg-b/package.json"),
-        r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*"}}"#,
+        temp_path.join(".changepacks/publish_log.json"),
+        r#"{"records": [
+            {"name": "a", "path": "a/package.json", "version": "1.1.0", "registry": "npm", "success": true, "date": "2025-01-01T00:05:00Z", "outputDigest": ""},
+            {"name": "b", "path": "b/package.json", "version": "2.0.0", "registry": "npm", "success": false, "date": "2025-01-01T00:05:00Z", "outputDigest": ""}
+        ]}"#,
     )
     .await
     .unwrap();
@@ -1709,24 +2248,29 @@ async fn test_cli_check_tree_circular_deps() {
 
     let args = vec![
         "changepacks".to_string(),
-        "check".to_string(),
-        "--tree".to_string(),
+        "publish".to_string(),
+        "--only-unpublished".to_string(),
+        "--yes".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    assert!(
-        result.is_ok(),
-        "check tree circular deps failed: {:?}",
-        result.err()
-    );
+    assert!(result.is_ok(), "publish failed: {:?}", result.err());
+
+    let log: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(temp_path.join(".changepacks/publish_log.json")).unwrap(),
+    )
+    .unwrap();
+    let records = log["records"].as_array().unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[2]["path"], "b/package.json");
+    assert_eq!(records[2]["success"], true);
 }
 
-// Test publish with JSON format and no projects (covers publish.rs lines 83-84)
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_json_no_projects() {
+async fn test_cli_publish_skips_publish_disabled() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1735,10 +2279,23 @@ async fn test_cli_publish_json_no_projects() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    tokio::fs::write(temp_path.join("README.md"), "# Test")
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"publish": {"node": "echo publishing", "internal/package.json": false}}"#,
+    )
+    .await
+    .unwrap();
+    for dir in ["internal", "public"] {
+        tokio::fs::create_dir_all(temp_path.join(dir))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(dir).join("package.json"),
+            format!(r#"{{"name": "{dir}", "version": "1.0.0"}}"#),
+        )
         .await
         .unwrap();
+    }
 
     git_add_and_commit(&temp_path, "Initial commit");
 
@@ -1748,77 +2305,88 @@ async fn test_cli_publish_json_no_projects() {
     let args = vec![
         "changepacks".to_string(),
         "publish".to_string(),
-        "--format".to_string(),
-        "json".to_string(),
+        "--yes".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    assert!(
-        result.is_ok(),
-        "publish json no projects failed: {:?}",
-        result.err()
-    );
+    assert!(result.is_ok(), "publish failed: {:?}", result.err());
+
+    let log: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(temp_path.join(".changepacks/publish_log.json")).unwrap(),
+    )
+    .unwrap();
+    let records = log["records"].as_array().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["path"], "public/package.json");
 }
 
-// Test check tree with workspace (covers check.rs lines 296, 303, 305-311)
+// Test check and update in a plain directory without git
 #[tokio::test]
 #[serial]
-async fn test_cli_check_tree_with_workspace() {
+async fn test_cli_update_without_git() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
-    init_git_repo(&temp_path);
-
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create a pnpm workspace with workspace:* dependencies
-    tokio::fs::write(temp_path.join("package.json"), r#"{"name": "root-workspace", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*"}}"#).await.unwrap();
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Minor"}, "note": "tarball release", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
     tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
-
-    tokio::fs::create_dir_all(temp_path.join("packages/pkg-a"))
+    tokio::fs::create_dir_all(temp_path.join("node_modules/dep"))
         .await
         .unwrap();
     tokio::fs::write(
-        temp_path.join("packages/pkg-a/package.json"),
-        r#"{"name": "pkg-a", "version": "1.0.0"}"#,
+        temp_path.join("node_modules/dep/package.json"),
+        r#"{"name": "dep", "version": "9.9.9"}"#,
     )
     .await
     .unwrap();
-
-    git_add_and_commit(&temp_path, "Initial commit");
+    tokio::fs::write(temp_path.join(".gitignore"), "node_modules/\n")
+        .await
+        .unwrap();
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    let args = vec![
+    let check = changepacks_cli::main(&["changepacks".to_string(), "check".to_string()]).await;
+    let update = changepacks_cli::main(&[
         "changepacks".to_string(),
-        "check".to_string(),
-        "--tree".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+        "update".to_string(),
+        "--yes".to_string(),
+    ])
+    .await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
+    assert!(check.is_ok(), "check without git failed: {:?}", check.err());
     assert!(
-        result.is_ok(),
-        "check tree with workspace failed: {:?}",
-        result.err()
+        update.is_ok(),
+        "update without git failed: {:?}",
+        update.err()
+    );
+    let package_json = std::fs::read_to_string(temp_path.join("package.json")).unwrap();
+    assert!(package_json.contains("1.1.0"));
+    let dep_json =
+        std::fs::read_to_string(temp_path.join("node_modules/dep/package.json")).unwrap();
+    assert!(dep_json.contains("9.9.9"));
+    assert!(
+        !temp_path
+            .join(".changepacks/changepack_log_test.json")
+            .exists()
     );
 }
 
-// Test check tree with deeply nested dependencies (covers check.rs lines 216-250)
+// Test actual update execution (not dry-run)
 #[tokio::test]
 #[serial]
-async fn test_cli_check_tree_deeply_nested() {
+async fn test_cli_update_actual_execution() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1827,47 +2395,15 @@ async fn test_cli_check_tree_deeply_nested() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test update", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
 
-    // Create a deep dependency chain with workspace:* deps: root -> a -> b -> c -> d
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "root", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*"}}"#,
-    )
-    .await
-    .unwrap();
-    tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
+        r#"{"name": "test", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
-    for (pkg, deps) in &[
-        (
-            "pkg-a",
-            r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-b": "workspace:*"}}"#,
-        ),
-        (
-            "pkg-b",
-            r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
-        ),
-        (
-            "pkg-c",
-            r#"{"name": "pkg-c", "version": "1.0.0", "dependencies": {"pkg-d": "workspace:*"}}"#,
-        ),
-        ("pkg-d", r#"{"name": "pkg-d", "version": "1.0.0"}"#),
-    ] {
-        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
-            .await
-            .unwrap();
-        tokio::fs::write(
-            temp_path.join(format!("packages/{}/package.json", pkg)),
-            deps,
-        )
-        .await
-        .unwrap();
-    }
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
@@ -1875,8 +2411,10 @@ async fn test_cli_check_tree_deeply_nested() {
 
     let args = vec![
         "changepacks".to_string(),
-        "check".to_string(),
-        "--tree".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -1884,16 +2422,52 @@ async fn test_cli_check_tree_deeply_nested() {
 
     assert!(
         result.is_ok(),
-        "check tree deeply nested failed: {:?}",
+        "update actual execution failed: {:?}",
         result.err()
     );
+
+    // Verify version was updated
+    let content = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    assert!(
+        content.contains("1.0.1"),
+        "Version should be updated to 1.0.1"
+    );
+
+    // Verify changepack log was cleared
+    let log_exists = temp_path
+        .join(".changepacks/changepack_log_test.json")
+        .exists();
+    assert!(!log_exists, "Changepack log should be cleared after update");
+
+    // Verify the release was recorded in the manifest
+    let manifest: serde_json::Value = serde_json::from_str(
+        &tokio::fs::read_to_string(temp_path.join(".changepacks/releases.json"))
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let package = &manifest["releases"][0]["packages"][0];
+    assert_eq!(package["path"], "package.json");
+    assert_eq!(package["previousVersion"], "1.0.0");
+    assert_eq!(package["version"], "1.0.1");
+    assert_eq!(package["notes"][0], "test update");
+
+    // Verify the notes were added to the changelog under the new version
+    let changelog = tokio::fs::read_to_string(temp_path.join("CHANGELOG.md"))
+        .await
+        .unwrap();
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    assert_eq!(
+        changelog,
+        format!("# Changelog\n\n## 1.0.1 - {today}\n\n### Patch Changes\n\n- test update\n")
+    );
 }
 
-// Test check tree where a dependency is visited multiple times (covers check.rs lines 237-252)
-// This test specifically ensures that an already-visited dep that is NOT the last dep hits line 240 (├── branch)
 #[tokio::test]
 #[serial]
-async fn test_cli_check_tree_shared_dep_visited_twice() {
+async fn test_cli_update_hooks() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1902,73 +2476,63 @@ async fn test_cli_check_tree_shared_dep_visited_twice() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create packages where shared-dep is depended on by multiple packages
-    // root1 -> shared-dep (visits shared-dep first)
-    // root2 -> [shared-dep, z-pkg] (shared-dep is NOT last after sorting, hits line 240)
-    // Both root1 and root2 are root nodes
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Minor"}, "note": "test update", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
     tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "root1", "version": "1.0.0", "dependencies": {"shared-dep": "workspace:*"}}"#,
+        temp_path.join(".changepacks/config.json"),
+        r#"{"hooks": {"preUpdate": "exit 1"}}"#,
     )
     .await
     .unwrap();
     tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
-    for (pkg, deps) in &[
-        // root2 depends on both shared-dep and z-pkg. After sorting: [shared-dep, z-pkg]
-        // shared-dep is idx=0 (not last), so when already visited, hits line 240 (├──)
-        (
-            "root2",
-            r#"{"name": "root2", "version": "1.0.0", "dependencies": {"shared-dep": "workspace:*", "z-pkg": "workspace:*"}}"#,
-        ),
-        (
-            "shared-dep",
-            r#"{"name": "shared-dep", "version": "1.0.0"}"#,
-        ),
-        ("z-pkg", r#"{"name": "z-pkg", "version": "1.0.0"}"#),
-    ] {
-        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
-            .await
-            .unwrap();
-        tokio::fs::write(
-            temp_path.join(format!("packages/{}/package.json", pkg)),
-            deps,
-        )
-        .await
-        .unwrap();
-    }
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    let args = vec![
-        "changepacks".to_string(),
-        "check".to_string(),
-        "--tree".to_string(),
-    ];
+    let args = ["changepacks", "update", "--yes"].map(String::from);
+    let failed = changepacks_cli::main(&args).await;
+    let unchanged = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"hooks": {
+            "preUpdate": "echo \"pre $CHANGEPACKS_PACKAGE $CHANGEPACKS_VERSION $CHANGEPACKS_UPDATE_TYPE\" >> hooks.log",
+            "postUpdate": ["echo \"post $CHANGEPACKS_PATH $CHANGEPACKS_PREVIOUS_VERSION $CHANGEPACKS_VERSION\" >> hooks.log"]
+        }}"#,
+    )
+    .await
+    .unwrap();
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
     assert!(
-        result.is_ok(),
-        "check tree shared dep visited twice failed: {:?}",
-        result.err()
+        failed
+            .unwrap_err()
+            .to_string()
+            .starts_with("preUpdate hook `exit 1` failed")
+    );
+    assert!(unchanged.contains("1.0.0"));
+    assert!(result.is_ok(), "{result:?}");
+    assert_eq!(
+        tokio::fs::read_to_string(temp_path.join("hooks.log"))
+            .await
+            .unwrap(),
+        "pre test 1.0.0 minor\npost package.json 1.0.0 1.1.0\n"
     );
 }
 
-// Test changepacks with package filter (covers changepacks.rs line 41)
+// Test update with workspace dependencies
 #[tokio::test]
 #[serial]
-async fn test_cli_changepacks_with_package_filter() {
+async fn test_cli_update_with_workspace_deps() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -1978,45 +2542,55 @@ async fn test_cli_changepacks_with_package_filter() {
         .await
         .unwrap();
 
-    // Create a workspace and a package
+    // Create pnpm workspace
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
+    )
+    .await
+    .unwrap();
+
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "root-workspace", "version": "1.0.0"}"#,
+        r#"{"name": "root", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
+
+    // Create core package
+    tokio::fs::create_dir_all(temp_path.join("packages/core"))
+        .await
+        .unwrap();
     tokio::fs::write(
-        temp_path.join("pnpm-workspace.yaml"),
-        "packages:\n  - packages/*",
+        temp_path.join("packages/core/package.json"),
+        r#"{"name": "core", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
-    tokio::fs::create_dir_all(temp_path.join("packages/pkg"))
+    // Create cli package that depends on core via workspace:*
+    tokio::fs::create_dir_all(temp_path.join("packages/cli"))
         .await
         .unwrap();
     tokio::fs::write(
-        temp_path.join("packages/pkg/package.json"),
-        r#"{"name": "pkg", "version": "1.0.0"}"#,
+        temp_path.join("packages/cli/package.json"),
+        r#"{"name": "cli", "version": "1.0.0", "dependencies": {"core": "workspace:*"}}"#,
     )
     .await
     .unwrap();
 
+    // Create changepack log for core only
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_core.json"), r#"{"changes": {"packages/core/package.json": "Minor"}, "note": "update core", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Use --filter package to only select packages (not workspaces)
     let args = vec![
         "changepacks".to_string(),
+        "update".to_string(),
         "--yes".to_string(),
-        "-m".to_string(),
-        "Package only update".to_string(),
-        "--update-type".to_string(),
-        "patch".to_string(),
-        "--filter".to_string(),
-        "package".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -2024,15 +2598,15 @@ async fn test_cli_changepacks_with_package_filter() {
 
     assert!(
         result.is_ok(),
-        "changepacks with package filter failed: {:?}",
+        "update with workspace deps failed: {:?}",
         result.err()
     );
 }
 
-// Test publish dry-run with JSON format (covers publish.rs lines 102-103)
+// Test check tree with pending updates and changed files
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_dry_run_json() {
+async fn test_cli_check_tree_with_updates_and_changes() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2042,33 +2616,65 @@ async fn test_cli_publish_dry_run_json() {
         .await
         .unwrap();
 
-    // Override dry-run with `echo` so the test does not depend on a working
-    // npm/registry environment.
+    // Create changepack log for one package
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_update.json"), r#"{"changes": {"packages/pkg-a/package.json": "Minor"}, "note": "update pkg-a", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+
+    // Create packages with workspace:* dependencies
+    // root -> pkg-a, pkg-b
+    // pkg-a -> pkg-c
+    // pkg-b -> pkg-c (diamond pattern)
+    tokio::fs::write(temp_path.join("package.json"), r#"{"name": "root", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*", "pkg-b": "workspace:*"}}"#).await.unwrap();
     tokio::fs::write(
-        temp_path.join(".changepacks/config.json"),
-        r#"{"publishDryRun": {"node": "echo dry-run"}}"#,
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
+    )
+    .await
+    .unwrap();
+
+    for pkg in &["pkg-a", "pkg-b", "pkg-c"] {
+        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
+            .await
+            .unwrap();
+    }
+
+    tokio::fs::write(
+        temp_path.join("packages/pkg-a/package.json"),
+        r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
     )
     .await
     .unwrap();
+    tokio::fs::write(temp_path.join("packages/pkg-a/index.js"), "// initial")
+        .await
+        .unwrap();
 
     tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        temp_path.join("packages/pkg-b/package.json"),
+        r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("packages/pkg-c/package.json"),
+        r#"{"name": "pkg-c", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
     git_add_and_commit(&temp_path, "Initial commit");
 
+    // Modify pkg-a to make it "changed"
+    tokio::fs::write(temp_path.join("packages/pkg-a/index.js"), "// modified")
+        .await
+        .unwrap();
+
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
     let args = vec![
         "changepacks".to_string(),
-        "publish".to_string(),
-        "--dry-run".to_string(),
-        "--format".to_string(),
-        "json".to_string(),
+        "check".to_string(),
+        "--tree".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -2076,15 +2682,15 @@ async fn test_cli_publish_dry_run_json() {
 
     assert!(
         result.is_ok(),
-        "publish dry-run json failed: {:?}",
+        "check tree with updates and changes failed: {:?}",
         result.err()
     );
 }
 
-// Test update dry-run with JSON format (covers update.rs lines 102-103)
+// Test check tree with orphaned project (no dependencies)
 #[tokio::test]
 #[serial]
-async fn test_cli_update_dry_run_json() {
+async fn test_cli_check_tree_with_orphan() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2093,16 +2699,38 @@ async fn test_cli_update_dry_run_json() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
+
+    // Create packages - one with workspace:* deps, one orphaned
     tokio::fs::write(
-        temp_path.join(".changepacks/changepack_log_test.json"),
-        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
+        temp_path.join("package.json"),
+        r#"{"name": "root", "version": "1.0.0", "dependencies": {"child": "workspace:*"}}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
     )
     .await
     .unwrap();
 
+    tokio::fs::create_dir_all(temp_path.join("packages/child"))
+        .await
+        .unwrap();
     tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        temp_path.join("packages/child/package.json"),
+        r#"{"name": "child", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    // Create an orphaned package (not in any dependency chain)
+    tokio::fs::create_dir_all(temp_path.join("packages/orphan"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("packages/orphan/package.json"),
+        r#"{"name": "orphan", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
@@ -2114,10 +2742,8 @@ async fn test_cli_update_dry_run_json() {
 
     let args = vec![
         "changepacks".to_string(),
-        "update".to_string(),
-        "--dry-run".to_string(),
-        "--format".to_string(),
-        "json".to_string(),
+        "check".to_string(),
+        "--tree".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -2125,30 +2751,32 @@ async fn test_cli_update_dry_run_json() {
 
     assert!(
         result.is_ok(),
-        "update dry-run json failed: {:?}",
+        "check tree with orphan failed: {:?}",
         result.err()
     );
 }
 
-// Test publish stdout with actual execution (covers publish.rs lines 131-139)
+// Test publish with failing command (to cover error path)
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_stdout_execution() {
+async fn test_cli_publish_with_failing_command() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
 
-    // Create config with echo publish command
+    // Create config with failing publish command
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-    tokio::fs::write(
-        temp_path.join(".changepacks/config.json"),
-        r#"{"publish": {"node": "echo publishing stdout"}}"#,
-    )
-    .await
-    .unwrap();
+    let fail_cmd = if cfg!(target_os = "windows") {
+        r#"{"publish": {"node": "cmd /c exit 1"}}"#
+    } else {
+        r#"{"publish": {"node": "exit 1"}}"#
+    };
+    tokio::fs::write(temp_path.join(".changepacks/config.json"), fail_cmd)
+        .await
+        .unwrap();
 
     tokio::fs::write(
         temp_path.join("package.json"),
@@ -2166,22 +2794,25 @@ async fn test_cli_publish_stdout_execution() {
         "changepacks".to_string(),
         "publish".to_string(),
         "--yes".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
+    // Should return error since publish failed (exit code propagation)
     assert!(
-        result.is_ok(),
-        "publish stdout execution failed: {:?}",
-        result.err()
+        result.is_err(),
+        "publish with failing command should return error for non-zero exit code"
     );
 }
 
-// Test update dry-run with stdout format (covers update.rs lines 99-100)
+// Test check tree with circular dependencies (covers check.rs lines 174-176 - orphan display)
+// When A depends on B and B depends on A, neither is a root, so both become orphans
 #[tokio::test]
 #[serial]
-async fn test_cli_update_dry_run_stdout() {
+async fn test_cli_check_tree_circular_deps() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2190,16 +2821,38 @@ async fn test_cli_update_dry_run_stdout() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
+
+    // Create circular dependency: pkg-a -> pkg-b, pkg-b -> pkg-a
+    // Neither is a root (both are in has_dependencies), so both become orphans
     tokio::fs::write(
-        temp_path.join(".changepacks/changepack_log_test.json"),
-        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
     )
     .await
     .unwrap();
-
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        r#"{"name": "root", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::create_dir_all(temp_path.join("packages/pkg-a"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("packages/pkg-a/package.json"),
+        r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-b": "workspace:*"}}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::create_dir_all(temp_path.join("packages/pkg-b"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("packages/pkg-b/package.json"),
+        r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*"}}"#,
     )
     .await
     .unwrap();
@@ -2209,11 +2862,10 @@ async fn test_cli_update_dry_run_stdout() {
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Use default stdout format with dry-run (not JSON)
     let args = vec![
         "changepacks".to_string(),
-        "update".to_string(),
-        "--dry-run".to_string(),
+        "check".to_string(),
+        "--tree".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -2221,15 +2873,15 @@ async fn test_cli_update_dry_run_stdout() {
 
     assert!(
         result.is_ok(),
-        "update dry-run stdout failed: {:?}",
+        "check tree circular deps failed: {:?}",
         result.err()
     );
 }
 
-// Test update with workspace in update list (covers update.rs line 141)
+// Test publish with JSON format and no projects (covers publish.rs lines 83-84)
 #[tokio::test]
 #[serial]
-async fn test_cli_update_with_workspace_only() {
+async fn test_cli_publish_json_no_projects() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2239,23 +2891,64 @@ async fn test_cli_update_with_workspace_only() {
         .await
         .unwrap();
 
-    // Create a pnpm workspace
+    tokio::fs::write(temp_path.join("README.md"), "# Test")
+        .await
+        .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "publish".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "publish json no projects failed: {:?}",
+        result.err()
+    );
+}
+
+// Test check tree with workspace (covers check.rs lines 296, 303, 305-311)
+#[tokio::test]
+#[serial]
+async fn test_cli_check_tree_with_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Create a pnpm workspace with workspace:* dependencies
+    tokio::fs::write(temp_path.join("package.json"), r#"{"name": "root-workspace", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*"}}"#).await.unwrap();
     tokio::fs::write(
         temp_path.join("pnpm-workspace.yaml"),
         "packages:\n  - packages/*",
     )
     .await
     .unwrap();
+
+    tokio::fs::create_dir_all(temp_path.join("packages/pkg-a"))
+        .await
+        .unwrap();
     tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "root-workspace", "version": "1.0.0"}"#,
+        temp_path.join("packages/pkg-a/package.json"),
+        r#"{"name": "pkg-a", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
 
-    // Create changepack log for the workspace
-    tokio::fs::write(temp_path.join(".changepacks/changepack_log_ws.json"), r#"{"changes": {"package.json": "Minor"}, "note": "update workspace", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
@@ -2263,8 +2956,8 @@ async fn test_cli_update_with_workspace_only() {
 
     let args = vec![
         "changepacks".to_string(),
-        "update".to_string(),
-        "--yes".to_string(),
+        "check".to_string(),
+        "--tree".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -2272,15 +2965,15 @@ async fn test_cli_update_with_workspace_only() {
 
     assert!(
         result.is_ok(),
-        "update with workspace only failed: {:?}",
+        "check tree with workspace failed: {:?}",
         result.err()
     );
 }
 
-// Test changepacks without --update-type (covers changepacks.rs line 54)
+// Test check tree with deeply nested dependencies (covers check.rs lines 216-250)
 #[tokio::test]
 #[serial]
-async fn test_cli_changepacks_without_update_type() {
+async fn test_cli_check_tree_deeply_nested() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2290,24 +2983,55 @@ async fn test_cli_changepacks_without_update_type() {
         .await
         .unwrap();
 
+    // Create a deep dependency chain with workspace:* deps: root -> a -> b -> c -> d
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+        r#"{"name": "root", "version": "1.0.0", "dependencies": {"pkg-a": "workspace:*"}}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
     )
     .await
     .unwrap();
 
+    for (pkg, deps) in &[
+        (
+            "pkg-a",
+            r#"{"name": "pkg-a", "version": "1.0.0", "dependencies": {"pkg-b": "workspace:*"}}"#,
+        ),
+        (
+            "pkg-b",
+            r#"{"name": "pkg-b", "version": "1.0.0", "dependencies": {"pkg-c": "workspace:*"}}"#,
+        ),
+        (
+            "pkg-c",
+            r#"{"name": "pkg-c", "version": "1.0.0", "dependencies": {"pkg-d": "workspace:*"}}"#,
+        ),
+        ("pkg-d", r#"{"name": "pkg-d", "version": "1.0.0"}"#),
+    ] {
+        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(format!("packages/{}/package.json", pkg)),
+            deps,
+        )
+        .await
+        .unwrap();
+    }
+
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Run without --update-type, so it will iterate Major, Minor, Patch
     let args = vec![
         "changepacks".to_string(),
-        "--yes".to_string(),
-        "-m".to_string(),
-        "Test without update type".to_string(),
+        "check".to_string(),
+        "--tree".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
@@ -2315,467 +3039,2546 @@ async fn test_cli_changepacks_without_update_type() {
 
     assert!(
         result.is_ok(),
-        "changepacks without update type failed: {:?}",
+        "check tree deeply nested failed: {:?}",
         result.err()
     );
 }
 
-// Test publish stdout with failing command (covers publish.rs line 149)
+// Test check tree where a dependency is visited multiple times (covers check.rs lines 237-252)
+// This test specifically ensures that an already-visited dep that is NOT the last dep hits line 240 (├── branch)
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_stdout_failing() {
+async fn test_cli_check_tree_shared_dep_visited_twice() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
     init_git_repo(&temp_path);
 
-    // Create config with failing publish command
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-    let fail_cmd = if cfg!(target_os = "windows") {
-        r#"{"publish": {"node": "cmd /c exit 1"}}"#
-    } else {
-        r#"{"publish": {"node": "exit 1"}}"#
-    };
-    tokio::fs::write(temp_path.join(".changepacks/config.json"), fail_cmd)
-        .await
-        .unwrap();
 
+    // Create packages where shared-dep is depended on by multiple packages
+    // root1 -> shared-dep (visits shared-dep first)
+    // root2 -> [shared-dep, z-pkg] (shared-dep is NOT last after sorting, hits line 240)
+    // Both root1 and root2 are root nodes
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        r#"{"name": "root1", "version": "1.0.0", "dependencies": {"shared-dep": "workspace:*"}}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
     )
     .await
     .unwrap();
 
+    for (pkg, deps) in &[
+        // root2 depends on both shared-dep and z-pkg. After sorting: [shared-dep, z-pkg]
+        // shared-dep is idx=0 (not last), so when already visited, hits line 240 (├──)
+        (
+            "root2",
+            r#"{"name": "root2", "version": "1.0.0", "dependencies": {"shared-dep": "workspace:*", "z-pkg": "workspace:*"}}"#,
+        ),
+        (
+            "shared-dep",
+            r#"{"name": "shared-dep", "version": "1.0.0"}"#,
+        ),
+        ("z-pkg", r#"{"name": "z-pkg", "version": "1.0.0"}"#),
+    ] {
+        tokio::fs::create_dir_all(temp_path.join(format!("packages/{}", pkg)))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(format!("packages/{}/package.json", pkg)),
+            deps,
+        )
+        .await
+        .unwrap();
+    }
+
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Use stdout format (default) to hit the error eprintln! path
     let args = vec![
         "changepacks".to_string(),
-        "publish".to_string(),
-        "--yes".to_string(),
+        "check".to_string(),
+        "--tree".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    // Publishing fails so command should return error (non-zero exit code)
     assert!(
-        result.is_err(),
-        "publish stdout failing should return error for non-zero exit code"
+        result.is_ok(),
+        "check tree shared dep visited twice failed: {:?}",
+        result.err()
     );
 }
 
-// Tests for interactive code paths using MockPrompter
-mod interactive_tests {
-    use super::*;
-    use changepacks_cli::commands::{
-        ChangepackArgs, PublishArgs, UpdateArgs, handle_changepack_with_prompter,
-        handle_publish_with_prompter, handle_update_with_prompter,
-    };
-    use changepacks_cli::options::FormatOptions;
-    use changepacks_cli::prompter::MockPrompter;
-
-    // Test publish cancelled (covers publish.rs lines 116-124)
-    #[tokio::test]
-    #[serial]
-    async fn test_publish_cancelled_stdout() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
-
-        init_git_repo(&temp_path);
+// Test changepacks with package filter (covers changepacks.rs line 41)
+#[tokio::test]
+#[serial]
+async fn test_cli_changepacks_with_package_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
-            .await
-            .unwrap();
+    init_git_repo(&temp_path);
 
-        tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
-        )
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
 
-        git_add_and_commit(&temp_path, "Initial commit");
+    // Create a workspace and a package
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "root-workspace", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
+    )
+    .await
+    .unwrap();
 
-        let original_dir = std::env::current_dir().unwrap();
+    tokio::fs::create_dir_all(temp_path.join("packages/pkg"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("packages/pkg/package.json"),
+        r#"{"name": "pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    // Use --filter package to only select packages (not workspaces)
+    let args = vec![
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Package only update".to_string(),
+        "--update-type".to_string(),
+        "patch".to_string(),
+        "--filter".to_string(),
+        "package".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "changepacks with package filter failed: {:?}",
+        result.err()
+    );
+}
+
+// Test publish dry-run with JSON format (covers publish.rs lines 102-103)
+#[tokio::test]
+#[serial]
+async fn test_cli_publish_dry_run_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Override dry-run with `echo` so the test does not depend on a working
+    // npm/registry environment.
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"publishDryRun": {"node": "echo dry-run"}}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "publish".to_string(),
+        "--dry-run".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "publish dry-run json failed: {:?}",
+        result.err()
+    );
+}
+
+// Test update dry-run with JSON format (covers update.rs lines 102-103)
+#[tokio::test]
+#[serial]
+async fn test_cli_update_dry_run_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_test.json"),
+        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--dry-run".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "update dry-run json failed: {:?}",
+        result.err()
+    );
+}
+
+// Test publish stdout with actual execution (covers publish.rs lines 131-139)
+#[tokio::test]
+#[serial]
+async fn test_cli_publish_stdout_execution() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    // Create config with echo publish command
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"publish": {"node": "echo publishing stdout"}}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "publish".to_string(),
+        "--yes".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "publish stdout execution failed: {:?}",
+        result.err()
+    );
+}
+
+// Test update dry-run with stdout format (covers update.rs lines 99-100)
+#[tokio::test]
+#[serial]
+async fn test_cli_update_dry_run_stdout() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_test.json"),
+        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    // Use default stdout format with dry-run (not JSON)
+    let args = vec![
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--dry-run".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "update dry-run stdout failed: {:?}",
+        result.err()
+    );
+    // The diff preview runs the write pipeline in capture mode, so nothing touches disk
+    assert_eq!(
+        tokio::fs::read_to_string(temp_path.join("package.json"))
+            .await
+            .unwrap(),
+        r#"{"name": "test", "version": "1.0.0"}"#
+    );
+    assert!(
+        temp_path
+            .join(".changepacks/changepack_log_test.json")
+            .exists()
+    );
+}
+
+// Test update with workspace in update list (covers update.rs line 141)
+#[tokio::test]
+#[serial]
+async fn test_cli_update_with_workspace_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Create a pnpm workspace
+    tokio::fs::write(
+        temp_path.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "root-workspace", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    // Create changepack log for the workspace
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_ws.json"), r#"{"changes": {"package.json": "Minor"}, "note": "update workspace", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "update with workspace only failed: {:?}",
+        result.err()
+    );
+}
+
+// Test changepacks without --update-type (covers changepacks.rs line 54)
+#[tokio::test]
+#[serial]
+async fn test_cli_changepacks_without_update_type() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    // Run without --update-type, so it will iterate Major, Minor, Patch
+    let args = vec![
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Test without update type".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "changepacks without update type failed: {:?}",
+        result.err()
+    );
+}
+
+// Test publish stdout with failing command (covers publish.rs line 149)
+#[tokio::test]
+#[serial]
+async fn test_cli_publish_stdout_failing() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    // Create config with failing publish command
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    let fail_cmd = if cfg!(target_os = "windows") {
+        r#"{"publish": {"node": "cmd /c exit 1"}}"#
+    } else {
+        r#"{"publish": {"node": "exit 1"}}"#
+    };
+    tokio::fs::write(temp_path.join(".changepacks/config.json"), fail_cmd)
+        .await
+        .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    // Use stdout format (default) to hit the error eprintln! path
+    let args = vec![
+        "changepacks".to_string(),
+        "publish".to_string(),
+        "--yes".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    // Publishing fails so command should return error (non-zero exit code)
+    assert!(
+        result.is_err(),
+        "publish stdout failing should return error for non-zero exit code"
+    );
+}
+
+// Tests for interactive code paths using MockPrompter
+mod interactive_tests {
+    use super::*;
+    use changepacks_cli::GlobalOptions;
+    use changepacks_cli::commands::{
+        ChangepackArgs, PublishArgs, UpdateArgs, handle_changepack_with_prompter,
+        handle_publish_with_prompter, handle_update_with_prompter,
+    };
+    use changepacks_cli::options::FormatOptions;
+    use changepacks_cli::prompter::MockPrompter;
+
+    // Test publish cancelled (covers publish.rs lines 116-124)
+    #[tokio::test]
+    #[serial]
+    async fn test_publish_cancelled_stdout() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = PublishArgs {
+            dry_run: false,
+            yes: false, // Not auto-confirm, will use prompter
+            format: FormatOptions::Stdout,
+            remote: false,
+            language: vec![],
+            project: vec![],
+            only_unpublished: false,
+            skip_existing: false,
+            strict: false,
+            wait_for_registry: None,
+            allow_protected: false,
+            concurrency: std::num::NonZeroUsize::MIN,
+            retries: None,
+            retry_delay: None,
+        };
+
+        // MockPrompter with confirm_value = false (cancelled)
+        let prompter = MockPrompter {
+            confirm_value: false,
+            ..Default::default()
+        };
+
+        let result =
+            handle_publish_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "publish cancelled should succeed");
+    }
+
+    // Test publish cancelled with JSON format (covers publish.rs lines 120-122)
+    #[tokio::test]
+    #[serial]
+    async fn test_publish_cancelled_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = PublishArgs {
+            dry_run: false,
+            yes: false,
+            format: FormatOptions::Json,
+            remote: false,
+            language: vec![],
+            project: vec![],
+            only_unpublished: false,
+            skip_existing: false,
+            strict: false,
+            wait_for_registry: None,
+            allow_protected: false,
+            concurrency: std::num::NonZeroUsize::MIN,
+            retries: None,
+            retry_delay: None,
+        };
+
+        let prompter = MockPrompter {
+            confirm_value: false,
+            ..Default::default()
+        };
+
+        let result =
+            handle_publish_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "publish cancelled json should succeed");
+    }
+
+    // Test update cancelled (covers update.rs lines 115-123)
+    #[tokio::test]
+    #[serial]
+    async fn test_update_cancelled_stdout() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+        tokio::fs::write(
+            temp_path.join(".changepacks/config.json"),
+            r#"{"hooks": {"preUpdate": "echo pre >> hooks.log"}}"#,
+        )
+        .await
+        .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = UpdateArgs {
+            dry_run: false,
+            yes: false,
+            format: FormatOptions::Stdout,
+            remote: false,
+            language: vec![],
+            force: false,
+            strict: false,
+            registry: false,
+            allow_protected: false,
+            tag: false,
+            tag_prefix: None,
+        };
+
+        let prompter = MockPrompter {
+            confirm_value: false,
+            ..Default::default()
+        };
+
+        let result = handle_update_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "update cancelled should succeed");
+        // Staged writes are dropped when the user declines
+        let content = tokio::fs::read_to_string(temp_path.join("package.json"))
+            .await
+            .unwrap();
+        assert!(content.contains("1.0.0"));
+        assert!(
+            temp_path
+                .join(".changepacks/changepack_log_test.json")
+                .exists()
+        );
+        // Declining runs no preUpdate hooks
+        assert!(!temp_path.join("hooks.log").exists());
+    }
+
+    // Confirming applies the staged writes
+    #[tokio::test]
+    #[serial]
+    async fn test_update_confirmed_stdout() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+        tokio::fs::write(
+            temp_path.join(".changepacks/config.json"),
+            r#"{"hooks": {"preUpdate": "echo pre >> hooks.log"}}"#,
+        )
+        .await
+        .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = UpdateArgs {
+            dry_run: false,
+            yes: false,
+            format: FormatOptions::Stdout,
+            remote: false,
+            language: vec![],
+            force: false,
+            strict: false,
+            registry: false,
+            allow_protected: false,
+            tag: false,
+            tag_prefix: None,
+        };
+
+        let result =
+            handle_update_with_prompter(&args, &GlobalOptions::default(), &MockPrompter::default())
+                .await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "update failed: {:?}", result.err());
+        let content = tokio::fs::read_to_string(temp_path.join("package.json"))
+            .await
+            .unwrap();
+        assert!(content.contains("1.0.1"));
+        assert!(
+            !temp_path
+                .join(".changepacks/changepack_log_test.json")
+                .exists()
+        );
+        assert!(temp_path.join(".changepacks/releases.json").exists());
+        assert_eq!(
+            tokio::fs::read_to_string(temp_path.join("hooks.log"))
+                .await
+                .unwrap(),
+            "pre\n"
+        );
+    }
+
+    // Test update cancelled with JSON format (covers update.rs lines 119-121)
+    #[tokio::test]
+    #[serial]
+    async fn test_update_cancelled_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = UpdateArgs {
+            dry_run: false,
+            yes: false,
+            format: FormatOptions::Json,
+            remote: false,
+            language: vec![],
+            force: false,
+            strict: false,
+            registry: false,
+            allow_protected: false,
+            tag: false,
+            tag_prefix: None,
+        };
+
+        let prompter = MockPrompter {
+            confirm_value: false,
+            ..Default::default()
+        };
+
+        let result = handle_update_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "update cancelled json should succeed");
+    }
+
+    // Test changepacks with interactive selection (covers changepacks.rs lines 61-95)
+    #[tokio::test]
+    #[serial]
+    async fn test_changepacks_interactive_select() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = ChangepackArgs {
+            filter: None,
+            remote: false,
+            yes: false, // Use interactive mode
+            all: false,
+            since_base: false,
+            message: Some("test message".to_string()), // Provide message to skip text prompt
+            update_type: None,                         // Will iterate through Major, Minor, Patch
+            language: vec![],
+            fields: vec![],
+            preid: None,
+        };
+
+        let prompter = MockPrompter {
+            select_all: true,
+            confirm_value: true,
+            text_value: "test note".to_string(),
+        };
+
+        let result =
+            handle_changepack_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "changepacks interactive should succeed");
+    }
+
+    // Test changepacks with no selection (covers changepacks.rs empty selection path)
+    #[tokio::test]
+    #[serial]
+    async fn test_changepacks_no_selection() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = ChangepackArgs {
+            filter: None,
+            remote: false,
+            yes: false,
+            all: false,
+            since_base: false,
+            message: Some("test".to_string()),
+            update_type: None,
+            language: vec![],
+            fields: vec![],
+            preid: None,
+        };
+
+        let prompter = MockPrompter {
+            select_all: false, // Select nothing
+            confirm_value: true,
+            text_value: "test note".to_string(),
+        };
+
+        let result =
+            handle_changepack_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "changepacks no selection should succeed");
+    }
+
+    // Test changepacks with text prompt (covers changepacks.rs line 133)
+    #[tokio::test]
+    #[serial]
+    async fn test_changepacks_text_prompt() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let args = ChangepackArgs {
+            filter: None,
+            remote: false,
+            yes: true, // Auto-select all
+            all: false,
+            since_base: false,
+            message: None, // No message, will use text prompt
+            update_type: Some(changepacks_core::UpdateType::Patch),
+            language: vec![],
+            fields: vec![],
+            preid: None,
+        };
+
+        let prompter = MockPrompter {
+            select_all: true,
+            confirm_value: true,
+            text_value: "prompted note".to_string(),
+        };
+
+        let result =
+            handle_changepack_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(result.is_ok(), "changepacks text prompt should succeed");
+    }
+
+    // Test changepacks with changed project in interactive mode (covers changepacks.rs line 77)
+    // Line 77 is `Some(index)` when project.is_changed() returns true
+    #[tokio::test]
+    #[serial]
+    async fn test_changepacks_interactive_with_changed_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+
+        init_git_repo(&temp_path);
+
+        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+            .await
+            .unwrap();
+
+        tokio::fs::write(
+            temp_path.join("package.json"),
+            r#"{"name": "test", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+        tokio::fs::write(temp_path.join("index.js"), "// initial")
+            .await
+            .unwrap();
+
+        git_add_and_commit(&temp_path, "Initial commit");
+
+        // Modify a file to make the project "changed"
+        tokio::fs::write(temp_path.join("index.js"), "// modified")
+            .await
+            .unwrap();
+
+        let original_dir = std::env::current_dir().unwrap();
         std::env::set_current_dir(&temp_path).unwrap();
 
-        let args = PublishArgs {
-            dry_run: false,
-            yes: false, // Not auto-confirm, will use prompter
-            format: FormatOptions::Stdout,
-            remote: false,
-            language: vec![],
-            project: vec![],
-        };
+        // Use interactive mode with update_type: None (will iterate Major, Minor, Patch)
+        // The changed project should be detected and line 77 will be hit
+        let args = ChangepackArgs {
+            filter: None,
+            remote: false,
+            yes: false, // Interactive mode
+            all: false,
+            since_base: false,
+            message: Some("test message".to_string()),
+            update_type: None, // Will iterate through all update types
+            language: vec![],
+            fields: vec![],
+            preid: None,
+        };
+
+        let prompter = MockPrompter {
+            select_all: true,
+            confirm_value: true,
+            text_value: "test note".to_string(),
+        };
+
+        let result =
+            handle_changepack_with_prompter(&args, &GlobalOptions::default(), &prompter).await;
+
+        std::env::set_current_dir(&original_dir).unwrap();
+
+        assert!(
+            result.is_ok(),
+            "changepacks with changed project should succeed"
+        );
+    }
+}
+
+// --- Language filter integration tests ---
+
+#[tokio::test]
+#[serial]
+async fn test_cli_check_with_language_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Create Node.js package
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "node-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    // Create Rust package
+    tokio::fs::write(
+        temp_path.join("Cargo.toml"),
+        "[package]\nname = \"rust-pkg\"\nversion = \"1.0.0\"\n",
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    // Filter check to only Node.js
+    let args = vec![
+        "changepacks".to_string(),
+        "check".to_string(),
+        "--language".to_string(),
+        "node".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "check with language filter failed: {:?}",
+        result.err()
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_update_with_language_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Create changepack log targeting the Node package
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_lang.json"),
+        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+
+    // Create Node.js package
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "node-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    // Filter update to only Rust (should filter out the Node package update)
+    let args = vec![
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+        "--language".to_string(),
+        "rust".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "update with language filter failed: {:?}",
+        result.err()
+    );
+
+    // Verify version was NOT updated (filtered out by language)
+    let content = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    assert!(
+        content.contains("1.0.0"),
+        "Node package should not be updated when filtering by Rust"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_changepacks_with_language_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+
+    // Create Node.js package
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "node-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    // Create Rust package
+    tokio::fs::write(
+        temp_path.join("Cargo.toml"),
+        "[package]\nname = \"rust-pkg\"\nversion = \"1.0.0\"\n",
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    // Filter to only Node.js and create changepack
+    let args = vec![
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Test language filter".to_string(),
+        "--update-type".to_string(),
+        "patch".to_string(),
+        "--language".to_string(),
+        "node".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        result.is_ok(),
+        "changepacks with language filter failed: {:?}",
+        result.err()
+    );
+}
+
+// Test publish with stderr output in stdout format (covers publish.rs line 128 - stderr branch in print_publish_output)
+#[tokio::test]
+#[serial]
+async fn test_cli_publish_stdout_failing_with_stderr() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    let fail_cmd = if cfg!(target_os = "windows") {
+        r#"{"publish": {"node": "echo error_output 1>&2 & exit 1"}}"#
+    } else {
+        r#"{"publish": {"node": "echo error_output >&2; exit 1"}}"#
+    };
+    tokio::fs::write(temp_path.join(".changepacks/config.json"), fail_cmd)
+        .await
+        .unwrap();
+
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "publish".to_string(),
+        "--yes".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(result.is_err(), "publish with stderr should fail");
+}
+
+// Test history command reading the release manifest written by update
+#[tokio::test]
+#[serial]
+async fn test_cli_history_after_update() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_test.json"),
+        r#"{"changes": {"package.json": "Minor"}, "note": "add history", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let update = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+    ])
+    .await;
+    let history = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "history".to_string(),
+        "test".to_string(),
+    ])
+    .await;
+    let history_json = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "history".to_string(),
+        "package.json".to_string(),
+        "--version".to_string(),
+        "1.1.0".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ])
+    .await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(update.is_ok(), "update failed: {:?}", update.err());
+    assert!(history.is_ok(), "history failed: {:?}", history.err());
+    assert!(
+        history_json.is_ok(),
+        "history json failed: {:?}",
+        history_json.err()
+    );
+}
+
+// Test history command with no release manifest
+#[tokio::test]
+#[serial]
+async fn test_cli_history_no_releases() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let result = changepacks_cli::main(&["changepacks".to_string(), "history".to_string()]).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(result.is_ok(), "history failed: {:?}", result.err());
+}
+
+// Test audit runs over released and never-released packages
+#[tokio::test]
+#[serial]
+async fn test_cli_audit() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let before_release = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "audit".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ])
+    .await;
+    let changepack = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Fix".to_string(),
+        "-u".to_string(),
+        "patch".to_string(),
+    ])
+    .await;
+    let update = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+    ])
+    .await;
+    git_add_and_commit(&temp_path, "Release");
+    let after_release = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "audit".to_string(),
+        "--days".to_string(),
+        "0".to_string(),
+    ])
+    .await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        before_release.is_ok(),
+        "audit failed: {:?}",
+        before_release.err()
+    );
+    assert!(
+        changepack.is_ok(),
+        "changepack failed: {:?}",
+        changepack.err()
+    );
+    assert!(update.is_ok(), "update failed: {:?}", update.err());
+    assert!(
+        after_release.is_ok(),
+        "audit failed: {:?}",
+        after_release.err()
+    );
+}
+
+// Test why explains a package scheduled by a changepack and rejects unknown packages
+#[tokio::test]
+#[serial]
+async fn test_cli_why() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let changepack = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Fix".to_string(),
+        "-u".to_string(),
+        "patch".to_string(),
+    ])
+    .await;
+    let by_name = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "why".to_string(),
+        "test-pkg".to_string(),
+    ])
+    .await;
+    let by_path = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "why".to_string(),
+        "package.json".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ])
+    .await;
+    let missing = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "why".to_string(),
+        "missing".to_string(),
+    ])
+    .await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        changepack.is_ok(),
+        "changepack failed: {:?}",
+        changepack.err()
+    );
+    assert!(by_name.is_ok(), "why failed: {:?}", by_name.err());
+    assert!(
+        by_path.is_ok(),
+        "why --format json failed: {:?}",
+        by_path.err()
+    );
+    assert!(
+        missing
+            .unwrap_err()
+            .to_string()
+            .contains("package 'missing' not found")
+    );
+}
+
+// Test affected lists projects changed in a range and their dependents
+#[tokio::test]
+#[serial]
+async fn test_cli_affected() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    for (dir, deps) in [("core", "{}"), ("app", r#"{"core": "workspace:*"}"#)] {
+        tokio::fs::create_dir_all(temp_path.join(dir))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join(dir).join("package.json"),
+            format!(r#"{{"name": "{dir}", "version": "1.0.0", "dependencies": {deps}}}"#),
+        )
+        .await
+        .unwrap();
+    }
+    git_add_and_commit(&temp_path, "Initial commit");
+    tokio::fs::write(temp_path.join("core").join("index.js"), "export {}")
+        .await
+        .unwrap();
+    git_add_and_commit(&temp_path, "Change core");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let default_range =
+        changepacks_cli::main(&["changepacks".to_string(), "affected".to_string()]).await;
+    let range = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "affected".to_string(),
+        "HEAD~1..HEAD".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ])
+    .await;
+    let paths = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "affected".to_string(),
+        "HEAD~1..HEAD".to_string(),
+        "--print0".to_string(),
+    ])
+    .await;
+    let invalid = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "affected".to_string(),
+        "missing..HEAD".to_string(),
+    ])
+    .await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(
+        default_range.is_ok(),
+        "affected failed: {:?}",
+        default_range.err()
+    );
+    assert!(
+        range.is_ok(),
+        "affected with range failed: {:?}",
+        range.err()
+    );
+    assert!(paths.is_ok(), "affected --print0 failed: {:?}", paths.err());
+    assert!(invalid.is_err());
+}
+
+// Test CHANGEPACKS_DIR relocates config, changepack logs, and the release manifest
+#[tokio::test]
+#[serial]
+async fn test_cli_custom_changepacks_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+    // SAFETY: integration tests run serially, so no other thread reads the environment
+    unsafe { std::env::set_var("CHANGEPACKS_DIR", ".changes") };
+
+    let init = changepacks_cli::main(&["changepacks".to_string(), "init".to_string()]).await;
+    let changepack = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Fix".to_string(),
+        "-u".to_string(),
+        "patch".to_string(),
+    ])
+    .await;
+    let pending = std::fs::read_dir(temp_path.join(".changes"))
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("changepack_log_")
+        })
+        .count();
+    let update = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+    ])
+    .await;
+
+    unsafe { std::env::remove_var("CHANGEPACKS_DIR") };
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(init.is_ok(), "init failed: {:?}", init.err());
+    assert!(
+        changepack.is_ok(),
+        "changepack failed: {:?}",
+        changepack.err()
+    );
+    assert!(update.is_ok(), "update failed: {:?}", update.err());
+    assert_eq!(pending, 1);
+    assert!(temp_path.join(".changes/config.json").exists());
+    assert!(temp_path.join(".changes/releases.json").exists());
+    assert!(!temp_path.join(".changepacks").exists());
+    let package_json = std::fs::read_to_string(temp_path.join("package.json")).unwrap();
+    assert!(package_json.contains("1.0.1"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_nested_release_roots() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+    let web_path = temp_path.join("apps/web");
+
+    init_git_repo(&temp_path);
+    std::fs::create_dir_all(&web_path).unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "root-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        web_path.join("package.json"),
+        r#"{"name": "web", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let init = changepacks_cli::main(&["changepacks".to_string(), "init".to_string()]).await;
+    let init_web = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--root".to_string(),
+        "apps/web".to_string(),
+        "init".to_string(),
+    ])
+    .await;
+    // Only an initialized root can be selected, outside of init
+    let uninitialized = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--root".to_string(),
+        "apps".to_string(),
+        "check".to_string(),
+    ])
+    .await;
+
+    // Commands run inside apps/web operate on its own release root
+    std::env::set_current_dir(&web_path).unwrap();
+    let changepack = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "--yes".to_string(),
+        "-m".to_string(),
+        "Fix".to_string(),
+        "-u".to_string(),
+        "patch".to_string(),
+    ])
+    .await;
+    let update = changepacks_cli::main(&[
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+    ])
+    .await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(init.is_ok(), "init failed: {:?}", init.err());
+    assert!(init_web.is_ok(), "init --root failed: {:?}", init_web.err());
+    assert!(
+        uninitialized
+            .unwrap_err()
+            .to_string()
+            .contains("apps is not a changepacks root")
+    );
+    assert!(
+        changepack.is_ok(),
+        "changepack failed: {:?}",
+        changepack.err()
+    );
+    assert!(update.is_ok(), "update failed: {:?}", update.err());
+    assert!(web_path.join(".changepacks/config.json").exists());
+    assert!(web_path.join(".changepacks/releases.json").exists());
+    assert!(!temp_path.join(".changepacks/releases.json").exists());
+    let web_json = std::fs::read_to_string(web_path.join("package.json")).unwrap();
+    assert!(web_json.contains("1.0.1"));
+    let root_json = std::fs::read_to_string(temp_path.join("package.json")).unwrap();
+    assert!(root_json.contains("1.0.0"));
+}
+
+// Test graduating a prerelease workspace member to stable
+#[tokio::test]
+#[serial]
+async fn test_cli_graduate() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/releases.json"),
+        r#"{"releases": [{"date": "2025-01-01T00:00:00Z", "packages": [{"name": "core", "path": "packages/core/package.json", "previousVersion": "1.1.0", "version": "1.2.0-rc.0", "updateType": "Minor", "notes": ["New API"]}]}]}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"hooks": {
+            "preUpdate": "echo \"pre $CHANGEPACKS_PACKAGE $CHANGEPACKS_VERSION\" >> ../../hooks.log",
+            "postUpdate": "echo \"post $CHANGEPACKS_PREVIOUS_VERSION $CHANGEPACKS_VERSION\" >> ../../hooks.log"
+        }}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "root", "private": true, "workspaces": ["packages/*"], "dependencies": {"core": "^1.2.0-rc.0"}}"#,
+    )
+    .await
+    .unwrap();
+    for (name, version) in [("core", "1.2.0-rc.0"), ("app", "2.0.0")] {
+        tokio::fs::create_dir_all(temp_path.join("packages").join(name))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            temp_path.join("packages").join(name).join("package.json"),
+            format!(r#"{{"name": "{name}", "version": "{version}"}}"#),
+        )
+        .await
+        .unwrap();
+    }
+
+    git_add_and_commit(&temp_path, "Initial commit");
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "graduate".to_string(),
+        "--yes".to_string(),
+    ];
+    let result = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(result.is_ok(), "graduate failed: {:?}", result.err());
+
+    let core = tokio::fs::read_to_string(temp_path.join("packages/core/package.json"))
+        .await
+        .unwrap();
+    assert!(core.contains(r#""version": "1.2.0""#), "{core}");
+    let app = tokio::fs::read_to_string(temp_path.join("packages/app/package.json"))
+        .await
+        .unwrap();
+    assert!(app.contains(r#""version": "2.0.0""#), "{app}");
+    let root = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    assert!(root.contains(r#""core": "^1.2.0""#), "{root}");
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &tokio::fs::read_to_string(temp_path.join(".changepacks/releases.json"))
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let package = &manifest["releases"][1]["packages"][0];
+    assert_eq!(package["previousVersion"], "1.2.0-rc.0");
+    assert_eq!(package["version"], "1.2.0");
+    assert_eq!(package["updateType"], "Minor");
+    assert_eq!(package["notes"][0], "New API");
+
+    // The stable release gets a changelog entry with the notes of its prereleases
+    let changelog = tokio::fs::read_to_string(temp_path.join("packages/core/CHANGELOG.md"))
+        .await
+        .unwrap();
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    assert_eq!(
+        changelog,
+        format!("# Changelog\n\n## 1.2.0 - {today}\n\n### Minor Changes\n\n- New API\n")
+    );
+    assert!(!temp_path.join("packages/app/CHANGELOG.md").exists());
+    assert_eq!(
+        tokio::fs::read_to_string(temp_path.join("hooks.log"))
+            .await
+            .unwrap(),
+        "pre core 1.2.0-rc.0\npost 1.2.0-rc.0 1.2.0\n"
+    );
+    assert!(!temp_path.join(".changepacks/run.lock").exists());
+}
+
+// Test that update refuses to re-release a version that already went out
+#[tokio::test]
+#[serial]
+async fn test_cli_update_refuses_non_monotonic_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "stale fix", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+
+    git_add_and_commit(&temp_path, "Initial commit");
+    std::process::Command::new("git")
+        .args(["tag", "v1.0.1"])
+        .current_dir(&temp_path)
+        .output()
+        .unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args: Vec<String> = ["changepacks", "update", "--yes"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let refused = changepacks_cli::main(&args).await;
+    let content_after_refusal = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
 
-        // MockPrompter with confirm_value = false (cancelled)
-        let prompter = MockPrompter {
-            confirm_value: false,
-            ..Default::default()
-        };
+    let mut forced_args = args.clone();
+    forced_args.push("--force".to_string());
+    let forced = changepacks_cli::main(&forced_args).await;
 
-        let result = handle_publish_with_prompter(&args, &prompter).await;
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    let err = refused.unwrap_err().to_string();
+    assert!(err.contains("--force"), "{err}");
+    assert!(
+        err.contains("1.0.0 -> 1.0.1 is not above 1.0.1 (tagged)"),
+        "{err}"
+    );
+    assert!(content_after_refusal.contains("1.0.0"));
 
-        assert!(result.is_ok(), "publish cancelled should succeed");
-    }
+    assert!(forced.is_ok(), "forced update failed: {:?}", forced.err());
+    let content = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    assert!(content.contains("1.0.1"));
+}
 
-    // Test publish cancelled with JSON format (covers publish.rs lines 120-122)
-    #[tokio::test]
-    #[serial]
-    async fn test_publish_cancelled_json() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
+#[tokio::test]
+#[serial]
+async fn test_cli_update_strict_requires_changepacks() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        init_git_repo(&temp_path);
+    init_git_repo(&temp_path);
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    for name in ["a", "b"] {
+        tokio::fs::create_dir_all(temp_path.join(name))
             .await
             .unwrap();
-
         tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
+            temp_path.join(format!("{name}/package.json")),
+            format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
         )
         .await
         .unwrap();
+        tokio::fs::write(temp_path.join(format!("{name}/index.js")), "")
+            .await
+            .unwrap();
+    }
+    git_add_and_commit(&temp_path, "Initial commit");
 
-        git_add_and_commit(&temp_path, "Initial commit");
-
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_path).unwrap();
+    for name in ["a", "b"] {
+        tokio::fs::write(temp_path.join(format!("{name}/index.js")), "export {};\n")
+            .await
+            .unwrap();
+    }
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_a.json"), r#"{"changes": {"a/package.json": "Patch"}, "note": "fix a", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
 
-        let args = PublishArgs {
-            dry_run: false,
-            yes: false,
-            format: FormatOptions::Json,
-            remote: false,
-            language: vec![],
-            project: vec![],
-        };
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        let prompter = MockPrompter {
-            confirm_value: false,
-            ..Default::default()
-        };
+    let args: Vec<String> = ["changepacks", "update", "--yes", "--strict"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let refused = changepacks_cli::main(&args).await;
 
-        let result = handle_publish_with_prompter(&args, &prompter).await;
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_b.json"), r#"{"changes": {"b/package.json": "Patch"}, "note": "fix b", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+    let covered = changepacks_cli::main(&args).await;
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        assert!(result.is_ok(), "publish cancelled json should succeed");
-    }
+    let err = refused.unwrap_err().to_string();
+    assert!(err.contains("b (b/package.json)"), "{err}");
+    assert!(!err.contains("a (a/package.json)"), "{err}");
 
-    // Test update cancelled (covers update.rs lines 115-123)
-    #[tokio::test]
-    #[serial]
-    async fn test_update_cancelled_stdout() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
+    assert!(covered.is_ok(), "strict update failed: {:?}", covered.err());
+    let content = tokio::fs::read_to_string(temp_path.join("b/package.json"))
+        .await
+        .unwrap();
+    assert!(content.contains("1.0.1"));
+}
 
-        init_git_repo(&temp_path);
+#[tokio::test]
+#[serial]
+async fn test_cli_status_fails_without_changepacks() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
-            .await
-            .unwrap();
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::create_dir_all(temp_path.join("a"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("a/package.json"),
+        r#"{"name": "a", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(temp_path.join("a/index.js"), "")
+        .await
+        .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
 
-        tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
-        )
+    let args: Vec<String> = ["changepacks", "status"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let unchanged = changepacks_cli::main(&args).await;
+    tokio::fs::write(temp_path.join("a/index.js"), "export {};\n")
         .await
         .unwrap();
+    let missing = changepacks_cli::main(&args).await;
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_a.json"), r#"{"changes": {"a/package.json": "Patch"}, "note": "fix a", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+    let covered = changepacks_cli::main(&args).await;
 
-        git_add_and_commit(&temp_path, "Initial commit");
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_path).unwrap();
+    assert!(unchanged.is_ok(), "status failed: {:?}", unchanged.err());
+    let err = missing.unwrap_err().to_string();
+    assert!(
+        err.contains("1 changed project without a changepack"),
+        "{err}"
+    );
+    assert!(covered.is_ok(), "status failed: {:?}", covered.err());
+}
 
-        let args = UpdateArgs {
-            dry_run: false,
-            yes: false,
-            format: FormatOptions::Stdout,
-            remote: false,
-            language: vec![],
-        };
+#[tokio::test]
+#[serial]
+async fn test_cli_release_pr_dry_run() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        let prompter = MockPrompter {
-            confirm_value: false,
-            ..Default::default()
-        };
+    init_git_repo(&temp_path);
 
-        let result = handle_update_with_prompter(&args, &prompter).await;
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "app", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        assert!(result.is_ok(), "update cancelled should succeed");
-    }
+    let args: Vec<String> = ["changepacks", "release-pr", "--dry-run"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let nothing_pending = changepacks_cli::main(&args).await;
 
-    // Test update cancelled with JSON format (covers update.rs lines 119-121)
-    #[tokio::test]
-    #[serial]
-    async fn test_update_cancelled_json() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_a.json"), r#"{"changes": {"package.json": "Minor"}, "note": "Add feature", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+    let preview = changepacks_cli::main(&args).await;
 
-        init_git_repo(&temp_path);
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
-            .await
-            .unwrap();
+    assert!(nothing_pending.is_ok(), "{:?}", nothing_pending.err());
+    assert!(
+        preview.is_ok(),
+        "release-pr dry run failed: {:?}",
+        preview.err()
+    );
+    let content = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    assert!(content.contains("1.0.0"));
+    assert!(
+        temp_path
+            .join(".changepacks/changepack_log_a.json")
+            .exists()
+    );
+}
 
-        tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
+#[tokio::test]
+#[serial]
+async fn test_cli_hook_install_and_uninstall() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
-        )
+    init_git_repo(&temp_path);
+    let hook = temp_path.join(".git/hooks/pre-push");
+    tokio::fs::create_dir_all(temp_path.join(".git/hooks"))
+        .await
+        .unwrap();
+    tokio::fs::write(&hook, "#!/bin/sh\nnpm test\n")
         .await
         .unwrap();
 
-        git_add_and_commit(&temp_path, "Initial commit");
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_path).unwrap();
+    let run = |args: &[&str]| {
+        let args: Vec<String> = std::iter::once("changepacks")
+            .chain(args.iter().copied())
+            .map(ToString::to_string)
+            .collect();
+        async move { changepacks_cli::main(&args).await }
+    };
+    let refused = run(&["hook", "install"]).await;
+    let forced = run(&["hook", "install", "--force"]).await;
+    let installed = tokio::fs::read_to_string(&hook).await.unwrap();
+    let removed = run(&["hook", "uninstall"]).await;
 
-        let args = UpdateArgs {
-            dry_run: false,
-            yes: false,
-            format: FormatOptions::Json,
-            remote: false,
-            language: vec![],
-        };
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        let prompter = MockPrompter {
-            confirm_value: false,
-            ..Default::default()
-        };
+    assert!(refused.unwrap_err().to_string().contains("--force"));
+    assert!(forced.is_ok(), "{:?}", forced.err());
+    assert!(installed.ends_with("changepacks check --strict\n"));
+    assert!(removed.is_ok(), "{:?}", removed.err());
+    assert!(!hook.exists());
+}
 
-        let result = handle_update_with_prompter(&args, &prompter).await;
+#[tokio::test]
+#[serial]
+async fn test_cli_doctor_archives_stale_changepacks() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_current.json"),
+        r#"{"changes": {"package.json": "Minor"}, "note": "current", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_renamed.json"),
+        r#"{"changes": {"old/package.json": "Patch"}, "note": "renamed", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
 
-        assert!(result.is_ok(), "update cancelled json should succeed");
-    }
+    git_add_and_commit(&temp_path, "Initial commit");
 
-    // Test changepacks with interactive selection (covers changepacks.rs lines 61-95)
-    #[tokio::test]
-    #[serial]
-    async fn test_changepacks_interactive_select() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args: Vec<String> = ["changepacks", "doctor", "--archive-stale"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let result = changepacks_cli::main(&args).await;
+    let current_exists = temp_path
+        .join(".changepacks/changepack_log_current.json")
+        .exists();
+    // The archive directory must survive `update` clearing the applied changepacks
+    let updated =
+        changepacks_cli::main(&["changepacks", "update", "--yes"].map(String::from)).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(result.is_ok(), "doctor failed: {:?}", result.err());
+    assert!(current_exists);
+    assert!(updated.is_ok(), "update failed: {:?}", updated.err());
+    assert!(
+        tokio::fs::read_to_string(temp_path.join("package.json"))
+            .await
+            .unwrap()
+            .contains("1.1.0")
+    );
+    assert!(
+        !temp_path
+            .join(".changepacks/changepack_log_current.json")
+            .exists()
+    );
+    assert!(
+        !temp_path
+            .join(".changepacks/changepack_log_renamed.json")
+            .exists()
+    );
+    assert!(
+        temp_path
+            .join(".changepacks/archive/changepack_log_renamed.json")
+            .exists()
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_update_tag_tags_release_commit() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_a.json"),
+        r#"{"changes": {"package.json": "Minor"}, "note": "feature", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
+    // Unrelated work in progress stays out of the release commit
+    tokio::fs::write(temp_path.join("notes.txt"), "draft")
+        .await
+        .unwrap();
 
-        init_git_repo(&temp_path);
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+    let result =
+        changepacks_cli::main(&["changepacks", "update", "--yes", "--tag"].map(String::from)).await;
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
-            .await
+    assert!(result.is_ok(), "update failed: {:?}", result.err());
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&temp_path)
+            .output()
             .unwrap();
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    };
+    assert_eq!(git(&["log", "-1", "--format=%s"]), "Release test-pkg@1.1.0");
+    assert_eq!(
+        git(&["rev-parse", "test-pkg@1.1.0^{commit}"]),
+        git(&["rev-parse", "HEAD"])
+    );
+    assert!(git(&["show", "HEAD:package.json"]).contains("1.1.0"));
+    assert_eq!(git(&["status", "--porcelain"]), "?? notes.txt");
+}
 
-        tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
-        )
+#[tokio::test]
+#[serial]
+async fn test_cli_update_tags_go_module_release_commit() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_a.json"),
+        r#"{"changes": {"api/go.mod": "Minor"}, "note": "feature", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::create_dir_all(temp_path.join("api"))
+        .await
+        .unwrap();
+    tokio::fs::write(temp_path.join("api/go.mod"), "module example.com/api\n")
         .await
         .unwrap();
+    tokio::fs::write(
+        temp_path.join("api/version.go"),
+        "package api\n\nconst Version = \"v1.2.0\"\n",
+    )
+    .await
+    .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&temp_path)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    };
+    git(&["tag", "api/v1.2.0"]);
 
-        git_add_and_commit(&temp_path, "Initial commit");
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+    let dry_run =
+        changepacks_cli::main(&["changepacks", "update", "--dry-run"].map(String::from)).await;
+    let tags_after_dry_run = git(&["tag", "--list"]);
+    let result = changepacks_cli::main(&["changepacks", "update", "--yes"].map(String::from)).await;
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_path).unwrap();
+    assert!(dry_run.is_ok(), "dry run failed: {:?}", dry_run.err());
+    assert_eq!(tags_after_dry_run, "api/v1.2.0");
+    assert!(result.is_ok(), "update failed: {:?}", result.err());
+    assert_eq!(git(&["log", "-1", "--format=%s"]), "Release api/v1.3.0");
+    assert_eq!(
+        git(&["rev-parse", "api/v1.3.0^{commit}"]),
+        git(&["rev-parse", "HEAD"])
+    );
+    assert!(git(&["show", "HEAD:api/version.go"]).contains("v1.3.0"));
+}
 
-        let args = ChangepackArgs {
-            filter: None,
-            remote: false,
-            yes: false,                                // Use interactive mode
-            message: Some("test message".to_string()), // Provide message to skip text prompt
-            update_type: None,                         // Will iterate through Major, Minor, Patch
-            language: vec![],
-        };
+#[tokio::test]
+#[serial]
+async fn test_cli_summary() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        let prompter = MockPrompter {
-            select_all: true,
-            confirm_value: true,
-            text_value: "test note".to_string(),
-        };
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_a.json"),
+        r#"{"changes": {"package.json": "Minor"}, "note": "feature", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
 
-        let result = handle_changepack_with_prompter(&args, &prompter).await;
+    git_add_and_commit(&temp_path, "Initial commit");
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        assert!(result.is_ok(), "changepacks interactive should succeed");
+    let mut results = Vec::new();
+    for format in ["stdout", "json"] {
+        let args: Vec<String> = ["changepacks", "summary", "--format", format]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        results.push(changepacks_cli::main(&args).await);
     }
 
-    // Test changepacks with no selection (covers changepacks.rs empty selection path)
-    #[tokio::test]
-    #[serial]
-    async fn test_changepacks_no_selection() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        init_git_repo(&temp_path);
+    for result in results {
+        assert!(result.is_ok(), "summary failed: {:?}", result.err());
+    }
+}
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
-            .await
-            .unwrap();
+#[tokio::test]
+#[serial]
+async fn test_cli_changelog_pending_and_released() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
-        )
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
+    tokio::fs::write(
+        temp_path.join(".changepacks/changepack_log_a.json"),
+        r#"{"changes": {"package.json": "Minor"}, "note": "feature", "date": "2025-01-01T00:00:00Z"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "test-pkg", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
 
-        git_add_and_commit(&temp_path, "Initial commit");
-
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_path).unwrap();
-
-        let args = ChangepackArgs {
-            filter: None,
-            remote: false,
-            yes: false,
-            message: Some("test".to_string()),
-            update_type: None,
-            language: vec![],
-        };
-
-        let prompter = MockPrompter {
-            select_all: false, // Select nothing
-            confirm_value: true,
-            text_value: "test note".to_string(),
-        };
+    git_add_and_commit(&temp_path, "Initial commit");
 
-        let result = handle_changepack_with_prompter(&args, &prompter).await;
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    let run = |args: &[&str]| {
+        let args: Vec<String> = std::iter::once("changepacks")
+            .chain(args.iter().copied())
+            .map(ToString::to_string)
+            .collect();
+        async move { changepacks_cli::main(&args).await }
+    };
+    let pending = run(&["changelog", "test-pkg"]).await;
+    let pending_json = run(&["changelog", "--format", "json"]).await;
+    let package_unchanged = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    let log_kept = temp_path
+        .join(".changepacks/changepack_log_a.json")
+        .exists();
+    let update = run(&["update", "--yes"]).await;
+    let released = run(&["changelog", "test-pkg", "--from", "1.0.0"]).await;
+    let invalid = run(&["changelog", "--to", "latest"]).await;
 
-        assert!(result.is_ok(), "changepacks no selection should succeed");
-    }
+    std::env::set_current_dir(&original_dir).unwrap();
 
-    // Test changepacks with text prompt (covers changepacks.rs line 133)
-    #[tokio::test]
-    #[serial]
-    async fn test_changepacks_text_prompt() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
+    assert!(pending.is_ok(), "changelog failed: {:?}", pending.err());
+    assert!(
+        pending_json.is_ok(),
+        "changelog json failed: {:?}",
+        pending_json.err()
+    );
+    // Rendering pending notes neither bumps versions nor consumes changepacks
+    assert!(package_unchanged.contains(r#""version": "1.0.0""#));
+    assert!(log_kept);
+    assert!(update.is_ok(), "update failed: {:?}", update.err());
+    assert!(
+        released.is_ok(),
+        "changelog --from failed: {:?}",
+        released.err()
+    );
+    assert!(invalid.is_err());
+}
 
-        init_git_repo(&temp_path);
+#[tokio::test]
+#[serial]
+async fn test_cli_check_github_format_writes_step_summary() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    for name in ["a", "b"] {
+        tokio::fs::create_dir_all(temp_path.join(name))
             .await
             .unwrap();
-
         tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
+            temp_path.join(format!("{name}/package.json")),
+            format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
         )
         .await
         .unwrap();
+        tokio::fs::write(temp_path.join(format!("{name}/index.js")), "")
+            .await
+            .unwrap();
+    }
+    git_add_and_commit(&temp_path, "Initial commit");
+    for name in ["a", "b"] {
+        tokio::fs::write(temp_path.join(format!("{name}/index.js")), "export {};\n")
+            .await
+            .unwrap();
+    }
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_a.json"), r#"{"changes": {"a/package.json": "Minor"}, "note": "feat a", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
 
-        git_add_and_commit(&temp_path, "Initial commit");
+    let summary_path = temp_path.join("step_summary.md");
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+    // SAFETY: tests touching the environment are serialized
+    unsafe { std::env::set_var("GITHUB_STEP_SUMMARY", &summary_path) };
 
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_path).unwrap();
+    let args: Vec<String> = ["changepacks", "check", "--format", "github", "--strict"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let result = changepacks_cli::main(&args).await;
 
-        let args = ChangepackArgs {
-            filter: None,
-            remote: false,
-            yes: true,     // Auto-select all
-            message: None, // No message, will use text prompt
-            update_type: Some(changepacks_core::UpdateType::Patch),
-            language: vec![],
-        };
+    unsafe { std::env::remove_var("GITHUB_STEP_SUMMARY") };
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        let prompter = MockPrompter {
-            select_all: true,
-            confirm_value: true,
-            text_value: "prompted note".to_string(),
-        };
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("b (b/package.json)"), "{err}");
+    let summary = tokio::fs::read_to_string(&summary_path).await.unwrap();
+    assert!(
+        summary.contains("| a | `a/package.json` | 1.0.0 -> 1.1.0 | minor | 1 |"),
+        "{summary}"
+    );
+    assert!(
+        summary.contains("| b | `b/package.json` | 1.0.0 | **missing changepack** | 0 |"),
+        "{summary}"
+    );
+}
 
-        let result = handle_changepack_with_prompter(&args, &prompter).await;
+#[tokio::test]
+#[serial]
+async fn test_cli_check_strict_honors_change_ignore() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::create_dir_all(temp_path.join("a"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("a/package.json"),
+        r#"{"name": "a", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(temp_path.join("a/README.md"), "# a\n")
+        .await
+        .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
 
-        assert!(result.is_ok(), "changepacks text prompt should succeed");
-    }
+    tokio::fs::write(temp_path.join("a/README.md"), "# a\n\nUsage\n")
+        .await
+        .unwrap();
 
-    // Test changepacks with changed project in interactive mode (covers changepacks.rs line 77)
-    // Line 77 is `Some(index)` when project.is_changed() returns true
-    #[tokio::test]
-    #[serial]
-    async fn test_changepacks_interactive_with_changed_project() {
-        let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path().canonicalize().unwrap();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        init_git_repo(&temp_path);
+    let args: Vec<String> = ["changepacks", "check", "--strict"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let refused = changepacks_cli::main(&args).await;
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"changeIgnore": ["**/*.md"]}"#,
+    )
+    .await
+    .unwrap();
+    let ignored = changepacks_cli::main(&args).await;
 
-        tokio::fs::create_dir_all(temp_path.join(".changepacks"))
-            .await
-            .unwrap();
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(refused.is_err());
+    assert!(
+        ignored.is_ok(),
+        "check --strict failed: {:?}",
+        ignored.err()
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_check_strict_honors_change_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
 
-        tokio::fs::write(
-            temp_path.join("package.json"),
-            r#"{"name": "test", "version": "1.0.0"}"#,
-        )
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-        tokio::fs::write(temp_path.join("index.js"), "// initial")
-            .await
-            .unwrap();
-
-        git_add_and_commit(&temp_path, "Initial commit");
-
-        // Modify a file to make the project "changed"
-        tokio::fs::write(temp_path.join("index.js"), "// modified")
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"changeFiles": {"a/package.json": ["src/**", "package.json"]}}"#,
+    )
+    .await
+    .unwrap();
+    for dir in ["a/src", "a/examples"] {
+        tokio::fs::create_dir_all(temp_path.join(dir))
             .await
             .unwrap();
+    }
+    tokio::fs::write(
+        temp_path.join("a/package.json"),
+        r#"{"name": "a", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(temp_path.join("a/src/index.js"), "")
+        .await
+        .unwrap();
+    tokio::fs::write(temp_path.join("a/examples/demo.js"), "")
+        .await
+        .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
 
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_path).unwrap();
-
-        // Use interactive mode with update_type: None (will iterate Major, Minor, Patch)
-        // The changed project should be detected and line 77 will be hit
-        let args = ChangepackArgs {
-            filter: None,
-            remote: false,
-            yes: false, // Interactive mode
-            message: Some("test message".to_string()),
-            update_type: None, // Will iterate through all update types
-            language: vec![],
-        };
+    tokio::fs::write(temp_path.join("a/examples/demo.js"), "demo();\n")
+        .await
+        .unwrap();
 
-        let prompter = MockPrompter {
-            select_all: true,
-            confirm_value: true,
-            text_value: "test note".to_string(),
-        };
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
 
-        let result = handle_changepack_with_prompter(&args, &prompter).await;
+    let args: Vec<String> = ["changepacks", "check", "--strict"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let example_only = changepacks_cli::main(&args).await;
+    tokio::fs::write(temp_path.join("a/src/index.js"), "export {};\n")
+        .await
+        .unwrap();
+    let source_changed = changepacks_cli::main(&args).await;
 
-        std::env::set_current_dir(&original_dir).unwrap();
+    std::env::set_current_dir(&original_dir).unwrap();
 
-        assert!(
-            result.is_ok(),
-            "changepacks with changed project should succeed"
-        );
-    }
+    assert!(
+        example_only.is_ok(),
+        "check --strict failed: {:?}",
+        example_only.err()
+    );
+    assert!(source_changed.is_err());
 }
 
-// --- Language filter integration tests ---
-
 #[tokio::test]
 #[serial]
-async fn test_cli_check_with_language_filter() {
+async fn test_cli_check_with_explicit_base_and_head() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2783,49 +5586,111 @@ async fn test_cli_check_with_language_filter() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create Node.js package
+    tokio::fs::create_dir_all(temp_path.join("a"))
+        .await
+        .unwrap();
     tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "node-pkg", "version": "1.0.0"}"#,
+        temp_path.join("a/package.json"),
+        r#"{"name": "a", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
+    tokio::fs::write(temp_path.join("a/index.js"), "")
+        .await
+        .unwrap();
+    git_add_and_commit(&temp_path, "Initial commit");
+    tokio::fs::write(temp_path.join("a/index.js"), "export {};\n")
+        .await
+        .unwrap();
+    git_add_and_commit(&temp_path, "Change a");
 
-    // Create Rust package
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let run = |extra: &[&str]| {
+        let args: Vec<String> = ["changepacks", "check", "--strict"]
+            .iter()
+            .chain(extra)
+            .map(ToString::to_string)
+            .collect();
+        async move { changepacks_cli::main(&args).await }
+    };
+    // HEAD is the base branch, so nothing changed by default
+    let default = run(&[]).await;
+    let explicit_base = run(&["--base", "HEAD~1"]).await;
+    // Uncommitted edits don't count once --head pins a commit
+    tokio::fs::write(temp_path.join("a/index.js"), "export default 1;\n")
+        .await
+        .unwrap();
+    let pinned_head = run(&["--base", "HEAD~1", "--head", "HEAD~1"]).await;
+    let missing = run(&["--base", "does-not-exist"]).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(default.is_ok(), "check failed: {:?}", default.err());
+    assert!(explicit_base.is_err());
+    assert!(pinned_head.is_ok(), "check failed: {:?}", pinned_head.err());
+    assert!(format!("{:#}", missing.unwrap_err()).contains("revision 'does-not-exist' not found"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_check_detached_head_without_local_base_branch() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::create_dir_all(temp_path.join("a"))
+        .await
+        .unwrap();
     tokio::fs::write(
-        temp_path.join("Cargo.toml"),
-        "[package]\nname = \"rust-pkg\"\nversion = \"1.0.0\"\n",
+        temp_path.join("a/package.json"),
+        r#"{"name": "a", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
+    // Mimic a CI checkout: detached HEAD, base branch only as a remote-tracking ref
+    for args in [
+        &["update-ref", "refs/remotes/origin/main", "HEAD"][..],
+        &["checkout", "-q", "--detach"],
+        &["branch", "-q", "-D", "main"],
+    ] {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&temp_path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+    }
+    tokio::fs::write(temp_path.join("a/index.js"), "")
+        .await
+        .unwrap();
+    git_add_and_commit(&temp_path, "Change a");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Filter check to only Node.js
     let args = vec![
         "changepacks".to_string(),
         "check".to_string(),
-        "--language".to_string(),
-        "node".to_string(),
+        "--strict".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    assert!(
-        result.is_ok(),
-        "check with language filter failed: {:?}",
-        result.err()
-    );
+    // a changed since origin/main without a changepack
+    assert!(result.is_err());
+    assert!(!format!("{:#}", result.unwrap_err()).contains("not found"));
 }
 
 #[tokio::test]
 #[serial]
-async fn test_cli_update_with_language_filter() {
+async fn test_cli_update_refuses_protected_branch() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2833,59 +5698,57 @@ async fn test_cli_update_with_language_filter() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create changepack log targeting the Node package
     tokio::fs::write(
-        temp_path.join(".changepacks/changepack_log_lang.json"),
-        r#"{"changes": {"package.json": "Patch"}, "note": "test", "date": "2025-01-01T00:00:00Z"}"#,
+        temp_path.join(".changepacks/config.json"),
+        r#"{"protectedBranches": ["main"]}"#,
     )
     .await
     .unwrap();
-
-    // Create Node.js package
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test update", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "node-pkg", "version": "1.0.0"}"#,
+        r#"{"name": "test", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
 
-    // Filter update to only Rust (should filter out the Node package update)
-    let args = vec![
-        "changepacks".to_string(),
-        "update".to_string(),
-        "--yes".to_string(),
-        "--language".to_string(),
-        "rust".to_string(),
-    ];
-    let result = changepacks_cli::main(&args).await;
+    let run = |extra: &[&str]| {
+        let args: Vec<String> = ["changepacks", "update", "--yes"]
+            .iter()
+            .chain(extra)
+            .map(ToString::to_string)
+            .collect();
+        async move { changepacks_cli::main(&args).await }
+    };
+    let refused = run(&[]).await;
+    let refused_version = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    let dry_run = run(&["--dry-run"]).await;
+    let allowed = run(&["--allow-protected"]).await;
 
     std::env::set_current_dir(&original_dir).unwrap();
 
     assert!(
-        result.is_ok(),
-        "update with language filter failed: {:?}",
-        result.err()
+        format!("{:#}", refused.unwrap_err())
+            .contains("Refusing to update on protected branch 'main'")
     );
-
-    // Verify version was NOT updated (filtered out by language)
+    assert!(refused_version.contains("1.0.0"));
+    assert!(dry_run.is_ok(), "dry run failed: {:?}", dry_run.err());
+    assert!(allowed.is_ok(), "update failed: {:?}", allowed.err());
     let content = tokio::fs::read_to_string(temp_path.join("package.json"))
         .await
         .unwrap();
-    assert!(
-        content.contains("1.0.0"),
-        "Node package should not be updated when filtering by Rust"
-    );
+    assert!(content.contains("1.0.1"));
 }
 
 #[tokio::test]
 #[serial]
-async fn test_cli_changepacks_with_language_filter() {
+async fn test_cli_update_waits_for_run_lock() {
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
@@ -2893,78 +5756,190 @@ async fn test_cli_changepacks_with_language_filter() {
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-
-    // Create Node.js package
+    tokio::fs::write(temp_path.join(".changepacks/changepack_log_test.json"), r#"{"changes": {"package.json": "Patch"}, "note": "test update", "date": "2025-01-01T00:00:00Z"}"#).await.unwrap();
     tokio::fs::write(
         temp_path.join("package.json"),
-        r#"{"name": "node-pkg", "version": "1.0.0"}"#,
+        r#"{"name": "test", "version": "1.0.0"}"#,
     )
     .await
     .unwrap();
-
-    // Create Rust package
+    git_add_and_commit(&temp_path, "Initial commit");
+    // A live run (this process) holds the lock
+    let lock = temp_path.join(".changepacks/run.lock");
     tokio::fs::write(
-        temp_path.join("Cargo.toml"),
-        "[package]\nname = \"rust-pkg\"\nversion = \"1.0.0\"\n",
+        &lock,
+        format!(
+            r#"{{"pid": {}, "command": "publish", "startedAt": "{}"}}"#,
+            std::process::id(),
+            chrono::Utc::now().to_rfc3339()
+        ),
     )
     .await
     .unwrap();
 
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&temp_path).unwrap();
+
+    let args = vec![
+        "changepacks".to_string(),
+        "update".to_string(),
+        "--yes".to_string(),
+    ];
+    let locked = changepacks_cli::main(&args).await;
+    tokio::fs::remove_file(&lock).await.unwrap();
+    let unlocked = changepacks_cli::main(&args).await;
+
+    std::env::set_current_dir(&original_dir).unwrap();
+
+    assert!(format!("{:#}", locked.unwrap_err()).contains("Another changepacks run holds"));
+    assert!(unlocked.is_ok(), "update failed: {:?}", unlocked.err());
+    assert!(!lock.exists());
+    let content = tokio::fs::read_to_string(temp_path.join("package.json"))
+        .await
+        .unwrap();
+    assert!(content.contains("1.0.1"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cli_from_pr_writes_changepack() {
+    use std::io::{Read, Write};
+
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path().canonicalize().unwrap();
+
+    init_git_repo(&temp_path);
+    tokio::fs::create_dir_all(temp_path.join(".changepacks"))
+        .await
+        .unwrap();
+    tokio::fs::create_dir_all(temp_path.join("packages/core"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        temp_path.join("packages/core/package.json"),
+        r#"{"name": "core", "version": "1.0.0"}"#,
+    )
+    .await
+    .unwrap();
     git_add_and_commit(&temp_path, "Initial commit");
 
+    // Stand-in for the GitHub API answering the pull request lookup
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let api_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 4096];
+        let n = stream.read(&mut buf).unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        let body = serde_json::json!({
+            "number": 12,
+            "title": "Add streaming parser",
+            "body": "Adds a parser.\n\n```changepacks\ncore: minor\n```\n",
+        })
+        .to_string();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        request
+    });
+
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(&temp_path).unwrap();
+    unsafe {
+        std::env::set_var("GITHUB_API_URL", &api_url);
+        std::env::set_var("GITHUB_TOKEN", "token");
+    }
 
-    // Filter to only Node.js and create changepack
     let args = vec![
         "changepacks".to_string(),
-        "--yes".to_string(),
-        "-m".to_string(),
-        "Test language filter".to_string(),
-        "--update-type".to_string(),
-        "patch".to_string(),
-        "--language".to_string(),
-        "node".to_string(),
+        "from-pr".to_string(),
+        "--pr".to_string(),
+        "12".to_string(),
+        "--repo".to_string(),
+        "org/repo".to_string(),
     ];
     let result = changepacks_cli::main(&args).await;
 
+    unsafe {
+        std::env::remove_var("GITHUB_API_URL");
+        std::env::remove_var("GITHUB_TOKEN");
+    }
     std::env::set_current_dir(&original_dir).unwrap();
 
+    assert!(result.is_ok(), "from-pr failed: {:?}", result.err());
     assert!(
-        result.is_ok(),
-        "changepacks with language filter failed: {:?}",
-        result.err()
+        server
+            .join()
+            .unwrap()
+            .starts_with("GET /repos/org/repo/pulls/12 ")
     );
+    let log: serde_json::Value = serde_json::from_str(
+        &tokio::fs::read_to_string(temp_path.join(".changepacks/changepack_log_pr-12.json"))
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(log["changes"]["packages/core/package.json"], "Minor");
+    assert_eq!(log["note"], "Add streaming parser");
 }
 
-// Test publish with stderr output in stdout format (covers publish.rs line 128 - stderr branch in print_publish_output)
 #[tokio::test]
 #[serial]
-async fn test_cli_publish_stdout_failing_with_stderr() {
+async fn test_cli_publish_bumps_homebrew_formula() {
+    use std::io::{Read, Write};
+
     let temp_dir = TempDir::new().unwrap();
     let temp_path = temp_dir.path().canonicalize().unwrap();
 
-    init_git_repo(&temp_path);
+    // Stand-in for the release download of the new archive
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 4096];
+        let n = stream.read(&mut buf).unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        let body = "archive-1.3.0";
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        request
+    });
 
+    init_git_repo(&temp_path);
     tokio::fs::create_dir_all(temp_path.join(".changepacks"))
         .await
         .unwrap();
-    let fail_cmd = if cfg!(target_os = "windows") {
-        r#"{"publish": {"node": "echo error_output 1>&2 & exit 1"}}"#
-    } else {
-        r#"{"publish": {"node": "echo error_output >&2; exit 1"}}"#
-    };
-    tokio::fs::write(temp_path.join(".changepacks/config.json"), fail_cmd)
+    tokio::fs::write(
+        temp_path.join(".changepacks/config.json"),
+        r#"{"publish": {"node": "echo published"}}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        temp_path.join("package.json"),
+        r#"{"name": "mytool", "version": "1.3.0"}"#,
+    )
+    .await
+    .unwrap();
+    tokio::fs::create_dir_all(temp_path.join("Formula"))
         .await
         .unwrap();
-
     tokio::fs::write(
-        temp_path.join("package.json"),
-        r#"{"name": "test", "version": "1.0.0"}"#,
+        temp_path.join("Formula/mytool.rb"),
+        format!(
+            "class Mytool < Formula\n  url \"{base_url}/v1.2.3/mytool-1.2.3.tar.gz\"\n  sha256 \"{}\"\nend\n",
+            "0".repeat(64)
+        ),
     )
     .await
     .unwrap();
-
     git_add_and_commit(&temp_path, "Initial commit");
 
     let original_dir = std::env::current_dir().unwrap();
@@ -2979,5 +5954,19 @@ async fn test_cli_publish_stdout_failing_with_stderr() {
 
     std::env::set_current_dir(&original_dir).unwrap();
 
-    assert!(result.is_err(), "publish with stderr should fail");
+    assert!(result.is_ok(), "publish failed: {:?}", result.err());
+    assert!(
+        server
+            .join()
+            .unwrap()
+            .starts_with("GET /v1.3.0/mytool-1.3.0.tar.gz ")
+    );
+    assert_eq!(
+        tokio::fs::read_to_string(temp_path.join("Formula/mytool.rb"))
+            .await
+            .unwrap(),
+        format!(
+            "class Mytool < Formula\n  url \"{base_url}/v1.3.0/mytool-1.3.0.tar.gz\"\n  sha256 \"d72ced606ef825bdc18741dfb9a92b77465054bc15084da56904adcef8f8ece4\"\nend\n"
+        )
+    );
 }
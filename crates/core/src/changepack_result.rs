@@ -1,8 +1,8 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::update_type::UpdateType;
+use crate::{package_metadata::PackageMetadata, update_type::UpdateType};
 
 /// Single changepack log entry for aggregated results.
 ///
@@ -13,12 +13,50 @@ pub struct ChangePackResultLog {
     r#type: UpdateType,
     /// User-provided changelog note
     note: String,
+    /// Answers to the configured template prompts, keyed by prompt key
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, String>,
 }
 
 impl ChangePackResultLog {
     #[must_use]
     pub const fn new(r#type: UpdateType, note: String) -> Self {
-        Self { r#type, note }
+        Self {
+            r#type,
+            note,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_fields(mut self, fields: BTreeMap<String, String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    #[must_use]
+    pub const fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    /// Note followed by one `key: value` line per template field, as shown in release notes
+    #[must_use]
+    pub fn rendered_note(&self) -> String {
+        self.fields
+            .iter()
+            .fold(self.note.clone(), |note, (key, value)| {
+                format!("{note}\n{key}: {value}")
+            })
+    }
+
+    #[must_use]
+    pub const fn update_type(&self) -> UpdateType {
+        self.r#type
+    }
+
+    #[must_use]
+    pub fn note(&self) -> &str {
+        &self.note
     }
 }
 
@@ -41,6 +79,35 @@ pub struct ChangePackResult {
     changed: bool,
     /// File path to the project manifest
     path: PathBuf,
+    /// Description, repository URL and license from the manifest
+    #[serde(flatten)]
+    metadata: PackageMetadata,
+    /// Resolved dependency edges, when the caller computed them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dependencies: Option<Vec<DependencyEdge>>,
+}
+
+/// Dependency of a project, resolved against the projects in the repository
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyEdge {
+    /// Dependency name as declared in the manifest
+    name: String,
+    /// Manifest path of the project providing it, if it lives in this repository
+    path: Option<PathBuf>,
+    /// Whether the dependency is another project of this repository
+    internal: bool,
+}
+
+impl DependencyEdge {
+    #[must_use]
+    pub fn new(name: String, path: Option<PathBuf>) -> Self {
+        Self {
+            internal: path.is_some(),
+            name,
+            path,
+        }
+    }
 }
 
 impl ChangePackResult {
@@ -60,8 +127,24 @@ impl ChangePackResult {
             name,
             changed,
             path,
+            metadata: PackageMetadata::EMPTY,
+            dependencies: None,
         }
     }
+
+    /// Attach the project's manifest metadata
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: PackageMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Attach the project's resolved dependency edges
+    #[must_use]
+    pub fn with_dependencies(mut self, dependencies: Vec<DependencyEdge>) -> Self {
+        self.dependencies = Some(dependencies);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -82,6 +165,30 @@ mod tests {
         assert!(debug_str.contains("Add new API endpoint"));
     }
 
+    #[test]
+    fn test_changepack_result_log_accessors() {
+        let log = ChangePackResultLog::new(UpdateType::Major, "Drop old API".to_string());
+        assert_eq!(log.update_type(), UpdateType::Major);
+        assert_eq!(log.note(), "Drop old API");
+    }
+
+    #[test]
+    fn test_changepack_result_log_rendered_note() {
+        let log = ChangePackResultLog::new(UpdateType::Major, "Drop old API".to_string());
+        assert_eq!(log.rendered_note(), "Drop old API");
+        assert!(serde_json::to_value(&log).unwrap().get("fields").is_none());
+
+        let log = log.with_fields(BTreeMap::from([
+            ("issue".to_string(), "ABC-123".to_string()),
+            ("breaking".to_string(), "Removed v1 routes".to_string()),
+        ]));
+        assert_eq!(
+            log.rendered_note(),
+            "Drop old API\nbreaking: Removed v1 routes\nissue: ABC-123"
+        );
+        assert_eq!(log.fields().len(), 2);
+    }
+
     #[test]
     fn test_changepack_result_log_serialize() {
         let log = ChangePackResultLog::new(UpdateType::Patch, "Fix serialization bug".to_string());
@@ -144,6 +251,62 @@ mod tests {
         assert!(json.get("next_version").is_none());
     }
 
+    #[test]
+    fn test_changepack_result_with_metadata() {
+        let result = ChangePackResult::new(
+            vec![],
+            Some("1.0.0".to_string()),
+            None,
+            Some("core".to_string()),
+            false,
+            PathBuf::from("crates/core/Cargo.toml"),
+        );
+        let json: Value = serde_json::to_value(&result).unwrap();
+        assert!(json.get("description").is_none());
+        assert!(json.get("license").is_none());
+
+        let result = result.with_metadata(PackageMetadata::new(
+            Some("Core types".to_string()),
+            None,
+            Some("MIT".to_string()),
+        ));
+        let json: Value = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["description"], "Core types");
+        assert_eq!(json["license"], "MIT");
+        assert!(json.get("repositoryUrl").is_none());
+    }
+
+    #[test]
+    fn test_changepack_result_with_dependencies() {
+        let result = ChangePackResult::new(
+            vec![],
+            Some("1.0.0".to_string()),
+            None,
+            Some("app".to_string()),
+            false,
+            PathBuf::from("app/package.json"),
+        );
+        assert!(
+            serde_json::to_value(&result)
+                .unwrap()
+                .get("dependencies")
+                .is_none()
+        );
+
+        let result = result.with_dependencies(vec![
+            DependencyEdge::new("core".to_string(), Some(PathBuf::from("core/package.json"))),
+            DependencyEdge::new("left-pad".to_string(), None),
+        ]);
+        let json: Value = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json["dependencies"],
+            serde_json::json!([
+                { "name": "core", "path": "core/package.json", "internal": true },
+                { "name": "left-pad", "path": null, "internal": false },
+            ])
+        );
+    }
+
     #[test]
     fn test_changepack_result_deserialize_roundtrip() {
         let logs = vec![
@@ -0,0 +1,67 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Default directory (relative to the project root) holding config, changepack logs, and manifests
+pub const DEFAULT_CHANGEPACKS_DIR: &str = ".changepacks";
+
+/// Environment variable overriding the changepacks directory, e.g. `.changes`
+///
+/// Relative values are resolved against the project root.
+pub const CHANGEPACKS_DIR_ENV: &str = "CHANGEPACKS_DIR";
+
+/// Directory holding the config file: `CHANGEPACKS_DIR`, or `.changepacks` when unset or empty
+#[must_use]
+pub fn changepacks_dir_name() -> PathBuf {
+    dir_name_from(std::env::var_os(CHANGEPACKS_DIR_ENV), None)
+}
+
+/// Directory holding changepack logs and release records, given the config
+/// `changepacksDir` setting
+///
+/// `CHANGEPACKS_DIR` takes precedence over the setting, which takes precedence over
+/// `.changepacks`. Both are relative to the project root.
+#[must_use]
+pub fn resolve_changepacks_dir_name(configured: Option<&str>) -> PathBuf {
+    dir_name_from(std::env::var_os(CHANGEPACKS_DIR_ENV), configured)
+}
+
+fn dir_name_from(env: Option<OsString>, configured: Option<&str>) -> PathBuf {
+    env.filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            configured
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        })
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CHANGEPACKS_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir_name_from() {
+        assert_eq!(dir_name_from(None, None), PathBuf::from(".changepacks"));
+        assert_eq!(
+            dir_name_from(Some(OsString::new()), Some("")),
+            PathBuf::from(".changepacks")
+        );
+        assert_eq!(
+            dir_name_from(Some(OsString::from(".changes")), None),
+            PathBuf::from(".changes")
+        );
+        assert_eq!(
+            dir_name_from(None, Some("release/changes")),
+            PathBuf::from("release/changes")
+        );
+        assert_eq!(
+            dir_name_from(Some(OsString::from(".changes")), Some("release/changes")),
+            PathBuf::from(".changes")
+        );
+        assert_eq!(
+            dir_name_from(Some(OsString::new()), Some("release/changes")),
+            PathBuf::from("release/changes")
+        );
+    }
+}
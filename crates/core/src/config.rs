@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::{hooks::HookEvent, timezone::Timezone, update_type::UpdateType, webhook::Webhook};
 
 /// Loaded from `.changepacks/config.json`, controls ignore patterns, base branch, publish commands, and update-on rules.
 ///
@@ -12,19 +17,36 @@ pub struct Config {
     #[serde(default)]
     pub ignore: Vec<String>,
 
+    /// Gitignore-style patterns for files whose changes never mark a project as changed
+    /// (e.g., "**/*.md"); unlike `ignore`, matching projects are still discovered
+    #[serde(default)]
+    pub change_ignore: Vec<String>,
+
+    /// Glob patterns, relative to the project directory, of the only files that mark a
+    /// project as changed, keyed by project path (e.g., "crates/core/Cargo.toml": ["src/**"])
+    #[serde(default)]
+    pub change_files: HashMap<String, Vec<String>>,
+
     /// Base branch to compare against for change detection (default: "main")
     #[serde(default = "default_base_branch")]
     pub base_branch: String,
 
+    /// Directory, relative to the project root, for changepack logs and release records
+    /// (default: ".changepacks"); the `CHANGEPACKS_DIR` environment variable takes precedence,
+    /// and only the local config file is read for it, not an `extends` base
+    #[serde(default)]
+    pub changepacks_dir: Option<String>,
+
     /// Optional path to the default main package for versioning
     #[serde(default)]
     pub latest_package: Option<String>,
 
-    /// Custom publish commands by language key or project path
+    /// Custom publish commands by language key, package name or project path, or `false` to never
+    /// publish
     #[serde(default)]
-    pub publish: HashMap<String, String>,
+    pub publish: HashMap<String, PublishSetting>,
 
-    /// Custom dry-run publish commands by language key or project path.
+    /// Custom dry-run publish commands by language key, package name or project path.
     ///
     /// Overrides the default dry-run derivation (appending `--dry-run` to the
     /// resolved publish command). Use this for ecosystems whose publish tool
@@ -38,25 +60,554 @@ pub struct Config {
     /// Value: list of package paths that must be updated when trigger matches
     #[serde(default)]
     pub update_on: HashMap<String, Vec<String>>,
+
+    /// Other repository roots, relative to this one, that `check` and the changepack
+    /// command also operate on (e.g., a product spanning a few repositories)
+    #[serde(default)]
+    pub repositories: Vec<String>,
+
+    /// Default update type by project path, pre-selected in the interactive flow and
+    /// applied by `--yes` when `--update-type` is omitted
+    #[serde(default)]
+    pub default_update_type: HashMap<String, UpdateType>,
+
+    /// Groups of project paths released in lockstep: bumping one member bumps all of them
+    /// with the same update type, and publishing stops the group if any member fails
+    #[serde(default)]
+    pub fixed: Vec<Vec<String>>,
+
+    /// Base config this one extends: an HTTPS URL, a git reference
+    /// (`git+<url>#<ref>:<path>`), or a path relative to the project root
+    #[serde(default)]
+    pub extends: Option<String>,
+
+    /// Expected `sha256:<hex>` hash of the `extends` content
+    #[serde(default)]
+    pub extends_integrity: Option<String>,
+
+    /// Run `hooks` set by a remote (HTTPS or git) `extends` base; only read from the local
+    /// config file
+    #[serde(default)]
+    pub extends_trust_hooks: bool,
+
+    /// Webhooks notified after `update` and `publish`: a URL receiving the JSON payload,
+    /// or `{ "url", "format" }` with a `slack` or `discord` message format
+    #[serde(default)]
+    pub webhooks: Vec<Webhook>,
+
+    /// Extra prompts asked when creating a changepack; answers are stored in the log
+    #[serde(default)]
+    pub template: ChangepackTemplate,
+
+    /// Make `update` and `publish` refuse to run while a changed project has no pending
+    /// changepack (same as passing `--strict`)
+    #[serde(default)]
+    pub require_changepacks: bool,
+
+    /// Branch names or glob patterns (e.g., "main", "release/*") on which `update` and a
+    /// non-dry-run `publish` refuse to run unless `--allow-protected` is passed
+    #[serde(default)]
+    pub protected_branches: Vec<String>,
+
+    /// Publish channel by prerelease identifier (e.g., `"rc": "next"`); prerelease versions
+    /// are published to their identifier's channel, which defaults to the identifier itself
+    #[serde(default)]
+    pub prerelease_channels: HashMap<String, String>,
+
+    /// Environment variables `publish` requires before running, by language key, registry
+    /// name (e.g., `"crates.io"`) or project path (e.g., `"node": ["NPM_TOKEN"]`)
+    #[serde(default)]
+    pub required_env: HashMap<String, Vec<String>>,
+
+    /// Times `publish` runs a failed publish command again (overridden by `--retries`)
+    #[serde(default)]
+    pub publish_retries: u32,
+
+    /// Seconds `publish` waits before the first retry, doubled after each further failure
+    /// (overridden by `--retry-delay`)
+    #[serde(default = "default_publish_retry_delay")]
+    pub publish_retry_delay: u64,
+
+    /// Age in days after which `check` and `doctor` warn about a pending changepack
+    #[serde(default)]
+    pub stale_changepack_days: Option<u64>,
+
+    /// Bridge packages by the crate manifest they wrap (e.g., a napi `package.json`); a bump
+    /// of the crate is applied to its bridges. napi and maturin bridges are also detected
+    /// from their manifests.
+    #[serde(default)]
+    pub bridges: HashMap<String, Vec<String>>,
+
+    /// strftime format for displayed dates such as changelog headings (default: "%Y-%m-%d")
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+
+    /// Timezone displayed dates are shown in: "UTC" (default), "local", or an offset like "+09:00"
+    #[serde(default)]
+    pub timezone: Timezone,
+
+    /// How Android modules' `versionCode` changes when they are bumped: "increment" (default)
+    /// adds one, "semver" derives it from the new version
+    #[serde(default)]
+    pub android_version_code: VersionCodeStrategy,
+
+    /// What bumps do to the `+build` number of pubspec versions (e.g., Flutter's `1.2.3+45`):
+    /// "increment" (default) adds one, "preserve" keeps it
+    #[serde(default)]
+    pub flutter_build_number: BuildNumberStrategy,
+
+    /// Version legacy .NET projects (a .csproj without `<Version>`) through the attributes of
+    /// their `Properties/AssemblyInfo.cs` instead of adding `<Version>` to the .csproj
+    #[serde(default)]
+    pub assembly_info: bool,
+
+    /// Bump the `appVersion` of Helm charts by the same update type as their chart `version`
+    #[serde(default)]
+    pub helm_app_version: bool,
+
+    /// How Node packages' dependency ranges on bumped packages are rewritten: "preserve"
+    /// (default) keeps the operator, "caret", "tilde" and "exact" replace it
+    #[serde(default)]
+    pub node_dependency_range: RangeStrategy,
+
+    /// Homebrew formulas whose `version`, `url` and `sha256` follow a package when it is published
+    #[serde(default)]
+    pub homebrew: HomebrewConfig,
+
+    /// Registry endpoints replacing the public ones, by registry ("npm", "crates.io", "pypi",
+    /// "nuget", "pub.dev"), for existence checks against private registries and feeds
+    #[serde(default)]
+    pub registries: HashMap<String, RegistryEndpoint>,
+
+    /// Write the notes of each release to a `CHANGELOG.md` next to the bumped manifest
+    /// (default: true)
+    #[serde(default = "default_changelog")]
+    pub changelog: bool,
+
+    /// Annotated git tags `update` creates for the packages it bumps
+    #[serde(default)]
+    pub git: GitConfig,
+
+    /// Format new changepack logs are written in: "json" (default) or "markdown"; both are read
+    #[serde(default)]
+    pub changepack_format: ChangepackFormat,
+
+    /// Shell commands run for each package before and after `update` bumps it and `publish`
+    /// publishes it
+    #[serde(default)]
+    pub hooks: HooksConfig,
+}
+
+/// Hook commands by the point of the run they fire at; each takes one command or a list
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HooksConfig {
+    /// Run before `update` writes anything; a failure stops the update
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub pre_update: Vec<String>,
+
+    /// Run once `update` has written the new versions, cleared the changepacks and tagged
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub post_update: Vec<String>,
+
+    /// Run before the publish command; a failure counts as a failed publish
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub pre_publish: Vec<String>,
+
+    /// Run after a successful publish
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub post_publish: Vec<String>,
+}
+
+impl HooksConfig {
+    /// Commands of the hook for `event`
+    #[must_use]
+    pub fn commands(&self, event: HookEvent) -> &[String] {
+        match event {
+            HookEvent::PreUpdate => &self.pre_update,
+            HookEvent::PostUpdate => &self.post_update,
+            HookEvent::PrePublish => &self.pre_publish,
+            HookEvent::PostPublish => &self.post_publish,
+        }
+    }
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(command) => vec![command],
+        OneOrMany::Many(commands) => commands,
+    })
+}
+
+/// Private registry (e.g., GitHub Packages, Artifactory, Verdaccio) queried instead of the public one
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryEndpoint {
+    /// Base URL of the registry's metadata API; a NuGet feed may give its v3 `index.json`
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Environment variable holding the token sent as `Authorization: Bearer <token>`
+    #[serde(default)]
+    pub token_env: Option<String>,
+}
+
+/// Homebrew tap kept in step with published packages
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HomebrewConfig {
+    /// Directory holding the formula files, relative to the repository root (default: "Formula")
+    #[serde(default = "default_homebrew_tap")]
+    pub tap: String,
+
+    /// Project name or path followed by each formula, by formula name (e.g.,
+    /// `"mytool": "crates/cli/Cargo.toml"`); unlisted formulas follow the package of their name
+    #[serde(default)]
+    pub formulas: HashMap<String, String>,
+}
+
+impl Default for HomebrewConfig {
+    fn default() -> Self {
+        Self {
+            tap: default_homebrew_tap(),
+            formulas: HashMap::new(),
+        }
+    }
+}
+
+/// Annotated git tags created for each package `update` bumps
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConfig {
+    /// Tag every bumped package, as `update --tag` does
+    #[serde(default)]
+    pub tag: bool,
+
+    /// Tag name, with `{name}` and `{version}` replaced (default: "{name}@{version}")
+    #[serde(default = "default_tag_template")]
+    pub tag_template: String,
+
+    /// Put in front of every tag name (e.g., "release/"); `update --tag-prefix` replaces it
+    #[serde(default)]
+    pub tag_prefix: String,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            tag: false,
+            tag_template: default_tag_template(),
+            tag_prefix: String::new(),
+        }
+    }
+}
+
+impl GitConfig {
+    /// Tag name for `version` of the package `name`, behind `prefix`
+    #[must_use]
+    pub fn tag_name(&self, prefix: &str, name: &str, version: &str) -> String {
+        format!(
+            "{prefix}{}",
+            self.tag_template
+                .replace("{name}", name)
+                .replace("{version}", version)
+        )
+    }
+}
+
+/// File format of the changepack logs written to `.changepacks`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangepackFormat {
+    /// `changepack_log_<id>.json`
+    #[default]
+    Json,
+    /// `changepack_log_<id>.md`: YAML frontmatter with the changes, followed by the notes
+    Markdown,
+}
+
+impl ChangepackFormat {
+    /// File extension of logs in this format
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// Strategy for the `versionCode` of Android Gradle modules, which `versionName` can't replace
+/// because Play Store uploads require a strictly increasing integer
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum VersionCodeStrategy {
+    /// Monotonic counter: the current code plus one
+    #[default]
+    Increment,
+    /// `major * 1_000_000 + minor * 1_000 + patch` of the new version
+    Semver,
+}
+
+/// Strategy for the numeric `+build` suffix of Dart/Flutter versions on bumps
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildNumberStrategy {
+    /// Add one, as store uploads need a new build number per release
+    #[default]
+    Increment,
+    /// Keep the build number as written
+    Preserve,
+}
+
+/// Operator of a dependency range rewritten to a bumped version
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum RangeStrategy {
+    /// Keep the range's own operator and precision (`~1.2` becomes `~1.3`)
+    #[default]
+    Preserve,
+    /// `^` and the full version
+    Caret,
+    /// `~` and the full version
+    Tilde,
+    /// The full version alone
+    Exact,
+}
+
+/// Entry of `map` keyed by the project's path, else its package name, else its language key
+pub(crate) fn project_entry<'a, V>(
+    map: &'a HashMap<String, V>,
+    relative_path: &Path,
+    name: Option<&str>,
+    language: crate::Language,
+) -> Option<&'a V> {
+    let path = crate::path_key(relative_path);
+    map.iter()
+        .find(|(key, _)| crate::path_key(Path::new(key)) == path)
+        .map(|(_, value)| value)
+        .or_else(|| name.and_then(|name| map.get(name)))
+        .or_else(|| map.get(language.publish_key()))
+}
+
+/// Entry of the `publish` config: a command, or `false` to version a project without publishing it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PublishSetting {
+    Command(String),
+    /// `true` keeps the default command; `false` turns publishing off
+    Enabled(bool),
+}
+
+impl PublishSetting {
+    /// Custom command, if this entry sets one
+    #[must_use]
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            Self::Command(command) => Some(command),
+            Self::Enabled(_) => None,
+        }
+    }
+}
+
+impl From<String> for PublishSetting {
+    fn from(command: String) -> Self {
+        Self::Command(command)
+    }
+}
+
+impl PartialEq<str> for PublishSetting {
+    fn eq(&self, other: &str) -> bool {
+        self.command() == Some(other)
+    }
+}
+
+/// Extra questions asked by the changepack command (e.g., ticket ID, breaking-change details)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangepackTemplate {
+    #[serde(default)]
+    pub prompts: Vec<TemplatePrompt>,
+}
+
+/// Single template prompt whose answer is stored under `key` in the changepack log
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplatePrompt {
+    /// Field name in the changepack log and release notes (e.g., "issue")
+    pub key: String,
+
+    /// Question shown to the user
+    pub message: String,
+
+    /// Reject empty answers instead of omitting the field
+    #[serde(default)]
+    pub required: bool,
 }
 
 fn default_base_branch() -> String {
     "main".to_string()
 }
 
+fn default_tag_template() -> String {
+    "{name}@{version}".to_string()
+}
+
+fn default_homebrew_tap() -> String {
+    "Formula".to_string()
+}
+
+const fn default_changelog() -> bool {
+    true
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+const fn default_publish_retry_delay() -> u64 {
+    5
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             ignore: Vec::new(),
+            change_ignore: Vec::new(),
+            change_files: HashMap::new(),
             base_branch: default_base_branch(),
+            changepacks_dir: None,
             latest_package: None,
             publish: HashMap::new(),
             publish_dry_run: HashMap::new(),
             update_on: HashMap::new(),
+            repositories: Vec::new(),
+            default_update_type: HashMap::new(),
+            fixed: Vec::new(),
+            extends: None,
+            extends_integrity: None,
+            extends_trust_hooks: false,
+            webhooks: Vec::new(),
+            template: ChangepackTemplate::default(),
+            require_changepacks: false,
+            protected_branches: Vec::new(),
+            prerelease_channels: HashMap::new(),
+            required_env: HashMap::new(),
+            publish_retries: 0,
+            publish_retry_delay: default_publish_retry_delay(),
+            stale_changepack_days: None,
+            bridges: HashMap::new(),
+            date_format: default_date_format(),
+            timezone: Timezone::default(),
+            android_version_code: VersionCodeStrategy::default(),
+            flutter_build_number: BuildNumberStrategy::default(),
+            assembly_info: false,
+            helm_app_version: false,
+            node_dependency_range: RangeStrategy::default(),
+            homebrew: HomebrewConfig::default(),
+            registries: HashMap::new(),
+            changelog: default_changelog(),
+            git: GitConfig::default(),
+            changepack_format: ChangepackFormat::default(),
+            hooks: HooksConfig::default(),
         }
     }
 }
 
+impl Config {
+    /// `date` in the configured `dateFormat` and `timezone`
+    ///
+    /// An invalid `dateFormat` falls back to the ISO-8601 default rather than failing output.
+    #[must_use]
+    pub fn format_date(&self, date: &DateTime<Utc>) -> String {
+        self.timezone
+            .format(date, &self.date_format)
+            .or_else(|| self.timezone.format(date, &default_date_format()))
+            .unwrap_or_default()
+    }
+
+    /// [`Config::format_date`] followed by the time of day and timezone
+    /// (e.g., `2025-01-02 15:04:05 UTC`)
+    #[must_use]
+    pub fn format_date_time(&self, date: &DateTime<Utc>) -> String {
+        format!(
+            "{} {} {}",
+            self.format_date(date),
+            self.timezone.format(date, "%H:%M:%S").unwrap_or_default(),
+            self.timezone.label(date)
+        )
+    }
+
+    /// Configured default update type for the project at `relative_path`
+    #[must_use]
+    pub fn package_default_update_type(&self, relative_path: &Path) -> Option<UpdateType> {
+        let path = crate::path_key(relative_path);
+        self.default_update_type
+            .iter()
+            .find(|(key, _)| crate::path_key(Path::new(key)) == path)
+            .map(|(_, update_type)| *update_type)
+    }
+
+    /// Whether `publish` turns publishing off for the project at `relative_path`
+    ///
+    /// A project path entry wins over a package name entry, which wins over the language entry.
+    #[must_use]
+    pub fn is_publish_disabled(
+        &self,
+        relative_path: &Path,
+        name: Option<&str>,
+        language: crate::Language,
+    ) -> bool {
+        project_entry(&self.publish, relative_path, name, language)
+            == Some(&PublishSetting::Enabled(false))
+    }
+
+    /// Environment variables required to publish the project at `relative_path`
+    ///
+    /// Entries for the language key, the registry name and the project path all apply.
+    #[must_use]
+    pub fn required_env_vars(
+        &self,
+        relative_path: &Path,
+        language: crate::Language,
+        registry: &str,
+    ) -> Vec<&str> {
+        let path = crate::path_key(relative_path);
+        let mut vars: Vec<&str> = Vec::new();
+        for (key, names) in &self.required_env {
+            if key == language.publish_key()
+                || key == registry
+                || crate::path_key(Path::new(key)) == path
+            {
+                vars.extend(names.iter().map(String::as_str));
+            }
+        }
+        vars.sort_unstable();
+        vars.dedup();
+        vars
+    }
+
+    /// Index of the `fixed` group containing the project at `relative_path`
+    #[must_use]
+    pub fn fixed_group_index(&self, relative_path: &Path) -> Option<usize> {
+        let path = crate::path_key(relative_path);
+        self.fixed.iter().position(|group| {
+            group
+                .iter()
+                .any(|member| crate::path_key(Path::new(member)) == path)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +621,270 @@ mod tests {
         assert!(config.publish.is_empty());
         assert!(config.publish_dry_run.is_empty());
         assert!(config.update_on.is_empty());
+        assert!(config.repositories.is_empty());
+        assert!(config.default_update_type.is_empty());
+        assert!(config.fixed.is_empty());
+        assert!(config.extends.is_none());
+        assert!(config.extends_integrity.is_none());
+        assert!(!config.extends_trust_hooks);
+        assert!(config.webhooks.is_empty());
+        assert!(config.template.prompts.is_empty());
+        assert!(!config.require_changepacks);
+        assert!(config.protected_branches.is_empty());
+        assert!(config.prerelease_channels.is_empty());
+        assert_eq!(config.publish_retries, 0);
+        assert_eq!(config.publish_retry_delay, 5);
+        assert!(config.stale_changepack_days.is_none());
+        assert!(config.bridges.is_empty());
+        assert!(config.change_ignore.is_empty());
+        assert!(config.change_files.is_empty());
+        assert_eq!(config.date_format, "%Y-%m-%d");
+        assert_eq!(config.timezone, Timezone::Utc);
+        assert_eq!(config.android_version_code, VersionCodeStrategy::Increment);
+        assert_eq!(config.flutter_build_number, BuildNumberStrategy::Increment);
+        assert!(!config.assembly_info);
+        assert!(!config.helm_app_version);
+        assert_eq!(config.node_dependency_range, RangeStrategy::Preserve);
+        assert_eq!(config.changepack_format, ChangepackFormat::Json);
+        assert_eq!(config.homebrew.tap, "Formula");
+        assert!(config.homebrew.formulas.is_empty());
+        assert!(config.registries.is_empty());
+        assert!(config.changelog);
+        assert_eq!(config.git, GitConfig::default());
+    }
+
+    #[test]
+    fn test_config_format_date() {
+        let date = DateTime::parse_from_rfc3339("2025-01-01T20:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let config = Config::default();
+        assert_eq!(config.format_date(&date), "2025-01-01");
+        assert_eq!(config.format_date_time(&date), "2025-01-01 20:30:00 UTC");
+
+        let config: Config =
+            serde_json::from_str(r#"{ "dateFormat": "%d.%m.%Y", "timezone": "+09:00" }"#).unwrap();
+        assert_eq!(config.format_date(&date), "02.01.2025");
+        assert_eq!(config.format_date_time(&date), "02.01.2025 05:30:00 +09:00");
+
+        let config: Config = serde_json::from_str(r#"{ "dateFormat": "%Q" }"#).unwrap();
+        assert_eq!(config.format_date(&date), "2025-01-01");
+        assert!(serde_json::from_str::<Config>(r#"{ "timezone": "Mars" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_android_version_code() {
+        let config: Config = serde_json::from_str(r#"{ "androidVersionCode": "semver" }"#).unwrap();
+        assert_eq!(config.android_version_code, VersionCodeStrategy::Semver);
+        assert!(serde_json::from_str::<Config>(r#"{ "androidVersionCode": "date" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_flutter_build_number() {
+        let config: Config =
+            serde_json::from_str(r#"{ "flutterBuildNumber": "preserve" }"#).unwrap();
+        assert_eq!(config.flutter_build_number, BuildNumberStrategy::Preserve);
+    }
+
+    #[test]
+    fn test_config_homebrew() {
+        let config: Config = serde_json::from_str(
+            r#"{ "homebrew": { "formulas": { "mytool": "crates/cli/Cargo.toml" } } }"#,
+        )
+        .unwrap();
+        assert_eq!(config.homebrew.tap, "Formula");
+        assert_eq!(
+            config.homebrew.formulas["mytool"],
+            "crates/cli/Cargo.toml".to_string()
+        );
+    }
+
+    #[test]
+    fn test_config_registries() {
+        let config: Config = serde_json::from_str(
+            r#"{ "registries": { "npm": { "url": "https://npm.pkg.github.com", "tokenEnv": "NODE_AUTH_TOKEN" } } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.registries["npm"],
+            RegistryEndpoint {
+                url: Some("https://npm.pkg.github.com".to_string()),
+                token_env: Some("NODE_AUTH_TOKEN".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_config_git_tags() {
+        let config: Config =
+            serde_json::from_str(r#"{ "git": { "tag": true, "tagPrefix": "release/" } }"#).unwrap();
+        assert!(config.git.tag);
+        assert_eq!(config.git.tag_template, "{name}@{version}");
+        assert_eq!(
+            config.git.tag_name(&config.git.tag_prefix, "core", "1.2.3"),
+            "release/core@1.2.3"
+        );
+
+        let config: Config =
+            serde_json::from_str(r#"{ "git": { "tagTemplate": "{name}-v{version}" } }"#).unwrap();
+        assert!(!config.git.tag);
+        assert_eq!(config.git.tag_name("", "core", "1.2.3"), "core-v1.2.3");
+    }
+
+    #[test]
+    fn test_config_changepack_format() {
+        let config: Config = serde_json::from_str(r#"{ "changepackFormat": "markdown" }"#).unwrap();
+        assert_eq!(config.changepack_format, ChangepackFormat::Markdown);
+        assert_eq!(config.changepack_format.extension(), "md");
+        assert!(serde_json::from_str::<Config>(r#"{ "changepackFormat": "toml" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_changelog_disabled() {
+        let config: Config = serde_json::from_str(r#"{ "changelog": false }"#).unwrap();
+        assert!(!config.changelog);
+    }
+
+    #[test]
+    fn test_config_publish_retries() {
+        let config: Config =
+            serde_json::from_str(r#"{ "publishRetries": 2, "publishRetryDelay": 30 }"#).unwrap();
+        assert_eq!(config.publish_retries, 2);
+        assert_eq!(config.publish_retry_delay, 30);
+    }
+
+    #[test]
+    fn test_config_require_changepacks() {
+        let config: Config = serde_json::from_str(r#"{ "requireChangepacks": true }"#).unwrap();
+        assert!(config.require_changepacks);
+    }
+
+    #[test]
+    fn test_config_template_prompts() {
+        let json = r#"{
+            "template": {
+                "prompts": [
+                    { "key": "issue", "message": "Issue ID?", "required": true },
+                    { "key": "breaking", "message": "Describe the breaking change" }
+                ]
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.template.prompts.len(), 2);
+        assert_eq!(config.template.prompts[0].key, "issue");
+        assert!(config.template.prompts[0].required);
+        assert!(!config.template.prompts[1].required);
+    }
+
+    #[test]
+    fn test_config_publish_disabled() {
+        let json = r#"{
+            "publish": {
+                "node": "npm publish --access public",
+                "python": false,
+                "tools/lint/package.json": false,
+                "libs/py/pyproject.toml": true,
+                "demo": false,
+                "apps/demo/package.json": true
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.publish.get("node").unwrap(),
+            "npm publish --access public"
+        );
+        let disabled = |path: &str, name: Option<&str>, language| {
+            config.is_publish_disabled(Path::new(path), name, language)
+        };
+        assert!(disabled(
+            "tools/lint/package.json",
+            None,
+            crate::Language::Node
+        ));
+        assert!(!disabled("web/package.json", None, crate::Language::Node));
+        assert!(disabled("py/pyproject.toml", None, crate::Language::Python));
+        assert!(!disabled(
+            "libs/py/pyproject.toml",
+            None,
+            crate::Language::Python
+        ));
+        // A name entry wins over the language, a path entry over the name
+        assert!(disabled(
+            "examples/demo/package.json",
+            Some("demo"),
+            crate::Language::Node
+        ));
+        assert!(!disabled(
+            "apps/demo/package.json",
+            Some("demo"),
+            crate::Language::Node
+        ));
+    }
+
+    #[test]
+    fn test_config_required_env_vars() {
+        let json = r#"{
+            "requiredEnv": {
+                "node": ["NPM_TOKEN"],
+                "crates.io": ["CARGO_REGISTRY_TOKEN"],
+                "web/package.json": ["SENTRY_TOKEN", "NPM_TOKEN"]
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.required_env_vars(Path::new("web/package.json"), crate::Language::Node, "npm"),
+            vec!["NPM_TOKEN", "SENTRY_TOKEN"]
+        );
+        assert_eq!(
+            config.required_env_vars(Path::new("api/package.json"), crate::Language::Node, "npm"),
+            vec!["NPM_TOKEN"]
+        );
+        assert_eq!(
+            config.required_env_vars(Path::new("Cargo.toml"), crate::Language::Rust, "crates.io"),
+            vec!["CARGO_REGISTRY_TOKEN"]
+        );
+        assert!(
+            config
+                .required_env_vars(Path::new("pyproject.toml"), crate::Language::Python, "pypi")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_config_fixed_group_index() {
+        let json = r#"{ "fixed": [["a/package.json", "b/package.json"], ["c/Cargo.toml"]] }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.fixed_group_index(Path::new("b/package.json")),
+            Some(0)
+        );
+        assert_eq!(config.fixed_group_index(Path::new("c/Cargo.toml")), Some(1));
+        assert_eq!(config.fixed_group_index(Path::new("d/package.json")), None);
+    }
+
+    #[test]
+    fn test_config_package_default_update_type() {
+        let json = r#"{ "defaultUpdateType": { "docs/package.json": "patch", "crates/core/Cargo.toml": "Minor" } }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.package_default_update_type(Path::new("docs/package.json")),
+            Some(UpdateType::Patch)
+        );
+        assert_eq!(
+            config.package_default_update_type(Path::new("crates/core/Cargo.toml")),
+            Some(UpdateType::Minor)
+        );
+        assert_eq!(
+            config.package_default_update_type(Path::new("other/package.json")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_config_repositories() {
+        let json = r#"{ "repositories": ["../api", "../web"] }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.repositories, vec!["../api", "../web"]);
     }
 
     #[test]
@@ -156,6 +971,19 @@ mod tests {
         assert!(config.update_on.is_empty());
     }
 
+    #[test]
+    fn test_config_deserialize_hooks() {
+        let json = r#"{ "hooks": { "preUpdate": "cargo build", "postPublish": ["./notify.sh", "echo done"] } }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.hooks.commands(HookEvent::PreUpdate), ["cargo build"]);
+        assert_eq!(
+            config.hooks.commands(HookEvent::PostPublish),
+            ["./notify.sh", "echo done"]
+        );
+        assert!(config.hooks.commands(HookEvent::PostUpdate).is_empty());
+        assert!(serde_json::from_str::<Config>(r#"{ "hooks": { "preUpdate": 1 } }"#).is_err());
+    }
+
     #[test]
     fn test_config_ignore_patterns() {
         let json = r#"{ "ignore": ["**/*", "!crates/changepacks/Cargo.toml", "!bridge/**"] }"#;
@@ -221,7 +1049,7 @@ mod tests {
         };
         config
             .publish
-            .insert("rust".to_string(), "cargo publish".to_string());
+            .insert("rust".to_string(), "cargo publish".to_string().into());
         config.update_on.insert(
             "Cargo.toml".to_string(),
             vec!["bridge/package.json".to_string()],
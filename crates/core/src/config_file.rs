@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::Config;
+
+/// Config file names looked up in the changepacks directory
+pub const CONFIG_FILE_NAMES: &[&str] = &["config.json", "config.toml", "config.yaml", "config.yml"];
+
+/// Syntax of a config file, chosen by its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFileFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFileFormat {
+    /// Format of the file at `path`, or `None` for an unsupported extension
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Parse `content` into the JSON value every format maps onto
+    ///
+    /// # Errors
+    /// Returns error if `content` is not valid in this format.
+    pub fn parse(self, content: &str) -> Result<Value> {
+        Ok(match self {
+            Self::Json => serde_json::from_str(content)?,
+            Self::Toml => toml::from_str(content)?,
+            Self::Yaml => serde_yaml::from_str(content)?,
+        })
+    }
+}
+
+/// The config file of a changepacks directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFile {
+    path: PathBuf,
+    format: ConfigFileFormat,
+}
+
+impl ConfigFile {
+    /// Config file in `changepacks_dir`, if there is one
+    ///
+    /// # Errors
+    /// Returns error if more than one of `config.json`, `config.toml` and `config.yaml`
+    /// (or `config.yml`) exists, since it would be unclear which one applies.
+    pub fn find(changepacks_dir: &Path) -> Result<Option<Self>> {
+        let found: Vec<PathBuf> = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| changepacks_dir.join(name))
+            .filter(|path| path.is_file())
+            .collect();
+        if found.len() > 1 {
+            let names: Vec<String> = found
+                .iter()
+                .filter_map(|path| path.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .collect();
+            anyhow::bail!(
+                "Multiple config files in {}: {} (keep one)",
+                changepacks_dir.display(),
+                names.join(", ")
+            );
+        }
+        Ok(found.into_iter().next().and_then(|path| {
+            ConfigFileFormat::from_path(&path).map(|format| Self { path, format })
+        }))
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub const fn format(&self) -> ConfigFileFormat {
+        self.format
+    }
+
+    /// File name, e.g. `config.toml`, for messages
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Raw settings of the file, or `None` when it is empty
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read or parsed.
+    pub fn read_value(&self) -> Result<Option<Value>> {
+        let content = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.name()))?;
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+        self.format
+            .parse(&content)
+            .with_context(|| format!("Failed to parse {}", self.name()))
+            .map(Some)
+    }
+
+    /// Typed config from the raw settings of this file, defaults filling the gaps
+    ///
+    /// # Errors
+    /// Returns error if a setting has the wrong type.
+    pub fn to_config(&self, value: Value) -> Result<Config> {
+        serde_json::from_value(value).with_context(|| format!("Failed to parse {}", self.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("config.json", Some(ConfigFileFormat::Json))]
+    #[case("config.toml", Some(ConfigFileFormat::Toml))]
+    #[case("config.yaml", Some(ConfigFileFormat::Yaml))]
+    #[case("config.yml", Some(ConfigFileFormat::Yaml))]
+    #[case("config.ini", None)]
+    #[case("config", None)]
+    fn test_config_file_format_from_path(
+        #[case] path: &str,
+        #[case] expected: Option<ConfigFileFormat>,
+    ) {
+        assert_eq!(ConfigFileFormat::from_path(Path::new(path)), expected);
+    }
+
+    #[rstest]
+    #[case(
+        ConfigFileFormat::Json,
+        r#"{"baseBranch": "develop", "ignore": ["docs/**"]}"#
+    )]
+    #[case(
+        ConfigFileFormat::Toml,
+        "# Release from develop\nbaseBranch = \"develop\"\nignore = [\"docs/**\"]\n"
+    )]
+    #[case(
+        ConfigFileFormat::Yaml,
+        "# Release from develop\nbaseBranch: develop\nignore:\n  - docs/**\n"
+    )]
+    fn test_config_file_formats_agree(#[case] format: ConfigFileFormat, #[case] content: &str) {
+        let value = format.parse(content).unwrap();
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert_eq!(config.base_branch, "develop");
+        assert_eq!(config.ignore, ["docs/**"]);
+    }
+
+    #[test]
+    fn test_config_file_find() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ConfigFile::find(dir.path()).unwrap().is_none());
+
+        std::fs::write(dir.path().join("config.toml"), "").unwrap();
+        let file = ConfigFile::find(dir.path()).unwrap().unwrap();
+        assert_eq!(file.format(), ConfigFileFormat::Toml);
+        assert_eq!(file.name(), "config.toml");
+        assert!(file.read_value().unwrap().is_none());
+
+        std::fs::write(dir.path().join("config.toml"), "baseBranch = 1").unwrap();
+        let value = file.read_value().unwrap().unwrap();
+        let err = file.to_config(value).unwrap_err();
+        assert!(err.to_string().contains("config.toml"));
+
+        std::fs::write(dir.path().join("config.json"), "{}").unwrap();
+        let err = ConfigFile::find(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("config.json, config.toml"), "{err}");
+    }
+}
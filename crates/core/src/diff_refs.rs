@@ -0,0 +1,10 @@
+/// Explicit ends of the change-detection comparison (set from the global `--base`/`--head`)
+///
+/// `DiffRefs::default()` derives the comparison from `HEAD` and the `baseBranch`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffRefs {
+    /// Revision compared against instead of the `baseBranch` (e.g., `origin/main`)
+    pub base: Option<String>,
+    /// Revision whose changes are detected instead of `HEAD` and the working tree
+    pub head: Option<String>,
+}
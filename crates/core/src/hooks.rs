@@ -0,0 +1,170 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::{Config, UpdateType, normalize_path::path_key, publish::build_shell_command};
+
+/// Point of an `update` or `publish` run at which the configured hooks run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    PreUpdate,
+    PostUpdate,
+    PrePublish,
+    PostPublish,
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::PreUpdate => "preUpdate",
+            Self::PostUpdate => "postUpdate",
+            Self::PrePublish => "prePublish",
+            Self::PostPublish => "postPublish",
+        })
+    }
+}
+
+/// Package a hook runs for, passed to it as `CHANGEPACKS_*` environment variables
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookContext {
+    /// Package name (`CHANGEPACKS_PACKAGE`)
+    pub package: Option<String>,
+    /// Manifest path relative to the repository root (`CHANGEPACKS_PATH`)
+    pub path: PathBuf,
+    /// Version the package is at when the hook runs (`CHANGEPACKS_VERSION`)
+    pub version: Option<String>,
+    /// Version before the update, for `postUpdate` (`CHANGEPACKS_PREVIOUS_VERSION`)
+    pub previous_version: Option<String>,
+    /// Bump applied by the update (`CHANGEPACKS_UPDATE_TYPE`)
+    pub update_type: Option<UpdateType>,
+}
+
+impl HookContext {
+    /// Environment variables of a hook run for `event`; unknown values are left empty
+    #[must_use]
+    pub fn env(&self, event: HookEvent) -> Vec<(&'static str, String)> {
+        let update_type = self.update_type.map(|update_type| match update_type {
+            UpdateType::Major => "major",
+            UpdateType::Minor => "minor",
+            UpdateType::Patch => "patch",
+            UpdateType::Prerelease => "prerelease",
+        });
+        vec![
+            ("CHANGEPACKS_HOOK", event.to_string()),
+            (
+                "CHANGEPACKS_PACKAGE",
+                self.package.clone().unwrap_or_default(),
+            ),
+            ("CHANGEPACKS_PATH", path_key(&self.path)),
+            (
+                "CHANGEPACKS_VERSION",
+                self.version.clone().unwrap_or_default(),
+            ),
+            (
+                "CHANGEPACKS_PREVIOUS_VERSION",
+                self.previous_version.clone().unwrap_or_default(),
+            ),
+            (
+                "CHANGEPACKS_UPDATE_TYPE",
+                update_type.unwrap_or_default().to_string(),
+            ),
+        ]
+    }
+}
+
+/// Run the `event` hooks of `config` in `working_dir`, one after another
+///
+/// Hook output goes to stderr so it never mixes into JSON printed on stdout.
+///
+/// # Errors
+/// Returns error at the first hook that cannot be started or exits with a non-zero status.
+pub async fn run_hooks(
+    config: &Config,
+    event: HookEvent,
+    context: &HookContext,
+    working_dir: &Path,
+) -> Result<()> {
+    for command in config.hooks.commands(event) {
+        let mut cmd = build_shell_command(command);
+        cmd.current_dir(working_dir)
+            .envs(context.env(event))
+            .stdout(std::io::stderr());
+        let status = cmd
+            .status()
+            .await
+            .with_context(|| format!("Failed to run {event} hook `{command}`"))?;
+        if !status.success() {
+            anyhow::bail!("{event} hook `{command}` failed ({status})");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(hooks: serde_json::Value) -> Config {
+        serde_json::from_value(serde_json::json!({ "hooks": hooks })).unwrap()
+    }
+
+    #[test]
+    fn test_hook_context_env() {
+        let context = HookContext {
+            package: Some("core".to_string()),
+            path: PathBuf::from("crates/core/Cargo.toml"),
+            version: Some("1.1.0".to_string()),
+            previous_version: Some("1.0.0".to_string()),
+            update_type: Some(UpdateType::Minor),
+        };
+        assert_eq!(
+            context.env(HookEvent::PostUpdate),
+            [
+                ("CHANGEPACKS_HOOK", "postUpdate".to_string()),
+                ("CHANGEPACKS_PACKAGE", "core".to_string()),
+                ("CHANGEPACKS_PATH", "crates/core/Cargo.toml".to_string()),
+                ("CHANGEPACKS_VERSION", "1.1.0".to_string()),
+                ("CHANGEPACKS_PREVIOUS_VERSION", "1.0.0".to_string()),
+                ("CHANGEPACKS_UPDATE_TYPE", "minor".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let context = HookContext {
+            package: Some("web".to_string()),
+            version: Some("2.0.0".to_string()),
+            ..HookContext::default()
+        };
+        let config = config(serde_json::json!({
+            "prePublish": [
+                "echo \"$CHANGEPACKS_HOOK $CHANGEPACKS_PACKAGE@$CHANGEPACKS_VERSION\" > hook.txt",
+                "echo second >> hook.txt"
+            ],
+            "postPublish": "exit 3"
+        }));
+
+        run_hooks(&config, HookEvent::PrePublish, &context, dir.path())
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hook.txt")).unwrap(),
+            "prePublish web@2.0.0\nsecond\n"
+        );
+
+        let err = run_hooks(&config, HookEvent::PostPublish, &context, dir.path())
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("postPublish hook `exit 3` failed"),
+            "{err}"
+        );
+        run_hooks(&config, HookEvent::PreUpdate, &context, dir.path())
+            .await
+            .unwrap();
+    }
+}
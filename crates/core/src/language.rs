@@ -18,6 +18,14 @@ pub enum Language {
     CSharp,
     /// Java projects using build.gradle or build.gradle.kts (Gradle)
     Java,
+    /// Go modules using go.mod, versioned by git tags
+    Go,
+    /// Elixir projects using mix.exs (Mix, Hex)
+    Elixir,
+    /// Deno projects using deno.json or jsr.json (JSR)
+    Deno,
+    /// Helm charts using Chart.yaml (OCI registries)
+    Helm,
 }
 
 impl Language {
@@ -31,6 +39,27 @@ impl Language {
             Self::Dart => "dart",
             Self::CSharp => "csharp",
             Self::Java => "java",
+            Self::Go => "go",
+            Self::Elixir => "elixir",
+            Self::Deno => "deno",
+            Self::Helm => "helm",
+        }
+    }
+
+    /// Returns the default registry packages of this language are published to
+    #[must_use]
+    pub const fn registry(&self) -> &'static str {
+        match self {
+            Self::Node => "npm",
+            Self::Python => "pypi",
+            Self::Rust => "crates.io",
+            Self::Dart => "pub.dev",
+            Self::CSharp => "nuget",
+            Self::Java => "maven",
+            Self::Go => "proxy.golang.org",
+            Self::Elixir => "hex",
+            Self::Deno => "jsr",
+            Self::Helm => "oci",
         }
     }
 }
@@ -47,6 +76,10 @@ impl Display for Language {
                 Self::Dart => "Dart".blue().bold(),
                 Self::CSharp => "C#".magenta().bold(),
                 Self::Java => "Java".red().bold(),
+                Self::Go => "Go".cyan().bold(),
+                Self::Elixir => "Elixir".truecolor(110, 74, 126).bold(),
+                Self::Deno => "Deno".white().bold(),
+                Self::Helm => "Helm".truecolor(15, 22, 137).bold(),
             }
         )
     }
@@ -64,6 +97,10 @@ mod tests {
     #[case(Language::Dart, "Dart")]
     #[case(Language::CSharp, "C#")]
     #[case(Language::Java, "Java")]
+    #[case(Language::Go, "Go")]
+    #[case(Language::Elixir, "Elixir")]
+    #[case(Language::Deno, "Deno")]
+    #[case(Language::Helm, "Helm")]
     fn test_language_display(#[case] language: Language, #[case] expected: &str) {
         let display = format!("{}", language);
         assert!(display.contains(expected));
@@ -76,7 +113,26 @@ mod tests {
     #[case(Language::Dart, "dart")]
     #[case(Language::CSharp, "csharp")]
     #[case(Language::Java, "java")]
+    #[case(Language::Go, "go")]
+    #[case(Language::Elixir, "elixir")]
+    #[case(Language::Deno, "deno")]
+    #[case(Language::Helm, "helm")]
     fn test_publish_key(#[case] language: Language, #[case] expected: &str) {
         assert_eq!(language.publish_key(), expected);
     }
+
+    #[rstest]
+    #[case(Language::Python, "pypi")]
+    #[case(Language::Node, "npm")]
+    #[case(Language::Rust, "crates.io")]
+    #[case(Language::Dart, "pub.dev")]
+    #[case(Language::CSharp, "nuget")]
+    #[case(Language::Java, "maven")]
+    #[case(Language::Go, "proxy.golang.org")]
+    #[case(Language::Elixir, "hex")]
+    #[case(Language::Deno, "jsr")]
+    #[case(Language::Helm, "oci")]
+    fn test_registry(#[case] language: Language, #[case] expected: &str) {
+        assert_eq!(language.registry(), expected);
+    }
 }
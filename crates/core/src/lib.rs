@@ -7,26 +7,57 @@
 //! monorepo roots, and `ProjectFinder` for discovering projects in a git tree.
 
 mod changepack_result;
+mod changepacks_dir;
 mod config;
+mod config_file;
+mod diff_refs;
+mod hooks;
 mod language;
+mod normalize_path;
 mod package;
+mod package_metadata;
 mod project;
 mod project_finder;
 pub mod publish;
+mod publish_log;
 mod publish_result;
+mod release_manifest;
+mod timezone;
 mod update_log;
 mod update_type;
+mod webhook;
 mod workspace;
 
 // Re-export traits for convenience
-pub use changepack_result::{ChangePackResult, ChangePackResultLog};
-pub use config::Config;
+pub use changepack_result::{ChangePackResult, ChangePackResultLog, DependencyEdge};
+pub use changepacks_dir::{
+    CHANGEPACKS_DIR_ENV, DEFAULT_CHANGEPACKS_DIR, changepacks_dir_name,
+    resolve_changepacks_dir_name,
+};
+pub use config::{
+    BuildNumberStrategy, ChangepackFormat, ChangepackTemplate, Config, GitConfig, HomebrewConfig,
+    HooksConfig, PublishSetting, RangeStrategy, RegistryEndpoint, TemplatePrompt,
+    VersionCodeStrategy,
+};
+pub use config_file::{CONFIG_FILE_NAMES, ConfigFile, ConfigFileFormat};
+pub use diff_refs::DiffRefs;
+pub use hooks::{HookContext, HookEvent, run_hooks};
 pub use language::Language;
+pub use normalize_path::{normalize_path, path_key, project_key};
 pub use package::Package;
+pub use package_metadata::PackageMetadata;
 pub use project::Project;
 pub use project_finder::ProjectFinder;
 pub use publish::PublishOutput;
+pub use publish_log::{PUBLISH_LOG_FILE, PublishLog, PublishRecord};
 pub use publish_result::PublishResult;
+pub use release_manifest::{
+    RELEASE_MANIFEST_FILE, ReleaseManifest, ReleaseRecord, ReleasedPackage,
+};
+pub use timezone::Timezone;
 pub use update_log::ChangePackLog;
 pub use update_type::UpdateType;
+pub use webhook::{
+    Webhook, WebhookEvent, WebhookFormat, WebhookPackage, WebhookPayload, WebhookStatus,
+};
 pub use workspace::Workspace;
@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+
+/// Comparable form of an absolute path
+///
+/// Windows `canonicalize` returns verbatim paths (`\\?\C:\repo`), the current directory may
+/// report a lower-case drive (`c:\repo`), and git hands out forward slashes; all of these are
+/// folded into `C:\repo`. `\\?\UNC\server\share` becomes `\\server\share`. Paths without a
+/// Windows prefix are returned unchanged.
+#[must_use]
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let Some(raw) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    let raw = match raw.strip_prefix(r"\\?\UNC\") {
+        Some(unc) => format!(r"\\{unc}"),
+        None => raw.strip_prefix(r"\\?\").unwrap_or(raw).to_string(),
+    };
+    let bytes = raw.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        let drive = char::from(bytes[0].to_ascii_uppercase());
+        return PathBuf::from(format!("{drive}{}", raw[1..].replace('/', "\\")));
+    }
+    if raw.starts_with(r"\\") {
+        return PathBuf::from(raw.replace('/', "\\"));
+    }
+    PathBuf::from(raw)
+}
+
+/// Key identifying a project manifest in a finder's project map
+///
+/// Windows and macOS file systems are case-insensitive by default, so a manifest reached as
+/// `Packages/App/package.json` from the filesystem and `packages/app/package.json` from the git
+/// index is the same project; there the key is lower-cased so it is registered once. Elsewhere
+/// the key is the [`normalize_path`] form.
+#[must_use]
+pub fn project_key(path: &Path) -> PathBuf {
+    fold_case(
+        &normalize_path(path),
+        cfg!(any(windows, target_os = "macos")),
+    )
+}
+
+fn fold_case(path: &Path, case_insensitive: bool) -> PathBuf {
+    match path.to_str() {
+        Some(raw) if case_insensitive => PathBuf::from(raw.to_lowercase()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Platform-independent key for a relative path, using `/` as the separator
+///
+/// Changepack logs, config keys, and `--project` arguments are matched on this form, so entries
+/// written on Windows apply on other platforms and vice versa.
+#[must_use]
+pub fn path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r"\\?\C:\repo\packages", r"C:\repo\packages")]
+    #[case(r"c:\repo\packages", r"C:\repo\packages")]
+    #[case("c:/repo/packages", r"C:\repo\packages")]
+    #[case(r"\\?\UNC\server\share\repo", r"\\server\share\repo")]
+    #[case("/home/user/repo", "/home/user/repo")]
+    #[case("packages/a", "packages/a")]
+    fn test_normalize_path(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(normalize_path(Path::new(input)), PathBuf::from(expected));
+    }
+
+    #[rstest]
+    #[case(r"packages\a\package.json", "packages/a/package.json")]
+    #[case("packages/a/package.json", "packages/a/package.json")]
+    #[case("", "")]
+    fn test_path_key(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(path_key(Path::new(input)), expected);
+    }
+
+    #[test]
+    fn test_fold_case() {
+        let path = Path::new("/Repo/Packages/App/package.json");
+        assert_eq!(
+            fold_case(path, true),
+            PathBuf::from("/repo/packages/app/package.json")
+        );
+        assert_eq!(fold_case(path, false), path);
+    }
+
+    #[test]
+    fn test_project_key_ignores_verbatim_prefix_and_drive_case() {
+        assert_eq!(
+            project_key(Path::new(r"\\?\C:\Repo\package.json")),
+            project_key(Path::new(r"c:\Repo\package.json"))
+        );
+    }
+}
@@ -16,22 +16,18 @@ pub trait Package: std::fmt::Debug + Send + Sync {
     fn relative_path(&self) -> &Path;
     /// # Errors
     /// Returns error if the version update operation fails.
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()>;
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()>;
     /// # Errors
     /// Returns error if the parent path cannot be determined.
-    ///
-    /// Excluded from coverage: tarpaulin mis-attributes the multi-line
-    /// `&&`-condition's first line under normal rustfmt despite both
-    /// branches being exercised by `test_check_changed_*`. The function
-    /// is fully covered by its tests; the gap is a reporting artifact.
-    #[cfg(not(tarpaulin_include))]
     fn check_changed(&mut self, path: &Path) -> Result<()> {
         if self.is_changed() {
             return Ok(());
         }
-        if !path.to_string_lossy().contains(".changepacks")
-            && path.starts_with(self.path().parent().context("Parent not found")?)
-        {
+        if path.starts_with(self.path().parent().context("Parent not found")?) {
             self.set_changed(true);
         }
         Ok(())
@@ -47,6 +43,24 @@ pub trait Package: std::fmt::Debug + Send + Sync {
     /// Set the package name (used for fallback when name is not found in manifest)
     fn set_name(&mut self, _name: String) {}
 
+    /// Short description from the manifest
+    fn description(&self) -> Option<&str> {
+        None
+    }
+
+    /// Source repository URL from the manifest
+    fn repository_url(&self) -> Option<&str> {
+        None
+    }
+
+    /// License (usually an SPDX expression) from the manifest
+    fn license(&self) -> Option<&str> {
+        None
+    }
+
+    /// Set the descriptive fields read by the finder
+    fn set_metadata(&mut self, _metadata: crate::PackageMetadata) {}
+
     /// Get the default publish command for this package type
     fn default_publish_command(&self) -> String;
 
@@ -69,6 +83,39 @@ pub trait Package: std::fmt::Debug + Send + Sync {
         None
     }
 
+    /// Flag that publishes to a named channel, like npm's `--tag`, if the registry has them
+    ///
+    /// Prerelease versions get it with their channel (see `Config::prerelease_channels`).
+    /// Registries that tell prereleases apart by version alone (crates.io, PyPI, NuGet,
+    /// pub.dev) have none.
+    fn channel_flag(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Git tag that holds `version`, for packages whose version is read from git tags instead
+    /// of a manifest (Go modules)
+    ///
+    /// `update` creates it on the release commit, with or without `--tag`.
+    fn version_tag(&self, _version: &str) -> Option<String> {
+        None
+    }
+
+    /// Registry this package is published to (e.g. "npm", "jsr")
+    fn registry(&self) -> &'static str {
+        self.language().registry()
+    }
+
+    /// Other files that declare this package's version, with the version each declares
+    ///
+    /// Used to flag duplicates that drifted apart, such as a Python `__version__` next to
+    /// pyproject.toml. The manifest at `path` itself is not included.
+    ///
+    /// # Errors
+    /// Returns error if a declaring file exists but cannot be read.
+    async fn version_declarations(&self) -> Result<Vec<(std::path::PathBuf, String)>> {
+        Ok(Vec::new())
+    }
+
     /// Publish the package using the configured command or default
     ///
     /// # Errors
@@ -114,10 +161,17 @@ pub trait Package: std::fmt::Debug + Send + Sync {
 
     /// Get the publish command for this package, checking config first
     fn get_publish_command(&self, config: &Config) -> String {
-        crate::publish::resolve_publish_command(
-            self.relative_path(),
-            self.language(),
-            &self.default_publish_command(),
+        crate::publish::with_prerelease_channel(
+            crate::publish::resolve_publish_command(
+                self.relative_path(),
+                self.name(),
+                self.version(),
+                self.language(),
+                &self.default_publish_command(),
+                config,
+            ),
+            self.version(),
+            self.channel_flag(),
             config,
         )
     }
@@ -127,10 +181,20 @@ pub trait Package: std::fmt::Debug + Send + Sync {
     fn get_dry_run_publish_command(&self, config: &Config) -> Option<String> {
         crate::publish::resolve_dry_run_publish_command(
             self.relative_path(),
+            self.name(),
+            self.version(),
             self.language(),
             self.default_dry_run_publish_command().as_deref(),
             config,
         )
+        .map(|command| {
+            crate::publish::with_prerelease_channel(
+                command,
+                self.version(),
+                self.channel_flag(),
+                config,
+            )
+        })
     }
 }
 
@@ -184,7 +248,11 @@ mod tests {
         fn relative_path(&self) -> &Path {
             &self.relative_path
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
         fn is_changed(&self) -> bool {
@@ -231,16 +299,6 @@ mod tests {
         assert!(package.is_changed());
     }
 
-    #[test]
-    fn test_check_changed_ignores_changepacks() {
-        let mut package = MockPackage::new(Some("test"), "/project/package.json", "package.json");
-
-        package
-            .check_changed(Path::new("/project/.changepacks/change.json"))
-            .unwrap();
-        assert!(!package.is_changed());
-    }
-
     #[test]
     fn test_check_changed_ignores_other_projects() {
         let mut package = MockPackage::new(Some("test"), "/project/package.json", "package.json");
@@ -273,7 +331,7 @@ mod tests {
         let mut publish = HashMap::new();
         publish.insert(
             "packages/core/package.json".to_string(),
-            "custom publish".to_string(),
+            "custom publish".to_string().into(),
         );
         let config = Config {
             publish,
@@ -290,7 +348,7 @@ mod tests {
         let mut publish = HashMap::new();
         publish.insert(
             "node".to_string(),
-            "npm publish --access public".to_string(),
+            "npm publish --access public".to_string().into(),
         );
         let config = Config {
             publish,
@@ -308,7 +366,7 @@ mod tests {
         let package = MockPackage::new(Some("test"), "/project/pyproject.toml", "pyproject.toml")
             .with_language(Language::Python);
         let mut publish = HashMap::new();
-        publish.insert("python".to_string(), "poetry publish".to_string());
+        publish.insert("python".to_string(), "poetry publish".to_string().into());
         let config = Config {
             publish,
             ..Default::default()
@@ -322,7 +380,7 @@ mod tests {
         let package = MockPackage::new(Some("test"), "/project/Cargo.toml", "Cargo.toml")
             .with_language(Language::Rust);
         let mut publish = HashMap::new();
-        publish.insert("rust".to_string(), "cargo publish".to_string());
+        publish.insert("rust".to_string(), "cargo publish".to_string().into());
         let config = Config {
             publish,
             ..Default::default()
@@ -336,7 +394,7 @@ mod tests {
         let package = MockPackage::new(Some("test"), "/project/pubspec.yaml", "pubspec.yaml")
             .with_language(Language::Dart);
         let mut publish = HashMap::new();
-        publish.insert("dart".to_string(), "dart pub publish".to_string());
+        publish.insert("dart".to_string(), "dart pub publish".to_string().into());
         let config = Config {
             publish,
             ..Default::default()
@@ -375,7 +433,7 @@ mod tests {
         } else {
             "exit 1"
         };
-        publish.insert("node".to_string(), fail_cmd.to_string());
+        publish.insert("node".to_string(), fail_cmd.to_string().into());
         let config = Config {
             publish,
             ..Default::default()
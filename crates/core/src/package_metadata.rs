@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+/// Descriptive fields read from a project manifest, shown next to release notes
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    repository_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+}
+
+impl PackageMetadata {
+    pub(crate) const EMPTY: Self = Self {
+        description: None,
+        repository_url: None,
+        license: None,
+    };
+
+    /// Metadata with blank values treated as missing
+    #[must_use]
+    pub fn new(
+        description: Option<String>,
+        repository_url: Option<String>,
+        license: Option<String>,
+    ) -> Self {
+        let present = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            description: present(description),
+            repository_url: present(repository_url),
+            license: present(license),
+        }
+    }
+
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    #[must_use]
+    pub fn repository_url(&self) -> Option<&str> {
+        self.repository_url.as_deref()
+    }
+
+    #[must_use]
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_deref()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.description.is_none() && self.repository_url.is_none() && self.license.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_metadata_new_drops_blank_values() {
+        let metadata =
+            PackageMetadata::new(Some("  A parser  ".to_string()), Some(String::new()), None);
+        assert_eq!(metadata.description(), Some("A parser"));
+        assert_eq!(metadata.repository_url(), None);
+        assert_eq!(metadata.license(), None);
+        assert!(!metadata.is_empty());
+        assert!(PackageMetadata::default().is_empty());
+    }
+
+    #[test]
+    fn test_package_metadata_serialize_skips_missing() {
+        let metadata = PackageMetadata::new(
+            None,
+            Some("https://github.com/org/repo".to_string()),
+            Some("MIT".to_string()),
+        );
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::json!({"repositoryUrl": "https://github.com/org/repo", "license": "MIT"})
+        );
+    }
+}
@@ -57,10 +57,16 @@ impl Project {
 
     /// # Errors
     /// Returns error if the underlying `update_version` call fails.
-    pub async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    pub async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         match self {
-            Self::Workspace(workspace) => workspace.update_version(update_type).await?,
-            Self::Package(package) => package.update_version(update_type).await?,
+            Self::Workspace(workspace) => {
+                workspace.update_version(update_type, prerelease_id).await?
+            }
+            Self::Package(package) => package.update_version(update_type, prerelease_id).await?,
         }
         Ok(())
     }
@@ -83,6 +89,13 @@ impl Project {
         }
     }
 
+    pub fn set_changed(&mut self, changed: bool) {
+        match self {
+            Self::Workspace(workspace) => workspace.set_changed(changed),
+            Self::Package(package) => package.set_changed(changed),
+        }
+    }
+
     #[must_use]
     pub fn dependencies(&self) -> &HashSet<String> {
         match self {
@@ -105,6 +118,44 @@ impl Project {
         }
     }
 
+    /// Description, repository URL and license read from the manifest
+    #[must_use]
+    pub fn metadata(&self) -> crate::PackageMetadata {
+        let (description, repository_url, license) = match self {
+            Self::Workspace(workspace) => (
+                workspace.description(),
+                workspace.repository_url(),
+                workspace.license(),
+            ),
+            Self::Package(package) => (
+                package.description(),
+                package.repository_url(),
+                package.license(),
+            ),
+        };
+        crate::PackageMetadata::new(
+            description.map(str::to_string),
+            repository_url.map(str::to_string),
+            license.map(str::to_string),
+        )
+    }
+
+    pub fn set_metadata(&mut self, metadata: crate::PackageMetadata) {
+        match self {
+            Self::Workspace(workspace) => workspace.set_metadata(metadata),
+            Self::Package(package) => package.set_metadata(metadata),
+        }
+    }
+
+    /// Member globs of a workspace (see `Workspace::member_patterns`); `None` for packages
+    #[must_use]
+    pub fn member_patterns(&self) -> Option<&[String]> {
+        match self {
+            Self::Workspace(workspace) => workspace.member_patterns(),
+            Self::Package(_) => None,
+        }
+    }
+
     #[must_use]
     pub fn language(&self) -> crate::Language {
         match self {
@@ -113,6 +164,34 @@ impl Project {
         }
     }
 
+    #[must_use]
+    pub fn registry(&self) -> &'static str {
+        match self {
+            Self::Workspace(workspace) => workspace.registry(),
+            Self::Package(package) => package.registry(),
+        }
+    }
+
+    /// Git tag that holds `version` (see `Package::version_tag`); `None` for workspaces
+    #[must_use]
+    pub fn version_tag(&self, version: &str) -> Option<String> {
+        match self {
+            Self::Workspace(_) => None,
+            Self::Package(package) => package.version_tag(version),
+        }
+    }
+
+    /// Other files that declare this project's version, with the version each declares
+    ///
+    /// # Errors
+    /// Returns error if a declaring file exists but cannot be read.
+    pub async fn version_declarations(&self) -> Result<Vec<(std::path::PathBuf, String)>> {
+        match self {
+            Self::Workspace(workspace) => workspace.version_declarations().await,
+            Self::Package(package) => package.version_declarations().await,
+        }
+    }
+
     /// # Errors
     /// Returns error if the underlying publish call fails to spawn.
     pub async fn publish(&self, config: &Config) -> Result<crate::publish::PublishOutput> {
@@ -288,7 +367,11 @@ mod tests {
         fn version(&self) -> Option<&str> {
             self.version.as_deref()
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
         fn language(&self) -> Language {
@@ -353,7 +436,11 @@ mod tests {
         fn version(&self) -> Option<&str> {
             self.version.as_deref()
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
         fn language(&self) -> Language {
@@ -435,11 +522,31 @@ mod tests {
         assert_eq!(project.relative_path(), Path::new("Cargo.toml"));
     }
 
+    #[tokio::test]
+    async fn test_project_registry_defaults() {
+        let workspace = Project::Workspace(Box::new(MockWorkspace::new(
+            Some("test"),
+            Some("1.0.0"),
+            Language::Node,
+        )));
+        let package = Project::Package(Box::new(MockPackage::new(
+            Some("test"),
+            Some("1.0.0"),
+            Language::Rust,
+        )));
+        assert_eq!(workspace.registry(), "npm");
+        assert_eq!(package.registry(), "crates.io");
+        assert!(workspace.version_declarations().await.unwrap().is_empty());
+        assert!(package.version_declarations().await.unwrap().is_empty());
+        assert!(workspace.metadata().is_empty());
+        assert!(package.metadata().is_empty());
+    }
+
     #[tokio::test]
     async fn test_project_workspace_update_version() {
         let workspace = MockWorkspace::new(Some("test"), Some("1.0.0"), Language::Node);
         let mut project = Project::Workspace(Box::new(workspace));
-        let result = project.update_version(UpdateType::Minor).await;
+        let result = project.update_version(UpdateType::Minor, None).await;
         assert!(result.is_ok());
     }
 
@@ -447,7 +554,7 @@ mod tests {
     async fn test_project_package_update_version() {
         let package = MockPackage::new(Some("test"), Some("1.0.0"), Language::Rust);
         let mut project = Project::Package(Box::new(package));
-        let result = project.update_version(UpdateType::Patch).await;
+        let result = project.update_version(UpdateType::Patch, None).await;
         assert!(result.is_ok());
     }
 
@@ -483,6 +590,21 @@ mod tests {
         assert!(project.is_changed());
     }
 
+    #[test]
+    fn test_project_set_changed() {
+        let workspace = MockWorkspace::new(Some("test"), Some("1.0.0"), Language::Node);
+        let package = MockPackage::new(Some("test"), Some("1.0.0"), Language::Rust);
+        for mut project in [
+            Project::Workspace(Box::new(workspace)),
+            Project::Package(Box::new(package)),
+        ] {
+            project.set_changed(true);
+            assert!(project.is_changed());
+            project.set_changed(false);
+            assert!(!project.is_changed());
+        }
+    }
+
     #[test]
     fn test_project_workspace_dependencies() {
         let mut workspace = MockWorkspace::new(Some("test"), Some("1.0.0"), Language::Node);
@@ -77,7 +77,11 @@ mod tests {
         fn relative_path(&self) -> &Path {
             &self.relative_path
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
         fn is_changed(&self) -> bool {
@@ -144,7 +148,11 @@ mod tests {
         fn version(&self) -> Option<&str> {
             Some("1.0.0")
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
         fn language(&self) -> Language {
@@ -1,6 +1,11 @@
-use crate::{Config, Language};
-use anyhow::Result;
+use crate::{
+    Config, Language,
+    config::{PublishSetting, project_entry},
+};
+use anyhow::{Context, Result};
 use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 
 /// Output captured from a publish command execution.
 #[derive(Debug)]
@@ -13,24 +18,26 @@ pub struct PublishOutput {
     pub stderr: String,
 }
 
-/// Resolve the publish command from config, language, or default
+/// Resolve the publish command from config, package name, language, or default
+///
+/// A configured command has its `{path}`, `{name}` and `{version}` placeholders expanded
+/// (see [`expand_publish_template`]).
 #[must_use]
 pub fn resolve_publish_command(
     relative_path: &Path,
+    name: Option<&str>,
+    version: Option<&str>,
     language: Language,
     default_command: &str,
     config: &Config,
 ) -> String {
-    // Check by relative path
-    if let Some(cmd) = config.publish.get(relative_path.to_string_lossy().as_ref()) {
-        return cmd.clone();
-    }
-    // Check by language
-    let lang_key = language.publish_key();
-    if let Some(cmd) = config.publish.get(lang_key) {
-        return cmd.clone();
-    }
-    default_command.to_string()
+    // Check by relative path, then package name, then language
+    project_entry(&config.publish, relative_path, name, language)
+        .and_then(PublishSetting::command)
+        .map_or_else(
+            || default_command.to_string(),
+            |cmd| expand_publish_template(cmd, relative_path, name, version),
+        )
 }
 
 /// Resolve the dry-run publish command from config or fall back to the
@@ -43,30 +50,99 @@ pub fn resolve_publish_command(
 #[must_use]
 pub fn resolve_dry_run_publish_command(
     relative_path: &Path,
+    name: Option<&str>,
+    version: Option<&str>,
     language: Language,
     default_dry_run_command: Option<&str>,
     config: &Config,
 ) -> Option<String> {
-    // 1) Per-project override
-    if let Some(cmd) = config
-        .publish_dry_run
-        .get(relative_path.to_string_lossy().as_ref())
-    {
-        return Some(cmd.clone());
-    }
-    // 2) Per-language override
-    if let Some(cmd) = config.publish_dry_run.get(language.publish_key()) {
-        return Some(cmd.clone());
+    // 1) Per-project, per-package-name or per-language override
+    if let Some(cmd) = project_entry(&config.publish_dry_run, relative_path, name, language) {
+        return Some(expand_publish_template(cmd, relative_path, name, version));
     }
-    // 3) Fall back to the language crate's own default dry-run command
+    // 2) Fall back to the language crate's own default dry-run command
     default_dry_run_command.map(str::to_string)
 }
 
+/// Expand the placeholders of a configured publish command
+///
+/// `{path}` is the project directory relative to the repository root (`.` at the root),
+/// `{name}` the package name and `{version}` its version; unknown values expand to nothing.
+#[must_use]
+pub fn expand_publish_template(
+    command: &str,
+    relative_path: &Path,
+    name: Option<&str>,
+    version: Option<&str>,
+) -> String {
+    let dir = relative_path
+        .parent()
+        .map(crate::path_key)
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or_else(|| ".".to_string());
+    command
+        .replace("{path}", &dir)
+        .replace("{name}", name.unwrap_or_default())
+        .replace("{version}", version.unwrap_or_default())
+}
+
+/// Channel a prerelease `version` is published to
+///
+/// Looks the prerelease identifier (`beta` in `2.0.0-beta.1`) up in `prereleaseChannels`,
+/// falling back to the identifier itself, or `next` when it is numeric (`1.0.0-0`).
+/// Returns `None` for stable versions.
+#[must_use]
+pub fn prerelease_channel(version: &str, config: &Config) -> Option<String> {
+    let version = version
+        .split_once('+')
+        .map_or(version, |(version, _)| version);
+    let (_, prerelease) = version.split_once('-')?;
+    let identifier = prerelease.split('.').next().unwrap_or_default();
+    if let Some(channel) = config.prerelease_channels.get(identifier) {
+        return Some(channel.clone());
+    }
+    if identifier.is_empty() || identifier.chars().all(|c| c.is_ascii_digit()) {
+        Some("next".to_string())
+    } else {
+        Some(identifier.to_string())
+    }
+}
+
+/// Publish a prerelease `version` to its channel by appending `flag <channel>` to `command`
+///
+/// Leaves the command alone for stable versions, registries without channels (`flag` is
+/// `None`), and commands that already pass `flag`.
+#[must_use]
+pub fn with_prerelease_channel(
+    command: String,
+    version: Option<&str>,
+    flag: Option<&str>,
+    config: &Config,
+) -> String {
+    let (Some(version), Some(flag)) = (version, flag) else {
+        return command;
+    };
+    let Some(channel) = prerelease_channel(version, config) else {
+        return command;
+    };
+    let already_set = command.split_whitespace().any(|arg| {
+        arg == flag
+            || arg
+                .strip_prefix(flag)
+                .is_some_and(|rest| rest.starts_with('='))
+    });
+    if already_set {
+        command
+    } else {
+        format!("{command} {flag} {channel}")
+    }
+}
+
 /// Build a platform-specific shell command.
 /// Uses compile-time `#[cfg]` so only the active platform's code is compiled,
 /// eliminating coverage gaps from unreachable platform branches.
 #[cfg(target_os = "windows")]
-fn build_shell_command(command: &str) -> tokio::process::Command {
+pub(crate) fn build_shell_command(command: &str) -> tokio::process::Command {
     let mut c = tokio::process::Command::new("cmd");
     c.arg("/C").arg(command);
     c
@@ -74,20 +150,39 @@ fn build_shell_command(command: &str) -> tokio::process::Command {
 
 /// Build a platform-specific shell command (Unix variant).
 #[cfg(not(target_os = "windows"))]
-fn build_shell_command(command: &str) -> tokio::process::Command {
+pub(crate) fn build_shell_command(command: &str) -> tokio::process::Command {
     let mut c = tokio::process::Command::new("sh");
     c.arg("-c").arg(command);
     c
 }
 
+tokio::task_local! {
+    /// Prefix of the output lines streamed by [`run_publish_command`]
+    static OUTPUT_PREFIX: String;
+}
+
+/// Run `future` with the publish commands it executes printing their output as it arrives,
+/// each line prefixed with `prefix`, instead of only capturing it
+///
+/// Used when several packages publish at once, so interleaved output stays attributable.
+/// The output is still captured in the returned [`PublishOutput`].
+pub async fn stream_publish_output<F: Future>(prefix: String, future: F) -> F::Output {
+    OUTPUT_PREFIX.scope(prefix, future).await
+}
+
 /// Execute a publish command in the given directory and return captured output.
 ///
+/// Inside [`stream_publish_output`] the output is also printed line by line as it arrives.
+///
 /// # Errors
 /// Returns error if the command fails to spawn (e.g., binary not found).
 /// A non-zero exit code is reported via `PublishOutput::success = false`, not as an error.
 pub async fn run_publish_command(command: &str, working_dir: &Path) -> Result<PublishOutput> {
     let mut cmd = build_shell_command(command);
     cmd.current_dir(working_dir);
+    if let Ok(prefix) = OUTPUT_PREFIX.try_with(Clone::clone) {
+        return run_streamed(cmd, &prefix).await;
+    }
     let output = cmd.output().await?;
     // Note: from_utf8_lossy silently replaces invalid UTF-8 with replacement characters.
     // This is acceptable since child processes may produce non-UTF-8 bytes.
@@ -98,6 +193,47 @@ pub async fn run_publish_command(command: &str, working_dir: &Path) -> Result<Pu
     })
 }
 
+/// Spawn `cmd`, printing each stdout and stderr line with `prefix` while capturing both
+async fn run_streamed(mut cmd: tokio::process::Command, prefix: &str) -> Result<PublishOutput> {
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let mut child = cmd.spawn()?;
+    let stdout = child.stdout.take().context("Failed to capture stdout")?;
+    let stderr = child.stderr.take().context("Failed to capture stderr")?;
+    let (stdout, stderr) = tokio::try_join!(
+        forward_lines(stdout, prefix, false),
+        forward_lines(stderr, prefix, true)
+    )?;
+    let status = child.wait().await?;
+    Ok(PublishOutput {
+        success: status.success(),
+        stdout,
+        stderr,
+    })
+}
+
+/// Print every line read from `reader` with `prefix` and return everything read
+async fn forward_lines(
+    reader: impl AsyncRead + Unpin,
+    prefix: &str,
+    to_stderr: bool,
+) -> std::io::Result<String> {
+    let mut reader = BufReader::new(reader);
+    let mut captured = String::new();
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line).await? > 0 {
+        let text = String::from_utf8_lossy(&line);
+        let trimmed = text.trim_end_matches(['\r', '\n']);
+        if to_stderr {
+            eprintln!("{prefix}{trimmed}");
+        } else {
+            println!("{prefix}{trimmed}");
+        }
+        captured.push_str(&text);
+        line.clear();
+    }
+    Ok(captured)
+}
+
 /// Execute a command by argv (no shell) with optional `kill_on_drop`.
 ///
 /// Use this when callers need cross-platform argument passing without shell
@@ -130,14 +266,86 @@ pub async fn run_publish_command_argv(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
     use std::collections::HashMap;
 
+    #[rstest]
+    #[case("1.0.0", None)]
+    #[case("2.0.0-beta.1", Some("beta"))]
+    #[case("2.0.0-rc.2+build.5", Some("next"))]
+    #[case("2.0.0-alpha", Some("alpha"))]
+    #[case("2.0.0-0", Some("next"))]
+    #[case("1.0.0+build", None)]
+    fn test_prerelease_channel(#[case] version: &str, #[case] expected: Option<&str>) {
+        let config = Config {
+            prerelease_channels: HashMap::from([("rc".to_string(), "next".to_string())]),
+            ..Config::default()
+        };
+        assert_eq!(prerelease_channel(version, &config).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "npm publish",
+        Some("2.0.0-beta.1"),
+        Some("--tag"),
+        "npm publish --tag beta"
+    )]
+    #[case("npm publish", Some("2.0.0"), Some("--tag"), "npm publish")]
+    #[case("npm publish", None, Some("--tag"), "npm publish")]
+    #[case("cargo publish", Some("2.0.0-beta.1"), None, "cargo publish")]
+    #[case(
+        "npm publish --tag canary",
+        Some("2.0.0-beta.1"),
+        Some("--tag"),
+        "npm publish --tag canary"
+    )]
+    #[case(
+        "npm publish --tag=canary",
+        Some("2.0.0-beta.1"),
+        Some("--tag"),
+        "npm publish --tag=canary"
+    )]
+    fn test_with_prerelease_channel(
+        #[case] command: &str,
+        #[case] version: Option<&str>,
+        #[case] flag: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            with_prerelease_channel(command.to_string(), version, flag, &Config::default()),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_publish_command_streamed() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = stream_publish_output(
+            "[core] ".to_string(),
+            run_publish_command("echo out && echo err 1>&2", dir.path()),
+        )
+        .await
+        .unwrap();
+        assert!(output.success);
+        assert_eq!(output.stdout.trim_end(), "out");
+        assert_eq!(output.stderr.trim_end(), "err");
+
+        let output = stream_publish_output(
+            "[core] ".to_string(),
+            run_publish_command("exit 3", dir.path()),
+        )
+        .await
+        .unwrap();
+        assert!(!output.success);
+    }
+
     #[test]
     fn test_resolve_publish_command_by_path() {
         let mut publish = HashMap::new();
         publish.insert(
             "packages/core/package.json".to_string(),
-            "custom publish".to_string(),
+            "custom publish".to_string().into(),
         );
         let config = Config {
             publish,
@@ -146,6 +354,8 @@ mod tests {
 
         let result = resolve_publish_command(
             Path::new("packages/core/package.json"),
+            None,
+            None,
             Language::Node,
             "npm publish",
             &config,
@@ -158,7 +368,7 @@ mod tests {
         let mut publish = HashMap::new();
         publish.insert(
             "node".to_string(),
-            "npm publish --access public".to_string(),
+            "npm publish --access public".to_string().into(),
         );
         let config = Config {
             publish,
@@ -167,6 +377,8 @@ mod tests {
 
         let result = resolve_publish_command(
             Path::new("package.json"),
+            None,
+            None,
             Language::Node,
             "npm publish",
             &config,
@@ -180,6 +392,8 @@ mod tests {
 
         let result = resolve_publish_command(
             Path::new("package.json"),
+            None,
+            None,
             Language::Node,
             "npm publish",
             &config,
@@ -187,6 +401,91 @@ mod tests {
         assert_eq!(result, "npm publish");
     }
 
+    #[test]
+    fn test_resolve_publish_command_by_name_with_template() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "publish": {
+                    "node": "npm publish",
+                    "@scope/web": "pnpm --filter {name} publish --tag v{version} # {path}",
+                    "packages/web/package.json": false
+                }
+            }"#,
+        )
+        .unwrap();
+
+        // A name entry wins over the language entry
+        assert_eq!(
+            resolve_publish_command(
+                Path::new("apps/web/package.json"),
+                Some("@scope/web"),
+                Some("1.2.0"),
+                Language::Node,
+                "npm publish",
+                &config,
+            ),
+            "pnpm --filter @scope/web publish --tag v1.2.0 # apps/web"
+        );
+        // A path entry wins over the name entry; `false` keeps the default command
+        assert_eq!(
+            resolve_publish_command(
+                Path::new("packages/web/package.json"),
+                Some("@scope/web"),
+                Some("1.2.0"),
+                Language::Node,
+                "yarn npm publish",
+                &config,
+            ),
+            "yarn npm publish"
+        );
+    }
+
+    #[rstest]
+    #[case("publish {path}", "crates/core/Cargo.toml", "publish crates/core")]
+    #[case("publish {path}", "Cargo.toml", "publish .")]
+    #[case("{name}@{version}", "Cargo.toml", "core@1.0.0")]
+    #[case("publish", "Cargo.toml", "publish")]
+    fn test_expand_publish_template(
+        #[case] command: &str,
+        #[case] relative_path: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            expand_publish_template(
+                command,
+                Path::new(relative_path),
+                Some("core"),
+                Some("1.0.0")
+            ),
+            expected
+        );
+        assert_eq!(
+            expand_publish_template("{name}{version}", Path::new("Cargo.toml"), None, None),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_resolve_dry_run_publish_command_by_name() {
+        let config = Config {
+            publish_dry_run: HashMap::from([(
+                "core".to_string(),
+                "cargo publish -p {name} --dry-run".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        let result = resolve_dry_run_publish_command(
+            Path::new("crates/core/Cargo.toml"),
+            Some("core"),
+            Some("1.0.0"),
+            Language::Rust,
+            None,
+            &config,
+        );
+        assert_eq!(result.as_deref(), Some("cargo publish -p core --dry-run"));
+    }
+
     #[test]
     fn test_resolve_dry_run_publish_command_by_path() {
         // Per-project override wins even when a default is provided.
@@ -202,6 +501,8 @@ mod tests {
 
         let result = resolve_dry_run_publish_command(
             Path::new("packages/core/package.json"),
+            None,
+            None,
             Language::Node,
             Some("npm publish --dry-run"),
             &config,
@@ -221,6 +522,8 @@ mod tests {
 
         let result = resolve_dry_run_publish_command(
             Path::new("package.json"),
+            None,
+            None,
             Language::Node,
             Some("npm publish --dry-run"),
             &config,
@@ -235,6 +538,8 @@ mod tests {
 
         let result = resolve_dry_run_publish_command(
             Path::new("package.json"),
+            None,
+            None,
             Language::Node,
             Some("npm publish --dry-run"),
             &config,
@@ -251,6 +556,8 @@ mod tests {
 
         let result = resolve_dry_run_publish_command(
             Path::new("project.csproj"),
+            None,
+            None,
             Language::CSharp,
             None,
             &config,
@@ -273,6 +580,8 @@ mod tests {
 
         let result = resolve_dry_run_publish_command(
             Path::new("project.csproj"),
+            None,
+            None,
             Language::CSharp,
             None,
             &config,
@@ -292,6 +601,8 @@ mod tests {
 
         let result = resolve_dry_run_publish_command(
             Path::new("project.csproj"),
+            None,
+            None,
             Language::CSharp,
             None,
             &config,
@@ -0,0 +1,194 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// File name of the publish log inside the `.changepacks` directory
+pub const PUBLISH_LOG_FILE: &str = "publish_log.json";
+
+/// Outcome of publishing one package, as recorded in the publish log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishRecord {
+    /// Project name from manifest
+    name: Option<String>,
+    /// File path to the project manifest, relative to the repository root
+    path: PathBuf,
+    /// Version that was published
+    version: Option<String>,
+    /// Registry the package was published to (e.g. "npm", "crates.io")
+    registry: String,
+    /// Whether the publish command succeeded
+    success: bool,
+    /// UTC timestamp of the attempt
+    date: DateTime<Utc>,
+    /// SHA-1 digest of the captured stdout and stderr
+    output_digest: String,
+    /// Error message when the command could not be run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl PublishRecord {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        path: PathBuf,
+        version: Option<String>,
+        registry: String,
+        success: bool,
+        output_digest: String,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            name,
+            path,
+            version,
+            registry,
+            success,
+            date: Utc::now(),
+            output_digest,
+            error,
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    #[must_use]
+    pub fn registry(&self) -> &str {
+        &self.registry
+    }
+
+    #[must_use]
+    pub const fn success(&self) -> bool {
+        self.success
+    }
+
+    #[must_use]
+    pub const fn date(&self) -> &DateTime<Utc> {
+        &self.date
+    }
+
+    #[must_use]
+    pub fn output_digest(&self) -> &str {
+        &self.output_digest
+    }
+
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// Append-only log of publish attempts stored in `.changepacks/publish_log.json`.
+///
+/// Lets interrupted publish runs be resumed and audited; records are kept in
+/// the order the attempts ran (oldest first).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PublishLog {
+    #[serde(default)]
+    records: Vec<PublishRecord>,
+}
+
+impl PublishLog {
+    #[must_use]
+    pub fn records(&self) -> &[PublishRecord] {
+        &self.records
+    }
+
+    pub fn push(&mut self, record: PublishRecord) {
+        self.records.push(record);
+    }
+
+    /// Whether `version` of the package at `path` has a successful publish record
+    #[must_use]
+    pub fn is_published(&self, path: &Path, version: &str) -> bool {
+        self.records.iter().any(|record| {
+            record.success && record.path == path && record.version() == Some(version)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str, version: &str, success: bool) -> PublishRecord {
+        PublishRecord::new(
+            Some("pkg".to_string()),
+            PathBuf::from(path),
+            Some(version.to_string()),
+            "npm".to_string(),
+            success,
+            "digest".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_publish_record_accessors() {
+        let record = PublishRecord::new(
+            Some("pkg".to_string()),
+            PathBuf::from("package.json"),
+            Some("1.0.0".to_string()),
+            "npm".to_string(),
+            false,
+            "abc".to_string(),
+            Some("spawn failed".to_string()),
+        );
+        assert_eq!(record.name(), Some("pkg"));
+        assert_eq!(record.path(), Path::new("package.json"));
+        assert_eq!(record.version(), Some("1.0.0"));
+        assert_eq!(record.registry(), "npm");
+        assert!(!record.success());
+        assert_eq!(record.output_digest(), "abc");
+        assert_eq!(record.error(), Some("spawn failed"));
+        assert!(record.date() <= &Utc::now());
+    }
+
+    #[test]
+    fn test_publish_log_is_published() {
+        let mut log = PublishLog::default();
+        log.push(record("a/package.json", "1.0.0", false));
+        assert!(!log.is_published(Path::new("a/package.json"), "1.0.0"));
+
+        log.push(record("a/package.json", "1.0.0", true));
+        assert!(log.is_published(Path::new("a/package.json"), "1.0.0"));
+        assert!(!log.is_published(Path::new("a/package.json"), "1.1.0"));
+        assert!(!log.is_published(Path::new("b/package.json"), "1.0.0"));
+        assert_eq!(log.records().len(), 2);
+    }
+
+    #[test]
+    fn test_publish_log_serialize_camel_case() {
+        let mut log = PublishLog::default();
+        log.push(record("a/package.json", "1.0.0", true));
+        let json = serde_json::to_value(&log).unwrap();
+        let entry = &json["records"][0];
+        assert_eq!(entry["outputDigest"], "digest");
+        assert_eq!(entry["registry"], "npm");
+        assert!(entry.get("error").is_none());
+    }
+
+    #[test]
+    fn test_publish_log_roundtrip() {
+        let mut log = PublishLog::default();
+        log.push(record("a/package.json", "1.0.0", true));
+        let json = serde_json::to_string(&log).unwrap();
+        let deserialized: PublishLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(log, deserialized);
+    }
+}
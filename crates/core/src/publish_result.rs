@@ -13,6 +13,9 @@ pub struct PublishResult {
     stdout: String,
     /// Captured stderr from the publish command
     stderr: String,
+    /// Number of times the publish command ran, when it ran at all
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attempts: Option<u32>,
 }
 
 impl PublishResult {
@@ -23,8 +26,16 @@ impl PublishResult {
             error,
             stdout,
             stderr,
+            attempts: None,
         }
     }
+
+    /// Record how many times the publish command ran
+    #[must_use]
+    pub const fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = Some(attempts);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -53,6 +64,19 @@ mod tests {
         assert_eq!(result.stderr, "err");
     }
 
+    #[test]
+    fn test_publish_result_attempts() {
+        let result = PublishResult::new(true, None, String::new(), String::new());
+        assert!(
+            serde_json::to_value(&result)
+                .unwrap()
+                .get("attempts")
+                .is_none()
+        );
+        let result = result.with_attempts(3);
+        assert_eq!(serde_json::to_value(&result).unwrap()["attempts"], 3);
+    }
+
     #[test]
     fn test_publish_result_debug() {
         let result = PublishResult::new(true, None, String::new(), String::new());
@@ -0,0 +1,346 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::update_type::UpdateType;
+
+/// File name of the release manifest inside the `.changepacks` directory
+pub const RELEASE_MANIFEST_FILE: &str = "releases.json";
+
+/// A single package bumped as part of a release.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleasedPackage {
+    /// Project name from manifest
+    name: Option<String>,
+    /// File path to the project manifest, relative to the repository root
+    path: PathBuf,
+    /// Version before the update
+    previous_version: Option<String>,
+    /// Version after the update
+    version: String,
+    /// Applied bump type
+    update_type: UpdateType,
+    /// Changepack notes consumed by this release
+    notes: Vec<String>,
+}
+
+impl ReleasedPackage {
+    #[must_use]
+    pub const fn new(
+        name: Option<String>,
+        path: PathBuf,
+        previous_version: Option<String>,
+        version: String,
+        update_type: UpdateType,
+        notes: Vec<String>,
+    ) -> Self {
+        Self {
+            name,
+            path,
+            previous_version,
+            version,
+            update_type,
+            notes,
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn previous_version(&self) -> Option<&str> {
+        self.previous_version.as_deref()
+    }
+
+    #[must_use]
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    #[must_use]
+    pub const fn update_type(&self) -> UpdateType {
+        self.update_type
+    }
+
+    #[must_use]
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Whether `query` names this package, by manifest name or relative manifest path
+    #[must_use]
+    pub fn matches(&self, query: &str) -> bool {
+        self.name.as_deref() == Some(query)
+            || crate::path_key(&self.path) == crate::path_key(std::path::Path::new(query))
+    }
+}
+
+/// One `update` run: when it happened and which packages it bumped.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReleaseRecord {
+    /// UTC timestamp of the update
+    date: DateTime<Utc>,
+    /// Packages bumped in this release
+    packages: Vec<ReleasedPackage>,
+}
+
+impl ReleaseRecord {
+    #[must_use]
+    pub fn new(packages: Vec<ReleasedPackage>) -> Self {
+        Self {
+            date: Utc::now(),
+            packages,
+        }
+    }
+
+    #[must_use]
+    pub const fn date(&self) -> &DateTime<Utc> {
+        &self.date
+    }
+
+    #[must_use]
+    pub fn packages(&self) -> &[ReleasedPackage] {
+        &self.packages
+    }
+}
+
+/// Append-only release history stored in `.changepacks/releases.json`.
+///
+/// Source of truth for tagging, publishing, and release notes; records are kept
+/// in the order the updates ran (oldest first).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReleaseManifest {
+    #[serde(default)]
+    releases: Vec<ReleaseRecord>,
+}
+
+impl ReleaseManifest {
+    #[must_use]
+    pub fn releases(&self) -> &[ReleaseRecord] {
+        &self.releases
+    }
+
+    pub fn push(&mut self, record: ReleaseRecord) {
+        self.releases.push(record);
+    }
+
+    /// Most recent release of the package at `path`, if it was ever released
+    #[must_use]
+    pub fn latest_release(&self, path: &Path) -> Option<&ReleasedPackage> {
+        self.releases
+            .iter()
+            .rev()
+            .flat_map(|record| &record.packages)
+            .find(|pkg| pkg.path == path)
+    }
+
+    /// When the package at `path` was last released, if it was ever released
+    #[must_use]
+    pub fn last_released_at(&self, path: &Path) -> Option<&DateTime<Utc>> {
+        self.releases
+            .iter()
+            .rev()
+            .find(|record| record.packages.iter().any(|pkg| pkg.path == path))
+            .map(ReleaseRecord::date)
+    }
+
+    /// Most recently released version of the package at `path`, if it was ever released
+    #[must_use]
+    pub fn latest_version(&self, path: &Path) -> Option<&str> {
+        self.latest_release(path).map(ReleasedPackage::version)
+    }
+
+    /// Narrow the history to matching packages and versions, dropping releases left empty
+    #[must_use]
+    pub fn filter(&self, package: Option<&str>, version: Option<&str>) -> Self {
+        let releases = self
+            .releases
+            .iter()
+            .filter_map(|record| {
+                let packages: Vec<ReleasedPackage> = record
+                    .packages
+                    .iter()
+                    .filter(|pkg| package.is_none_or(|query| pkg.matches(query)))
+                    .filter(|pkg| version.is_none_or(|v| pkg.version == v.trim_start_matches('v')))
+                    .cloned()
+                    .collect();
+                (!packages.is_empty()).then_some(ReleaseRecord {
+                    date: record.date,
+                    packages,
+                })
+            })
+            .collect();
+        Self { releases }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn released(path: &str, previous: Option<&str>, version: &str) -> ReleasedPackage {
+        ReleasedPackage::new(
+            Some("pkg".to_string()),
+            PathBuf::from(path),
+            previous.map(str::to_string),
+            version.to_string(),
+            UpdateType::Minor,
+            vec!["Add feature".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_released_package_accessors() {
+        let package = released("packages/a/package.json", Some("1.0.0"), "1.1.0");
+        assert_eq!(package.name(), Some("pkg"));
+        assert_eq!(package.path(), Path::new("packages/a/package.json"));
+        assert_eq!(package.previous_version(), Some("1.0.0"));
+        assert_eq!(package.version(), "1.1.0");
+        assert_eq!(package.update_type(), UpdateType::Minor);
+        assert_eq!(package.notes(), ["Add feature".to_string()]);
+    }
+
+    #[test]
+    fn test_release_manifest_push_keeps_order() {
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(ReleaseRecord::new(vec![released(
+            "a/package.json",
+            Some("1.0.0"),
+            "1.1.0",
+        )]));
+        manifest.push(ReleaseRecord::new(vec![released(
+            "a/package.json",
+            Some("1.1.0"),
+            "1.2.0",
+        )]));
+        assert_eq!(manifest.releases().len(), 2);
+        assert_eq!(manifest.releases()[1].packages()[0].version(), "1.2.0");
+    }
+
+    #[test]
+    fn test_release_manifest_serialize_camel_case() {
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(ReleaseRecord::new(vec![released(
+            "a/package.json",
+            None,
+            "0.1.0",
+        )]));
+        let json = serde_json::to_value(&manifest).unwrap();
+        let package = &json["releases"][0]["packages"][0];
+        assert_eq!(package["previousVersion"], serde_json::Value::Null);
+        assert_eq!(package["updateType"], "Minor");
+        assert!(json["releases"][0]["date"].is_string());
+    }
+
+    #[test]
+    fn test_release_manifest_roundtrip() {
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(ReleaseRecord::new(vec![released(
+            "a/package.json",
+            Some("1.0.0"),
+            "1.1.0",
+        )]));
+        let json = serde_json::to_string(&manifest).unwrap();
+        let deserialized: ReleaseManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(manifest, deserialized);
+    }
+
+    #[test]
+    fn test_released_package_matches_name_or_path() {
+        let package = released("packages/a/package.json", Some("1.0.0"), "1.1.0");
+        assert!(package.matches("pkg"));
+        assert!(package.matches("packages/a/package.json"));
+        assert!(package.matches("packages\\a\\package.json"));
+        assert!(!package.matches("other"));
+    }
+
+    #[test]
+    fn test_release_manifest_filter() {
+        let mut manifest = ReleaseManifest::default();
+        manifest.push(ReleaseRecord::new(vec![
+            released("a/package.json", Some("1.0.0"), "1.1.0"),
+            ReleasedPackage::new(
+                Some("other".to_string()),
+                PathBuf::from("b/Cargo.toml"),
+                Some("0.1.0".to_string()),
+                "0.2.0".to_string(),
+                UpdateType::Minor,
+                vec![],
+            ),
+        ]));
+        manifest.push(ReleaseRecord::new(vec![released(
+            "a/package.json",
+            Some("1.1.0"),
+            "1.2.0",
+        )]));
+
+        assert_eq!(manifest.filter(None, None), manifest);
+
+        let by_package = manifest.filter(Some("other"), None);
+        assert_eq!(by_package.releases().len(), 1);
+        assert_eq!(by_package.releases()[0].packages().len(), 1);
+        assert_eq!(by_package.releases()[0].packages()[0].version(), "0.2.0");
+
+        let by_version = manifest.filter(Some("a/package.json"), Some("v1.2.0"));
+        assert_eq!(by_version.releases().len(), 1);
+        assert_eq!(
+            by_version.releases()[0].date(),
+            manifest.releases()[1].date()
+        );
+
+        assert!(
+            manifest
+                .filter(Some("pkg"), Some("9.9.9"))
+                .releases()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_release_manifest_latest_version() {
+        let mut manifest = ReleaseManifest::default();
+        assert_eq!(manifest.latest_version(Path::new("a/package.json")), None);
+        manifest.push(ReleaseRecord::new(vec![
+            released("a/package.json", Some("1.0.0"), "1.1.0"),
+            released("b/package.json", Some("0.1.0"), "0.1.1"),
+        ]));
+        manifest.push(ReleaseRecord::new(vec![released(
+            "a/package.json",
+            Some("1.1.0"),
+            "1.2.0",
+        )]));
+        assert_eq!(
+            manifest.latest_version(Path::new("a/package.json")),
+            Some("1.2.0")
+        );
+        assert_eq!(
+            manifest.latest_version(Path::new("b/package.json")),
+            Some("0.1.1")
+        );
+        assert_eq!(manifest.latest_version(Path::new("c/package.json")), None);
+        assert_eq!(
+            manifest.last_released_at(Path::new("a/package.json")),
+            Some(manifest.releases()[1].date())
+        );
+        assert_eq!(
+            manifest.last_released_at(Path::new("b/package.json")),
+            Some(manifest.releases()[0].date())
+        );
+        assert_eq!(manifest.last_released_at(Path::new("c/package.json")), None);
+    }
+
+    #[test]
+    fn test_release_manifest_deserialize_empty_object() {
+        let manifest: ReleaseManifest = serde_json::from_str("{}").unwrap();
+        assert!(manifest.releases().is_empty());
+    }
+}
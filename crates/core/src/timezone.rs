@@ -0,0 +1,125 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Timezone dates are displayed in: `"UTC"`, `"local"`, or a fixed offset such as `"+09:00"`
+///
+/// Only affects display (history, summaries, changelog headings); changepack logs and the
+/// release manifest keep storing RFC 3339 UTC timestamps.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum Timezone {
+    #[default]
+    Utc,
+    /// The timezone of the machine running changepacks
+    Local,
+    Offset(FixedOffset),
+}
+
+impl Timezone {
+    /// `date` rendered with the strftime `format` in this timezone
+    ///
+    /// Returns `None` when `format` holds an invalid specifier.
+    #[must_use]
+    pub fn format(&self, date: &DateTime<Utc>, format: &str) -> Option<String> {
+        match self {
+            Self::Utc => format_in(date, &Utc, format),
+            Self::Local => format_in(date, &Local, format),
+            Self::Offset(offset) => format_in(date, offset, format),
+        }
+    }
+
+    /// Suffix naming this timezone after a time of day (`UTC`, or the offset like `+09:00`)
+    #[must_use]
+    pub fn label(&self, date: &DateTime<Utc>) -> String {
+        match self {
+            Self::Utc => "UTC".to_string(),
+            _ => self.format(date, "%:z").unwrap_or_default(),
+        }
+    }
+}
+
+/// Write through `fmt::Write` so an invalid specifier is an error instead of a panic
+fn format_in<Tz: TimeZone>(date: &DateTime<Utc>, tz: &Tz, format: &str) -> Option<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut out = String::new();
+    write!(out, "{}", date.with_timezone(tz).format(format)).ok()?;
+    Some(out)
+}
+
+impl TryFrom<String> for Timezone {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.trim() {
+            tz if tz.eq_ignore_ascii_case("utc") || tz == "Z" => Ok(Self::Utc),
+            tz if tz.eq_ignore_ascii_case("local") => Ok(Self::Local),
+            tz => tz.parse().map(Self::Offset).map_err(|_| {
+                format!("invalid timezone '{tz}' (expected \"UTC\", \"local\" or an offset like \"+09:00\")")
+            }),
+        }
+    }
+}
+
+impl From<Timezone> for String {
+    fn from(timezone: Timezone) -> Self {
+        match timezone {
+            Timezone::Utc => "UTC".to_string(),
+            Timezone::Local => "local".to_string(),
+            Timezone::Offset(offset) => offset.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("UTC", Timezone::Utc)]
+    #[case("utc", Timezone::Utc)]
+    #[case("Z", Timezone::Utc)]
+    #[case("local", Timezone::Local)]
+    #[case("+09:00", Timezone::Offset(FixedOffset::east_opt(9 * 3600).unwrap()))]
+    #[case("-05:30", Timezone::Offset(FixedOffset::west_opt(5 * 3600 + 1800).unwrap()))]
+    fn test_timezone_parse(#[case] input: &str, #[case] expected: Timezone) {
+        assert_eq!(Timezone::try_from(input.to_string()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_timezone_parse_invalid() {
+        let err = Timezone::try_from("Mars/Olympus".to_string()).unwrap_err();
+        assert!(err.contains("invalid timezone 'Mars/Olympus'"));
+    }
+
+    #[test]
+    fn test_timezone_format() {
+        let date = DateTime::parse_from_rfc3339("2025-01-01T20:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let seoul = Timezone::try_from("+09:00".to_string()).unwrap();
+        assert_eq!(
+            Timezone::Utc.format(&date, "%Y-%m-%d").as_deref(),
+            Some("2025-01-01")
+        );
+        assert_eq!(
+            seoul.format(&date, "%Y-%m-%d").as_deref(),
+            Some("2025-01-02")
+        );
+        assert_eq!(Timezone::Utc.label(&date), "UTC");
+        assert_eq!(seoul.label(&date), "+09:00");
+        assert!(Timezone::Utc.format(&date, "%Y-%Q").is_none());
+    }
+
+    #[test]
+    fn test_timezone_serde_roundtrip() {
+        for tz in ["UTC", "local", "+09:00"] {
+            let parsed: Timezone = serde_json::from_str(&format!("\"{tz}\"")).unwrap();
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), format!("\"{tz}\""));
+        }
+    }
+}
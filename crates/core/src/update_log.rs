@@ -1,34 +1,62 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::update_type::UpdateType;
 
 /// On-disk changepack log entry with changes map, note, and timestamp.
 ///
-/// Stored in `.changepacks/changepack_log_*.json` files and used to calculate
-/// version updates during the update command.
+/// Stored in `.changepacks/changepack_log_*.json` files (or `.md` files with YAML frontmatter,
+/// see [`ChangePackLog::from_markdown`]) and used to calculate version updates during the
+/// update command.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ChangePackLog {
     /// Map of package file paths to their update types
+    ///
+    /// Paths are kept in `/`-separated form, so logs written on Windows apply elsewhere.
+    #[serde(deserialize_with = "deserialize_changes")]
     changes: HashMap<PathBuf, UpdateType>,
     /// User-provided changelog note for this changepack
     note: String,
     /// UTC timestamp when this changepack was created
     date: DateTime<Utc>,
+    /// Answers to the configured template prompts, keyed by prompt key
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, String>,
+    /// Identifier of the prerelease bumps in `changes` (e.g., `beta` for `1.2.0-beta.3`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    preid: Option<String>,
 }
 
 impl ChangePackLog {
     #[must_use]
     pub fn new(changes: HashMap<PathBuf, UpdateType>, note: String) -> Self {
         Self {
-            changes,
+            changes: normalize_changes(changes),
             note,
             date: Utc::now(),
+            fields: BTreeMap::new(),
+            preid: None,
         }
     }
 
+    #[must_use]
+    pub fn with_fields(mut self, fields: BTreeMap<String, String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    #[must_use]
+    pub fn with_preid(mut self, preid: Option<String>) -> Self {
+        self.preid = preid;
+        self
+    }
+
     #[must_use]
     pub fn changes(&self) -> &HashMap<PathBuf, UpdateType> {
         &self.changes
@@ -38,6 +66,105 @@ impl ChangePackLog {
     pub fn note(&self) -> &str {
         &self.note
     }
+
+    #[must_use]
+    pub const fn date(&self) -> &DateTime<Utc> {
+        &self.date
+    }
+
+    #[must_use]
+    pub const fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    #[must_use]
+    pub fn preid(&self) -> Option<&str> {
+        self.preid.as_deref()
+    }
+}
+
+/// YAML frontmatter of a Markdown changepack log; the notes follow it
+#[derive(Serialize, Deserialize)]
+struct Frontmatter {
+    changes: BTreeMap<PathBuf, UpdateType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    preid: Option<String>,
+}
+
+impl ChangePackLog {
+    /// Parse a Markdown changepack log: `---` fenced YAML frontmatter with `changes` (and
+    /// optionally `date`, `fields` and `preid`), followed by the notes
+    ///
+    /// Hand-written logs may leave out `date`; `default_date` (e.g. the file's modification
+    /// time) is used instead.
+    ///
+    /// # Errors
+    /// Returns error if the frontmatter is missing, unterminated or not valid YAML.
+    pub fn from_markdown(content: &str, default_date: DateTime<Utc>) -> Result<Self> {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let rest = content
+            .strip_prefix("---")
+            .and_then(|rest| {
+                rest.strip_prefix("\r\n")
+                    .or_else(|| rest.strip_prefix('\n'))
+            })
+            .context("Missing `---` frontmatter")?;
+        let mut end = 0;
+        let mut notes = None;
+        for line in rest.split_inclusive('\n') {
+            if line.trim_end() == "---" {
+                notes = Some(&rest[end + line.len()..]);
+                break;
+            }
+            end += line.len();
+        }
+        let notes = notes.context("Unterminated `---` frontmatter")?;
+        let frontmatter: Frontmatter =
+            serde_yaml::from_str(&rest[..end]).context("Invalid frontmatter")?;
+        Ok(Self {
+            changes: normalize_changes(frontmatter.changes.into_iter().collect()),
+            note: notes.trim().to_string(),
+            date: frontmatter.date.unwrap_or(default_date),
+            fields: frontmatter.fields,
+            preid: frontmatter.preid,
+        })
+    }
+
+    /// Render as a Markdown changepack log, the inverse of [`ChangePackLog::from_markdown`]
+    ///
+    /// # Errors
+    /// Returns error if the frontmatter cannot be serialized.
+    pub fn to_markdown(&self) -> Result<String> {
+        let frontmatter = serde_yaml::to_string(&Frontmatter {
+            changes: self
+                .changes
+                .iter()
+                .map(|(path, update_type)| (path.clone(), *update_type))
+                .collect(),
+            date: Some(self.date),
+            fields: self.fields.clone(),
+            preid: self.preid.clone(),
+        })?;
+        Ok(format!("---\n{frontmatter}---\n\n{}\n", self.note.trim()))
+    }
+}
+
+fn normalize_changes(changes: HashMap<PathBuf, UpdateType>) -> HashMap<PathBuf, UpdateType> {
+    changes
+        .into_iter()
+        .map(|(path, update_type)| (PathBuf::from(crate::path_key(&path)), update_type))
+        .collect()
+}
+
+fn deserialize_changes<'de, D>(deserializer: D) -> Result<HashMap<PathBuf, UpdateType>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    HashMap::deserialize(deserializer).map(normalize_changes)
 }
 
 #[cfg(test)]
@@ -125,6 +252,32 @@ mod tests {
         assert_eq!(deserialized.date, log.date);
     }
 
+    #[test]
+    fn test_changepack_log_fields_roundtrip() {
+        let log = ChangePackLog::new(HashMap::new(), "Note".to_string());
+        let json = serde_json::to_value(&log).unwrap();
+        assert!(json.get("fields").is_none());
+
+        let fields = BTreeMap::from([("issue".to_string(), "ABC-123".to_string())]);
+        let log = log.with_fields(fields.clone());
+        let json = serde_json::to_string(&log).unwrap();
+        let deserialized: ChangePackLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.fields(), &fields);
+    }
+
+    #[test]
+    fn test_changepack_log_preid_roundtrip() {
+        let changes = HashMap::from([(PathBuf::from("package.json"), UpdateType::Prerelease)]);
+        let log = ChangePackLog::new(changes, "Note".to_string());
+        assert!(serde_json::to_value(&log).unwrap().get("preid").is_none());
+
+        let log = log.with_preid(Some("beta".to_string()));
+        let json = serde_json::to_string(&log).unwrap();
+        assert!(json.contains(r#""package.json":"Prerelease""#));
+        let deserialized: ChangePackLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.preid(), Some("beta"));
+    }
+
     #[test]
     fn test_changepack_log_deserialize_from_json() {
         let json = r#"{
@@ -154,4 +307,98 @@ mod tests {
         assert_eq!(log.note(), "Ship feature and fix");
         assert_eq!(log.date, expected_date);
     }
+
+    #[test]
+    fn test_changepack_log_normalizes_windows_separators() {
+        let json = r#"{
+            "changes": {"packages\\foo\\package.json": "Patch"},
+            "note": "Fix",
+            "date": "2025-01-01T00:00:00Z"
+        }"#;
+        let log: ChangePackLog = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            log.changes()
+                .get(&PathBuf::from("packages/foo/package.json")),
+            Some(&UpdateType::Patch)
+        );
+
+        let log = ChangePackLog::new(
+            HashMap::from([(PathBuf::from(r"crates\core\Cargo.toml"), UpdateType::Minor)]),
+            "Add".to_string(),
+        );
+        assert!(
+            log.changes()
+                .contains_key(&PathBuf::from("crates/core/Cargo.toml"))
+        );
+    }
+
+    #[test]
+    fn test_changepack_log_markdown_roundtrip() {
+        let changes = HashMap::from([
+            (
+                PathBuf::from("packages/foo/package.json"),
+                UpdateType::Minor,
+            ),
+            (
+                PathBuf::from("crates/core/Cargo.toml"),
+                UpdateType::Prerelease,
+            ),
+        ]);
+        let log = ChangePackLog::new(changes, "- Add X\n- Fix Y".to_string())
+            .with_fields(BTreeMap::from([("issue".to_string(), "ABC-1".to_string())]))
+            .with_preid(Some("beta".to_string()));
+
+        let markdown = log.to_markdown().unwrap();
+        assert!(markdown.starts_with(
+            "---\nchanges:\n  crates/core/Cargo.toml: Prerelease\n  packages/foo/package.json: Minor\n"
+        ));
+        assert!(markdown.ends_with("---\n\n- Add X\n- Fix Y\n"));
+
+        let parsed = ChangePackLog::from_markdown(&markdown, Utc::now()).unwrap();
+        assert_eq!(parsed.changes(), log.changes());
+        assert_eq!(parsed.note(), log.note());
+        assert_eq!(parsed.date(), log.date());
+        assert_eq!(parsed.fields(), log.fields());
+        assert_eq!(parsed.preid(), Some("beta"));
+    }
+
+    #[test]
+    fn test_changepack_log_from_hand_written_markdown() {
+        let markdown = "---\r\nchanges:\r\n  packages\\foo\\package.json: minor\r\n  \"crates/core/Cargo.toml\": patch\r\n---\r\n\r\nAdd a **streaming** parser\r\n";
+        let default_date = DateTime::parse_from_rfc3339("2025-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let log = ChangePackLog::from_markdown(markdown, default_date).unwrap();
+        assert_eq!(
+            log.changes(),
+            &HashMap::from([
+                (
+                    PathBuf::from("packages/foo/package.json"),
+                    UpdateType::Minor
+                ),
+                (PathBuf::from("crates/core/Cargo.toml"), UpdateType::Patch),
+            ])
+        );
+        assert_eq!(log.note(), "Add a **streaming** parser");
+        assert_eq!(log.date(), &default_date);
+    }
+
+    #[test]
+    fn test_changepack_log_from_invalid_markdown() {
+        let err = |content: &str| {
+            ChangePackLog::from_markdown(content, Utc::now())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(err("Just notes"), "Missing `---` frontmatter");
+        assert_eq!(
+            err("---\nchanges: {}\nNotes"),
+            "Unterminated `---` frontmatter"
+        );
+        assert_eq!(err("---\nchanges: [\n---\nNotes"), "Invalid frontmatter");
+        assert_eq!(
+            err("---\nchanges:\n  package.json: huge\n---\n"),
+            "Invalid frontmatter"
+        );
+    }
 }
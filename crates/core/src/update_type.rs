@@ -4,15 +4,22 @@ use serde::{Deserialize, Serialize};
 
 /// Semantic versioning bump types following semver conventions.
 ///
-/// Determines how the version number increments: major (breaking), minor (features), or patch (fixes).
+/// Determines how the version number increments: major (breaking), minor (features), patch
+/// (fixes), or prerelease (the next pre-release build).
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum UpdateType {
     /// Breaking changes: increments X.0.0
+    #[serde(alias = "major")]
     Major = 0,
     /// New features, backward-compatible: increments 0.X.0
+    #[serde(alias = "minor")]
     Minor = 1,
     /// Bug fixes, backward-compatible: increments 0.0.X
+    #[serde(alias = "patch")]
     Patch = 2,
+    /// Pre-release build: increments 0.0.X-id.N, or N of an existing pre-release
+    #[serde(alias = "prerelease")]
+    Prerelease = 3,
 }
 
 impl Display for UpdateType {
@@ -24,6 +31,7 @@ impl Display for UpdateType {
                 Self::Major => "\x1b[1;31mMajor\x1b[0m", // bold red
                 Self::Minor => "\x1b[1;33mMinor\x1b[0m", // bold yellow
                 Self::Patch => "\x1b[1;32mPatch\x1b[0m", // bold green
+                Self::Prerelease => "\x1b[1;36mPrerelease\x1b[0m", // bold cyan
             }
         )
     }
@@ -38,6 +46,7 @@ mod tests {
     #[case(UpdateType::Major, "Major")]
     #[case(UpdateType::Minor, "Minor")]
     #[case(UpdateType::Patch, "Patch")]
+    #[case(UpdateType::Prerelease, "Prerelease")]
     fn test_update_type_display(#[case] update_type: UpdateType, #[case] expected: &str) {
         let display = format!("{}", update_type);
         assert!(display.contains(expected));
@@ -0,0 +1,280 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{package_metadata::PackageMetadata, release_manifest::ReleaseRecord};
+
+/// Command that triggered a webhook notification
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEvent {
+    Update,
+    Publish,
+}
+
+/// Body format POSTed to a webhook target
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookFormat {
+    /// The raw `WebhookPayload`
+    #[default]
+    Json,
+    /// Slack incoming webhook message
+    Slack,
+    /// Discord webhook message
+    Discord,
+}
+
+/// Configured webhook: a bare URL receiving the JSON payload, or a URL with a format
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Webhook {
+    Url(String),
+    Target {
+        url: String,
+        #[serde(default)]
+        format: WebhookFormat,
+    },
+}
+
+impl Webhook {
+    #[must_use]
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Url(url) | Self::Target { url, .. } => url,
+        }
+    }
+
+    #[must_use]
+    pub const fn format(&self) -> WebhookFormat {
+        match self {
+            Self::Url(_) => WebhookFormat::Json,
+            Self::Target { format, .. } => *format,
+        }
+    }
+}
+
+/// Outcome for a single package in a webhook notification
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookStatus {
+    Updated,
+    Published,
+    Failed,
+}
+
+/// A package reported in a webhook notification.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPackage {
+    /// Project name from manifest
+    name: Option<String>,
+    /// File path to the project manifest, relative to the repository root
+    path: PathBuf,
+    /// Version after the update, or the version that was published
+    version: Option<String>,
+    /// Changepack notes for this version
+    notes: Vec<String>,
+    status: WebhookStatus,
+    /// Description, repository URL and license from the manifest
+    #[serde(flatten)]
+    metadata: PackageMetadata,
+}
+
+impl WebhookPackage {
+    #[must_use]
+    pub const fn new(
+        name: Option<String>,
+        path: PathBuf,
+        version: Option<String>,
+        notes: Vec<String>,
+        status: WebhookStatus,
+    ) -> Self {
+        Self {
+            name,
+            path,
+            version,
+            notes,
+            status,
+            metadata: PackageMetadata::EMPTY,
+        }
+    }
+
+    /// Attach the package's manifest metadata
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: PackageMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    #[must_use]
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    #[must_use]
+    pub const fn status(&self) -> WebhookStatus {
+        self.status
+    }
+
+    #[must_use]
+    pub const fn metadata(&self) -> &PackageMetadata {
+        &self.metadata
+    }
+}
+
+/// JSON body POSTed to each configured webhook after `update` or `publish`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    event: WebhookEvent,
+    /// UTC timestamp when the payload was created
+    date: DateTime<Utc>,
+    packages: Vec<WebhookPackage>,
+}
+
+impl WebhookPayload {
+    #[must_use]
+    pub fn new(event: WebhookEvent, packages: Vec<WebhookPackage>) -> Self {
+        Self {
+            event,
+            date: Utc::now(),
+            packages,
+        }
+    }
+
+    /// Payload for an `update` run, reporting every package bumped in `record`
+    #[must_use]
+    pub fn from_release(record: &ReleaseRecord) -> Self {
+        Self::new(
+            WebhookEvent::Update,
+            record
+                .packages()
+                .iter()
+                .map(|pkg| {
+                    WebhookPackage::new(
+                        pkg.name().map(str::to_string),
+                        pkg.path().to_path_buf(),
+                        Some(pkg.version().to_string()),
+                        pkg.notes().to_vec(),
+                        WebhookStatus::Updated,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Attach manifest metadata to each package, looked up by its relative path
+    #[must_use]
+    pub fn with_package_metadata(mut self, metadata: impl Fn(&Path) -> PackageMetadata) -> Self {
+        for package in &mut self.packages {
+            package.metadata = metadata(&package.path);
+        }
+        self
+    }
+
+    #[must_use]
+    pub const fn event(&self) -> WebhookEvent {
+        self.event
+    }
+
+    #[must_use]
+    pub fn packages(&self) -> &[WebhookPackage] {
+        &self.packages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ReleasedPackage, UpdateType};
+
+    #[test]
+    fn test_webhook_payload_from_release() {
+        let record = ReleaseRecord::new(vec![ReleasedPackage::new(
+            Some("pkg".to_string()),
+            PathBuf::from("packages/a/package.json"),
+            Some("1.0.0".to_string()),
+            "1.1.0".to_string(),
+            UpdateType::Minor,
+            vec!["Add feature".to_string()],
+        )]);
+        let payload = WebhookPayload::from_release(&record);
+        assert_eq!(payload.event(), WebhookEvent::Update);
+        assert_eq!(payload.packages().len(), 1);
+        let package = &payload.packages()[0];
+        assert_eq!(package.name(), Some("pkg"));
+        assert_eq!(package.path(), Path::new("packages/a/package.json"));
+        assert_eq!(package.version(), Some("1.1.0"));
+        assert_eq!(package.notes(), ["Add feature".to_string()]);
+        assert_eq!(package.status(), WebhookStatus::Updated);
+        assert!(package.metadata().is_empty());
+
+        let payload = payload.with_package_metadata(|path| {
+            PackageMetadata::new(
+                Some(format!("Built from {}", path.display())),
+                None,
+                Some("MIT".to_string()),
+            )
+        });
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(
+            json["packages"][0]["description"],
+            "Built from packages/a/package.json"
+        );
+        assert_eq!(json["packages"][0]["license"], "MIT");
+        assert!(json["packages"][0].get("repositoryUrl").is_none());
+    }
+
+    #[test]
+    fn test_webhook_deserialize() {
+        let webhooks: Vec<Webhook> = serde_json::from_str(
+            r#"[
+                "https://example.com/hook",
+                { "url": "https://hooks.slack.com/services/x", "format": "slack" },
+                { "url": "https://example.com/raw" }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(webhooks[0].url(), "https://example.com/hook");
+        assert_eq!(webhooks[0].format(), WebhookFormat::Json);
+        assert_eq!(webhooks[1].url(), "https://hooks.slack.com/services/x");
+        assert_eq!(webhooks[1].format(), WebhookFormat::Slack);
+        assert_eq!(webhooks[2].format(), WebhookFormat::Json);
+    }
+
+    #[test]
+    fn test_webhook_payload_serialize() {
+        let payload = WebhookPayload::new(
+            WebhookEvent::Publish,
+            vec![WebhookPackage::new(
+                None,
+                PathBuf::from("Cargo.toml"),
+                Some("0.2.0".to_string()),
+                vec![],
+                WebhookStatus::Failed,
+            )],
+        );
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["event"], "publish");
+        assert_eq!(json["packages"][0]["status"], "failed");
+        assert_eq!(json["packages"][0]["path"], "Cargo.toml");
+        assert!(json["date"].is_string());
+    }
+}
@@ -16,7 +16,11 @@ pub trait Workspace: std::fmt::Debug + Send + Sync {
     fn version(&self) -> Option<&str>;
     /// # Errors
     /// Returns error if the version update operation fails.
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()>;
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()>;
     fn language(&self) -> Language;
 
     fn dependencies(&self) -> &HashSet<String>;
@@ -25,17 +29,11 @@ pub trait Workspace: std::fmt::Debug + Send + Sync {
     /// # Errors
     /// Returns error if the parent path cannot be determined.
     // Default implementation for check_changed
-    ///
-    /// Excluded from coverage: see `Package::check_changed` for the same
-    /// tarpaulin attribution caveat on the multi-line `&&` condition.
-    #[cfg(not(tarpaulin_include))]
     fn check_changed(&mut self, path: &Path) -> Result<()> {
         if self.is_changed() {
             return Ok(());
         }
-        if !path.to_string_lossy().contains(".changepacks")
-            && path.starts_with(self.path().parent().context("Parent not found")?)
-        {
+        if path.starts_with(self.path().parent().context("Parent not found")?) {
             self.set_changed(true);
         }
         Ok(())
@@ -47,6 +45,33 @@ pub trait Workspace: std::fmt::Debug + Send + Sync {
     /// Set the workspace name (used for fallback when name is not found in manifest)
     fn set_name(&mut self, _name: String) {}
 
+    /// Short description from the manifest
+    fn description(&self) -> Option<&str> {
+        None
+    }
+
+    /// Source repository URL from the manifest
+    fn repository_url(&self) -> Option<&str> {
+        None
+    }
+
+    /// License (usually an SPDX expression) from the manifest
+    fn license(&self) -> Option<&str> {
+        None
+    }
+
+    /// Set the descriptive fields read by the finder
+    fn set_metadata(&mut self, _metadata: crate::PackageMetadata) {}
+
+    /// Member globs declared by the manifest, relative to the workspace directory (e.g.,
+    /// `packages/*`); patterns starting with `!` exclude
+    ///
+    /// `None` when the workspace does not list its members, in which case packages below
+    /// its directory are taken to belong to it.
+    fn member_patterns(&self) -> Option<&[String]> {
+        None
+    }
+
     /// Get the default publish command for this workspace type
     fn default_publish_command(&self) -> String;
 
@@ -57,6 +82,31 @@ pub trait Workspace: std::fmt::Debug + Send + Sync {
     /// via `config.publish_dry_run`.
     fn default_dry_run_publish_command(&self) -> Option<String>;
 
+    /// Flag that publishes to a named channel, like npm's `--tag`, if the registry has them
+    ///
+    /// Prerelease versions get it with their channel (see `Config::prerelease_channels`).
+    /// Registries that tell prereleases apart by version alone (crates.io, PyPI, NuGet,
+    /// pub.dev) have none.
+    fn channel_flag(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Registry this workspace is published to (e.g. "npm", "jsr")
+    fn registry(&self) -> &'static str {
+        self.language().registry()
+    }
+
+    /// Other files that declare this workspace's version, with the version each declares
+    ///
+    /// Used to flag duplicates that drifted apart, such as a Python `__version__` next to
+    /// pyproject.toml. The manifest at `path` itself is not included.
+    ///
+    /// # Errors
+    /// Returns error if a declaring file exists but cannot be read.
+    async fn version_declarations(&self) -> Result<Vec<(std::path::PathBuf, String)>> {
+        Ok(Vec::new())
+    }
+
     /// Publish the workspace using the configured command or default
     ///
     /// # Errors
@@ -102,10 +152,17 @@ pub trait Workspace: std::fmt::Debug + Send + Sync {
 
     /// Get the publish command for this workspace, checking config first
     fn get_publish_command(&self, config: &Config) -> String {
-        crate::publish::resolve_publish_command(
-            self.relative_path(),
-            self.language(),
-            &self.default_publish_command(),
+        crate::publish::with_prerelease_channel(
+            crate::publish::resolve_publish_command(
+                self.relative_path(),
+                self.name(),
+                self.version(),
+                self.language(),
+                &self.default_publish_command(),
+                config,
+            ),
+            self.version(),
+            self.channel_flag(),
             config,
         )
     }
@@ -115,10 +172,20 @@ pub trait Workspace: std::fmt::Debug + Send + Sync {
     fn get_dry_run_publish_command(&self, config: &Config) -> Option<String> {
         crate::publish::resolve_dry_run_publish_command(
             self.relative_path(),
+            self.name(),
+            self.version(),
             self.language(),
             self.default_dry_run_publish_command().as_deref(),
             config,
         )
+        .map(|command| {
+            crate::publish::with_prerelease_channel(
+                command,
+                self.version(),
+                self.channel_flag(),
+                config,
+            )
+        })
     }
 
     #[cfg(not(tarpaulin_include))]
@@ -177,7 +244,11 @@ mod tests {
         fn version(&self) -> Option<&str> {
             self.version.as_deref()
         }
-        async fn update_version(&mut self, _update_type: UpdateType) -> Result<()> {
+        async fn update_version(
+            &mut self,
+            _update_type: UpdateType,
+            _prerelease_id: Option<&str>,
+        ) -> Result<()> {
             Ok(())
         }
         fn language(&self) -> Language {
@@ -228,18 +299,6 @@ mod tests {
         assert!(workspace.is_changed());
     }
 
-    #[test]
-    fn test_check_changed_ignores_changepacks() {
-        let mut workspace =
-            MockWorkspace::new(Some("test"), "/project/package.json", "package.json");
-
-        // Files in .changepacks should be ignored
-        workspace
-            .check_changed(Path::new("/project/.changepacks/change.json"))
-            .unwrap();
-        assert!(!workspace.is_changed());
-    }
-
     #[test]
     fn test_check_changed_ignores_other_projects() {
         let mut workspace =
@@ -262,7 +321,7 @@ mod tests {
         let mut publish = HashMap::new();
         publish.insert(
             "packages/core/package.json".to_string(),
-            "custom publish".to_string(),
+            "custom publish".to_string().into(),
         );
         let config = Config {
             publish,
@@ -279,7 +338,7 @@ mod tests {
         let mut publish = HashMap::new();
         publish.insert(
             "node".to_string(),
-            "npm publish --access public".to_string(),
+            "npm publish --access public".to_string().into(),
         );
         let config = Config {
             publish,
@@ -298,7 +357,7 @@ mod tests {
             MockWorkspace::new(Some("test"), "/project/pyproject.toml", "pyproject.toml")
                 .with_language(Language::Python);
         let mut publish = HashMap::new();
-        publish.insert("python".to_string(), "poetry publish".to_string());
+        publish.insert("python".to_string(), "poetry publish".to_string().into());
         let config = Config {
             publish,
             ..Default::default()
@@ -312,7 +371,7 @@ mod tests {
         let workspace = MockWorkspace::new(Some("test"), "/project/Cargo.toml", "Cargo.toml")
             .with_language(Language::Rust);
         let mut publish = HashMap::new();
-        publish.insert("rust".to_string(), "cargo publish".to_string());
+        publish.insert("rust".to_string(), "cargo publish".to_string().into());
         let config = Config {
             publish,
             ..Default::default()
@@ -326,7 +385,7 @@ mod tests {
         let workspace = MockWorkspace::new(Some("test"), "/project/pubspec.yaml", "pubspec.yaml")
             .with_language(Language::Dart);
         let mut publish = HashMap::new();
-        publish.insert("dart".to_string(), "dart pub publish".to_string());
+        publish.insert("dart".to_string(), "dart pub publish".to_string().into());
         let config = Config {
             publish,
             ..Default::default()
@@ -425,7 +484,7 @@ mod tests {
         } else {
             "exit 1"
         };
-        publish.insert("node".to_string(), fail_cmd.to_string());
+        publish.insert("node".to_string(), fail_cmd.to_string().into());
         let config = Config {
             publish,
             ..Default::default()
@@ -0,0 +1,86 @@
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+
+/// Version attribute file of legacy (non-SDK) .NET projects, relative to the project directory
+pub const ASSEMBLY_INFO: &str = "Properties/AssemblyInfo.cs";
+
+static VERSION_ATTRIBUTE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(\[\s*assembly\s*:\s*(?:System\.Reflection\.)?(Assembly(?:File|Informational)?Version)(?:Attribute)?\s*\(\s*")([^"]*)"#,
+    )
+    .expect("hardcoded regex must compile")
+});
+
+/// `MAJOR.MINOR.PATCH` of the `AssemblyVersion` attribute (or `AssemblyFileVersion`)
+///
+/// The fourth (revision) part of assembly versions has no semver counterpart and is dropped.
+pub fn extract_assembly_version(content: &str) -> Option<String> {
+    let versions: Vec<(String, String)> = VERSION_ATTRIBUTE_PATTERN
+        .captures_iter(content)
+        .map(|caps| (caps[2].to_string(), caps[3].to_string()))
+        .collect();
+    ["AssemblyVersion", "AssemblyFileVersion"]
+        .iter()
+        .find_map(|attribute| versions.iter().find(|(name, _)| name == attribute))
+        .map(|(_, version)| version.split('.').take(3).collect::<Vec<_>>().join("."))
+}
+
+/// Rewrite the version attributes of an `AssemblyInfo.cs` to `new_version`
+///
+/// `AssemblyVersion` and `AssemblyFileVersion` only accept numbers, so they get the version
+/// without prerelease or build suffix, keeping their number of parts (`1.2.3.0` stays four
+/// parts, with the revision reset to 0). `AssemblyInformationalVersion` gets `new_version` as is.
+pub fn update_assembly_info(content: &str, new_version: &str) -> String {
+    let numeric = new_version.split(['-', '+']).next().unwrap_or(new_version);
+    VERSION_ATTRIBUTE_PATTERN
+        .replace_all(content, |caps: &Captures| {
+            let version = if &caps[2] == "AssemblyInformationalVersion" {
+                new_version.to_string()
+            } else {
+                let mut parts: Vec<&str> = numeric.split('.').collect();
+                parts.resize(caps[3].split('.').count().max(3), "0");
+                parts.join(".")
+            };
+            format!("{}{version}", &caps[1])
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASSEMBLY_INFO_CS: &str = r#"using System.Reflection;
+
+[assembly: AssemblyTitle("Legacy")]
+[assembly: AssemblyVersion("1.2.3.0")]
+[assembly: AssemblyFileVersion("1.2.3")]
+[assembly: AssemblyInformationalVersion("1.2.3")]
+"#;
+
+    #[test]
+    fn test_extract_assembly_version() {
+        assert_eq!(
+            extract_assembly_version(ASSEMBLY_INFO_CS).as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(
+            extract_assembly_version(r#"[assembly: AssemblyFileVersionAttribute("2.0.1.7")]"#)
+                .as_deref(),
+            Some("2.0.1")
+        );
+        assert_eq!(
+            extract_assembly_version(r#"[assembly: AssemblyTitle("Legacy")]"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_update_assembly_info() {
+        let updated = update_assembly_info(ASSEMBLY_INFO_CS, "1.3.0-rc.1");
+        assert!(updated.contains(r#"[assembly: AssemblyVersion("1.3.0.0")]"#));
+        assert!(updated.contains(r#"[assembly: AssemblyFileVersion("1.3.0")]"#));
+        assert!(updated.contains(r#"[assembly: AssemblyInformationalVersion("1.3.0-rc.1")]"#));
+        assert!(updated.contains(r#"[assembly: AssemblyTitle("Legacy")]"#));
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use changepacks_core::{Project, ProjectFinder};
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, project_key};
 use quick_xml::Reader;
 use quick_xml::events::Event;
 use std::{
@@ -9,12 +9,17 @@ use std::{
 };
 use tokio::fs::read_to_string;
 
-use crate::{package::CSharpPackage, workspace::CSharpWorkspace};
+use crate::assembly_info::{ASSEMBLY_INFO, extract_assembly_version};
+use crate::solution::solution_projects;
+use crate::{
+    DIRECTORY_BUILD_PROPS, find_props, package::CSharpPackage, workspace::CSharpWorkspace,
+};
 
 #[derive(Debug)]
 pub struct CSharpProjectFinder {
     projects: HashMap<PathBuf, Project>,
     project_files: Vec<&'static str>,
+    assembly_info: bool,
 }
 
 impl Default for CSharpProjectFinder {
@@ -29,9 +34,17 @@ impl CSharpProjectFinder {
         Self {
             projects: HashMap::new(),
             project_files: vec![".csproj"],
+            assembly_info: false,
         }
     }
 
+    /// Version projects without a .csproj `<Version>` through their `AssemblyInfo.cs`
+    #[must_use]
+    pub fn with_assembly_info(mut self, assembly_info: bool) -> Self {
+        self.assembly_info = assembly_info;
+        self
+    }
+
     /// Extract the project name from the .csproj file path (filename without extension)
     fn extract_name_from_path(path: &Path) -> Option<String> {
         path.file_stem()
@@ -55,11 +68,16 @@ fn extract_project_name_from_path(path_str: &str) -> Option<String> {
 
 impl CSharpProjectFinder {
     /// Extract version from .csproj XML content using quick-xml
-    fn extract_version(content: &str) -> Option<String> {
+    pub(crate) fn extract_version(content: &str) -> Option<String> {
+        Self::extract_property(content, "Version")
+    }
+
+    /// Extract the first non-empty `<PropertyGroup>` property named `property`
+    pub(crate) fn extract_property(content: &str, property: &str) -> Option<String> {
         let mut reader = Reader::from_str(content);
         let mut buf = Vec::new();
         let mut in_property_group = false;
-        let mut in_version = false;
+        let mut in_property = false;
 
         loop {
             match reader.read_event_into(&mut buf) {
@@ -67,23 +85,23 @@ impl CSharpProjectFinder {
                     let name = e.local_name();
                     if name.as_ref() == b"PropertyGroup" {
                         in_property_group = true;
-                    } else if in_property_group && name.as_ref() == b"Version" {
-                        in_version = true;
+                    } else if in_property_group && name.as_ref() == property.as_bytes() {
+                        in_property = true;
                     }
                 }
                 Ok(Event::End(e)) => {
                     let name = e.local_name();
                     if name.as_ref() == b"PropertyGroup" {
                         in_property_group = false;
-                    } else if name.as_ref() == b"Version" {
-                        in_version = false;
+                    } else if name.as_ref() == property.as_bytes() {
+                        in_property = false;
                     }
                 }
                 Ok(Event::Text(e)) => {
-                    if in_version && let Ok(text) = e.decode() {
-                        let version = text.trim().to_string();
-                        if !version.is_empty() {
-                            return Some(version);
+                    if in_property && let Ok(text) = e.decode() {
+                        let value = text.trim().to_string();
+                        if !value.is_empty() {
+                            return Some(value);
                         }
                     }
                 }
@@ -95,6 +113,15 @@ impl CSharpProjectFinder {
         None
     }
 
+    /// Description, repository and license of a .csproj (the NuGet package properties)
+    fn extract_metadata(content: &str) -> PackageMetadata {
+        PackageMetadata::new(
+            Self::extract_property(content, "Description"),
+            Self::extract_property(content, "RepositoryUrl"),
+            Self::extract_property(content, "PackageLicenseExpression"),
+        )
+    }
+
     /// Extract `PackageReference` dependencies from .csproj XML content using quick-xml
     ///
     /// Excluded from coverage: marked `#[allow(dead_code)]` because the
@@ -162,22 +189,36 @@ impl CSharpProjectFinder {
         projects
     }
 
-    /// Check if this project is part of a solution (workspace)
-    /// A project is considered a workspace if there's a .sln file in the same directory
-    async fn is_workspace(path: &Path) -> bool {
-        if let Some(parent) = path.parent() {
-            // Check if there's a .sln file in the parent directory
-            if let Ok(mut entries) = tokio::fs::read_dir(parent).await {
-                while let Ok(Some(entry)) = entries.next_entry().await {
-                    if let Some(ext) = entry.path().extension()
-                        && ext == "sln"
-                    {
-                        return true;
-                    }
-                }
-            }
+    /// Add the .sln at `path` as a workspace of the .csproj projects it lists
+    async fn visit_solution(&mut self, path: &Path, relative_path: &Path) -> Result<()> {
+        if self.projects.contains_key(&project_key(path)) {
+            return Ok(());
         }
-        false
+        let content = read_to_string(path).await?;
+
+        // A solution has no version of its own, but may share one through Directory.Build.props
+        let version_props = match find_props(path, DIRECTORY_BUILD_PROPS) {
+            Some(props) => read_to_string(&props)
+                .await
+                .ok()
+                .and_then(|content| Self::extract_version(&content))
+                .map(|version| (props, version)),
+            None => None,
+        };
+        let mut workspace = CSharpWorkspace::new(
+            Self::extract_name_from_path(path),
+            version_props.as_ref().map(|(_, version)| version.clone()),
+            path.to_path_buf(),
+            relative_path.to_path_buf(),
+        )
+        .with_members(solution_projects(&content));
+        if let Some((props, _)) = version_props {
+            workspace = workspace.with_version_props(props);
+        }
+
+        self.projects
+            .insert(project_key(path), Project::Workspace(Box::new(workspace)));
+        Ok(())
     }
 }
 
@@ -200,11 +241,14 @@ impl ProjectFinder for CSharpProjectFinder {
         if path.is_file() {
             let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
+            if extension == "sln" {
+                return self.visit_solution(path, relative_path).await;
+            }
             if extension != "csproj" {
                 return Ok(());
             }
 
-            if self.projects.contains_key(path) {
+            if self.projects.contains_key(&project_key(path)) {
                 return Ok(());
             }
 
@@ -212,37 +256,51 @@ impl ProjectFinder for CSharpProjectFinder {
             let csproj_content = read_to_string(path).await?;
 
             let name = Self::extract_name_from_path(path);
-            let version = Self::extract_version(&csproj_content);
-            let is_workspace = Self::is_workspace(path).await;
-
-            let (path_key, mut project) = if is_workspace {
-                (
-                    path.to_path_buf(),
-                    Project::Workspace(Box::new(CSharpWorkspace::new(
-                        name,
-                        version,
-                        path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
-                )
-            } else {
-                (
-                    path.to_path_buf(),
-                    Project::Package(Box::new(CSharpPackage::new(
-                        name,
-                        version,
-                        path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
-                )
-            };
+            let mut version = Self::extract_version(&csproj_content);
+
+            // Legacy projects keep their version in assembly attributes
+            let mut assembly_info = None;
+            if self.assembly_info
+                && version.is_none()
+                && let Some(file) = path.parent().map(|dir| dir.join(ASSEMBLY_INFO))
+                && let Ok(content) = read_to_string(&file).await
+                && let Some(assembly_version) = extract_assembly_version(&content)
+            {
+                version = Some(assembly_version);
+                assembly_info = Some(file);
+            }
+            // Otherwise the version may be shared through a Directory.Build.props above
+            let mut version_props = None;
+            if version.is_none()
+                && assembly_info.is_none()
+                && let Some(props) = find_props(path, DIRECTORY_BUILD_PROPS)
+                && let Ok(content) = read_to_string(&props).await
+                && let Some(props_version) = Self::extract_version(&content)
+            {
+                version = Some(props_version);
+                version_props = Some(props);
+            }
+
+            let package = CSharpPackage::new(
+                name,
+                version,
+                path.to_path_buf(),
+                relative_path.to_path_buf(),
+            );
+            let mut project = Project::Package(Box::new(match (assembly_info, version_props) {
+                (Some(file), _) => package.with_assembly_info(file),
+                (None, Some(props)) => package.with_version_props(props),
+                (None, None) => package,
+            }));
+
+            project.set_metadata(Self::extract_metadata(&csproj_content));
 
             // Add ProjectReference dependencies (local project references)
             for dep in Self::extract_project_references(&csproj_content) {
                 project.add_dependency(&dep);
             }
 
-            self.projects.insert(path_key, project);
+            self.projects.insert(project_key(path), project);
         }
         Ok(())
     }
@@ -268,6 +326,96 @@ mod tests {
         assert_eq!(finder.projects().len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_visit_legacy_project_with_assembly_info() {
+        let temp_dir = TempDir::new().unwrap();
+        let csproj_path = temp_dir.path().join("Legacy.csproj");
+        let csproj = r#"<Project ToolsVersion="15.0" xmlns="http://schemas.microsoft.com/developer/msbuild/2003">
+  <PropertyGroup>
+    <AssemblyName>Legacy</AssemblyName>
+  </PropertyGroup>
+</Project>
+"#;
+        fs::write(&csproj_path, csproj).unwrap();
+        let assembly_info = temp_dir.path().join(ASSEMBLY_INFO);
+        fs::create_dir_all(assembly_info.parent().unwrap()).unwrap();
+        fs::write(&assembly_info, "[assembly: AssemblyVersion(\"1.2.3.0\")]\n").unwrap();
+
+        let mut finder = CSharpProjectFinder::new();
+        finder
+            .visit(&csproj_path, &PathBuf::from("Legacy.csproj"))
+            .await
+            .unwrap();
+        assert_eq!(finder.projects()[0].version(), None);
+
+        let mut finder = CSharpProjectFinder::new().with_assembly_info(true);
+        finder
+            .visit(&csproj_path, &PathBuf::from("Legacy.csproj"))
+            .await
+            .unwrap();
+        let mut projects = finder.projects_mut();
+        assert_eq!(projects[0].version(), Some("1.2.3"));
+        projects[0]
+            .update_version(changepacks_core::UpdateType::Patch, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&assembly_info).unwrap(),
+            "[assembly: AssemblyVersion(\"1.2.4.0\")]\n"
+        );
+        assert_eq!(fs::read_to_string(&csproj_path).unwrap(), csproj);
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_visit_project_versioned_by_directory_build_props() {
+        let temp_dir = TempDir::new().unwrap();
+        let props = temp_dir.path().join(DIRECTORY_BUILD_PROPS);
+        fs::write(
+            &props,
+            "<Project>\n  <PropertyGroup>\n    <Version>2.1.0</Version>\n  </PropertyGroup>\n</Project>\n",
+        )
+        .unwrap();
+        let csproj = "<Project Sdk=\"Microsoft.NET.Sdk\">\n  <PropertyGroup>\n    <TargetFramework>net8.0</TargetFramework>\n  </PropertyGroup>\n</Project>\n";
+        for name in ["Core", "Api"] {
+            let dir = temp_dir.path().join("src").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(format!("{name}.csproj")), csproj).unwrap();
+        }
+
+        let mut finder = CSharpProjectFinder::new();
+        for name in ["Core", "Api"] {
+            let relative_path = PathBuf::from(format!("src/{name}/{name}.csproj"));
+            finder
+                .visit(&temp_dir.path().join(&relative_path), &relative_path)
+                .await
+                .unwrap();
+        }
+        let mut projects = finder.projects_mut();
+        assert!(
+            projects
+                .iter()
+                .all(|project| project.version() == Some("2.1.0"))
+        );
+
+        projects[0]
+            .update_version(changepacks_core::UpdateType::Minor, None)
+            .await
+            .unwrap();
+        assert_eq!(projects[0].version(), Some("2.2.0"));
+        assert_eq!(
+            fs::read_to_string(&props).unwrap(),
+            "<Project>\n  <PropertyGroup>\n    <Version>2.2.0</Version>\n  </PropertyGroup>\n</Project>\n"
+        );
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("src/Core/Core.csproj")).unwrap(),
+            csproj
+        );
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_visit_package() {
         let temp_dir = TempDir::new().unwrap();
@@ -304,36 +452,131 @@ mod tests {
     #[tokio::test]
     async fn test_visit_workspace_with_sln() {
         let temp_dir = TempDir::new().unwrap();
-        let csproj_path = temp_dir.path().join("TestProject.csproj");
         let sln_path = temp_dir.path().join("TestSolution.sln");
-
         fs::write(
-            &csproj_path,
-            r#"<Project Sdk="Microsoft.NET.Sdk">
-  <PropertyGroup>
-    <Version>1.0.0</Version>
-  </PropertyGroup>
-</Project>
+            &sln_path,
+            r#"Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Core", "src\Core\Core.csproj", "{1D6E0B5A-3C9F-4E0A-8B0E-7F4F3C2A1B01}"
+EndProject
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Api", "src\Api\Api.csproj", "{1D6E0B5A-3C9F-4E0A-8B0E-7F4F3C2A1B02}"
+EndProject
 "#,
         )
         .unwrap();
+        for (name, references) in [
+            ("Core", ""),
+            (
+                "Api",
+                "  <ItemGroup>\n    <ProjectReference Include=\"..\\Core\\Core.csproj\" />\n  </ItemGroup>\n",
+            ),
+            ("Sandbox", ""),
+        ] {
+            let dir = temp_dir.path().join("src").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join(format!("{name}.csproj")),
+                format!("<Project Sdk=\"Microsoft.NET.Sdk\">\n  <PropertyGroup>\n    <Version>1.0.0</Version>\n  </PropertyGroup>\n{references}</Project>\n"),
+            )
+            .unwrap();
+        }
 
-        fs::write(&sln_path, "Microsoft Visual Studio Solution File").unwrap();
+        let mut finder = CSharpProjectFinder::new();
+        for relative_path in [
+            "TestSolution.sln",
+            "src/Core/Core.csproj",
+            "src/Api/Api.csproj",
+            "src/Sandbox/Sandbox.csproj",
+        ] {
+            finder
+                .visit(
+                    &temp_dir.path().join(relative_path),
+                    Path::new(relative_path),
+                )
+                .await
+                .unwrap();
+        }
+
+        let projects = finder.projects();
+        assert_eq!(projects.len(), 4);
+        let project = |name: &str| {
+            *projects
+                .iter()
+                .find(|project| project.name() == Some(name))
+                .unwrap()
+        };
+        let Project::Workspace(workspace) = project("TestSolution") else {
+            panic!("Expected Workspace");
+        };
+        assert_eq!(workspace.version(), None);
+        assert_eq!(
+            workspace.member_patterns(),
+            Some(&["src/Api".to_string(), "src/Core".to_string()][..])
+        );
+        assert!(matches!(project("Core"), Project::Package(_)));
+        assert!(project("Api").dependencies().contains("Core"));
+
+        let membership =
+            |name: &str| match changepacks_utils::workspace_membership(project(name), &projects) {
+                changepacks_utils::Membership::Member(workspace) => {
+                    format!("member of {}", workspace.name().unwrap())
+                }
+                changepacks_utils::Membership::Orphan(workspace) => {
+                    format!("orphan of {}", workspace.name().unwrap())
+                }
+                changepacks_utils::Membership::Standalone => "standalone".to_string(),
+            };
+        assert_eq!(membership("Core"), "member of TestSolution");
+        assert_eq!(membership("Api"), "member of TestSolution");
+        assert_eq!(membership("Sandbox"), "orphan of TestSolution");
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_visit_sln_versioned_by_directory_build_props() {
+        let temp_dir = TempDir::new().unwrap();
+        let sln_path = temp_dir.path().join("App.sln");
+        fs::write(&sln_path, "Microsoft Visual Studio Solution File\n").unwrap();
 
         let mut finder = CSharpProjectFinder::new();
         finder
-            .visit(&csproj_path, &PathBuf::from("TestProject.csproj"))
+            .visit(&sln_path, &PathBuf::from("App.sln"))
             .await
             .unwrap();
+        let mut projects = finder.projects_mut();
+        assert!(
+            projects[0]
+                .update_version(changepacks_core::UpdateType::Patch, None)
+                .await
+                .is_err()
+        );
 
-        assert_eq!(finder.projects().len(), 1);
-        match finder.projects()[0] {
-            Project::Workspace(ws) => {
-                assert_eq!(ws.name(), Some("TestProject"));
-                assert_eq!(ws.version(), Some("1.0.0"));
-            }
-            _ => panic!("Expected Workspace"),
-        }
+        let props = temp_dir.path().join(DIRECTORY_BUILD_PROPS);
+        fs::write(
+            &props,
+            "<Project>\n  <PropertyGroup>\n    <Version>3.0.0</Version>\n  </PropertyGroup>\n</Project>\n",
+        )
+        .unwrap();
+        let mut finder = CSharpProjectFinder::new();
+        finder
+            .visit(&sln_path, &PathBuf::from("App.sln"))
+            .await
+            .unwrap();
+        let mut projects = finder.projects_mut();
+        assert_eq!(projects[0].version(), Some("3.0.0"));
+        projects[0]
+            .update_version(changepacks_core::UpdateType::Patch, None)
+            .await
+            .unwrap();
+        assert!(
+            fs::read_to_string(&props)
+                .unwrap()
+                .contains("<Version>3.0.1</Version>")
+        );
+        assert_eq!(
+            fs::read_to_string(&sln_path).unwrap(),
+            "Microsoft Visual Studio Solution File\n"
+        );
 
         temp_dir.close().unwrap();
     }
@@ -578,6 +821,26 @@ mod tests {
         assert_eq!(CSharpProjectFinder::extract_version(no_version), None);
     }
 
+    #[test]
+    fn test_extract_metadata() {
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <Version>1.2.3</Version>
+    <Description>Core library</Description>
+    <RepositoryUrl>https://github.com/org/repo</RepositoryUrl>
+    <PackageLicenseExpression>MIT</PackageLicenseExpression>
+  </PropertyGroup>
+</Project>"#;
+        let metadata = CSharpProjectFinder::extract_metadata(content);
+        assert_eq!(metadata.description(), Some("Core library"));
+        assert_eq!(
+            metadata.repository_url(),
+            Some("https://github.com/org/repo")
+        );
+        assert_eq!(metadata.license(), Some("MIT"));
+        assert!(CSharpProjectFinder::extract_metadata("<Project />").is_empty());
+    }
+
     #[test]
     fn test_extract_package_references() {
         let content = r#"<Project Sdk="Microsoft.NET.Sdk">
@@ -4,12 +4,34 @@
 //!
 //! Implements project discovery and version management for .csproj XML files. Uses quick-xml
 //! for parsing with format preservation. Supports `MSBuild` project files with version elements
-//! and handles both single projects and multi-project solutions.
+//! and handles both single projects and multi-project solutions, whose `.sln` lists the member
+//! projects. Legacy projects can be versioned through their `AssemblyInfo.cs` attributes
+//! instead.
 
+mod assembly_info;
 mod dry_run;
 pub mod finder;
 pub mod package;
+mod solution;
 pub mod workspace;
 mod xml_utils;
 
 pub use finder::CSharpProjectFinder;
+
+use std::path::{Path, PathBuf};
+
+/// `MSBuild` file whose properties apply to every project below its directory
+pub const DIRECTORY_BUILD_PROPS: &str = "Directory.Build.props";
+
+/// Central package management file whose `<PackageVersion>` items pin dependency versions
+pub const DIRECTORY_PACKAGES_PROPS: &str = "Directory.Packages.props";
+
+/// Nearest `file_name` in the directories above the project at `project_path`, the one
+/// `MSBuild` imports
+pub(crate) fn find_props(project_path: &Path, file_name: &str) -> Option<PathBuf> {
+    project_path
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join(file_name))
+        .find(|props| props.is_file())
+}
@@ -6,12 +6,13 @@ use async_trait::async_trait;
 use changepacks_core::publish::{
     PublishOutput, resolve_dry_run_publish_command, run_publish_command,
 };
-use changepacks_core::{Config, Language, Package, UpdateType};
-use changepacks_utils::next_version;
-use tokio::fs::{read_to_string, write};
+use changepacks_core::{Config, Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
 
+use crate::assembly_info::update_assembly_info;
 use crate::dry_run::run_managed_dry_run;
 use crate::xml_utils::update_version_in_xml;
+use crate::{CSharpProjectFinder, DIRECTORY_BUILD_PROPS, find_props};
 
 #[derive(Debug)]
 pub struct CSharpPackage {
@@ -20,7 +21,10 @@ pub struct CSharpPackage {
     path: PathBuf,
     relative_path: PathBuf,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    assembly_info: Option<PathBuf>,
+    version_props: Option<PathBuf>,
 }
 
 impl CSharpPackage {
@@ -37,9 +41,28 @@ impl CSharpPackage {
             path,
             relative_path,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            assembly_info: None,
+            version_props: None,
         }
     }
+
+    /// Version this project through the attributes of `assembly_info` (an `AssemblyInfo.cs`)
+    /// instead of its .csproj
+    #[must_use]
+    pub fn with_assembly_info(mut self, assembly_info: PathBuf) -> Self {
+        self.assembly_info = Some(assembly_info);
+        self
+    }
+
+    /// Version this project through the `<Version>` of `props` (a `Directory.Build.props`
+    /// shared by the projects below it) instead of its .csproj
+    #[must_use]
+    pub fn with_version_props(mut self, props: PathBuf) -> Self {
+        self.version_props = Some(props);
+        self
+    }
 }
 
 #[async_trait]
@@ -60,16 +83,29 @@ impl Package for CSharpPackage {
         &self.relative_path
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         let current_version = self.version.as_deref().unwrap_or("0.0.0");
-        let new_version = next_version(current_version, update_type)?;
-
-        let csproj_raw = read_to_string(&self.path).await?;
-        let has_version = self.version.is_some();
-
-        let updated_content = update_version_in_xml(&csproj_raw, &new_version, has_version)?;
-
-        write(&self.path, updated_content).await?;
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+
+        if let Some(assembly_info) = &self.assembly_info {
+            let content = read_file(assembly_info).await?;
+            write_file(assembly_info, update_assembly_info(&content, &new_version)).await?;
+        } else if let Some(props) = &self.version_props {
+            let content = read_file(props).await?;
+            write_file(props, update_version_in_xml(&content, &new_version, true)?).await?;
+        } else {
+            let csproj_raw = read_file(&self.path).await?;
+            let has_version = self.version.is_some();
+
+            let updated_content = update_version_in_xml(&csproj_raw, &new_version, has_version)?;
+
+            write_file(&self.path, updated_content).await?;
+        }
         self.version = Some(new_version);
         Ok(())
     }
@@ -82,6 +118,18 @@ impl Package for CSharpPackage {
         self.is_changed
     }
 
+    async fn version_declarations(&self) -> Result<Vec<(PathBuf, String)>> {
+        // MSBuild imports the nearest Directory.Build.props above the project
+        let Some(props) = find_props(&self.path, DIRECTORY_BUILD_PROPS) else {
+            return Ok(Vec::new());
+        };
+        let content = read_file(&props).await?;
+        Ok(CSharpProjectFinder::extract_version(&content)
+            .map(|version| (props, version))
+            .into_iter()
+            .collect())
+    }
+
     fn set_changed(&mut self, changed: bool) {
         self.is_changed = changed;
     }
@@ -90,6 +138,22 @@ impl Package for CSharpPackage {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn default_publish_command(&self) -> String {
         "dotnet pack -c Release && dotnet nuget push".to_string()
     }
@@ -119,9 +183,14 @@ impl Package for CSharpPackage {
             .context("Package directory not found")?;
 
         // 1) Per-project / per-language override wins (existing semantics).
-        if let Some(user_cmd) =
-            resolve_dry_run_publish_command(self.relative_path(), self.language(), None, config)
-        {
+        if let Some(user_cmd) = resolve_dry_run_publish_command(
+            self.relative_path(),
+            self.name(),
+            self.version(),
+            self.language(),
+            None,
+            config,
+        ) {
             return Ok(Some(run_publish_command(&user_cmd, dir).await?));
         }
 
@@ -236,7 +305,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>1.0.1</Version>"));
@@ -266,7 +338,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        package.update_version(UpdateType::Minor).await.unwrap();
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>1.1.0</Version>"));
@@ -296,7 +371,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        package.update_version(UpdateType::Major).await.unwrap();
+        package
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>2.0.0</Version>"));
@@ -326,7 +404,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>1.0.1</Version>"));
@@ -375,4 +456,40 @@ mod tests {
         package.set_name("my-project".to_string());
         assert_eq!(package.name(), Some("my-project"));
     }
+
+    #[tokio::test]
+    async fn test_version_declarations_directory_build_props() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("src/MyLib");
+        fs::create_dir_all(&project_dir).unwrap();
+        let csproj_path = project_dir.join("MyLib.csproj");
+        let package = CSharpPackage::new(
+            Some("MyLib".to_string()),
+            Some("1.2.0".to_string()),
+            csproj_path,
+            PathBuf::from("src/MyLib/MyLib.csproj"),
+        );
+        assert!(package.version_declarations().await.unwrap().is_empty());
+
+        let props = temp_dir.path().join(DIRECTORY_BUILD_PROPS);
+        fs::write(
+            &props,
+            "<Project>\n  <PropertyGroup>\n    <Version>1.1.0</Version>\n  </PropertyGroup>\n</Project>\n",
+        )
+        .unwrap();
+        assert_eq!(
+            package.version_declarations().await.unwrap(),
+            [(props, "1.1.0".to_string())]
+        );
+
+        // The nearest props file wins, even without a version
+        fs::write(
+            temp_dir.path().join("src").join(DIRECTORY_BUILD_PROPS),
+            "<Project />\n",
+        )
+        .unwrap();
+        assert!(package.version_declarations().await.unwrap().is_empty());
+
+        temp_dir.close().unwrap();
+    }
 }
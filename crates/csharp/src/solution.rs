@@ -0,0 +1,84 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Paths of the .csproj projects a .sln lists, relative to the solution directory and with `/`
+/// separators
+///
+/// Each project is one `Project("{type}") = "Name", "src\Core\Core.csproj", "{id}"` line.
+/// Solution folders and other project types (`.vbproj`, `.fsproj`, shared projects) are
+/// skipped, as are projects outside the solution directory.
+pub(crate) fn solution_projects(content: &str) -> Vec<PathBuf> {
+    let mut projects: Vec<PathBuf> = content
+        .lines()
+        .filter_map(|line| {
+            let (_, fields) = line
+                .trim_start()
+                .strip_prefix("Project(")?
+                .split_once('=')?;
+            let path = fields.split(',').nth(1)?.trim().trim_matches('"');
+            let path = PathBuf::from(path.replace('\\', "/"));
+            let is_csproj = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("csproj"));
+            let inside = path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+            (is_csproj && inside).then_some(path)
+        })
+        .collect();
+    projects.sort();
+    projects.dedup();
+    projects
+}
+
+/// Member globs selecting exactly the directories of `projects` (solution-relative .csproj
+/// paths), `""` standing for the solution directory itself
+pub(crate) fn member_patterns(projects: &[PathBuf]) -> Vec<String> {
+    let mut patterns: Vec<String> = projects
+        .iter()
+        .map(|project| {
+            let dir = project.parent().unwrap_or(Path::new(""));
+            glob::Pattern::escape(&dir.to_string_lossy())
+        })
+        .collect();
+    patterns.sort();
+    patterns.dedup();
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solution_projects() {
+        let sln = r#"
+Microsoft Visual Studio Solution File, Format Version 12.00
+# Visual Studio Version 17
+Project("{2150E333-8FDC-42A3-9474-1A3956D46DE8}") = "src", "src", "{8C2F0A4E-1A5B-4B57-9C5E-2C1B9A0B7D11}"
+EndProject
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Core", "src\Core\Core.csproj", "{1D6E0B5A-3C9F-4E0A-8B0E-7F4F3C2A1B01}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Api", "src\Web\Api\Api.csproj", "{1D6E0B5A-3C9F-4E0A-8B0E-7F4F3C2A1B02}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Tool", "Tool.csproj", "{1D6E0B5A-3C9F-4E0A-8B0E-7F4F3C2A1B03}"
+EndProject
+Project("{F2A71F9B-5D33-465A-A702-920D77279786}") = "Script", "scripts\Script.fsproj", "{1D6E0B5A-3C9F-4E0A-8B0E-7F4F3C2A1B04}"
+EndProject
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Shared", "..\shared\Shared.csproj", "{1D6E0B5A-3C9F-4E0A-8B0E-7F4F3C2A1B05}"
+EndProject
+Global
+EndGlobal
+"#;
+        let projects = solution_projects(sln);
+        assert_eq!(
+            projects,
+            [
+                PathBuf::from("Tool.csproj"),
+                PathBuf::from("src/Core/Core.csproj"),
+                PathBuf::from("src/Web/Api/Api.csproj"),
+            ]
+        );
+        assert_eq!(member_patterns(&projects), ["", "src/Core", "src/Web/Api"]);
+        assert!(solution_projects("Microsoft Visual Studio Solution File").is_empty());
+    }
+}
@@ -3,14 +3,16 @@ use async_trait::async_trait;
 use changepacks_core::publish::{
     PublishOutput, resolve_dry_run_publish_command, run_publish_command,
 };
-use changepacks_core::{Config, Language, UpdateType, Workspace};
-use changepacks_utils::next_version;
-use std::collections::HashSet;
+use changepacks_core::{Config, Language, Package, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
 
+use crate::assembly_info::update_assembly_info;
 use crate::dry_run::run_managed_dry_run;
-use crate::xml_utils::update_version_in_xml;
+use crate::solution::member_patterns;
+use crate::xml_utils::{update_package_references_in_xml, update_version_in_xml};
+use crate::{DIRECTORY_PACKAGES_PROPS, find_props};
 
 #[derive(Debug)]
 pub struct CSharpWorkspace {
@@ -19,7 +21,12 @@ pub struct CSharpWorkspace {
     version: Option<String>,
     name: Option<String>,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    assembly_info: Option<PathBuf>,
+    version_props: Option<PathBuf>,
+    members: Vec<PathBuf>,
+    member_patterns: Option<Vec<String>>,
 }
 
 impl CSharpWorkspace {
@@ -36,9 +43,43 @@ impl CSharpWorkspace {
             name,
             version,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            assembly_info: None,
+            version_props: None,
+            members: Vec::new(),
+            member_patterns: None,
         }
     }
+
+    /// Version this project through the attributes of `assembly_info` (an `AssemblyInfo.cs`)
+    /// instead of its .csproj
+    #[must_use]
+    pub fn with_assembly_info(mut self, assembly_info: PathBuf) -> Self {
+        self.assembly_info = Some(assembly_info);
+        self
+    }
+
+    /// Version this project through the `<Version>` of `props` (a `Directory.Build.props`
+    /// shared by the projects below it) instead of its .csproj
+    #[must_use]
+    pub fn with_version_props(mut self, props: PathBuf) -> Self {
+        self.version_props = Some(props);
+        self
+    }
+
+    /// Record the .csproj projects a solution lists (paths relative to the .sln directory)
+    #[must_use]
+    pub fn with_members(mut self, members: Vec<PathBuf>) -> Self {
+        self.member_patterns = Some(member_patterns(&members));
+        self.members = members;
+        self
+    }
+
+    /// Whether this workspace is a .sln rather than a .csproj
+    fn is_solution(&self) -> bool {
+        self.path.extension().is_some_and(|ext| ext == "sln")
+    }
 }
 
 #[async_trait]
@@ -55,18 +96,37 @@ impl Workspace for CSharpWorkspace {
         self.version.as_deref()
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
-        let next_version = next_version(
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let next_version = next_version_with_id(
             self.version.as_ref().unwrap_or(&String::from("0.0.0")),
             update_type,
-        )?;
-
-        let csproj_raw = read_to_string(&self.path).await?;
-        let has_version = self.version.is_some();
-
-        let updated_content = update_version_in_xml(&csproj_raw, &next_version, has_version)?;
-
-        write(&self.path, updated_content).await?;
+            prerelease_id,
+        )
+        .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+
+        if let Some(assembly_info) = &self.assembly_info {
+            let content = read_file(assembly_info).await?;
+            write_file(assembly_info, update_assembly_info(&content, &next_version)).await?;
+        } else if let Some(props) = &self.version_props {
+            let content = read_file(props).await?;
+            write_file(props, update_version_in_xml(&content, &next_version, true)?).await?;
+        } else if self.is_solution() {
+            anyhow::bail!(
+                "{} has no version to bump; set <Version> in a Directory.Build.props next to it",
+                self.path.display()
+            );
+        } else {
+            let csproj_raw = read_file(&self.path).await?;
+            let has_version = self.version.is_some();
+
+            let updated_content = update_version_in_xml(&csproj_raw, &next_version, has_version)?;
+
+            write_file(&self.path, updated_content).await?;
+        }
         self.version = Some(next_version);
         Ok(())
     }
@@ -91,6 +151,22 @@ impl Workspace for CSharpWorkspace {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn default_publish_command(&self) -> String {
         "dotnet pack -c Release && dotnet nuget push".to_string()
     }
@@ -113,9 +189,14 @@ impl Workspace for CSharpWorkspace {
             .parent()
             .context("Workspace directory not found")?;
 
-        if let Some(user_cmd) =
-            resolve_dry_run_publish_command(self.relative_path(), self.language(), None, config)
-        {
+        if let Some(user_cmd) = resolve_dry_run_publish_command(
+            self.relative_path(),
+            self.name(),
+            self.version(),
+            self.language(),
+            None,
+            config,
+        ) {
             return Ok(Some(run_publish_command(&user_cmd, dir).await?));
         }
 
@@ -129,6 +210,44 @@ impl Workspace for CSharpWorkspace {
     fn add_dependency(&mut self, dependency: &str) {
         self.dependencies.insert(dependency.to_string());
     }
+
+    fn member_patterns(&self) -> Option<&[String]> {
+        self.member_patterns.as_deref()
+    }
+
+    async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
+        let versions: HashMap<&str, &str> = packages
+            .iter()
+            .filter(|package| package.language() == Language::CSharp)
+            .filter_map(|package| Some((package.name()?, package.version()?)))
+            .collect();
+        if versions.is_empty() {
+            return Ok(());
+        }
+
+        // A solution pins versions in its member projects, and central package management in
+        // Directory.Packages.props instead
+        let projects = if self.is_solution() {
+            let dir = self.path.parent().unwrap_or(Path::new(""));
+            self.members
+                .iter()
+                .map(|member| dir.join(member))
+                .filter(|project| project.is_file())
+                .collect()
+        } else {
+            vec![self.path.clone()]
+        };
+        for path in projects
+            .into_iter()
+            .chain(find_props(&self.path, DIRECTORY_PACKAGES_PROPS))
+        {
+            let raw = read_file(&path).await?;
+            if let Some(updated_content) = update_package_references_in_xml(&raw, &versions)? {
+                write_file(&path, updated_content).await?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -258,7 +377,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>1.0.1</Version>"));
@@ -288,7 +410,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>0.0.1</Version>"));
@@ -318,7 +443,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        workspace.update_version(UpdateType::Minor).await.unwrap();
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>1.1.0</Version>"));
@@ -348,7 +476,10 @@ mod tests {
             PathBuf::from("Test.csproj"),
         );
 
-        workspace.update_version(UpdateType::Major).await.unwrap();
+        workspace
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&csproj_path).unwrap();
         assert!(content.contains("<Version>2.0.0</Version>"));
@@ -356,6 +487,162 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_update_workspace_dependencies() {
+        use crate::package::CSharpPackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let csproj_path = temp_dir.path().join("App.csproj");
+        fs::write(
+            &csproj_path,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Core" Version="[1.2.0]" />
+  </ItemGroup>
+</Project>
+"#,
+        )
+        .unwrap();
+
+        let workspace = CSharpWorkspace::new(
+            Some("App".to_string()),
+            Some("1.0.0".to_string()),
+            csproj_path.clone(),
+            PathBuf::from("App.csproj"),
+        );
+        let core = CSharpPackage::new(
+            Some("Core".to_string()),
+            Some("1.3.0".to_string()),
+            PathBuf::from("/test/Core/Core.csproj"),
+            PathBuf::from("Core/Core.csproj"),
+        );
+
+        workspace
+            .update_workspace_dependencies(&[&core])
+            .await
+            .unwrap();
+
+        let content = fs::read_to_string(&csproj_path).unwrap();
+        assert!(content.contains(r#"<PackageReference Include="Core" Version="[1.3.0]"/>"#));
+        assert!(content.ends_with("</Project>\n"));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_workspace_dependencies_central_package_versions() {
+        use crate::package::CSharpPackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let packages_props = temp_dir.path().join(DIRECTORY_PACKAGES_PROPS);
+        fs::write(
+            &packages_props,
+            r#"<Project>
+  <ItemGroup>
+    <PackageVersion Include="Core" Version="1.2.0" />
+    <PackageVersion Include="Newtonsoft.Json" Version="13.0.3" />
+  </ItemGroup>
+</Project>
+"#,
+        )
+        .unwrap();
+        let csproj_path = temp_dir.path().join("src/App/App.csproj");
+        fs::create_dir_all(csproj_path.parent().unwrap()).unwrap();
+        let csproj = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Core" />
+  </ItemGroup>
+</Project>
+"#;
+        fs::write(&csproj_path, csproj).unwrap();
+
+        let workspace = CSharpWorkspace::new(
+            Some("App".to_string()),
+            Some("1.0.0".to_string()),
+            csproj_path.clone(),
+            PathBuf::from("src/App/App.csproj"),
+        );
+        let core = CSharpPackage::new(
+            Some("Core".to_string()),
+            Some("1.3.0".to_string()),
+            PathBuf::from("/test/Core/Core.csproj"),
+            PathBuf::from("Core/Core.csproj"),
+        );
+
+        workspace
+            .update_workspace_dependencies(&[&core])
+            .await
+            .unwrap();
+
+        let content = fs::read_to_string(&packages_props).unwrap();
+        assert!(content.contains(r#"<PackageVersion Include="Core" Version="1.3.0"/>"#));
+        assert!(
+            content.contains(r#"<PackageVersion Include="Newtonsoft.Json" Version="13.0.3" />"#)
+        );
+        assert_eq!(fs::read_to_string(&csproj_path).unwrap(), csproj);
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_workspace_dependencies_solution_members() {
+        use crate::package::CSharpPackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let sln_path = temp_dir.path().join("App.sln");
+        fs::write(&sln_path, "Microsoft Visual Studio Solution File\n").unwrap();
+        let api_path = temp_dir.path().join("src/Api/Api.csproj");
+        fs::create_dir_all(api_path.parent().unwrap()).unwrap();
+        fs::write(
+            &api_path,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Core" Version="1.2.0" />
+  </ItemGroup>
+</Project>
+"#,
+        )
+        .unwrap();
+
+        let workspace = CSharpWorkspace::new(
+            Some("App".to_string()),
+            None,
+            sln_path.clone(),
+            PathBuf::from("App.sln"),
+        )
+        .with_members(vec![
+            PathBuf::from("src/Api/Api.csproj"),
+            PathBuf::from("src/Removed/Removed.csproj"),
+        ]);
+        assert_eq!(
+            workspace.member_patterns(),
+            Some(&["src/Api".to_string(), "src/Removed".to_string()][..])
+        );
+        let core = CSharpPackage::new(
+            Some("Core".to_string()),
+            Some("1.3.0".to_string()),
+            PathBuf::from("/test/Core/Core.csproj"),
+            PathBuf::from("Core/Core.csproj"),
+        );
+
+        workspace
+            .update_workspace_dependencies(&[&core])
+            .await
+            .unwrap();
+
+        assert!(
+            fs::read_to_string(&api_path)
+                .unwrap()
+                .contains(r#"<PackageReference Include="Core" Version="1.3.0"/>"#)
+        );
+        assert_eq!(
+            fs::read_to_string(&sln_path).unwrap(),
+            "Microsoft Visual Studio Solution File\n"
+        );
+
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn test_dependencies() {
         let mut workspace = CSharpWorkspace::new(
@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
+use changepacks_utils::rewrite_version_range;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::{Reader, Writer};
+use std::collections::HashMap;
 use std::io::Cursor;
 
 /// Update version in csproj XML content using quick-xml
@@ -103,6 +105,94 @@ pub fn update_version_in_xml(
     String::from_utf8(result).context("Failed to convert XML to UTF-8")
 }
 
+/// Rewrite the `Version` attribute of `<PackageReference>` (and central `<PackageVersion>`)
+/// elements naming a key of `versions`, keeping the requirement's range style (`1.2.3`,
+/// `[1.2.3]`, `[1.2.3,)`)
+///
+/// Returns `None` when no reference needed rewriting, so callers can leave the file as is.
+pub fn update_package_references_in_xml(
+    content: &str,
+    versions: &HashMap<&str, &str>,
+) -> Result<Option<String>> {
+    let mut reader = Reader::from_str(content);
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+
+    let mut buf = Vec::new();
+    let mut updated = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match rewrite_package_reference(&e, versions)? {
+                Some(rewritten) => {
+                    writer.write_event(Event::Start(rewritten))?;
+                    updated = true;
+                }
+                None => writer.write_event(Event::Start(e))?,
+            },
+            Ok(Event::Empty(e)) => match rewrite_package_reference(&e, versions)? {
+                Some(rewritten) => {
+                    writer.write_event(Event::Empty(rewritten))?;
+                    updated = true;
+                }
+                None => writer.write_event(Event::Empty(e))?,
+            },
+            Ok(Event::Eof) => break,
+            Ok(event) => writer.write_event(event)?,
+            Err(e) => return Err(anyhow::anyhow!("XML parsing error: {e}")),
+        }
+        buf.clear();
+    }
+
+    if !updated {
+        return Ok(None);
+    }
+    let result = writer.into_inner().into_inner();
+    String::from_utf8(result)
+        .context("Failed to convert XML to UTF-8")
+        .map(Some)
+}
+
+/// `<PackageReference>` or `<PackageVersion>` with its `Version` rewritten, if it references
+/// a bumped package
+fn rewrite_package_reference(
+    element: &BytesStart,
+    versions: &HashMap<&str, &str>,
+) -> Result<Option<BytesStart<'static>>> {
+    if !matches!(
+        element.local_name().as_ref(),
+        b"PackageReference" | b"PackageVersion"
+    ) {
+        return Ok(None);
+    }
+    let attribute = |key: &str| -> Result<Option<String>> {
+        element
+            .try_get_attribute(key)?
+            .map(|attr| Ok(attr.unescape_value()?.into_owned()))
+            .transpose()
+    };
+    let (Some(include), Some(current)) = (attribute("Include")?, attribute("Version")?) else {
+        return Ok(None);
+    };
+    let Some(rewritten) = versions
+        .get(include.as_str())
+        .and_then(|version| rewrite_version_range(&current, version))
+        .filter(|rewritten| *rewritten != current)
+    else {
+        return Ok(None);
+    };
+
+    let mut updated = BytesStart::new(String::from_utf8(element.name().as_ref().to_vec())?);
+    for attr in element.attributes() {
+        let attr = attr?;
+        if attr.key.as_ref() == b"Version" {
+            updated.push_attribute(("Version", rewritten.as_str()));
+        } else {
+            updated.push_attribute(attr);
+        }
+    }
+    Ok(Some(updated))
+}
+
 /// Detect indentation style from XML content
 pub fn detect_indent(content: &str) -> &'static str {
     for line in content.lines() {
@@ -145,6 +235,46 @@ mod tests {
         assert!(result.contains("<Version>0.0.1</Version>"));
     }
 
+    #[test]
+    fn test_update_package_references_in_xml() {
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <!-- local packages -->
+  <ItemGroup>
+    <PackageReference Include="Core" Version="1.2.0" />
+    <PackageReference Include="Utils" Version="[1.0.0,)" PrivateAssets="all" />
+    <PackageReference Include="Newtonsoft.Json" Version="13.0.1" />
+    <PackageReference Include="Cli" Version="[1.0,2.0)"></PackageReference>
+  </ItemGroup>
+</Project>"#;
+        let versions = HashMap::from([("Core", "1.3.0"), ("Utils", "1.1.0"), ("Cli", "1.5.0")]);
+
+        let result = update_package_references_in_xml(content, &versions)
+            .unwrap()
+            .unwrap();
+        assert!(result.contains(r#"<PackageReference Include="Core" Version="1.3.0"/>"#));
+        assert!(result.contains(
+            r#"<PackageReference Include="Utils" Version="[1.1.0,)" PrivateAssets="all"/>"#
+        ));
+        assert!(result.contains(r#"Include="Newtonsoft.Json" Version="13.0.1" />"#));
+        assert!(result.contains(r#"Include="Cli" Version="[1.0,2.0)">"#));
+        assert!(result.contains("<!-- local packages -->"));
+    }
+
+    #[test]
+    fn test_update_package_references_in_xml_unchanged() {
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Core" Version="1.3.0" />
+  </ItemGroup>
+</Project>"#;
+        let versions = HashMap::from([("Core", "1.3.0"), ("Other", "2.0.0")]);
+        assert!(
+            update_package_references_in_xml(content, &versions)
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_detect_indent_two_spaces() {
         let content = "  <PropertyGroup>";
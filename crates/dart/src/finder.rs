@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Project, ProjectFinder};
+use changepacks_core::{BuildNumberStrategy, PackageMetadata, Project, ProjectFinder, project_key};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
@@ -13,6 +13,7 @@ use crate::{package::DartPackage, workspace::DartWorkspace};
 pub struct DartProjectFinder {
     projects: HashMap<PathBuf, Project>,
     project_files: Vec<&'static str>,
+    build_number: BuildNumberStrategy,
 }
 
 impl Default for DartProjectFinder {
@@ -27,8 +28,16 @@ impl DartProjectFinder {
         Self {
             projects: HashMap::new(),
             project_files: vec!["pubspec.yaml"],
+            build_number: BuildNumberStrategy::default(),
         }
     }
+
+    /// Set what bumps do to the `+build` number of discovered versions
+    #[must_use]
+    pub fn with_build_number(mut self, build_number: BuildNumberStrategy) -> Self {
+        self.build_number = build_number;
+        self
+    }
 }
 
 #[async_trait]
@@ -55,7 +64,7 @@ impl ProjectFinder for DartProjectFinder {
                     .context(format!("File name not found - {}", path.display()))?,
             )
         {
-            if self.projects.contains_key(path) {
+            if self.projects.contains_key(&project_key(path)) {
                 return Ok(());
             }
             // read pubspec.yaml
@@ -79,12 +88,15 @@ impl ProjectFinder for DartProjectFinder {
                     .map(std::string::ToString::to_string);
                 (
                     path.to_path_buf(),
-                    Project::Workspace(Box::new(DartWorkspace::new(
-                        name,
-                        version,
-                        path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
+                    Project::Workspace(Box::new(
+                        DartWorkspace::new(
+                            name,
+                            version,
+                            path.to_path_buf(),
+                            relative_path.to_path_buf(),
+                        )
+                        .with_build_number(self.build_number),
+                    )),
                 )
             } else {
                 let version = pubspec["version"]
@@ -96,15 +108,26 @@ impl ProjectFinder for DartProjectFinder {
 
                 (
                     path.to_path_buf(),
-                    Project::Package(Box::new(DartPackage::new(
-                        name,
-                        version,
-                        path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
+                    Project::Package(Box::new(
+                        DartPackage::new(
+                            name,
+                            version,
+                            path.to_path_buf(),
+                            relative_path.to_path_buf(),
+                        )
+                        .with_build_number(self.build_number),
+                    )),
                 )
             };
 
+            let field = |key: &str| pubspec[key].as_str().map(str::to_string);
+            // pubspec.yaml has no license field; pub.dev reads the LICENSE file
+            project.set_metadata(PackageMetadata::new(
+                field("description"),
+                field("repository"),
+                None,
+            ));
+
             // read dependencies section
             if let Some(dependencies) = pubspec.get("dependencies").and_then(|d| d.as_mapping()) {
                 for (dep_name, _) in dependencies {
@@ -113,7 +136,7 @@ impl ProjectFinder for DartProjectFinder {
                     }
                 }
             }
-            self.projects.insert(path, project);
+            self.projects.insert(project_key(&path), project);
         }
         Ok(())
     }
@@ -169,6 +192,33 @@ version: 1.0.0
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_visit_package_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let pubspec_path = temp_dir.path().join("pubspec.yaml");
+        fs::write(
+            &pubspec_path,
+            "name: test_package\nversion: 1.0.0\ndescription: A test package\nrepository: https://github.com/org/repo\n",
+        )
+        .unwrap();
+
+        let mut finder = DartProjectFinder::new();
+        finder
+            .visit(&pubspec_path, &PathBuf::from("pubspec.yaml"))
+            .await
+            .unwrap();
+
+        let metadata = finder.projects()[0].metadata();
+        assert_eq!(metadata.description(), Some("A test package"));
+        assert_eq!(
+            metadata.repository_url(),
+            Some("https://github.com/org/repo")
+        );
+        assert_eq!(metadata.license(), None);
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_visit_workspace_with_workspace_field() {
         let temp_dir = TempDir::new().unwrap();
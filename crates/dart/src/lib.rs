@@ -4,10 +4,13 @@
 //!
 //! Implements project discovery and version management for pubspec.yaml files. Parses YAML
 //! using the `serde_yaml` crate while maintaining formatting. Supports both single packages
-//! and workspace configurations with pub as the package manager.
+//! and workspace configurations with pub as the package manager. Flutter build numbers
+//! (`1.2.3+45`) are bumped along with the version.
 
 pub mod finder;
 pub mod package;
+pub mod version;
 pub mod workspace;
 
 pub use finder::DartProjectFinder;
+pub use version::next_pubspec_version;
@@ -3,9 +3,10 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, Package, UpdateType};
-use changepacks_utils::next_version;
-use tokio::fs::{read_to_string, write};
+use changepacks_core::{BuildNumberStrategy, Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::{read_file, write_file};
+
+use crate::next_pubspec_version;
 
 #[derive(Debug)]
 pub struct DartPackage {
@@ -14,7 +15,9 @@ pub struct DartPackage {
     path: PathBuf,
     relative_path: PathBuf,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    build_number: BuildNumberStrategy,
 }
 
 impl DartPackage {
@@ -31,9 +34,18 @@ impl DartPackage {
             path,
             relative_path,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            build_number: BuildNumberStrategy::default(),
         }
     }
+
+    /// Set what bumps do to the `+build` number of the version
+    #[must_use]
+    pub fn with_build_number(mut self, build_number: BuildNumberStrategy) -> Self {
+        self.build_number = build_number;
+        self
+    }
 }
 
 #[async_trait]
@@ -54,12 +66,22 @@ impl Package for DartPackage {
         &self.relative_path
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         let current_version = self.version.as_deref().unwrap_or("0.0.0");
-        let new_version = next_version(current_version, update_type)?;
+        let new_version = next_pubspec_version(
+            current_version,
+            update_type,
+            prerelease_id,
+            self.build_number,
+        )
+        .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let pubspec_yaml_raw = read_to_string(&self.path).await?;
-        write(
+        let pubspec_yaml_raw = read_file(&self.path).await?;
+        write_file(
             &self.path,
             format!(
                 "{}{}",
@@ -101,6 +123,22 @@ impl Package for DartPackage {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn default_publish_command(&self) -> String {
         "dart pub publish".to_string()
     }
@@ -205,7 +243,10 @@ version: 1.0.0
             PathBuf::from("pubspec.yaml"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 1.0.1"));
@@ -213,6 +254,37 @@ version: 1.0.0
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_update_version_flutter_build_number() {
+        let temp_dir = TempDir::new().unwrap();
+        let pubspec_path = temp_dir.path().join("pubspec.yaml");
+        fs::write(&pubspec_path, "name: app\nversion: 1.2.3+45\n").unwrap();
+
+        let mut package = DartPackage::new(
+            Some("app".to_string()),
+            Some("1.2.3+45".to_string()),
+            pubspec_path.clone(),
+            PathBuf::from("pubspec.yaml"),
+        );
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&pubspec_path).unwrap(),
+            "name: app\nversion: 1.3.0+46\n"
+        );
+
+        let mut package = package.with_build_number(BuildNumberStrategy::Preserve);
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
+        assert_eq!(package.version(), Some("1.3.1+46"));
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_update_version_minor() {
         let temp_dir = TempDir::new().unwrap();
@@ -232,7 +304,10 @@ version: 1.0.0
             PathBuf::from("pubspec.yaml"),
         );
 
-        package.update_version(UpdateType::Minor).await.unwrap();
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 1.1.0"));
@@ -259,7 +334,10 @@ version: 1.0.0
             PathBuf::from("pubspec.yaml"),
         );
 
-        package.update_version(UpdateType::Major).await.unwrap();
+        package
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 2.0.0"));
@@ -286,7 +364,10 @@ dependencies:
             PathBuf::from("pubspec.yaml"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 1.0.1"));
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use changepacks_core::{BuildNumberStrategy, UpdateType};
+use changepacks_utils::next_version_with_id;
+
+/// Next pubspec version, handling the `+build` suffix Flutter uses as its build number
+///
+/// The semver part is bumped by `update_type`. A numeric build number (`1.2.3+45`) is
+/// incremented or kept per `build_number`; other build metadata is always kept.
+///
+/// # Errors
+/// Returns error if the semver part is not a valid version.
+pub fn next_pubspec_version(
+    version: &str,
+    update_type: UpdateType,
+    prerelease_id: Option<&str>,
+    build_number: BuildNumberStrategy,
+) -> Result<String> {
+    let Some((core, build)) = version.split_once('+') else {
+        return next_version_with_id(version, update_type, prerelease_id);
+    };
+    let next_core = next_version_with_id(core, update_type, prerelease_id)?;
+    let next_build = match build_number {
+        BuildNumberStrategy::Increment => build
+            .parse::<u64>()
+            .map_or_else(|_| build.to_string(), |number| (number + 1).to_string()),
+        BuildNumberStrategy::Preserve => build.to_string(),
+    };
+    if next_build.is_empty() {
+        // Let next_version report the empty build metadata
+        return next_version_with_id(version, update_type, prerelease_id);
+    }
+    Ok(format!("{next_core}+{next_build}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "1.2.3+45",
+        UpdateType::Patch,
+        BuildNumberStrategy::Increment,
+        "1.2.4+46"
+    )]
+    #[case(
+        "1.2.3+45",
+        UpdateType::Minor,
+        BuildNumberStrategy::Increment,
+        "1.3.0+46"
+    )]
+    #[case(
+        "1.2.3+45",
+        UpdateType::Major,
+        BuildNumberStrategy::Preserve,
+        "2.0.0+45"
+    )]
+    #[case(
+        "1.2.3+ci.7",
+        UpdateType::Patch,
+        BuildNumberStrategy::Increment,
+        "1.2.4+ci.7"
+    )]
+    #[case(
+        "1.0.0-beta.1+9",
+        UpdateType::Patch,
+        BuildNumberStrategy::Increment,
+        "1.0.0+10"
+    )]
+    #[case("1.2.3", UpdateType::Patch, BuildNumberStrategy::Increment, "1.2.4")]
+    fn test_next_pubspec_version(
+        #[case] version: &str,
+        #[case] update_type: UpdateType,
+        #[case] build_number: BuildNumberStrategy,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            next_pubspec_version(version, update_type, None, build_number).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_next_pubspec_version_invalid() {
+        for version in ["1.2.3+", "x.y+4"] {
+            assert!(
+                next_pubspec_version(
+                    version,
+                    UpdateType::Patch,
+                    None,
+                    BuildNumberStrategy::Increment
+                )
+                .is_err()
+            );
+        }
+    }
+}
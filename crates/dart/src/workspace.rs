@@ -1,10 +1,13 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, UpdateType, Workspace};
-use changepacks_utils::next_version;
+use changepacks_core::{
+    BuildNumberStrategy, Language, Package, PackageMetadata, UpdateType, Workspace,
+};
+use changepacks_utils::{read_file, rewrite_version_range, write_file};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
+
+use crate::next_pubspec_version;
 
 #[derive(Debug)]
 pub struct DartWorkspace {
@@ -13,7 +16,9 @@ pub struct DartWorkspace {
     version: Option<String>,
     name: Option<String>,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    build_number: BuildNumberStrategy,
 }
 
 impl DartWorkspace {
@@ -30,9 +35,18 @@ impl DartWorkspace {
             name,
             version,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            build_number: BuildNumberStrategy::default(),
         }
     }
+
+    /// Set what bumps do to the `+build` number of the version
+    #[must_use]
+    pub fn with_build_number(mut self, build_number: BuildNumberStrategy) -> Self {
+        self.build_number = build_number;
+        self
+    }
 }
 
 #[async_trait]
@@ -49,15 +63,22 @@ impl Workspace for DartWorkspace {
         self.version.as_deref()
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
-        let next_version = next_version(
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let next_version = next_pubspec_version(
             self.version.as_ref().unwrap_or(&String::from("0.0.0")),
             update_type,
-        )?;
+            prerelease_id,
+            self.build_number,
+        )
+        .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let pubspec_yaml_raw = read_to_string(&self.path).await?;
+        let pubspec_yaml_raw = read_file(&self.path).await?;
 
-        write(
+        write_file(
             &self.path,
             format!(
                 "{}{}",
@@ -113,6 +134,22 @@ impl Workspace for DartWorkspace {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn default_publish_command(&self) -> String {
         "dart pub publish".to_string()
     }
@@ -128,6 +165,67 @@ impl Workspace for DartWorkspace {
     fn add_dependency(&mut self, dependency: &str) {
         self.dependencies.insert(dependency.to_string());
     }
+
+    async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
+        let pubspec_yaml_raw = read_file(&self.path).await?;
+        let pubspec: serde_yaml::Value = serde_yaml::from_str(&pubspec_yaml_raw)?;
+
+        let mut patches = Vec::new();
+        for section in ["dependencies", "dev_dependencies", "dependency_overrides"] {
+            let Some(dependencies) = pubspec.get(section) else {
+                continue;
+            };
+            for package in packages {
+                if package.language() != Language::Dart {
+                    continue;
+                }
+                let (Some(name), Some(version)) = (package.name(), package.version()) else {
+                    continue;
+                };
+                // `core: ^1.2.0` or `core: { hosted: ..., version: ^1.2.0 }`
+                let (current, route) = match dependencies.get(name) {
+                    Some(serde_yaml::Value::String(current)) => {
+                        (current.as_str(), yamlpath::route!(section, name))
+                    }
+                    Some(dependency) => match dependency.get("version").and_then(|v| v.as_str()) {
+                        Some(current) => (current, yamlpath::route!(section, name, "version")),
+                        None => continue,
+                    },
+                    None => continue,
+                };
+                if let Some(rewritten) = rewrite_version_range(current, version)
+                    && rewritten != current
+                {
+                    patches.push(yamlpatch::Patch {
+                        operation: yamlpatch::Op::Replace(serde_yaml::Value::String(rewritten)),
+                        route,
+                    });
+                }
+            }
+        }
+        if patches.is_empty() {
+            return Ok(());
+        }
+
+        write_file(
+            &self.path,
+            format!(
+                "{}{}",
+                yamlpatch::apply_yaml_patches(
+                    &yamlpath::Document::new(&pubspec_yaml_raw).context("Failed to parse YAML")?,
+                    &patches,
+                )?
+                .source()
+                .trim_end(),
+                if pubspec_yaml_raw.ends_with('\n') {
+                    "\n"
+                } else {
+                    ""
+                }
+            ),
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -254,7 +352,10 @@ workspace:
             PathBuf::from("pubspec.yaml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 1.0.1"));
@@ -283,7 +384,10 @@ workspace:
             PathBuf::from("pubspec.yaml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 0.0.1"));
@@ -313,7 +417,10 @@ workspace:
             PathBuf::from("pubspec.yaml"),
         );
 
-        workspace.update_version(UpdateType::Minor).await.unwrap();
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 1.1.0"));
@@ -343,7 +450,10 @@ workspace:
             PathBuf::from("pubspec.yaml"),
         );
 
-        workspace.update_version(UpdateType::Major).await.unwrap();
+        workspace
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&pubspec_path).unwrap();
         assert!(content.contains("version: 2.0.0"));
@@ -351,6 +461,67 @@ workspace:
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_update_workspace_dependencies() {
+        use crate::package::DartPackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let pubspec_path = temp_dir.path().join("pubspec.yaml");
+        fs::write(
+            &pubspec_path,
+            r#"name: test_workspace
+# shared packages
+dependencies:
+  core: ^1.2.0
+  http: ^1.0.0
+  utils:
+    hosted: https://pub.example.com
+    version: ">=1.0.0"
+dev_dependencies:
+  lints: any
+workspace:
+  - packages/*
+"#,
+        )
+        .unwrap();
+
+        let workspace = DartWorkspace::new(
+            Some("test_workspace".to_string()),
+            None,
+            pubspec_path.clone(),
+            PathBuf::from("pubspec.yaml"),
+        );
+        let package = |name: &str, version: &str| {
+            DartPackage::new(
+                Some(name.to_string()),
+                Some(version.to_string()),
+                PathBuf::from(format!("/test/packages/{name}/pubspec.yaml")),
+                PathBuf::from(format!("packages/{name}/pubspec.yaml")),
+            )
+        };
+        let (core, utils, lints) = (
+            package("core", "1.3.0"),
+            package("utils", "1.1.0"),
+            package("lints", "2.0.0"),
+        );
+        let packages: Vec<&dyn Package> = vec![&core, &utils, &lints];
+
+        workspace
+            .update_workspace_dependencies(&packages)
+            .await
+            .unwrap();
+
+        let content = fs::read_to_string(&pubspec_path).unwrap();
+        assert!(content.contains("# shared packages"));
+        assert!(content.contains("core: ^1.3.0"));
+        assert!(content.contains("http: ^1.0.0"));
+        assert!(content.contains(">=1.1.0"));
+        assert!(content.contains("lints: any"));
+        assert!(content.ends_with("  - packages/*\n"));
+
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn test_dependencies() {
         let mut workspace = DartWorkspace::new(
@@ -0,0 +1,232 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, project_key};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tokio::fs::read_to_string;
+
+use crate::manifest::{DENO_MANIFESTS, jsr_dependencies, manifest_in, parse, workspace_members};
+use crate::{package::DenoPackage, workspace::DenoWorkspace};
+
+#[derive(Debug)]
+pub struct DenoProjectFinder {
+    projects: HashMap<PathBuf, Project>,
+    project_files: Vec<&'static str>,
+}
+
+impl Default for DenoProjectFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DenoProjectFinder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            projects: HashMap::new(),
+            project_files: DENO_MANIFESTS.to_vec(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProjectFinder for DenoProjectFinder {
+    fn projects(&self) -> Vec<&Project> {
+        self.projects.values().collect::<Vec<_>>()
+    }
+    fn projects_mut(&mut self) -> Vec<&mut Project> {
+        self.projects.values_mut().collect::<Vec<_>>()
+    }
+
+    fn project_files(&self) -> &[&str] {
+        &self.project_files
+    }
+
+    async fn visit(&mut self, path: &Path, relative_path: &Path) -> Result<()> {
+        if path.is_file()
+            && self.project_files().contains(
+                &path
+                    .file_name()
+                    .context(format!("File name not found - {}", path.display()))?
+                    .to_str()
+                    .context(format!("File name not found - {}", path.display()))?,
+            )
+        {
+            if self.projects.contains_key(&project_key(path)) {
+                return Ok(());
+            }
+            let dir = path
+                .parent()
+                .context(format!("Parent not found - {}", path.display()))?;
+            // A package.json makes it a Node package, and a manifest that takes precedence
+            // over this one represents the package instead
+            if dir.join("package.json").is_file()
+                || manifest_in(dir).is_some_and(|manifest| manifest != path)
+            {
+                return Ok(());
+            }
+            let manifest = parse(&read_to_string(path).await?)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            let name = manifest["name"].as_str().map(str::to_string);
+            let version = manifest["version"].as_str().map(str::to_string);
+
+            let mut project = if let Some(members) = workspace_members(&manifest) {
+                Project::Workspace(Box::new(
+                    DenoWorkspace::new(
+                        name,
+                        version,
+                        path.to_path_buf(),
+                        relative_path.to_path_buf(),
+                    )
+                    .with_members(members.iter().map(|member| dir.join(member)).collect()),
+                ))
+            } else {
+                Project::Package(Box::new(DenoPackage::new(
+                    name,
+                    version,
+                    path.to_path_buf(),
+                    relative_path.to_path_buf(),
+                )))
+            };
+
+            project.set_metadata(PackageMetadata::new(
+                None,
+                None,
+                manifest["license"].as_str().map(str::to_string),
+            ));
+            for dependency in jsr_dependencies(&manifest) {
+                project.add_dependency(&dependency);
+            }
+            self.projects.insert(project_key(path), project);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_new() {
+        let finder = DenoProjectFinder::new();
+        assert_eq!(
+            finder.project_files(),
+            &["deno.json", "deno.jsonc", "jsr.json"]
+        );
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_default() {
+        let finder = DenoProjectFinder::default();
+        assert_eq!(finder.project_files().len(), 3);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_visit_jsr_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let jsr_json = temp_dir.path().join("jsr.json");
+        fs::write(
+            &jsr_json,
+            r#"{
+  "name": "@scope/lib",
+  "version": "0.3.0",
+  "license": "MIT",
+  "exports": "./mod.ts"
+}
+"#,
+        )
+        .unwrap();
+
+        let mut finder = DenoProjectFinder::new();
+        finder
+            .visit(&jsr_json, &PathBuf::from("jsr.json"))
+            .await
+            .unwrap();
+
+        let projects = finder.projects();
+        assert_eq!(projects.len(), 1);
+        match projects[0] {
+            Project::Package(pkg) => {
+                assert_eq!(pkg.name(), Some("@scope/lib"));
+                assert_eq!(pkg.version(), Some("0.3.0"));
+                assert_eq!(pkg.license(), Some("MIT"));
+                assert_eq!(pkg.registry(), "jsr");
+                assert_eq!(pkg.default_publish_command(), "deno publish");
+            }
+            Project::Workspace(_) => panic!("Expected Package"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visit_workspace_and_members() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("deno.json"),
+            r#"{ "workspace": ["./packages/core", "./packages/cli"] }"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("packages/cli")).unwrap();
+        fs::write(
+            root.join("packages/cli/deno.jsonc"),
+            "{\n  // cli\n  \"name\": \"@acme/cli\",\n  \"version\": \"0.2.0\",\n  \"imports\": { \"@acme/core\": \"jsr:@acme/core@^1.0.0\", },\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("packages/cli/jsr.json"),
+            r#"{"name": "@acme/cli", "version": "0.2.0"}"#,
+        )
+        .unwrap();
+
+        let mut finder = DenoProjectFinder::new();
+        for relative in [
+            "deno.json",
+            "packages/cli/jsr.json",
+            "packages/cli/deno.jsonc",
+        ] {
+            finder
+                .visit(&root.join(relative), &PathBuf::from(relative))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(finder.projects().len(), 2);
+        for project in finder.projects() {
+            match project {
+                Project::Workspace(ws) => {
+                    assert_eq!(ws.name(), None);
+                    assert_eq!(ws.version(), None);
+                }
+                Project::Package(pkg) => {
+                    assert_eq!(pkg.name(), Some("@acme/cli"));
+                    assert_eq!(pkg.relative_path(), Path::new("packages/cli/deno.jsonc"));
+                    assert!(pkg.dependencies().contains("@acme/core"));
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visit_skips_node_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), r#"{"name": "lib"}"#).unwrap();
+        let deno_json = temp_dir.path().join("deno.json");
+        fs::write(&deno_json, r#"{"name": "@scope/lib", "version": "1.0.0"}"#).unwrap();
+
+        let mut finder = DenoProjectFinder::new();
+        finder
+            .visit(&deno_json, &PathBuf::from("deno.json"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 0);
+    }
+}
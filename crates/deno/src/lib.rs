@@ -0,0 +1,16 @@
+//! # changepacks-deno
+//!
+//! Deno project support for changepacks.
+//!
+//! Implements project discovery and version management for `deno.json`, `deno.jsonc` and
+//! standalone `jsr.json` manifests, published to JSR with `deno publish`. A manifest with a
+//! `workspace` member list is a workspace. Manifests are edited in place, so comments and
+//! formatting in `deno.jsonc` survive a bump. Directories with a `package.json` are left to
+//! the Node finder.
+
+pub mod finder;
+mod manifest;
+pub mod package;
+pub mod workspace;
+
+pub use finder::DenoProjectFinder;
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use changepacks_utils::{JSR_MANIFEST, rewrite_version_range};
+use regex::Regex;
+use serde_json::Value;
+
+/// Manifests of a Deno package, by precedence
+pub(crate) const DENO_MANIFESTS: [&str; 3] = ["deno.json", "deno.jsonc", JSR_MANIFEST];
+
+static JSR_SPECIFIER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#""jsr:/?(@[^/@"\s]+/[^/@"\s]+)(?:@([^/"\s]+))?"#)
+        .expect("hardcoded regex must compile")
+});
+
+/// The manifest describing the Deno package in `dir`, if any
+pub(crate) fn manifest_in(dir: &Path) -> Option<PathBuf> {
+    DENO_MANIFESTS
+        .iter()
+        .map(|file| dir.join(file))
+        .find(|path| path.is_file())
+}
+
+/// `content` with comments and trailing commas blanked out
+///
+/// Every byte keeps its offset, so ranges found in the result apply to `content`.
+pub(crate) fn strip_jsonc(content: &str) -> String {
+    let mut bytes = content.as_bytes().to_vec();
+    let mut commas = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => index = string_end(&bytes, index).unwrap_or(bytes.len()),
+            b',' => commas.push(index),
+            b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    bytes[index] = b' ';
+                    index += 1;
+                }
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                let end = content[index + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| index + 2 + end + 2);
+                for byte in &mut bytes[index..end] {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+                index = end - 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    for comma in commas {
+        let next = bytes[comma + 1..]
+            .iter()
+            .find(|byte| !byte.is_ascii_whitespace());
+        if matches!(next, Some(b'}' | b']')) {
+            bytes[comma] = b' ';
+        }
+    }
+    // Only ASCII bytes were replaced, and only by spaces
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
+/// Index of the quote closing the string opened at `start`
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 1,
+            b'"' => return Some(index),
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Parse a deno.json, deno.jsonc or jsr.json
+pub(crate) fn parse(content: &str) -> Result<Value> {
+    Ok(serde_json::from_str(&strip_jsonc(content))?)
+}
+
+/// Byte range of the top-level `version` string, inside its quotes
+pub(crate) fn version_range(content: &str) -> Option<Range<usize>> {
+    let stripped = strip_jsonc(content);
+    let bytes = stripped.as_bytes();
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b'"' => {
+                let end = string_end(bytes, index)?;
+                if depth == 1 && &stripped[index + 1..end] == "version" {
+                    let value = stripped[end + 1..]
+                        .trim_start()
+                        .strip_prefix(':')
+                        .map(str::trim_start)
+                        .filter(|value| value.starts_with('"'));
+                    if let Some(value) = value {
+                        let start = stripped.len() - value.len();
+                        return Some(start + 1..string_end(bytes, start)?);
+                    }
+                }
+                index = end;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// `content` with its top-level `version` set to `version`, added first if missing
+pub(crate) fn write_version(content: &str, version: &str) -> String {
+    let mut updated = content.to_string();
+    if let Some(range) = version_range(content) {
+        updated.replace_range(range, version);
+        return updated;
+    }
+    let stripped = strip_jsonc(content);
+    let Some(open) = stripped.find('{') else {
+        return updated;
+    };
+    let indent = content
+        .lines()
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ");
+    let empty = stripped[open + 1..].trim_start().starts_with('}');
+    let field = if empty {
+        format!("\n{indent}\"version\": \"{version}\"\n")
+    } else {
+        format!("\n{indent}\"version\": \"{version}\",")
+    };
+    updated.insert_str(open + 1, &field);
+    updated
+}
+
+/// JSR packages named by `jsr:` specifiers in the manifest's `imports`
+pub(crate) fn jsr_dependencies(manifest: &Value) -> Vec<String> {
+    manifest["imports"]
+        .as_object()
+        .into_iter()
+        .flat_map(|imports| imports.values())
+        .filter_map(Value::as_str)
+        .filter_map(|specifier| {
+            let quoted = format!("\"{specifier}\"");
+            Some(JSR_SPECIFIER_PATTERN.captures(&quoted)?[1].to_string())
+        })
+        .collect()
+}
+
+/// `content` with the ranges of `jsr:` specifiers moved to the new `versions` of their packages
+///
+/// Specifiers without a range and ranges that cannot follow a version are left alone.
+pub(crate) fn rewrite_jsr_specifiers(content: &str, versions: &HashMap<&str, &str>) -> String {
+    let stripped = strip_jsonc(content);
+    let mut updated = content.to_string();
+    let replacements: Vec<(Range<usize>, String)> = JSR_SPECIFIER_PATTERN
+        .captures_iter(&stripped)
+        .filter_map(|captures| {
+            let version = versions.get(&captures[1])?;
+            let range = captures.get(2)?;
+            let rewritten = rewrite_version_range(range.as_str(), version)?;
+            (rewritten != range.as_str()).then(|| (range.range(), rewritten))
+        })
+        .collect();
+    for (range, rewritten) in replacements.into_iter().rev() {
+        updated.replace_range(range, &rewritten);
+    }
+    updated
+}
+
+/// Member directories listed in `workspace`, either an array or `{ "members": [...] }`
+pub(crate) fn workspace_members(manifest: &Value) -> Option<Vec<String>> {
+    let workspace = manifest.get("workspace")?;
+    let members = workspace
+        .as_array()
+        .or_else(|| workspace["members"].as_array())?;
+    Some(
+        members
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DENO_JSONC: &str = r#"{
+  // Published to JSR
+  "name": "@acme/cli",
+  "version": "1.2.0", /* bumped by changepacks */
+  "exports": "./mod.ts",
+  "imports": {
+    "@acme/core": "jsr:@acme/core@^0.4.0",
+    "@acme/fmt": "jsr:@acme/fmt",
+    "@std/path": "jsr:@std/path@^1.0.0/posix",
+    "chalk": "npm:chalk@5",
+  },
+}
+"#;
+
+    #[test]
+    fn test_parse() {
+        let manifest = parse(DENO_JSONC).unwrap();
+        assert_eq!(manifest["name"], "@acme/cli");
+        assert_eq!(
+            jsr_dependencies(&manifest),
+            vec!["@acme/core", "@acme/fmt", "@std/path"]
+        );
+        assert_eq!(workspace_members(&manifest), None);
+        assert_eq!(
+            version_range(DENO_JSONC).map(|range| &DENO_JSONC[range]),
+            Some("1.2.0")
+        );
+    }
+
+    #[test]
+    fn test_strip_jsonc_keeps_strings() {
+        let content = "{\"url\": \"https://jsr.io/*\", // note\n\"a\": [1,]}";
+        assert_eq!(
+            strip_jsonc(content),
+            "{\"url\": \"https://jsr.io/*\",        \n\"a\": [1 ]}"
+        );
+    }
+
+    #[test]
+    fn test_version_range_ignores_nested() {
+        let content = r#"{"tasks": {"version": "echo"}, "compilerOptions": {}}"#;
+        assert_eq!(version_range(content), None);
+    }
+
+    #[test]
+    fn test_write_version() {
+        assert_eq!(
+            write_version(DENO_JSONC, "1.3.0"),
+            DENO_JSONC.replace("\"1.2.0\"", "\"1.3.0\"")
+        );
+        assert_eq!(
+            write_version("{\n    \"name\": \"@acme/x\"\n}\n", "0.1.0"),
+            "{\n    \"version\": \"0.1.0\",\n    \"name\": \"@acme/x\"\n}\n"
+        );
+        assert_eq!(
+            write_version("{}", "0.1.0"),
+            "{\n  \"version\": \"0.1.0\"\n}"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_jsr_specifiers() {
+        let versions = HashMap::from([("@acme/core", "0.5.0"), ("@std/path", "1.1.0")]);
+        assert_eq!(
+            rewrite_jsr_specifiers(DENO_JSONC, &versions),
+            DENO_JSONC
+                .replace("@acme/core@^0.4.0", "@acme/core@^0.5.0")
+                .replace("@std/path@^1.0.0", "@std/path@^1.1.0")
+        );
+    }
+
+    #[test]
+    fn test_workspace_members() {
+        let array = parse(r#"{"workspace": ["./core", "./cli"]}"#).unwrap();
+        assert_eq!(
+            workspace_members(&array),
+            Some(vec!["./core".to_string(), "./cli".to_string()])
+        );
+        let object = parse(r#"{"workspace": {"members": ["core"]}}"#).unwrap();
+        assert_eq!(workspace_members(&object), Some(vec!["core".to_string()]));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::{jsr_manifest, next_version_with_id, read_file, write_file};
+
+use crate::manifest::write_version;
+
+#[derive(Debug)]
+pub struct DenoPackage {
+    name: Option<String>,
+    version: Option<String>,
+    path: PathBuf,
+    relative_path: PathBuf,
+    is_changed: bool,
+    metadata: PackageMetadata,
+    dependencies: HashSet<String>,
+}
+
+impl DenoPackage {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            name,
+            version,
+            path,
+            relative_path,
+            is_changed: false,
+            metadata: PackageMetadata::default(),
+            dependencies: HashSet::new(),
+        }
+    }
+}
+
+/// Set the version of the manifest at `path`, and of a jsr.json next to it
+///
+/// JSR reads the version from either file, so both are kept in step.
+pub(crate) async fn write_manifest_versions(path: &Path, version: &str) -> Result<()> {
+    let content = read_file(path).await?;
+    write_file(path, write_version(&content, version)).await?;
+    if let Some(manifest) = jsr_manifest(path)
+        && manifest != path
+    {
+        let content = read_file(&manifest).await?;
+        write_file(&manifest, write_version(&content, version)).await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Package for DenoPackage {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+        write_manifest_versions(&self.path, &new_version).await?;
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Deno
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn default_publish_command(&self) -> String {
+        "deno publish".to_string()
+    }
+
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        Some("deno publish --dry-run".to_string())
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_version_syncs_jsr_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let deno_json = temp_dir.path().join("deno.jsonc");
+        let jsr_json = temp_dir.path().join("jsr.json");
+        fs::write(
+            &deno_json,
+            "{\n  // library\n  \"name\": \"@acme/lib\",\n  \"version\": \"0.3.0\"\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            &jsr_json,
+            "{\n  \"name\": \"@acme/lib\",\n  \"version\": \"0.3.0\"\n}\n",
+        )
+        .unwrap();
+        let mut package = DenoPackage::new(
+            Some("@acme/lib".to_string()),
+            Some("0.3.0".to_string()),
+            deno_json.clone(),
+            PathBuf::from("deno.jsonc"),
+        );
+
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
+
+        assert_eq!(package.version(), Some("0.4.0"));
+        assert_eq!(
+            fs::read_to_string(&deno_json).unwrap(),
+            "{\n  // library\n  \"name\": \"@acme/lib\",\n  \"version\": \"0.4.0\"\n}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&jsr_json).unwrap(),
+            "{\n  \"name\": \"@acme/lib\",\n  \"version\": \"0.4.0\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_publish_commands() {
+        let package = DenoPackage::new(
+            Some("@acme/lib".to_string()),
+            Some("1.0.0".to_string()),
+            PathBuf::from("deno.json"),
+            PathBuf::from("deno.json"),
+        );
+        assert_eq!(package.language(), Language::Deno);
+        assert_eq!(package.registry(), "jsr");
+        assert_eq!(package.default_publish_command(), "deno publish");
+        assert_eq!(
+            package.default_dry_run_publish_command(),
+            Some("deno publish --dry-run".to_string())
+        );
+    }
+}
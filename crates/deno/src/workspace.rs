@@ -0,0 +1,208 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
+
+use crate::manifest::{manifest_in, rewrite_jsr_specifiers};
+use crate::package::write_manifest_versions;
+
+#[derive(Debug)]
+pub struct DenoWorkspace {
+    path: PathBuf,
+    relative_path: PathBuf,
+    version: Option<String>,
+    name: Option<String>,
+    is_changed: bool,
+    metadata: PackageMetadata,
+    dependencies: HashSet<String>,
+    members: Vec<PathBuf>,
+}
+
+impl DenoWorkspace {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            path,
+            relative_path,
+            name,
+            version,
+            is_changed: false,
+            metadata: PackageMetadata::default(),
+            dependencies: HashSet::new(),
+            members: Vec::new(),
+        }
+    }
+
+    /// Set the directories of the `workspace` members
+    #[must_use]
+    pub fn with_members(mut self, members: Vec<PathBuf>) -> Self {
+        self.members = members;
+        self
+    }
+}
+
+#[async_trait]
+impl Workspace for DenoWorkspace {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+        write_manifest_versions(&self.path, &new_version).await?;
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Deno
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn default_publish_command(&self) -> String {
+        "deno publish".to_string()
+    }
+
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        Some("deno publish --dry-run".to_string())
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+
+    /// Move `jsr:` imports of bumped packages, in this manifest and its members', to the new versions
+    async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
+        let versions: HashMap<&str, &str> = packages
+            .iter()
+            .filter(|package| package.language() == Language::Deno)
+            .filter_map(|package| Some((package.name()?, package.version()?)))
+            .collect();
+        if versions.is_empty() {
+            return Ok(());
+        }
+
+        let manifests = std::iter::once(self.path.clone())
+            .chain(self.members.iter().filter_map(|member| manifest_in(member)));
+        for manifest in manifests {
+            let content = read_file(&manifest).await?;
+            let updated = rewrite_jsr_specifiers(&content, &versions);
+            if updated != content {
+                write_file(&manifest, updated).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::DenoPackage;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_workspace_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let root_manifest = root.join("deno.json");
+        let root_json = "{\n  \"workspace\": [\"./core\", \"./cli\"],\n  \"imports\": {\n    \"@acme/core\": \"jsr:@acme/core@^1.0.0\"\n  }\n}\n";
+        fs::write(&root_manifest, root_json).unwrap();
+        fs::create_dir_all(root.join("core")).unwrap();
+        fs::write(
+            root.join("core/deno.json"),
+            "{\n  \"name\": \"@acme/core\",\n  \"version\": \"1.0.0\"\n}\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("cli")).unwrap();
+        let cli_jsonc = "{\n  // command line\n  \"name\": \"@acme/cli\",\n  \"version\": \"0.1.0\",\n  \"imports\": { \"@acme/core\": \"jsr:@acme/core@~1.0.0\" }\n}\n";
+        fs::write(root.join("cli/deno.jsonc"), cli_jsonc).unwrap();
+
+        let workspace = DenoWorkspace::new(
+            None,
+            None,
+            root_manifest.clone(),
+            PathBuf::from("deno.json"),
+        )
+        .with_members(vec![root.join("core"), root.join("cli"), root.join("gone")]);
+        let mut core = DenoPackage::new(
+            Some("@acme/core".to_string()),
+            Some("1.0.0".to_string()),
+            root.join("core/deno.json"),
+            PathBuf::from("core/deno.json"),
+        );
+        core.update_version(UpdateType::Minor, None).await.unwrap();
+        workspace
+            .update_workspace_dependencies(&[&core as &dyn Package])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&root_manifest).unwrap(),
+            root_json.replace("^1.0.0", "^1.1.0")
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("cli/deno.jsonc")).unwrap(),
+            cli_jsonc.replace("~1.0.0", "~1.1.0")
+        );
+        assert_eq!(workspace.language(), Language::Deno);
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, project_key};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tokio::fs::read_to_string;
+
+use crate::mix::{self, MIX_FILE};
+use crate::{package::ElixirPackage, workspace::ElixirWorkspace};
+
+#[derive(Debug)]
+pub struct ElixirProjectFinder {
+    projects: HashMap<PathBuf, Project>,
+    project_files: Vec<&'static str>,
+}
+
+impl Default for ElixirProjectFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ElixirProjectFinder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            projects: HashMap::new(),
+            project_files: vec![MIX_FILE],
+        }
+    }
+}
+
+#[async_trait]
+impl ProjectFinder for ElixirProjectFinder {
+    fn projects(&self) -> Vec<&Project> {
+        self.projects.values().collect::<Vec<_>>()
+    }
+    fn projects_mut(&mut self) -> Vec<&mut Project> {
+        self.projects.values_mut().collect::<Vec<_>>()
+    }
+
+    fn project_files(&self) -> &[&str] {
+        &self.project_files
+    }
+
+    async fn visit(&mut self, path: &Path, relative_path: &Path) -> Result<()> {
+        if path.is_file()
+            && self.project_files().contains(
+                &path
+                    .file_name()
+                    .context(format!("File name not found - {}", path.display()))?
+                    .to_str()
+                    .context(format!("File name not found - {}", path.display()))?,
+            )
+        {
+            if self.projects.contains_key(&project_key(path)) {
+                return Ok(());
+            }
+            let mix_exs = read_to_string(path).await?;
+            let name = mix::app_name(&mix_exs);
+            let version = mix::version(&mix_exs);
+
+            let mut project = if mix::apps_path(&mix_exs).is_some() {
+                Project::Workspace(Box::new(ElixirWorkspace::new(
+                    name,
+                    version,
+                    path.to_path_buf(),
+                    relative_path.to_path_buf(),
+                )))
+            } else {
+                Project::Package(Box::new(ElixirPackage::new(
+                    name,
+                    version,
+                    path.to_path_buf(),
+                    relative_path.to_path_buf(),
+                )))
+            };
+
+            let (description, source_url, license) = mix::metadata(&mix_exs);
+            project.set_metadata(PackageMetadata::new(description, source_url, license));
+            for dependency in mix::dependencies(&mix_exs) {
+                project.add_dependency(&dependency);
+            }
+            self.projects.insert(project_key(path), project);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_new() {
+        let finder = ElixirProjectFinder::new();
+        assert_eq!(finder.project_files(), &["mix.exs"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_default() {
+        let finder = ElixirProjectFinder::default();
+        assert_eq!(finder.project_files(), &["mix.exs"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_visit_umbrella() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("mix.exs"),
+            "defmodule Acme.Umbrella.MixProject do\n  def project do\n    [apps_path: \"apps\", deps: deps()]\n  end\n\n  defp deps, do: []\nend\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("apps/web")).unwrap();
+        fs::write(
+            root.join("apps/web/mix.exs"),
+            "defmodule Web.MixProject do\n  def project do\n    [app: :web, version: \"0.2.0\", deps: deps()]\n  end\n\n  defp deps do\n    [\n      {:core, in_umbrella: true},\n      {:phoenix, \"~> 1.7\"}\n    ]\n  end\nend\n",
+        )
+        .unwrap();
+
+        let mut finder = ElixirProjectFinder::new();
+        finder
+            .visit(&root.join("mix.exs"), &PathBuf::from("mix.exs"))
+            .await
+            .unwrap();
+        finder
+            .visit(
+                &root.join("apps/web/mix.exs"),
+                &PathBuf::from("apps/web/mix.exs"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 2);
+        for project in finder.projects() {
+            match project {
+                Project::Workspace(ws) => {
+                    assert_eq!(ws.name(), None);
+                    assert_eq!(ws.version(), None);
+                }
+                Project::Package(pkg) => {
+                    assert_eq!(pkg.name(), Some("web"));
+                    assert_eq!(pkg.version(), Some("0.2.0"));
+                    assert!(pkg.dependencies().contains("core"));
+                    assert!(pkg.dependencies().contains("phoenix"));
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visit_ignores_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mix.lock");
+        fs::write(&path, "%{}").unwrap();
+
+        let mut finder = ElixirProjectFinder::new();
+        finder
+            .visit(&path, &PathBuf::from("mix.lock"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 0);
+    }
+}
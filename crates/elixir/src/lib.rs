@@ -0,0 +1,15 @@
+//! # changepacks-elixir
+//!
+//! Elixir project support for changepacks.
+//!
+//! Implements project discovery and version management for Mix projects (mix.exs). The
+//! `version:` of the project keyword list is rewritten in place, following it to a module
+//! attribute such as `@version "1.2.0"` when the project reads its version from one. Umbrella
+//! projects (with `apps_path:`) are workspaces.
+
+pub mod finder;
+mod mix;
+pub mod package;
+pub mod workspace;
+
+pub use finder::ElixirProjectFinder;
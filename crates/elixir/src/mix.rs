@@ -0,0 +1,194 @@
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+/// Manifest file of a Mix project
+pub(crate) const MIX_FILE: &str = "mix.exs";
+
+static APP_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bapp:\s*:([A-Za-z_][A-Za-z0-9_]*)").expect("hardcoded regex must compile")
+});
+
+static VERSION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bversion:\s*"([^"]*)""#).expect("hardcoded regex must compile")
+});
+
+static VERSION_ATTRIBUTE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bversion:\s*@([a-z_][A-Za-z0-9_]*)").expect("hardcoded regex must compile")
+});
+
+static APPS_PATH_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bapps_path:\s*"([^"]*)""#).expect("hardcoded regex must compile")
+});
+
+static DEP_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\s*:([a-z_][A-Za-z0-9_]*)\s*,").expect("hardcoded regex must compile")
+});
+
+static DESCRIPTION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bdescription:\s*"([^"]*)""#).expect("hardcoded regex must compile")
+});
+
+static SOURCE_URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bsource_url:\s*"([^"]*)""#).expect("hardcoded regex must compile")
+});
+
+static LICENSES_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\blicenses:\s*\[\s*"([^"]*)""#).expect("hardcoded regex must compile")
+});
+
+fn capture(pattern: &Regex, content: &str) -> Option<String> {
+    Some(pattern.captures(content)?.get(1)?.as_str().to_string())
+}
+
+/// OTP application name (`app: :my_app`)
+pub(crate) fn app_name(content: &str) -> Option<String> {
+    capture(&APP_PATTERN, content)
+}
+
+/// Byte range of the project version string, inside its quotes
+///
+/// Handles both `version: "1.2.0"` and `version: @version` with `@version "1.2.0"` defined
+/// in the module.
+pub(crate) fn version_range(content: &str) -> Option<Range<usize>> {
+    if let Some(captures) = VERSION_PATTERN.captures(content) {
+        return Some(captures.get(1)?.range());
+    }
+    let attribute = capture(&VERSION_ATTRIBUTE_PATTERN, content)?;
+    let definition = Regex::new(&format!(
+        r#"(?m)^\s*@{}\s+"([^"]*)""#,
+        regex::escape(&attribute)
+    ))
+    .ok()?;
+    Some(definition.captures(content)?.get(1)?.range())
+}
+
+/// Project version, see [`version_range`]
+pub(crate) fn version(content: &str) -> Option<String> {
+    version_range(content).map(|range| content[range].to_string())
+}
+
+/// `content` with the project version replaced by `version`
+///
+/// # Errors
+/// Returns error if the project declares no version string.
+pub(crate) fn replace_version(content: &str, version: &str) -> Result<String> {
+    let range = version_range(content).context("mix.exs declares no version string")?;
+    let mut updated = content.to_string();
+    updated.replace_range(range, version);
+    Ok(updated)
+}
+
+/// Directory of an umbrella project's apps (`apps_path: "apps"`)
+pub(crate) fn apps_path(content: &str) -> Option<String> {
+    capture(&APPS_PATH_PATTERN, content)
+}
+
+/// Apps named in the `deps` function, including `in_umbrella: true` siblings
+pub(crate) fn dependencies(content: &str) -> Vec<String> {
+    let Some(start) = content.find("defp deps") else {
+        return Vec::new();
+    };
+    let body = &content[start..];
+    // The function ends at its first `end` line, or with its line for `defp deps, do: [...]`
+    let mut end = body.len();
+    let mut offset = 0;
+    for (index, line) in body.split_inclusive('\n').enumerate() {
+        if index == 0 && line.contains("do:") {
+            end = line.len();
+            break;
+        }
+        if index > 0 && line.trim() == "end" {
+            end = offset;
+            break;
+        }
+        offset += line.len();
+    }
+    DEP_PATTERN
+        .captures_iter(&body[..end])
+        .filter_map(|captures| Some(captures.get(1)?.as_str().to_string()))
+        .collect()
+}
+
+/// Description, source URL and first license from the Hex `package` metadata
+pub(crate) fn metadata(content: &str) -> (Option<String>, Option<String>, Option<String>) {
+    (
+        capture(&DESCRIPTION_PATTERN, content),
+        capture(&SOURCE_URL_PATTERN, content),
+        capture(&LICENSES_PATTERN, content),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIX: &str = r#"defmodule Acme.MixProject do
+  use Mix.Project
+
+  @version "1.4.0"
+  @source_url "https://github.com/acme/acme"
+
+  def project do
+    [
+      app: :acme,
+      version: @version,
+      elixir: "~> 1.15",
+      description: "Acme toolkit",
+      source_url: "https://github.com/acme/acme",
+      package: [licenses: ["MIT"]],
+      deps: deps()
+    ]
+  end
+
+  defp deps do
+    [
+      {:jason, "~> 1.4"},
+      {:acme_core, in_umbrella: true}
+    ]
+  end
+
+  defp aliases, do: [{:ok, "ignored"}]
+end
+"#;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(app_name(MIX), Some("acme".to_string()));
+        assert_eq!(version(MIX), Some("1.4.0".to_string()));
+        assert_eq!(apps_path(MIX), None);
+        assert_eq!(dependencies(MIX), vec!["jason", "acme_core"]);
+        assert_eq!(
+            metadata(MIX),
+            (
+                Some("Acme toolkit".to_string()),
+                Some("https://github.com/acme/acme".to_string()),
+                Some("MIT".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_replace_version() {
+        assert_eq!(
+            replace_version(MIX, "1.5.0").unwrap(),
+            MIX.replace("@version \"1.4.0\"", "@version \"1.5.0\"")
+        );
+        let inline = "def project do\n  [app: :x, version: \"0.1.0\"]\nend\n";
+        assert_eq!(
+            replace_version(inline, "0.2.0").unwrap(),
+            "def project do\n  [app: :x, version: \"0.2.0\"]\nend\n"
+        );
+        assert!(replace_version("[apps_path: \"apps\"]", "1.0.0").is_err());
+    }
+
+    #[test]
+    fn test_umbrella() {
+        let umbrella = "def project do\n  [apps_path: \"apps\", deps: []]\nend\n";
+        assert_eq!(apps_path(umbrella), Some("apps".to_string()));
+        assert_eq!(version(umbrella), None);
+        assert!(dependencies(umbrella).is_empty());
+    }
+}
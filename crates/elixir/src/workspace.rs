@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Language, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
+
+use crate::mix::replace_version;
+
+/// Umbrella project, whose apps live under its `apps_path`
+#[derive(Debug)]
+pub struct ElixirWorkspace {
+    path: PathBuf,
+    relative_path: PathBuf,
+    version: Option<String>,
+    name: Option<String>,
+    is_changed: bool,
+    metadata: PackageMetadata,
+    dependencies: HashSet<String>,
+}
+
+impl ElixirWorkspace {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            path,
+            relative_path,
+            name,
+            version,
+            is_changed: false,
+            metadata: PackageMetadata::default(),
+            dependencies: HashSet::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Workspace for ElixirWorkspace {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+
+        let mix_exs = read_file(&self.path).await?;
+        write_file(
+            &self.path,
+            replace_version(&mix_exs, &new_version)
+                .with_context(|| format!("Cannot bump {}", self.path.display()))?,
+        )
+        .await?;
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Elixir
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn default_publish_command(&self) -> String {
+        "mix hex.publish".to_string()
+    }
+
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        Some("mix hex.publish --dry-run".to_string())
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let mix_path = temp_dir.path().join("mix.exs");
+        fs::write(
+            &mix_path,
+            "def project do\n  [apps_path: \"apps\", version: \"1.0.0\"]\nend\n",
+        )
+        .unwrap();
+        let mut workspace = ElixirWorkspace::new(
+            None,
+            Some("1.0.0".to_string()),
+            mix_path.clone(),
+            PathBuf::from("mix.exs"),
+        );
+
+        workspace
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
+
+        assert_eq!(workspace.version(), Some("2.0.0"));
+        assert_eq!(
+            fs::read_to_string(&mix_path).unwrap(),
+            "def project do\n  [apps_path: \"apps\", version: \"2.0.0\"]\nend\n"
+        );
+        assert_eq!(workspace.language(), Language::Elixir);
+    }
+}
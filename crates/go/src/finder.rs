@@ -0,0 +1,214 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Project, ProjectFinder, path_key, project_key};
+use changepacks_utils::{find_current_git_repo, list_git_tags};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tokio::fs::read_to_string;
+
+use crate::{
+    go_mod::{module_path, required_modules},
+    package::GoPackage,
+    version::latest_tag_version,
+};
+
+#[derive(Debug)]
+pub struct GoProjectFinder {
+    projects: HashMap<PathBuf, Project>,
+    project_files: Vec<&'static str>,
+}
+
+impl Default for GoProjectFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoProjectFinder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            projects: HashMap::new(),
+            project_files: vec!["go.mod"],
+        }
+    }
+}
+
+#[async_trait]
+impl ProjectFinder for GoProjectFinder {
+    fn projects(&self) -> Vec<&Project> {
+        self.projects.values().collect::<Vec<_>>()
+    }
+    fn projects_mut(&mut self) -> Vec<&mut Project> {
+        self.projects.values_mut().collect::<Vec<_>>()
+    }
+
+    fn project_files(&self) -> &[&str] {
+        &self.project_files
+    }
+
+    async fn visit(&mut self, path: &Path, relative_path: &Path) -> Result<()> {
+        if path.is_file()
+            && self.project_files().contains(
+                &path
+                    .file_name()
+                    .context(format!("File name not found - {}", path.display()))?
+                    .to_str()
+                    .context(format!("File name not found - {}", path.display()))?,
+            )
+        {
+            if self.projects.contains_key(&project_key(path)) {
+                return Ok(());
+            }
+            let go_mod = read_to_string(path).await?;
+            let dir = path.parent().context("Parent not found")?;
+
+            let tag_prefix = tag_prefix(dir);
+            let version = latest_tag_version(&list_git_tags(dir).await, &tag_prefix);
+
+            let mut project = Project::Package(Box::new(
+                GoPackage::new(
+                    module_path(&go_mod),
+                    version,
+                    path.to_path_buf(),
+                    relative_path.to_path_buf(),
+                )
+                .with_tag_prefix(tag_prefix),
+            ));
+            for module in required_modules(&go_mod) {
+                project.add_dependency(&module);
+            }
+            self.projects.insert(project_key(path), project);
+        }
+        Ok(())
+    }
+}
+
+/// Prefix of the version tags of a module in `dir`: its directory relative to the repository
+/// root with a trailing `/`, empty at the root and outside a repository (no tags to read)
+fn tag_prefix(dir: &Path) -> String {
+    let Ok(repo) = find_current_git_repo(dir) else {
+        return String::new();
+    };
+    let (Some(work_dir), Ok(dir)) = (
+        repo.work_dir()
+            .and_then(|work_dir| work_dir.canonicalize().ok()),
+        dir.canonicalize(),
+    ) else {
+        return String::new();
+    };
+    match dir.strip_prefix(&work_dir) {
+        Ok(relative) if !relative.as_os_str().is_empty() => format!("{}/", path_key(relative)),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_utils::run_git;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_new() {
+        let finder = GoProjectFinder::new();
+        assert_eq!(finder.project_files(), &["go.mod"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_default() {
+        let finder = GoProjectFinder::default();
+        assert_eq!(finder.project_files(), &["go.mod"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_visit_outside_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let go_mod = temp_dir.path().join("go.mod");
+        fs::write(
+            &go_mod,
+            "module example.com/m\n\ngo 1.22\n\nrequire example.com/core v1.0.0\n",
+        )
+        .unwrap();
+
+        let mut finder = GoProjectFinder::new();
+        finder
+            .visit(&go_mod, &PathBuf::from("go.mod"))
+            .await
+            .unwrap();
+        finder
+            .visit(&go_mod, &PathBuf::from("go.mod"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 1);
+        let project = finder.projects()[0];
+        assert_eq!(project.name(), Some("example.com/m"));
+        assert_eq!(project.version(), None);
+        assert!(project.dependencies().contains("example.com/core"));
+    }
+
+    #[tokio::test]
+    async fn test_visit_reads_version_from_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+            &["tag", "v0.9.0"],
+            &["tag", "api/v1.2.0"],
+            &["tag", "api/v1.10.0"],
+        ] {
+            run_git(root, args).await.unwrap();
+        }
+        fs::write(root.join("go.mod"), "module example.com/m\n").unwrap();
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(root.join("api/go.mod"), "module example.com/m/api\n").unwrap();
+
+        let mut finder = GoProjectFinder::new();
+        finder
+            .visit(&root.join("go.mod"), &PathBuf::from("go.mod"))
+            .await
+            .unwrap();
+        finder
+            .visit(&root.join("api/go.mod"), &PathBuf::from("api/go.mod"))
+            .await
+            .unwrap();
+
+        let mut versions: Vec<_> = finder
+            .projects()
+            .iter()
+            .map(|project| (project.name(), project.version()))
+            .collect();
+        versions.sort();
+        assert_eq!(
+            versions,
+            vec![
+                (Some("example.com/m"), Some("0.9.0")),
+                (Some("example.com/m/api"), Some("1.10.0")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_visit_ignores_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let go_sum = temp_dir.path().join("go.sum");
+        fs::write(&go_sum, "").unwrap();
+
+        let mut finder = GoProjectFinder::new();
+        finder
+            .visit(&go_sum, &PathBuf::from("go.sum"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 0);
+    }
+}
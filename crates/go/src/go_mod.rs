@@ -0,0 +1,92 @@
+/// Module path declared by the `module` directive of a go.mod
+pub(crate) fn module_path(go_mod: &str) -> Option<String> {
+    go_mod.lines().find_map(|line| {
+        let rest = strip_comment(line).strip_prefix("module")?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let path = unquote(rest.trim());
+        (!path.is_empty()).then(|| path.to_string())
+    })
+}
+
+/// Module paths named by the `require` directives of a go.mod, single-line and block form
+pub(crate) fn required_modules(go_mod: &str) -> Vec<String> {
+    let mut modules = Vec::new();
+    let mut in_block = false;
+    for line in go_mod.lines() {
+        let line = strip_comment(line);
+        let spec = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else {
+            let Some(rest) = line.strip_prefix("require") else {
+                continue;
+            };
+            let rest = rest.trim_start();
+            if rest == "(" {
+                in_block = true;
+                continue;
+            }
+            rest
+        };
+        if let Some(module) = spec.split_whitespace().next() {
+            modules.push(unquote(module).to_string());
+        }
+    }
+    modules
+}
+
+/// `line` trimmed, without a trailing `//` comment
+fn strip_comment(line: &str) -> &str {
+    line.split_once("//").map_or(line, |(code, _)| code).trim()
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '`')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GO_MOD: &str = r#"// Package docs
+module github.com/acme/api // the API
+
+go 1.22
+
+require github.com/acme/core v1.2.0
+
+require (
+	github.com/pkg/errors v0.9.1
+	"golang.org/x/text" v0.14.0 // indirect
+)
+"#;
+
+    #[test]
+    fn test_module_path() {
+        assert_eq!(module_path(GO_MOD), Some("github.com/acme/api".to_string()));
+        assert_eq!(
+            module_path("module \"example.com/m/v2\"\n"),
+            Some("example.com/m/v2".to_string())
+        );
+        assert_eq!(module_path("go 1.22\n"), None);
+        assert_eq!(module_path("modules x\n"), None);
+    }
+
+    #[test]
+    fn test_required_modules() {
+        assert_eq!(
+            required_modules(GO_MOD),
+            vec![
+                "github.com/acme/core",
+                "github.com/pkg/errors",
+                "golang.org/x/text"
+            ]
+        );
+        assert!(required_modules("module m\n").is_empty());
+    }
+}
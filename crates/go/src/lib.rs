@@ -0,0 +1,16 @@
+//! # changepacks-go
+//!
+//! Go module support for changepacks.
+//!
+//! Implements project discovery and version management for go.mod files. Go modules have no
+//! version field: the version is the latest `vX.Y.Z` git tag of the module (prefixed with the
+//! module directory in multi-module repositories, like `api/v1.2.0`), and a bump creates the
+//! next tag. A `Version` constant in the module's `version.go` is kept in step when present.
+
+pub mod finder;
+mod go_mod;
+pub mod package;
+mod version;
+
+pub use finder::GoProjectFinder;
+pub use package::GoPackage;
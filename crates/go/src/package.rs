@@ -0,0 +1,315 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use changepacks_core::{Language, Package, UpdateType};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
+
+use crate::version::{VERSION_FILE, check_major_suffix, version_constant};
+
+#[derive(Debug)]
+pub struct GoPackage {
+    name: Option<String>,
+    version: Option<String>,
+    path: PathBuf,
+    relative_path: PathBuf,
+    is_changed: bool,
+    dependencies: HashSet<String>,
+    tag_prefix: String,
+}
+
+impl GoPackage {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            name,
+            version,
+            path,
+            relative_path,
+            is_changed: false,
+            dependencies: HashSet::new(),
+            tag_prefix: String::new(),
+        }
+    }
+
+    /// Set the prefix of the module's version tags, its directory relative to the repository
+    /// root with a trailing `/` (empty for a module at the root)
+    #[must_use]
+    pub fn with_tag_prefix(mut self, tag_prefix: String) -> Self {
+        self.tag_prefix = tag_prefix;
+        self
+    }
+
+    /// Git tag of `version` for this module, like `v1.2.0` or `api/v1.2.0`
+    #[must_use]
+    pub fn tag(&self, version: &str) -> String {
+        format!("{}v{version}", self.tag_prefix)
+    }
+
+    fn version_file(&self) -> Option<PathBuf> {
+        Some(self.path.parent()?.join(VERSION_FILE))
+    }
+}
+
+#[async_trait]
+impl Package for GoPackage {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    /// Update the `version.go` constant if there is one
+    ///
+    /// The version tag itself is created by `update` on the release commit (see
+    /// [`Package::version_tag`]).
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)?;
+        if let Some(name) = &self.name {
+            check_major_suffix(name, &new_version)?;
+        }
+
+        if let Some(version_file) = self.version_file().filter(|path| path.is_file()) {
+            let source = read_file(&version_file).await?;
+            if let Some(range) = version_constant(&source) {
+                let prefix = if source[range.clone()].starts_with('v') {
+                    "v"
+                } else {
+                    ""
+                };
+                let mut updated = source.clone();
+                updated.replace_range(range, &format!("{prefix}{new_version}"));
+                write_file(&version_file, updated).await?;
+            }
+        }
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Go
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    async fn version_declarations(&self) -> Result<Vec<(PathBuf, String)>> {
+        let Some(version_file) = self.version_file().filter(|path| path.is_file()) else {
+            return Ok(Vec::new());
+        };
+        let source = tokio::fs::read_to_string(&version_file).await?;
+        Ok(version_constant(&source)
+            .map(|range| {
+                let version = source[range].trim_start_matches('v').to_string();
+                (version_file, version)
+            })
+            .into_iter()
+            .collect())
+    }
+
+    fn version_tag(&self, version: &str) -> Option<String> {
+        Some(self.tag(version))
+    }
+
+    /// Push the version tag; the Go module proxy picks new versions up from the repository's
+    /// tags
+    fn default_publish_command(&self) -> String {
+        let tag = self.tag(self.version.as_deref().unwrap_or("0.0.0"));
+        format!("git push origin refs/tags/{tag}")
+    }
+
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        let tag = self.tag(self.version.as_deref().unwrap_or("0.0.0"));
+        Some(format!("git push --dry-run origin refs/tags/{tag}"))
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_utils::{capture_writes, run_git};
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn git_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        ] {
+            run_git(dir, args).await.unwrap();
+        }
+        temp_dir
+    }
+
+    fn package(dir: &Path, version: Option<&str>, tag_prefix: &str) -> GoPackage {
+        fs::create_dir_all(dir).unwrap();
+        let path = dir.join("go.mod");
+        fs::write(&path, "module example.com/m\n").unwrap();
+        GoPackage::new(
+            Some("example.com/m".to_string()),
+            version.map(str::to_string),
+            path,
+            PathBuf::from("go.mod"),
+        )
+        .with_tag_prefix(tag_prefix.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_update_version_leaves_tags_alone() {
+        let temp_dir = git_repo().await;
+        let mut package = package(&temp_dir.path().join("api"), Some("1.2.3"), "api/");
+
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
+
+        assert_eq!(package.version(), Some("1.3.0"));
+        assert_eq!(package.version_tag("1.3.0").as_deref(), Some("api/v1.3.0"));
+        assert!(
+            run_git(temp_dir.path(), &["tag", "--list"])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_version_without_tags() {
+        let temp_dir = git_repo().await;
+        let mut package = package(temp_dir.path(), None, "");
+
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
+
+        assert_eq!(package.version(), Some("0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn test_update_version_updates_version_go() {
+        let temp_dir = git_repo().await;
+        let mut package = package(temp_dir.path(), Some("1.0.0"), "");
+        let version_go = temp_dir.path().join("version.go");
+        fs::write(&version_go, "package m\n\nconst Version = \"v1.0.0\"\n").unwrap();
+        assert_eq!(
+            package.version_declarations().await.unwrap(),
+            vec![(version_go.clone(), "1.0.0".to_string())]
+        );
+
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&version_go).unwrap(),
+            "package m\n\nconst Version = \"v1.0.1\"\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_version_captured() {
+        let temp_dir = git_repo().await;
+        let mut package = package(temp_dir.path(), Some("1.0.0"), "");
+        let version_go = temp_dir.path().join("version.go");
+        fs::write(&version_go, "package m\n\nconst Version = \"1.0.0\"\n").unwrap();
+
+        let (result, changes) =
+            capture_writes(package.update_version(UpdateType::Patch, None)).await;
+
+        result.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, version_go);
+        assert_eq!(
+            fs::read_to_string(&version_go).unwrap(),
+            "package m\n\nconst Version = \"1.0.0\"\n"
+        );
+        assert!(
+            !temp_dir
+                .path()
+                .join(".git/refs/tags")
+                .join("v1.0.1")
+                .exists()
+        );
+        assert!(
+            run_git(temp_dir.path(), &["tag", "--list"])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_version_requires_major_suffix() {
+        let temp_dir = git_repo().await;
+        let mut package = package(temp_dir.path(), Some("1.4.0"), "");
+
+        let err = package
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("/v2"), "{err}");
+        assert_eq!(package.version(), Some("1.4.0"));
+    }
+
+    #[test]
+    fn test_publish_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let package = package(temp_dir.path(), Some("1.2.0"), "api/");
+        assert_eq!(package.language(), Language::Go);
+        assert_eq!(
+            package.default_publish_command(),
+            "git push origin refs/tags/api/v1.2.0"
+        );
+        assert_eq!(
+            package.default_dry_run_publish_command(),
+            Some("git push --dry-run origin refs/tags/api/v1.2.0".to_string())
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use anyhow::{Result, bail};
+use changepacks_utils::compare_versions;
+
+/// File whose `Version` constant is kept in step with the module version
+pub(crate) const VERSION_FILE: &str = "version.go";
+
+/// Highest `<prefix>vX.Y.Z` tag among `tags`, as `X.Y.Z`
+///
+/// Only full three-part versions count, as the Go toolchain ignores other tags.
+pub(crate) fn latest_tag_version(tags: &[String], prefix: &str) -> Option<String> {
+    tags.iter()
+        .filter_map(|tag| tag.strip_prefix(prefix)?.strip_prefix('v'))
+        .filter(|version| {
+            let core = version.split(['-', '+']).next().unwrap_or_default();
+            core.split('.').count() == 3 && compare_versions(version, version).is_some()
+        })
+        .max_by(|a, b| compare_versions(a, b).unwrap_or(Ordering::Equal))
+        .map(str::to_string)
+}
+
+/// Byte range of the string literal assigned to `Version` in a Go source file
+///
+/// Matches `const Version = "..."`, `var Version string = "..."` and `Version = "..."` lines
+/// of a `const (...)` block.
+pub(crate) fn version_constant(source: &str) -> Option<Range<usize>> {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let decl = line.trim_start();
+        let decl = decl
+            .strip_prefix("const ")
+            .or_else(|| decl.strip_prefix("var "))
+            .unwrap_or(decl)
+            .trim_start();
+        if let Some(rest) = decl.strip_prefix("Version")
+            && rest.starts_with([' ', '\t', '='])
+        {
+            let rest = rest.trim_start();
+            let rest = rest.strip_prefix("string").unwrap_or(rest).trim_start();
+            if let Some(literal) = rest
+                .strip_prefix('=')
+                .and_then(|value| value.trim_start().strip_prefix('"'))
+                && let Some(end) = literal.find('"')
+            {
+                let start = offset + line.len() - literal.len();
+                return Some(start..start + end);
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Fail unless `module` may carry `version`
+///
+/// From v2 on, Go requires the major version as the last element of the module path
+/// (`example.com/m/v2`, or `gopkg.in/m.v2`), so a major bump past v1 needs go.mod renamed first.
+///
+/// # Errors
+/// Returns error if the module path lacks the suffix of the version's major version.
+pub(crate) fn check_major_suffix(module: &str, version: &str) -> Result<()> {
+    let major: u64 = version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+        .unwrap_or_default();
+    if major >= 2
+        && !module.ends_with(&format!("/v{major}"))
+        && !module.ends_with(&format!(".v{major}"))
+    {
+        bail!(
+            "Go module {module} cannot be released as v{version}; rename it to end in /v{major} in go.mod first"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_tag_version() {
+        let tags: Vec<String> = [
+            "v1.2.0",
+            "v1.10.0",
+            "v1.11.0-rc.1",
+            "v2",
+            "api/v3.0.0",
+            "release-1",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(
+            latest_tag_version(&tags, ""),
+            Some("1.11.0-rc.1".to_string())
+        );
+        assert_eq!(latest_tag_version(&tags, "api/"), Some("3.0.0".to_string()));
+        assert_eq!(latest_tag_version(&tags, "web/"), None);
+        assert_eq!(latest_tag_version(&[], ""), None);
+    }
+
+    #[test]
+    fn test_version_constant() {
+        let source = "package api\n\n// Version of the API\nconst Version = \"1.2.0\"\n";
+        let range = version_constant(source).unwrap();
+        assert_eq!(&source[range], "1.2.0");
+
+        let source = "package api\n\nconst (\n\tName    = \"api\"\n\tVersion = \"v0.3.1\"\n)\n";
+        assert_eq!(&source[version_constant(source).unwrap()], "v0.3.1");
+
+        let source = "package api\n\nvar Version string = \"2.0.0\"\n";
+        assert_eq!(&source[version_constant(source).unwrap()], "2.0.0");
+
+        assert_eq!(version_constant("const VersionInfo = \"1.0.0\"\n"), None);
+        assert_eq!(version_constant("var Version = buildVersion()\n"), None);
+    }
+
+    #[test]
+    fn test_check_major_suffix() {
+        assert!(check_major_suffix("example.com/m", "1.9.0").is_ok());
+        assert!(check_major_suffix("example.com/m/v2", "2.1.0").is_ok());
+        assert!(check_major_suffix("gopkg.in/yaml.v3", "3.0.1").is_ok());
+        let err = check_major_suffix("example.com/m", "2.0.0")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("rename it to end in /v2"), "{err}");
+        assert!(check_major_suffix("example.com/m/v2", "3.0.0").is_err());
+    }
+}
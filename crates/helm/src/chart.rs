@@ -0,0 +1,197 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use changepacks_core::UpdateType;
+use changepacks_utils::{next_version_with_id, read_file, write_file};
+use serde_yaml::Value;
+
+/// Manifest of a Helm chart
+pub(crate) const CHART_FILE: &str = "Chart.yaml";
+
+/// Dependencies resolved from a sibling chart in the source tree, not a chart repository
+pub(crate) fn local_dependencies(chart: &Value) -> Vec<(usize, &str, Option<&str>)> {
+    chart
+        .get("dependencies")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter(|(_, dependency)| {
+            dependency
+                .get("repository")
+                .and_then(Value::as_str)
+                .is_some_and(|repository| repository.starts_with("file://"))
+        })
+        .filter_map(|(index, dependency)| {
+            Some((
+                index,
+                dependency.get("name")?.as_str()?,
+                dependency.get("version").and_then(Value::as_str),
+            ))
+        })
+        .collect()
+}
+
+/// Version string of a top-level `key`, which YAML may have parsed as a number
+pub(crate) fn version_field(chart: &Value, key: &str) -> Option<String> {
+    match chart.get(key)? {
+        Value::String(version) => Some(version.clone()),
+        Value::Number(version) => Some(version.to_string()),
+        _ => None,
+    }
+}
+
+/// Next `appVersion`, keeping a leading `v` (`v1.2.0` → `v1.3.0`)
+pub(crate) fn next_app_version(
+    app_version: &str,
+    update_type: UpdateType,
+    prerelease_id: Option<&str>,
+) -> Result<String> {
+    let (prefix, version) = app_version
+        .strip_prefix('v')
+        .map_or(("", app_version), |version| ("v", version));
+    Ok(format!(
+        "{prefix}{}",
+        next_version_with_id(version, update_type, prerelease_id)?
+    ))
+}
+
+/// `raw` with the scalar at `route` set to `value`, keeping its quotes
+///
+/// # Errors
+/// Returns error if `raw` is not YAML or has nothing at `route`.
+pub(crate) fn replace_scalar(raw: &str, route: &yamlpath::Route, value: &str) -> Result<String> {
+    let document = yamlpath::Document::new(raw).context("Failed to parse YAML")?;
+    let feature = document
+        .query_exact(route)?
+        .with_context(|| format!("No value at {route:?}"))?;
+    let (start, end) = feature.location.byte_span;
+    let quote = raw[start..end]
+        .chars()
+        .next()
+        .filter(|quote| matches!(quote, '"' | '\''));
+    let value = quote.map_or_else(
+        || value.to_string(),
+        |quote| format!("{quote}{value}{quote}"),
+    );
+    Ok(format!("{}{value}{}", &raw[..start], &raw[end..]))
+}
+
+/// Bump the chart `version` at `path` to `version`, and `appVersion` by `update_type` when
+/// `app_version` is set
+///
+/// # Errors
+/// Returns error if the chart cannot be read or written, or its `appVersion` is not semver.
+pub(crate) async fn write_chart_version(
+    path: &Path,
+    version: &str,
+    update_type: UpdateType,
+    prerelease_id: Option<&str>,
+    app_version: bool,
+) -> Result<()> {
+    let raw = read_file(path).await?;
+    let chart: Value = serde_yaml::from_str(&raw)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let mut updated = if chart.get("version").is_some() {
+        replace_scalar(&raw, &yamlpath::route!("version"), version)?
+    } else {
+        let patched = yamlpatch::apply_yaml_patches(
+            &yamlpath::Document::new(&raw).context("Failed to parse YAML")?,
+            &[yamlpatch::Patch {
+                operation: yamlpatch::Op::Add {
+                    key: "version".to_string(),
+                    value: Value::String(version.to_string()),
+                },
+                route: yamlpath::route!(),
+            }],
+        )?;
+        format!(
+            "{}{}",
+            patched.source().trim_end(),
+            if raw.ends_with('\n') { "\n" } else { "" }
+        )
+    };
+    if app_version && let Some(current) = version_field(&chart, "appVersion") {
+        let next = next_app_version(&current, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump appVersion of {}", path.display()))?;
+        updated = replace_scalar(&updated, &yamlpath::route!("appVersion"), &next)?;
+    }
+    write_file(path, updated).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CHART: &str = r#"apiVersion: v2
+name: platform
+version: 1.4.0
+appVersion: "v2.0.1"
+dependencies:
+  - name: redis
+    version: 19.x.x
+    repository: oci://registry-1.docker.io/bitnamicharts
+  - name: api
+    version: ~0.3.0
+    repository: file://../api
+  - name: common
+    repository: "file://../common"
+"#;
+
+    #[test]
+    fn test_local_dependencies() {
+        let chart: Value = serde_yaml::from_str(CHART).unwrap();
+        assert_eq!(
+            local_dependencies(&chart),
+            vec![(1, "api", Some("~0.3.0")), (2, "common", None)]
+        );
+        assert_eq!(version_field(&chart, "version"), Some("1.4.0".to_string()));
+        assert_eq!(
+            version_field(
+                &serde_yaml::from_str("appVersion: 1.16").unwrap(),
+                "appVersion"
+            ),
+            Some("1.16".to_string())
+        );
+    }
+
+    #[test]
+    fn test_next_app_version() {
+        assert_eq!(
+            next_app_version("v2.0.1", UpdateType::Minor, None).unwrap(),
+            "v2.1.0"
+        );
+        assert_eq!(
+            next_app_version("1.16.0", UpdateType::Patch, None).unwrap(),
+            "1.16.1"
+        );
+        assert!(next_app_version("latest", UpdateType::Patch, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_chart_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CHART_FILE);
+        fs::write(&path, CHART).unwrap();
+
+        write_chart_version(&path, "1.5.0", UpdateType::Minor, None, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            CHART.replace("version: 1.4.0", "version: 1.5.0")
+        );
+
+        write_chart_version(&path, "1.6.0", UpdateType::Minor, None, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            CHART
+                .replace("version: 1.4.0", "version: 1.6.0")
+                .replace("\"v2.0.1\"", "\"v2.1.0\"")
+        );
+    }
+}
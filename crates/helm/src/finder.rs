@@ -0,0 +1,203 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, project_key};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tokio::fs::read_to_string;
+
+use crate::chart::{CHART_FILE, local_dependencies, version_field};
+use crate::{package::HelmPackage, workspace::HelmWorkspace};
+
+#[derive(Debug)]
+pub struct HelmProjectFinder {
+    projects: HashMap<PathBuf, Project>,
+    project_files: Vec<&'static str>,
+    app_version: bool,
+}
+
+impl Default for HelmProjectFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HelmProjectFinder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            projects: HashMap::new(),
+            project_files: vec![CHART_FILE],
+            app_version: false,
+        }
+    }
+
+    /// Bump charts' `appVersion` along with their `version`
+    #[must_use]
+    pub fn with_app_version(mut self, app_version: bool) -> Self {
+        self.app_version = app_version;
+        self
+    }
+}
+
+#[async_trait]
+impl ProjectFinder for HelmProjectFinder {
+    fn projects(&self) -> Vec<&Project> {
+        self.projects.values().collect::<Vec<_>>()
+    }
+    fn projects_mut(&mut self) -> Vec<&mut Project> {
+        self.projects.values_mut().collect::<Vec<_>>()
+    }
+
+    fn project_files(&self) -> &[&str] {
+        &self.project_files
+    }
+
+    async fn visit(&mut self, path: &Path, relative_path: &Path) -> Result<()> {
+        if path.is_file()
+            && self.project_files().contains(
+                &path
+                    .file_name()
+                    .context(format!("File name not found - {}", path.display()))?
+                    .to_str()
+                    .context(format!("File name not found - {}", path.display()))?,
+            )
+        {
+            if self.projects.contains_key(&project_key(path)) {
+                return Ok(());
+            }
+            let chart: serde_yaml::Value = serde_yaml::from_str(&read_to_string(path).await?)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            let name = chart
+                .get("name")
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::to_string);
+            let version = version_field(&chart, "version");
+            let dependencies = local_dependencies(&chart);
+
+            let mut project = if dependencies.is_empty() {
+                Project::Package(Box::new(
+                    HelmPackage::new(
+                        name,
+                        version,
+                        path.to_path_buf(),
+                        relative_path.to_path_buf(),
+                    )
+                    .with_app_version(self.app_version),
+                ))
+            } else {
+                Project::Workspace(Box::new(
+                    HelmWorkspace::new(
+                        name,
+                        version,
+                        path.to_path_buf(),
+                        relative_path.to_path_buf(),
+                    )
+                    .with_app_version(self.app_version),
+                ))
+            };
+
+            let field = |key: &str| {
+                chart
+                    .get(key)
+                    .and_then(serde_yaml::Value::as_str)
+                    .map(str::to_string)
+            };
+            let source = chart
+                .get("sources")
+                .and_then(|sources| sources.get(0))
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::to_string);
+            project.set_metadata(PackageMetadata::new(
+                field("description"),
+                source.or_else(|| field("home")),
+                None,
+            ));
+            for (_, dependency, _) in dependencies {
+                project.add_dependency(dependency);
+            }
+            self.projects.insert(project_key(path), project);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_new() {
+        let finder = HelmProjectFinder::new();
+        assert_eq!(finder.project_files(), &["Chart.yaml"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_default() {
+        let finder = HelmProjectFinder::default();
+        assert_eq!(finder.project_files(), &["Chart.yaml"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_visit_charts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("charts/platform")).unwrap();
+        fs::create_dir_all(root.join("charts/api")).unwrap();
+        fs::write(
+            root.join("charts/platform/Chart.yaml"),
+            "apiVersion: v2\nname: platform\nversion: 2.0.0\ndependencies:\n  - name: api\n    version: \"^0.3.0\"\n    repository: file://../api\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("charts/api/Chart.yaml"),
+            "apiVersion: v2\nname: api\ndescription: API server\nversion: 0.3.0\nsources:\n  - https://github.com/acme/api\n",
+        )
+        .unwrap();
+
+        let mut finder = HelmProjectFinder::new();
+        for relative in ["charts/platform/Chart.yaml", "charts/api/Chart.yaml"] {
+            finder
+                .visit(&root.join(relative), &PathBuf::from(relative))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(finder.projects().len(), 2);
+        for project in finder.projects() {
+            match project {
+                Project::Workspace(ws) => {
+                    assert_eq!(ws.name(), Some("platform"));
+                    assert_eq!(ws.version(), Some("2.0.0"));
+                    assert!(ws.dependencies().contains("api"));
+                }
+                Project::Package(pkg) => {
+                    assert_eq!(pkg.name(), Some("api"));
+                    assert_eq!(pkg.version(), Some("0.3.0"));
+                    assert_eq!(pkg.description(), Some("API server"));
+                    assert_eq!(pkg.repository_url(), Some("https://github.com/acme/api"));
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visit_ignores_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("values.yaml");
+        fs::write(&path, "replicaCount: 1\n").unwrap();
+
+        let mut finder = HelmProjectFinder::new();
+        finder
+            .visit(&path, &PathBuf::from("values.yaml"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 0);
+    }
+}
@@ -0,0 +1,15 @@
+//! # changepacks-helm
+//!
+//! Helm chart support for changepacks.
+//!
+//! Implements project discovery and version management for `Chart.yaml`. Bumps rewrite the
+//! chart `version`, and the `appVersion` too when enabled. Charts that depend on sibling
+//! charts through `file://` repositories are umbrella charts, treated as workspaces that keep
+//! those dependency versions in step.
+
+mod chart;
+pub mod finder;
+pub mod package;
+pub mod workspace;
+
+pub use finder::HelmProjectFinder;
@@ -0,0 +1,201 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::next_version_with_id;
+
+use crate::chart::write_chart_version;
+
+#[derive(Debug)]
+pub struct HelmPackage {
+    name: Option<String>,
+    version: Option<String>,
+    path: PathBuf,
+    relative_path: PathBuf,
+    is_changed: bool,
+    metadata: PackageMetadata,
+    dependencies: HashSet<String>,
+    app_version: bool,
+}
+
+impl HelmPackage {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            name,
+            version,
+            path,
+            relative_path,
+            is_changed: false,
+            metadata: PackageMetadata::default(),
+            dependencies: HashSet::new(),
+            app_version: false,
+        }
+    }
+
+    /// Bump `appVersion` by the same update type as the chart `version`
+    #[must_use]
+    pub fn with_app_version(mut self, app_version: bool) -> Self {
+        self.app_version = app_version;
+        self
+    }
+}
+
+/// `helm push` target of a chart, read from `HELM_REGISTRY` (e.g. `oci://ghcr.io/acme/charts`)
+pub(crate) fn publish_command(name: Option<&str>, version: Option<&str>, flags: &str) -> String {
+    format!(
+        "helm package{flags} . && helm push {}-{}.tgz \"$HELM_REGISTRY\"",
+        name.unwrap_or_default(),
+        version.unwrap_or("0.0.0")
+    )
+}
+
+#[async_trait]
+impl Package for HelmPackage {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+        write_chart_version(
+            &self.path,
+            &new_version,
+            update_type,
+            prerelease_id,
+            self.app_version,
+        )
+        .await?;
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Helm
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn default_publish_command(&self) -> String {
+        publish_command(self.name.as_deref(), self.version.as_deref(), "")
+    }
+
+    // Linting renders the templates without packaging or pushing anything
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        Some("helm lint .".to_string())
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let chart_path = temp_dir.path().join("Chart.yaml");
+        fs::write(
+            &chart_path,
+            "apiVersion: v2\nname: api\n# chart release\nversion: 0.3.0\nappVersion: \"1.16.0\"\n",
+        )
+        .unwrap();
+        let mut package = HelmPackage::new(
+            Some("api".to_string()),
+            Some("0.3.0".to_string()),
+            chart_path.clone(),
+            PathBuf::from("Chart.yaml"),
+        )
+        .with_app_version(true);
+
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
+
+        assert_eq!(package.version(), Some("0.3.1"));
+        assert_eq!(
+            fs::read_to_string(&chart_path).unwrap(),
+            "apiVersion: v2\nname: api\n# chart release\nversion: 0.3.1\nappVersion: \"1.16.1\"\n"
+        );
+    }
+
+    #[test]
+    fn test_publish_commands() {
+        let package = HelmPackage::new(
+            Some("api".to_string()),
+            Some("0.3.0".to_string()),
+            PathBuf::from("Chart.yaml"),
+            PathBuf::from("Chart.yaml"),
+        );
+        assert_eq!(package.language(), Language::Helm);
+        assert_eq!(
+            package.default_publish_command(),
+            "helm package . && helm push api-0.3.0.tgz \"$HELM_REGISTRY\""
+        );
+        assert_eq!(
+            package.default_dry_run_publish_command(),
+            Some("helm lint .".to_string())
+        );
+    }
+}
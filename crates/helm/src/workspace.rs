@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{next_version_with_id, read_file, rewrite_version_range, write_file};
+
+use crate::chart::{local_dependencies, replace_scalar, write_chart_version};
+use crate::package::publish_command;
+
+/// Umbrella chart, which depends on sibling charts through `file://` repositories
+#[derive(Debug)]
+pub struct HelmWorkspace {
+    path: PathBuf,
+    relative_path: PathBuf,
+    version: Option<String>,
+    name: Option<String>,
+    is_changed: bool,
+    metadata: PackageMetadata,
+    dependencies: HashSet<String>,
+    app_version: bool,
+}
+
+impl HelmWorkspace {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            path,
+            relative_path,
+            name,
+            version,
+            is_changed: false,
+            metadata: PackageMetadata::default(),
+            dependencies: HashSet::new(),
+            app_version: false,
+        }
+    }
+
+    /// Bump `appVersion` by the same update type as the chart `version`
+    #[must_use]
+    pub fn with_app_version(mut self, app_version: bool) -> Self {
+        self.app_version = app_version;
+        self
+    }
+}
+
+#[async_trait]
+impl Workspace for HelmWorkspace {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+        write_chart_version(
+            &self.path,
+            &new_version,
+            update_type,
+            prerelease_id,
+            self.app_version,
+        )
+        .await?;
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Helm
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    // The sibling charts are vendored into charts/ before packaging
+    fn default_publish_command(&self) -> String {
+        publish_command(
+            self.name.as_deref(),
+            self.version.as_deref(),
+            " --dependency-update",
+        )
+    }
+
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        Some("helm lint .".to_string())
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+
+    /// Move the `version` constraints of bumped sibling charts to their new versions
+    async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
+        let raw = read_file(&self.path).await?;
+        let chart: serde_yaml::Value = serde_yaml::from_str(&raw)
+            .with_context(|| format!("Failed to parse {}", self.path.display()))?;
+
+        let mut updated = raw.clone();
+        for (index, name, current) in local_dependencies(&chart) {
+            let Some(version) = packages
+                .iter()
+                .filter(|package| package.language() == Language::Helm)
+                .find(|package| package.name() == Some(name))
+                .and_then(|package| package.version())
+            else {
+                continue;
+            };
+            if let Some(current) = current
+                && let Some(rewritten) = rewrite_version_range(current, version)
+                && rewritten != current
+            {
+                updated = replace_scalar(
+                    &updated,
+                    &yamlpath::route!("dependencies", index, "version"),
+                    &rewritten,
+                )?;
+            }
+        }
+        if updated != raw {
+            write_file(&self.path, updated).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::HelmPackage;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_workspace_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("platform")).unwrap();
+        fs::create_dir_all(root.join("api")).unwrap();
+        let umbrella = "apiVersion: v2\nname: platform\nversion: 1.0.0\ndependencies:\n  - name: api\n    version: \"0.3.0\"\n    repository: file://../api\n  - name: redis\n    version: 0.3.0\n    repository: oci://registry-1.docker.io/bitnamicharts\n";
+        fs::write(root.join("platform/Chart.yaml"), umbrella).unwrap();
+        fs::write(
+            root.join("api/Chart.yaml"),
+            "apiVersion: v2\nname: api\nversion: 0.3.0\n",
+        )
+        .unwrap();
+
+        let workspace = HelmWorkspace::new(
+            Some("platform".to_string()),
+            Some("1.0.0".to_string()),
+            root.join("platform/Chart.yaml"),
+            PathBuf::from("platform/Chart.yaml"),
+        );
+        let mut api = HelmPackage::new(
+            Some("api".to_string()),
+            Some("0.3.0".to_string()),
+            root.join("api/Chart.yaml"),
+            PathBuf::from("api/Chart.yaml"),
+        );
+        api.update_version(UpdateType::Minor, None).await.unwrap();
+        workspace
+            .update_workspace_dependencies(&[&api as &dyn Package])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("platform/Chart.yaml")).unwrap(),
+            umbrella.replace("\"0.3.0\"", "\"0.4.0\"")
+        );
+        assert_eq!(
+            workspace.default_publish_command(),
+            "helm package --dependency-update . && helm push platform-1.0.0.tgz \"$HELM_REGISTRY\""
+        );
+    }
+}
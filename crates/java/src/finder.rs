@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Project, ProjectFinder};
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, VersionCodeStrategy, project_key};
 use regex::Regex;
 use std::{
     collections::HashMap,
@@ -9,12 +9,13 @@ use std::{
 };
 use tokio::process::Command;
 
-use crate::{package::GradlePackage, workspace::GradleWorkspace};
+use crate::{android_version_name, package::GradlePackage, workspace::GradleWorkspace};
 
 #[derive(Debug)]
 pub struct GradleProjectFinder {
     projects: HashMap<PathBuf, Project>,
     project_files: Vec<&'static str>,
+    version_code: VersionCodeStrategy,
 }
 
 impl Default for GradleProjectFinder {
@@ -29,8 +30,16 @@ impl GradleProjectFinder {
         Self {
             projects: HashMap::new(),
             project_files: vec!["build.gradle.kts", "build.gradle"],
+            version_code: VersionCodeStrategy::default(),
         }
     }
+
+    /// Set how the `versionCode` of discovered Android modules changes on bumps
+    #[must_use]
+    pub fn with_version_code(mut self, version_code: VersionCodeStrategy) -> Self {
+        self.version_code = version_code;
+        self
+    }
 }
 
 /// Project info obtained from gradlew properties
@@ -38,6 +47,7 @@ impl GradleProjectFinder {
 struct GradleProperties {
     name: Option<String>,
     version: Option<String>,
+    description: Option<String>,
     has_subprojects: bool,
 }
 
@@ -178,6 +188,7 @@ async fn get_gradle_properties(project_dir: &Path) -> Result<GradleProperties> {
     let name_pattern = Regex::new(r"(?m)^name:\s*(.+)$").context("regex")?;
     let version_pattern = Regex::new(r"(?m)^version:\s*(.+)$").context("regex")?;
     let subprojects_pattern = Regex::new(r"(?m)^subprojects:\s*(.+)$").context("regex")?;
+    let description_pattern = Regex::new(r"(?m)^description:\s*(.+)$").context("regex")?;
 
     if let Some(caps) = name_pattern.captures(&stdout) {
         let name = caps.get(1).map(|m| m.as_str().trim().to_string());
@@ -193,6 +204,13 @@ async fn get_gradle_properties(project_dir: &Path) -> Result<GradleProperties> {
         }
     }
 
+    if let Some(caps) = description_pattern.captures(&stdout) {
+        let description = caps.get(1).map(|m| m.as_str().trim().to_string());
+        if description.as_deref() != Some("null") {
+            props.description = description;
+        }
+    }
+
     // Detect workspace: subprojects is non-empty (e.g. "[project ':sub1', project ':sub2']")
     if let Some(caps) = subprojects_pattern.captures(&stdout) {
         let value = caps.get(1).map(|m| m.as_str().trim()).unwrap_or("");
@@ -226,7 +244,7 @@ impl ProjectFinder for GradleProjectFinder {
                     .context(format!("File name not found - {}", path.display()))?,
             )
         {
-            if self.projects.contains_key(path) {
+            if self.projects.contains_key(&project_key(path)) {
                 return Ok(());
             }
 
@@ -245,14 +263,23 @@ impl ProjectFinder for GradleProjectFinder {
                     .map(std::string::ToString::to_string)
             });
 
-            let version = props.version;
+            // Android app modules usually leave `version` unset and declare `versionName`
+            let version = match props.version {
+                Some(version) => Some(version),
+                None => tokio::fs::read_to_string(path)
+                    .await
+                    .ok()
+                    .and_then(|content| android_version_name(&content)),
+            };
+            // Gradle has no standard repository or license property
+            let metadata = PackageMetadata::new(props.description, None, None);
 
             // Workspace detection: gradlew reports non-empty subprojects list.
             // Previous approach (checking for settings.gradle.kts existence) caused
             // false positives in composite builds and subprojects with IDE-generated files.
             let is_workspace = props.has_subprojects;
 
-            let (path, project) = if is_workspace {
+            let (path, mut project) = if is_workspace {
                 (
                     path.to_path_buf(),
                     Project::Workspace(Box::new(GradleWorkspace::new(
@@ -265,16 +292,20 @@ impl ProjectFinder for GradleProjectFinder {
             } else {
                 (
                     path.to_path_buf(),
-                    Project::Package(Box::new(GradlePackage::new(
-                        name,
-                        version,
-                        path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
+                    Project::Package(Box::new(
+                        GradlePackage::new(
+                            name,
+                            version,
+                            path.to_path_buf(),
+                            relative_path.to_path_buf(),
+                        )
+                        .with_version_code(self.version_code),
+                    )),
                 )
             };
 
-            self.projects.insert(path, project);
+            project.set_metadata(metadata);
+            self.projects.insert(project_key(&path), project);
         }
         Ok(())
     }
@@ -369,6 +400,50 @@ version = "1.0.0"
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_gradle_project_finder_visit_android_module() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("app");
+        fs::create_dir_all(&project_dir).unwrap();
+
+        let build_gradle = project_dir.join("build.gradle.kts");
+        fs::write(
+            &build_gradle,
+            r#"
+android {
+    defaultConfig {
+        versionCode = 3
+        versionName = "0.4.0"
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        create_mock_gradlew(&project_dir, "app", "unspecified");
+
+        let mut finder = GradleProjectFinder::new().with_version_code(VersionCodeStrategy::Semver);
+        finder
+            .visit(&build_gradle, &PathBuf::from("app/build.gradle.kts"))
+            .await
+            .unwrap();
+
+        let mut projects = finder.projects_mut();
+        assert_eq!(projects.len(), 1);
+        let Project::Package(pkg) = &mut projects[0] else {
+            panic!("Expected Package");
+        };
+        assert_eq!(pkg.version(), Some("0.4.0"));
+        pkg.update_version(changepacks_core::UpdateType::Patch, None)
+            .await
+            .unwrap();
+        let content = fs::read_to_string(&build_gradle).unwrap();
+        assert!(content.contains("versionCode = 4001"));
+        assert!(content.contains(r#"versionName = "0.4.1""#));
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_gradle_project_finder_visit_groovy_package() {
         let temp_dir = TempDir::new().unwrap();
@@ -3,7 +3,8 @@
 //! Java/Gradle project support for changepacks.
 //!
 //! Implements project discovery and version management for Gradle build files (build.gradle,
-//! build.gradle.kts). Handles both Groovy and Kotlin DSL syntax for version declarations.
+//! build.gradle.kts). Handles both Groovy and Kotlin DSL syntax for version declarations, and
+//! keeps Android `versionName`/`versionCode` in step with the version.
 //! Requires the Gradle wrapper (gradlew) for dynamic version detection.
 
 pub mod finder;
@@ -12,4 +13,6 @@ pub mod version_updater;
 pub mod workspace;
 
 pub use finder::GradleProjectFinder;
-pub use version_updater::{update_version_in_groovy, update_version_in_kts};
+pub use version_updater::{
+    android_version_name, update_android_version, update_version_in_groovy, update_version_in_kts,
+};
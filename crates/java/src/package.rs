@@ -1,12 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, Package, UpdateType};
-use changepacks_utils::next_version;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType, VersionCodeStrategy};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
 
-use crate::{update_version_in_groovy, update_version_in_kts};
+use crate::{update_android_version, update_version_in_groovy, update_version_in_kts};
 
 #[derive(Debug)]
 pub struct GradlePackage {
@@ -15,7 +14,9 @@ pub struct GradlePackage {
     path: PathBuf,
     relative_path: PathBuf,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    version_code: VersionCodeStrategy,
 }
 
 impl GradlePackage {
@@ -32,9 +33,18 @@ impl GradlePackage {
             path,
             relative_path,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            version_code: VersionCodeStrategy::default(),
         }
     }
+
+    /// Set how an Android module's `versionCode` changes on bumps
+    #[must_use]
+    pub fn with_version_code(mut self, version_code: VersionCodeStrategy) -> Self {
+        self.version_code = version_code;
+        self
+    }
 }
 
 #[async_trait]
@@ -55,11 +65,16 @@ impl Package for GradlePackage {
         &self.relative_path
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         let current_version = self.version.as_deref().unwrap_or("0.0.0");
-        let new_version = next_version(current_version, update_type)?;
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let content = read_to_string(&self.path).await?;
+        let content = read_file(&self.path).await?;
         let file_name = self
             .path
             .file_name()
@@ -74,8 +89,11 @@ impl Package for GradlePackage {
         } else {
             update_version_in_groovy(&content, &new_version)
         };
+        let updated_content =
+            update_android_version(&updated_content, &new_version, self.version_code)
+                .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        write(&self.path, updated_content).await?;
+        write_file(&self.path, updated_content).await?;
         self.version = Some(new_version);
         Ok(())
     }
@@ -96,6 +114,22 @@ impl Package for GradlePackage {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     #[cfg(windows)]
     fn default_publish_command(&self) -> String {
         ".\\gradlew.bat publish".to_string()
@@ -218,7 +252,10 @@ version = "1.0.0"
             PathBuf::from("myproject/build.gradle.kts"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"version = "1.0.1""#));
@@ -253,7 +290,10 @@ version = "1.0.0"
             PathBuf::from("myproject/build.gradle.kts"),
         );
 
-        package.update_version(UpdateType::Minor).await.unwrap();
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"version = "1.1.0""#));
@@ -288,7 +328,10 @@ version = "1.0.0"
             PathBuf::from("myproject/build.gradle.kts"),
         );
 
-        package.update_version(UpdateType::Major).await.unwrap();
+        package
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"version = "2.0.0""#));
@@ -323,7 +366,10 @@ version = '1.0.0'
             PathBuf::from("myproject/build.gradle"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains("version = '1.0.1'"));
@@ -354,7 +400,10 @@ version = project.findProperty("releaseVersion") ?: "1.0.11"
             PathBuf::from("myproject/build.gradle.kts"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"?: "1.0.12""#));
@@ -362,6 +411,44 @@ version = project.findProperty("releaseVersion") ?: "1.0.11"
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_gradle_package_update_version_android() {
+        let temp_dir = TempDir::new().unwrap();
+        let build_gradle = temp_dir.path().join("build.gradle");
+        fs::write(
+            &build_gradle,
+            r#"
+android {
+    defaultConfig {
+        versionCode 12
+        versionName "1.2.3"
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let mut package = GradlePackage::new(
+            Some("app".to_string()),
+            Some("1.2.3".to_string()),
+            build_gradle.clone(),
+            PathBuf::from("app/build.gradle"),
+        )
+        .with_version_code(VersionCodeStrategy::Semver);
+
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
+
+        let content = read_to_string(&build_gradle).await.unwrap();
+        assert!(content.contains("versionCode 1003000"));
+        assert!(content.contains(r#"versionName "1.3.0""#));
+        assert_eq!(package.version(), Some("1.3.0"));
+
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn test_gradle_package_dependencies() {
         let mut package = GradlePackage::new(
@@ -1,4 +1,6 @@
-use regex::Regex;
+use anyhow::{Context, Result, bail};
+use changepacks_core::VersionCodeStrategy;
+use regex::{Captures, Regex};
 use std::sync::LazyLock;
 
 static KTS_SIMPLE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
@@ -58,6 +60,89 @@ pub fn update_version_in_groovy(content: &str, new_version: &str) -> String {
     content.to_string()
 }
 
+static VERSION_CODE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^([ \t]*versionCode\s*=?[ \t]*)(\d+)\b").expect("hardcoded regex must compile")
+});
+
+static VERSION_NAME_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^([ \t]*versionName\s*=?[ \t]*)(["'])([^"'\n]*)["']"#)
+        .expect("hardcoded regex must compile")
+});
+
+/// Largest `versionCode` Google Play accepts
+const MAX_VERSION_CODE: u64 = 2_100_000_000;
+
+/// `versionName` declared in an Android module's build file (Groovy or Kotlin DSL)
+#[must_use]
+pub fn android_version_name(content: &str) -> Option<String> {
+    VERSION_NAME_PATTERN
+        .captures(content)
+        .map(|caps| caps[3].to_string())
+}
+
+/// Rewrite `versionName` to `new_version` and bump every `versionCode` by `strategy`
+///
+/// Content without Android version declarations is returned unchanged.
+///
+/// # Errors
+/// Returns error if the semver strategy can't encode `new_version` or a code would exceed the
+/// Play Store maximum.
+pub fn update_android_version(
+    content: &str,
+    new_version: &str,
+    strategy: VersionCodeStrategy,
+) -> Result<String> {
+    let content = VERSION_NAME_PATTERN.replace_all(content, |caps: &Captures| {
+        format!("{}{}{new_version}{}", &caps[1], &caps[2], &caps[2])
+    });
+    if !VERSION_CODE_PATTERN.is_match(&content) {
+        return Ok(content.into_owned());
+    }
+
+    let semver_code = match strategy {
+        VersionCodeStrategy::Increment => None,
+        VersionCodeStrategy::Semver => Some(version_code_from_semver(new_version)?),
+    };
+    let mut overflow = None;
+    let content = VERSION_CODE_PATTERN.replace_all(&content, |caps: &Captures| {
+        let code = semver_code.unwrap_or_else(|| caps[2].parse::<u64>().map_or(0, |c| c + 1));
+        if code > MAX_VERSION_CODE {
+            overflow = Some(code);
+        }
+        format!("{}{code}", &caps[1])
+    });
+    if let Some(code) = overflow {
+        bail!("versionCode {code} exceeds the Play Store maximum of {MAX_VERSION_CODE}");
+    }
+    Ok(content.into_owned())
+}
+
+/// `major * 1_000_000 + minor * 1_000 + patch`; build metadata is ignored
+///
+/// Pre-releases are refused: their code would repeat the one of the release they lead up to.
+fn version_code_from_semver(version: &str) -> Result<u64> {
+    let core = version.split('+').next().unwrap_or_default();
+    if core.contains('-') {
+        bail!(
+            "Cannot derive a versionCode from pre-release '{version}'; use the \"increment\" androidVersionCode strategy for pre-releases"
+        );
+    }
+    let parts = core
+        .split('.')
+        .map(str::parse::<u64>)
+        .collect::<Result<Vec<_>, _>>()
+        .ok()
+        .filter(|parts| parts.len() == 3)
+        .with_context(|| format!("Cannot derive a versionCode from version '{version}'"))?;
+    let (major, minor, patch) = (parts[0], parts[1], parts[2]);
+    if minor >= 1_000 || patch >= 1_000 {
+        bail!(
+            "Cannot derive a versionCode from version '{version}': minor and patch must be below 1000"
+        );
+    }
+    Ok(major * 1_000_000 + minor * 1_000 + patch)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,4 +218,66 @@ group = 'com.example'
         let result = update_version_in_groovy(content, "2.0.0");
         assert_eq!(result, content);
     }
+
+    #[test]
+    fn test_update_android_version_increment() {
+        let content = r#"
+android {
+    defaultConfig {
+        applicationId "com.example.app"
+        versionCode 41
+        versionName "1.2.3"
+    }
+}
+"#;
+        let updated =
+            update_android_version(content, "1.3.0", VersionCodeStrategy::Increment).unwrap();
+        assert!(updated.contains("        versionCode 42\n"));
+        assert!(updated.contains(r#"        versionName "1.3.0""#));
+        assert_eq!(android_version_name(content).as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn test_update_android_version_semver_kts() {
+        let content = r#"
+android {
+    defaultConfig {
+        versionCode = 7
+        versionName = "1.2.3"
+    }
+}
+"#;
+        let updated =
+            update_android_version(content, "2.10.4", VersionCodeStrategy::Semver).unwrap();
+        assert!(updated.contains("versionCode = 2010004\n"));
+        assert!(updated.contains(r#"versionName = "2.10.4""#));
+    }
+
+    #[test]
+    fn test_update_android_version_errors() {
+        let content = "versionCode 1\n";
+        let err =
+            update_android_version(content, "1.1000.0", VersionCodeStrategy::Semver).unwrap_err();
+        assert!(err.to_string().contains("below 1000"));
+        let err =
+            update_android_version(content, "2101.0.0", VersionCodeStrategy::Semver).unwrap_err();
+        assert!(err.to_string().contains("exceeds the Play Store maximum"));
+        let err = update_android_version(content, "1.2.1-beta.0", VersionCodeStrategy::Semver)
+            .unwrap_err();
+        assert!(err.to_string().contains("pre-release '1.2.1-beta.0'"));
+        let updated =
+            update_android_version(content, "1.2.1-beta.0", VersionCodeStrategy::Increment)
+                .unwrap();
+        assert_eq!(updated, "versionCode 2\n");
+    }
+
+    #[test]
+    fn test_update_android_version_without_android_block() {
+        let content = "group = 'com.example'\nversion = '1.0.0'\n";
+        assert_eq!(
+            update_android_version(content, "1.0.1", VersionCodeStrategy::Increment).unwrap(),
+            content
+        );
+        assert_eq!(android_version_name(content), None);
+    }
 }
@@ -1,10 +1,9 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, UpdateType, Workspace};
-use changepacks_utils::next_version;
+use changepacks_core::{Language, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
 
 use crate::{update_version_in_groovy, update_version_in_kts};
 
@@ -15,6 +14,7 @@ pub struct GradleWorkspace {
     version: Option<String>,
     name: Option<String>,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
 }
 
@@ -32,6 +32,7 @@ impl GradleWorkspace {
             name,
             version,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
         }
     }
@@ -51,11 +52,16 @@ impl Workspace for GradleWorkspace {
         self.version.as_deref()
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         let current_version = self.version.as_deref().unwrap_or("0.0.0");
-        let new_version = next_version(current_version, update_type)?;
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let content = read_to_string(&self.path).await?;
+        let content = read_file(&self.path).await?;
         let file_name = self
             .path
             .file_name()
@@ -71,7 +77,7 @@ impl Workspace for GradleWorkspace {
             update_version_in_groovy(&content, &new_version)
         };
 
-        write(&self.path, updated_content).await?;
+        write_file(&self.path, updated_content).await?;
         self.version = Some(new_version);
         Ok(())
     }
@@ -96,6 +102,22 @@ impl Workspace for GradleWorkspace {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     #[cfg(windows)]
     fn default_publish_command(&self) -> String {
         ".\\gradlew.bat publish".to_string()
@@ -231,7 +253,10 @@ version = "1.0.0"
             PathBuf::from("multiproject/build.gradle.kts"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"version = "1.0.1""#));
@@ -266,7 +291,10 @@ version = "1.0.0"
             PathBuf::from("multiproject/build.gradle.kts"),
         );
 
-        workspace.update_version(UpdateType::Minor).await.unwrap();
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"version = "1.1.0""#));
@@ -301,7 +329,10 @@ version = "1.0.0"
             PathBuf::from("multiproject/build.gradle.kts"),
         );
 
-        workspace.update_version(UpdateType::Major).await.unwrap();
+        workspace
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"version = "2.0.0""#));
@@ -336,7 +367,10 @@ version = '1.0.0'
             PathBuf::from("multiproject/build.gradle"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains("version = '1.0.1'"));
@@ -371,7 +405,10 @@ version = "0.0.0"
             PathBuf::from("multiproject/build.gradle.kts"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&build_gradle).await.unwrap();
         assert!(content.contains(r#"version = "0.0.1""#));
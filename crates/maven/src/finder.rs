@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, project_key};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tokio::fs::read_to_string;
+
+use crate::pom::{POM_FILE, Pom};
+use crate::{package::MavenPackage, workspace::MavenWorkspace};
+
+#[derive(Debug)]
+pub struct MavenProjectFinder {
+    projects: HashMap<PathBuf, Project>,
+    project_files: Vec<&'static str>,
+}
+
+impl Default for MavenProjectFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MavenProjectFinder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            projects: HashMap::new(),
+            project_files: vec![POM_FILE],
+        }
+    }
+}
+
+#[async_trait]
+impl ProjectFinder for MavenProjectFinder {
+    fn projects(&self) -> Vec<&Project> {
+        self.projects.values().collect::<Vec<_>>()
+    }
+    fn projects_mut(&mut self) -> Vec<&mut Project> {
+        self.projects.values_mut().collect::<Vec<_>>()
+    }
+
+    fn project_files(&self) -> &[&str] {
+        &self.project_files
+    }
+
+    async fn visit(&mut self, path: &Path, relative_path: &Path) -> Result<()> {
+        if path.is_file()
+            && self.project_files().contains(
+                &path
+                    .file_name()
+                    .context(format!("File name not found - {}", path.display()))?
+                    .to_str()
+                    .context(format!("File name not found - {}", path.display()))?,
+            )
+        {
+            if self.projects.contains_key(&project_key(path)) {
+                return Ok(());
+            }
+            let pom = Pom::parse(&read_to_string(path).await?)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+            // A module without a <version> takes its parent's; it is bumped through the
+            // parent when that pom is part of the source tree
+            let parent_pom = pom.parent_path(path).filter(|parent| parent.is_file());
+            let version = pom
+                .version
+                .as_ref()
+                .or(pom.parent_version.as_ref())
+                .map(|version| version.value.clone());
+
+            let mut project = if pom.modules.is_empty() {
+                let package = MavenPackage::new(
+                    pom.artifact_id.clone(),
+                    version,
+                    path.to_path_buf(),
+                    relative_path.to_path_buf(),
+                );
+                Project::Package(Box::new(match parent_pom {
+                    Some(parent_pom) if pom.version.is_none() => {
+                        package.with_parent_version(parent_pom)
+                    }
+                    _ => package,
+                }))
+            } else {
+                Project::Workspace(Box::new(
+                    MavenWorkspace::new(
+                        pom.artifact_id.clone(),
+                        version,
+                        path.to_path_buf(),
+                        relative_path.to_path_buf(),
+                    )
+                    .with_modules(pom.module_paths(path)),
+                ))
+            };
+
+            project.set_metadata(PackageMetadata::new(
+                pom.description.clone(),
+                pom.url.clone(),
+                pom.license.clone(),
+            ));
+            for dependency in pom.dependencies.iter().filter(|d| !d.managed) {
+                if let Some(artifact_id) = &dependency.artifact_id {
+                    project.add_dependency(artifact_id);
+                }
+            }
+            self.projects.insert(project_key(path), project);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_new() {
+        let finder = MavenProjectFinder::new();
+        assert_eq!(finder.project_files(), &["pom.xml"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_default() {
+        let finder = MavenProjectFinder::default();
+        assert_eq!(finder.project_files(), &["pom.xml"]);
+        assert_eq!(finder.projects().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_visit_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_path = temp_dir.path().join("pom.xml");
+        fs::write(
+            &pom_path,
+            r"<project>
+  <groupId>com.acme</groupId>
+  <artifactId>acme-core</artifactId>
+  <version>1.2.0</version>
+  <description>Core library</description>
+  <url>https://github.com/acme/core</url>
+  <dependencies>
+    <dependency>
+      <artifactId>acme-util</artifactId>
+      <version>1.0.0</version>
+    </dependency>
+  </dependencies>
+</project>
+",
+        )
+        .unwrap();
+
+        let mut finder = MavenProjectFinder::new();
+        finder
+            .visit(&pom_path, &PathBuf::from("pom.xml"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 1);
+        match finder.projects()[0] {
+            Project::Package(pkg) => {
+                assert_eq!(pkg.name(), Some("acme-core"));
+                assert_eq!(pkg.version(), Some("1.2.0"));
+                assert!(!pkg.inherits_workspace_version());
+                assert!(pkg.dependencies().contains("acme-util"));
+                assert_eq!(pkg.description(), Some("Core library"));
+                assert_eq!(pkg.repository_url(), Some("https://github.com/acme/core"));
+            }
+            Project::Workspace(_) => panic!("Expected Package"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visit_aggregator_and_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("pom.xml"),
+            "<project><artifactId>parent</artifactId><version>3.0.0</version><packaging>pom</packaging><modules><module>core</module></modules></project>",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("core")).unwrap();
+        fs::write(
+            root.join("core/pom.xml"),
+            "<project><parent><artifactId>parent</artifactId><version>3.0.0</version></parent><artifactId>core</artifactId></project>",
+        )
+        .unwrap();
+
+        let mut finder = MavenProjectFinder::new();
+        finder
+            .visit(&root.join("pom.xml"), &PathBuf::from("pom.xml"))
+            .await
+            .unwrap();
+        finder
+            .visit(&root.join("core/pom.xml"), &PathBuf::from("core/pom.xml"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 2);
+        for project in finder.projects() {
+            match project {
+                Project::Workspace(ws) => {
+                    assert_eq!(ws.name(), Some("parent"));
+                    assert_eq!(ws.version(), Some("3.0.0"));
+                }
+                Project::Package(pkg) => {
+                    assert_eq!(pkg.name(), Some("core"));
+                    assert_eq!(pkg.version(), Some("3.0.0"));
+                    assert!(pkg.inherits_workspace_version());
+                    assert_eq!(
+                        pkg.workspace_root_path(),
+                        Some(root.join("pom.xml").as_path())
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visit_external_parent() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_path = temp_dir.path().join("pom.xml");
+        fs::write(
+            &pom_path,
+            "<project><parent><artifactId>spring-boot-starter-parent</artifactId><version>3.3.0</version><relativePath/></parent><artifactId>app</artifactId><version>0.1.0</version></project>",
+        )
+        .unwrap();
+
+        let mut finder = MavenProjectFinder::new();
+        finder
+            .visit(&pom_path, &PathBuf::from("pom.xml"))
+            .await
+            .unwrap();
+
+        let project = finder.projects()[0];
+        assert_eq!(project.version(), Some("0.1.0"));
+        assert!(project.dependencies().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_visit_ignores_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("build.xml");
+        fs::write(&path, "<project/>").unwrap();
+
+        let mut finder = MavenProjectFinder::new();
+        finder
+            .visit(&path, &PathBuf::from("build.xml"))
+            .await
+            .unwrap();
+
+        assert_eq!(finder.projects().len(), 0);
+    }
+}
@@ -0,0 +1,16 @@
+//! # changepacks-maven
+//!
+//! Maven project support for changepacks.
+//!
+//! Implements project discovery and version management for pom.xml files. Uses quick-xml to
+//! locate elements and rewrites only their text, so the rest of the file keeps its formatting.
+//! Aggregator poms (with `<modules>`) are workspaces: modules without a `<version>` of their
+//! own inherit the parent's, and bumps keep the modules' `<parent>` and sibling
+//! `<dependency>` versions in step.
+
+pub mod finder;
+pub mod package;
+mod pom;
+pub mod workspace;
+
+pub use finder::MavenProjectFinder;
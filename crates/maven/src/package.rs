@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::next_version_with_id;
+
+use crate::pom::write_pom_version;
+
+#[derive(Debug)]
+pub struct MavenPackage {
+    name: Option<String>,
+    version: Option<String>,
+    path: PathBuf,
+    relative_path: PathBuf,
+    is_changed: bool,
+    metadata: PackageMetadata,
+    dependencies: HashSet<String>,
+    parent_pom: Option<PathBuf>,
+}
+
+impl MavenPackage {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            name,
+            version,
+            path,
+            relative_path,
+            is_changed: false,
+            metadata: PackageMetadata::default(),
+            dependencies: HashSet::new(),
+            parent_pom: None,
+        }
+    }
+
+    /// Mark the version as inherited from the parent pom at `parent_pom`, which is bumped
+    /// in this module's place
+    #[must_use]
+    pub fn with_parent_version(mut self, parent_pom: PathBuf) -> Self {
+        self.parent_pom = Some(parent_pom);
+        self
+    }
+}
+
+#[async_trait]
+impl Package for MavenPackage {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+        write_pom_version(&self.path, &new_version).await?;
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Java
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn inherits_workspace_version(&self) -> bool {
+        self.parent_pom.is_some()
+    }
+
+    fn workspace_root_path(&self) -> Option<&Path> {
+        self.parent_pom.as_deref()
+    }
+
+    fn default_publish_command(&self) -> String {
+        "mvn -B deploy".to_string()
+    }
+
+    // Installing into the local repository runs the same build without uploading
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        Some("mvn -B install".to_string())
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_path = temp_dir.path().join("pom.xml");
+        fs::write(
+            &pom_path,
+            "<project>\n  <artifactId>core</artifactId>\n  <version>1.0.0-SNAPSHOT</version>\n</project>\n",
+        )
+        .unwrap();
+        let mut package = MavenPackage::new(
+            Some("core".to_string()),
+            Some("1.0.0-SNAPSHOT".to_string()),
+            pom_path.clone(),
+            PathBuf::from("pom.xml"),
+        );
+
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
+        assert_eq!(package.version(), Some("1.0.0"));
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
+
+        assert_eq!(package.version(), Some("1.1.0"));
+        assert_eq!(
+            fs::read_to_string(&pom_path).unwrap(),
+            "<project>\n  <artifactId>core</artifactId>\n  <version>1.1.0</version>\n</project>\n"
+        );
+    }
+
+    #[test]
+    fn test_parent_version() {
+        let package = MavenPackage::new(
+            Some("core".to_string()),
+            Some("1.0.0".to_string()),
+            PathBuf::from("/repo/core/pom.xml"),
+            PathBuf::from("core/pom.xml"),
+        );
+        assert!(!package.inherits_workspace_version());
+        assert_eq!(package.language(), Language::Java);
+        assert_eq!(package.default_publish_command(), "mvn -B deploy");
+
+        let package = package.with_parent_version(PathBuf::from("/repo/pom.xml"));
+        assert!(package.inherits_workspace_version());
+        assert_eq!(
+            package.workspace_root_path(),
+            Some(Path::new("/repo/pom.xml"))
+        );
+    }
+}
@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use changepacks_utils::{read_file, rewrite_version_range, write_file};
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+
+/// Manifest file of a Maven project
+pub(crate) const POM_FILE: &str = "pom.xml";
+
+/// Text of a pom element, with the byte range it occupies in the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PomText {
+    pub value: String,
+    pub range: Range<usize>,
+}
+
+/// A `<dependency>` of the project or its `<dependencyManagement>`
+#[derive(Debug, Default)]
+pub(crate) struct PomDependency {
+    pub artifact_id: Option<String>,
+    pub version: Option<PomText>,
+    /// Declared under `<dependencyManagement>`, so it pins a version without adding a dependency
+    pub managed: bool,
+}
+
+/// The parts of a pom.xml changepacks reads and rewrites
+#[derive(Debug, Default)]
+pub(crate) struct Pom {
+    pub artifact_id: Option<String>,
+    pub version: Option<PomText>,
+    pub parent_artifact_id: Option<String>,
+    pub parent_version: Option<PomText>,
+    pub parent_relative_path: Option<String>,
+    pub modules: Vec<String>,
+    pub dependencies: Vec<PomDependency>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub license: Option<String>,
+}
+
+const DEPENDENCY: &str = "project/dependencies/dependency";
+const MANAGED_DEPENDENCY: &str = "project/dependencyManagement/dependencies/dependency";
+
+impl Pom {
+    /// Parse the elements of `content` changepacks cares about
+    ///
+    /// # Errors
+    /// Returns error if `content` is not well-formed XML.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(content);
+        let mut pom = Self::default();
+        // Open elements with the offset their content starts at
+        let mut stack: Vec<(String, usize)> = Vec::new();
+        let mut dependency: Option<PomDependency> = None;
+
+        loop {
+            let before = usize::try_from(reader.buffer_position())?;
+            match reader.read_event().context("Failed to parse pom.xml")? {
+                Event::Start(e) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                    stack.push((name, usize::try_from(reader.buffer_position())?));
+                    match element_path(&stack).as_str() {
+                        DEPENDENCY => dependency = Some(PomDependency::default()),
+                        MANAGED_DEPENDENCY => {
+                            dependency = Some(PomDependency {
+                                managed: true,
+                                ..PomDependency::default()
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(_) => {
+                    let path = element_path(&stack);
+                    let Some((_, start)) = stack.pop() else {
+                        break;
+                    };
+                    let text = || element_text(content, start..before);
+                    match path.as_str() {
+                        "project/artifactId" => pom.artifact_id = Some(text()?.value),
+                        "project/version" => pom.version = Some(text()?),
+                        "project/parent/artifactId" => pom.parent_artifact_id = Some(text()?.value),
+                        "project/parent/version" => pom.parent_version = Some(text()?),
+                        "project/parent/relativePath" => {
+                            pom.parent_relative_path = Some(text()?.value);
+                        }
+                        "project/modules/module" => pom.modules.push(text()?.value),
+                        "project/description" => pom.description = Some(text()?.value),
+                        "project/url" => pom.url = Some(text()?.value),
+                        "project/licenses/license/name" if pom.license.is_none() => {
+                            pom.license = Some(text()?.value);
+                        }
+                        DEPENDENCY | MANAGED_DEPENDENCY => {
+                            pom.dependencies.extend(dependency.take());
+                        }
+                        path => {
+                            if let Some(dependency) = dependency.as_mut()
+                                && let Some(field) = path
+                                    .strip_prefix(DEPENDENCY)
+                                    .or_else(|| path.strip_prefix(MANAGED_DEPENDENCY))
+                            {
+                                match field {
+                                    "/artifactId" => dependency.artifact_id = Some(text()?.value),
+                                    "/version" => dependency.version = Some(text()?),
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(pom)
+    }
+
+    /// Path of the parent pom, following `<relativePath>` (default `../pom.xml`)
+    ///
+    /// `None` when the pom has no `<parent>` or an empty `<relativePath/>` says the parent
+    /// comes from a repository rather than the source tree.
+    pub fn parent_path(&self, pom_path: &Path) -> Option<PathBuf> {
+        self.parent_artifact_id.as_ref()?;
+        let relative_path = match self.parent_relative_path.as_deref() {
+            Some("") => return None,
+            Some(relative_path) => relative_path,
+            None => "../pom.xml",
+        };
+        let path = lexical_join(pom_path.parent()?, relative_path);
+        Some(if path.is_dir() {
+            path.join(POM_FILE)
+        } else {
+            path
+        })
+    }
+
+    /// Pom paths of the `<modules>` of the pom at `pom_path`
+    pub fn module_paths(&self, pom_path: &Path) -> Vec<PathBuf> {
+        let Some(dir) = pom_path.parent() else {
+            return Vec::new();
+        };
+        self.modules
+            .iter()
+            .map(|module| {
+                let path = lexical_join(dir, module);
+                if module.ends_with(".xml") {
+                    path
+                } else {
+                    path.join(POM_FILE)
+                }
+            })
+            .collect()
+    }
+}
+
+/// `base` joined with `relative`, resolving `..` and `.` so paths name projects the same way
+/// discovery does
+fn lexical_join(base: &Path, relative: &str) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::CurDir => {}
+            component => path.push(component),
+        }
+    }
+    path
+}
+
+/// `/`-joined names of the open elements
+fn element_path(stack: &[(String, usize)]) -> String {
+    stack
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Unescaped, trimmed text in `range` of `content`, with the range narrowed to the trimmed text
+fn element_text(content: &str, range: Range<usize>) -> Result<PomText> {
+    let raw = &content[range.clone()];
+    let start = range.start + (raw.len() - raw.trim_start().len());
+    let trimmed = raw.trim();
+    Ok(PomText {
+        value: unescape(trimmed)
+            .context("Failed to unescape pom.xml text")?
+            .into_owned(),
+        range: start..start + trimmed.len(),
+    })
+}
+
+/// `content` with each range replaced by its text
+pub(crate) fn replace_ranges(content: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut updated = content.to_string();
+    for (range, text) in edits {
+        updated.replace_range(range, &text);
+    }
+    updated
+}
+
+/// Replace the project `<version>` of the pom at `pom_path` with `version`
+///
+/// # Errors
+/// Returns error if the pom cannot be read or parsed, declares no `<version>` of its own, or
+/// cannot be written.
+pub(crate) async fn write_pom_version(pom_path: &Path, version: &str) -> Result<()> {
+    let content = read_file(pom_path).await?;
+    let pom =
+        Pom::parse(&content).with_context(|| format!("Failed to parse {}", pom_path.display()))?;
+    let current = pom.version.with_context(|| {
+        format!(
+            "{} has no <version> of its own; bump the parent pom it inherits its version from",
+            pom_path.display()
+        )
+    })?;
+    write_file(
+        pom_path,
+        replace_ranges(&content, vec![(current.range, version.to_string())]),
+    )
+    .await
+}
+
+/// Point the pom at `pom_path` at the current versions of what it references
+///
+/// `<dependency>` versions of artifacts in `versions` are rewritten in their existing style,
+/// property references like `${project.version}` are left alone, and a `<parent>` found in the
+/// source tree gets the parent pom's `<version>`. The file is only written when something
+/// changed.
+///
+/// # Errors
+/// Returns error if the pom or its parent cannot be read or parsed, or the pom cannot be written.
+pub(crate) async fn sync_pom_references(
+    pom_path: &Path,
+    versions: &HashMap<&str, &str>,
+) -> Result<()> {
+    let content = read_file(pom_path).await?;
+    let pom =
+        Pom::parse(&content).with_context(|| format!("Failed to parse {}", pom_path.display()))?;
+    let mut edits = Vec::new();
+    for dependency in &pom.dependencies {
+        if let (Some(artifact_id), Some(current)) = (&dependency.artifact_id, &dependency.version)
+            && let Some(version) = versions.get(artifact_id.as_str())
+            && let Some(rewritten) = rewrite_version_range(&current.value, version)
+            && rewritten != current.value
+        {
+            edits.push((current.range.clone(), rewritten));
+        }
+    }
+    if let Some(current) = &pom.parent_version
+        && let Some(parent_path) = pom.parent_path(pom_path).filter(|path| path.is_file())
+    {
+        let parent = Pom::parse(&read_file(&parent_path).await?)
+            .with_context(|| format!("Failed to parse {}", parent_path.display()))?;
+        if parent.artifact_id == pom.parent_artifact_id
+            && let Some(version) = parent.version
+            && version.value != current.value
+        {
+            edits.push((current.range.clone(), version.value));
+        }
+    }
+    if !edits.is_empty() {
+        write_file(pom_path, replace_ranges(&content, edits)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <parent>
+    <groupId>com.acme</groupId>
+    <artifactId>acme-parent</artifactId>
+    <version>1.0.0</version>
+  </parent>
+  <artifactId>acme-api</artifactId>
+  <version> 1.2.0 </version>
+  <description>API &amp; models</description>
+  <licenses>
+    <license><name>Apache-2.0</name></license>
+  </licenses>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <artifactId>acme-bom</artifactId>
+        <version>[2.0.0]</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>com.acme</groupId>
+      <artifactId>acme-core</artifactId>
+      <version>1.1.0</version>
+      <exclusions>
+        <exclusion><artifactId>ignored</artifactId></exclusion>
+      </exclusions>
+    </dependency>
+    <dependency>
+      <artifactId>acme-util</artifactId>
+      <version>${project.version}</version>
+    </dependency>
+  </dependencies>
+  <build>
+    <plugins>
+      <plugin>
+        <artifactId>maven-compiler-plugin</artifactId>
+        <version>3.13.0</version>
+      </plugin>
+    </plugins>
+  </build>
+</project>
+"#;
+
+    #[test]
+    fn test_parse() {
+        let pom = Pom::parse(POM).unwrap();
+        assert_eq!(pom.artifact_id.as_deref(), Some("acme-api"));
+        let version = pom.version.unwrap();
+        assert_eq!(version.value, "1.2.0");
+        assert_eq!(&POM[version.range], "1.2.0");
+        assert_eq!(pom.parent_artifact_id.as_deref(), Some("acme-parent"));
+        assert_eq!(pom.parent_version.unwrap().value, "1.0.0");
+        assert_eq!(pom.description.as_deref(), Some("API & models"));
+        assert_eq!(pom.license.as_deref(), Some("Apache-2.0"));
+        assert!(pom.modules.is_empty());
+        let dependencies: Vec<_> = pom
+            .dependencies
+            .iter()
+            .map(|d| {
+                (
+                    d.artifact_id.as_deref(),
+                    d.version.as_ref().map(|v| v.value.as_str()),
+                    d.managed,
+                )
+            })
+            .collect();
+        assert_eq!(
+            dependencies,
+            vec![
+                (Some("acme-bom"), Some("[2.0.0]"), true),
+                (Some("acme-core"), Some("1.1.0"), false),
+                (Some("acme-util"), Some("${project.version}"), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Pom::parse("<project><version>1.0.0</project>").is_err());
+    }
+
+    #[test]
+    fn test_parent_and_module_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("parent")).unwrap();
+        let pom_path = root.join("api/pom.xml");
+
+        let mut pom = Pom::parse(
+            "<project><modules><module>core</module><module>alt/pom-alt.xml</module></modules></project>",
+        )
+        .unwrap();
+        assert_eq!(pom.parent_path(&pom_path), None);
+        assert_eq!(
+            pom.module_paths(&pom_path),
+            vec![
+                root.join("api/core/pom.xml"),
+                root.join("api/alt/pom-alt.xml")
+            ]
+        );
+
+        pom.parent_artifact_id = Some("parent".to_string());
+        assert_eq!(pom.parent_path(&pom_path), Some(root.join("pom.xml")));
+        pom.parent_relative_path = Some("../parent".to_string());
+        assert_eq!(
+            pom.parent_path(&pom_path),
+            Some(root.join("parent/pom.xml"))
+        );
+        pom.parent_relative_path = Some(String::new());
+        assert_eq!(pom.parent_path(&pom_path), None);
+    }
+
+    #[test]
+    fn test_replace_ranges() {
+        assert_eq!(
+            replace_ranges(
+                "a1b2c",
+                vec![(1..2, "10".to_string()), (3..4, "20".to_string())]
+            ),
+            "a10b20c"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_pom_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_path = temp_dir.path().join("pom.xml");
+        fs::write(&pom_path, POM).unwrap();
+
+        write_pom_version(&pom_path, "1.3.0").await.unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&pom_path).unwrap(),
+            POM.replace("<version> 1.2.0 </version>", "<version> 1.3.0 </version>")
+        );
+
+        fs::write(&pom_path, "<project><artifactId>a</artifactId></project>").unwrap();
+        let err = write_pom_version(&pom_path, "1.0.0")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("has no <version> of its own"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_sync_pom_references() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("pom.xml"),
+            "<project><artifactId>acme-parent</artifactId><version>1.1.0</version></project>",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("api")).unwrap();
+        let pom_path = root.join("api/pom.xml");
+        fs::write(&pom_path, POM).unwrap();
+
+        let versions = HashMap::from([
+            ("acme-core", "1.2.0"),
+            ("acme-util", "1.2.0"),
+            ("acme-bom", "2.1.0"),
+        ]);
+        sync_pom_references(&pom_path, &versions).await.unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&pom_path).unwrap(),
+            POM.replace(
+                "<version>1.0.0</version>\n  </parent>",
+                "<version>1.1.0</version>\n  </parent>"
+            )
+            .replace(
+                "<version>1.1.0</version>\n      <exclusions>",
+                "<version>1.2.0</version>\n      <exclusions>"
+            )
+            .replace("[2.0.0]", "[2.1.0]")
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{next_version_with_id, read_file};
+
+use crate::pom::{Pom, sync_pom_references, write_pom_version};
+
+#[derive(Debug)]
+pub struct MavenWorkspace {
+    path: PathBuf,
+    relative_path: PathBuf,
+    version: Option<String>,
+    name: Option<String>,
+    is_changed: bool,
+    metadata: PackageMetadata,
+    dependencies: HashSet<String>,
+    modules: Vec<PathBuf>,
+}
+
+impl MavenWorkspace {
+    #[must_use]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        path: PathBuf,
+        relative_path: PathBuf,
+    ) -> Self {
+        Self {
+            path,
+            relative_path,
+            name,
+            version,
+            is_changed: false,
+            metadata: PackageMetadata::default(),
+            dependencies: HashSet::new(),
+            modules: Vec::new(),
+        }
+    }
+
+    /// Set the pom paths of the aggregated `<modules>`
+    #[must_use]
+    pub fn with_modules(mut self, modules: Vec<PathBuf>) -> Self {
+        self.modules = modules;
+        self
+    }
+}
+
+#[async_trait]
+impl Workspace for MavenWorkspace {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn relative_path(&self) -> &Path {
+        &self.relative_path
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let current_version = self.version.as_deref().unwrap_or("0.0.0");
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+        write_pom_version(&self.path, &new_version).await?;
+        self.version = Some(new_version);
+        Ok(())
+    }
+
+    fn language(&self) -> Language {
+        Language::Java
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
+    fn set_changed(&mut self, changed: bool) {
+        self.is_changed = changed;
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn default_publish_command(&self) -> String {
+        "mvn -B deploy".to_string()
+    }
+
+    fn default_dry_run_publish_command(&self) -> Option<String> {
+        Some("mvn -B install".to_string())
+    }
+
+    fn dependencies(&self) -> &HashSet<String> {
+        &self.dependencies
+    }
+
+    fn add_dependency(&mut self, dependency: &str) {
+        self.dependencies.insert(dependency.to_string());
+    }
+
+    /// Sync this pom and its plain (non-aggregator) modules with the bumped versions
+    ///
+    /// Nested aggregators are workspaces of their own and sync their own pom, so every file
+    /// has a single writer.
+    async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
+        let versions: HashMap<&str, &str> = packages
+            .iter()
+            .filter(|package| package.language() == Language::Java)
+            .filter_map(|package| Some((package.name()?, package.version()?)))
+            .collect();
+
+        sync_pom_references(&self.path, &versions).await?;
+        for module in &self.modules {
+            if !module.is_file() {
+                continue;
+            }
+            let content = read_file(module).await?;
+            let pom = Pom::parse(&content)
+                .with_context(|| format!("Failed to parse {}", module.display()))?;
+            if pom.modules.is_empty() {
+                sync_pom_references(module, &versions).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::MavenPackage;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_version_and_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let root_pom = root.join("pom.xml");
+        fs::write(
+            &root_pom,
+            "<project>\n  <artifactId>parent</artifactId>\n  <version>1.0.0</version>\n  <modules>\n    <module>core</module>\n    <module>app</module>\n  </modules>\n</project>\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("core")).unwrap();
+        fs::write(
+            root.join("core/pom.xml"),
+            "<project>\n  <parent>\n    <artifactId>parent</artifactId>\n    <version>1.0.0</version>\n  </parent>\n  <artifactId>core</artifactId>\n  <version>2.0.0</version>\n</project>\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("app")).unwrap();
+        let app = "<project>\n  <parent>\n    <artifactId>parent</artifactId>\n    <version>1.0.0</version>\n  </parent>\n  <artifactId>app</artifactId>\n  <dependencies>\n    <dependency>\n      <artifactId>core</artifactId>\n      <version>2.0.0</version>\n    </dependency>\n  </dependencies>\n</project>\n";
+        fs::write(root.join("app/pom.xml"), app).unwrap();
+
+        let mut workspace = MavenWorkspace::new(
+            Some("parent".to_string()),
+            Some("1.0.0".to_string()),
+            root_pom.clone(),
+            PathBuf::from("pom.xml"),
+        )
+        .with_modules(vec![root.join("core/pom.xml"), root.join("app/pom.xml")]);
+        let mut core = MavenPackage::new(
+            Some("core".to_string()),
+            Some("2.0.0".to_string()),
+            root.join("core/pom.xml"),
+            PathBuf::from("core/pom.xml"),
+        );
+
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
+        core.update_version(UpdateType::Patch, None).await.unwrap();
+        workspace
+            .update_workspace_dependencies(&[&core as &dyn Package])
+            .await
+            .unwrap();
+
+        assert_eq!(workspace.version(), Some("1.1.0"));
+        assert!(
+            fs::read_to_string(&root_pom)
+                .unwrap()
+                .contains("<version>1.1.0</version>")
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("core/pom.xml")).unwrap(),
+            "<project>\n  <parent>\n    <artifactId>parent</artifactId>\n    <version>1.1.0</version>\n  </parent>\n  <artifactId>core</artifactId>\n  <version>2.0.1</version>\n</project>\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("app/pom.xml")).unwrap(),
+            app.replacen("1.0.0", "1.1.0", 1)
+                .replace("<version>2.0.0</version>", "<version>2.0.1</version>")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_workspace_dependencies_skips_nested_aggregators() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let root_pom = root.join("pom.xml");
+        fs::write(
+            &root_pom,
+            "<project><artifactId>parent</artifactId><version>2.0.0</version></project>",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("libs")).unwrap();
+        let nested = "<project><parent><artifactId>parent</artifactId><version>1.0.0</version></parent><artifactId>libs</artifactId><modules><module>a</module></modules></project>";
+        fs::write(root.join("libs/pom.xml"), nested).unwrap();
+
+        let workspace = MavenWorkspace::new(
+            Some("parent".to_string()),
+            Some("2.0.0".to_string()),
+            root_pom,
+            PathBuf::from("pom.xml"),
+        )
+        .with_modules(vec![
+            root.join("libs/pom.xml"),
+            root.join("missing/pom.xml"),
+        ]);
+        workspace.update_workspace_dependencies(&[]).await.unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("libs/pom.xml")).unwrap(),
+            nested
+        );
+    }
+}
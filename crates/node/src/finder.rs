@@ -1,18 +1,25 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Project, ProjectFinder};
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, RangeStrategy, project_key};
+use changepacks_utils::rewrite_version_range;
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
 };
 use tokio::fs::read_to_string;
 
-use crate::{package::NodePackage, workspace::NodeWorkspace};
+use crate::{
+    package::NodePackage,
+    workspace::{DEPENDENCY_SECTIONS, NodeWorkspace, PEER_DEPENDENCIES},
+};
 
 #[derive(Debug)]
 pub struct NodeProjectFinder {
     projects: HashMap<PathBuf, Project>,
     project_files: Vec<&'static str>,
+    dependency_range: RangeStrategy,
+    /// Version ranges each package.json has on other packages, resolved in `finalize`
+    range_dependencies: HashMap<PathBuf, Vec<(String, String)>>,
 }
 
 impl Default for NodeProjectFinder {
@@ -27,8 +34,17 @@ impl NodeProjectFinder {
         Self {
             projects: HashMap::new(),
             project_files: vec!["package.json"],
+            dependency_range: RangeStrategy::default(),
+            range_dependencies: HashMap::new(),
         }
     }
+
+    /// Set how workspaces rewrite dependency ranges on bumped packages
+    #[must_use]
+    pub const fn with_dependency_range(mut self, dependency_range: RangeStrategy) -> Self {
+        self.dependency_range = dependency_range;
+        self
+    }
 }
 
 #[async_trait]
@@ -55,52 +71,59 @@ impl ProjectFinder for NodeProjectFinder {
                     .context(format!("File name not found - {}", path.display()))?,
             )
         {
-            if self.projects.contains_key(path) {
+            if self.projects.contains_key(&project_key(path)) {
                 return Ok(());
             }
             // read package.json
             let package_json = read_to_string(path).await?;
             let package_json: serde_json::Value = serde_json::from_str(&package_json)?;
             // if workspaces
-            let (path, mut project) = if package_json.get("workspaces").is_some()
-                || path
-                    .parent()
-                    .context(format!("Parent not found - {}", path.display()))?
-                    .join("pnpm-workspace.yaml")
-                    .is_file()
-            {
-                let version = package_json["version"]
-                    .as_str()
-                    .map(std::string::ToString::to_string);
-                let name = package_json["name"]
-                    .as_str()
-                    .map(std::string::ToString::to_string);
-                (
-                    path.to_path_buf(),
-                    Project::Workspace(Box::new(NodeWorkspace::new(
-                        name,
-                        version,
+            let pnpm_workspace = path
+                .parent()
+                .context(format!("Parent not found - {}", path.display()))?
+                .join("pnpm-workspace.yaml");
+            let (path, mut project) =
+                if package_json.get("workspaces").is_some() || pnpm_workspace.is_file() {
+                    let version = package_json["version"]
+                        .as_str()
+                        .map(std::string::ToString::to_string);
+                    let name = package_json["name"]
+                        .as_str()
+                        .map(std::string::ToString::to_string);
+                    (
                         path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
-                )
-            } else {
-                let version = package_json["version"]
-                    .as_str()
-                    .map(std::string::ToString::to_string);
-                let name = package_json["name"]
-                    .as_str()
-                    .map(std::string::ToString::to_string);
-                (
-                    path.to_path_buf(),
-                    Project::Package(Box::new(NodePackage::new(
-                        name,
-                        version,
+                        Project::Workspace(Box::new(
+                            NodeWorkspace::new(
+                                name,
+                                version,
+                                path.to_path_buf(),
+                                relative_path.to_path_buf(),
+                            )
+                            .with_member_patterns(
+                                member_patterns(&package_json, &pnpm_workspace).await?,
+                            )
+                            .with_dependency_range(self.dependency_range),
+                        )),
+                    )
+                } else {
+                    let version = package_json["version"]
+                        .as_str()
+                        .map(std::string::ToString::to_string);
+                    let name = package_json["name"]
+                        .as_str()
+                        .map(std::string::ToString::to_string);
+                    (
                         path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
-                )
-            };
+                        Project::Package(Box::new(NodePackage::new(
+                            name,
+                            version,
+                            path.to_path_buf(),
+                            relative_path.to_path_buf(),
+                        ))),
+                    )
+                };
+
+            project.set_metadata(package_metadata(&package_json));
 
             if let Some(deps) = package_json.get("dependencies").and_then(|d| d.as_object()) {
                 for (dep_name, value) in deps {
@@ -111,13 +134,98 @@ impl ProjectFinder for NodeProjectFinder {
                     }
                 }
             }
+            let ranges: Vec<(String, String)> = DEPENDENCY_SECTIONS
+                .iter()
+                .filter(|section| **section != PEER_DEPENDENCIES)
+                .filter_map(|section| package_json[section].as_object())
+                .flatten()
+                .filter_map(|(name, range)| Some((name.clone(), range.as_str()?.to_string())))
+                .collect();
+            if !ranges.is_empty() {
+                self.range_dependencies.insert(project_key(&path), ranges);
+            }
 
-            self.projects.insert(path, project);
+            self.projects.insert(project_key(&path), project);
+        }
+        Ok(())
+    }
+
+    /// Turn version ranges on other packages of the repository into dependency edges
+    ///
+    /// Workspaces rewrite such ranges when the package they name is bumped, so the package
+    /// declaring them is bumped with it, just like with `workspace:*`. Peer ranges are left
+    /// out: they usually keep covering the new version.
+    async fn finalize(&mut self) -> Result<()> {
+        let versions: HashMap<String, String> = self
+            .projects
+            .values()
+            .filter_map(|project| {
+                Some((project.name()?.to_string(), project.version()?.to_string()))
+            })
+            .collect();
+        for (key, ranges) in std::mem::take(&mut self.range_dependencies) {
+            let Some(project) = self.projects.get_mut(&key) else {
+                continue;
+            };
+            for (name, range) in ranges {
+                if project.name() != Some(name.as_str())
+                    && versions
+                        .get(&name)
+                        .is_some_and(|version| rewrite_version_range(&range, version).is_some())
+                {
+                    project.add_dependency(&name);
+                }
+            }
         }
         Ok(())
     }
 }
 
+/// Member globs of a workspace root: the package.json `workspaces` (a list, or Yarn's
+/// `{ "packages": [...] }`) and the `packages` of the `pnpm-workspace.yaml` next to it
+async fn member_patterns(
+    package_json: &serde_json::Value,
+    pnpm_workspace: &Path,
+) -> Result<Vec<String>> {
+    let workspaces = &package_json["workspaces"];
+    let mut patterns: Vec<String> = workspaces
+        .as_array()
+        .or_else(|| workspaces["packages"].as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|pattern| pattern.as_str().map(str::to_string))
+        .collect();
+    if pnpm_workspace.is_file() {
+        let content = read_to_string(pnpm_workspace).await?;
+        let pnpm: serde_yaml::Value = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", pnpm_workspace.display()))?;
+        patterns.extend(
+            pnpm["packages"]
+                .as_sequence()
+                .into_iter()
+                .flatten()
+                .filter_map(|pattern| pattern.as_str().map(str::to_string)),
+        );
+    }
+    Ok(patterns)
+}
+
+/// Description, repository and license of a package.json
+///
+/// `repository` may be a URL string or `{ "type": "git", "url": ... }`; a `git+` prefix and
+/// `.git` suffix are dropped so the URL can be linked to.
+fn package_metadata(package_json: &serde_json::Value) -> PackageMetadata {
+    let field = |key: &str| package_json[key].as_str().map(str::to_string);
+    let repository = package_json["repository"]
+        .as_str()
+        .or_else(|| package_json["repository"]["url"].as_str())
+        .map(|url| {
+            let url = url.strip_prefix("git+").unwrap_or(url);
+            url.strip_suffix(".git").unwrap_or(url).to_string()
+        });
+    PackageMetadata::new(field("description"), repository, field("license"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +280,67 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_node_project_finder_visit_package_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(
+            &package_json,
+            r#"{
+  "name": "test-package",
+  "version": "1.0.0",
+  "description": "A test package",
+  "repository": { "type": "git", "url": "git+https://github.com/org/repo.git" },
+  "license": "MIT"
+}
+"#,
+        )
+        .unwrap();
+
+        let mut finder = NodeProjectFinder::new();
+        finder
+            .visit(&package_json, &PathBuf::from("package.json"))
+            .await
+            .unwrap();
+
+        let metadata = finder.projects()[0].metadata();
+        assert_eq!(metadata.description(), Some("A test package"));
+        assert_eq!(
+            metadata.repository_url(),
+            Some("https://github.com/org/repo")
+        );
+        assert_eq!(metadata.license(), Some("MIT"));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_node_project_finder_jsr_next_to_package_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        let jsr_json = temp_dir.path().join("jsr.json");
+        fs::write(&package_json, r#"{"name": "lib", "version": "1.0.0"}"#).unwrap();
+        fs::write(&jsr_json, r#"{"name": "@scope/lib", "version": "1.0.0"}"#).unwrap();
+
+        let mut finder = NodeProjectFinder::new();
+        finder
+            .visit(&jsr_json, &PathBuf::from("jsr.json"))
+            .await
+            .unwrap();
+        finder
+            .visit(&package_json, &PathBuf::from("package.json"))
+            .await
+            .unwrap();
+
+        // The jsr.json is left to the package.json next to it, which publishes to JSR
+        let projects = finder.projects();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name(), Some("lib"));
+        assert_eq!(projects[0].registry(), "jsr");
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_node_project_finder_visit_workspace_with_workspaces() {
         let temp_dir = TempDir::new().unwrap();
@@ -222,7 +391,11 @@ mod tests {
 
         // Create pnpm-workspace.yaml
         let pnpm_workspace = temp_dir.path().join("pnpm-workspace.yaml");
-        fs::write(&pnpm_workspace, "packages:\n  - 'packages/*'\n").unwrap();
+        fs::write(
+            &pnpm_workspace,
+            "packages:\n  - 'packages/*'\n  - '!packages/legacy'\n",
+        )
+        .unwrap();
 
         let mut finder = NodeProjectFinder::new();
         finder
@@ -236,6 +409,10 @@ mod tests {
             Project::Workspace(ws) => {
                 assert_eq!(ws.name(), Some("test-workspace"));
                 assert_eq!(ws.version(), Some("1.0.0"));
+                assert_eq!(
+                    ws.member_patterns(),
+                    Some(&["packages/*".to_string(), "!packages/legacy".to_string()][..])
+                );
             }
             _ => panic!("Expected Workspace"),
         }
@@ -243,6 +420,40 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_node_project_finder_visit_workspace_with_yarn_workspaces_object() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(
+            &package_json,
+            r#"{
+  "name": "test-workspace",
+  "workspaces": {
+    "packages": ["packages/*", "apps/web"],
+    "nohoist": ["**/react-native"]
+  }
+}
+"#,
+        )
+        .unwrap();
+
+        let mut finder = NodeProjectFinder::new();
+        finder
+            .visit(&package_json, &PathBuf::from("package.json"))
+            .await
+            .unwrap();
+
+        match finder.projects()[0] {
+            Project::Workspace(ws) => assert_eq!(
+                ws.member_patterns(),
+                Some(&["packages/*".to_string(), "apps/web".to_string()][..])
+            ),
+            _ => panic!("Expected Workspace"),
+        }
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_node_project_finder_visit_workspace_without_version() {
         let temp_dir = TempDir::new().unwrap();
@@ -460,4 +671,61 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[tokio::test]
+    async fn test_node_project_finder_finalize_range_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, manifest) in [
+            ("core", r#"{"name": "core", "version": "1.2.0"}"#),
+            ("tools", r#"{"name": "tools", "version": "0.3.0"}"#),
+            (
+                "web",
+                r#"{
+  "name": "web",
+  "version": "1.0.0",
+  "dependencies": { "core": "^1.2.0", "react": "^18.2.0" },
+  "devDependencies": { "tools": "workspace:~0.3.0" }
+}"#,
+            ),
+            (
+                "plugin",
+                r#"{
+  "name": "plugin",
+  "version": "1.0.0",
+  "dependencies": { "tools": "*" },
+  "peerDependencies": { "core": "^1.0.0" }
+}"#,
+            ),
+        ] {
+            let dir = temp_dir.path().join("packages").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("package.json"), manifest).unwrap();
+        }
+
+        let mut finder = NodeProjectFinder::new();
+        for name in ["core", "tools", "web", "plugin"] {
+            let relative_path = PathBuf::from(format!("packages/{name}/package.json"));
+            finder
+                .visit(&temp_dir.path().join(&relative_path), &relative_path)
+                .await
+                .unwrap();
+        }
+        finder.finalize().await.unwrap();
+
+        let dependencies = |name: &str| {
+            let project = finder
+                .projects()
+                .into_iter()
+                .find(|project| project.name() == Some(name))
+                .unwrap();
+            let mut dependencies: Vec<String> = project.dependencies().iter().cloned().collect();
+            dependencies.sort();
+            dependencies
+        };
+        // Ranges that get rewritten on a bump are edges; external, `*` and peer ranges are not
+        assert_eq!(dependencies("web"), ["core", "tools"]);
+        assert!(dependencies("plugin").is_empty());
+
+        temp_dir.close().unwrap();
+    }
 }
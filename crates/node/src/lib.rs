@@ -12,6 +12,9 @@ pub mod workspace;
 
 pub use finder::NodeProjectFinder;
 
+use anyhow::Result;
+use changepacks_utils::{detect_indent, read_file, write_file};
+use serde::Serialize;
 use std::path::Path;
 
 /// Represents the detected Node.js package manager
@@ -92,6 +95,37 @@ pub fn detect_package_manager_recursive(path: &Path) -> PackageManager {
     PackageManager::Npm
 }
 
+/// Set the `version` of the package.json (or jsr.json) at `path`, keeping its formatting
+pub(crate) async fn write_manifest_version(path: &Path, version: &str) -> Result<()> {
+    let raw = read_file(path).await?;
+    let mut manifest: serde_json::Value = serde_json::from_str(&raw)?;
+    manifest["version"] = serde_json::Value::String(version.to_string());
+    write_package_json(path, &raw, &manifest).await
+}
+
+/// Write `package_json` back with the indentation and trailing newline of `raw`
+pub(crate) async fn write_package_json(
+    path: &Path,
+    raw: &str,
+    package_json: &serde_json::Value,
+) -> Result<()> {
+    let indent = detect_indent(raw);
+    let ind = &b" ".repeat(indent);
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(ind);
+    let writer = Vec::new();
+    let mut ser = serde_json::Serializer::with_formatter(writer, formatter);
+    package_json.serialize(&mut ser)?;
+    write_file(
+        path,
+        format!(
+            "{}{}",
+            String::from_utf8(ser.into_inner())?.trim_end(),
+            if raw.ends_with('\n') { "\n" } else { "" }
+        ),
+    )
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,13 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, Package, UpdateType};
-use changepacks_utils::{detect_indent, next_version};
-use serde::Serialize;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::{JSR_REGISTRY, jsr_manifest, jsr_publish_command, next_version_with_id};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
 
-use crate::detect_package_manager_recursive;
+use crate::{detect_package_manager_recursive, write_manifest_version};
 
 #[derive(Debug)]
 pub struct NodePackage {
@@ -16,6 +14,7 @@ pub struct NodePackage {
     path: PathBuf,
     relative_path: PathBuf,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
 }
 
@@ -33,6 +32,7 @@ impl NodePackage {
             path,
             relative_path,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
         }
     }
@@ -56,32 +56,22 @@ impl Package for NodePackage {
         &self.relative_path
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         let current_version = self.version.as_deref().unwrap_or("0.0.0");
-        let new_version = next_version(current_version, update_type)?;
-
-        let package_json_raw = read_to_string(&self.path).await?;
-        let indent = detect_indent(&package_json_raw);
-        let mut package_json: serde_json::Value = serde_json::from_str(&package_json_raw)?;
-        package_json["version"] = serde_json::Value::String(new_version.clone());
-        let ind = &b" ".repeat(indent);
-        let formatter = serde_json::ser::PrettyFormatter::with_indent(ind);
-        let writer = Vec::new();
-        let mut ser = serde_json::Serializer::with_formatter(writer, formatter);
-        package_json.serialize(&mut ser)?;
-        write(
-            &self.path,
-            format!(
-                "{}{}",
-                String::from_utf8(ser.into_inner())?.trim_end(),
-                if package_json_raw.ends_with('\n') {
-                    "\n"
-                } else {
-                    ""
-                }
-            ),
-        )
-        .await?;
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+
+        write_manifest_version(&self.path, &new_version).await?;
+        // Keep a jsr.json next to the package.json in step, or JSR publishes the old version
+        if let Some(manifest) = jsr_manifest(&self.path)
+            && manifest != self.path
+        {
+            write_manifest_version(&manifest, &new_version).await?;
+        }
         self.version = Some(new_version);
         Ok(())
     }
@@ -102,13 +92,39 @@ impl Package for NodePackage {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn default_publish_command(&self) -> String {
+        if let Some(manifest) = jsr_manifest(&self.path)
+            && let Some(dir) = manifest.parent()
+        {
+            return jsr_publish_command(dir, false);
+        }
         detect_package_manager_recursive(&self.path)
             .publish_command()
             .to_string()
     }
 
     fn default_dry_run_publish_command(&self) -> Option<String> {
+        if let Some(manifest) = jsr_manifest(&self.path)
+            && let Some(dir) = manifest.parent()
+        {
+            return Some(jsr_publish_command(dir, true));
+        }
         Some(
             detect_package_manager_recursive(&self.path)
                 .dry_run_publish_command()
@@ -116,6 +132,19 @@ impl Package for NodePackage {
         )
     }
 
+    /// npm dist-tags; JSR has no channels
+    fn channel_flag(&self) -> Option<&'static str> {
+        jsr_manifest(&self.path).is_none().then_some("--tag")
+    }
+
+    fn registry(&self) -> &'static str {
+        if jsr_manifest(&self.path).is_some() {
+            JSR_REGISTRY
+        } else {
+            self.language().registry()
+        }
+    }
+
     fn dependencies(&self) -> &HashSet<String> {
         &self.dependencies
     }
@@ -192,7 +221,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "1.0.1""#));
@@ -200,6 +232,88 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_node_package_update_version_invalid_names_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(
+            &package_json,
+            r#"{
+  "name": "test-package",
+  "version": "latest"
+}
+"#,
+        )
+        .unwrap();
+
+        let mut package = NodePackage::new(
+            Some("test-package".to_string()),
+            Some("latest".to_string()),
+            package_json.clone(),
+            PathBuf::from("package.json"),
+        );
+
+        let err = package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(&package_json.display().to_string())
+        );
+        assert!(format!("{err:#}").contains("Invalid version 'latest'"));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_node_package_update_version_syncs_jsr_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        let jsr_json = temp_dir.path().join("jsr.json");
+        fs::write(
+            &package_json,
+            "{\n  \"name\": \"lib\",\n  \"version\": \"1.0.0\"\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            &jsr_json,
+            "{\n    \"name\": \"@scope/lib\",\n    \"version\": \"1.0.0\"\n}\n",
+        )
+        .unwrap();
+
+        let mut package = NodePackage::new(
+            Some("lib".to_string()),
+            Some("1.0.0".to_string()),
+            package_json.clone(),
+            PathBuf::from("package.json"),
+        );
+        assert_eq!(package.registry(), "jsr");
+        assert_eq!(package.default_publish_command(), "npx jsr publish");
+        assert_eq!(
+            package.default_dry_run_publish_command().as_deref(),
+            Some("npx jsr publish --dry-run")
+        );
+
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
+
+        assert!(
+            read_to_string(&package_json)
+                .await
+                .unwrap()
+                .contains(r#"  "version": "1.1.0""#)
+        );
+        assert_eq!(
+            read_to_string(&jsr_json).await.unwrap(),
+            "{\n    \"name\": \"@scope/lib\",\n    \"version\": \"1.1.0\"\n}\n"
+        );
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_node_package_update_version_minor() {
         let temp_dir = TempDir::new().unwrap();
@@ -221,7 +335,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        package.update_version(UpdateType::Minor).await.unwrap();
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "1.1.0""#));
@@ -250,7 +367,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        package.update_version(UpdateType::Major).await.unwrap();
+        package
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "2.0.0""#));
@@ -283,7 +403,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "1.2.4""#));
@@ -312,7 +435,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.ends_with('\n'));
@@ -333,4 +459,38 @@ mod tests {
         package.set_name("my-project".to_string());
         assert_eq!(package.name(), Some("my-project"));
     }
+
+    #[test]
+    fn test_prerelease_publish_command_uses_dist_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(&package_json, "{}").unwrap();
+        let package = |version: &str| {
+            NodePackage::new(
+                Some("test".to_string()),
+                Some(version.to_string()),
+                package_json.clone(),
+                PathBuf::from("package.json"),
+            )
+        };
+        let config = changepacks_core::Config {
+            prerelease_channels: std::collections::HashMap::from([(
+                "rc".to_string(),
+                "next".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        assert_eq!(package("1.0.0").get_publish_command(&config), "npm publish");
+        assert_eq!(
+            package("2.0.0-beta.1").get_publish_command(&config),
+            "npm publish --tag beta"
+        );
+        assert_eq!(
+            package("2.0.0-rc.1").get_dry_run_publish_command(&config),
+            Some("npm publish --dry-run --tag next".to_string())
+        );
+
+        temp_dir.close().unwrap();
+    }
 }
@@ -1,13 +1,13 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, UpdateType, Workspace};
-use changepacks_utils::{detect_indent, next_version};
-use serde::Serialize;
+use changepacks_core::{Language, Package, PackageMetadata, RangeStrategy, UpdateType, Workspace};
+use changepacks_utils::{
+    member_manifests, next_version_with_id, range_allows, read_file, rewrite_dependency_range,
+};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
 
-use crate::detect_package_manager_recursive;
+use crate::{detect_package_manager_recursive, write_manifest_version, write_package_json};
 
 #[derive(Debug)]
 pub struct NodeWorkspace {
@@ -16,7 +16,10 @@ pub struct NodeWorkspace {
     version: Option<String>,
     name: Option<String>,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    member_patterns: Option<Vec<String>>,
+    dependency_range: RangeStrategy,
 }
 
 impl NodeWorkspace {
@@ -33,9 +36,26 @@ impl NodeWorkspace {
             name,
             version,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            member_patterns: None,
+            dependency_range: RangeStrategy::default(),
         }
     }
+
+    /// Record the member globs the manifest declares (`!` patterns exclude)
+    #[must_use]
+    pub fn with_member_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.member_patterns = Some(patterns);
+        self
+    }
+
+    /// Set how dependency ranges on bumped packages are rewritten
+    #[must_use]
+    pub const fn with_dependency_range(mut self, dependency_range: RangeStrategy) -> Self {
+        self.dependency_range = dependency_range;
+        self
+    }
 }
 
 #[async_trait]
@@ -52,34 +72,19 @@ impl Workspace for NodeWorkspace {
         self.version.as_deref()
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
-        let next_version = next_version(
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let next_version = next_version_with_id(
             self.version.as_ref().unwrap_or(&String::from("0.0.0")),
             update_type,
-        )?;
-
-        let package_json_raw = read_to_string(Path::new(&self.path)).await?;
-        let indent = detect_indent(&package_json_raw);
-        let mut package_json: serde_json::Value = serde_json::from_str(&package_json_raw)?;
-        package_json["version"] = serde_json::Value::String(next_version.clone());
-        let ind = &b" ".repeat(indent);
-        let formatter = serde_json::ser::PrettyFormatter::with_indent(ind);
-        let writer = Vec::new();
-        let mut ser = serde_json::Serializer::with_formatter(writer, formatter);
-        package_json.serialize(&mut ser)?;
-        write(
-            &self.path,
-            format!(
-                "{}{}",
-                String::from_utf8(ser.into_inner())?.trim_end(),
-                if package_json_raw.ends_with('\n') {
-                    "\n"
-                } else {
-                    ""
-                }
-            ),
+            prerelease_id,
         )
-        .await?;
+        .with_context(|| format!("Cannot bump {}", self.path.display()))?;
+
+        write_manifest_version(&self.path, &next_version).await?;
         self.version = Some(next_version);
         Ok(())
     }
@@ -104,6 +109,31 @@ impl Workspace for NodeWorkspace {
         self.name = Some(name);
     }
 
+    /// npm dist-tags
+    fn channel_flag(&self) -> Option<&'static str> {
+        Some("--tag")
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn member_patterns(&self) -> Option<&[String]> {
+        self.member_patterns.as_deref()
+    }
+
     fn default_publish_command(&self) -> String {
         detect_package_manager_recursive(&self.path)
             .publish_command()
@@ -125,8 +155,89 @@ impl Workspace for NodeWorkspace {
     fn add_dependency(&mut self, dependency: &str) {
         self.dependencies.insert(dependency.to_string());
     }
+
+    /// Rewrite the ranges on bumped packages in the root package.json and in the
+    /// package.json of every member
+    async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
+        let members = self.member_patterns.as_deref().unwrap_or_default();
+        for path in std::iter::once(self.path.clone()).chain(member_manifests(
+            &self.path,
+            members,
+            "package.json",
+        )) {
+            update_dependency_ranges(&path, packages, self.dependency_range).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Rewrite the ranges the package.json at `path` has on `packages` to their new versions
+///
+/// A peer range is a compatibility promise rather than a pin: it is only moved when the new
+/// version falls outside it, and only in a package that is itself being released.
+async fn update_dependency_ranges(
+    path: &Path,
+    packages: &[&dyn Package],
+    strategy: RangeStrategy,
+) -> Result<()> {
+    let package_json_raw = read_file(path).await?;
+    let mut package_json: serde_json::Value = serde_json::from_str(&package_json_raw)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let released = packages.iter().any(|package| package.path() == path);
+    let mut updated = false;
+    for section in DEPENDENCY_SECTIONS {
+        let peer = section == PEER_DEPENDENCIES;
+        let Some(dependencies) = package_json
+            .get_mut(section)
+            .and_then(|d| d.as_object_mut())
+        else {
+            continue;
+        };
+        for package in packages {
+            if package.language() != Language::Node {
+                continue;
+            }
+            let (Some(name), Some(version)) = (package.name(), package.version()) else {
+                continue;
+            };
+            let Some(range) = dependencies.get_mut(name) else {
+                continue;
+            };
+            let outside = range
+                .as_str()
+                .and_then(|range| range_allows(range, version))
+                == Some(false);
+            if peer && !(released && outside) {
+                continue;
+            }
+            if let Some(rewritten) = range
+                .as_str()
+                .and_then(|range| rewrite_dependency_range(range, version, strategy))
+                && range.as_str() != Some(rewritten.as_str())
+            {
+                *range = serde_json::Value::String(rewritten);
+                updated = true;
+            }
+        }
+    }
+
+    if updated {
+        write_package_json(path, &package_json_raw, &package_json).await?;
+    }
+    Ok(())
 }
 
+/// Sections of a package.json that can reference workspace members by version
+pub(crate) const DEPENDENCY_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    PEER_DEPENDENCIES,
+    "optionalDependencies",
+];
+
+pub(crate) const PEER_DEPENDENCIES: &str = "peerDependencies";
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,7 +322,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "1.0.1""#));
@@ -240,7 +354,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "0.0.1""#));
@@ -270,7 +387,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        workspace.update_version(UpdateType::Minor).await.unwrap();
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "1.1.0""#));
@@ -300,7 +420,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        workspace.update_version(UpdateType::Major).await.unwrap();
+        workspace
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "2.0.0""#));
@@ -333,7 +456,10 @@ mod tests {
             PathBuf::from("package.json"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&package_json).await.unwrap();
         assert!(content.contains(r#""version": "1.0.1""#));
@@ -344,6 +470,270 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_node_workspace_update_workspace_dependencies() {
+        use crate::package::NodePackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(
+            &package_json,
+            r#"{
+  "name": "root",
+  "private": true,
+  "dependencies": {
+    "core": "^1.2.0",
+    "react": "^18.0.0"
+  },
+  "devDependencies": {
+    "utils": "1.x",
+    "cli": "workspace:*"
+  }
+}
+"#,
+        )
+        .unwrap();
+
+        let workspace = NodeWorkspace::new(
+            Some("root".to_string()),
+            None,
+            package_json.clone(),
+            PathBuf::from("package.json"),
+        );
+        let package = |name: &str, version: &str| {
+            NodePackage::new(
+                Some(name.to_string()),
+                Some(version.to_string()),
+                PathBuf::from(format!("/test/packages/{name}/package.json")),
+                PathBuf::from(format!("packages/{name}/package.json")),
+            )
+        };
+        let (core, utils, cli) = (
+            package("core", "1.3.0"),
+            package("utils", "2.0.0"),
+            package("cli", "0.2.0"),
+        );
+        let packages: Vec<&dyn Package> = vec![&core, &utils, &cli];
+
+        workspace
+            .update_workspace_dependencies(&packages)
+            .await
+            .unwrap();
+
+        let content = read_to_string(&package_json).await.unwrap();
+        assert_eq!(
+            content,
+            r#"{
+  "name": "root",
+  "private": true,
+  "dependencies": {
+    "core": "^1.3.0",
+    "react": "^18.0.0"
+  },
+  "devDependencies": {
+    "utils": "2.x",
+    "cli": "workspace:*"
+  }
+}
+"#
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_node_workspace_update_member_dependencies() {
+        use crate::package::NodePackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("package.json");
+        fs::write(
+            &root,
+            "{\n  \"name\": \"root\",\n  \"workspaces\": [\"packages/*\"]\n}\n",
+        )
+        .unwrap();
+        for (name, manifest) in [
+            (
+                "core",
+                "{\n  \"name\": \"core\",\n  \"version\": \"1.3.0\"\n}\n",
+            ),
+            (
+                "web",
+                r#"{
+  "name": "web",
+  "dependencies": {
+    "core": "^1.2.0"
+  },
+  "peerDependencies": {
+    "core": ">=1.0.0 <2.0.0"
+  },
+  "devDependencies": {
+    "core": "workspace:~1.2.0"
+  }
+}
+"#,
+            ),
+        ] {
+            fs::create_dir_all(temp_dir.path().join("packages").join(name)).unwrap();
+            fs::write(
+                temp_dir
+                    .path()
+                    .join("packages")
+                    .join(name)
+                    .join("package.json"),
+                manifest,
+            )
+            .unwrap();
+        }
+
+        let workspace = NodeWorkspace::new(
+            Some("root".to_string()),
+            None,
+            root.clone(),
+            PathBuf::from("package.json"),
+        )
+        .with_member_patterns(vec!["packages/*".to_string()])
+        .with_dependency_range(RangeStrategy::Exact);
+        let core = NodePackage::new(
+            Some("core".to_string()),
+            Some("1.3.0".to_string()),
+            temp_dir.path().join("packages/core/package.json"),
+            PathBuf::from("packages/core/package.json"),
+        );
+
+        workspace
+            .update_workspace_dependencies(&[&core])
+            .await
+            .unwrap();
+
+        let web = read_to_string(temp_dir.path().join("packages/web/package.json"))
+            .await
+            .unwrap();
+        assert_eq!(
+            web,
+            r#"{
+  "name": "web",
+  "dependencies": {
+    "core": "1.3.0"
+  },
+  "peerDependencies": {
+    "core": ">=1.0.0 <2.0.0"
+  },
+  "devDependencies": {
+    "core": "workspace:1.3.0"
+  }
+}
+"#
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_node_workspace_update_peer_dependencies() {
+        use crate::package::NodePackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("package.json");
+        fs::write(&root, r#"{"name": "root", "workspaces": ["packages/*"]}"#).unwrap();
+        let manifest = |name: &str, peer: &str| {
+            format!(
+                "{{\n  \"name\": \"{name}\",\n  \"peerDependencies\": {{\n    \"core\": \"{peer}\"\n  }}\n}}\n"
+            )
+        };
+        let plugin = |name: &str| {
+            temp_dir
+                .path()
+                .join("packages")
+                .join(name)
+                .join("package.json")
+        };
+        for (name, peer) in [
+            ("outside", "^1.0.0"),
+            ("inside", ">=1.0.0"),
+            ("unreleased", "^1.0.0"),
+        ] {
+            fs::create_dir_all(plugin(name).parent().unwrap()).unwrap();
+            fs::write(plugin(name), manifest(name, peer)).unwrap();
+        }
+
+        let workspace = NodeWorkspace::new(
+            Some("root".to_string()),
+            None,
+            root.clone(),
+            PathBuf::from("package.json"),
+        )
+        .with_member_patterns(vec!["packages/*".to_string()]);
+        let package = |name: &str, version: &str, path: PathBuf| {
+            NodePackage::new(
+                Some(name.to_string()),
+                Some(version.to_string()),
+                path,
+                PathBuf::from(format!("packages/{name}/package.json")),
+            )
+        };
+        let core = package(
+            "core",
+            "2.0.0",
+            PathBuf::from("/test/packages/core/package.json"),
+        );
+        let outside = package("outside", "1.0.1", plugin("outside"));
+        let inside = package("inside", "1.0.1", plugin("inside"));
+
+        workspace
+            .update_workspace_dependencies(&[&core, &outside, &inside])
+            .await
+            .unwrap();
+
+        // Only a released package whose peer range excludes the new version is moved
+        for (name, peer) in [
+            ("outside", "^2.0.0"),
+            ("inside", ">=1.0.0"),
+            ("unreleased", "^1.0.0"),
+        ] {
+            assert_eq!(
+                read_to_string(plugin(name)).await.unwrap(),
+                manifest(name, peer),
+                "{name}"
+            );
+        }
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_node_workspace_update_workspace_dependencies_untouched() {
+        use crate::package::NodePackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        let raw = "{\n    \"name\": \"root\",\n    \"dependencies\": { \"core\": \"*\" }\n}";
+        fs::write(&package_json, raw).unwrap();
+
+        let workspace = NodeWorkspace::new(
+            Some("root".to_string()),
+            None,
+            package_json.clone(),
+            PathBuf::from("package.json"),
+        );
+        let core = NodePackage::new(
+            Some("core".to_string()),
+            Some("1.3.0".to_string()),
+            PathBuf::from("/test/packages/core/package.json"),
+            PathBuf::from("packages/core/package.json"),
+        );
+
+        workspace
+            .update_workspace_dependencies(&[&core])
+            .await
+            .unwrap();
+
+        // Nothing to rewrite, so the file keeps its original formatting
+        assert_eq!(read_to_string(&package_json).await.unwrap(), raw);
+
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn test_node_workspace_dependencies() {
         let mut workspace = NodeWorkspace::new(
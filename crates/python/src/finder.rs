@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Project, ProjectFinder};
+use changepacks_core::{PackageMetadata, Project, ProjectFinder, project_key};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
@@ -54,7 +54,7 @@ impl ProjectFinder for PythonProjectFinder {
                     .context(format!("File name not found - {}", path.display()))?,
             )
         {
-            if self.projects.contains_key(path) {
+            if self.projects.contains_key(&project_key(path)) {
                 return Ok(());
             }
             // read pyproject.toml
@@ -63,6 +63,7 @@ impl ProjectFinder for PythonProjectFinder {
             let project = pyproject_toml
                 .get("project")
                 .context(format!("Project not found - {}", path.display()))?;
+            let metadata = pyproject_metadata(project);
 
             // if workspace
             let (path, mut project) = if pyproject_toml
@@ -80,12 +81,15 @@ impl ProjectFinder for PythonProjectFinder {
                     .map(std::string::ToString::to_string);
                 (
                     path.to_path_buf(),
-                    Project::Workspace(Box::new(PythonWorkspace::new(
-                        name,
-                        version,
-                        path.to_path_buf(),
-                        relative_path.to_path_buf(),
-                    ))),
+                    Project::Workspace(Box::new(
+                        PythonWorkspace::new(
+                            name,
+                            version,
+                            path.to_path_buf(),
+                            relative_path.to_path_buf(),
+                        )
+                        .with_member_patterns(member_patterns(&pyproject_toml)),
+                    )),
                 )
             } else {
                 let version = project
@@ -108,25 +112,77 @@ impl ProjectFinder for PythonProjectFinder {
                 )
             };
 
-            // read tool.uv.sources section
+            project.set_metadata(metadata);
+
+            // [tool.uv.sources] entries with `workspace = true` point at workspace members
             if let Some(sources) = pyproject_toml
                 .get("tool")
                 .and_then(|t| t.get("uv").and_then(|u| u.get("sources")))
-                && let Some(sources) = sources.as_array()
+                .and_then(toml::Value::as_table)
             {
-                for source in sources {
-                    if let Some(source_str) = source.as_str() {
-                        project.add_dependency(source_str);
+                for (name, source) in sources {
+                    if source.get("workspace").and_then(toml::Value::as_bool) == Some(true) {
+                        project.add_dependency(name);
                     }
                 }
             }
 
-            self.projects.insert(path, project);
+            self.projects.insert(project_key(&path), project);
         }
         Ok(())
     }
 }
 
+/// Member globs of `[tool.uv.workspace]`: `members`, with each `exclude` entry as a `!`
+/// pattern
+fn member_patterns(pyproject_toml: &toml::Value) -> Vec<String> {
+    let list = |key: &str| {
+        pyproject_toml
+            .get("tool")
+            .and_then(|t| t.get("uv"))
+            .and_then(|u| u.get("workspace"))
+            .and_then(|w| w.get(key))
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str)
+    };
+    list("members")
+        .map(String::from)
+        .chain(list("exclude").map(|pattern| format!("!{pattern}")))
+        .collect()
+}
+
+/// `[project.urls]` labels that point at the source repository, in order of preference
+const REPOSITORY_URL_LABELS: [&str; 4] = ["repository", "source", "sourcecode", "code"];
+
+/// Description, repository and license of a `[project]` table
+///
+/// `license` may be an SPDX string or a `{ text = ... }` table; the repository comes from a
+/// `[project.urls]` entry labelled like `Repository` or `Source Code`.
+fn pyproject_metadata(project: &toml::Value) -> PackageMetadata {
+    let description = project
+        .get("description")
+        .and_then(toml::Value::as_str)
+        .map(str::to_string);
+    let license = project
+        .get("license")
+        .and_then(|license| license.as_str().or_else(|| license.get("text")?.as_str()))
+        .map(str::to_string);
+    let urls = project.get("urls").and_then(toml::Value::as_table);
+    let repository = REPOSITORY_URL_LABELS.iter().find_map(|wanted| {
+        urls?.iter().find_map(|(label, url)| {
+            let label: String = label.chars().filter(char::is_ascii_alphanumeric).collect();
+            label
+                .eq_ignore_ascii_case(wanted)
+                .then(|| url.as_str())
+                .flatten()
+                .map(str::to_string)
+        })
+    });
+    PackageMetadata::new(description, repository, license)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,6 +236,42 @@ version = "1.0.0"
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_python_project_finder_visit_package_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject_toml = temp_dir.path().join("pyproject.toml");
+        fs::write(
+            &pyproject_toml,
+            r#"[project]
+name = "test-package"
+version = "1.0.0"
+description = "A test package"
+license = { text = "BSD-3-Clause" }
+
+[project.urls]
+Homepage = "https://example.com"
+"Source Code" = "https://github.com/org/repo"
+"#,
+        )
+        .unwrap();
+
+        let mut finder = PythonProjectFinder::new();
+        finder
+            .visit(&pyproject_toml, &PathBuf::from("pyproject.toml"))
+            .await
+            .unwrap();
+
+        let metadata = finder.projects()[0].metadata();
+        assert_eq!(metadata.description(), Some("A test package"));
+        assert_eq!(metadata.license(), Some("BSD-3-Clause"));
+        assert_eq!(
+            metadata.repository_url(),
+            Some("https://github.com/org/repo")
+        );
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_python_project_finder_visit_workspace() {
         let temp_dir = TempDir::new().unwrap();
@@ -188,10 +280,16 @@ version = "1.0.0"
             &pyproject_toml,
             r#"[tool.uv.workspace]
 members = ["packages/*"]
+exclude = ["packages/legacy"]
 
 [project]
 name = "test-workspace"
 version = "1.0.0"
+dependencies = ["core>=1.0.0"]
+
+[tool.uv.sources]
+core = { workspace = true }
+requests = { git = "https://github.com/psf/requests" }
 "#,
         )
         .unwrap();
@@ -208,6 +306,11 @@ version = "1.0.0"
             Project::Workspace(ws) => {
                 assert_eq!(ws.name(), Some("test-workspace"));
                 assert_eq!(ws.version(), Some("1.0.0"));
+                assert_eq!(
+                    ws.member_patterns(),
+                    Some(&["packages/*".to_string(), "!packages/legacy".to_string()][..])
+                );
+                assert_eq!(ws.dependencies().iter().collect::<Vec<_>>(), ["core"]);
             }
             _ => panic!("Expected Workspace"),
         }
@@ -6,8 +6,135 @@
 //! TOML using the toml crate and preserves formatting when updating versions. Supports
 //! both single packages and workspace configurations.
 
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
 pub mod finder;
 pub mod package;
+mod pep440;
+mod requirement;
 pub mod workspace;
 
 pub use finder::PythonProjectFinder;
+
+/// Modules that conventionally declare `__version__`
+const VERSION_MODULES: [&str; 4] = ["__init__.py", "__about__.py", "_version.py", "version.py"];
+
+/// `__version__` string literals declared next to the pyproject.toml at `pyproject_path`
+///
+/// Looks at the conventional version modules of each top-level package, in both the flat
+/// (`pkg/__init__.py`) and the src (`src/pkg/__init__.py`) layout. Dynamically computed
+/// versions such as `importlib.metadata.version(...)` are not declarations and are skipped.
+///
+/// # Errors
+/// Returns error if a package directory or version module cannot be read.
+pub(crate) async fn dunder_versions(pyproject_path: &Path) -> Result<Vec<(PathBuf, String)>> {
+    let Some(root) = pyproject_path.parent() else {
+        return Ok(Vec::new());
+    };
+    let mut declarations = Vec::new();
+    for base in [root.to_path_buf(), root.join("src")] {
+        let Ok(mut entries) = tokio::fs::read_dir(&base).await else {
+            continue;
+        };
+        let mut packages = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            if entry.file_type().await?.is_dir() && !name.to_string_lossy().starts_with('.') {
+                packages.push(entry.path());
+            }
+        }
+        packages.sort();
+        for package in packages {
+            for module in VERSION_MODULES {
+                let path = package.join(module);
+                if !path.is_file() {
+                    continue;
+                }
+                if let Some(version) =
+                    parse_dunder_version(&tokio::fs::read_to_string(&path).await?)
+                {
+                    declarations.push((path, version));
+                }
+            }
+        }
+    }
+    Ok(declarations)
+}
+
+/// Value of a module-level `__version__ = "..."` (optionally annotated) assignment
+fn parse_dunder_version(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let rest = line.strip_prefix("__version__")?.trim_start();
+        let rest = rest
+            .strip_prefix(':')
+            .map_or(rest, |annotated| {
+                annotated.split_once('=').map_or("", |(_, v)| v)
+            })
+            .trim_start();
+        let rest = rest.strip_prefix('=').unwrap_or(rest).trim();
+        let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+        let (version, _) = rest[1..].split_once(quote)?;
+        Some(version.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_dunder_version() {
+        assert_eq!(
+            parse_dunder_version("\"\"\"Docs\"\"\"\n__version__ = \"1.2.3\"\n"),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(
+            parse_dunder_version("__version__ = '1.2.3'  # bumped by CI"),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(
+            parse_dunder_version("__version__: str = \"2.0.0rc1\""),
+            Some("2.0.0rc1".to_string())
+        );
+        assert_eq!(parse_dunder_version("__version__ = version(\"pkg\")"), None);
+        assert_eq!(parse_dunder_version("    __version__ = \"1.0.0\""), None);
+        assert_eq!(parse_dunder_version("__version_info__ = (1, 0)"), None);
+    }
+
+    #[tokio::test]
+    async fn test_dunder_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let pyproject = root.join("pyproject.toml");
+        std::fs::write(&pyproject, "[project]\nversion = \"1.0.0\"\n").unwrap();
+        assert!(dunder_versions(&pyproject).await.unwrap().is_empty());
+
+        std::fs::create_dir_all(root.join("flat")).unwrap();
+        std::fs::write(root.join("flat/__init__.py"), "__version__ = \"1.0.0\"\n").unwrap();
+        std::fs::create_dir_all(root.join("src/pkg")).unwrap();
+        std::fs::write(
+            root.join("src/pkg/__init__.py"),
+            "from ._version import *\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/pkg/_version.py"),
+            "__version__ = \"0.9.0\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join(".venv")).unwrap();
+        std::fs::write(root.join(".venv/version.py"), "__version__ = \"9.9.9\"\n").unwrap();
+
+        assert_eq!(
+            dunder_versions(&pyproject).await.unwrap(),
+            [
+                (root.join("flat/__init__.py"), "1.0.0".to_string()),
+                (root.join("src/pkg/_version.py"), "0.9.0".to_string()),
+            ]
+        );
+
+        temp_dir.close().unwrap();
+    }
+}
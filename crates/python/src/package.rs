@@ -1,12 +1,13 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, Package, UpdateType};
-use changepacks_utils::next_version;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::{read_file, write_file};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
 use toml_edit::DocumentMut;
 
+use crate::pep440::next_pep440_version;
+
 #[derive(Debug)]
 pub struct PythonPackage {
     name: Option<String>,
@@ -14,6 +15,7 @@ pub struct PythonPackage {
     path: PathBuf,
     relative_path: PathBuf,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
 }
 
@@ -31,6 +33,7 @@ impl PythonPackage {
             path,
             relative_path,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
         }
     }
@@ -54,14 +57,19 @@ impl Package for PythonPackage {
         &self.relative_path
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         let current_version = self.version.as_deref().unwrap_or("0.0.0");
-        let new_version = next_version(current_version, update_type)?;
+        let new_version = next_pep440_version(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let pyproject_toml_raw = read_to_string(&self.path).await?;
+        let pyproject_toml_raw = read_file(&self.path).await?;
         let mut pyproject_toml: DocumentMut = pyproject_toml_raw.parse::<DocumentMut>()?;
         pyproject_toml["project"]["version"] = new_version.clone().into();
-        write(
+        write_file(
             &self.path,
             format!(
                 "{}{}",
@@ -90,10 +98,30 @@ impl Package for PythonPackage {
         self.is_changed
     }
 
+    async fn version_declarations(&self) -> Result<Vec<(PathBuf, String)>> {
+        crate::dunder_versions(&self.path).await
+    }
+
     fn set_name(&mut self, name: String) {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn default_publish_command(&self) -> String {
         "uv publish".to_string()
     }
@@ -183,7 +211,10 @@ version = "1.0.0"
             PathBuf::from("pyproject.toml"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("version = \"1.0.1\""));
@@ -191,6 +222,41 @@ version = "1.0.0"
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_python_package_update_version_prerelease() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject_toml = temp_dir.path().join("pyproject.toml");
+        fs::write(
+            &pyproject_toml,
+            r#"[project]
+name = "test-package"
+version = "1.2.0b3"
+"#,
+        )
+        .unwrap();
+
+        let mut package = PythonPackage::new(
+            Some("test-package".to_string()),
+            Some("1.2.0b3".to_string()),
+            pyproject_toml.clone(),
+            PathBuf::from("pyproject.toml"),
+        );
+
+        package
+            .update_version(UpdateType::Prerelease, None)
+            .await
+            .unwrap();
+        let content = read_to_string(&pyproject_toml).await.unwrap();
+        assert!(content.contains("version = \"1.2.0b4\""));
+
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
+        let content = read_to_string(&pyproject_toml).await.unwrap();
+        assert!(content.contains("version = \"1.2.0\""));
+    }
+
     #[tokio::test]
     async fn test_python_package_update_version_minor() {
         let temp_dir = TempDir::new().unwrap();
@@ -211,7 +277,10 @@ version = "1.0.0"
             PathBuf::from("pyproject.toml"),
         );
 
-        package.update_version(UpdateType::Minor).await.unwrap();
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("version = \"1.1.0\""));
@@ -239,7 +308,10 @@ version = "1.0.0"
             PathBuf::from("pyproject.toml"),
         );
 
-        package.update_version(UpdateType::Major).await.unwrap();
+        package
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("version = \"2.0.0\""));
@@ -272,7 +344,10 @@ requests = "2.31.0"
             PathBuf::from("pyproject.toml"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("version = \"1.2.4\""));
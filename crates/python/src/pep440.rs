@@ -0,0 +1,165 @@
+use anyhow::{Result, bail};
+use changepacks_core::UpdateType;
+use changepacks_utils::next_version_with_id;
+
+/// PEP 440 pre-release labels with the semver identifier they stand for, longest first so
+/// `alpha` is not read as `a` followed by `lpha`
+const PRE_LABELS: [(&str, &str); 9] = [
+    ("alpha", "alpha"),
+    ("beta", "beta"),
+    ("preview", "rc"),
+    ("pre", "rc"),
+    ("rc", "rc"),
+    ("dev", "dev"),
+    ("a", "alpha"),
+    ("b", "beta"),
+    ("c", "rc"),
+];
+
+/// Next version of a pyproject version, in the normalized PEP 440 spelling
+///
+/// The version is bumped as semver, so `1.2.0b3` takes a prerelease bump to `1.2.0b4` and a
+/// patch bump to `1.2.0`.
+///
+/// # Errors
+/// Returns error if the version can't be bumped or the result has no PEP 440 form.
+pub fn next_pep440_version(
+    version: &str,
+    update_type: UpdateType,
+    prerelease_id: Option<&str>,
+) -> Result<String> {
+    to_pep440(&next_version_with_id(
+        &to_semver(version),
+        update_type,
+        prerelease_id,
+    )?)
+}
+
+/// Semver spelling of a PEP 440 pre-release (`1.2.0b3` is `1.2.0-beta.3`, `1.0.dev1` is
+/// `1.0-dev.1`); anything else is returned unchanged
+fn to_semver(version: &str) -> String {
+    let (public, local) = match version.split_once('+') {
+        Some((public, local)) => (public, Some(local)),
+        None => (version, None),
+    };
+    let release_len = release_len(public);
+    let (release, rest) = public.split_at(release_len);
+    let rest = rest.to_ascii_lowercase();
+    let rest = rest.strip_prefix(['-', '_', '.']).unwrap_or(&rest);
+    let Some((label, number)) = PRE_LABELS.iter().find_map(|(label, semver)| {
+        let number = rest.strip_prefix(label)?;
+        let number = number.strip_prefix(['-', '_', '.']).unwrap_or(number);
+        number
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+            .then_some((*semver, if number.is_empty() { "0" } else { number }))
+    }) else {
+        return version.to_string();
+    };
+    if release.is_empty() {
+        return version.to_string();
+    }
+    format!(
+        "{release}-{label}.{number}{}",
+        local.map(|local| format!("+{local}")).unwrap_or_default()
+    )
+}
+
+/// Length of the leading `N(.N)*` release segment
+fn release_len(version: &str) -> usize {
+    let mut len = 0;
+    for (index, part) in version.split('.').enumerate() {
+        let digits = part.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            break;
+        }
+        len += digits + usize::from(index > 0);
+        if digits < part.len() {
+            break;
+        }
+    }
+    len
+}
+
+/// PEP 440 spelling of a semver version (`1.2.0-beta.3` is `1.2.0b3`)
+///
+/// # Errors
+/// Returns error for a pre-release whose identifier is not `alpha`, `beta`, `rc` or `dev`.
+fn to_pep440(version: &str) -> Result<String> {
+    let (public, build) = match version.split_once('+') {
+        Some((public, build)) => (public, Some(build)),
+        None => (version, None),
+    };
+    let Some((release, prerelease)) = public.split_once('-') else {
+        return Ok(version.to_string());
+    };
+    let (name, number) = match prerelease.rsplit_once('.') {
+        Some((name, number)) if number.bytes().all(|b| b.is_ascii_digit()) => (name, number),
+        _ => (prerelease, "0"),
+    };
+    let marker = match name {
+        "alpha" => "a",
+        "beta" => "b",
+        "rc" => "rc",
+        "dev" => ".dev",
+        _ => bail!(
+            "Pre-release '{prerelease}' of {version} has no PEP 440 form; use the identifier alpha, beta, rc or dev"
+        ),
+    };
+    Ok(format!(
+        "{release}{marker}{number}{}",
+        build.map(|build| format!("+{build}")).unwrap_or_default()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_semver() {
+        for (pep440, semver) in [
+            ("1.2.0b3", "1.2.0-beta.3"),
+            ("1.2.0a1", "1.2.0-alpha.1"),
+            ("1.2.0rc2", "1.2.0-rc.2"),
+            ("1.2.0.RC2", "1.2.0-rc.2"),
+            ("1.2.0-preview.1", "1.2.0-rc.1"),
+            ("1.2.0c", "1.2.0-rc.0"),
+            ("1.0.dev1", "1.0-dev.1"),
+            ("1.2.0b3+local.1", "1.2.0-beta.3+local.1"),
+            ("1.2.0-beta.3", "1.2.0-beta.3"),
+            ("1.2.0", "1.2.0"),
+            ("1.2.0.post1", "1.2.0.post1"),
+        ] {
+            assert_eq!(to_semver(pep440), semver, "{pep440}");
+        }
+    }
+
+    #[test]
+    fn test_to_pep440() {
+        assert_eq!(to_pep440("1.2.0-beta.3").unwrap(), "1.2.0b3");
+        assert_eq!(to_pep440("1.2.0-alpha.0").unwrap(), "1.2.0a0");
+        assert_eq!(to_pep440("1.2.0-rc.1+local").unwrap(), "1.2.0rc1+local");
+        assert_eq!(to_pep440("1.0.1-dev.2").unwrap(), "1.0.1.dev2");
+        assert_eq!(to_pep440("1.2.0").unwrap(), "1.2.0");
+        let err = to_pep440("1.2.1-0").unwrap_err().to_string();
+        assert!(err.contains("has no PEP 440 form"), "{err}");
+        assert!(to_pep440("1.2.1-next.1").is_err());
+    }
+
+    #[test]
+    fn test_next_pep440_version() {
+        assert_eq!(
+            next_pep440_version("1.2.0b3", UpdateType::Prerelease, None).unwrap(),
+            "1.2.0b4"
+        );
+        assert_eq!(
+            next_pep440_version("1.2.0b3", UpdateType::Patch, None).unwrap(),
+            "1.2.0"
+        );
+        assert_eq!(
+            next_pep440_version("1.2.0", UpdateType::Minor, None).unwrap(),
+            "1.3.0"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use changepacks_utils::rewrite_version_range;
+
+/// Operators of a single-version specifier that keep pointing at the bumped version
+const OPERATORS: [&str; 4] = ["===", "==", "~=", ">="];
+
+/// Name of a distribution normalized as in PEP 503 (`My_Package` is `my-package`)
+pub(crate) fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Name of the distribution a PEP 508 requirement such as `core[cli]>=1.2; python_version>'3.9'`
+/// asks for
+pub(crate) fn requirement_name(requirement: &str) -> &str {
+    let requirement = requirement.trim_start();
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    &requirement[..end]
+}
+
+/// Rewrite the version specifier of a PEP 508 requirement to point at `version`
+///
+/// `==1.2.0`, `>=1.2.0`, `~=1.2` and `==1.2.*` keep their operator and precision; extras,
+/// spacing and environment markers are kept as written. Returns `None` for requirements
+/// without a single-version specifier, e.g. `core`, `core<2`, `core>=1.0,<2.0` or a direct
+/// URL reference.
+pub(crate) fn rewrite_requirement(requirement: &str, version: &str) -> Option<String> {
+    let (spec, marker) = match requirement.find(';') {
+        Some(index) => requirement.split_at(index),
+        None => (requirement, ""),
+    };
+    let name = requirement_name(spec);
+    let name_end = spec.find(name)? + name.len();
+    let rest = &spec[name_end..];
+    let extras_end = match rest.trim_start().strip_prefix('[') {
+        Some(extras) => rest.len() - extras.len() + extras.find(']')? + 1,
+        None => 0,
+    };
+    let (head, specifier) = spec.split_at(name_end + extras_end);
+    if specifier.contains([',', '@']) {
+        return None;
+    }
+    let trimmed = specifier.trim();
+    let (open, trimmed, close) = match trimmed.strip_prefix('(') {
+        Some(inner) => ("(", inner.strip_suffix(')')?.trim(), ")"),
+        None => ("", trimmed, ""),
+    };
+    let operator = OPERATORS.iter().find(|op| trimmed.starts_with(**op))?;
+    let current = trimmed[operator.len()..].trim();
+    let rewritten = rewrite_version_range(current, version)?;
+    let leading = &specifier[..specifier.len() - specifier.trim_start().len()];
+    let trailing = &specifier[specifier.trim_end().len()..];
+    let spacing = &trimmed[operator.len()..trimmed.len() - current.len()];
+    Some(format!(
+        "{head}{leading}{open}{operator}{spacing}{rewritten}{close}{trailing}{marker}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("My_Package"), "my-package");
+        assert_eq!(normalize_name("a.-_b"), "a-b");
+    }
+
+    #[test]
+    fn test_rewrite_requirement() {
+        for (requirement, expected) in [
+            ("core==1.2.0", Some("core==1.3.0")),
+            ("core>=1.2.0", Some("core>=1.3.0")),
+            ("core ~= 1.2", Some("core ~= 1.3")),
+            ("core==1.2.*", Some("core==1.3.*")),
+            ("core[cli]>=1.2.0", Some("core[cli]>=1.3.0")),
+            (
+                "core>=1.2.0; python_version >= '3.9'",
+                Some("core>=1.3.0; python_version >= '3.9'"),
+            ),
+            ("core (>=1.2.0)", Some("core (>=1.3.0)")),
+            ("core", None),
+            ("core<2", None),
+            ("core>=1.0,<2.0", None),
+            ("core @ file:///src/core", None),
+        ] {
+            assert_eq!(
+                rewrite_requirement(requirement, "1.3.0").as_deref(),
+                expected,
+                "{requirement}"
+            );
+        }
+        assert_eq!(requirement_name(" core[cli]>=1.0"), "core");
+    }
+}
@@ -1,11 +1,13 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, UpdateType, Workspace};
-use changepacks_utils::next_version;
-use std::collections::HashSet;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{member_manifests, read_file, write_file};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
-use toml_edit::DocumentMut;
+use toml_edit::{Array, DocumentMut};
+
+use crate::pep440::next_pep440_version;
+use crate::requirement::{normalize_name, requirement_name, rewrite_requirement};
 
 #[derive(Debug)]
 pub struct PythonWorkspace {
@@ -14,7 +16,9 @@ pub struct PythonWorkspace {
     version: Option<String>,
     name: Option<String>,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    member_patterns: Option<Vec<String>>,
 }
 
 impl PythonWorkspace {
@@ -31,9 +35,18 @@ impl PythonWorkspace {
             name,
             version,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            member_patterns: None,
         }
     }
+
+    /// Record the member globs of `[tool.uv.workspace]` (`!` patterns exclude)
+    #[must_use]
+    pub fn with_member_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.member_patterns = Some(patterns);
+        self
+    }
 }
 
 #[async_trait]
@@ -50,19 +63,25 @@ impl Workspace for PythonWorkspace {
         self.version.as_deref()
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
-        let next_version = next_version(
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let next_version = next_pep440_version(
             self.version.as_ref().unwrap_or(&String::from("0.0.0")),
             update_type,
-        )?;
+            prerelease_id,
+        )
+        .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let pyproject_toml_raw = read_to_string(&self.path).await?;
+        let pyproject_toml_raw = read_file(&self.path).await?;
         let mut pyproject_toml: DocumentMut = pyproject_toml_raw.parse::<DocumentMut>()?;
         if pyproject_toml.get("project").is_none() {
             pyproject_toml["project"] = toml_edit::Item::Table(toml_edit::Table::new());
         }
         pyproject_toml["project"]["version"] = next_version.clone().into();
-        write(
+        write_file(
             &self.path,
             format!(
                 "{}{}",
@@ -87,6 +106,10 @@ impl Workspace for PythonWorkspace {
         self.is_changed
     }
 
+    async fn version_declarations(&self) -> Result<Vec<(PathBuf, String)>> {
+        crate::dunder_versions(&self.path).await
+    }
+
     fn set_changed(&mut self, changed: bool) {
         self.is_changed = changed;
     }
@@ -99,6 +122,22 @@ impl Workspace for PythonWorkspace {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn default_publish_command(&self) -> String {
         "uv publish".to_string()
     }
@@ -114,6 +153,100 @@ impl Workspace for PythonWorkspace {
     fn add_dependency(&mut self, dependency: &str) {
         self.dependencies.insert(dependency.to_string());
     }
+
+    fn member_patterns(&self) -> Option<&[String]> {
+        self.member_patterns.as_deref()
+    }
+
+    /// Move the requirements on bumped members in the root and member pyproject.toml files
+    async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
+        let versions: HashMap<String, &str> = packages
+            .iter()
+            .filter(|package| package.language() == Language::Python)
+            .filter_map(|package| Some((normalize_name(package.name()?), package.version()?)))
+            .collect();
+        if versions.is_empty() {
+            return Ok(());
+        }
+        let members = self.member_patterns.as_deref().unwrap_or_default();
+        for path in std::iter::once(self.path.clone()).chain(member_manifests(
+            &self.path,
+            members,
+            "pyproject.toml",
+        )) {
+            let raw = read_file(&path).await?;
+            let mut pyproject_toml: DocumentMut = raw
+                .parse()
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            if !rewrite_requirements(&mut pyproject_toml, &versions) {
+                continue;
+            }
+            write_file(
+                &path,
+                format!(
+                    "{}{}",
+                    pyproject_toml.to_string().trim_end(),
+                    if raw.ends_with('\n') { "\n" } else { "" }
+                ),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Rewrite the requirements on bumped packages (`versions` by normalized name) in
+/// `[project] dependencies`, `[project.optional-dependencies]` and `[dependency-groups]`;
+/// returns whether anything changed
+fn rewrite_requirements(
+    pyproject_toml: &mut DocumentMut,
+    versions: &HashMap<String, &str>,
+) -> bool {
+    let mut lists: Vec<&mut Array> = Vec::new();
+    for (key, item) in pyproject_toml.as_table_mut().iter_mut() {
+        let Some(table) = item.as_table_like_mut() else {
+            continue;
+        };
+        match key.get() {
+            "project" => {
+                for (key, item) in table.iter_mut() {
+                    match key.get() {
+                        "dependencies" => lists.extend(item.as_array_mut()),
+                        "optional-dependencies" => lists.extend(
+                            item.as_table_like_mut()
+                                .into_iter()
+                                .flat_map(|extras| extras.iter_mut())
+                                .filter_map(|(_, list)| list.as_array_mut()),
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            "dependency-groups" => {
+                lists.extend(table.iter_mut().filter_map(|(_, list)| list.as_array_mut()));
+            }
+            _ => {}
+        }
+    }
+
+    let mut updated = false;
+    for list in lists {
+        for requirement in list.iter_mut() {
+            let Some(current) = requirement.as_str() else {
+                continue;
+            };
+            if let Some(version) = versions.get(&normalize_name(requirement_name(current)))
+                && let Some(rewritten) = rewrite_requirement(current, version)
+                && rewritten != current
+            {
+                let decor = requirement.decor().clone();
+                *requirement = rewritten.into();
+                *requirement.decor_mut() = decor;
+                updated = true;
+            }
+        }
+    }
+    updated
 }
 
 #[cfg(test)]
@@ -201,7 +334,10 @@ version = "1.0.0"
             PathBuf::from("pyproject.toml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("version = \"1.0.1\""));
@@ -228,7 +364,10 @@ members = ["packages/*"]
             PathBuf::from("pyproject.toml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("[project]"));
@@ -260,7 +399,10 @@ version = "1.0.0"
             PathBuf::from("pyproject.toml"),
         );
 
-        workspace.update_version(UpdateType::Minor).await.unwrap();
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("version = \"1.1.0\""));
@@ -291,7 +433,10 @@ version = "1.0.0"
             PathBuf::from("pyproject.toml"),
         );
 
-        workspace.update_version(UpdateType::Major).await.unwrap();
+        workspace
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&pyproject_toml).await.unwrap();
         assert!(content.contains("version = \"2.0.0\""));
@@ -337,4 +482,87 @@ version = "1.0.0"
         workspace.set_name("my-project".to_string());
         assert_eq!(workspace.name(), Some("my-project"));
     }
+
+    #[tokio::test]
+    async fn test_python_workspace_update_workspace_dependencies() {
+        use crate::package::PythonPackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("pyproject.toml");
+        let root_raw = r#"[project]
+name = "root"
+version = "1.0.0"
+dependencies = ["My_Core>=1.2.0"]
+
+[tool.uv.workspace]
+members = ["packages/*"]
+
+[tool.uv.sources]
+my-core = { workspace = true }
+"#;
+        fs::write(&root, root_raw).unwrap();
+        let app = temp_dir.path().join("packages/app/pyproject.toml");
+        fs::create_dir_all(app.parent().unwrap()).unwrap();
+        fs::write(
+            &app,
+            r#"[project]
+name = "app"
+version = "0.1.0"
+dependencies = [
+    "my-core==1.2.0",  # pinned
+    "requests>=2.0",
+]
+
+[project.optional-dependencies]
+cli = ["my-core[cli]~=1.2"]
+
+[dependency-groups]
+dev = ["my-core>=1.0,<2.0", { include-group = "lint" }]
+"#,
+        )
+        .unwrap();
+
+        let workspace = PythonWorkspace::new(
+            Some("root".to_string()),
+            Some("1.0.0".to_string()),
+            root.clone(),
+            PathBuf::from("pyproject.toml"),
+        )
+        .with_member_patterns(vec!["packages/*".to_string()]);
+        let core = PythonPackage::new(
+            Some("my-core".to_string()),
+            Some("1.3.0".to_string()),
+            temp_dir.path().join("packages/core/pyproject.toml"),
+            PathBuf::from("packages/core/pyproject.toml"),
+        );
+
+        workspace
+            .update_workspace_dependencies(&[&core])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_to_string(&root).await.unwrap(),
+            root_raw.replace("My_Core>=1.2.0", "My_Core>=1.3.0")
+        );
+        assert_eq!(
+            read_to_string(&app).await.unwrap(),
+            r#"[project]
+name = "app"
+version = "0.1.0"
+dependencies = [
+    "my-core==1.3.0",  # pinned
+    "requests>=2.0",
+]
+
+[project.optional-dependencies]
+cli = ["my-core[cli]~=1.3"]
+
+[dependency-groups]
+dev = ["my-core>=1.0,<2.0", { include-group = "lint" }]
+"#
+        );
+
+        temp_dir.close().unwrap();
+    }
 }
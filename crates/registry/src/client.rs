@@ -0,0 +1,323 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::Registry;
+
+/// crates.io rejects requests without an identifying User-Agent
+const USER_AGENT: &str = concat!(
+    "changepacks/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/changepacks/changepacks)"
+);
+
+/// Client for one registry's metadata API
+#[derive(Debug, Clone)]
+pub struct RegistryClient {
+    registry: Registry,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl RegistryClient {
+    /// Client for the public instance of `registry`
+    #[must_use]
+    pub fn new(registry: Registry) -> Self {
+        Self::with_base_url(registry, registry.default_url())
+    }
+
+    /// Client for a mirror or private instance of `registry` at `base_url`
+    #[must_use]
+    pub fn with_base_url(registry: Registry, base_url: &str) -> Self {
+        Self {
+            registry,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// Send `token` as a bearer token, for private registries and feeds
+    #[must_use]
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn registry(&self) -> Registry {
+        self.registry
+    }
+
+    /// Latest stable version of package `name`, or `None` if it was never published
+    ///
+    /// # Errors
+    /// Returns error if the registry cannot be reached or answers with invalid metadata.
+    pub async fn latest_version(&self, name: &str) -> Result<Option<String>> {
+        Ok(self
+            .metadata(name)
+            .await?
+            .and_then(|metadata| self.registry.latest_version(&metadata)))
+    }
+
+    /// Whether `version` of package `name` is already published
+    ///
+    /// # Errors
+    /// Returns error if the registry cannot be reached or answers with invalid metadata.
+    pub async fn version_exists(&self, name: &str, version: &str) -> Result<bool> {
+        Ok(self.metadata(name).await?.is_some_and(|metadata| {
+            self.registry
+                .versions(&metadata)
+                .iter()
+                .any(|published| self.registry.same_version(published, version))
+        }))
+    }
+
+    /// Poll until `version` of package `name` is visible, checking every `interval`
+    ///
+    /// Registries serve new versions from caches and mirrors that lag behind the
+    /// upload, so dependents published right away may fail to resolve them. Lookup
+    /// errors count as not visible yet. Returns `false` if `timeout` passes first.
+    pub async fn wait_for_version(
+        &self,
+        name: &str,
+        version: &str,
+        timeout: Duration,
+        interval: Duration,
+    ) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if self.version_exists(name, version).await.unwrap_or(false) {
+                return true;
+            }
+            if tokio::time::Instant::now() + interval > deadline {
+                return false;
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn metadata(&self, name: &str) -> Result<Option<Value>> {
+        let base_url = self.base_url.clone();
+        let registry = self.registry;
+        let name = name.to_string();
+        let token = self.token.clone();
+        tokio::task::spawn_blocking(move || {
+            // Private NuGet feeds are addressed by their service index, which names the
+            // flat container holding the version lists
+            let url = if registry == Registry::NuGet && base_url.ends_with("/index.json") {
+                let index = fetch(&base_url, token.as_deref())?
+                    .with_context(|| format!("NuGet service index not found at {base_url}"))?;
+                let package_base = nuget_package_base(&index).with_context(|| {
+                    format!("NuGet service index {base_url} has no PackageBaseAddress")
+                })?;
+                format!(
+                    "{}/{}/index.json",
+                    package_base.trim_end_matches('/'),
+                    name.to_lowercase()
+                )
+            } else {
+                registry.package_url(&base_url, &name)
+            };
+            fetch(&url, token.as_deref())
+        })
+        .await?
+    }
+}
+
+/// `@id` of the `PackageBaseAddress` resource in a NuGet v3 service index
+fn nuget_package_base(index: &Value) -> Option<&str> {
+    index["resources"].as_array()?.iter().find_map(|resource| {
+        resource["@type"]
+            .as_str()?
+            .starts_with("PackageBaseAddress/")
+            .then(|| resource["@id"].as_str())?
+    })
+}
+
+/// JSON body at `url`, or `None` on 404
+fn fetch(url: &str, token: Option<&str>) -> Result<Option<Value>> {
+    let mut request = ureq::get(url).header("User-Agent", USER_AGENT);
+    if let Some(token) = token {
+        request = request.header("Authorization", &format!("Bearer {token}"));
+    }
+    match request.call() {
+        Ok(mut response) => {
+            let body = response.body_mut().read_to_string()?;
+            Ok(Some(serde_json::from_str(&body).with_context(|| {
+                format!("Invalid registry metadata from {url}")
+            })?))
+        }
+        Err(ureq::Error::StatusCode(404)) => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to query {url}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve one response with `status` and `body`, returning the base URL
+    fn serve(status: u16, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        url
+    }
+
+    const NPM_METADATA: &str =
+        r#"{"dist-tags":{"latest":"1.1.0"},"versions":{"1.0.0":{},"1.1.0":{}}}"#;
+
+    #[test]
+    fn test_new_uses_default_url() {
+        let client = RegistryClient::new(Registry::CratesIo);
+        assert_eq!(client.registry(), Registry::CratesIo);
+        assert_eq!(client.base_url, "https://crates.io");
+        let client = RegistryClient::with_base_url(Registry::Npm, "http://mirror/");
+        assert_eq!(client.base_url, "http://mirror");
+    }
+
+    #[tokio::test]
+    async fn test_latest_version() {
+        let client = RegistryClient::with_base_url(Registry::Npm, &serve(200, NPM_METADATA));
+        assert_eq!(
+            client.latest_version("pkg").await.unwrap().as_deref(),
+            Some("1.1.0")
+        );
+
+        let client = RegistryClient::with_base_url(Registry::Npm, &serve(404, "Not Found"));
+        assert_eq!(client.latest_version("new-pkg").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_version_exists() {
+        let client = RegistryClient::with_base_url(Registry::Npm, &serve(200, NPM_METADATA));
+        assert!(client.version_exists("pkg", "1.0.0").await.unwrap());
+
+        let client = RegistryClient::with_base_url(Registry::Npm, &serve(200, NPM_METADATA));
+        assert!(!client.version_exists("pkg", "1.2.0").await.unwrap());
+
+        let client = RegistryClient::with_base_url(Registry::Npm, &serve(404, "Not Found"));
+        assert!(!client.version_exists("new-pkg", "1.0.0").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_version() {
+        let client = RegistryClient::with_base_url(Registry::Npm, &serve(200, NPM_METADATA));
+        assert!(
+            client
+                .wait_for_version(
+                    "pkg",
+                    "1.1.0",
+                    Duration::from_secs(5),
+                    Duration::from_millis(10)
+                )
+                .await
+        );
+
+        // Only the first request is answered; later polls fail to connect
+        let client = RegistryClient::with_base_url(Registry::Npm, &serve(404, "Not Found"));
+        assert!(
+            !client
+                .wait_for_version(
+                    "pkg",
+                    "1.2.0",
+                    Duration::from_millis(50),
+                    Duration::from_millis(10)
+                )
+                .await
+        );
+    }
+
+    /// Serve one `200` response with `body`, returning the base URL and the request received
+    fn serve_recording(body: String) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_with_token_sends_bearer() {
+        let (url, request) = serve_recording(NPM_METADATA.to_string());
+        let client = RegistryClient::with_base_url(Registry::Npm, &url).with_token("secret");
+        assert!(client.version_exists("@acme/pkg", "1.0.0").await.unwrap());
+        let request = request.join().unwrap().to_lowercase();
+        assert!(request.starts_with("get /@acme%2fpkg "));
+        assert!(request.contains("authorization: bearer secret"));
+    }
+
+    #[tokio::test]
+    async fn test_nuget_service_index() {
+        let (flat_url, flat_request) =
+            serve_recording(r#"{"versions":["1.0.0","1.1.0"]}"#.to_string());
+        let index = serde_json::json!({
+            "version": "3.0.0",
+            "resources": [
+                { "@id": "http://unused/query", "@type": "SearchQueryService" },
+                { "@id": format!("{flat_url}/flat2/"), "@type": "PackageBaseAddress/3.0.0" },
+            ],
+        });
+        let (index_url, _) = serve_recording(index.to_string());
+        let client =
+            RegistryClient::with_base_url(Registry::NuGet, &format!("{index_url}/v3/index.json"));
+        assert!(client.version_exists("Acme.Core", "1.1.0").await.unwrap());
+        assert!(
+            flat_request
+                .join()
+                .unwrap()
+                .starts_with("GET /flat2/acme.core/index.json ")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jsr_version_exists() {
+        let (url, request) =
+            serve_recording(r#"{"latest":"1.0.0","versions":{"1.0.0":{}}}"#.to_string());
+        let client = RegistryClient::with_base_url(Registry::Jsr, &url);
+        assert!(client.version_exists("@std/path", "1.0.0").await.unwrap());
+        assert!(
+            request
+                .join()
+                .unwrap()
+                .starts_with("GET /@std/path/meta.json ")
+        );
+
+        let client = RegistryClient::with_base_url(Registry::Jsr, &serve(404, "Not Found"));
+        assert!(!client.version_exists("@std/new", "1.0.0").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let client = RegistryClient::with_base_url(Registry::PyPi, &serve(500, "oops"));
+        let err = client.version_exists("pkg", "1.0.0").await.unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to query"));
+
+        let client = RegistryClient::with_base_url(Registry::PyPi, &serve(200, "<html>"));
+        let err = client.latest_version("pkg").await.unwrap_err();
+        assert!(err.to_string().contains("Invalid registry metadata"));
+    }
+}
@@ -0,0 +1,7 @@
+//! Clients for looking up published package versions on public and private registries.
+
+mod client;
+mod registry;
+
+pub use client::RegistryClient;
+pub use registry::Registry;
@@ -0,0 +1,244 @@
+use serde_json::Value;
+
+/// Package registry with a public metadata API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Registry {
+    Npm,
+    CratesIo,
+    PyPi,
+    NuGet,
+    PubDev,
+    Jsr,
+}
+
+impl Registry {
+    /// Registry for a name reported by `Language::registry`, if it has a client
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "npm" => Some(Self::Npm),
+            "crates.io" => Some(Self::CratesIo),
+            "pypi" => Some(Self::PyPi),
+            "nuget" => Some(Self::NuGet),
+            "pub.dev" => Some(Self::PubDev),
+            "jsr" => Some(Self::Jsr),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::CratesIo => "crates.io",
+            Self::PyPi => "pypi",
+            Self::NuGet => "nuget",
+            Self::PubDev => "pub.dev",
+            Self::Jsr => "jsr",
+        }
+    }
+
+    /// Base URL of the public registry API
+    #[must_use]
+    pub const fn default_url(&self) -> &'static str {
+        match self {
+            Self::Npm => "https://registry.npmjs.org",
+            Self::CratesIo => "https://crates.io",
+            Self::PyPi => "https://pypi.org",
+            Self::NuGet => "https://api.nuget.org",
+            Self::PubDev => "https://pub.dev",
+            Self::Jsr => "https://jsr.io",
+        }
+    }
+
+    /// Metadata URL for package `name` under `base`
+    pub(crate) fn package_url(&self, base: &str, name: &str) -> String {
+        let base = base.trim_end_matches('/');
+        match self {
+            Self::Npm => format!("{base}/{}", name.replace('/', "%2F")),
+            Self::CratesIo => format!("{base}/api/v1/crates/{name}"),
+            Self::PyPi => format!("{base}/pypi/{name}/json"),
+            Self::NuGet => format!("{base}/v3-flatcontainer/{}/index.json", name.to_lowercase()),
+            Self::PubDev => format!("{base}/api/packages/{name}"),
+            // JSR names are always scoped (`@scope/package`)
+            Self::Jsr => format!("{base}/@{}/meta.json", name.trim_start_matches('@')),
+        }
+    }
+
+    /// Latest stable version named in the package metadata
+    pub(crate) fn latest_version(&self, metadata: &Value) -> Option<String> {
+        let latest = match self {
+            Self::Npm => metadata.pointer("/dist-tags/latest"),
+            Self::CratesIo => metadata
+                .pointer("/crate/max_stable_version")
+                .filter(|version| !version.is_null())
+                .or_else(|| metadata.pointer("/crate/max_version")),
+            Self::PyPi => metadata.pointer("/info/version"),
+            Self::PubDev => metadata.pointer("/latest/version"),
+            Self::Jsr => metadata.get("latest"),
+            Self::NuGet => {
+                // The flat container lists versions oldest first
+                let versions = self.versions(metadata);
+                return versions
+                    .iter()
+                    .rev()
+                    .find(|version| !version.contains('-'))
+                    .or_else(|| versions.last())
+                    .cloned();
+            }
+        };
+        latest.and_then(Value::as_str).map(str::to_string)
+    }
+
+    /// Every version listed in the package metadata, yanked ones included
+    pub(crate) fn versions(&self, metadata: &Value) -> Vec<String> {
+        let strings = |values: Option<&Value>, key: Option<&str>| -> Vec<String> {
+            values
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|value| key.map_or(Some(value), |key| value.get(key)))
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        };
+        let keys = |value: Option<&Value>| -> Vec<String> {
+            value
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|map| map.keys().cloned())
+                .collect()
+        };
+        match self {
+            Self::Npm | Self::Jsr => keys(metadata.get("versions")),
+            Self::PyPi => keys(metadata.get("releases")),
+            Self::CratesIo => strings(metadata.get("versions"), Some("num")),
+            Self::PubDev => strings(metadata.get("versions"), Some("version")),
+            Self::NuGet => strings(metadata.get("versions"), None),
+        }
+    }
+
+    /// Whether `published` and `version` name the same release
+    pub(crate) fn same_version(&self, published: &str, version: &str) -> bool {
+        match self {
+            // NuGet lowercases versions in the flat container
+            Self::NuGet => published.eq_ignore_ascii_case(version),
+            _ => published == version,
+        }
+    }
+}
+
+impl std::fmt::Display for Registry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case(Registry::Npm)]
+    #[case(Registry::CratesIo)]
+    #[case(Registry::PyPi)]
+    #[case(Registry::NuGet)]
+    #[case(Registry::PubDev)]
+    #[case(Registry::Jsr)]
+    fn test_from_name_round_trip(#[case] registry: Registry) {
+        assert_eq!(Registry::from_name(registry.name()), Some(registry));
+        assert_eq!(registry.to_string(), registry.name());
+    }
+
+    #[rstest]
+    #[case("maven")]
+    #[case("")]
+    fn test_from_name_unsupported(#[case] name: &str) {
+        assert_eq!(Registry::from_name(name), None);
+    }
+
+    #[rstest]
+    #[case(Registry::Npm, "@scope/pkg", "http://r/@scope%2Fpkg")]
+    #[case(Registry::Npm, "pkg", "http://r/pkg")]
+    #[case(Registry::CratesIo, "serde", "http://r/api/v1/crates/serde")]
+    #[case(Registry::PyPi, "requests", "http://r/pypi/requests/json")]
+    #[case(
+        Registry::NuGet,
+        "Newtonsoft.Json",
+        "http://r/v3-flatcontainer/newtonsoft.json/index.json"
+    )]
+    #[case(Registry::PubDev, "http", "http://r/api/packages/http")]
+    #[case(Registry::Jsr, "@std/path", "http://r/@std/path/meta.json")]
+    fn test_package_url(#[case] registry: Registry, #[case] name: &str, #[case] expected: &str) {
+        assert_eq!(registry.package_url("http://r/", name), expected);
+    }
+
+    #[rstest]
+    #[case(
+        Registry::Npm,
+        json!({"dist-tags": {"latest": "1.1.0", "next": "2.0.0-rc.1"}, "versions": {"1.0.0": {}, "1.1.0": {}, "2.0.0-rc.1": {}}}),
+        Some("1.1.0"),
+        &["1.0.0", "1.1.0", "2.0.0-rc.1"]
+    )]
+    #[case(
+        Registry::CratesIo,
+        json!({"crate": {"max_version": "2.0.0-rc.1", "max_stable_version": "1.1.0"}, "versions": [{"num": "2.0.0-rc.1"}, {"num": "1.1.0"}]}),
+        Some("1.1.0"),
+        &["2.0.0-rc.1", "1.1.0"]
+    )]
+    #[case(
+        Registry::CratesIo,
+        json!({"crate": {"max_version": "0.1.0-alpha", "max_stable_version": null}, "versions": [{"num": "0.1.0-alpha"}]}),
+        Some("0.1.0-alpha"),
+        &["0.1.0-alpha"]
+    )]
+    #[case(
+        Registry::PyPi,
+        json!({"info": {"version": "2.31.0"}, "releases": {"2.30.0": [], "2.31.0": []}}),
+        Some("2.31.0"),
+        &["2.30.0", "2.31.0"]
+    )]
+    #[case(
+        Registry::NuGet,
+        json!({"versions": ["1.0.0", "1.1.0", "2.0.0-beta"]}),
+        Some("1.1.0"),
+        &["1.0.0", "1.1.0", "2.0.0-beta"]
+    )]
+    #[case(
+        Registry::NuGet,
+        json!({"versions": ["1.0.0-beta"]}),
+        Some("1.0.0-beta"),
+        &["1.0.0-beta"]
+    )]
+    #[case(
+        Registry::PubDev,
+        json!({"latest": {"version": "1.2.0"}, "versions": [{"version": "1.1.0"}, {"version": "1.2.0"}]}),
+        Some("1.2.0"),
+        &["1.1.0", "1.2.0"]
+    )]
+    #[case(
+        Registry::Jsr,
+        json!({"scope": "std", "name": "path", "latest": "1.0.1", "versions": {"1.0.0": {}, "1.0.1": {"yanked": false}}}),
+        Some("1.0.1"),
+        &["1.0.0", "1.0.1"]
+    )]
+    #[case(Registry::Npm, json!({}), None, &[])]
+    fn test_parse_metadata(
+        #[case] registry: Registry,
+        #[case] metadata: Value,
+        #[case] latest: Option<&str>,
+        #[case] versions: &[&str],
+    ) {
+        assert_eq!(registry.latest_version(&metadata).as_deref(), latest);
+        assert_eq!(registry.versions(&metadata), versions);
+    }
+
+    #[test]
+    fn test_same_version() {
+        assert!(Registry::NuGet.same_version("1.0.0-beta", "1.0.0-Beta"));
+        assert!(!Registry::Npm.same_version("1.0.0-beta", "1.0.0-Beta"));
+        assert!(Registry::Npm.same_version("1.0.0", "1.0.0"));
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Package, Project, ProjectFinder};
+use changepacks_core::{Package, PackageMetadata, Project, ProjectFinder, project_key};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
@@ -25,6 +25,10 @@ pub struct RustProjectFinder {
     workspace_package_version: Option<String>,
     workspace_root_path: Option<PathBuf>,
     pending_workspace_packages: Vec<PendingWorkspacePackage>,
+    /// `[workspace.package]` of the workspace root, for `field.workspace = true` metadata
+    workspace_package: Option<toml::Value>,
+    /// `[package]` tables whose metadata waits on the workspace root, by manifest path
+    pending_metadata: Vec<(PathBuf, toml::Value)>,
 }
 
 impl Default for RustProjectFinder {
@@ -42,6 +46,8 @@ impl RustProjectFinder {
             workspace_package_version: None,
             workspace_root_path: None,
             pending_workspace_packages: Vec::new(),
+            workspace_package: None,
+            pending_metadata: Vec::new(),
         }
     }
 }
@@ -69,7 +75,7 @@ impl ProjectFinder for RustProjectFinder {
                     .context(format!("File name not found - {}", path.display()))?,
             )
         {
-            if self.projects.contains_key(path) {
+            if self.projects.contains_key(&project_key(path)) {
                 return Ok(());
             }
             // read Cargo.toml
@@ -102,6 +108,10 @@ impl ProjectFinder for RustProjectFinder {
                     self.workspace_package_version = ws_pkg_version;
                     self.workspace_root_path = Some(path.to_path_buf());
                 }
+                let ws_package = cargo_toml.get("workspace").and_then(|w| w.get("package"));
+                if let Some(ws_package) = ws_package {
+                    self.workspace_package = Some(ws_package.clone());
+                }
 
                 let version = cargo_toml
                     .get("package")
@@ -113,16 +123,24 @@ impl ProjectFinder for RustProjectFinder {
                     .and_then(|p| p.get("name"))
                     .and_then(|v| v.as_str())
                     .map(std::string::ToString::to_string);
-                let mut project = Project::Workspace(Box::new(RustWorkspace::new(
-                    name,
-                    version,
-                    path.to_path_buf(),
-                    relative_path.to_path_buf(),
-                )));
+                let mut project = Project::Workspace(Box::new(
+                    RustWorkspace::new(
+                        name,
+                        version,
+                        path.to_path_buf(),
+                        relative_path.to_path_buf(),
+                    )
+                    .with_member_patterns(member_patterns(&cargo_toml)),
+                ));
                 for dep_name in &dep_names {
                     project.add_dependency(dep_name);
                 }
-                self.projects.insert(path.to_path_buf(), project);
+                // Virtual workspaces describe themselves in [workspace.package]
+                project.set_metadata(cargo_metadata(
+                    cargo_toml.get("package").or(ws_package),
+                    ws_package,
+                ));
+                self.projects.insert(project_key(path), project);
 
                 // Resolve any pending packages that were visited before this workspace
                 let pending = std::mem::take(&mut self.pending_workspace_packages);
@@ -138,7 +156,7 @@ impl ProjectFinder for RustProjectFinder {
                         pkg.add_dependency(dep);
                     }
                     self.projects
-                        .insert(p.abs_path, Project::Package(Box::new(pkg)));
+                        .insert(project_key(&p.abs_path), Project::Package(Box::new(pkg)));
                 }
             } else {
                 // Check if version.workspace = true
@@ -154,6 +172,16 @@ impl ProjectFinder for RustProjectFinder {
                     .as_str()
                     .map(std::string::ToString::to_string);
 
+                if let Some(package) = cargo_toml.get("package")
+                    && ((inherits_workspace && self.workspace_package_version.is_none())
+                        || (inherits_metadata(package) && self.workspace_package.is_none()))
+                {
+                    self.pending_metadata
+                        .push((path.to_path_buf(), package.clone()));
+                }
+                let metadata =
+                    cargo_metadata(cargo_toml.get("package"), self.workspace_package.as_ref());
+
                 if inherits_workspace {
                     if self.workspace_package_version.is_some() {
                         // Workspace already visited — resolve immediately
@@ -167,9 +195,14 @@ impl ProjectFinder for RustProjectFinder {
                         for dep_name in &dep_names {
                             pkg.add_dependency(dep_name);
                         }
+                        pkg.set_metadata(metadata);
                         self.projects
-                            .insert(path.to_path_buf(), Project::Package(Box::new(pkg)));
-                    } else {
+                            .insert(project_key(path), Project::Package(Box::new(pkg)));
+                    } else if !self
+                        .pending_workspace_packages
+                        .iter()
+                        .any(|pending| project_key(&pending.abs_path) == project_key(path))
+                    {
                         // Workspace not yet visited — defer
                         self.pending_workspace_packages
                             .push(PendingWorkspacePackage {
@@ -192,7 +225,8 @@ impl ProjectFinder for RustProjectFinder {
                     for dep_name in &dep_names {
                         project.add_dependency(dep_name);
                     }
-                    self.projects.insert(path.to_path_buf(), project);
+                    project.set_metadata(metadata);
+                    self.projects.insert(project_key(path), project);
                 }
             };
         }
@@ -228,6 +262,10 @@ impl ProjectFinder for RustProjectFinder {
                 {
                     self.workspace_package_version = Some(version.to_string());
                     self.workspace_root_path = Some(candidate.clone());
+                    self.workspace_package = parsed
+                        .get("workspace")
+                        .and_then(|w| w.get("package"))
+                        .cloned();
 
                     // Insert synthetic workspace project so apply_updates() can find it
                     let ws_name = parsed
@@ -251,9 +289,10 @@ impl ProjectFinder for RustProjectFinder {
                         ws_pkg_version.or_else(|| self.workspace_package_version.clone()),
                         candidate,
                         ws_relative_path,
-                    );
+                    )
+                    .with_member_patterns(member_patterns(&parsed));
                     self.projects.insert(
-                        self.workspace_root_path.clone().unwrap(),
+                        project_key(self.workspace_root_path.as_ref().unwrap()),
                         Project::Workspace(Box::new(workspace)),
                     );
                     break;
@@ -273,13 +312,73 @@ impl ProjectFinder for RustProjectFinder {
             for dep in &pending.dependencies {
                 pkg.add_dependency(dep);
             }
-            self.projects
-                .insert(pending.abs_path, Project::Package(Box::new(pkg)));
+            self.projects.insert(
+                project_key(&pending.abs_path),
+                Project::Package(Box::new(pkg)),
+            );
+        }
+
+        for (path, package) in std::mem::take(&mut self.pending_metadata) {
+            if let Some(project) = self.projects.get_mut(&project_key(&path)) {
+                project.set_metadata(cargo_metadata(
+                    Some(&package),
+                    self.workspace_package.as_ref(),
+                ));
+            }
         }
         Ok(())
     }
 }
 
+const METADATA_FIELDS: [&str; 3] = ["description", "repository", "license"];
+
+/// Member globs of a workspace root: `[workspace].members`, with each `exclude` entry as a
+/// `!` pattern
+fn member_patterns(cargo_toml: &toml::Value) -> Vec<String> {
+    let list = |key: &str| {
+        cargo_toml
+            .get("workspace")
+            .and_then(|w| w.get(key))
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+    };
+    list("members")
+        .map(String::from)
+        .chain(list("exclude").map(|pattern| format!("!{pattern}")))
+        .collect()
+}
+
+/// Whether a metadata field of a `[package]` table is `field.workspace = true`
+fn inherits_metadata(package: &toml::Value) -> bool {
+    METADATA_FIELDS.iter().any(|key| {
+        package
+            .get(key)
+            .and_then(|value| value.get("workspace"))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false)
+    })
+}
+
+/// Description, repository and license of a `[package]` table
+///
+/// `field.workspace = true` values are taken from the workspace root's `[workspace.package]`.
+fn cargo_metadata(
+    package: Option<&toml::Value>,
+    workspace_package: Option<&toml::Value>,
+) -> PackageMetadata {
+    let field = |key: &str| {
+        let value = package?.get(key)?;
+        match value.as_str() {
+            Some(value) => Some(value.to_string()),
+            None => workspace_package?.get(key)?.as_str().map(str::to_string),
+        }
+    };
+    let [description, repository, license] = METADATA_FIELDS.map(field);
+    PackageMetadata::new(description, repository, license)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -376,6 +475,7 @@ version = "1.0.0"
             &cargo_toml,
             r#"[workspace]
 members = ["crates/*"]
+exclude = ["crates/legacy"]
 "#,
         )
         .unwrap();
@@ -392,6 +492,10 @@ members = ["crates/*"]
             Project::Workspace(ws) => {
                 assert_eq!(ws.name(), None);
                 assert_eq!(ws.version(), None);
+                assert_eq!(
+                    ws.member_patterns(),
+                    Some(&["crates/*".to_string(), "!crates/legacy".to_string()][..])
+                );
             }
             _ => panic!("Expected Workspace"),
         }
@@ -641,6 +745,65 @@ workspace = true
         assert_eq!(pkg.version(), Some("0.1.33"));
     }
 
+    #[tokio::test]
+    async fn test_rust_project_finder_metadata_inherited_from_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_toml = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &workspace_toml,
+            r#"[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "1.0.0"
+license = "Apache-2.0"
+repository = "https://github.com/org/repo"
+"#,
+        )
+        .unwrap();
+        let pkg_dir = temp_dir.path().join("crates").join("core");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        let pkg_toml = pkg_dir.join("Cargo.toml");
+        fs::write(
+            &pkg_toml,
+            r#"[package]
+name = "core"
+version.workspace = true
+description = "Core types"
+license.workspace = true
+repository.workspace = true
+"#,
+        )
+        .unwrap();
+
+        // The member is visited first, so its metadata waits for the workspace root
+        let mut finder = RustProjectFinder::new();
+        finder
+            .visit(&pkg_toml, &PathBuf::from("crates/core/Cargo.toml"))
+            .await
+            .unwrap();
+        finder
+            .visit(&workspace_toml, &PathBuf::from("Cargo.toml"))
+            .await
+            .unwrap();
+        finder.finalize().await.unwrap();
+
+        let projects = finder.projects();
+        let metadata = projects
+            .iter()
+            .find(|p| p.name() == Some("core"))
+            .unwrap()
+            .metadata();
+        assert_eq!(metadata.description(), Some("Core types"));
+        assert_eq!(metadata.license(), Some("Apache-2.0"));
+        assert_eq!(
+            metadata.repository_url(),
+            Some("https://github.com/org/repo")
+        );
+        let workspace = projects.iter().find(|p| p.name().is_none()).unwrap();
+        assert_eq!(workspace.metadata().license(), Some("Apache-2.0"));
+    }
+
     #[tokio::test]
     async fn test_rust_project_finder_visit_package_with_workspace_version() {
         let temp_dir = TempDir::new().unwrap();
@@ -756,6 +919,51 @@ version.workspace = true
         assert_eq!(pkg.version(), Some("3.0.0")); // Should still resolve correctly
     }
 
+    #[tokio::test]
+    async fn test_rust_project_finder_pending_package_registered_once() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let workspace_toml = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &workspace_toml,
+            r#"[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "3.0.0"
+"#,
+        )
+        .unwrap();
+        let pkg_dir = temp_dir.path().join("crates").join("my-crate");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        let pkg_toml = pkg_dir.join("Cargo.toml");
+        fs::write(
+            &pkg_toml,
+            r#"[package]
+name = "my-crate"
+version.workspace = true
+"#,
+        )
+        .unwrap();
+
+        let mut finder = RustProjectFinder::new();
+        // The same manifest reached twice (index and filesystem) before its workspace
+        for _ in 0..2 {
+            finder
+                .visit(&pkg_toml, &PathBuf::from("crates/my-crate/Cargo.toml"))
+                .await
+                .unwrap();
+        }
+        finder
+            .visit(&workspace_toml, &PathBuf::from("Cargo.toml"))
+            .await
+            .unwrap();
+        finder.finalize().await.unwrap();
+
+        let names: Vec<_> = finder.projects().iter().map(|p| p.name()).collect();
+        assert_eq!(names.iter().filter(|n| **n == Some("my-crate")).count(), 1);
+    }
+
     #[tokio::test]
     async fn test_rust_project_finder_workspace_ignored_by_config() {
         // Simulates when ignore patterns like ["**", "!crates/**"] skip the root Cargo.toml
@@ -1,10 +1,9 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, Package, UpdateType};
-use changepacks_utils::next_version;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType};
+use changepacks_utils::{next_version_with_id, read_file, write_file};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
 use toml_edit::DocumentMut;
 
 #[derive(Debug)]
@@ -14,6 +13,7 @@ pub struct RustPackage {
     path: PathBuf,
     relative_path: PathBuf,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
     workspace_version_inherited: bool,
     workspace_root: Option<PathBuf>,
@@ -33,6 +33,7 @@ impl RustPackage {
             path,
             relative_path,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
             workspace_version_inherited: false,
             workspace_root: None,
@@ -53,6 +54,7 @@ impl RustPackage {
             path,
             relative_path,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
             workspace_version_inherited: true,
             workspace_root,
@@ -77,14 +79,19 @@ impl Package for RustPackage {
         &self.path
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
         let current_version = self.version.as_deref().unwrap_or("0.0.0");
-        let new_version = next_version(current_version, update_type)?;
+        let new_version = next_version_with_id(current_version, update_type, prerelease_id)
+            .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let cargo_toml_raw = read_to_string(&self.path).await?;
+        let cargo_toml_raw = read_file(&self.path).await?;
         let mut cargo_toml: DocumentMut = cargo_toml_raw.parse::<DocumentMut>()?;
         cargo_toml["package"]["version"] = new_version.clone().into();
-        write(
+        write_file(
             &self.path,
             format!(
                 "{}{}",
@@ -113,6 +120,22 @@ impl Package for RustPackage {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
     fn is_changed(&self) -> bool {
         self.is_changed
     }
@@ -208,7 +231,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("version = \"1.0.1\""));
@@ -236,7 +262,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        package.update_version(UpdateType::Minor).await.unwrap();
+        package
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("version = \"1.1.0\""));
@@ -264,7 +293,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        package.update_version(UpdateType::Major).await.unwrap();
+        package
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("version = \"2.0.0\""));
@@ -296,7 +328,10 @@ tokio = "1.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        package.update_version(UpdateType::Patch).await.unwrap();
+        package
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("version = \"1.2.4\""));
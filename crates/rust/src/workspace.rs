@@ -1,11 +1,13 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use changepacks_core::{Language, Package, UpdateType, Workspace};
-use changepacks_utils::{next_version, split_version};
-use std::collections::HashSet;
+use changepacks_core::{Language, Package, PackageMetadata, UpdateType, Workspace};
+use changepacks_utils::{
+    member_manifests, next_version_with_id, read_file, rewrite_version_range, split_version,
+    write_file,
+};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_to_string, write};
-use toml_edit::DocumentMut;
+use toml_edit::{DocumentMut, Item, TableLike};
 
 #[derive(Debug)]
 pub struct RustWorkspace {
@@ -14,7 +16,9 @@ pub struct RustWorkspace {
     version: Option<String>,
     name: Option<String>,
     is_changed: bool,
+    metadata: PackageMetadata,
     dependencies: HashSet<String>,
+    member_patterns: Option<Vec<String>>,
 }
 
 impl RustWorkspace {
@@ -31,9 +35,18 @@ impl RustWorkspace {
             name,
             version,
             is_changed: false,
+            metadata: PackageMetadata::default(),
             dependencies: HashSet::new(),
+            member_patterns: None,
         }
     }
+
+    /// Record the member globs the manifest declares (`!` patterns exclude)
+    #[must_use]
+    pub fn with_member_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.member_patterns = Some(patterns);
+        self
+    }
 }
 
 #[async_trait]
@@ -50,13 +63,19 @@ impl Workspace for RustWorkspace {
         self.version.as_deref()
     }
 
-    async fn update_version(&mut self, update_type: UpdateType) -> Result<()> {
-        let next_version = next_version(
+    async fn update_version(
+        &mut self,
+        update_type: UpdateType,
+        prerelease_id: Option<&str>,
+    ) -> Result<()> {
+        let next_version = next_version_with_id(
             self.version.as_ref().unwrap_or(&String::from("0.0.0")),
             update_type,
-        )?;
+            prerelease_id,
+        )
+        .with_context(|| format!("Cannot bump {}", self.path.display()))?;
 
-        let cargo_toml_raw = read_to_string(&self.path).await?;
+        let cargo_toml_raw = read_file(&self.path).await?;
         let mut cargo_toml: DocumentMut = cargo_toml_raw.parse::<DocumentMut>()?;
 
         let has_package = cargo_toml.get("package").is_some();
@@ -101,27 +120,16 @@ impl Workspace for RustWorkspace {
                 if let Some(dep) = value.as_inline_table_mut()
                     && dep.get("path").is_some()
                     && let Some(ver_str) = dep.get("version").and_then(|v| v.as_str())
-                    && let Ok((prefix, ver)) = split_version(ver_str)
+                    && let Ok((_, ver)) = split_version(ver_str)
                     && ver == old_version
+                    && let Some(rewritten) = rewrite_version_range(ver_str, &next_version)
                 {
-                    dep["version"] = format!("{}{next_version}", prefix.unwrap_or_default()).into();
+                    dep["version"] = rewritten.into();
                 }
             }
         }
 
-        write(
-            &self.path,
-            format!(
-                "{}{}",
-                cargo_toml.to_string().trim_end(),
-                if cargo_toml_raw.ends_with('\n') {
-                    "\n"
-                } else {
-                    ""
-                }
-            ),
-        )
-        .await?;
+        write_cargo_toml(&self.path, &cargo_toml_raw, &cargo_toml).await?;
         self.version = Some(next_version);
         Ok(())
     }
@@ -142,6 +150,26 @@ impl Workspace for RustWorkspace {
         self.name = Some(name);
     }
 
+    fn description(&self) -> Option<&str> {
+        self.metadata.description()
+    }
+
+    fn repository_url(&self) -> Option<&str> {
+        self.metadata.repository_url()
+    }
+
+    fn license(&self) -> Option<&str> {
+        self.metadata.license()
+    }
+
+    fn set_metadata(&mut self, metadata: PackageMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn member_patterns(&self) -> Option<&[String]> {
+        self.member_patterns.as_deref()
+    }
+
     fn relative_path(&self) -> &Path {
         &self.relative_path
     }
@@ -162,59 +190,134 @@ impl Workspace for RustWorkspace {
         self.dependencies.insert(dependency.to_string());
     }
 
+    /// Move the requirements on bumped crates in `[workspace.dependencies]`, and those of
+    /// path dependencies in the dependency tables of the root and member manifests
     async fn update_workspace_dependencies(&self, packages: &[&dyn Package]) -> Result<()> {
-        let cargo_toml_raw = read_to_string(&self.path).await?;
-        let mut cargo_toml: DocumentMut = cargo_toml_raw.parse::<DocumentMut>()?;
-
-        // check has workspace.dependencies section
-        if cargo_toml.get("workspace").is_none()
-            || cargo_toml["workspace"].get("dependencies").is_none()
-        {
+        let versions: HashMap<&str, &str> = packages
+            .iter()
+            .filter(|package| package.language() == Language::Rust)
+            .filter_map(|package| Some((package.name()?, package.version()?)))
+            .collect();
+        if versions.is_empty() {
             return Ok(());
         }
-        let dependencies = cargo_toml
+
+        let cargo_toml_raw = read_file(&self.path).await?;
+        let mut cargo_toml: DocumentMut = cargo_toml_raw.parse::<DocumentMut>()?;
+        let mut updated = rewrite_path_dependencies(&mut cargo_toml, &versions);
+        if let Some(dependencies) = cargo_toml
             .get_mut("workspace")
             .and_then(|w| w.get_mut("dependencies"))
             .and_then(|d| d.as_table_mut())
-            .context("Dependencies section not found")?;
-
-        for package in packages {
-            if package.language() != Language::Rust {
-                continue;
-            }
-            let Some(package_name) = package.name() else {
-                continue;
-            };
-            if dependencies.get(package_name).is_none() {
-                continue;
+        {
+            for (package_name, next_version) in &versions {
+                if let Some(dep) = dependencies
+                    .get_mut(package_name)
+                    .and_then(|dep| dep.as_inline_table_mut())
+                    && let Some(current_version) = dep.get("version").and_then(|v| v.as_str())
+                    && let Some(rewritten) = rewrite_version_range(current_version, next_version)
+                    && rewritten != current_version
+                {
+                    dep["version"] = rewritten.into();
+                    updated = true;
+                }
             }
+        }
+        if updated {
+            write_cargo_toml(&self.path, &cargo_toml_raw, &cargo_toml).await?;
+        }
 
-            let dep = dependencies[package_name].as_inline_table_mut();
-            if let Some(dep) = dep
-                && let Some(current_version) = dep.get("version").and_then(|v| v.as_str())
-                && let Some(next_version) = package.version()
-            {
-                let (prefix, _) = split_version(current_version)?;
-                dep["version"] = format!("{}{}", prefix.unwrap_or_default(), next_version).into();
+        let members = self.member_patterns.as_deref().unwrap_or_default();
+        for path in member_manifests(&self.path, members, "Cargo.toml") {
+            let raw = read_file(&path).await?;
+            let mut manifest: DocumentMut = raw
+                .parse()
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            if rewrite_path_dependencies(&mut manifest, &versions) {
+                write_cargo_toml(&path, &raw, &manifest).await?;
             }
         }
+        Ok(())
+    }
+}
 
-        write(
-            &self.path,
-            format!(
-                "{}{}",
-                cargo_toml.to_string().trim_end(),
-                if cargo_toml_raw.ends_with('\n') {
-                    "\n"
-                } else {
-                    ""
+/// Dependency tables of a Cargo manifest, also found under `[target.'cfg(..)']`
+const DEPENDENCY_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Move the `version` of path dependencies on bumped crates (`versions` by crate name) to the
+/// new versions, keeping each requirement's style; returns whether anything changed
+fn rewrite_path_dependencies(cargo_toml: &mut DocumentMut, versions: &HashMap<&str, &str>) -> bool {
+    let root = cargo_toml.as_table_mut();
+    let mut updated = false;
+    for section in DEPENDENCY_SECTIONS {
+        if let Some(dependencies) = root.get_mut(section).and_then(Item::as_table_like_mut) {
+            updated |= rewrite_dependency_table(dependencies, versions);
+        }
+    }
+    if let Some(targets) = root.get_mut("target").and_then(Item::as_table_like_mut) {
+        for (_, target) in targets.iter_mut() {
+            let Some(target) = target.as_table_like_mut() else {
+                continue;
+            };
+            for section in DEPENDENCY_SECTIONS {
+                if let Some(dependencies) =
+                    target.get_mut(section).and_then(Item::as_table_like_mut)
+                {
+                    updated |= rewrite_dependency_table(dependencies, versions);
                 }
-            ),
-        )
-        .await?;
+            }
+        }
+    }
+    updated
+}
 
-        Ok(())
+fn rewrite_dependency_table(
+    dependencies: &mut dyn TableLike,
+    versions: &HashMap<&str, &str>,
+) -> bool {
+    let mut updated = false;
+    for (key, dep) in dependencies.iter_mut() {
+        let Some(dep) = dep.as_table_like_mut() else {
+            continue;
+        };
+        // Renamed dependencies name the crate in `package`
+        let name = dep
+            .get("package")
+            .and_then(Item::as_str)
+            .unwrap_or(key.get())
+            .to_string();
+        if dep.get("path").is_none() {
+            continue;
+        }
+        let Some(next_version) = versions.get(name.as_str()) else {
+            continue;
+        };
+        if let Some(Item::Value(version)) = dep.get_mut("version")
+            && let Some(current_version) = version.as_str()
+            && let Some(rewritten) = rewrite_version_range(current_version, next_version)
+            && rewritten != current_version
+        {
+            // Keep the spacing and any trailing comment
+            let decor = version.decor().clone();
+            *version = rewritten.into();
+            *version.decor_mut() = decor;
+            updated = true;
+        }
     }
+    updated
+}
+
+/// Write `cargo_toml` back with the trailing newline of `raw`
+async fn write_cargo_toml(path: &Path, raw: &str, cargo_toml: &DocumentMut) -> Result<()> {
+    write_file(
+        path,
+        format!(
+            "{}{}",
+            cargo_toml.to_string().trim_end(),
+            if raw.ends_with('\n') { "\n" } else { "" }
+        ),
+    )
+    .await
 }
 
 #[cfg(test)]
@@ -302,7 +405,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("version = \"1.0.1\""));
@@ -329,7 +435,10 @@ members = ["crates/*"]
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("[package]"));
@@ -354,7 +463,10 @@ members = ["crates/*"]
         let mut workspace =
             RustWorkspace::new(None, None, cargo_toml.clone(), PathBuf::from("Cargo.toml"));
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("[package]"));
@@ -387,7 +499,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Minor).await.unwrap();
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("version = \"1.1.0\""));
@@ -418,7 +533,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Major).await.unwrap();
+        workspace
+            .update_version(UpdateType::Major, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("version = \"2.0.0\""));
@@ -449,7 +567,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         assert!(content.contains("name = \"existing-name\""));
@@ -533,6 +654,55 @@ utils = { version = "2.0.0", path = "crates/utils" }
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_rust_workspace_update_workspace_dependencies_preserves_range_style() {
+        use crate::package::RustPackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo_toml,
+            r#"[workspace]
+members = ["crates/*"]
+
+[workspace.dependencies]
+core = { version = "~1.0", path = "crates/core" }
+utils = { version = "=2.0.0", path = "crates/utils" }
+cli = { version = "*", path = "crates/cli" }
+"#,
+        )
+        .unwrap();
+
+        let workspace =
+            RustWorkspace::new(None, None, cargo_toml.clone(), PathBuf::from("Cargo.toml"));
+        let package = |name: &str, version: &str| {
+            RustPackage::new(
+                Some(name.to_string()),
+                Some(version.to_string()),
+                PathBuf::from(format!("/test/crates/{name}/Cargo.toml")),
+                PathBuf::from(format!("crates/{name}/Cargo.toml")),
+            )
+        };
+        let (core_pkg, utils_pkg, cli_pkg) = (
+            package("core", "1.1.0"),
+            package("utils", "2.0.1"),
+            package("cli", "0.2.0"),
+        );
+        let packages: Vec<&dyn Package> = vec![&core_pkg, &utils_pkg, &cli_pkg];
+
+        workspace
+            .update_workspace_dependencies(&packages)
+            .await
+            .unwrap();
+
+        let content = read_to_string(&cargo_toml).await.unwrap();
+        assert!(content.contains(r#"core = { version = "~1.1", path = "crates/core" }"#));
+        assert!(content.contains(r#"utils = { version = "=2.0.1", path = "crates/utils" }"#));
+        assert!(content.contains(r#"cli = { version = "*", path = "crates/cli" }"#));
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_rust_workspace_update_workspace_dependencies_without_dependency_version() {
         use crate::package::RustPackage;
@@ -663,6 +833,82 @@ version = "1.0.0"
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_rust_workspace_update_member_path_dependencies() {
+        use crate::package::RustPackage;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        fs::write(&cargo_toml, "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+        let cli_toml = temp_dir.path().join("crates/cli/Cargo.toml");
+        fs::create_dir_all(cli_toml.parent().unwrap()).unwrap();
+        let cli = r#"[package]
+name = "cli"
+version = "0.1.0"
+
+[dependencies]
+core = { path = "../core", version = "^1.2" }
+my-utils = { package = "utils", path = "../utils", version = "=0.3.0" }
+serde = { version = "1.0" }
+
+[dev-dependencies.core]
+path = "../core"
+version = "1.2.0" # keep in step
+
+[target.'cfg(unix)'.build-dependencies]
+utils = { path = "../utils", version = "0.3" }
+"#;
+        fs::write(&cli_toml, cli).unwrap();
+
+        let workspace =
+            RustWorkspace::new(None, None, cargo_toml.clone(), PathBuf::from("Cargo.toml"))
+                .with_member_patterns(vec!["crates/*".to_string()]);
+        let package = |name: &str, version: &str| {
+            RustPackage::new(
+                Some(name.to_string()),
+                Some(version.to_string()),
+                temp_dir.path().join(format!("crates/{name}/Cargo.toml")),
+                PathBuf::from(format!("crates/{name}/Cargo.toml")),
+            )
+        };
+        let (core, utils, serde) = (
+            package("core", "1.3.0"),
+            package("utils", "0.4.0"),
+            package("serde", "2.0.0"),
+        );
+
+        workspace
+            .update_workspace_dependencies(&[&core, &utils, &serde])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_to_string(&cli_toml).await.unwrap(),
+            r#"[package]
+name = "cli"
+version = "0.1.0"
+
+[dependencies]
+core = { path = "../core", version = "^1.3" }
+my-utils = { package = "utils", path = "../utils", version = "=0.4.0" }
+serde = { version = "1.0" }
+
+[dev-dependencies.core]
+path = "../core"
+version = "1.3.0" # keep in step
+
+[target.'cfg(unix)'.build-dependencies]
+utils = { path = "../utils", version = "0.4" }
+"#
+        );
+        assert_eq!(
+            read_to_string(&cargo_toml).await.unwrap(),
+            "[workspace]\nmembers = [\"crates/*\"]\n"
+        );
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_rust_workspace_update_version_updates_workspace_package_version() {
         let temp_dir = TempDir::new().unwrap();
@@ -690,7 +936,10 @@ version = "1.0.0"
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Minor).await.unwrap();
+        workspace
+            .update_version(UpdateType::Minor, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         // Both should be updated
@@ -730,7 +979,10 @@ edition = "2024"
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         let doc: toml_edit::DocumentMut = content.parse().unwrap();
@@ -779,7 +1031,10 @@ other_local = { path = "crates/other", version = "0.5.0" }
             PathBuf::from("Cargo.toml"),
         );
 
-        workspace.update_version(UpdateType::Patch).await.unwrap();
+        workspace
+            .update_version(UpdateType::Patch, None)
+            .await
+            .unwrap();
 
         let content = read_to_string(&cargo_toml).await.unwrap();
         let doc: toml_edit::DocumentMut = content.parse().unwrap();
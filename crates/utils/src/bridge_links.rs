@@ -0,0 +1,167 @@
+use std::path::{Component, Path, PathBuf};
+
+use changepacks_core::{Config, Language, Project};
+
+/// Crates wrapped by bridge packages, as `(crate, bridge)` relative manifest paths
+///
+/// Links come from the `bridges` config (crate manifest to bridge manifests) and from
+/// manifest hints: a `package.json` with a `napi` section, or a `pyproject.toml` built with
+/// maturin, wraps the crate whose `Cargo.toml` sits next to it (maturin's
+/// `tool.maturin.manifest-path` is honored). Hints naming a crate that is not a discovered
+/// project are ignored.
+pub async fn bridge_links(projects: &[&Project], config: &Config) -> Vec<(PathBuf, PathBuf)> {
+    let mut links: Vec<(PathBuf, PathBuf)> = config
+        .bridges
+        .iter()
+        .flat_map(|(krate, bridges)| {
+            bridges
+                .iter()
+                .map(move |bridge| (PathBuf::from(krate), PathBuf::from(bridge)))
+        })
+        .collect();
+    for project in projects {
+        let Some(manifest) = bridged_manifest(project).await else {
+            continue;
+        };
+        if let Some(krate) = projects.iter().find(|candidate| {
+            candidate.language() == Language::Rust && lexical(candidate.path()) == manifest
+        }) {
+            links.push((
+                krate.relative_path().to_path_buf(),
+                project.relative_path().to_path_buf(),
+            ));
+        }
+    }
+    links.sort();
+    links.dedup();
+    links
+}
+
+/// `Cargo.toml` a napi `package.json` or maturin `pyproject.toml` builds from
+async fn bridged_manifest(project: &Project) -> Option<PathBuf> {
+    let dir = project.path().parent()?;
+    match project.language() {
+        Language::Node => {
+            let content = tokio::fs::read_to_string(project.path()).await.ok()?;
+            let package_json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            package_json.get("napi")?;
+            Some(lexical(&dir.join("Cargo.toml")))
+        }
+        Language::Python => {
+            let content = tokio::fs::read_to_string(project.path()).await.ok()?;
+            let pyproject: toml::Value = toml::from_str(&content).ok()?;
+            let maturin = pyproject.get("tool").and_then(|tool| tool.get("maturin"));
+            let backend = pyproject
+                .get("build-system")
+                .and_then(|build| build.get("build-backend"))
+                .and_then(toml::Value::as_str);
+            if maturin.is_none() && backend != Some("maturin") {
+                return None;
+            }
+            let manifest = maturin
+                .and_then(|maturin| maturin.get("manifest-path"))
+                .and_then(toml::Value::as_str)
+                .unwrap_or("Cargo.toml");
+            Some(lexical(&dir.join(manifest)))
+        }
+        _ => None,
+    }
+}
+
+/// `path` with `.` and `..` components folded away, without touching the filesystem
+fn lexical(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_node::package::NodePackage;
+    use changepacks_rust::package::RustPackage;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn node(root: &Path, rel: &str, package_json: &str) -> Project {
+        std::fs::create_dir_all(root.join(rel).parent().unwrap()).unwrap();
+        std::fs::write(root.join(rel), package_json).unwrap();
+        Project::Package(Box::new(NodePackage::new(
+            Some(rel.to_string()),
+            Some("1.0.0".to_string()),
+            root.join(rel),
+            PathBuf::from(rel),
+        )))
+    }
+
+    #[test]
+    fn test_lexical() {
+        assert_eq!(
+            lexical(Path::new(
+                "/repo/bindings/python/../../crates/core/./Cargo.toml"
+            )),
+            PathBuf::from("/repo/crates/core/Cargo.toml")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bridge_links_from_config() {
+        let config = Config {
+            bridges: HashMap::from([(
+                "crates/core/Cargo.toml".to_string(),
+                vec!["bindings/node/package.json".to_string()],
+            )]),
+            ..Config::default()
+        };
+        assert_eq!(
+            bridge_links(&[], &config).await,
+            [(
+                PathBuf::from("crates/core/Cargo.toml"),
+                PathBuf::from("bindings/node/package.json")
+            )]
+        );
+        assert!(bridge_links(&[], &Config::default()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_links_from_napi_hint() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let napi = node(
+            root,
+            "bindings/node/package.json",
+            r#"{"name": "@org/core", "napi": {"binaryName": "core"}}"#,
+        );
+        let plain = node(root, "web/package.json", r#"{"name": "web"}"#);
+        let krate = Project::Package(Box::new(RustPackage::new(
+            Some("core".to_string()),
+            Some("1.0.0".to_string()),
+            root.join("bindings/node/Cargo.toml"),
+            PathBuf::from("bindings/node/Cargo.toml"),
+        )));
+
+        assert_eq!(
+            bridge_links(&[&napi, &plain, &krate], &Config::default()).await,
+            [(
+                PathBuf::from("bindings/node/Cargo.toml"),
+                PathBuf::from("bindings/node/package.json")
+            )]
+        );
+        // Without the crate among the projects there is nothing to link
+        assert!(
+            bridge_links(&[&napi, &plain], &Config::default())
+                .await
+                .is_empty()
+        );
+
+        temp_dir.close().unwrap();
+    }
+}
@@ -0,0 +1,209 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use changepacks_core::{Config, Project, ProjectFinder, changepacks_dir_name, path_key};
+use glob::Pattern;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::get_changepacks_dir::changepacks_dir_in;
+
+/// Decides which changed files mark projects as changed
+///
+/// Files matching the config `changeIgnore` patterns (gitignore syntax, relative to the
+/// release root) never mark a project as changed, while they are still discovered normally.
+/// A project listed in `changeFiles` is only marked changed by files inside it that match
+/// one of its globs. Files in the changepacks directory (changepack logs, release records)
+/// and the config directory never mark a project as changed.
+#[derive(Debug)]
+pub struct ChangeFilter {
+    root: PathBuf,
+    changepacks_dirs: Vec<PathBuf>,
+    ignore: Option<Gitignore>,
+    change_files: Vec<(String, Vec<Pattern>)>,
+}
+
+impl ChangeFilter {
+    /// # Errors
+    /// Returns error if a `changeIgnore` or `changeFiles` pattern is invalid, or the config
+    /// file cannot be read.
+    pub fn new(root: &Path, config: &Config) -> Result<Self> {
+        let mut changepacks_dirs =
+            vec![root.join(changepacks_dir_name()), changepacks_dir_in(root)?];
+        changepacks_dirs.dedup();
+        let ignore = if config.change_ignore.is_empty() {
+            None
+        } else {
+            let mut builder = GitignoreBuilder::new(root);
+            for pattern in &config.change_ignore {
+                builder.add_line(None, pattern)?;
+            }
+            Some(builder.build()?)
+        };
+        let change_files = config
+            .change_files
+            .iter()
+            .map(|(project, patterns)| {
+                let patterns = patterns
+                    .iter()
+                    .map(|pattern| {
+                        Pattern::new(pattern).with_context(|| {
+                            format!("Invalid changeFiles pattern for {project}: {pattern}")
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Ok((path_key(Path::new(project)), patterns))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            changepacks_dirs,
+            ignore,
+            change_files,
+        })
+    }
+
+    /// Whether a change to `abs_path` is excluded by `changeIgnore`
+    #[must_use]
+    pub fn is_ignored(&self, abs_path: &Path) -> bool {
+        self.ignore.as_ref().is_some_and(|ignore| {
+            abs_path.starts_with(ignore.path())
+                && ignore
+                    .matched_path_or_any_parents(abs_path, false)
+                    .is_ignore()
+        })
+    }
+
+    /// Whether `abs_path` lies in the changepacks or config directory
+    #[must_use]
+    pub fn is_in_changepacks_dir(&self, abs_path: &Path) -> bool {
+        self.changepacks_dirs
+            .iter()
+            .any(|dir| abs_path.starts_with(dir))
+    }
+
+    /// Whether a change to `abs_path` may mark `project` as changed under its `changeFiles`
+    ///
+    /// Always true for projects without `changeFiles` and for files outside the project.
+    #[must_use]
+    pub fn is_significant(&self, project: &Project, abs_path: &Path) -> bool {
+        let key = path_key(project.relative_path());
+        let Some((_, patterns)) = self.change_files.iter().find(|(path, _)| *path == key) else {
+            return true;
+        };
+        let dir = project
+            .relative_path()
+            .parent()
+            .map_or_else(|| self.root.clone(), |dir| self.root.join(dir));
+        abs_path.strip_prefix(&dir).map_or(true, |file| {
+            patterns.iter().any(|pattern| pattern.matches_path(file))
+        })
+    }
+
+    /// Mark the projects owning `abs_path` as changed, unless the change is ignored
+    ///
+    /// # Errors
+    /// Returns error if a finder fails to check the path.
+    pub fn mark_changed(
+        &self,
+        project_finders: &mut [Box<dyn ProjectFinder>],
+        abs_path: &Path,
+    ) -> Result<()> {
+        if self.is_ignored(abs_path) || self.is_in_changepacks_dir(abs_path) {
+            return Ok(());
+        }
+        for finder in project_finders.iter_mut() {
+            if self.change_files.is_empty() {
+                finder.check_changed(abs_path)?;
+                continue;
+            }
+            for project in finder.projects_mut() {
+                if self.is_significant(project, abs_path) {
+                    project.check_changed(abs_path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_node::package::NodePackage;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    #[rstest]
+    #[case("/repo/docs/guide.md", true)]
+    #[case("/repo/packages/core/README.md", true)]
+    #[case("/repo/packages/core/tests/fixture.json", true)]
+    #[case("/repo/packages/core/src/lib.rs", false)]
+    #[case("/repo/packages/core/CHANGELOG.md", false)]
+    #[case("/elsewhere/notes.md", false)]
+    fn test_change_filter_is_ignored(#[case] path: &str, #[case] ignored: bool) {
+        let config = Config {
+            change_ignore: vec![
+                "**/*.md".to_string(),
+                "!CHANGELOG.md".to_string(),
+                "**/tests/**".to_string(),
+            ],
+            ..Config::default()
+        };
+        let filter = ChangeFilter::new(Path::new("/repo"), &config).unwrap();
+        assert_eq!(filter.is_ignored(Path::new(path)), ignored);
+    }
+
+    #[test]
+    fn test_change_filter_empty() {
+        let filter = ChangeFilter::new(Path::new("/repo"), &Config::default()).unwrap();
+        assert!(!filter.is_ignored(Path::new("/repo/docs/guide.md")));
+    }
+
+    #[rstest]
+    #[case("/repo/core/src/index.js", true)]
+    #[case("/repo/core/package.json", true)]
+    #[case("/repo/core/examples/demo.js", false)]
+    #[case("/repo/core/fixtures/data.json", false)]
+    #[case("/repo/web/index.js", true)]
+    fn test_change_filter_is_significant(#[case] path: &str, #[case] significant: bool) {
+        let config = Config {
+            change_files: HashMap::from([(
+                "core/package.json".to_string(),
+                vec!["src/**".to_string(), "package.json".to_string()],
+            )]),
+            ..Config::default()
+        };
+        let filter = ChangeFilter::new(Path::new("/repo"), &config).unwrap();
+        let core = Project::Package(Box::new(NodePackage::new(
+            Some("core".to_string()),
+            Some("1.0.0".to_string()),
+            PathBuf::from("/repo/core/package.json"),
+            PathBuf::from("core/package.json"),
+        )));
+        assert_eq!(filter.is_significant(&core, Path::new(path)), significant);
+    }
+
+    #[rstest]
+    #[case("/repo/.changepacks/changepack_log_a.json", true)]
+    #[case("/repo/.changepacks", true)]
+    #[case("/repo/src/.changepacks.rs", false)]
+    #[case("/repo/docs/.changepacks-notes/a.md", false)]
+    #[case("/repo/core/.changepacks/a.json", false)]
+    fn test_change_filter_is_in_changepacks_dir(#[case] path: &str, #[case] inside: bool) {
+        let filter = ChangeFilter::new(Path::new("/repo"), &Config::default()).unwrap();
+        assert_eq!(filter.is_in_changepacks_dir(Path::new(path)), inside);
+    }
+
+    #[test]
+    fn test_change_filter_invalid_pattern() {
+        let config = Config {
+            change_files: HashMap::from([(
+                "core/package.json".to_string(),
+                vec!["src/[".to_string()],
+            )]),
+            ..Config::default()
+        };
+        let err = ChangeFilter::new(Path::new("/repo"), &config).unwrap_err();
+        assert!(err.to_string().contains("core/package.json"));
+    }
+}
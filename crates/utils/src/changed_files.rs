@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use tokio::process::Command;
+
+/// Files changed in a git diff range, relative to `repo_root`
+///
+/// Files outside `repo_root` (when it is a release root below the git work tree) are left out.
+///
+/// `range` is passed to `git diff --name-only`, so `main..HEAD`, `main...HEAD` (since the
+/// merge base), and a single revision (compared against the working tree) all work.
+///
+/// # Errors
+/// Returns error if `git` cannot be run or the range is invalid.
+pub async fn changed_files(repo_root: &Path, range: &str) -> Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--relative",
+            range,
+            "--",
+        ])
+        .current_dir(repo_root)
+        .output()
+        .await?;
+    if !output.status.success() {
+        bail!(
+            "git diff {range} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    fn commit(dir: &Path, file: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, file).unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-m", file]);
+    }
+
+    #[tokio::test]
+    async fn test_changed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-b", "main"]);
+        git(dir, &["config", "user.email", "test@test.com"]);
+        git(dir, &["config", "user.name", "Test"]);
+        commit(dir, "a/package.json");
+        git(dir, &["checkout", "-b", "feature"]);
+        commit(dir, "b/index.js");
+        commit(dir, "c/lib.rs");
+
+        assert_eq!(
+            changed_files(dir, "main..HEAD").await.unwrap(),
+            [PathBuf::from("b/index.js"), PathBuf::from("c/lib.rs")]
+        );
+        assert_eq!(
+            changed_files(dir, "HEAD~1").await.unwrap(),
+            [PathBuf::from("c/lib.rs")]
+        );
+        assert!(changed_files(dir, "HEAD").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_changed_files_invalid_range() {
+        let temp_dir = TempDir::new().unwrap();
+        git(temp_dir.path(), &["init", "-b", "main"]);
+        let err = changed_files(temp_dir.path(), "missing..HEAD")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("git diff missing..HEAD failed"));
+    }
+}
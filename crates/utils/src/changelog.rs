@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use changepacks_core::{ChangePackResultLog, UpdateType};
+
+use crate::{read_file, write_file};
+
+/// File name of the changelog kept next to each manifest
+const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// Path of the changelog for the project whose manifest is `manifest_path`
+///
+/// Every supported ecosystem treats the manifest's directory as the package root, which is
+/// where crates.io, npm, PyPI, pub.dev and NuGet tooling look for `CHANGELOG.md`; a Cargo
+/// workspace whose members inherit its version keeps one changelog beside the workspace
+/// `Cargo.toml`.
+#[must_use]
+fn changelog_path(manifest_path: &Path) -> PathBuf {
+    manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(CHANGELOG_FILE)
+}
+
+/// `## <version> - <date>` section listing `logs` under a heading per update type
+#[must_use]
+pub fn render_changelog_section(version: &str, date: &str, logs: &[ChangePackResultLog]) -> String {
+    let mut section = format!("## {version} - {date}\n");
+    for (update_type, heading) in [
+        (UpdateType::Major, "Major Changes"),
+        (UpdateType::Minor, "Minor Changes"),
+        (UpdateType::Patch, "Patch Changes"),
+        (UpdateType::Prerelease, "Prerelease Changes"),
+    ] {
+        let notes: Vec<String> = logs
+            .iter()
+            .filter(|log| log.update_type() == update_type)
+            .map(ChangePackResultLog::rendered_note)
+            .filter(|note| !note.trim().is_empty())
+            .collect();
+        if notes.is_empty() {
+            continue;
+        }
+        section.push_str(&format!("\n### {heading}\n\n"));
+        for note in notes {
+            section.push_str(&list_item(note.trim()));
+        }
+    }
+    section
+}
+
+/// `note` as a markdown list item, continuation lines indented under the bullet
+fn list_item(note: &str) -> String {
+    let mut item = String::new();
+    for (index, line) in note.lines().enumerate() {
+        match (index, line.trim_end()) {
+            (0, line) => item.push_str(&format!("- {line}\n")),
+            (_, "") => item.push('\n'),
+            (_, line) => item.push_str(&format!("  {line}\n")),
+        }
+    }
+    item
+}
+
+/// `changelog` with `section` added above its latest version
+///
+/// The section goes before the first `## ` heading, so a title and introduction stay on top;
+/// an empty changelog gets a `# Changelog` title.
+#[must_use]
+fn insert_changelog_section(changelog: &str, section: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("# Changelog\n\n{section}");
+    }
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            return format!(
+                "{}{section}\n{}",
+                &changelog[..offset],
+                &changelog[offset..]
+            );
+        }
+        offset += line.len();
+    }
+    format!("{}\n\n{section}", changelog.trim_end())
+}
+
+/// Add the section for `version` to the changelog next to `manifest_path`, creating it if needed
+///
+/// Goes through [`write_file`], so dry runs and confirmations show the change like any
+/// version write.
+///
+/// # Errors
+/// Returns error if an existing changelog cannot be read or the changelog cannot be written.
+pub async fn write_changelog(
+    manifest_path: &Path,
+    version: &str,
+    date: &str,
+    logs: &[ChangePackResultLog],
+) -> Result<PathBuf> {
+    let path = changelog_path(manifest_path);
+    let existing = match read_file(&path).await {
+        Ok(content) => content,
+        Err(_) if !tokio::fs::try_exists(&path).await? => String::new(),
+        Err(e) => return Err(e),
+    };
+    let section = render_changelog_section(version, date, logs);
+    write_file(&path, insert_changelog_section(&existing, &section)).await?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    fn logs() -> Vec<ChangePackResultLog> {
+        vec![
+            ChangePackResultLog::new(UpdateType::Patch, "Fix a crash".to_string()),
+            ChangePackResultLog::new(
+                UpdateType::Minor,
+                "Add a streaming parser\n\nWith docs".to_string(),
+            ),
+            ChangePackResultLog::new(UpdateType::Patch, "Tidy errors".to_string())
+                .with_fields(BTreeMap::from([("issue".to_string(), "#12".to_string())])),
+        ]
+    }
+
+    #[test]
+    fn test_changelog_path() {
+        assert_eq!(
+            changelog_path(Path::new("/repo/packages/core/package.json")),
+            PathBuf::from("/repo/packages/core/CHANGELOG.md")
+        );
+        assert_eq!(
+            changelog_path(Path::new("Cargo.toml")),
+            PathBuf::from("CHANGELOG.md")
+        );
+    }
+
+    #[test]
+    fn test_render_changelog_section() {
+        assert_eq!(
+            render_changelog_section("1.3.0", "2025-01-02", &logs()),
+            "## 1.3.0 - 2025-01-02\n\n### Minor Changes\n\n- Add a streaming parser\n\n  With docs\n\n### Patch Changes\n\n- Fix a crash\n- Tidy errors\n  issue: #12\n"
+        );
+        assert_eq!(
+            render_changelog_section("2.0.0", "2025-01-02", &[]),
+            "## 2.0.0 - 2025-01-02\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_changelog_section() {
+        let section = "## 1.1.0 - 2025-01-02\n\n### Patch Changes\n\n- Fix\n";
+        assert_eq!(
+            insert_changelog_section("", section),
+            format!("# Changelog\n\n{section}")
+        );
+        assert_eq!(
+            insert_changelog_section(
+                "# Changelog\n\nAll notable changes.\n\n## 1.0.0 - 2024-12-01\n\n- Initial\n",
+                section
+            ),
+            format!(
+                "# Changelog\n\nAll notable changes.\n\n{section}\n## 1.0.0 - 2024-12-01\n\n- Initial\n"
+            )
+        );
+        assert_eq!(
+            insert_changelog_section("# History\n", section),
+            format!("# History\n\n{section}")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_changelog() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("package.json");
+        let logs = [ChangePackResultLog::new(
+            UpdateType::Patch,
+            "Fix".to_string(),
+        )];
+        let path = write_changelog(&manifest, "1.0.1", "2025-01-01", &logs)
+            .await
+            .unwrap();
+        write_changelog(&manifest, "1.0.2", "2025-01-02", &logs)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "# Changelog\n\n## 1.0.2 - 2025-01-02\n\n### Patch Changes\n\n- Fix\n\n## 1.0.1 - 2025-01-01\n\n### Patch Changes\n\n- Fix\n"
+        );
+    }
+}
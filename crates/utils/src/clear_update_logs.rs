@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use anyhow::Result;
 use tokio::fs::{read_dir, remove_file};
 
+use crate::is_changepack_log_entry;
+
 /// Remove all update logs without confirmation
 ///
 /// # Errors
@@ -14,7 +16,7 @@ pub async fn clear_update_logs(changepacks_dir: &PathBuf) -> Result<()> {
     let mut entries = read_dir(&changepacks_dir).await?;
     let mut update_logs = vec![];
     while let Some(file) = entries.next_entry().await? {
-        if file.file_name().to_string_lossy() == "config.json" {
+        if !is_changepack_log_entry(&file).await {
             continue;
         }
         update_logs.push(remove_file(file.path()));
@@ -135,9 +137,9 @@ mod tests {
         fs::write(&config_file, r#"{"ignore": [], "baseBranch": "main"}"#).unwrap();
 
         // Create update log files
-        let log_file1 = changepacks_dir.join("update_log_1.json");
-        let log_file2 = changepacks_dir.join("update_log_2.json");
-        let log_file3 = changepacks_dir.join("update_log_3.json");
+        let log_file1 = changepacks_dir.join("changepack_log_1.json");
+        let log_file2 = changepacks_dir.join("changepack_log_2.json");
+        let log_file3 = changepacks_dir.join("changepack_log_3.json");
         fs::write(&log_file1, r#"{"changes": {}, "note": "test1"}"#).unwrap();
         fs::write(&log_file2, r#"{"changes": {}, "note": "test2"}"#).unwrap();
         fs::write(&log_file3, r#"{"changes": {}, "note": "test3"}"#).unwrap();
@@ -150,9 +152,38 @@ mod tests {
         assert!(config_file.exists(), "config.json should not be deleted");
 
         // All update log files should be deleted
-        assert!(!log_file1.exists(), "update_log_1.json should be deleted");
-        assert!(!log_file2.exists(), "update_log_2.json should be deleted");
-        assert!(!log_file3.exists(), "update_log_3.json should be deleted");
+        assert!(
+            !log_file1.exists(),
+            "changepack_log_1.json should be deleted"
+        );
+        assert!(
+            !log_file2.exists(),
+            "changepack_log_2.json should be deleted"
+        );
+        assert!(
+            !log_file3.exists(),
+            "changepack_log_3.json should be deleted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clear_update_logs_keeps_release_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let changepacks_dir = temp_dir.path().join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).unwrap();
+
+        let manifest_file = changepacks_dir.join("releases.json");
+        let log_file = changepacks_dir.join("changepack_log_1.json");
+        fs::write(&manifest_file, r#"{"releases": []}"#).unwrap();
+        fs::write(&log_file, r#"{"changes": {}, "note": "test"}"#).unwrap();
+
+        clear_update_logs(&changepacks_dir).await.unwrap();
+
+        assert!(
+            manifest_file.exists(),
+            "releases.json should not be deleted"
+        );
+        assert!(!log_file.exists());
     }
 
     #[tokio::test]
@@ -177,10 +208,10 @@ mod tests {
         fs::write(&config_file, r#"{"ignore": [], "baseBranch": "main"}"#).unwrap();
 
         // Create various update log files with different names
-        let log_file1 = changepacks_dir.join("2024-01-01.json");
-        let log_file2 = changepacks_dir.join("2024-01-02.json");
-        let log_file3 = changepacks_dir.join("update.json");
-        let log_file4 = changepacks_dir.join("log.json");
+        let log_file1 = changepacks_dir.join("changepack_log_2024-01-01.json");
+        let log_file2 = changepacks_dir.join("changepack_log_2024-01-02.json");
+        let log_file3 = changepacks_dir.join("changepack_log_update.md");
+        let log_file4 = changepacks_dir.join("notes.json");
         fs::write(&log_file1, r#"{"changes": {}, "note": "test1"}"#).unwrap();
         fs::write(&log_file2, r#"{"changes": {}, "note": "test2"}"#).unwrap();
         fs::write(&log_file3, r#"{"changes": {}, "note": "test3"}"#).unwrap();
@@ -194,10 +225,19 @@ mod tests {
         assert!(config_file.exists(), "config.json should not be deleted");
 
         // All update log files should be deleted
-        assert!(!log_file1.exists(), "2024-01-01.json should be deleted");
-        assert!(!log_file2.exists(), "2024-01-02.json should be deleted");
-        assert!(!log_file3.exists(), "update.json should be deleted");
-        assert!(!log_file4.exists(), "log.json should be deleted");
+        assert!(
+            !log_file1.exists(),
+            "changepack_log_2024-01-01.json should be deleted"
+        );
+        assert!(
+            !log_file2.exists(),
+            "changepack_log_2024-01-02.json should be deleted"
+        );
+        assert!(
+            !log_file3.exists(),
+            "changepack_log_update.md should be deleted"
+        );
+        assert!(log_file4.exists(), "notes.json is not a changepack log");
     }
 
     #[tokio::test]
@@ -218,8 +258,8 @@ mod tests {
         fs::create_dir_all(&changepacks_dir).unwrap();
 
         // Create update log files without config.json
-        let log_file1 = changepacks_dir.join("update_log_1.json");
-        let log_file2 = changepacks_dir.join("update_log_2.json");
+        let log_file1 = changepacks_dir.join("changepack_log_1.json");
+        let log_file2 = changepacks_dir.join("changepack_log_2.json");
         fs::write(&log_file1, r#"{"changes": {}, "note": "test1"}"#).unwrap();
         fs::write(&log_file2, r#"{"changes": {}, "note": "test2"}"#).unwrap();
 
@@ -228,36 +268,30 @@ mod tests {
         assert!(result.is_ok());
 
         // All update log files should be deleted
-        assert!(!log_file1.exists(), "update_log_1.json should be deleted");
-        assert!(!log_file2.exists(), "update_log_2.json should be deleted");
+        assert!(
+            !log_file1.exists(),
+            "changepack_log_1.json should be deleted"
+        );
+        assert!(
+            !log_file2.exists(),
+            "changepack_log_2.json should be deleted"
+        );
     }
 
     #[tokio::test]
-    async fn test_clear_update_logs_file_deletion_failure() {
-        // Create a temporary directory and initialize git
+    async fn test_clear_update_logs_keeps_directories() {
         let temp_dir = TempDir::new().unwrap();
-        let temp_path = temp_dir.path();
-
-        // Initialize git repository
-        std::process::Command::new("git")
-            .arg("init")
-            .current_dir(temp_path)
-            .output()
-            .unwrap();
-
-        // Create .changepacks directory
-        let changepacks_dir = get_changepacks_dir(temp_path).unwrap();
-        fs::create_dir_all(&changepacks_dir).unwrap();
-
-        // Create a subdirectory with a name that looks like a JSON file
-        // This will cause remove_file to fail because it's a directory, not a file
-        let log_dir = changepacks_dir.join("update_log.json");
+        let changepacks_dir = temp_dir.path().join(".changepacks");
+        // `doctor --archive-stale` keeps archived changepacks in a subdirectory
+        let archive_dir = changepacks_dir.join("archive");
+        fs::create_dir_all(&archive_dir).unwrap();
+        fs::write(archive_dir.join("changepack_log_old.json"), "{}").unwrap();
+        let log_dir = changepacks_dir.join("changepack_log_dir.json");
         fs::create_dir_all(&log_dir).unwrap();
 
-        // Test clearing logs - should fail because we're trying to remove a directory
-        let result = clear_update_logs(&changepacks_dir).await;
-        assert!(result.is_err());
-        let error_msg = result.unwrap_err().to_string();
-        assert!(error_msg.contains("Failed to remove 1 update log(s)"));
+        clear_update_logs(&changepacks_dir).await.unwrap();
+
+        assert!(archive_dir.join("changepack_log_old.json").exists());
+        assert!(log_dir.is_dir());
     }
 }
@@ -0,0 +1,90 @@
+use std::cmp::Ordering;
+
+/// Compare two versions by semver precedence
+///
+/// Partial versions are padded with zeros (`1.2` equals `1.2.0`), a prerelease sorts
+/// before its release (`1.2.0-rc.1 < 1.2.0`), prerelease identifiers compare numerically
+/// when both are numbers (`rc.2 < rc.10`), and build metadata is ignored. A leading `v`
+/// is accepted.
+///
+/// Returns `None` if either side is not `MAJOR[.MINOR[.PATCH]][-PRERELEASE][+BUILD]`.
+#[must_use]
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (a_core, a_pre) = parse(a)?;
+    let (b_core, b_pre) = parse(b)?;
+    Some(a_core.cmp(&b_core).then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => compare_prerelease(a, b),
+    }))
+}
+
+fn parse(version: &str) -> Option<([u64; 3], Option<&str>)> {
+    let version = version.trim();
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = version.split_once('+').map_or(version, |(core, _)| core);
+    let (core, prerelease) = match version.split_once('-') {
+        Some((_, "")) => return None,
+        Some((core, prerelease)) => (core, Some(prerelease)),
+        None => (version, None),
+    };
+    let mut parts = [0; 3];
+    for (index, part) in core.split('.').enumerate() {
+        if index == 3 || part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        parts[index] = part.parse().ok()?;
+    }
+    Some((parts, prerelease))
+}
+
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a_ids = a.split('.');
+    let mut b_ids = b.split('.');
+    loop {
+        match (a_ids.next(), b_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1.0.0", "1.0.0", Some(Ordering::Equal))]
+    #[case("1.0.1", "1.0.0", Some(Ordering::Greater))]
+    #[case("1.9.0", "1.10.0", Some(Ordering::Less))]
+    #[case("2.0.0", "1.99.99", Some(Ordering::Greater))]
+    #[case("1.2", "1.2.0", Some(Ordering::Equal))]
+    #[case("v1.2.3", "1.2.3", Some(Ordering::Equal))]
+    #[case("1.2.3+build.1", "1.2.3+build.2", Some(Ordering::Equal))]
+    #[case("1.2.0-rc.1", "1.2.0", Some(Ordering::Less))]
+    #[case("1.2.0-rc.2", "1.2.0-rc.10", Some(Ordering::Less))]
+    #[case("1.2.0-alpha", "1.2.0-beta", Some(Ordering::Less))]
+    #[case("1.2.0-alpha.1", "1.2.0-alpha", Some(Ordering::Greater))]
+    #[case("1.2.0-1", "1.2.0-alpha", Some(Ordering::Less))]
+    #[case("1.2.0-rc.1", "1.1.9", Some(Ordering::Greater))]
+    #[case("latest", "1.0.0", None)]
+    #[case("1.0.0", "1.0.0.0", None)]
+    #[case("1.0.0-", "1.0.0", None)]
+    fn test_compare_versions(#[case] a: &str, #[case] b: &str, #[case] expected: Option<Ordering>) {
+        assert_eq!(compare_versions(a, b), expected, "{a} vs {b}");
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use gix::refs::transaction::PreviousValue;
+
+/// Create the annotated tag `name` on `HEAD` of the repository at `repo_root`
+///
+/// The configured git committer (`user.name` and `user.email`) is recorded as the tagger,
+/// as `git tag -a` does.
+///
+/// # Errors
+/// Returns error if the tag already exists, `HEAD` has no commit, or no committer is configured.
+pub fn create_git_tag(repo_root: &Path, name: &str, message: &str) -> Result<()> {
+    let repo = gix::discover(repo_root)?;
+    let head = repo.head_commit().context("HEAD has no commit to tag")?;
+    let tagger = repo
+        .committer()
+        .context("Set git user.name and user.email to create tags")??;
+    repo.tag(
+        name,
+        head.id,
+        gix::objs::Kind::Commit,
+        Some(tagger),
+        message,
+        PreviousValue::MustNotExist,
+    )
+    .with_context(|| format!("Failed to create tag {name}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_create_git_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        git(temp_dir.path(), &["init", "-b", "main"]);
+        git(temp_dir.path(), &["config", "user.email", "test@test.com"]);
+        git(temp_dir.path(), &["config", "user.name", "Test"]);
+        git(
+            temp_dir.path(),
+            &["commit", "--allow-empty", "-m", "Initial commit"],
+        );
+
+        create_git_tag(
+            temp_dir.path(),
+            "core@1.2.0",
+            "core@1.2.0\n\n- Add parser\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            git(temp_dir.path(), &["cat-file", "-t", "core@1.2.0"]).trim(),
+            "tag"
+        );
+        assert_eq!(
+            git(temp_dir.path(), &["rev-parse", "core@1.2.0^{commit}"]),
+            git(temp_dir.path(), &["rev-parse", "HEAD"])
+        );
+        let tag = git(temp_dir.path(), &["cat-file", "-p", "core@1.2.0"]);
+        assert!(tag.contains("tagger Test <test@test.com>"), "{tag}");
+        assert!(tag.ends_with("- Add parser\n"), "{tag}");
+
+        let err = create_git_tag(temp_dir.path(), "core@1.2.0", "again").unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to create tag core@1.2.0"));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_create_git_tag_without_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        git(temp_dir.path(), &["init", "-b", "main"]);
+
+        assert!(create_git_tag(temp_dir.path(), "v1.0.0", "v1.0.0").is_err());
+
+        temp_dir.close().unwrap();
+    }
+}
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+/// Short name of the branch checked out in the repository at `repo_root`, `None` on a
+/// detached `HEAD`
+///
+/// # Errors
+/// Returns error if `repo_root` is not in a git repository or `HEAD` cannot be read.
+pub fn current_branch(repo_root: &Path) -> Result<Option<String>> {
+    let repo = gix::discover(repo_root)?;
+    Ok(repo.head_name()?.map(|name| name.shorten().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_git;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_current_branch() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(current_branch(temp_dir.path()).is_err());
+
+        for args in [
+            &["init", "-q", "-b", "release/next"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        ] {
+            run_git(temp_dir.path(), args).await.unwrap();
+        }
+        assert_eq!(
+            current_branch(temp_dir.path()).unwrap().as_deref(),
+            Some("release/next")
+        );
+
+        run_git(temp_dir.path(), &["checkout", "-q", "--detach"])
+            .await
+            .unwrap();
+        assert_eq!(current_branch(temp_dir.path()).unwrap(), None);
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use changepacks_core::{Config, DiffRefs, ProjectFinder};
+
+use crate::{find_current_git_repo, find_project_dirs, find_project_root, walk_project_dirs};
+
+/// Outcome of [`discover_projects`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectDiscovery {
+    /// Release root the projects belong to (see [`find_project_root`])
+    pub root: PathBuf,
+    /// Areas left out of the sparse checkout whose index entries were skipped
+    pub sparse_skipped: Vec<PathBuf>,
+}
+
+/// Discover projects for `current_dir`
+///
+/// Uses the git index and change detection (comparing `refs`) when inside a git repository,
+/// and falls back to a plain filesystem walk (no change detection) otherwise.
+///
+/// # Errors
+/// Returns error if project discovery fails.
+///
+/// Excluded from coverage: thin dispatch between `find_project_dirs` and
+/// `walk_project_dirs`, both of which are covered by their own tests.
+#[cfg(not(tarpaulin_include))]
+pub async fn discover_projects(
+    current_dir: &Path,
+    project_finders: &mut [Box<dyn ProjectFinder>],
+    config: &Config,
+    remote: bool,
+    refs: &DiffRefs,
+) -> Result<ProjectDiscovery> {
+    let root = find_project_root(current_dir)?;
+    if let Ok(repo) = find_current_git_repo(&root) {
+        repo.work_dir()
+            .context("Not a git working directory. Ensure you are inside a git repository.")?;
+        let sparse_skipped =
+            find_project_dirs(&repo, &root, project_finders, config, remote, refs).await?;
+        return Ok(ProjectDiscovery {
+            root,
+            sparse_skipped,
+        });
+    }
+    walk_project_dirs(&root, project_finders, config).await?;
+    Ok(ProjectDiscovery {
+        root,
+        sparse_skipped: Vec::new(),
+    })
+}
@@ -1,19 +1,41 @@
 use anyhow::Result;
 use changepacks_core::UpdateType;
+use colored::{ColoredString, Colorize};
 
-use crate::next_version;
+use crate::next_version_with_id;
 
-/// Display the version update as a formatted string
+/// Display the version update as a formatted string (`1.2.3 -> 1.3.0`)
+///
+/// The next version is colored by bump severity: major red, minor yellow, patch green.
+/// Prerelease bumps use `prerelease_id` (see [`crate::next_version_with_id`]).
 ///
 /// # Errors
 /// Returns error if the next version cannot be calculated.
-pub fn display_update(current_version: Option<&str>, update_type: UpdateType) -> Result<String> {
-    if let Some(current_version) = current_version {
-        let next_version = next_version(current_version, update_type)?;
-        Ok(format!("v{current_version} → v{next_version}"))
-    } else {
-        let next_version = next_version("0.0.0", update_type)?;
-        Ok(format!("{} → v{}", "unknown", next_version))
+pub fn display_update(
+    current_version: Option<&str>,
+    update_type: UpdateType,
+    prerelease_id: Option<&str>,
+) -> Result<String> {
+    let next_version = next_version_with_id(
+        current_version.unwrap_or("0.0.0"),
+        update_type,
+        prerelease_id,
+    )?;
+    Ok(format!(
+        "{} -> {}",
+        current_version.unwrap_or("unknown"),
+        color_by_severity(&next_version, update_type)
+    ))
+}
+
+/// Color text by bump severity: major red, minor yellow, patch green, prerelease cyan
+#[must_use]
+pub(crate) fn color_by_severity(text: &str, update_type: UpdateType) -> ColoredString {
+    match update_type {
+        UpdateType::Major => text.bright_red().bold(),
+        UpdateType::Minor => text.bright_yellow(),
+        UpdateType::Patch => text.bright_green(),
+        UpdateType::Prerelease => text.bright_cyan(),
     }
 }
 
@@ -24,28 +46,37 @@ mod tests {
     use super::*;
 
     #[rstest]
-    #[case(Some("1.0.0"), UpdateType::Major, "v1.0.0 → v2.0.0")]
-    #[case(Some("1.0.0"), UpdateType::Minor, "v1.0.0 → v1.1.0")]
-    #[case(Some("1.0.0"), UpdateType::Patch, "v1.0.0 → v1.0.1")]
-    #[case(Some("2.5.3"), UpdateType::Major, "v2.5.3 → v3.0.0")]
-    #[case(Some("2.5.3"), UpdateType::Minor, "v2.5.3 → v2.6.0")]
-    #[case(Some("2.5.3"), UpdateType::Patch, "v2.5.3 → v2.5.4")]
-    #[case(Some("0.1.0"), UpdateType::Major, "v0.1.0 → v1.0.0")]
-    #[case(Some("10.20.30"), UpdateType::Major, "v10.20.30 → v11.0.0")]
-    #[case(Some("10.20.30"), UpdateType::Minor, "v10.20.30 → v10.21.0")]
-    #[case(Some("10.20.30"), UpdateType::Patch, "v10.20.30 → v10.20.31")]
-    #[case(Some("10.20.30+1"), UpdateType::Patch, "v10.20.30+1 → v10.20.31+1")]
-    #[case(None, UpdateType::Major, "unknown → v1.0.0")]
-    #[case(None, UpdateType::Minor, "unknown → v0.1.0")]
-    #[case(None, UpdateType::Patch, "unknown → v0.0.1")]
+    #[case(Some("1.0.0"), UpdateType::Major, "1.0.0", "2.0.0")]
+    #[case(Some("1.0.0"), UpdateType::Minor, "1.0.0", "1.1.0")]
+    #[case(Some("1.0.0"), UpdateType::Patch, "1.0.0", "1.0.1")]
+    #[case(Some("2.5.3"), UpdateType::Major, "2.5.3", "3.0.0")]
+    #[case(Some("2.5.3"), UpdateType::Minor, "2.5.3", "2.6.0")]
+    #[case(Some("2.5.3"), UpdateType::Patch, "2.5.3", "2.5.4")]
+    #[case(Some("0.1.0"), UpdateType::Major, "0.1.0", "1.0.0")]
+    #[case(Some("10.20.30"), UpdateType::Major, "10.20.30", "11.0.0")]
+    #[case(Some("10.20.30"), UpdateType::Minor, "10.20.30", "10.21.0")]
+    #[case(Some("10.20.30"), UpdateType::Patch, "10.20.30", "10.20.31")]
+    #[case(Some("10.20.30+1"), UpdateType::Patch, "10.20.30+1", "10.20.31+1")]
+    #[case(None, UpdateType::Major, "unknown", "1.0.0")]
+    #[case(None, UpdateType::Minor, "unknown", "0.1.0")]
+    #[case(None, UpdateType::Patch, "unknown", "0.0.1")]
     fn test_display_update(
         #[case] current_version: Option<&str>,
         #[case] update_type: UpdateType,
-        #[case] expected: &str,
+        #[case] current: &str,
+        #[case] next: &str,
     ) {
         assert_eq!(
-            display_update(current_version, update_type).unwrap(),
-            expected
+            display_update(current_version, update_type, None).unwrap(),
+            format!("{current} -> {}", color_by_severity(next, update_type))
         );
     }
+
+    #[rstest]
+    #[case(UpdateType::Major, "1".bright_red().bold())]
+    #[case(UpdateType::Minor, "1".bright_yellow())]
+    #[case(UpdateType::Patch, "1".bright_green())]
+    fn test_color_by_severity(#[case] update_type: UpdateType, #[case] expected: ColoredString) {
+        assert_eq!(color_by_severity("1", update_type), expected);
+    }
 }
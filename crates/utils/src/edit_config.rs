@@ -0,0 +1,282 @@
+use anyhow::{Context, Result};
+use changepacks_core::ConfigFileFormat;
+use serde::Serialize;
+use serde_json::Value;
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::detect_indent;
+
+/// JSON pointer of a setting given as `key`
+///
+/// A key starting with `/` already is a JSON pointer (RFC 6901, `~1` for `/` and `~0` for `~`
+/// within a segment). Otherwise it is a dotted path such as `publish.node` or `fixed.0`,
+/// whose segments cannot contain dots; use the pointer form for keys like
+/// `/publish/crates~1cli~1Cargo.toml`.
+#[must_use]
+pub fn config_pointer(key: &str) -> String {
+    if key.starts_with('/') || key.is_empty() {
+        return key.to_string();
+    }
+    key.split('.')
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Unescaped segments of a JSON pointer
+fn pointer_segments(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Set the setting at JSON `pointer` in `root`, creating missing tables on the way
+///
+/// Array elements are addressed by index, and `-` appends to an array.
+///
+/// # Errors
+/// Returns error if the pointer is empty or passes through a value that is neither a table
+/// nor an array, or an array index is out of range.
+pub fn set_pointer(root: &mut Value, pointer: &str, value: Value) -> Result<()> {
+    let segments = pointer_segments(pointer);
+    let Some((last, parents)) = segments.split_last() else {
+        anyhow::bail!("No setting given");
+    };
+    let mut current = root;
+    for segment in parents {
+        current = match current {
+            Value::Object(map) => map
+                .entry(segment.clone())
+                .or_insert_with(|| Value::Object(serde_json::Map::new())),
+            Value::Array(items) => {
+                let index = array_index(segment, items.len())?;
+                &mut items[index]
+            }
+            _ => anyhow::bail!("`{segment}` is inside a setting that is not a table: {pointer}"),
+        };
+    }
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        Value::Array(items) if last == "-" => items.push(value),
+        Value::Array(items) => {
+            let index = array_index(last, items.len())?;
+            items[index] = value;
+        }
+        _ => anyhow::bail!("`{last}` is inside a setting that is not a table: {pointer}"),
+    }
+    Ok(())
+}
+
+fn array_index(segment: &str, len: usize) -> Result<usize> {
+    segment
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index < len)
+        .with_context(|| format!("Index `{segment}` is out of range for a list of {len}"))
+}
+
+/// Content of a config file in `format` after setting `pointer` to `value`
+///
+/// Returns the new raw settings along with the new content. JSON keeps its indentation and
+/// TOML its comments and layout; YAML is written out anew.
+///
+/// # Errors
+/// Returns error if `content` does not parse, the pointer cannot be set, or the value cannot
+/// be written in `format` (e.g., `null` in TOML).
+pub fn set_config_setting(
+    format: ConfigFileFormat,
+    content: &str,
+    pointer: &str,
+    value: Value,
+) -> Result<(Value, String)> {
+    let mut settings = if content.trim().is_empty() {
+        Value::Object(serde_json::Map::new())
+    } else {
+        format.parse(content)?
+    };
+    set_pointer(&mut settings, pointer, value)?;
+    let updated = match format {
+        ConfigFileFormat::Json => {
+            let indent = match detect_indent(content) {
+                0 => 2,
+                indent => indent,
+            };
+            let ind = b" ".repeat(indent);
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(&ind);
+            let mut ser = serde_json::Serializer::with_formatter(Vec::new(), formatter);
+            settings.serialize(&mut ser)?;
+            format!("{}\n", String::from_utf8(ser.into_inner())?)
+        }
+        ConfigFileFormat::Toml => patch_toml(content, &pointer_segments(pointer), &settings)?,
+        ConfigFileFormat::Yaml => serde_yaml::to_string(&settings)?,
+    };
+    Ok((settings, updated))
+}
+
+/// Replace the TOML item changed in `settings`, leaving the rest of the document as it was
+///
+/// Tables along the pointer are kept (or created); the first segment below them is
+/// rewritten from `settings` as a whole.
+fn patch_toml(content: &str, segments: &[String], settings: &Value) -> Result<String> {
+    let mut document: DocumentMut = content.parse().context("Failed to parse TOML")?;
+    let mut table = document.as_table_mut() as &mut dyn toml_edit::TableLike;
+    let mut value = settings;
+    for (index, segment) in segments.iter().enumerate() {
+        value = &value[segment];
+        let is_table = table.get(segment).is_none_or(Item::is_table_like);
+        if index + 1 < segments.len() && value.is_object() && is_table {
+            let mut implicit = Table::new();
+            implicit.set_implicit(true);
+            table = table
+                .entry(segment)
+                .or_insert(Item::Table(implicit))
+                .as_table_like_mut()
+                .context("Expected a TOML table")?;
+            continue;
+        }
+        table.insert(segment, toml_item(value)?);
+        break;
+    }
+    Ok(document.to_string())
+}
+
+fn toml_item(value: &Value) -> Result<Item> {
+    Ok(match value {
+        Value::Object(map) => {
+            let mut table = Table::new();
+            for (key, value) in map {
+                table.insert(key, toml_item(value)?);
+            }
+            Item::Table(table)
+        }
+        value => Item::Value(toml_value(value)?),
+    })
+}
+
+fn toml_value(value: &Value) -> Result<toml_edit::Value> {
+    Ok(match value {
+        Value::Null => anyhow::bail!("TOML has no null value"),
+        Value::Bool(value) => (*value).into(),
+        Value::Number(number) => match number.as_i64() {
+            Some(number) => number.into(),
+            None => number.as_f64().context("Unsupported number")?.into(),
+        },
+        Value::String(value) => value.as_str().into(),
+        Value::Array(items) => items
+            .iter()
+            .map(toml_value)
+            .collect::<Result<toml_edit::Array>>()?
+            .into(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), toml_value(value)?)))
+            .collect::<Result<toml_edit::InlineTable>>()?
+            .into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case("baseBranch", "/baseBranch")]
+    #[case("publish.node", "/publish/node")]
+    #[case("fixed.0", "/fixed/0")]
+    #[case("/publish/crates~1cli~1Cargo.toml", "/publish/crates~1cli~1Cargo.toml")]
+    fn test_config_pointer(#[case] key: &str, #[case] pointer: &str) {
+        assert_eq!(config_pointer(key), pointer);
+    }
+
+    #[test]
+    fn test_set_pointer() {
+        let mut root = json!({"ignore": ["a"], "fixed": [["x"]]});
+        set_pointer(&mut root, "/publish/crates~1cli~1Cargo.toml", json!(false)).unwrap();
+        set_pointer(&mut root, "/ignore/-", json!("b")).unwrap();
+        set_pointer(&mut root, "/fixed/0/0", json!("y")).unwrap();
+        assert_eq!(
+            root,
+            json!({
+                "ignore": ["a", "b"],
+                "fixed": [["y"]],
+                "publish": {"crates/cli/Cargo.toml": false}
+            })
+        );
+        assert!(set_pointer(&mut root, "/ignore/5", json!("c")).is_err());
+        assert!(set_pointer(&mut root, "/ignore/0/x", json!("c")).is_err());
+        assert!(set_pointer(&mut root, "", json!("c")).is_err());
+    }
+
+    #[test]
+    fn test_set_config_setting_json_keeps_indent() {
+        let content = "{\n    \"ignore\": [],\n    \"baseBranch\": \"main\"\n}\n";
+        let (settings, updated) = set_config_setting(
+            ConfigFileFormat::Json,
+            content,
+            "/publish/node",
+            json!("pnpm publish"),
+        )
+        .unwrap();
+        assert_eq!(settings["publish"]["node"], "pnpm publish");
+        assert_eq!(
+            updated,
+            "{\n    \"ignore\": [],\n    \"baseBranch\": \"main\",\n    \"publish\": {\n        \"node\": \"pnpm publish\"\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_set_config_setting_toml_keeps_comments() {
+        let content = "# Release from develop\nbaseBranch = \"develop\"\n\n[publish]\n# npm needs a token\nnode = \"npm publish\"\n";
+        let (_, updated) = set_config_setting(
+            ConfigFileFormat::Toml,
+            content,
+            "/publish/rust",
+            json!("cargo publish"),
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            "# Release from develop\nbaseBranch = \"develop\"\n\n[publish]\n# npm needs a token\nnode = \"npm publish\"\nrust = \"cargo publish\"\n"
+        );
+
+        let (_, updated) = set_config_setting(
+            ConfigFileFormat::Toml,
+            "",
+            "/registries/npm/url",
+            json!("https://npm.example.com"),
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            "[registries.npm]\nurl = \"https://npm.example.com\"\n"
+        );
+
+        let (_, updated) = set_config_setting(
+            ConfigFileFormat::Toml,
+            "ignore = [\"a\"] # skipped\n",
+            "/ignore/-",
+            json!("b"),
+        )
+        .unwrap();
+        assert_eq!(updated, "ignore = [\"a\", \"b\"]\n");
+
+        assert!(set_config_setting(ConfigFileFormat::Toml, "", "/extends", Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_set_config_setting_yaml() {
+        let (_, updated) = set_config_setting(
+            ConfigFileFormat::Yaml,
+            "baseBranch: main\n",
+            "/ignore",
+            json!(["docs/**"]),
+        )
+        .unwrap();
+        assert_eq!(updated, "baseBranch: main\nignore:\n- docs/**\n");
+    }
+}
@@ -1,12 +1,30 @@
-use crate::get_relative_path;
+use crate::{ChangeFilter, get_relative_path};
 use anyhow::{Context, Result};
-use changepacks_core::{Config, ProjectFinder};
+use changepacks_core::{Config, DiffRefs, ProjectFinder, changepacks_dir_name, normalize_path};
 use gix::{ThreadSafeRepository, bstr::ByteSlice, features::progress};
-use ignore::gitignore::GitignoreBuilder;
-use std::path::Path;
+use ignore::{
+    WalkBuilder,
+    gitignore::{Gitignore, GitignoreBuilder},
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 /// Find project directories containing specific files from git tracked files
 ///
+/// Only files under `root` (the work tree, or a release root inside it) are visited, and
+/// directories below `root` holding their own changepacks directory are left to that root.
+///
+/// Tracked symlinks to directories outside `root` (e.g. vendored packages) are followed; see
+/// [`visit_linked_dirs`].
+///
+/// Index entries outside a sparse checkout are not materialized on disk, so they are skipped
+/// instead of visited. Returns the skipped areas (outermost directories only), for verbose output.
+///
+/// HEAD is resolved to its commit, so detached checkouts work, and the base branch falls back
+/// to `origin/<base_branch>` when it has no local branch. Explicit `refs` replace either end.
+///
 /// # Errors
 /// Returns error if git operations fail, gitignore parsing fails, or project visiting fails.
 ///
@@ -18,55 +36,49 @@ use std::path::Path;
 #[cfg(not(tarpaulin_include))]
 pub async fn find_project_dirs(
     repo: &ThreadSafeRepository,
+    root: &Path,
     project_finders: &mut [Box<dyn ProjectFinder>],
     config: &Config,
     remote: bool,
-) -> Result<()> {
+    refs: &DiffRefs,
+) -> Result<Vec<PathBuf>> {
     // Get git root for relative path conversion
-    let git_root_path = repo.work_dir().context("Not a working directory")?;
+    let git_root_path = &normalize_path(repo.work_dir().context("Not a working directory")?);
 
-    // Build gitignore from config patterns (supports ! negation patterns)
-    let gitignore = if config.ignore.is_empty() {
-        None
-    } else {
-        let mut builder = GitignoreBuilder::new(git_root_path);
-        for pattern in &config.ignore {
-            builder.add_line(None, pattern)?;
-        }
-        Some(builder.build()?)
-    };
+    let gitignore = build_ignore_matcher(root, config)?;
+    let change_filter = ChangeFilter::new(root, config)?;
+    let mut nested_roots = NestedRoots::new(root);
 
     let repo = repo.to_thread_local();
     let index = repo
         .index()
         .context("Failed to get index, Please add files to git")?;
+    let mut sparse_skipped = BTreeSet::new();
+    let mut links = Vec::new();
     // Iterate through git tracked files and find matching project files
     for entry in index.entries() {
         let file_path = entry.path(&index);
         let file_path_str = file_path.to_string();
-        let path = Path::new(&file_path_str);
-
-        // Check if this file matches any of the project files
-        // Insert absolute path using git_root_path.join(parent)
-        let abs_path = git_root_path.join(path);
-        let rel_path = get_relative_path(git_root_path, &abs_path)?;
-
-        // Skip if path matches ignore patterns (gitignore supports ! negation)
-        if let Some(ref gitignore) = gitignore
-            && gitignore.matched(&rel_path, false).is_ignore()
+        if entry
+            .flags
+            .contains(gix::index::entry::Flags::SKIP_WORKTREE)
+            || entry.mode.is_sparse()
         {
+            sparse_skipped.insert(sparse_area(&file_path_str, entry.mode.is_sparse()));
             continue;
         }
-
-        futures::future::join_all(
-            project_finders
-                .iter_mut()
-                .map(async |finder| finder.visit(&abs_path, &rel_path).await),
-        )
-        .await
-        .into_iter()
-        .collect::<Result<Vec<_>>>()?;
+        // Insert absolute path using git_root_path.join(parent)
+        let abs_path = git_root_path.join(Path::new(&file_path_str));
+        if !abs_path.starts_with(root) || nested_roots.contains(&abs_path) {
+            continue;
+        }
+        if entry.mode == gix::index::entry::Mode::SYMLINK {
+            links.push(abs_path);
+            continue;
+        }
+        visit_project_file(project_finders, gitignore.as_ref(), root, &abs_path).await?;
     }
+    visit_linked_dirs(project_finders, gitignore.as_ref(), root, links).await?;
 
     // Post-visit finalization (resolves deferred state like workspace-inherited versions)
     for finder in project_finders.iter_mut() {
@@ -89,37 +101,33 @@ pub async fn find_project_dirs(
                 Some(name.to_string())
             }
         })
-        .or_else(|| {
-            git_root_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .map(String::from)
-        });
+        .or_else(|| root.file_name().and_then(|n| n.to_str()).map(String::from));
     if let Some(ref repo_name) = repo_name {
-        for finder in project_finders.iter_mut() {
-            for project in finder.projects_mut() {
-                if project.name().is_none() {
-                    project.set_name(repo_name.clone());
-                }
-            }
-        }
+        set_fallback_names(project_finders, repo_name);
     }
 
-    let changed_files = repo
-        .status(progress::Discard)?
-        .into_index_worktree_iter(Vec::new())?
-        .filter_map(|entry| {
-            entry.ok().and_then(|entry| {
-                entry
-                    .rela_path()
-                    .to_path()
-                    .ok()
-                    .map(std::path::Path::to_path_buf)
-            })
-        })
-        .collect::<Vec<_>>();
-    // diff from main branch
-    let main_tree = if remote {
+    // Stream worktree/index changes straight into the finders instead of
+    // collecting them first, keeping peak memory flat on very large repositories.
+    // An explicit --head compares two commits, so local edits don't count. A status that
+    // can't be computed (e.g. a read-only checkout) only loses local edits, so it is skipped.
+    let worktree_changes = refs
+        .head
+        .is_none()
+        .then(|| repo.status(progress::Discard).ok())
+        .flatten()
+        .and_then(|status| status.into_index_worktree_iter(Vec::new()).ok());
+    for entry in worktree_changes.into_iter().flatten() {
+        let Ok(entry) = entry else { continue };
+        let Ok(file) = entry.rela_path().to_path() else {
+            continue;
+        };
+        change_filter.mark_changed(project_finders, &git_root_path.join(file))?;
+    }
+
+    // diff from main branch (or the explicit --base)
+    let main_tree = if let Some(base) = &refs.base {
+        revision_tree(&repo, base)?
+    } else if remote {
         repo.find_remote("origin")?
             .repo
             .find_reference(&format!("refs/remotes/origin/{}", config.base_branch))?
@@ -130,13 +138,21 @@ pub async fn find_project_dirs(
             .object()?
             .try_into_tree()?
     } else {
-        repo.find_reference(&format!("refs/heads/{}", config.base_branch))
+        // CI checkouts often only fetch the remote-tracking branch
+        let base_ref = ["refs/heads", "refs/remotes/origin"]
+            .iter()
+            .find_map(|prefix| {
+                repo.try_find_reference(&format!("{prefix}/{}", config.base_branch))
+                    .ok()
+                    .flatten()
+            })
             .with_context(|| {
                 format!(
-                    "base branch '{}' not found in local refs",
+                    "base branch '{}' not found in local or origin refs",
                     config.base_branch
                 )
-            })?
+            })?;
+        base_ref
             .id()
             .object()?
             .try_into_commit()?
@@ -144,32 +160,205 @@ pub async fn find_project_dirs(
             .object()?
             .try_into_tree()?
     };
-    let head_tree = repo.head_tree()?;
-    let diff = repo
-        .diff_tree_to_tree(
-            Some(&head_tree),
-            Some(&main_tree),
-            gix::diff::Options::default(),
-        )?
-        .into_iter()
-        .filter_map(|change| {
-            change
-                .location()
-                .to_path()
-                .ok()
-                .map(std::path::Path::to_path_buf)
-        })
-        .collect::<Vec<_>>();
+    let head_tree = match &refs.head {
+        Some(head) => revision_tree(&repo, head)?,
+        // Resolve the commit HEAD points to, which also works when HEAD is detached
+        None => repo
+            .head_id()
+            .context("HEAD does not point to a commit")?
+            .object()?
+            .peel_to_tree()?,
+    };
+    head_tree
+        .changes()?
+        .options(|opts| *opts = gix::diff::Options::default())
+        .for_each_to_obtain_tree(&main_tree, |change| -> Result<_> {
+            if let Ok(file) = change.location().to_path() {
+                change_filter.mark_changed(project_finders, &git_root_path.join(file))?;
+            }
+            Ok(std::ops::ControlFlow::Continue(()))
+        })?;
+
+    Ok(outermost_dirs(sparse_skipped))
+}
+
+/// Tree of the commit `revision` (a branch, tag, remote ref or commit id) points to
+fn revision_tree<'repo>(repo: &'repo gix::Repository, revision: &str) -> Result<gix::Tree<'repo>> {
+    Ok(repo
+        .rev_parse_single(revision)
+        .with_context(|| format!("revision '{revision}' not found"))?
+        .object()?
+        .peel_to_tree()?)
+}
+
+/// Directory a skipped sparse index entry belongs to (sparse directory entries are their own area)
+fn sparse_area(entry_path: &str, is_sparse_dir: bool) -> PathBuf {
+    let path = Path::new(entry_path.trim_end_matches('/'));
+    if is_sparse_dir {
+        path.to_path_buf()
+    } else {
+        path.parent().unwrap_or(Path::new("")).to_path_buf()
+    }
+}
 
-    for file in changed_files.iter().chain(diff.iter()) {
-        for finder in project_finders.iter_mut() {
-            finder.check_changed(&git_root_path.join(file))?;
+/// Drop directories already covered by one of their ancestors
+fn outermost_dirs(dirs: BTreeSet<PathBuf>) -> Vec<PathBuf> {
+    let mut outermost: Vec<PathBuf> = Vec::new();
+    // BTreeSet order puts every ancestor before its descendants
+    for dir in dirs {
+        if !outermost.iter().any(|kept| dir.starts_with(kept)) {
+            outermost.push(dir);
         }
     }
+    outermost
+}
+
+/// Directories below a root that hold their own changepacks directory
+///
+/// Each is an independent release root, so its files belong to it rather than to the outer root.
+struct NestedRoots<'a> {
+    root: &'a Path,
+    dir_name: PathBuf,
+    cache: HashMap<PathBuf, bool>,
+}
+
+impl<'a> NestedRoots<'a> {
+    fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            dir_name: changepacks_dir_name(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Whether `abs_path` lies inside a nested release root
+    fn contains(&mut self, abs_path: &Path) -> bool {
+        for dir in abs_path.ancestors().skip(1) {
+            if dir == self.root || !dir.starts_with(self.root) {
+                break;
+            }
+            if *self
+                .cache
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| dir.join(&self.dir_name).is_dir())
+            {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Gitignore-syntax file at the release root whose patterns add to the config `ignore`
+const IGNORE_FILE_NAME: &str = ".changepacksignore";
+
+/// Build a matcher from the `.changepacksignore` file and the config `ignore` patterns
+/// (supports ! negation patterns)
+///
+/// The file's patterns come first, so a config pattern wins over a conflicting file pattern.
+///
+/// # Errors
+/// Returns error if the file cannot be read or a pattern is not a valid gitignore pattern.
+pub(crate) fn build_ignore_matcher(root: &Path, config: &Config) -> Result<Option<Gitignore>> {
+    let ignore_file = root.join(IGNORE_FILE_NAME);
+    let has_ignore_file = ignore_file.is_file();
+    if config.ignore.is_empty() && !has_ignore_file {
+        return Ok(None);
+    }
+    let mut builder = GitignoreBuilder::new(root);
+    if has_ignore_file && let Some(err) = builder.add(&ignore_file) {
+        return Err(err).with_context(|| format!("Failed to read {}", ignore_file.display()));
+    }
+    for pattern in &config.ignore {
+        builder.add_line(None, pattern)?;
+    }
+    Ok(Some(builder.build()?))
+}
+
+/// Hand one file to every finder unless the config ignore patterns exclude it
+///
+/// # Errors
+/// Returns error if the relative path cannot be computed or a finder fails to visit the file.
+pub(crate) async fn visit_project_file(
+    project_finders: &mut [Box<dyn ProjectFinder>],
+    gitignore: Option<&Gitignore>,
+    root: &Path,
+    abs_path: &Path,
+) -> Result<()> {
+    let rel_path = get_relative_path(root, abs_path)?;
+    if let Some(gitignore) = gitignore
+        && gitignore.matched(&rel_path, false).is_ignore()
+    {
+        return Ok(());
+    }
+
+    futures::future::join_all(
+        project_finders
+            .iter_mut()
+            .map(async |finder| finder.visit(abs_path, &rel_path).await),
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>>>()?;
+    Ok(())
+}
 
+/// Visit the files of symlinked directories whose target lies outside `root`
+///
+/// Files are visited through the link, so their relative paths stay under `root`. Targets inside
+/// `root` are skipped because they are already visited through their real path, and several
+/// links to one target are followed once (the first link in path order), so every project is
+/// found exactly once. Links to files and dangling links are ignored.
+///
+/// # Errors
+/// Returns error if `root` cannot be resolved, walking a target fails, or visiting fails.
+pub(crate) async fn visit_linked_dirs(
+    project_finders: &mut [Box<dyn ProjectFinder>],
+    gitignore: Option<&Gitignore>,
+    root: &Path,
+    mut links: Vec<PathBuf>,
+) -> Result<()> {
+    if links.is_empty() {
+        return Ok(());
+    }
+    let canonical_root = std::fs::canonicalize(root)?;
+    let mut seen = HashSet::new();
+    links.sort();
+    for link in links {
+        let Ok(target) = std::fs::canonicalize(&link) else {
+            continue;
+        };
+        if !target.is_dir() || target.starts_with(&canonical_root) || !seen.insert(target) {
+            continue;
+        }
+        let walker = WalkBuilder::new(&link)
+            .hidden(false)
+            .require_git(false)
+            .follow_links(true)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .sort_by_file_name(Ord::cmp)
+            .build();
+        for entry in walker {
+            let entry = entry?;
+            if entry.file_type().is_some_and(|ty| ty.is_file()) {
+                visit_project_file(project_finders, gitignore, root, entry.path()).await?;
+            }
+        }
+    }
     Ok(())
 }
 
+/// Name projects that have no name in their manifest
+pub(crate) fn set_fallback_names(project_finders: &mut [Box<dyn ProjectFinder>], name: &str) {
+    for finder in project_finders.iter_mut() {
+        for project in finder.projects_mut() {
+            if project.name().is_none() {
+                project.set_name(name.to_string());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,6 +397,108 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_sparse_area() {
+        assert_eq!(sparse_area("a/b/package.json", false), PathBuf::from("a/b"));
+        assert_eq!(sparse_area("a/b/", true), PathBuf::from("a/b"));
+        assert_eq!(sparse_area("package.json", false), PathBuf::from(""));
+    }
+
+    #[test]
+    fn test_outermost_dirs() {
+        let dirs: BTreeSet<PathBuf> = ["a", "a/b", "a/b/c", "ab", "d/e"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(
+            outermost_dirs(dirs),
+            vec![
+                PathBuf::from("a"),
+                PathBuf::from("ab"),
+                PathBuf::from("d/e")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_ignore_matcher_merges_ignore_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        assert!(
+            build_ignore_matcher(root, &Config::default())
+                .unwrap()
+                .is_none()
+        );
+
+        std::fs::write(
+            root.join(IGNORE_FILE_NAME),
+            "# vendored\nvendor/**\nexamples/**\n",
+        )
+        .unwrap();
+        let config = Config {
+            ignore: vec!["docs/**".to_string(), "!examples/keep/**".to_string()],
+            ..Default::default()
+        };
+        let matcher = build_ignore_matcher(root, &config).unwrap().unwrap();
+        let ignored = |path: &str| matcher.matched(path, false).is_ignore();
+        assert!(ignored("vendor/lib/package.json"));
+        assert!(ignored("docs/package.json"));
+        assert!(ignored("examples/demo/package.json"));
+        assert!(!ignored("examples/keep/package.json"));
+        assert!(!ignored("packages/core/package.json"));
+    }
+
+    #[tokio::test]
+    async fn test_find_project_dirs_sparse_checkout() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        init_git_repo(temp_path);
+
+        for name in ["core", "other"] {
+            fs::create_dir_all(temp_path.join(format!("packages/{name}")))
+                .await
+                .unwrap();
+            fs::write(
+                temp_path.join(format!("packages/{name}/package.json")),
+                format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+            )
+            .await
+            .unwrap();
+        }
+        git_add_and_commit(temp_path, "Initial commit");
+
+        let output = std::process::Command::new("git")
+            .args(["sparse-checkout", "set", "--cone", "packages/core"])
+            .current_dir(temp_path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert!(!temp_path.join("packages/other/package.json").exists());
+
+        let repo = gix::discover(temp_path).unwrap().into_sync();
+        let config = Config::default();
+        let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
+
+        let skipped = find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
+
+        let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name(), Some("core"));
+        assert_eq!(skipped, vec![PathBuf::from("packages/other")]);
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_find_project_dirs_basic() {
         let temp_dir = TempDir::new().unwrap();
@@ -229,9 +520,16 @@ mod tests {
         let config = Config::default();
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -240,6 +538,139 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_find_project_dirs_separates_nested_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        init_git_repo(temp_path);
+
+        for name in ["core", "web"] {
+            fs::create_dir_all(temp_path.join(format!("packages/{name}/.changepacks")))
+                .await
+                .unwrap();
+            fs::write(
+                temp_path.join(format!("packages/{name}/package.json")),
+                format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+            )
+            .await
+            .unwrap();
+        }
+        // Only `web` is its own release root
+        fs::remove_dir(temp_path.join("packages/core/.changepacks"))
+            .await
+            .unwrap();
+        fs::write(
+            temp_path.join("packages/web/.changepacks/config.json"),
+            "{}",
+        )
+        .await
+        .unwrap();
+
+        git_add_and_commit(temp_path, "Initial commit");
+
+        let repo = gix::discover(temp_path).unwrap().into_sync();
+        let config = Config::default();
+        let names = async |root: &Path| {
+            let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
+            find_project_dirs(
+                &repo,
+                root,
+                &mut finders,
+                &config,
+                false,
+                &DiffRefs::default(),
+            )
+            .await
+            .unwrap();
+            finders
+                .iter()
+                .flat_map(|f| f.projects())
+                .map(|project| project.name().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(temp_path).await, ["core"]);
+        assert_eq!(names(&temp_path.join("packages/web")).await, ["web"]);
+
+        temp_dir.close().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_find_project_dirs_follows_tracked_symlinks_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        let repo_path = temp_path.join("repo");
+        let vendor_path = temp_path.join("vendor/ext");
+        fs::create_dir_all(repo_path.join("packages/core"))
+            .await
+            .unwrap();
+        fs::create_dir_all(&vendor_path).await.unwrap();
+
+        init_git_repo(&repo_path);
+        fs::write(
+            repo_path.join("packages/core/package.json"),
+            r#"{"name": "core", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+        fs::write(
+            vendor_path.join("package.json"),
+            r#"{"name": "ext", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+        std::os::unix::fs::symlink(repo_path.join("packages/core"), repo_path.join("core-link"))
+            .unwrap();
+        std::os::unix::fs::symlink(&vendor_path, repo_path.join("packages/ext")).unwrap();
+        std::os::unix::fs::symlink(&vendor_path, repo_path.join("packages/ext-again")).unwrap();
+
+        git_add_and_commit(&repo_path, "Initial commit");
+
+        let repo = gix::discover(&repo_path).unwrap().into_sync();
+        let config = Config::default();
+        let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
+
+        find_project_dirs(
+            &repo,
+            &repo_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
+
+        let mut projects: Vec<(String, PathBuf)> = finders
+            .iter()
+            .flat_map(|f| f.projects())
+            .map(|project| {
+                (
+                    project.name().unwrap().to_string(),
+                    project.relative_path().to_path_buf(),
+                )
+            })
+            .collect();
+        projects.sort();
+        assert_eq!(
+            projects,
+            [
+                (
+                    "core".to_string(),
+                    PathBuf::from("packages/core/package.json")
+                ),
+                (
+                    "ext".to_string(),
+                    PathBuf::from("packages/ext/package.json")
+                ),
+            ]
+        );
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_find_project_dirs_with_ignore() {
         let temp_dir = TempDir::new().unwrap();
@@ -277,9 +708,16 @@ mod tests {
         };
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -326,9 +764,16 @@ mod tests {
         let config = Config::default();
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -362,9 +807,16 @@ mod tests {
         };
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -398,9 +850,16 @@ mod tests {
         let config = Config::default();
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 3);
@@ -459,9 +918,16 @@ mod tests {
         let config = Config::default();
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -540,9 +1006,16 @@ mod tests {
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
         // Test with remote=true to hit lines 88-90
-        find_project_dirs(&repo, &mut finders, &config, true)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            local_path,
+            &mut finders,
+            &config,
+            true,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -582,9 +1055,16 @@ mod tests {
         let config = Config::default();
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -622,9 +1102,16 @@ mod tests {
         let config = Config::default();
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -665,9 +1152,16 @@ mod tests {
         let config = Config::default();
         let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
 
-        find_project_dirs(&repo, &mut finders, &config, false)
-            .await
-            .unwrap();
+        find_project_dirs(
+            &repo,
+            temp_path,
+            &mut finders,
+            &config,
+            false,
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
 
         let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
         assert_eq!(projects.len(), 1);
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use changepacks_core::{changepacks_dir_name, normalize_path};
+
+use crate::find_current_git_repo;
+
+/// Root directory changepacks operates on
+///
+/// The nearest ancestor of `current_dir` (itself included) containing a `.changepacks` (or
+/// `CHANGEPACKS_DIR`) directory, so a repository can hold several independent release roots.
+/// A root selected with `--root` is passed in as `current_dir`. Inside git the search stops at the work tree,
+/// which is the root when no ancestor has a changepacks directory; without git the fallback is
+/// `current_dir` itself. The result is in [`normalize_path`] form.
+///
+/// # Errors
+/// Returns error if the git repository has no work tree (bare repository).
+pub fn find_project_root(current_dir: &Path) -> Result<PathBuf> {
+    let current_dir = &normalize_path(current_dir);
+    let changepacks_dir = changepacks_dir_name();
+    let nearest = |stop: Option<&Path>| {
+        current_dir
+            .ancestors()
+            .take_while(|dir| stop.is_none_or(|stop| dir.starts_with(stop)))
+            .find(|dir| dir.join(&changepacks_dir).is_dir())
+            .map(Path::to_path_buf)
+    };
+    if let Ok(repo) = find_current_git_repo(current_dir) {
+        let work_dir = normalize_path(
+            repo.work_dir()
+                .context("Failed to find current git repository")?,
+        );
+        return Ok(nearest(Some(&work_dir)).unwrap_or(work_dir));
+    }
+    Ok(nearest(None).unwrap_or_else(|| current_dir.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_project_root_git_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        std::process::Command::new("git")
+            .arg("init")
+            .current_dir(temp_path)
+            .output()
+            .unwrap();
+        std::fs::create_dir_all(temp_path.join("a/b")).unwrap();
+
+        let root = find_project_root(&temp_path.join("a/b")).unwrap();
+        assert_eq!(root, temp_path);
+    }
+
+    #[test]
+    fn test_find_project_root_without_git_uses_changepacks_ancestor() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        std::fs::create_dir_all(temp_path.join(".changepacks")).unwrap();
+        std::fs::create_dir_all(temp_path.join("a/b")).unwrap();
+
+        let root = find_project_root(&temp_path.join("a/b")).unwrap();
+        assert_eq!(root, temp_path);
+    }
+
+    #[test]
+    fn test_find_project_root_without_git_falls_back_to_current_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let root = find_project_root(&nested).unwrap();
+        assert_eq!(root, nested);
+    }
+
+    #[test]
+    fn test_find_project_root_git_repo_nearest_changepacks_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        std::process::Command::new("git")
+            .arg("init")
+            .current_dir(temp_path)
+            .output()
+            .unwrap();
+        std::fs::create_dir_all(temp_path.join(".changepacks")).unwrap();
+        std::fs::create_dir_all(temp_path.join("apps/web/.changepacks")).unwrap();
+        std::fs::create_dir_all(temp_path.join("apps/web/src")).unwrap();
+        std::fs::create_dir_all(temp_path.join("libs/core")).unwrap();
+
+        let root = find_project_root(&temp_path.join("apps/web/src")).unwrap();
+        assert_eq!(root, temp_path.join("apps/web"));
+        let root = find_project_root(&temp_path.join("libs/core")).unwrap();
+        assert_eq!(root, temp_path);
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use changepacks_core::path_key;
+use chrono::{DateTime, SecondsFormat, Utc};
+use tokio::process::Command;
+
+/// Commit time of the oldest change under `package_dir` that is not part of a release
+///
+/// The release is located as the first commit after `released_at` that touched
+/// `release_manifest` (the commit that recorded it); changes are the commits after it.
+/// When that commit cannot be found (e.g. the manifest was never committed), any commit
+/// after `released_at` counts. Both paths are relative to `repo_root`.
+///
+/// # Errors
+/// Returns error if `git` cannot be run or fails.
+pub async fn first_unreleased_change(
+    repo_root: &Path,
+    package_dir: &Path,
+    release_manifest: &Path,
+    released_at: &DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>> {
+    let since = format!(
+        "--since={}",
+        released_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    let release_commit = git_log(
+        repo_root,
+        &["--format=%H", &since, "--", &pathspec(release_manifest)],
+    )
+    .await?;
+    let range = release_commit.map_or(since, |commit| format!("{commit}..HEAD"));
+    let Some(timestamp) = git_log(
+        repo_root,
+        &["--format=%ct", &range, "--", &pathspec(package_dir)],
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+    Ok(DateTime::from_timestamp(timestamp.parse()?, 0))
+}
+
+fn pathspec(path: &Path) -> String {
+    if path.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        path_key(path)
+    }
+}
+
+/// First line of `git log --reverse` (the oldest matching commit)
+async fn git_log(repo_root: &Path, args: &[&str]) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .arg("log")
+        .arg("--reverse")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .await?;
+    if !output.status.success() {
+        bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str], date: &str) {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    fn commit(dir: &Path, file: &str, date: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, date).unwrap();
+        git(dir, &["add", "."], date);
+        git(dir, &["commit", "-m", file], date);
+    }
+
+    fn date(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().to_utc()
+    }
+
+    #[tokio::test]
+    async fn test_first_unreleased_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-b", "main"], "2020-01-01T00:00:00Z");
+        git(
+            dir,
+            &["config", "user.email", "test@test.com"],
+            "2020-01-01T00:00:00Z",
+        );
+        git(
+            dir,
+            &["config", "user.name", "Test"],
+            "2020-01-01T00:00:00Z",
+        );
+        commit(dir, "a/package.json", "2020-01-01T00:00:00Z");
+        commit(dir, "b/package.json", "2020-01-02T00:00:00Z");
+        commit(dir, ".changepacks/releases.json", "2020-02-01T00:00:00Z");
+        commit(dir, "a/index.js", "2020-03-01T00:00:00Z");
+        commit(dir, "a/lib.js", "2020-04-01T00:00:00Z");
+
+        let manifest = Path::new(".changepacks/releases.json");
+        let released_at = date("2020-01-31T00:00:00Z");
+        assert_eq!(
+            first_unreleased_change(dir, Path::new("a"), manifest, &released_at)
+                .await
+                .unwrap(),
+            Some(date("2020-03-01T00:00:00Z"))
+        );
+        assert_eq!(
+            first_unreleased_change(dir, Path::new("b"), manifest, &released_at)
+                .await
+                .unwrap(),
+            None
+        );
+
+        // No release commit after this date: fall back to commits after it
+        let released_at = date("2020-03-15T00:00:00Z");
+        assert_eq!(
+            first_unreleased_change(dir, Path::new(""), manifest, &released_at)
+                .await
+                .unwrap(),
+            Some(date("2020-04-01T00:00:00Z"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_first_unreleased_change_not_a_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(
+            first_unreleased_change(
+                temp_dir.path(),
+                Path::new("a"),
+                Path::new(".changepacks/releases.json"),
+                &Utc::now()
+            )
+            .await
+            .is_err()
+        );
+    }
+}
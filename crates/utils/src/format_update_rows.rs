@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use changepacks_core::UpdateType;
+use colored::Colorize;
+
+use crate::{display_update::color_by_severity, next_version_with_id};
+
+/// Format package lines with their version transitions in aligned columns
+///
+/// Each row is `(label, current version, update type, changepack count)`. Rows with an
+/// update get `current -> next` (the next version colored by bump severity) and the number
+/// of changepacks behind it; labels may contain color codes and are padded by visible width.
+/// Prerelease bumps use `prerelease_id` (see [`crate::next_version_with_id`]).
+///
+/// # Errors
+/// Returns error if a next version cannot be calculated.
+pub fn format_update_rows(
+    rows: &[(String, Option<&str>, Option<UpdateType>, usize)],
+    prerelease_id: Option<&str>,
+) -> Result<Vec<String>> {
+    let mut transitions = Vec::with_capacity(rows.len());
+    for (label, current_version, update_type, _) in rows {
+        transitions.push(match update_type {
+            Some(update_type) => Some((
+                current_version.unwrap_or("unknown"),
+                next_version_with_id(
+                    current_version.unwrap_or("0.0.0"),
+                    *update_type,
+                    prerelease_id,
+                )
+                .with_context(|| format!("Cannot bump {}", strip_ansi(label).trim()))?,
+                *update_type,
+            )),
+            None => None,
+        });
+    }
+    let updated = || rows.iter().zip(&transitions).filter(|(_, t)| t.is_some());
+    let label_width = updated()
+        .map(|((label, ..), _)| visible_width(label))
+        .max()
+        .unwrap_or(0);
+    let current_width = transitions
+        .iter()
+        .flatten()
+        .map(|(current, ..)| current.chars().count())
+        .max()
+        .unwrap_or(0);
+    let next_width = transitions
+        .iter()
+        .flatten()
+        .map(|(_, next, _)| next.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    Ok(rows
+        .iter()
+        .zip(transitions)
+        .map(|((label, _, _, count), transition)| {
+            let Some((current, next, update_type)) = transition else {
+                return label.clone();
+            };
+            let padding = " ".repeat(label_width - visible_width(label));
+            let line = format!(
+                "{label}{padding}  {current:<current_width$} -> {}",
+                color_by_severity(&next, update_type)
+            );
+            let next_padding = " ".repeat(next_width - next.chars().count());
+            match count {
+                0 => line,
+                1 => format!("{line}{next_padding}  {}", "1 changepack".bright_black()),
+                _ => format!(
+                    "{line}{next_padding}  {}",
+                    format!("{count} changepacks").bright_black()
+                ),
+            }
+        })
+        .collect())
+}
+
+/// Width of `text` as displayed, ignoring ANSI color sequences
+fn visible_width(text: &str) -> usize {
+    strip_ansi(text).chars().count()
+}
+
+/// Drop ANSI color sequences, leaving the visible text
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_update_rows_aligns_columns() {
+        let rows = [
+            (
+                "core".bold().to_string(),
+                Some("1.2.3"),
+                Some(UpdateType::Minor),
+                2,
+            ),
+            (
+                "cli-tools".to_string(),
+                Some("10.0.9"),
+                Some(UpdateType::Patch),
+                1,
+            ),
+            ("docs".to_string(), Some("0.1.0"), None, 0),
+            ("new".to_string(), None, Some(UpdateType::Major), 0),
+        ];
+        let lines: Vec<String> = format_update_rows(&rows, None)
+            .unwrap()
+            .iter()
+            .map(|line| strip_ansi(line))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "core       1.2.3   -> 1.3.0    2 changepacks",
+                "cli-tools  10.0.9  -> 10.0.10  1 changepack",
+                "docs",
+                "new        unknown -> 1.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_update_rows_colors_by_severity() {
+        let lines = format_update_rows(
+            &[(
+                "core".to_string(),
+                Some("1.0.0"),
+                Some(UpdateType::Major),
+                1,
+            )],
+            None,
+        )
+        .unwrap();
+        assert!(lines[0].contains(&"2.0.0".bright_red().bold().to_string()));
+    }
+
+    #[test]
+    fn test_format_update_rows_empty() {
+        assert!(format_update_rows(&[], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width(&"abc".bright_blue().bold().to_string()), 3);
+        assert_eq!(visible_width("héllo"), 5);
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::Result;
+use changepacks_core::{WebhookEvent, WebhookFormat, WebhookPayload, WebhookStatus};
+use serde_json::json;
+
+/// Discord rejects messages longer than this
+const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+/// Notes shown per package in chat announcements
+const HIGHLIGHTS_PER_PACKAGE: usize = 3;
+
+/// Render the request body for a webhook in the given format
+///
+/// `Json` sends the payload as-is; `Slack` and `Discord` send a chat message listing the
+/// released packages, their versions and descriptions, and the first line of each note.
+///
+/// # Errors
+/// Returns error if the payload cannot be serialized.
+pub fn format_webhook_body(format: WebhookFormat, payload: &WebhookPayload) -> Result<Vec<u8>> {
+    let body = match format {
+        WebhookFormat::Json => return Ok(serde_json::to_vec(payload)?),
+        WebhookFormat::Slack => json!({ "text": announcement(payload, "*") }),
+        WebhookFormat::Discord => {
+            let mut content = announcement(payload, "**");
+            if content.chars().count() > DISCORD_MESSAGE_LIMIT {
+                content = content.chars().take(DISCORD_MESSAGE_LIMIT - 1).collect();
+                content.push('…');
+            }
+            json!({ "content": content })
+        }
+    };
+    Ok(serde_json::to_vec(&body)?)
+}
+
+/// Markdown summary of the payload; `bold` is the platform's bold marker
+fn announcement(payload: &WebhookPayload, bold: &str) -> String {
+    let succeeded = payload
+        .packages()
+        .iter()
+        .filter(|pkg| pkg.status() != WebhookStatus::Failed)
+        .count();
+    let verb = match payload.event() {
+        WebhookEvent::Update => "Released",
+        WebhookEvent::Publish => "Published",
+    };
+    let mut out = format!(
+        "{bold}{verb} {succeeded} package{}{bold}",
+        if succeeded == 1 { "" } else { "s" }
+    );
+    for pkg in payload.packages() {
+        let name = pkg
+            .name()
+            .map_or_else(|| pkg.path().display().to_string(), str::to_string);
+        out.push_str(&format!("\n• {name}"));
+        if let Some(version) = pkg.version() {
+            out.push_str(&format!(" v{version}"));
+        }
+        if pkg.status() == WebhookStatus::Failed {
+            out.push_str(" (failed)");
+        }
+        if let Some(description) = pkg.metadata().description() {
+            out.push_str(&format!(" — {description}"));
+        }
+        for note in pkg.notes().iter().take(HIGHLIGHTS_PER_PACKAGE) {
+            out.push_str(&format!(
+                "\n    – {}",
+                note.lines().next().unwrap_or_default()
+            ));
+        }
+        if pkg.notes().len() > HIGHLIGHTS_PER_PACKAGE {
+            out.push_str(&format!(
+                "\n    – and {} more",
+                pkg.notes().len() - HIGHLIGHTS_PER_PACKAGE
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::{PackageMetadata, WebhookPackage};
+    use std::path::PathBuf;
+
+    fn payload(event: WebhookEvent, notes: Vec<String>) -> WebhookPayload {
+        WebhookPayload::new(
+            event,
+            vec![
+                WebhookPackage::new(
+                    Some("foo".to_string()),
+                    PathBuf::from("packages/foo/package.json"),
+                    Some("1.3.0".to_string()),
+                    notes,
+                    if event == WebhookEvent::Update {
+                        WebhookStatus::Updated
+                    } else {
+                        WebhookStatus::Published
+                    },
+                ),
+                WebhookPackage::new(
+                    None,
+                    PathBuf::from("crates/bar/Cargo.toml"),
+                    Some("0.2.0".to_string()),
+                    vec![],
+                    if event == WebhookEvent::Update {
+                        WebhookStatus::Updated
+                    } else {
+                        WebhookStatus::Failed
+                    },
+                ),
+            ],
+        )
+    }
+
+    fn body(format: WebhookFormat, payload: &WebhookPayload) -> serde_json::Value {
+        serde_json::from_slice(&format_webhook_body(format, payload).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_format_webhook_body_json() {
+        let payload = payload(WebhookEvent::Update, vec!["Add streaming".to_string()]);
+        assert_eq!(
+            body(WebhookFormat::Json, &payload),
+            serde_json::to_value(&payload).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_webhook_body_slack() {
+        let payload = payload(
+            WebhookEvent::Update,
+            vec!["Add streaming\n\nLong description".to_string()],
+        );
+        assert_eq!(
+            body(WebhookFormat::Slack, &payload)["text"],
+            "*Released 2 packages*\n• foo v1.3.0\n    – Add streaming\n• crates/bar/Cargo.toml v0.2.0"
+        );
+    }
+
+    #[test]
+    fn test_format_webhook_body_slack_description() {
+        let payload = payload(WebhookEvent::Update, vec![]).with_package_metadata(|path| {
+            PackageMetadata::new(
+                path.starts_with("packages")
+                    .then(|| "Streaming parser".to_string()),
+                None,
+                None,
+            )
+        });
+        assert_eq!(
+            body(WebhookFormat::Slack, &payload)["text"],
+            "*Released 2 packages*\n• foo v1.3.0 — Streaming parser\n• crates/bar/Cargo.toml v0.2.0"
+        );
+    }
+
+    #[test]
+    fn test_format_webhook_body_discord_publish() {
+        let notes = (1..=5).map(|i| format!("Note {i}")).collect();
+        let payload = payload(WebhookEvent::Publish, notes);
+        assert_eq!(
+            body(WebhookFormat::Discord, &payload)["content"],
+            "**Published 1 package**\n• foo v1.3.0\n    – Note 1\n    – Note 2\n    – Note 3\n    – and 2 more\n• crates/bar/Cargo.toml v0.2.0 (failed)"
+        );
+    }
+
+    #[test]
+    fn test_format_webhook_body_discord_truncates() {
+        let payload = payload(WebhookEvent::Update, vec!["x".repeat(3000)]);
+        let content = body(WebhookFormat::Discord, &payload)["content"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(content.chars().count(), DISCORD_MESSAGE_LIMIT);
+        assert!(content.ends_with('…'));
+    }
+}
@@ -4,12 +4,13 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use changepacks_core::{ChangePackResult, ChangePackResultLog, Project, UpdateType};
 
-use crate::{get_relative_path, next_version};
+use crate::{get_relative_path, next_version_with_id};
 
-/// Generate a changepack result map from projects and update results
+/// Generate a changepack result map from projects and update results, bumping prereleases to
+/// `prerelease_id`
 ///
 /// # Errors
 /// Returns error if relative path calculation or version calculation fails.
@@ -24,6 +25,7 @@ pub fn gen_changepack_result_map<S: BuildHasher>(
     projects: &[&Project],
     repo_root_path: &Path,
     update_result: &mut HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>), S>,
+    prerelease_id: Option<&str>,
 ) -> Result<BTreeMap<PathBuf, ChangePackResult>> {
     let mut map = BTreeMap::<PathBuf, ChangePackResult>::new();
     for project in projects {
@@ -33,12 +35,17 @@ pub fn gen_changepack_result_map<S: BuildHasher>(
         let changed = project.is_changed();
         let result = match update_result.remove(&key) {
             Some((update_type, notes)) => {
-                let next = next_version(project.version().unwrap_or("0.0.0"), update_type)?;
+                let next = next_version_with_id(
+                    project.version().unwrap_or("0.0.0"),
+                    update_type,
+                    prerelease_id,
+                )
+                .with_context(|| format!("Cannot bump {}", key.display()))?;
                 ChangePackResult::new(notes, version, Some(next), name, changed, key.clone())
             }
             None => ChangePackResult::new(vec![], version, None, name, changed, key.clone()),
         };
-        map.insert(key.clone(), result);
+        map.insert(key.clone(), result.with_metadata(project.metadata()));
     }
     Ok(map)
 }
@@ -117,7 +124,8 @@ mod tests {
         );
 
         let projects = vec![&project];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         assert_eq!(result.len(), 1);
         let change_result = result.get(&PathBuf::from("project1/package.json")).unwrap();
@@ -181,7 +189,8 @@ mod tests {
 
         let mut update_result = HashMap::new();
         let projects = vec![&project];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         assert_eq!(result.len(), 1);
         let change_result = result.get(&PathBuf::from("project2/package.json")).unwrap();
@@ -276,7 +285,8 @@ mod tests {
         // project2 has no update result
 
         let projects = vec![&project1, &project2];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         assert_eq!(result.len(), 2);
 
@@ -359,7 +369,8 @@ mod tests {
         );
 
         let projects = vec![&project];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         let change_result = result.get(&PathBuf::from("project3/package.json")).unwrap();
         let json = serde_json::to_value(change_result).unwrap();
@@ -416,7 +427,8 @@ mod tests {
         );
 
         let projects = vec![&project];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         let change_result = result.get(&PathBuf::from("project4/package.json")).unwrap();
         let json = serde_json::to_value(change_result).unwrap();
@@ -476,7 +488,8 @@ mod tests {
         );
 
         let projects = vec![&project];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         let change_result = result.get(&PathBuf::from("project5/package.json")).unwrap();
         let json = serde_json::to_value(change_result).unwrap();
@@ -503,7 +516,8 @@ mod tests {
 
         let mut update_result = HashMap::new();
         let projects: Vec<&Project> = vec![];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         assert!(result.is_empty());
 
@@ -548,7 +562,8 @@ mod tests {
         );
 
         let projects = vec![&project];
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result).unwrap();
+        let result =
+            gen_changepack_result_map(&projects, repo_root, &mut update_result, None).unwrap();
 
         assert_eq!(result.len(), 1);
         let change_result = result.get(&PathBuf::from("projectA/package.json")).unwrap();
@@ -603,7 +618,7 @@ mod tests {
 
         let projects = vec![&project];
         // Empty version "" with an update triggers next_version("", Patch) which should fail
-        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result);
+        let result = gen_changepack_result_map(&projects, repo_root, &mut update_result, None);
         assert!(result.is_err());
 
         temp_dir.close().unwrap();
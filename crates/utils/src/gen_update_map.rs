@@ -1,59 +1,185 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
     hash::BuildHasher,
     path::{Path, PathBuf},
 };
 
-use anyhow::Result;
-use changepacks_core::{ChangePackLog, ChangePackResultLog, Config, Project, UpdateType};
+use anyhow::{Result, bail};
+use changepacks_core::{
+    ChangePackLog, ChangePackResultLog, Config, DiffRefs, Project, RELEASE_MANIFEST_FILE,
+    UpdateType, path_key,
+};
 use glob::Pattern;
-use tokio::fs::{read_dir, read_to_string};
 
-use crate::get_changepacks_dir;
+use crate::{
+    bridge_links, get_changepacks_dir, next_version_with_id, read_changepack_logs,
+    read_commit_changepacks,
+};
 
-/// Generate update map from changepack logs
+/// Generate update map from changepack logs and `Changepack:` commit trailers, with the
+/// prerelease identifier (`preid`) the pending prerelease changepacks name
+///
+/// Trailers may name a project instead of its manifest path; names are looked up in `projects`.
+/// Pass the identifier on to [`crate::next_version_with_id`] and `Project::update_version` so
+/// every version computed from the map agrees.
 ///
 /// # Errors
-/// Returns error if reading changepacks directory or parsing JSON fails.
+/// Returns error if reading changepacks directory or parsing JSON fails, or if pending
+/// changepacks name different prerelease identifiers.
 pub async fn gen_update_map(
     current_dir: &Path,
     config: &Config,
-) -> Result<HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>> {
+    projects: &[&Project],
+    refs: &DiffRefs,
+) -> Result<(
+    HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    Option<String>,
+)> {
     let mut update_map = HashMap::<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>::new();
     let changepacks_dir = get_changepacks_dir(current_dir)?;
-
-    let mut entries = read_dir(&changepacks_dir).await?;
-    while let Some(file) = entries.next_entry().await? {
-        let file_name = file.file_name();
-        let file_name = file_name.to_string_lossy();
-        if file_name.as_ref() == "config.json"
-            || !Path::new(file_name.as_ref())
-                .extension()
-                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    let commit_logs = read_commit_changepacks(
+        current_dir,
+        &config.base_branch,
+        &changepacks_dir.join(RELEASE_MANIFEST_FILE),
+        refs,
+    )
+    .await
+    .into_iter()
+    .map(|log| {
+        let changes = log
+            .changes()
+            .iter()
+            .map(|(target, update_type)| {
+                let path = projects
+                    .iter()
+                    .find(|project| project.name() == target.to_str())
+                    .map_or_else(
+                        || target.clone(),
+                        |project| project.relative_path().to_path_buf(),
+                    );
+                (path, *update_type)
+            })
+            .collect();
+        ChangePackLog::new(changes, log.note().to_string())
+    });
+    let file_logs = read_changepack_logs(&changepacks_dir)
+        .await?
+        .into_iter()
+        .map(|(_, log)| log);
+    let mut preids = BTreeSet::new();
+    for file_json in file_logs.chain(commit_logs) {
+        if let Some(preid) = file_json.preid()
+            && file_json
+                .changes()
+                .values()
+                .any(|update_type| *update_type == UpdateType::Prerelease)
         {
-            continue;
+            preids.insert(preid.to_string());
         }
-        let file_json = read_to_string(file.path()).await?;
-        let file_json: ChangePackLog = serde_json::from_str(&file_json)?;
         for (project_path, update_type) in file_json.changes() {
             let ret = update_map
                 .entry(project_path.clone())
                 .or_insert((*update_type, vec![]));
-            ret.1.push(ChangePackResultLog::new(
-                *update_type,
-                file_json.note().to_string(),
-            ));
+            // The same note merged in from several branches is listed once
+            if !ret
+                .1
+                .iter()
+                .any(|log| log.note().trim() == file_json.note().trim())
+            {
+                ret.1.push(
+                    ChangePackResultLog::new(*update_type, file_json.note().to_string())
+                        .with_fields(file_json.fields().clone()),
+                );
+            }
             if ret.0 > *update_type {
                 ret.0 = *update_type;
             }
         }
     }
 
+    if preids.len() > 1 {
+        bail!(
+            "Pending changepacks name different prerelease identifiers: {}",
+            preids.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+    let prerelease_id = preids.pop_first();
+
     // Apply updateOn rules: if any updated package matches a trigger pattern,
     // add dependent packages as PATCH updates
     apply_update_on_rules(&mut update_map, config);
 
-    Ok(update_map)
+    // Apply bridges: a bridge package takes the bump of the crate it wraps
+    apply_bridges(
+        &mut update_map,
+        &bridge_links(projects, config).await,
+        projects,
+        prerelease_id.as_deref(),
+    );
+
+    // Apply fixed groups: every member takes the strongest update in its group
+    apply_fixed_groups(&mut update_map, config);
+
+    Ok((update_map, prerelease_id))
+}
+
+fn apply_fixed_groups(
+    update_map: &mut HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    config: &Config,
+) {
+    for group in &config.fixed {
+        let Some(update_type) = group
+            .iter()
+            .filter_map(|member| update_map.get(Path::new(member)).map(|entry| entry.0))
+            .min()
+        else {
+            continue;
+        };
+        for member in group {
+            let entry = update_map.entry(PathBuf::from(member)).or_insert_with(|| {
+                (
+                    update_type,
+                    vec![ChangePackResultLog::new(
+                        update_type,
+                        format!("Auto-update with fixed group: {}", group.join(", ")),
+                    )],
+                )
+            });
+            entry.0 = update_type;
+        }
+    }
+}
+
+fn apply_bridges(
+    update_map: &mut HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>)>,
+    links: &[(PathBuf, PathBuf)],
+    projects: &[&Project],
+    prerelease_id: Option<&str>,
+) {
+    for (krate, bridge) in links {
+        let Some(update_type) = update_map.get(krate).map(|entry| entry.0) else {
+            continue;
+        };
+        let project = projects
+            .iter()
+            .find(|project| project.relative_path() == krate);
+        let name = project
+            .and_then(|project| project.name())
+            .map_or_else(|| path_key(krate), str::to_string);
+        let note = project
+            .and_then(|project| {
+                next_version_with_id(project.version()?, update_type, prerelease_id).ok()
+            })
+            .map_or_else(
+                || format!("Updated bridged crate `{name}`"),
+                |version| format!("Updated bridged crate `{name}` to {version}"),
+            );
+        let entry = update_map
+            .entry(bridge.clone())
+            .or_insert_with(|| (update_type, Vec::new()));
+        entry.0 = entry.0.min(update_type);
+        entry.1.push(ChangePackResultLog::new(update_type, note));
+    }
 }
 
 fn apply_update_on_rules(
@@ -92,8 +218,10 @@ fn apply_update_on_rules(
     }
 }
 
-/// Apply reverse dependency updates: if package A depends on package B (via workspace:*),
-/// and B is being updated, then A should also be updated as PATCH.
+/// Apply reverse dependency updates: if package A depends on package B (e.g., via workspace:*),
+/// and B is being updated, then A should also be updated as PATCH (or as a prerelease when B
+/// is pre-released). A's note names B and the version B is bumped to, using `prerelease_id`
+/// for prerelease bumps.
 ///
 /// Excluded from coverage: traverses the full project graph using
 /// `project.path().strip_prefix(repo_root_path)` against a live workspace
@@ -104,14 +232,19 @@ pub fn apply_reverse_dependencies<S: BuildHasher>(
     update_map: &mut HashMap<PathBuf, (UpdateType, Vec<ChangePackResultLog>), S>,
     projects: &[&Project],
     repo_root_path: &Path,
+    prerelease_id: Option<&str>,
 ) {
     // Build a map from package name to its relative file path (e.g., "crates/core/Cargo.toml")
-    let mut name_to_path: HashMap<String, PathBuf> = HashMap::new();
+    // and its current version
+    let mut name_to_path: HashMap<String, (PathBuf, Option<&str>)> = HashMap::new();
     for project in projects {
         if let Some(name) = project.name()
             && let Ok(rel_path) = project.path().strip_prefix(repo_root_path)
         {
-            name_to_path.insert(name.to_string(), rel_path.to_path_buf());
+            name_to_path.insert(
+                name.to_string(),
+                (rel_path.to_path_buf(), project.version()),
+            );
         }
     }
 
@@ -134,54 +267,55 @@ pub fn apply_reverse_dependencies<S: BuildHasher>(
         }
     }
 
-    // Find all packages that need to be updated due to dependencies
-    let mut packages_to_add: Vec<(PathBuf, String)> = Vec::new();
-    let mut processed: HashSet<PathBuf> = HashSet::new();
-
     // Initial set of updated package names
-    let updated_names: HashSet<String> = update_map
-        .keys()
-        .filter_map(|path| {
-            // Find the package name for this path
-            name_to_path
-                .iter()
-                .find_map(|(name, p)| if p == path { Some(name.clone()) } else { None })
-        })
+    let updated_names: Vec<String> = name_to_path
+        .iter()
+        .filter(|(_, (path, _))| update_map.contains_key(path))
+        .map(|(name, _)| name.clone())
         .collect();
 
-    // Process reverse dependencies transitively
-    let mut to_process: Vec<String> = updated_names.into_iter().collect();
+    // Process reverse dependencies transitively, so each dependent's note can name the
+    // version its dependency is bumped to
+    let mut to_process = updated_names;
     while let Some(pkg_name) = to_process.pop() {
-        if let Some(dependents) = reverse_deps.get(&pkg_name) {
-            for (dep_path, dep_name) in dependents {
-                if !processed.contains(dep_path) && !update_map.contains_key(dep_path) {
-                    processed.insert(dep_path.clone());
-                    packages_to_add.push((dep_path.clone(), pkg_name.clone()));
-                    to_process.push(dep_name.clone());
-                }
+        let Some(dependents) = reverse_deps.get(&pkg_name) else {
+            continue;
+        };
+        let update_type = name_to_path
+            .get(&pkg_name)
+            .and_then(|(path, _)| Some(update_map.get(path)?.0));
+        let new_version = name_to_path.get(&pkg_name).and_then(|(_, version)| {
+            next_version_with_id((*version)?, update_type?, prerelease_id).ok()
+        });
+        // Dependents of a pre-release are pre-released with it
+        let dependent_type = match update_type {
+            Some(UpdateType::Prerelease) => UpdateType::Prerelease,
+            _ => UpdateType::Patch,
+        };
+        let note = new_version.map_or_else(
+            || format!("Updated dependency `{pkg_name}`"),
+            |version| format!("Updated dependency `{pkg_name}` to {version}"),
+        );
+        for (dep_path, dep_name) in dependents {
+            if !update_map.contains_key(dep_path) {
+                update_map.insert(
+                    dep_path.clone(),
+                    (
+                        dependent_type,
+                        vec![ChangePackResultLog::new(dependent_type, note.clone())],
+                    ),
+                );
+                to_process.push(dep_name.clone());
             }
         }
     }
-
-    // Add the dependent packages to update_map
-    for (path, dependency_name) in packages_to_add {
-        update_map.entry(path).or_insert_with(|| {
-            (
-                UpdateType::Patch,
-                vec![ChangePackResultLog::new(
-                    UpdateType::Patch,
-                    format!("Auto-update: depends on '{dependency_name}' via workspace:*"),
-                )],
-            )
-        });
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use changepacks_core::{Config, Package};
+    use changepacks_core::{ChangePackLog, Config, Package};
     use changepacks_node::package::NodePackage;
     use tempfile::TempDir;
     use tokio::fs;
@@ -202,6 +336,230 @@ mod tests {
         Project::Package(Box::new(package))
     }
 
+    #[tokio::test]
+    async fn test_gen_update_map_no_changepacks_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        std::process::Command::new("git")
+            .arg("init")
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+
+        let (update_map, _) = gen_update_map(
+            temp_dir.path(),
+            &Config::default(),
+            &[],
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
+        assert!(update_map.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gen_update_map_dedupes_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let changepacks_dir = temp_dir.path().join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).await.unwrap();
+        for (name, update_type, note) in [
+            ("a", UpdateType::Patch, "Fix crash"),
+            ("b", UpdateType::Minor, "Fix crash\n"),
+            ("c", UpdateType::Patch, "Add docs"),
+        ] {
+            fs::write(
+                changepacks_dir.join(format!("changepack_log_{name}.json")),
+                serde_json::to_string(&ChangePackLog::new(
+                    HashMap::from([(PathBuf::from("core/package.json"), update_type)]),
+                    note.to_string(),
+                ))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let (update_map, _) = gen_update_map(
+            temp_dir.path(),
+            &Config::default(),
+            &[],
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
+        let (update_type, logs) = &update_map[&PathBuf::from("core/package.json")];
+        assert_eq!(*update_type, UpdateType::Minor);
+        let notes: Vec<&str> = logs.iter().map(ChangePackResultLog::note).collect();
+        assert_eq!(notes.len(), 2);
+        assert!(notes.contains(&"Add docs"));
+    }
+
+    #[tokio::test]
+    async fn test_gen_update_map_returns_preid() {
+        let temp_dir = TempDir::new().unwrap();
+        let changepacks_dir = temp_dir.path().join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).await.unwrap();
+        fs::write(
+            changepacks_dir.join("changepack_log_beta.json"),
+            serde_json::to_string(
+                &ChangePackLog::new(
+                    HashMap::from([(PathBuf::from("package.json"), UpdateType::Prerelease)]),
+                    "Try it".to_string(),
+                )
+                .with_preid(Some("beta".to_string())),
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let (update_map, prerelease_id) = gen_update_map(
+            temp_dir.path(),
+            &Config::default(),
+            &[],
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(update_map.len(), 1);
+        assert_eq!(prerelease_id.as_deref(), Some("beta"));
+    }
+
+    #[tokio::test]
+    async fn test_gen_update_map_conflicting_preids() {
+        let temp_dir = TempDir::new().unwrap();
+        let changepacks_dir = temp_dir.path().join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).await.unwrap();
+        for preid in ["beta", "rc"] {
+            fs::write(
+                changepacks_dir.join(format!("changepack_log_{preid}.json")),
+                serde_json::to_string(
+                    &ChangePackLog::new(
+                        HashMap::from([(PathBuf::from("package.json"), UpdateType::Prerelease)]),
+                        "Try it".to_string(),
+                    )
+                    .with_preid(Some(preid.to_string())),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let err = gen_update_map(
+            temp_dir.path(),
+            &Config::default(),
+            &[],
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Pending changepacks name different prerelease identifiers: beta, rc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gen_update_map_bridges() {
+        let temp_dir = TempDir::new().unwrap();
+        let changepacks_dir = temp_dir.path().join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).await.unwrap();
+        fs::write(
+            changepacks_dir.join("changepack_log_a.json"),
+            serde_json::to_string(&ChangePackLog::new(
+                HashMap::from([
+                    (PathBuf::from("core/package.json"), UpdateType::Minor),
+                    (PathBuf::from("python/pyproject.toml"), UpdateType::Patch),
+                ]),
+                "Add streaming".to_string(),
+            ))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+        let core = create_project("core", vec![]);
+        let config = Config {
+            bridges: HashMap::from([(
+                "core/package.json".to_string(),
+                vec![
+                    "node/package.json".to_string(),
+                    "python/pyproject.toml".to_string(),
+                ],
+            )]),
+            ..Config::default()
+        };
+
+        let (update_map, _) =
+            gen_update_map(temp_dir.path(), &config, &[&core], &DiffRefs::default())
+                .await
+                .unwrap();
+        let (update_type, logs) = &update_map[&PathBuf::from("node/package.json")];
+        assert_eq!(*update_type, UpdateType::Minor);
+        assert_eq!(logs[0].note(), "Updated bridged crate `core` to 1.1.0");
+        // A bridge with its own patch changepack still takes the crate's minor bump
+        let (update_type, logs) = &update_map[&PathBuf::from("python/pyproject.toml")];
+        assert_eq!(*update_type, UpdateType::Minor);
+        assert_eq!(logs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gen_update_map_commit_trailers() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(temp_path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{output:?}");
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "Initial commit"]);
+        git(&["checkout", "-q", "-b", "feature"]);
+        git(&[
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "Add parser\n\nChangepack: core=minor\nChangepack: cli/package.json=patch",
+        ]);
+        let changepacks_dir = temp_path.join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).await.unwrap();
+        fs::write(
+            changepacks_dir.join("changepack_log_a.json"),
+            serde_json::to_string(&ChangePackLog::new(
+                HashMap::from([(PathBuf::from("core/package.json"), UpdateType::Patch)]),
+                "Fix core".to_string(),
+            ))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let core = create_project("core", vec![]);
+        let (update_map, _) = gen_update_map(
+            temp_path,
+            &Config::default(),
+            &[&core],
+            &DiffRefs::default(),
+        )
+        .await
+        .unwrap();
+
+        let (update_type, logs) = &update_map[&PathBuf::from("core/package.json")];
+        assert_eq!(*update_type, UpdateType::Minor);
+        let mut notes: Vec<&str> = logs.iter().map(ChangePackResultLog::note).collect();
+        notes.sort_unstable();
+        assert_eq!(notes, vec!["Add parser", "Fix core"]);
+        assert_eq!(
+            update_map[&PathBuf::from("cli/package.json")].0,
+            UpdateType::Patch
+        );
+    }
+
     #[tokio::test]
     async fn test_gen_update_map() {
         let temp_dir = TempDir::new().unwrap();
@@ -219,7 +577,13 @@ mod tests {
         fs::create_dir_all(&changepacks_dir).await.unwrap();
 
         {
-            assert!(gen_update_map(temp_path, &config).await.unwrap().is_empty());
+            assert!(
+                gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                    .await
+                    .unwrap()
+                    .0
+                    .is_empty()
+            );
         }
         {
             fs::write(
@@ -228,13 +592,25 @@ mod tests {
             )
             .await
             .unwrap();
-            assert!(gen_update_map(temp_path, &config).await.unwrap().is_empty());
+            assert!(
+                gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                    .await
+                    .unwrap()
+                    .0
+                    .is_empty()
+            );
         }
         {
             fs::write(changepacks_dir.join("wrong.file"), "{}")
                 .await
                 .unwrap();
-            assert!(gen_update_map(temp_path, &config).await.unwrap().is_empty());
+            assert!(
+                gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                    .await
+                    .unwrap()
+                    .0
+                    .is_empty()
+            );
         }
         {
             let mut map = HashMap::new();
@@ -247,14 +623,18 @@ mod tests {
             )
             .await
             .unwrap();
-            let update_map = gen_update_map(temp_path, &config).await.unwrap();
+            let (update_map, _) = gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                .await
+                .unwrap();
             assert!(update_map.len() == 1);
             assert!(update_map.contains_key(&temp_path.join("package")));
             assert!(update_map[&temp_path.join("package")].0 == UpdateType::Patch);
         }
 
         {
-            let update_map = gen_update_map(temp_path, &config).await.unwrap();
+            let (update_map, _) = gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                .await
+                .unwrap();
             assert!(update_map.len() == 1);
 
             let mut map = HashMap::new();
@@ -267,7 +647,9 @@ mod tests {
             )
             .await
             .unwrap();
-            let update_map = gen_update_map(temp_path, &config).await.unwrap();
+            let (update_map, _) = gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                .await
+                .unwrap();
             assert!(update_map.len() == 1);
             assert!(update_map.contains_key(&temp_path.join("package")));
             // overwrite the previous update type
@@ -284,7 +666,9 @@ mod tests {
             )
             .await
             .unwrap();
-            let update_map = gen_update_map(temp_path, &config).await.unwrap();
+            let (update_map, _) = gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                .await
+                .unwrap();
             assert!(update_map.len() == 2);
             assert!(update_map.contains_key(&temp_path.join("package2")));
             assert!(update_map[&temp_path.join("package2")].0 == UpdateType::Major);
@@ -300,7 +684,9 @@ mod tests {
             )
             .await
             .unwrap();
-            let update_map = gen_update_map(temp_path, &config).await.unwrap();
+            let (update_map, _) = gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+                .await
+                .unwrap();
             assert!(update_map.len() == 2);
             assert!(update_map.contains_key(&temp_path.join("package2")));
             // remain
@@ -309,6 +695,33 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[test]
+    fn test_apply_fixed_groups() {
+        let mut update_map = HashMap::new();
+        update_map.insert(PathBuf::from("a/package.json"), (UpdateType::Patch, vec![]));
+        update_map.insert(PathBuf::from("b/package.json"), (UpdateType::Minor, vec![]));
+        let config = Config {
+            fixed: vec![
+                vec![
+                    "a/package.json".to_string(),
+                    "b/package.json".to_string(),
+                    "c/package.json".to_string(),
+                ],
+                vec!["d/package.json".to_string()],
+            ],
+            ..Default::default()
+        };
+
+        apply_fixed_groups(&mut update_map, &config);
+
+        assert_eq!(update_map.len(), 3);
+        for member in ["a/package.json", "b/package.json", "c/package.json"] {
+            assert_eq!(update_map[&PathBuf::from(member)].0, UpdateType::Minor);
+        }
+        assert_eq!(update_map[&PathBuf::from("c/package.json")].1.len(), 1);
+        assert!(!update_map.contains_key(&PathBuf::from("d/package.json")));
+    }
+
     #[tokio::test]
     async fn test_update_on_rules() {
         let temp_dir = TempDir::new().unwrap();
@@ -342,13 +755,15 @@ mod tests {
         let changepack_log = ChangePackLog::new(map, "Update core".to_string());
 
         fs::write(
-            changepacks_dir.join("changepack_log.json"),
+            changepacks_dir.join("changepack_log_rules.json"),
             serde_json::to_string(&changepack_log).unwrap(),
         )
         .await
         .unwrap();
 
-        let update_map = gen_update_map(temp_path, &config).await.unwrap();
+        let (update_map, _) = gen_update_map(temp_path, &config, &[], &DiffRefs::default())
+            .await
+            .unwrap();
 
         // Should have 3 entries: crates/core (Minor), bridge/node (Patch), bridge/python (Patch)
         assert_eq!(update_map.len(), 3);
@@ -396,7 +811,7 @@ mod tests {
             ),
         );
 
-        apply_reverse_dependencies(&mut update_map, &projects, repo_root);
+        apply_reverse_dependencies(&mut update_map, &projects, repo_root, None);
 
         // cli should be added as PATCH update
         assert_eq!(update_map.len(), 2);
@@ -407,6 +822,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_apply_reverse_dependencies_prerelease() {
+        let core = create_project("core", vec![]);
+        let cli = create_project("cli", vec!["core"]);
+        let projects: Vec<&Project> = vec![&core, &cli];
+
+        let mut update_map = HashMap::from([(
+            PathBuf::from("core/package.json"),
+            (
+                UpdateType::Prerelease,
+                vec![ChangePackResultLog::new(
+                    UpdateType::Prerelease,
+                    "Try core".to_string(),
+                )],
+            ),
+        )]);
+        apply_reverse_dependencies(&mut update_map, &projects, Path::new("/test"), Some("beta"));
+
+        let (update_type, logs) = &update_map[&PathBuf::from("cli/package.json")];
+        assert_eq!(*update_type, UpdateType::Prerelease);
+        assert_eq!(logs[0].update_type(), UpdateType::Prerelease);
+        assert_eq!(logs[0].note(), "Updated dependency `core` to 1.0.1-beta.0");
+    }
+
     #[test]
     fn test_apply_reverse_dependencies_transitive() {
         // Setup: core -> utils -> cli (cli depends on utils, utils depends on core)
@@ -430,7 +869,7 @@ mod tests {
             ),
         );
 
-        apply_reverse_dependencies(&mut update_map, &projects, repo_root);
+        apply_reverse_dependencies(&mut update_map, &projects, repo_root, None);
 
         // Both utils and cli should be added as PATCH updates (transitive)
         assert_eq!(update_map.len(), 3);
@@ -444,6 +883,14 @@ mod tests {
             update_map[&PathBuf::from("cli/package.json")].0,
             UpdateType::Patch
         );
+        assert_eq!(
+            update_map[&PathBuf::from("utils/package.json")].1[0].note(),
+            "Updated dependency `core` to 1.1.0"
+        );
+        assert_eq!(
+            update_map[&PathBuf::from("cli/package.json")].1[0].note(),
+            "Updated dependency `utils` to 1.0.1"
+        );
     }
 
     #[test]
@@ -468,7 +915,7 @@ mod tests {
             ),
         );
 
-        apply_reverse_dependencies(&mut update_map, &projects, repo_root);
+        apply_reverse_dependencies(&mut update_map, &projects, repo_root, None);
 
         // utils should NOT be added (no dependency on core)
         assert_eq!(update_map.len(), 1);
@@ -507,7 +954,7 @@ mod tests {
             ),
         );
 
-        apply_reverse_dependencies(&mut update_map, &projects, repo_root);
+        apply_reverse_dependencies(&mut update_map, &projects, repo_root, None);
 
         // cli should remain Major (not overwritten to Patch)
         assert_eq!(update_map.len(), 2);
@@ -542,7 +989,7 @@ mod tests {
             ),
         );
 
-        apply_reverse_dependencies(&mut update_map, &projects, repo_root);
+        apply_reverse_dependencies(&mut update_map, &projects, repo_root, None);
 
         // All packages should be updated
         assert_eq!(update_map.len(), 4);
@@ -571,7 +1018,7 @@ mod tests {
             ),
         );
 
-        apply_reverse_dependencies(&mut update_map, &projects, repo_root);
+        apply_reverse_dependencies(&mut update_map, &projects, repo_root, None);
 
         // No changes, missing dependency is ignored
         assert_eq!(update_map.len(), 1);
@@ -1,35 +1,66 @@
 use std::path::Path;
 
-use anyhow::{Context, Result};
-use changepacks_core::Config;
-use tokio::fs::read_to_string;
-
-use crate::get_changepacks_dir;
-
-/// Get the changepacks configuration from .changepacks/config.json
-/// Returns default config if the file doesn't exist or is empty
+use anyhow::{Result, bail};
+use changepacks_core::{Config, ConfigFile};
+use serde_json::Value;
+
+use crate::{
+    get_config_dir, merge_config_values,
+    resolve_extends::{is_remote, resolve_extends},
+};
+
+/// Config key allowing the `hooks` of a remote `extends` base to run
+const TRUST_HOOKS_KEY: &str = "extendsTrustHooks";
+
+/// Get the changepacks configuration from `.changepacks/config.json`, `config.toml` or
+/// `config.yaml`
+/// Returns default config if there is no config file or it is empty.
+/// Settings from an `extends` base config apply unless overridden locally.
 ///
 /// # Errors
-/// Returns error if reading or parsing the config.json file fails.
+/// Returns error if several config files exist, reading or parsing the config file fails, or
+/// a remote `extends` base sets hooks without `extendsTrustHooks` in the local config.
 pub async fn get_changepacks_config(current_dir: &Path) -> Result<Config> {
-    let changepacks_dir = get_changepacks_dir(current_dir)?;
-    let config_file = changepacks_dir.join("config.json");
-
-    if !config_file.exists() {
+    let config_dir = get_config_dir(current_dir)?;
+    let Some(config_file) = ConfigFile::find(&config_dir)? else {
         return Ok(Config::default());
-    }
-
-    let content = read_to_string(&config_file).await?;
-
+    };
     // If file is empty or only whitespace, return default config
-    if content.trim().is_empty() {
+    let Some(mut value) = config_file.read_value()? else {
         return Ok(Config::default());
+    };
+
+    // Layer the config over any `extends` base before merging with defaults
+    if let Some(source) = value.get("extends").and_then(|v| v.as_str()) {
+        let integrity = value.get("extendsIntegrity").and_then(|v| v.as_str());
+        let project_root = config_dir.parent().unwrap_or(current_dir);
+        let mut base = resolve_extends(project_root, source, integrity).await?;
+        check_extended_hooks(source, &mut base, &value)?;
+        value = merge_config_values(base, value);
     }
+    config_file.to_config(value)
+}
 
-    // Parse JSON config, merging with defaults
-    let config: Config = serde_json::from_str(&content).context("Failed to parse config.json")?;
-
-    Ok(config)
+/// Reject `hooks` in the remote `extends` base of `local` unless `local` sets
+/// `extendsTrustHooks`, which a base cannot set for itself
+///
+/// Hooks run shell commands, so a fetched config may only add them when trusted locally.
+fn check_extended_hooks(source: &str, base: &mut Value, local: &Value) -> Result<()> {
+    let Some(base) = base.as_object_mut() else {
+        return Ok(());
+    };
+    base.remove(TRUST_HOOKS_KEY);
+    let trusted = local.get(TRUST_HOOKS_KEY).and_then(Value::as_bool) == Some(true);
+    let has_hooks = base
+        .get("hooks")
+        .and_then(Value::as_object)
+        .is_some_and(|hooks| !hooks.is_empty());
+    if has_hooks && is_remote(source) && !trusted {
+        bail!(
+            "The extended config '{source}' sets hooks; set \"{TRUST_HOOKS_KEY}\": true to run them"
+        );
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -138,6 +169,113 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_get_changepacks_config_extends_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        std::process::Command::new("git")
+            .arg("init")
+            .current_dir(temp_path)
+            .output()
+            .unwrap();
+
+        let changepacks_dir = temp_path.join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).unwrap();
+        write(
+            temp_path.join("shared.json"),
+            r#"{"baseBranch": "develop", "ignore": ["docs/**"], "publish": {"node": "npm publish"}}"#,
+        )
+        .await
+        .unwrap();
+        write(
+            changepacks_dir.join("config.json"),
+            r#"{"extends": "shared.json", "publish": {"rust": "cargo publish"}}"#,
+        )
+        .await
+        .unwrap();
+
+        let config = get_changepacks_config(temp_path).await.unwrap();
+        assert_eq!(config.base_branch, "develop");
+        assert_eq!(config.ignore, vec!["docs/**"]);
+        assert_eq!(config.publish.len(), 2);
+        assert_eq!(config.extends.as_deref(), Some("shared.json"));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_check_extended_hooks() {
+        let hooks =
+            || serde_json::json!({"hooks": {"preUpdate": "./x.sh"}, "extendsTrustHooks": true});
+        let remote = "https://example.com/base.json";
+        let err = check_extended_hooks(remote, &mut hooks(), &serde_json::json!({})).unwrap_err();
+        assert!(err.to_string().contains("extendsTrustHooks"));
+
+        let mut base = hooks();
+        check_extended_hooks(
+            remote,
+            &mut base,
+            &serde_json::json!({"extendsTrustHooks": true}),
+        )
+        .unwrap();
+        assert!(base.get("extendsTrustHooks").is_none());
+        check_extended_hooks("shared.json", &mut hooks(), &serde_json::json!({})).unwrap();
+        check_extended_hooks(
+            remote,
+            &mut serde_json::json!({"hooks": {}}),
+            &serde_json::json!({}),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_changepacks_config_toml_and_yaml() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        std::process::Command::new("git")
+            .arg("init")
+            .current_dir(temp_path)
+            .output()
+            .unwrap();
+
+        let changepacks_dir = temp_path.join(".changepacks");
+        fs::create_dir_all(&changepacks_dir).unwrap();
+        write(temp_path.join("shared.json"), r#"{"ignore": ["docs/**"]}"#)
+            .await
+            .unwrap();
+        write(
+            changepacks_dir.join("config.toml"),
+            "# Shared settings\nextends = \"shared.json\"\nbaseBranch = \"develop\"\n\n[publish]\nnode = \"pnpm publish\"\n",
+        )
+        .await
+        .unwrap();
+
+        let config = get_changepacks_config(temp_path).await.unwrap();
+        assert_eq!(config.base_branch, "develop");
+        assert_eq!(config.ignore, vec!["docs/**"]);
+        assert_eq!(config.publish.get("node").unwrap(), "pnpm publish");
+
+        fs::remove_file(changepacks_dir.join("config.toml")).unwrap();
+        write(
+            changepacks_dir.join("config.yaml"),
+            "baseBranch: trunk # release branch\nfixed:\n  - [a/package.json, b/package.json]\n",
+        )
+        .await
+        .unwrap();
+        let config = get_changepacks_config(temp_path).await.unwrap();
+        assert_eq!(config.base_branch, "trunk");
+        assert_eq!(config.fixed.len(), 1);
+
+        write(changepacks_dir.join("config.json"), "{}")
+            .await
+            .unwrap();
+        assert!(get_changepacks_config(temp_path).await.is_err());
+
+        temp_dir.close().unwrap();
+    }
+
     #[tokio::test]
     async fn test_get_changepacks_config_empty_json() {
         let temp_dir = TempDir::new().unwrap();
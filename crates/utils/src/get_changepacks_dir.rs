@@ -1,20 +1,45 @@
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::Result;
+use changepacks_core::{ConfigFile, changepacks_dir_name, resolve_changepacks_dir_name};
 
-use crate::find_current_git_repo;
+use crate::find_project_root;
 
-/// Get the .changepacks directory path from the git repository root
+/// Config key relocating the changepacks directory, read from the local config file only
+const CHANGEPACKS_DIR_KEY: &str = "changepacksDir";
+
+/// Get the changepacks directory path from the project root (see [`find_project_root`])
+///
+/// This is `.changepacks` unless overridden by the `CHANGEPACKS_DIR` environment variable
+/// or, when that is unset, the config `changepacksDir` setting.
 ///
 /// # Errors
-/// Returns error if finding the git repository fails.
+/// Returns error if the git repository has no work tree, or the config file cannot be read.
 pub fn get_changepacks_dir(current_dir: &Path) -> Result<PathBuf> {
-    let repo = find_current_git_repo(current_dir)?;
-    let changepacks_dir = repo
-        .work_dir()
-        .context("Failed to find current git repository")?
-        .join(".changepacks");
-    Ok(changepacks_dir)
+    changepacks_dir_in(&find_project_root(current_dir)?)
+}
+
+/// Get the directory holding the config file from the project root: `.changepacks`, or
+/// `CHANGEPACKS_DIR` when set
+///
+/// # Errors
+/// Returns error if the git repository has no work tree.
+pub fn get_config_dir(current_dir: &Path) -> Result<PathBuf> {
+    Ok(find_project_root(current_dir)?.join(changepacks_dir_name()))
+}
+
+/// Changepacks directory of the project at `root` (see [`get_changepacks_dir`])
+pub(crate) fn changepacks_dir_in(root: &Path) -> Result<PathBuf> {
+    let configured = match ConfigFile::find(&root.join(changepacks_dir_name()))? {
+        Some(config_file) => config_file.read_value()?.and_then(|value| {
+            value
+                .get(CHANGEPACKS_DIR_KEY)
+                .and_then(|dir| dir.as_str())
+                .map(str::to_string)
+        }),
+        None => None,
+    };
+    Ok(root.join(resolve_changepacks_dir_name(configured.as_deref())))
 }
 
 #[cfg(test)]
@@ -72,12 +97,12 @@ mod tests {
 
     #[test]
     fn test_get_changepacks_dir_without_git_repo() {
-        // Create a temporary directory without git
+        // Create a temporary directory without git: falls back to the directory itself
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path();
 
         let result = get_changepacks_dir(temp_path);
-        assert!(result.is_err());
+        assert_eq!(result.unwrap(), temp_path.join(".changepacks"));
 
         temp_dir.close().unwrap();
     }
@@ -107,6 +132,27 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[test]
+    fn test_get_changepacks_dir_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        fs::create_dir_all(temp_path.join(".changepacks")).unwrap();
+        fs::write(
+            temp_path.join(".changepacks/config.json"),
+            r#"{"changepacksDir": "release/changes"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_changepacks_dir(temp_path).unwrap(),
+            temp_path.join("release/changes")
+        );
+        assert_eq!(
+            get_config_dir(temp_path).unwrap(),
+            temp_path.join(".changepacks")
+        );
+    }
+
     #[test]
     fn test_get_changepacks_dir_nested_subdirectory() {
         // Create a temporary directory and initialize git
@@ -1,14 +1,37 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use changepacks_core::{normalize_path, path_key};
 
 /// Get the relative path from a git root to an absolute path
 ///
+/// Both paths are compared in [`normalize_path`] form when they do not match as given, so a
+/// verbatim (`\\?\C:\...`) or lower-case drive path on one side still resolves on Windows, and
+/// then with symlinks resolved, so a root reached through a symlinked directory still matches.
+/// Paths that do match as given keep their form, so files under a symlinked project directory
+/// are reported under the link rather than its target. The result always uses `/` separators, making it a stable key for changepack logs and the config.
+///
 /// # Errors
 /// Returns error if the absolute path is not within the git root directory.
 pub fn get_relative_path(git_root_path: &Path, absolute_path: &Path) -> Result<PathBuf> {
-    match absolute_path.strip_prefix(git_root_path) {
-        Ok(relative) => Ok(relative.to_path_buf()),
+    let relative = absolute_path
+        .strip_prefix(git_root_path)
+        .map(Path::to_path_buf)
+        .or_else(|_| {
+            normalize_path(absolute_path)
+                .strip_prefix(normalize_path(git_root_path))
+                .map(Path::to_path_buf)
+        })
+        .or_else(
+            |err| match (absolute_path.canonicalize(), git_root_path.canonicalize()) {
+                (Ok(absolute), Ok(root)) => normalize_path(&absolute)
+                    .strip_prefix(normalize_path(&root))
+                    .map(Path::to_path_buf),
+                _ => Err(err),
+            },
+        );
+    match relative {
+        Ok(relative) => Ok(PathBuf::from(path_key(&relative))),
         Err(_) => Err(anyhow::anyhow!(
             "Failed to get relative path: '{}' is not within '{}'",
             absolute_path.display(),
@@ -128,4 +151,29 @@ mod tests {
         let result = get_relative_path(root, &absolute).unwrap();
         assert_eq!(result, PathBuf::from("src").join("lib.rs"));
     }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_get_relative_path_windows_prefix_and_drive_case() {
+        let root = PathBuf::from(r"\\?\C:\repo");
+        let absolute = PathBuf::from(r"c:\repo\packages\foo/package.json");
+        let result = get_relative_path(&root, &absolute).unwrap();
+        assert_eq!(result.to_str(), Some("packages/foo/package.json"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_relative_path_root_through_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let real_root = temp_dir.path().join("real");
+        fs::create_dir_all(real_root.join("src")).unwrap();
+        fs::write(real_root.join("src/lib.rs"), "").unwrap();
+        let linked_root = temp_dir.path().join("linked");
+        std::os::unix::fs::symlink(&real_root, &linked_root).unwrap();
+
+        let result = get_relative_path(&linked_root, &real_root.join("src/lib.rs")).unwrap();
+        assert_eq!(result, PathBuf::from("src/lib.rs"));
+        let result = get_relative_path(&real_root, &linked_root.join("src/lib.rs")).unwrap();
+        assert_eq!(result, PathBuf::from("src/lib.rs"));
+    }
 }
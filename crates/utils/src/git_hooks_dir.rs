@@ -0,0 +1,53 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+/// Directory git runs hooks from for the repository at `current_dir`
+///
+/// This is `core.hooksPath` when set (relative to the work tree), otherwise `hooks` in the
+/// common git directory, which linked worktrees share with the main one.
+///
+/// # Errors
+/// Returns error if `current_dir` is not in a git repository or `core.hooksPath` cannot be
+/// interpolated.
+pub fn git_hooks_dir(current_dir: &Path) -> Result<PathBuf> {
+    let repo = gix::discover(current_dir)?;
+    if let Some(hooks_path) = repo.config_snapshot().trusted_path("core.hooksPath") {
+        let hooks_path = hooks_path?;
+        if hooks_path.is_absolute() {
+            return Ok(hooks_path.into_owned());
+        }
+        let base = repo.workdir().unwrap_or_else(|| repo.git_dir());
+        return Ok(base.join(hooks_path));
+    }
+    Ok(repo.common_dir().join("hooks"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_git;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_git_hooks_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        run_git(&root, &["init", "-q"]).await.unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+
+        assert!(
+            git_hooks_dir(&root.join("src"))
+                .unwrap()
+                .ends_with(".git/hooks")
+        );
+
+        run_git(&root, &["config", "core.hooksPath", ".githooks"])
+            .await
+            .unwrap();
+        assert_eq!(
+            git_hooks_dir(&root.join("src")).unwrap(),
+            root.join(".githooks")
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Fetch URL of the git remote `remote` of the repository at `repo_root`
+///
+/// # Errors
+/// Returns error if `repo_root` is not in a git repository or has no remote `remote`.
+pub fn git_remote_url(repo_root: &Path, remote: &str) -> Result<String> {
+    let repo = gix::discover(repo_root)?;
+    let remote_config = repo
+        .find_remote(remote)
+        .with_context(|| format!("No git remote named {remote}"))?;
+    let url = remote_config
+        .url(gix::remote::Direction::Fetch)
+        .with_context(|| format!("Git remote {remote} has no URL"))?;
+    Ok(url.to_bstring().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_git;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_git_remote_url() {
+        let temp_dir = TempDir::new().unwrap();
+        run_git(temp_dir.path(), &["init", "-q"]).await.unwrap();
+        run_git(
+            temp_dir.path(),
+            &["remote", "add", "origin", "git@github.com:acme/tools.git"],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            git_remote_url(temp_dir.path(), "origin").unwrap(),
+            "git@github.com:acme/tools.git"
+        );
+        let err = git_remote_url(temp_dir.path(), "upstream").unwrap_err();
+        assert!(err.to_string().contains("upstream"), "{err}");
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use anyhow::{Context, Result};
+use changepacks_core::{HomebrewConfig, Project, path_key};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use crate::{read_file, write_file};
+
+static URL_VERSION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\d+\.\d+\.\d+(?:-[0-9A-Za-z]+(?:\.\d+)?)?").expect("hardcoded regex must compile")
+});
+
+/// Point the Homebrew formulas following `projects` at their current versions
+///
+/// Every `<name>.rb` in the configured tap directory follows the project named in the config
+/// `formulas` (or the package called `<name>`). When that project is among `projects` and its
+/// version differs from the formula's, the version in the formula `url` is replaced, the new
+/// archive is downloaded to compute its `sha256`, and the `version` field (if any) is updated.
+/// Returns the rewritten formula files with their new versions.
+///
+/// # Errors
+/// Returns error if a formula cannot be read or written, has no `url`, or its new archive
+/// cannot be downloaded.
+pub async fn bump_homebrew_formulas(
+    repo_root: &Path,
+    config: &HomebrewConfig,
+    projects: &[&Project],
+) -> Result<Vec<(PathBuf, String)>> {
+    let mut formulas = Vec::new();
+    if let Ok(mut entries) = tokio::fs::read_dir(repo_root.join(&config.tap)).await {
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "rb") && path.is_file() {
+                formulas.push(path);
+            }
+        }
+    }
+    formulas.sort();
+
+    let mut bumped = Vec::new();
+    for formula in formulas {
+        let Some(name) = formula.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let target = config.formulas.get(name).map_or(name, String::as_str);
+        let Some(version) = projects
+            .iter()
+            .find(|project| {
+                project.name() == Some(target)
+                    || path_key(project.relative_path()) == path_key(Path::new(target))
+            })
+            .and_then(|project| project.version())
+        else {
+            continue;
+        };
+
+        let content = read_file(&formula).await?;
+        let (url, current_version) = formula_source(&content)
+            .with_context(|| format!("{} has no versioned url", formula.display()))?;
+        if current_version == version {
+            continue;
+        }
+        let new_url = url.replace(&current_version, version);
+        let sha256 = download_sha256(&new_url)
+            .await
+            .with_context(|| format!("Failed to download {new_url} for {}", formula.display()))?;
+        write_file(
+            &formula,
+            rewrite_formula(&content, &new_url, version, &sha256),
+        )
+        .await?;
+        bumped.push((formula, version.to_string()));
+    }
+    Ok(bumped)
+}
+
+/// Main `url` of a formula and the version it points at (its `version` field, or the first
+/// version-like part of the url path)
+fn formula_source(content: &str) -> Option<(String, String)> {
+    let url = field(content, "url")?;
+    let version = field(content, "version").or_else(|| {
+        // Skip the host, which may be an IP address
+        let path_start = url
+            .find("://")
+            .and_then(|scheme| url[scheme + 3..].find('/').map(|i| scheme + 3 + i))
+            .unwrap_or(0);
+        URL_VERSION_PATTERN
+            .find(&url[path_start..])
+            .map(|m| m.as_str().to_string())
+    })?;
+    Some((url, version))
+}
+
+/// Value of the first `<name> "<value>"` line
+fn field(content: &str, name: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        line.trim_start()
+            .strip_prefix(name)?
+            .trim_start()
+            .strip_prefix('"')?
+            .split_once('"')
+            .map(|(value, _)| value.to_string())
+    })
+}
+
+/// Replace the main `url`, the `sha256` that follows it, and the `version` field if present
+///
+/// Bottle checksums (`sha256 cellar: ...`) are left alone.
+fn rewrite_formula(content: &str, url: &str, version: &str, sha256: &str) -> String {
+    let (mut url_done, mut sha_done, mut version_done) = (false, false, false);
+    let mut out: Vec<String> = Vec::new();
+    for line in content.split_inclusive('\n') {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let ending = if line.ends_with("\r\n") {
+            "\r\n"
+        } else if line.ends_with('\n') {
+            "\n"
+        } else {
+            ""
+        };
+        let replaced = if !url_done && field(line, "url").is_some() {
+            url_done = true;
+            Some(format!("url \"{url}\""))
+        } else if url_done && !sha_done && field(line, "sha256").is_some() {
+            sha_done = true;
+            Some(format!("sha256 \"{sha256}\""))
+        } else if !version_done && field(line, "version").is_some() {
+            version_done = true;
+            Some(format!("version \"{version}\""))
+        } else {
+            None
+        };
+        match replaced {
+            Some(replaced) => out.push(format!("{indent}{replaced}{ending}")),
+            None => out.push(line.to_string()),
+        }
+    }
+    out.concat()
+}
+
+/// Hex SHA-256 of the archive at `url`, streamed rather than buffered
+async fn download_sha256(url: &str) -> Result<String> {
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut response = ureq::get(&url).call()?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut response.body_mut().as_reader(), &mut hasher)?;
+        Ok(hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect())
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMULA: &str = r#"class Mytool < Formula
+  desc "Does things"
+  homepage "https://example.com"
+  url "https://github.com/acme/mytool/releases/download/v1.2.3/mytool-1.2.3.tar.gz"
+  sha256 "0000000000000000000000000000000000000000000000000000000000000000"
+  license "MIT"
+
+  bottle do
+    sha256 cellar: :any, arm64_sonoma: "1111111111111111111111111111111111111111111111111111111111111111"
+  end
+end
+"#;
+
+    #[test]
+    fn test_formula_source() {
+        let (url, version) = formula_source(FORMULA).unwrap();
+        assert_eq!(
+            url,
+            "https://github.com/acme/mytool/releases/download/v1.2.3/mytool-1.2.3.tar.gz"
+        );
+        assert_eq!(version, "1.2.3");
+
+        let explicit = "  url \"https://example.com/mytool.tar.gz\"\n  version \"0.9.0\"\n";
+        assert_eq!(formula_source(explicit).unwrap().1, "0.9.0");
+        assert!(formula_source("  url \"https://example.com/latest.tar.gz\"\n").is_none());
+        assert_eq!(
+            formula_source("  url \"http://10.0.0.1:8080/v2.0.0/tool.tar.gz\"\n")
+                .unwrap()
+                .1,
+            "2.0.0"
+        );
+        assert!(formula_source("class Mytool < Formula\nend\n").is_none());
+    }
+
+    #[test]
+    fn test_rewrite_formula() {
+        let updated = rewrite_formula(
+            FORMULA,
+            "https://github.com/acme/mytool/releases/download/v1.3.0/mytool-1.3.0.tar.gz",
+            "1.3.0",
+            "abcd",
+        );
+        assert!(updated.contains(
+            "  url \"https://github.com/acme/mytool/releases/download/v1.3.0/mytool-1.3.0.tar.gz\"\n"
+        ));
+        assert!(updated.contains("  sha256 \"abcd\"\n"));
+        assert!(updated.contains("arm64_sonoma: \"1111"));
+        assert!(!updated.contains("version \""));
+        assert_eq!(updated.lines().count(), FORMULA.lines().count());
+    }
+
+    #[tokio::test]
+    async fn test_bump_homebrew_formulas_skips_unrelated_and_current() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let tap = temp_dir.path().join("Formula");
+        std::fs::create_dir_all(&tap).unwrap();
+        std::fs::write(tap.join("mytool.rb"), FORMULA).unwrap();
+        std::fs::write(tap.join("README.md"), "not a formula").unwrap();
+
+        // No project follows the formula
+        let bumped = bump_homebrew_formulas(temp_dir.path(), &HomebrewConfig::default(), &[])
+            .await
+            .unwrap();
+        assert!(bumped.is_empty());
+        // A missing tap is not an error
+        let config = HomebrewConfig {
+            tap: "missing".to_string(),
+            ..Default::default()
+        };
+        assert!(
+            bump_homebrew_formulas(temp_dir.path(), &config, &[])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            std::fs::read_to_string(tap.join("mytool.rb")).unwrap(),
+            FORMULA
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use tokio::fs::DirEntry;
+
+/// Prefix of every changepack log file name, `changepack_log_<id>.json` or `.md`
+pub const CHANGEPACK_LOG_PREFIX: &str = "changepack_log_";
+
+/// Whether a file in `.changepacks` is a changepack log rather than config, release state or
+/// anything else kept there
+#[must_use]
+pub fn is_changepack_log(file_name: &str) -> bool {
+    file_name
+        .strip_prefix(CHANGEPACK_LOG_PREFIX)
+        .and_then(|rest| rest.rsplit_once('.'))
+        .is_some_and(|(id, extension)| {
+            !id.is_empty()
+                && (extension.eq_ignore_ascii_case("json") || extension.eq_ignore_ascii_case("md"))
+        })
+}
+
+/// Whether a `.changepacks` directory entry is a changepack log file (never a directory)
+pub async fn is_changepack_log_entry(entry: &DirEntry) -> bool {
+    is_changepack_log(&entry.file_name().to_string_lossy())
+        && entry
+            .file_type()
+            .await
+            .is_ok_and(|file_type| file_type.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("changepack_log_abc.json", true)]
+    #[case("changepack_log_abc.md", true)]
+    #[case("changepack_log_abc.JSON", true)]
+    #[case("config.json", false)]
+    #[case("config.yaml", false)]
+    #[case("releases.json", false)]
+    #[case("publish_log.json", false)]
+    #[case("run.lock", false)]
+    #[case("README.md", false)]
+    #[case("archive", false)]
+    #[case("changepack_log_.json", false)]
+    #[case("changepack_log_abc.txt", false)]
+    #[case("notes.json", false)]
+    fn test_is_changepack_log(#[case] file_name: &str, #[case] expected: bool) {
+        assert_eq!(is_changepack_log(file_name), expected);
+    }
+
+    #[tokio::test]
+    async fn test_is_changepack_log_entry_skips_directories() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("changepack_log_dir.json")).unwrap();
+        std::fs::write(temp_dir.path().join("changepack_log_a.json"), "{}").unwrap();
+
+        let mut entries = tokio::fs::read_dir(temp_dir.path()).await.unwrap();
+        let mut logs = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            if is_changepack_log_entry(&entry).await {
+                logs.push(entry.file_name());
+            }
+        }
+        assert_eq!(logs, ["changepack_log_a.json"]);
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+/// Manifest that marks a package as published to JSR (<https://jsr.io>), shared by Node and
+/// Deno projects
+pub const JSR_MANIFEST: &str = "jsr.json";
+
+/// Registry name recorded for JSR packages
+pub const JSR_REGISTRY: &str = "jsr";
+
+/// The `jsr.json` describing the package at `path`: the file itself, or one next to its package.json
+#[must_use]
+pub fn jsr_manifest(path: &Path) -> Option<PathBuf> {
+    if path.file_name().is_some_and(|name| name == JSR_MANIFEST) {
+        return Some(path.to_path_buf());
+    }
+    let manifest = path.parent()?.join(JSR_MANIFEST);
+    manifest.is_file().then_some(manifest)
+}
+
+/// Publish command for a JSR package in `dir`: `deno publish` in Deno projects, `npx jsr publish` otherwise
+#[must_use]
+pub fn jsr_publish_command(dir: &Path, dry_run: bool) -> String {
+    let command = if ["deno.json", "deno.jsonc"]
+        .iter()
+        .any(|file| dir.join(file).is_file())
+    {
+        "deno publish"
+    } else {
+        "npx jsr publish"
+    };
+    if dry_run {
+        format!("{command} --dry-run")
+    } else {
+        command.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_jsr_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        assert_eq!(jsr_manifest(&package_json), None);
+
+        let manifest = temp_dir.path().join(JSR_MANIFEST);
+        fs::write(&manifest, "{}").unwrap();
+        assert_eq!(jsr_manifest(&package_json), Some(manifest.clone()));
+        assert_eq!(jsr_manifest(&manifest), Some(manifest));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_jsr_publish_command() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(
+            jsr_publish_command(temp_dir.path(), false),
+            "npx jsr publish"
+        );
+        assert_eq!(
+            jsr_publish_command(temp_dir.path(), true),
+            "npx jsr publish --dry-run"
+        );
+
+        fs::write(temp_dir.path().join("deno.json"), "{}").unwrap();
+        assert_eq!(jsr_publish_command(temp_dir.path(), false), "deno publish");
+        assert_eq!(
+            jsr_publish_command(temp_dir.path(), true),
+            "deno publish --dry-run"
+        );
+
+        temp_dir.close().unwrap();
+    }
+}
@@ -6,30 +6,104 @@
 //! Kahn's algorithm, config management, and format detection for JSON indentation. These
 //! utilities are used across all language-specific crates and CLI commands.
 
+mod bridge_links;
+mod change_filter;
+mod changed_files;
+mod changelog;
 mod clear_update_logs;
+mod compare_versions;
+mod create_git_tag;
+mod current_branch;
 mod detect_indent;
+mod discover_projects;
 mod display_update;
+mod edit_config;
 mod filter_project_dirs;
 mod find_current_git_repo;
+mod find_project_root;
+mod first_unreleased_change;
+mod format_update_rows;
+mod format_webhook_body;
 mod gen_changepack_result_map;
 mod gen_update_map;
 mod get_changepacks_config;
 mod get_changepacks_dir;
 mod get_relative_path;
+mod git_hooks_dir;
+mod git_remote_url;
+mod homebrew_formula;
+mod is_changepack_log;
+mod jsr_manifest;
+mod list_git_tags;
 mod next_version;
+mod publish_log;
+mod read_changepack_logs;
+mod read_commit_changepacks;
+mod release_manifest;
+mod render_changepack_log;
+mod render_note;
+mod resolve_extends;
+mod rewrite_version_range;
+mod run_git;
+mod run_lock;
+mod send_webhooks;
 mod sort_by_dep;
 mod split_version;
+mod unified_diff;
+mod upsert_pull_request;
+mod validate_config;
+mod walk_project_dirs;
+mod workspace_members;
+mod write_file;
 
+pub use bridge_links::bridge_links;
+pub use change_filter::ChangeFilter;
+pub use changed_files::changed_files;
+pub use changelog::{render_changelog_section, write_changelog};
 pub use clear_update_logs::clear_update_logs;
+pub use compare_versions::compare_versions;
+pub use create_git_tag::create_git_tag;
+pub use current_branch::current_branch;
 pub use detect_indent::detect_indent;
+pub use discover_projects::{ProjectDiscovery, discover_projects};
 pub use display_update::display_update;
+pub use edit_config::{config_pointer, set_config_setting, set_pointer};
 pub use filter_project_dirs::find_project_dirs;
 pub use find_current_git_repo::find_current_git_repo;
+pub use find_project_root::find_project_root;
+pub use first_unreleased_change::first_unreleased_change;
+pub use format_update_rows::format_update_rows;
+pub use format_webhook_body::format_webhook_body;
 pub use gen_changepack_result_map::gen_changepack_result_map;
 pub use gen_update_map::{apply_reverse_dependencies, gen_update_map};
 pub use get_changepacks_config::get_changepacks_config;
-pub use get_changepacks_dir::get_changepacks_dir;
+pub use get_changepacks_dir::{get_changepacks_dir, get_config_dir};
 pub use get_relative_path::get_relative_path;
-pub use next_version::next_version;
-pub use sort_by_dep::sort_by_dependencies;
+pub use git_hooks_dir::git_hooks_dir;
+pub use git_remote_url::git_remote_url;
+pub use homebrew_formula::bump_homebrew_formulas;
+pub use is_changepack_log::{CHANGEPACK_LOG_PREFIX, is_changepack_log, is_changepack_log_entry};
+pub use jsr_manifest::{JSR_MANIFEST, JSR_REGISTRY, jsr_manifest, jsr_publish_command};
+pub use list_git_tags::list_git_tags;
+pub use next_version::{next_version, next_version_with_id};
+pub use publish_log::{append_publish_record, output_digest, read_publish_log};
+pub use read_changepack_logs::read_changepack_logs;
+pub use read_commit_changepacks::read_commit_changepacks;
+pub use release_manifest::{append_release_record, read_release_manifest};
+pub use render_changepack_log::{changepack_log_file, render_changepack_log};
+pub use render_note::{render_changepack_notes, render_note};
+pub use resolve_extends::{merge_config_values, resolve_extends};
+pub use rewrite_version_range::{range_allows, rewrite_dependency_range, rewrite_version_range};
+pub use run_git::{run_git, run_git_output};
+pub use run_lock::{RUN_LOCK_FILE, RunLock};
+pub use send_webhooks::send_webhooks;
+pub use sort_by_dep::{dependency_levels, sort_by_dependencies};
 pub use split_version::split_version;
+pub use unified_diff::unified_diff;
+pub use upsert_pull_request::{fetch_pull_request, upsert_pull_request};
+pub use validate_config::{ConfigIssue, validate_config};
+pub use walk_project_dirs::walk_project_dirs;
+pub use workspace_members::{Membership, member_manifests, workspace_membership};
+pub use write_file::{
+    FileChange, apply_file_changes, capture_writes, read_file, write_file, write_json_file,
+};
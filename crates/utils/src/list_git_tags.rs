@@ -0,0 +1,63 @@
+use std::path::Path;
+
+/// Names of the tags in the repository at `repo_root`, sorted
+///
+/// Returns an empty list outside a git repository or when the refs cannot be read, since
+/// tags only add information when they exist.
+pub async fn list_git_tags(repo_root: &Path) -> Vec<String> {
+    let Ok(repo) = gix::discover(repo_root) else {
+        return Vec::new();
+    };
+    let Ok(references) = repo.references() else {
+        return Vec::new();
+    };
+    let Ok(tags) = references.tags() else {
+        return Vec::new();
+    };
+    let mut tags: Vec<String> = tags
+        .filter_map(Result::ok)
+        .map(|tag| tag.name().shorten().to_string())
+        .collect();
+    tags.sort();
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    #[tokio::test]
+    async fn test_list_git_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(list_git_tags(temp_dir.path()).await.is_empty());
+
+        git(temp_dir.path(), &["init", "-b", "main"]);
+        git(temp_dir.path(), &["config", "user.email", "test@test.com"]);
+        git(temp_dir.path(), &["config", "user.name", "Test"]);
+        git(
+            temp_dir.path(),
+            &["commit", "--allow-empty", "-m", "Initial commit"],
+        );
+        assert!(list_git_tags(temp_dir.path()).await.is_empty());
+
+        git(temp_dir.path(), &["tag", "core@1.0.0"]);
+        git(temp_dir.path(), &["tag", "v2.0.0"]);
+        assert_eq!(
+            list_git_tags(temp_dir.path()).await,
+            ["core@1.0.0", "v2.0.0"]
+        );
+
+        temp_dir.close().unwrap();
+    }
+}
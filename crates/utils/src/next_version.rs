@@ -1,47 +1,155 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use changepacks_core::UpdateType;
 
 /// Calculate the next version based on semver and update type
 ///
+/// Partial versions are padded with zeros (`1` and `1.0` bump like `1.0.0`), so the result is
+/// always `MAJOR.MINOR.PATCH`. Bumping a prerelease releases it the way npm does: `1.0.0-beta.1`
+/// becomes `1.0.0` for a patch, `1.2.0-rc.1` becomes `1.2.0` for a minor, and `2.0.0-rc.1`
+/// becomes `2.0.0` for a major; a prerelease of a lower part bumps normally (`1.2.3-rc.1` minor
+/// is `1.3.0`). Build metadata (`+build`) is kept, and parts that are not bumped keep their
+/// original spelling, so calendar versions like `2024.01.5` stay recognizable.
+///
+/// A prerelease bump keeps the identifier of a pre-release and makes a release `1.2.1-0`; use
+/// [`next_version_with_id`] to bump to a named identifier.
+///
 /// # Errors
-/// Returns error if the version format is invalid.
+/// Returns error if the version is not `MAJOR[.MINOR[.PATCH]][-PRERELEASE][+BUILD]`.
 pub fn next_version(version: &str, update_type: UpdateType) -> Result<String> {
-    let mut version_parts = version.split('.').collect::<Vec<&str>>();
+    next_version_with_id(version, update_type, None)
+}
 
-    // Ensure we have exactly 3 parts (major.minor.patch)
-    if version_parts.len() != 3 {
-        return Err(anyhow::anyhow!("Invalid version format: {version}"));
-    }
-    let plus_split = version_parts[2].split('+').collect::<Vec<&str>>();
-    let plus_part = if plus_split.len() == 2 {
-        version_parts[2] = plus_split[0];
-        Some(plus_split[1])
-    } else {
-        None
+/// [`next_version`] bumping prereleases to `prerelease_id` (e.g. `beta`, the `preid` of the
+/// pending changepacks) when given
+///
+/// `1.2.0` becomes `1.2.1-beta.0`, `1.2.0-beta.2` becomes `1.2.0-beta.3`, and a pre-release
+/// with another identifier restarts it (`1.2.0-alpha.4` becomes `1.2.0-beta.0`). Other update
+/// types ignore the identifier.
+///
+/// # Errors
+/// Returns error if the version is not `MAJOR[.MINOR[.PATCH]][-PRERELEASE][+BUILD]`, or
+/// `prerelease_id` is not a valid identifier.
+pub fn next_version_with_id(
+    version: &str,
+    update_type: UpdateType,
+    prerelease_id: Option<&str>,
+) -> Result<String> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid version '{version}': expected MAJOR.MINOR.PATCH, optionally followed by \
+             -PRERELEASE or +BUILD"
+        )
+    };
+    let (version_core, build) = match version.split_once('+') {
+        Some((_, "")) => return Err(invalid()),
+        Some((core, build)) => (core, Some(build)),
+        None => (version, None),
     };
+    let (version_core, prerelease) = match version_core.split_once('-') {
+        Some((_, "")) => return Err(invalid()),
+        Some((core, prerelease)) => (core, Some(prerelease)),
+        None => (version_core, None),
+    };
+
+    let mut version_parts = version_core
+        .split('.')
+        .map(String::from)
+        .collect::<Vec<_>>();
+    if version_parts.len() > 3
+        || version_parts
+            .iter()
+            .any(|part| part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(invalid());
+    }
+    version_parts.resize(3, "0".to_string());
 
     let version_index = match update_type {
         UpdateType::Major => 0,
         UpdateType::Minor => 1,
         UpdateType::Patch => 2,
+        UpdateType::Prerelease => {
+            return next_prerelease(version_parts, prerelease, build, prerelease_id, invalid);
+        }
     };
 
-    let version_part = (version_parts[version_index]
-        .parse::<usize>()
-        .context(format!("Invalid version: {version}"))?
-        + 1)
-    .to_string();
-    version_parts[version_index] = version_part.as_str();
+    // A prerelease of exactly this bump (all lower parts zero) is released as is
+    let releases_prerelease = prerelease.is_some()
+        && version_parts
+            .iter()
+            .skip(version_index + 1)
+            .all(|part| part.bytes().all(|b| b == b'0'));
+    if !releases_prerelease {
+        let bumped = version_parts[version_index]
+            .parse::<u64>()
+            .with_context(invalid)?
+            + 1;
+        version_parts[version_index] = bumped.to_string();
+    }
 
     // Reset lower version parts to 0
     for part in version_parts.iter_mut().skip(version_index + 1) {
-        *part = "0";
+        *part = "0".to_string();
     }
 
     Ok(format!(
         "{}{}",
         version_parts.join("."),
-        plus_part.map(|p| format!("+{p}")).unwrap_or_default()
+        build.map(|b| format!("+{b}")).unwrap_or_default()
+    ))
+}
+
+/// Prerelease bump of the parsed `MAJOR.MINOR.PATCH` parts of a version
+fn next_prerelease(
+    mut version_parts: Vec<String>,
+    prerelease: Option<&str>,
+    build: Option<&str>,
+    id: Option<&str>,
+    invalid: impl Fn() -> anyhow::Error,
+) -> Result<String> {
+    if let Some(id) = id
+        && (id.is_empty()
+            || !id.split('.').all(|part| {
+                !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            }))
+    {
+        bail!(
+            "Invalid prerelease identifier '{id}': expected dot-separated alphanumerics or hyphens"
+        );
+    }
+    let next = match prerelease {
+        Some(prerelease) => {
+            // `beta.2` is identifier `beta` at build 2; `beta` has no build yet
+            let (name, build_number) = match prerelease.rsplit_once('.') {
+                Some((name, number)) if number.bytes().all(|b| b.is_ascii_digit()) => {
+                    (name, Some(number))
+                }
+                _ if prerelease.bytes().all(|b| b.is_ascii_digit()) => ("", Some(prerelease)),
+                _ => (prerelease, None),
+            };
+            match (id, build_number) {
+                (Some(id), _) if id != name => format!("{id}.0"),
+                (_, Some(number)) => {
+                    let number = number.parse::<u64>().with_context(&invalid)? + 1;
+                    if name.is_empty() {
+                        number.to_string()
+                    } else {
+                        format!("{name}.{number}")
+                    }
+                }
+                (_, None) => format!("{name}.0"),
+            }
+        }
+        None => {
+            let patch = version_parts[2].parse::<u64>().with_context(&invalid)? + 1;
+            version_parts[2] = patch.to_string();
+            id.map_or_else(|| "0".to_string(), |id| format!("{id}.0"))
+        }
+    };
+    Ok(format!(
+        "{}-{next}{}",
+        version_parts.join("."),
+        build.map(|b| format!("+{b}")).unwrap_or_default()
     ))
 }
 
@@ -62,6 +170,19 @@ mod tests {
     #[case("10.20.30", UpdateType::Minor, "10.21.0")]
     #[case("10.20.30", UpdateType::Patch, "10.20.31")]
     #[case("10.20.30+1", UpdateType::Patch, "10.20.31+1")]
+    #[case("1", UpdateType::Patch, "1.0.1")]
+    #[case("1", UpdateType::Major, "2.0.0")]
+    #[case("1.0", UpdateType::Patch, "1.0.1")]
+    #[case("1.2", UpdateType::Minor, "1.3.0")]
+    #[case("1.0.0-beta.1", UpdateType::Patch, "1.0.0")]
+    #[case("1.0.0-beta.1", UpdateType::Minor, "1.0.0")]
+    #[case("1.0.0-beta.1", UpdateType::Major, "1.0.0")]
+    #[case("1.2.0-rc.1", UpdateType::Minor, "1.2.0")]
+    #[case("1.2.0-rc.1", UpdateType::Major, "2.0.0")]
+    #[case("1.2.3-rc.1", UpdateType::Minor, "1.3.0")]
+    #[case("1.2.3-rc.1+build.5", UpdateType::Patch, "1.2.3+build.5")]
+    #[case("2024.1.0", UpdateType::Minor, "2024.2.0")]
+    #[case("2024.01.5", UpdateType::Patch, "2024.01.6")]
     fn test_next_version(
         #[case] version: &str,
         #[case] update_type: UpdateType,
@@ -73,11 +194,51 @@ mod tests {
 
     #[rstest]
     #[case("invalid", UpdateType::Major)]
-    #[case("1.2", UpdateType::Minor)]
+    #[case("", UpdateType::Patch)]
+    #[case("1..3", UpdateType::Patch)]
+    #[case("1.2.3-", UpdateType::Patch)]
+    #[case("1.2.3+", UpdateType::Patch)]
     #[case("1.2.3.4", UpdateType::Patch)]
     #[case("1.2.wrong", UpdateType::Patch)]
     fn test_next_version_invalid_input(#[case] version: &str, #[case] update_type: UpdateType) {
         let result = next_version(version, update_type);
         assert!(result.is_err());
     }
+
+    #[rstest]
+    #[case("1.2.0", Some("beta"), "1.2.1-beta.0")]
+    #[case("1.2", Some("beta"), "1.2.1-beta.0")]
+    #[case("1.2.0", None, "1.2.1-0")]
+    #[case("1.2.0-beta.2", Some("beta"), "1.2.0-beta.3")]
+    #[case("1.2.0-beta.2", None, "1.2.0-beta.3")]
+    #[case("1.2.0-beta", Some("beta"), "1.2.0-beta.0")]
+    #[case("1.2.0-alpha.4", Some("beta"), "1.2.0-beta.0")]
+    #[case("1.2.0-0", None, "1.2.0-1")]
+    #[case("1.2.0-beta.9+build.5", Some("beta"), "1.2.0-beta.10+build.5")]
+    #[case("1.2.0-next.1.2", Some("next.1"), "1.2.0-next.1.3")]
+    fn test_next_version_prerelease(
+        #[case] version: &str,
+        #[case] id: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            next_version_with_id(version, UpdateType::Prerelease, id).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("be ta")]
+    #[case("beta..1")]
+    fn test_next_version_prerelease_invalid_id(#[case] id: &str) {
+        let err = next_version_with_id("1.0.0", UpdateType::Prerelease, Some(id)).unwrap_err();
+        assert!(err.to_string().contains("Invalid prerelease identifier"));
+    }
+
+    #[test]
+    fn test_next_version_error_names_version() {
+        let err = next_version("1.x", UpdateType::Patch).unwrap_err();
+        assert!(err.to_string().contains("Invalid version '1.x'"));
+    }
 }
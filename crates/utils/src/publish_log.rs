@@ -0,0 +1,104 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use changepacks_core::{PUBLISH_LOG_FILE, PublishLog, PublishRecord};
+
+use crate::{read_file, write_json_file};
+
+/// Read `.changepacks/publish_log.json`, returning an empty log when it does not exist yet
+///
+/// # Errors
+/// Returns error if the log exists but cannot be read or parsed.
+pub async fn read_publish_log(changepacks_dir: &Path) -> Result<PublishLog> {
+    let log_file = changepacks_dir.join(PUBLISH_LOG_FILE);
+    if !log_file.exists() {
+        return Ok(PublishLog::default());
+    }
+    let content = read_file(&log_file).await?;
+    if content.trim().is_empty() {
+        return Ok(PublishLog::default());
+    }
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {PUBLISH_LOG_FILE}"))
+}
+
+/// Append a publish record to `.changepacks/publish_log.json`, creating the file if needed
+///
+/// # Errors
+/// Returns error if the existing log cannot be parsed or the file cannot be written.
+pub async fn append_publish_record(changepacks_dir: &Path, record: PublishRecord) -> Result<()> {
+    let mut log = read_publish_log(changepacks_dir).await?;
+    log.push(record);
+    tokio::fs::create_dir_all(changepacks_dir).await?;
+    write_json_file(changepacks_dir.join(PUBLISH_LOG_FILE), &log).await
+}
+
+/// SHA-1 hex digest of a publish command's captured output, for auditing without storing it
+///
+/// # Errors
+/// Returns error if the hasher fails (e.g. a SHA-1 collision attack is detected).
+pub fn output_digest(stdout: &str, stderr: &str) -> Result<String> {
+    let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+    hasher.update(stdout.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(stderr.as_bytes());
+    Ok(hasher.try_finalize()?.to_hex().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn record(success: bool) -> PublishRecord {
+        PublishRecord::new(
+            Some("core".to_string()),
+            PathBuf::from("packages/core/package.json"),
+            Some("1.0.0".to_string()),
+            "npm".to_string(),
+            success,
+            output_digest("out", "").unwrap(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_read_publish_log_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = read_publish_log(temp_dir.path()).await.unwrap();
+        assert!(log.records().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_publish_log_invalid() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(PUBLISH_LOG_FILE), "[").unwrap();
+        assert!(read_publish_log(temp_dir.path()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_append_publish_record_appends() {
+        let temp_dir = TempDir::new().unwrap();
+        let changepacks_dir = temp_dir.path().join(".changepacks");
+        append_publish_record(&changepacks_dir, record(false))
+            .await
+            .unwrap();
+        append_publish_record(&changepacks_dir, record(true))
+            .await
+            .unwrap();
+
+        let log = read_publish_log(&changepacks_dir).await.unwrap();
+        assert_eq!(log.records().len(), 2);
+        assert!(!log.records()[0].success());
+        assert!(log.records()[1].success());
+        assert!(log.is_published(Path::new("packages/core/package.json"), "1.0.0"));
+    }
+
+    #[test]
+    fn test_output_digest() {
+        let digest = output_digest("hello", "").unwrap();
+        assert_eq!(digest.len(), 40);
+        assert_eq!(digest, output_digest("hello", "").unwrap());
+        assert_ne!(digest, output_digest("", "hello").unwrap());
+    }
+}
@@ -0,0 +1,141 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use changepacks_core::ChangePackLog;
+use tokio::fs::{read_dir, read_to_string};
+
+use crate::is_changepack_log_entry;
+
+/// Read every pending changepack log in `changepacks_dir`, oldest first
+///
+/// Logs written at the same time are ordered by file path, so logs merged in from several
+/// branches always come out in the same order. Returns an empty list when the directory
+/// does not exist.
+///
+/// Logs are `.json` files or Markdown (`.md`) files with YAML frontmatter.
+///
+/// # Errors
+/// Returns error if the directory cannot be read or a log is not valid JSON or Markdown.
+pub async fn read_changepack_logs(changepacks_dir: &Path) -> Result<Vec<(PathBuf, ChangePackLog)>> {
+    let mut logs = Vec::new();
+    if !changepacks_dir.exists() {
+        return Ok(logs);
+    }
+
+    let mut entries = read_dir(changepacks_dir).await?;
+    while let Some(file) = entries.next_entry().await? {
+        let file_name = file.file_name();
+        let file_name = file_name.to_string_lossy();
+        let extension = Path::new(file_name.as_ref())
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+        if !is_changepack_log_entry(&file).await {
+            continue;
+        }
+        let content = read_to_string(file.path()).await?;
+        let log = if extension.as_deref() == Some("md") {
+            // Hand-written logs may leave out the date; order them by modification time
+            let modified = file.metadata().await?.modified()?;
+            ChangePackLog::from_markdown(&content, modified.into())
+        } else {
+            serde_json::from_str(&content).map_err(Into::into)
+        }
+        .with_context(|| format!("Failed to parse changepack log {file_name}"))?;
+        logs.push((file.path(), log));
+    }
+    logs.sort_by(|a, b| a.1.date().cmp(b.1.date()).then_with(|| a.0.cmp(&b.0)));
+    Ok(logs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_read_changepack_logs_missing_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let logs = read_changepack_logs(&temp_dir.path().join(".changepacks"))
+            .await
+            .unwrap();
+        assert!(logs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_changepack_logs_skips_reserved_and_non_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let log = ChangePackLog::new(HashMap::new(), "note".to_string());
+        for name in ["changepack_log_b.json", "changepack_log_a.json"] {
+            std::fs::write(dir.join(name), serde_json::to_string(&log).unwrap()).unwrap();
+        }
+        std::fs::write(dir.join("config.json"), "{}").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not json").unwrap();
+
+        let logs = read_changepack_logs(dir).await.unwrap();
+        let names: Vec<_> = logs
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["changepack_log_a.json", "changepack_log_b.json"]);
+        assert_eq!(logs[0].1.note(), "note");
+    }
+
+    #[tokio::test]
+    async fn test_read_changepack_logs_oldest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for (name, note, date) in [
+            ("changepack_log_a.json", "newer", "2025-02-01T00:00:00Z"),
+            ("changepack_log_b.json", "older", "2025-01-01T00:00:00Z"),
+        ] {
+            std::fs::write(
+                dir.join(name),
+                format!(r#"{{"changes": {{}}, "note": "{note}", "date": "{date}"}}"#),
+            )
+            .unwrap();
+        }
+
+        let logs = read_changepack_logs(dir).await.unwrap();
+        let notes: Vec<_> = logs.iter().map(|(_, log)| log.note()).collect();
+        assert_eq!(notes, ["older", "newer"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_changepack_logs_markdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        std::fs::write(
+            dir.join("changepack_log_a.json"),
+            r#"{"changes": {"a/package.json": "Patch"}, "note": "json", "date": "2025-02-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("changepack_log_b.md"),
+            "---\nchanges:\n  b/package.json: minor\ndate: 2025-01-01T00:00:00Z\n---\n\nmarkdown\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("README.md"), "# Changepacks\n").unwrap();
+
+        let logs = read_changepack_logs(dir).await.unwrap();
+        let notes: Vec<_> = logs.iter().map(|(_, log)| log.note()).collect();
+        assert_eq!(notes, ["markdown", "json"]);
+        assert_eq!(
+            logs[0].1.changes().get(Path::new("b/package.json")),
+            Some(&changepacks_core::UpdateType::Minor)
+        );
+
+        std::fs::write(dir.join("changepack_log_c.md"), "no frontmatter").unwrap();
+        let err = read_changepack_logs(dir).await.unwrap_err();
+        assert!(err.to_string().contains("changepack_log_c.md"));
+    }
+
+    #[tokio::test]
+    async fn test_read_changepack_logs_invalid_json() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("changepack_log_x.json"), "{").unwrap();
+        let err = read_changepack_logs(temp_dir.path()).await.unwrap_err();
+        assert!(err.to_string().contains("changepack_log_x.json"));
+    }
+}
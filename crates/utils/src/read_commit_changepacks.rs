@@ -0,0 +1,179 @@
+use std::{collections::HashMap, path::Path};
+
+use changepacks_core::{ChangePackLog, DiffRefs, UpdateType};
+
+use crate::run_git;
+
+/// Trailer key recording a bump intent in a commit message
+const TRAILER_KEY: &str = "Changepack";
+
+/// Changepacks recorded as `Changepack: <project>=<type>` commit trailers since `base_branch`
+///
+/// Each commit carrying trailers becomes one changepack whose note is the commit subject;
+/// several projects can be listed in one trailer (`core=minor, cli=patch`) or in repeated
+/// trailers. `<project>` is kept as written in the `changes` map, so it may be a project name
+/// or a manifest path. Commits up to the last one touching `release_manifest` are skipped,
+/// since `update` already applied them. Explicit `refs` replace `base_branch` and `HEAD`. Returns nothing outside a git repository or when neither `base_branch` nor
+/// `origin/<base_branch>` exists.
+pub async fn read_commit_changepacks(
+    current_dir: &Path,
+    base_branch: &str,
+    release_manifest: &Path,
+    refs: &DiffRefs,
+) -> Vec<ChangePackLog> {
+    let candidates = match &refs.base {
+        Some(base) => vec![base.clone()],
+        None => vec![base_branch.to_string(), format!("origin/{base_branch}")],
+    };
+    let head = refs.head.as_deref().unwrap_or("HEAD");
+    let Ok(repo) = gix::discover(current_dir) else {
+        return Vec::new();
+    };
+    let Some(base) = candidates
+        .into_iter()
+        .find(|candidate| repo.rev_parse_single(candidate.as_str()).is_ok())
+    else {
+        return Vec::new();
+    };
+    let mut range = format!("{base}..{head}");
+    let manifest = release_manifest.to_string_lossy();
+    if let Ok(applied) = run_git(
+        current_dir,
+        &["log", "-1", "--format=%H", &range, "--", &manifest],
+    )
+    .await
+        && !applied.is_empty()
+    {
+        range = format!("{applied}..{head}");
+    }
+    let format = format!("--format=%s%x1f%(trailers:key={TRAILER_KEY},valueonly)%x1e");
+    let Ok(log) = run_git(current_dir, &["log", &format, &range]).await else {
+        return Vec::new();
+    };
+    log.split('\u{1e}')
+        .filter_map(|commit| {
+            let (subject, trailers) = commit.trim().split_once('\u{1f}')?;
+            let changes = parse_trailers(trailers);
+            (!changes.is_empty()).then(|| ChangePackLog::new(changes, subject.trim().to_string()))
+        })
+        .collect()
+}
+
+/// `project=type` pairs from trailer values, ignoring malformed entries
+fn parse_trailers(trailers: &str) -> HashMap<std::path::PathBuf, UpdateType> {
+    let mut changes = HashMap::new();
+    for entry in trailers.lines().flat_map(|line| line.split(',')) {
+        let Some((project, update_type)) = entry.split_once('=') else {
+            continue;
+        };
+        let update_type = match update_type.trim().to_ascii_lowercase().as_str() {
+            "major" => UpdateType::Major,
+            "minor" => UpdateType::Minor,
+            "patch" => UpdateType::Patch,
+            _ => continue,
+        };
+        let project = project.trim();
+        if project.is_empty() {
+            continue;
+        }
+        changes
+            .entry(project.into())
+            .and_modify(|existing: &mut UpdateType| *existing = (*existing).min(update_type))
+            .or_insert(update_type);
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    fn commit(dir: &Path, message: &str) {
+        git(dir, &["commit", "-q", "--allow-empty", "-m", message]);
+    }
+
+    #[test]
+    fn test_parse_trailers() {
+        let changes = parse_trailers("core=minor, cli = Patch\ncore=major\nbad\nweb=huge\n=patch");
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[&PathBuf::from("core")], UpdateType::Major);
+        assert_eq!(changes[&PathBuf::from("cli")], UpdateType::Patch);
+    }
+
+    #[tokio::test]
+    async fn test_read_commit_changepacks() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q", "-b", "main"]);
+        git(dir, &["config", "user.email", "test@test.com"]);
+        git(dir, &["config", "user.name", "Test"]);
+        commit(dir, "Initial commit\n\nChangepack: old=major");
+        let manifest = dir.join(".changepacks/releases.json");
+
+        // On the base branch itself nothing is pending
+        assert!(
+            read_commit_changepacks(dir, "main", &manifest, &DiffRefs::default())
+                .await
+                .is_empty()
+        );
+        assert!(
+            read_commit_changepacks(dir, "trunk", &manifest, &DiffRefs::default())
+                .await
+                .is_empty()
+        );
+
+        git(dir, &["checkout", "-q", "-b", "feature"]);
+        commit(
+            dir,
+            "Add parser\n\nChangepack: core=minor\nChangepack: cli=patch",
+        );
+        commit(dir, "Refactor without release");
+        commit(
+            dir,
+            "Fix crash\n\nSigned-off-by: Test <test@test.com>\nChangepack: crates/cli/Cargo.toml=patch",
+        );
+
+        let mut logs = read_commit_changepacks(dir, "main", &manifest, &DiffRefs::default()).await;
+        logs.sort_by(|a, b| a.note().cmp(b.note()));
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].note(), "Add parser");
+        assert_eq!(logs[0].changes()[&PathBuf::from("core")], UpdateType::Minor);
+        assert_eq!(logs[0].changes()[&PathBuf::from("cli")], UpdateType::Patch);
+        assert_eq!(logs[1].note(), "Fix crash");
+        assert_eq!(
+            logs[1].changes()[&PathBuf::from("crates/cli/Cargo.toml")],
+            UpdateType::Patch
+        );
+
+        // Explicit refs replace the base branch and HEAD
+        let refs = DiffRefs {
+            base: Some("main".to_string()),
+            head: Some("HEAD~1".to_string()),
+        };
+        let logs = read_commit_changepacks(dir, "trunk", &manifest, &refs).await;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].note(), "Add parser");
+
+        // Trailers already applied by an update are not counted again
+        std::fs::create_dir_all(dir.join(".changepacks")).unwrap();
+        std::fs::write(&manifest, "{}").unwrap();
+        git(dir, &["add", "."]);
+        commit(dir, "Version Packages");
+        commit(dir, "Follow-up\n\nChangepack: web=patch");
+        let logs = read_commit_changepacks(dir, "main", &manifest, &DiffRefs::default()).await;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].note(), "Follow-up");
+    }
+}
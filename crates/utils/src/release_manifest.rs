@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use changepacks_core::{RELEASE_MANIFEST_FILE, ReleaseManifest, ReleaseRecord};
+
+use crate::{read_file, write_json_file};
+
+/// Read `.changepacks/releases.json`, returning an empty manifest when it does not exist yet
+///
+/// # Errors
+/// Returns error if the manifest exists but cannot be read or parsed.
+pub async fn read_release_manifest(changepacks_dir: &Path) -> Result<ReleaseManifest> {
+    let manifest_file = changepacks_dir.join(RELEASE_MANIFEST_FILE);
+    if !manifest_file.exists() {
+        return Ok(ReleaseManifest::default());
+    }
+    let content = read_file(&manifest_file).await?;
+    if content.trim().is_empty() {
+        return Ok(ReleaseManifest::default());
+    }
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {RELEASE_MANIFEST_FILE}"))
+}
+
+/// Append a release record to `.changepacks/releases.json`, creating the file if needed
+///
+/// # Errors
+/// Returns error if the existing manifest cannot be parsed or the file cannot be written.
+pub async fn append_release_record(changepacks_dir: &Path, record: ReleaseRecord) -> Result<()> {
+    let mut manifest = read_release_manifest(changepacks_dir).await?;
+    manifest.push(record);
+
+    write_json_file(changepacks_dir.join(RELEASE_MANIFEST_FILE), &manifest).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::{ReleasedPackage, UpdateType};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn record(version: &str) -> ReleaseRecord {
+        ReleaseRecord::new(vec![ReleasedPackage::new(
+            Some("core".to_string()),
+            PathBuf::from("packages/core/package.json"),
+            Some("1.0.0".to_string()),
+            version.to_string(),
+            UpdateType::Minor,
+            vec!["Add feature".to_string()],
+        )])
+    }
+
+    #[tokio::test]
+    async fn test_read_release_manifest_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = read_release_manifest(temp_dir.path()).await.unwrap();
+        assert!(manifest.releases().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_release_manifest_empty_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(RELEASE_MANIFEST_FILE), "\n").unwrap();
+        let manifest = read_release_manifest(temp_dir.path()).await.unwrap();
+        assert!(manifest.releases().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_release_manifest_invalid() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(RELEASE_MANIFEST_FILE), "not json").unwrap();
+        assert!(read_release_manifest(temp_dir.path()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_append_release_record_appends() {
+        let temp_dir = TempDir::new().unwrap();
+        append_release_record(temp_dir.path(), record("1.1.0"))
+            .await
+            .unwrap();
+        append_release_record(temp_dir.path(), record("1.2.0"))
+            .await
+            .unwrap();
+
+        let manifest = read_release_manifest(temp_dir.path()).await.unwrap();
+        assert_eq!(manifest.releases().len(), 2);
+        assert_eq!(manifest.releases()[0].packages()[0].version(), "1.1.0");
+        assert_eq!(manifest.releases()[1].packages()[0].version(), "1.2.0");
+
+        let raw = std::fs::read_to_string(temp_dir.path().join(RELEASE_MANIFEST_FILE)).unwrap();
+        assert!(raw.starts_with("{\n  \"releases\": ["));
+        assert!(raw.ends_with("}\n"));
+    }
+}
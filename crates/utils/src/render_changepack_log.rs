@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use changepacks_core::{ChangePackLog, ChangepackFormat};
+
+use crate::CHANGEPACK_LOG_PREFIX;
+
+/// Path of the changepack log `changepack_log_<id>` written in `format`
+#[must_use]
+pub fn changepack_log_file(changepacks_dir: &Path, id: &str, format: ChangepackFormat) -> PathBuf {
+    changepacks_dir.join(format!(
+        "{CHANGEPACK_LOG_PREFIX}{id}.{}",
+        format.extension()
+    ))
+}
+
+/// File content of a changepack log in `format`
+///
+/// # Errors
+/// Returns error if the log cannot be serialized.
+pub fn render_changepack_log(log: &ChangePackLog, format: ChangepackFormat) -> Result<String> {
+    match format {
+        ChangepackFormat::Json => Ok(serde_json::to_string(log)?),
+        ChangepackFormat::Markdown => log.to_markdown(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::UpdateType;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_changepack_log_file() {
+        let dir = Path::new(".changepacks");
+        assert_eq!(
+            changepack_log_file(dir, "abc", ChangepackFormat::Json),
+            dir.join("changepack_log_abc.json")
+        );
+        assert_eq!(
+            changepack_log_file(dir, "pr-7", ChangepackFormat::Markdown),
+            dir.join("changepack_log_pr-7.md")
+        );
+    }
+
+    #[test]
+    fn test_render_changepack_log() {
+        let log = ChangePackLog::new(
+            HashMap::from([(PathBuf::from("package.json"), UpdateType::Patch)]),
+            "Fix".to_string(),
+        );
+        let json = render_changepack_log(&log, ChangepackFormat::Json).unwrap();
+        let parsed: ChangePackLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.changes(), log.changes());
+
+        let markdown = render_changepack_log(&log, ChangepackFormat::Markdown).unwrap();
+        let parsed = ChangePackLog::from_markdown(&markdown, Utc::now()).unwrap();
+        assert_eq!(parsed.changes(), log.changes());
+        assert_eq!(parsed.note(), "Fix");
+    }
+}
@@ -0,0 +1,306 @@
+use changepacks_core::ChangePackResultLog;
+use colored::Colorize;
+
+/// Column width notes are wrapped to in `check` and `update` output
+const NOTE_WIDTH: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Code,
+    Bold,
+}
+
+/// A word made of styled pieces with no whitespace between them (e.g. "`foo`.")
+type Word = Vec<(String, Style)>;
+
+/// Render a markdown changepack note for terminal output
+///
+/// Paragraphs and list items are wrapped to `width` columns (list items with a hanging
+/// indent), `-`/`*`/`+` bullets become `•`, headings and `**bold**` are bold, and inline
+/// `code` and fenced code blocks are highlighted. Every line is indented by `indent` spaces.
+#[must_use]
+pub fn render_note(note: &str, indent: usize, width: usize) -> String {
+    let pad = " ".repeat(indent);
+    let mut out = String::new();
+    let mut block: Option<(String, String)> = None; // (first-line prefix, text)
+    let mut in_fence = false;
+    let mut blank_pending = false;
+
+    for line in note.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            flush_block(&mut out, &mut block, &pad, width);
+            if blank_pending {
+                out.push('\n');
+                blank_pending = false;
+            }
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            out.push_str(&format!("{pad}  {}\n", line.cyan()));
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush_block(&mut out, &mut block, &pad, width);
+            blank_pending = !out.is_empty();
+            continue;
+        }
+        if blank_pending {
+            out.push('\n');
+            blank_pending = false;
+        }
+        if let Some(heading) = heading_text(trimmed) {
+            flush_block(&mut out, &mut block, &pad, width);
+            out.push_str(&format!("{pad}{}\n", heading.bold()));
+        } else if let Some((marker, text)) = list_item(trimmed) {
+            flush_block(&mut out, &mut block, &pad, width);
+            block = Some((marker, text.to_string()));
+        } else if let Some((_, text)) = block.as_mut() {
+            // Continuation of the current paragraph or list item
+            text.push(' ');
+            text.push_str(trimmed);
+        } else {
+            block = Some((String::new(), trimmed.to_string()));
+        }
+    }
+    flush_block(&mut out, &mut block, &pad, width);
+    out
+}
+
+/// Render the notes of every changepack for a package, one list item per changepack
+///
+/// Notes that are not already a list or heading become a bullet; template fields follow
+/// their note as dimmed `key: value` lines.
+#[must_use]
+pub fn render_changepack_notes(logs: &[ChangePackResultLog], indent: usize) -> String {
+    let mut out = String::new();
+    for log in logs {
+        let note = log.note().trim();
+        if note.is_empty() {
+            continue;
+        }
+        let first_line = note.lines().next().unwrap_or_default().trim();
+        if list_item(first_line).is_some()
+            || heading_text(first_line).is_some()
+            || first_line.starts_with("```")
+        {
+            out.push_str(&render_note(note, indent, NOTE_WIDTH));
+        } else {
+            out.push_str(&render_note(&format!("- {note}"), indent, NOTE_WIDTH));
+        }
+        for (key, value) in log.fields() {
+            out.push_str(&format!(
+                "{}{}\n",
+                " ".repeat(indent + 2),
+                format!("{key}: {value}").bright_black()
+            ));
+        }
+    }
+    out
+}
+
+fn heading_text(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    (text.len() < line.len() && text.starts_with(' ')).then(|| text.trim())
+}
+
+/// Split a list item into its display marker ("• " or "1. ") and text
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(text) = line.strip_prefix(bullet) {
+            return Some(("• ".to_string(), text));
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = &line[digits..];
+    if digits > 0
+        && let Some(text) = rest.strip_prefix(". ")
+    {
+        return Some((format!("{}. ", &line[..digits]), text));
+    }
+    None
+}
+
+fn flush_block(out: &mut String, block: &mut Option<(String, String)>, pad: &str, width: usize) {
+    let Some((marker, text)) = block.take() else {
+        return;
+    };
+    let hang = " ".repeat(marker.chars().count());
+    let available = width
+        .saturating_sub(pad.len() + marker.chars().count())
+        .max(1);
+    for (index, line) in wrap_words(&inline_words(&text), available)
+        .iter()
+        .enumerate()
+    {
+        let prefix = if index == 0 { &marker } else { &hang };
+        out.push_str(&format!("{pad}{prefix}{line}\n"));
+    }
+}
+
+/// Split text into words, tracking `code` and **bold** spans
+fn inline_words(text: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut word: Word = Vec::new();
+    let mut piece = String::new();
+    let mut style = Style::Plain;
+    let mut chars = text.chars().peekable();
+
+    let end_piece = |word: &mut Word, piece: &mut String, style: Style| {
+        if !piece.is_empty() {
+            word.push((std::mem::take(piece), style));
+        }
+    };
+    while let Some(c) = chars.next() {
+        if c == '`' && style != Style::Bold {
+            end_piece(&mut word, &mut piece, style);
+            style = if style == Style::Code {
+                Style::Plain
+            } else {
+                Style::Code
+            };
+        } else if c == '*' && style != Style::Code && chars.peek() == Some(&'*') {
+            chars.next();
+            end_piece(&mut word, &mut piece, style);
+            style = if style == Style::Bold {
+                Style::Plain
+            } else {
+                Style::Bold
+            };
+        } else if c.is_whitespace() {
+            end_piece(&mut word, &mut piece, style);
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else {
+            piece.push(c);
+        }
+    }
+    end_piece(&mut word, &mut piece, style);
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn word_width(word: &Word) -> usize {
+    word.iter().map(|(text, _)| text.chars().count()).sum()
+}
+
+fn styled(word: &Word) -> String {
+    word.iter()
+        .map(|(text, style)| match style {
+            Style::Plain => text.clone(),
+            Style::Code => text.cyan().to_string(),
+            Style::Bold => text.bold().to_string(),
+        })
+        .collect()
+}
+
+/// Greedy word wrap; a word longer than `width` gets a line of its own
+fn wrap_words(words: &[Word], width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for word in words {
+        let len = word_width(word);
+        if line_width > 0 && line_width + 1 + len > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(&styled(word));
+        line_width += len;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drop ANSI color sequences so assertions see the visible text
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                for c in chars.by_ref() {
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_render_note_plain() {
+        assert_eq!(
+            strip_ansi(&render_note("Fix crash", 2, 80)),
+            "  Fix crash\n"
+        );
+    }
+
+    #[test]
+    fn test_render_note_wraps_bullets_with_hanging_indent() {
+        let note = "- Add streaming support for very large payloads\n* Fix `parse()` crash";
+        assert_eq!(
+            strip_ansi(&render_note(note, 2, 30)),
+            "  • Add streaming support for\n    very large payloads\n  • Fix parse() crash\n"
+        );
+    }
+
+    #[test]
+    fn test_render_note_paragraphs_headings_and_fences() {
+        let note = "## Breaking\n\nThe `Config` type\nwas **renamed**.\n\n1. Update imports\n\n```rust\nuse a::Settings;\n```";
+        assert_eq!(
+            strip_ansi(&render_note(note, 0, 80)),
+            "Breaking\n\nThe Config type was renamed.\n\n1. Update imports\n\n  use a::Settings;\n"
+        );
+    }
+
+    #[test]
+    fn test_render_note_highlights_inline_code() {
+        let rendered = render_note("Use `foo`.", 0, 80);
+        assert!(rendered.contains(&"foo".cyan().to_string()));
+        assert!(strip_ansi(&rendered).contains("Use foo."));
+    }
+
+    #[test]
+    fn test_render_changepack_notes() {
+        use changepacks_core::UpdateType;
+        use std::collections::BTreeMap;
+
+        let logs = [
+            ChangePackResultLog::new(UpdateType::Patch, "Fix crash".to_string())
+                .with_fields(BTreeMap::from([("issue".to_string(), "ABC-1".to_string())])),
+            ChangePackResultLog::new(UpdateType::Minor, "- One\n- Two".to_string()),
+            ChangePackResultLog::new(UpdateType::Patch, String::new()),
+        ];
+        assert_eq!(
+            strip_ansi(&render_changepack_notes(&logs, 4)),
+            "    • Fix crash\n      issue: ABC-1\n    • One\n    • Two\n"
+        );
+    }
+
+    #[test]
+    fn test_render_note_long_word() {
+        let word = "x".repeat(20);
+        assert_eq!(
+            strip_ansi(&render_note(&format!("a {word} b"), 0, 10)),
+            format!("a\n{word}\nb\n")
+        );
+    }
+}
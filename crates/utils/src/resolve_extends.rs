@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use changepacks_core::ConfigFileFormat;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{run_git, run_git_output};
+
+/// Load the base config named by an `extends` entry
+///
+/// `source` is an HTTPS URL, a git reference (`git+<url>#<ref>:<path>`, defaulting to `HEAD` and
+/// `.changepacks/config.json`), or a path relative to the project root. Remote sources are cached
+/// under the user cache directory; with an `integrity` hash (`sha256:<hex>`) a cached copy is
+/// reused without fetching, and any fetched content must match it.
+///
+/// # Errors
+/// Returns error if the source cannot be fetched (and is not cached), fails the integrity
+/// check, or is not valid JSON (or TOML or YAML, for sources ending in `.toml`, `.yaml` or
+/// `.yml`).
+pub async fn resolve_extends(
+    project_root: &Path,
+    source: &str,
+    integrity: Option<&str>,
+) -> Result<Value> {
+    resolve_extends_with_cache(project_root, source, integrity, &default_cache_dir()).await
+}
+
+async fn resolve_extends_with_cache(
+    project_root: &Path,
+    source: &str,
+    integrity: Option<&str>,
+    cache_dir: &Path,
+) -> Result<Value> {
+    let content = if is_remote(source) {
+        load_remote(source, integrity, cache_dir).await?
+    } else {
+        let content = tokio::fs::read(project_root.join(source))
+            .await
+            .with_context(|| format!("Failed to read extended config '{source}'"))?;
+        if let Some(integrity) = integrity {
+            verify_integrity(source, &content, integrity)?;
+        }
+        content
+    };
+    let content = String::from_utf8(content)
+        .with_context(|| format!("Failed to parse extended config '{source}'"))?;
+    extends_format(source)
+        .parse(&content)
+        .with_context(|| format!("Failed to parse extended config '{source}'"))
+}
+
+/// Syntax of an `extends` source by its file extension, JSON unless it names a TOML or YAML file
+fn extends_format(source: &str) -> ConfigFileFormat {
+    let path = match source.strip_prefix("git+") {
+        Some(git_ref) => parse_git_source(git_ref).2,
+        None => source.split(['?', '#']).next().unwrap_or(source),
+    };
+    ConfigFileFormat::from_path(Path::new(path)).unwrap_or(ConfigFileFormat::Json)
+}
+
+/// Whether an `extends` source is fetched (an HTTPS URL or a git reference) rather than read
+/// from the project
+pub(crate) fn is_remote(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("git+")
+}
+
+async fn load_remote(source: &str, integrity: Option<&str>, cache_dir: &Path) -> Result<Vec<u8>> {
+    let cache_file = cache_dir.join(format!("{}.json", sha256_hex(source.as_bytes())));
+    let cached = tokio::fs::read(&cache_file).await.ok();
+    if let (Some(cached), Some(integrity)) = (&cached, integrity)
+        && verify_integrity(source, cached, integrity).is_ok()
+    {
+        return Ok(cached.clone());
+    }
+
+    let fetched = match source.strip_prefix("git+") {
+        Some(git_ref) => fetch_git(git_ref).await,
+        None => {
+            let owned_source = source.to_string();
+            tokio::task::spawn_blocking(move || fetch_https(&owned_source)).await?
+        }
+    };
+    let content = match (fetched, cached) {
+        (Ok(content), _) => content,
+        // Offline: fall back to the last copy we fetched
+        (Err(_), Some(cached)) if integrity.is_none() => cached,
+        (Err(e), _) => return Err(e),
+    };
+    if let Some(integrity) = integrity {
+        verify_integrity(source, &content, integrity)?;
+    }
+    tokio::fs::create_dir_all(cache_dir).await?;
+    tokio::fs::write(&cache_file, &content).await?;
+    Ok(content)
+}
+
+fn fetch_https(source: &str) -> Result<Vec<u8>> {
+    let mut response = ureq::get(source)
+        .call()
+        .with_context(|| format!("Failed to fetch extended config '{source}'"))?;
+    Ok(response.body_mut().read_to_vec()?)
+}
+
+/// Fetch a single file from a git remote via a shallow fetch into a scratch repository
+async fn fetch_git(git_ref: &str) -> Result<Vec<u8>> {
+    let (url, rev, path) = parse_git_source(git_ref);
+    let scratch = std::env::temp_dir().join(format!(
+        "changepacks-extends-{}-{}",
+        std::process::id(),
+        sha256_hex(git_ref.as_bytes())
+    ));
+    let result = async {
+        tokio::fs::create_dir_all(&scratch).await?;
+        run_git(&scratch, &["init", "--quiet"]).await?;
+        run_git(&scratch, &["fetch", "--quiet", "--depth", "1", url, rev]).await?;
+        run_git_output(&scratch, &["show", &format!("FETCH_HEAD:{path}")]).await
+    }
+    .await;
+    let _ = tokio::fs::remove_dir_all(&scratch).await;
+    result.with_context(|| format!("Failed to fetch extended config 'git+{git_ref}'"))
+}
+
+/// Split `<url>#<ref>:<path>` into its parts, applying defaults for the optional ones
+fn parse_git_source(git_ref: &str) -> (&str, &str, &str) {
+    let (url, fragment) = git_ref.rsplit_once('#').unwrap_or((git_ref, ""));
+    let (rev, path) = fragment.split_once(':').unwrap_or((fragment, ""));
+    (
+        url,
+        if rev.is_empty() { "HEAD" } else { rev },
+        if path.is_empty() {
+            ".changepacks/config.json"
+        } else {
+            path
+        },
+    )
+}
+
+fn verify_integrity(source: &str, content: &[u8], integrity: &str) -> Result<()> {
+    let Some(expected) = integrity.strip_prefix("sha256:") else {
+        bail!("Unsupported integrity '{integrity}' for '{source}', expected 'sha256:<hex>'");
+    };
+    let actual = sha256_hex(content);
+    if !actual.eq_ignore_ascii_case(expected) {
+        bail!(
+            "Integrity check failed for '{source}': expected sha256:{expected}, got sha256:{actual}"
+        );
+    }
+    Ok(())
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn default_cache_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::home_dir().map(|home| home.join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("changepacks")
+        .join("extends")
+}
+
+/// Overlay `local` onto `base`: objects merge key by key, any other value in `local` wins
+#[must_use]
+pub fn merge_config_values(base: Value, local: Value) -> Value {
+    match (base, local) {
+        (Value::Object(mut base), Value::Object(local)) => {
+            for (key, value) in local {
+                let merged = match base.remove(&key) {
+                    Some(base_value) => merge_config_values(base_value, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Value::Object(base)
+        }
+        (_, local) => local,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[rstest]
+    #[case("https://example.com/a.git#v1:cfg.json", ("https://example.com/a.git", "v1", "cfg.json"))]
+    #[case("https://example.com/a.git#v1", ("https://example.com/a.git", "v1", ".changepacks/config.json"))]
+    #[case("https://example.com/a.git", ("https://example.com/a.git", "HEAD", ".changepacks/config.json"))]
+    #[case("/srv/policy#:shared.json", ("/srv/policy", "HEAD", "shared.json"))]
+    fn test_parse_git_source(#[case] source: &str, #[case] expected: (&str, &str, &str)) {
+        assert_eq!(parse_git_source(source), expected);
+    }
+
+    #[rstest]
+    #[case("shared.json", ConfigFileFormat::Json)]
+    #[case("config/shared.toml", ConfigFileFormat::Toml)]
+    #[case("https://example.com/changepacks.yaml?raw=1", ConfigFileFormat::Yaml)]
+    #[case("git+https://example.com/a.git#v1:policy.yml", ConfigFileFormat::Yaml)]
+    #[case("git+https://example.com/a.git#v1", ConfigFileFormat::Json)]
+    #[case("https://example.com/policy", ConfigFileFormat::Json)]
+    fn test_extends_format(#[case] source: &str, #[case] expected: ConfigFileFormat) {
+        assert_eq!(extends_format(source), expected);
+    }
+
+    #[test]
+    fn test_merge_config_values() {
+        let base = json!({
+            "baseBranch": "develop",
+            "ignore": ["a"],
+            "publish": {"node": "npm publish", "rust": "cargo publish"}
+        });
+        let local = json!({
+            "ignore": ["b"],
+            "publish": {"node": "pnpm publish"}
+        });
+        assert_eq!(
+            merge_config_values(base, local),
+            json!({
+                "baseBranch": "develop",
+                "ignore": ["b"],
+                "publish": {"node": "pnpm publish", "rust": "cargo publish"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_integrity() {
+        let hash = sha256_hex(b"{}");
+        assert!(verify_integrity("s", b"{}", &format!("sha256:{hash}")).is_ok());
+        assert!(verify_integrity("s", b"{}", &format!("sha256:{}", hash.to_uppercase())).is_ok());
+        assert!(verify_integrity("s", b"[]", &format!("sha256:{hash}")).is_err());
+        assert!(verify_integrity("s", b"{}", "md5:abc").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_extends_local_path() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("base.json"),
+            r#"{"baseBranch": "trunk"}"#,
+        )
+        .unwrap();
+
+        let value = resolve_extends(temp_dir.path(), "base.json", None)
+            .await
+            .unwrap();
+        assert_eq!(value, json!({"baseBranch": "trunk"}));
+
+        assert!(
+            resolve_extends(temp_dir.path(), "base.json", Some("sha256:00"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_extends_uses_cache_matching_integrity() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join("cache");
+        // Unreachable source: only the cached copy can satisfy it
+        let source = "https://invalid.invalid/config.json";
+        let content = br#"{"baseBranch": "cached"}"#;
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        std::fs::write(
+            cache_dir.join(format!("{}.json", sha256_hex(source.as_bytes()))),
+            content,
+        )
+        .unwrap();
+
+        let integrity = format!("sha256:{}", sha256_hex(content));
+        let value =
+            resolve_extends_with_cache(temp_dir.path(), source, Some(&integrity), &cache_dir)
+                .await
+                .unwrap();
+        assert_eq!(value, json!({"baseBranch": "cached"}));
+
+        let wrong = format!("sha256:{}", sha256_hex(b"other"));
+        assert!(
+            resolve_extends_with_cache(temp_dir.path(), source, Some(&wrong), &cache_dir)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_extends_git_reference() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy = temp_dir.path().join("policy");
+        std::fs::create_dir_all(&policy).unwrap();
+        for args in [
+            vec!["init", "-b", "main"],
+            vec!["config", "user.email", "test@test.com"],
+            vec!["config", "user.name", "Test"],
+        ] {
+            run_git(&policy, &args).await.unwrap();
+        }
+        std::fs::write(policy.join("shared.json"), r#"{"baseBranch": "release"}"#).unwrap();
+        run_git(&policy, &["add", "."]).await.unwrap();
+        run_git(&policy, &["commit", "-m", "policy"]).await.unwrap();
+        run_git(&policy, &["tag", "v1"]).await.unwrap();
+
+        let cache_dir = temp_dir.path().join("cache");
+        let source = format!("git+{}#v1:shared.json", policy.display());
+        let value = resolve_extends_with_cache(temp_dir.path(), &source, None, &cache_dir)
+            .await
+            .unwrap();
+        assert_eq!(value, json!({"baseBranch": "release"}));
+        assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,280 @@
+use changepacks_core::RangeStrategy;
+
+use crate::split_version;
+
+/// Operators kept verbatim in front of a rewritten version
+const OPERATORS: [&str; 6] = ["", "^", "~", ">=", "=", "v"];
+
+/// Rewrite a dependency version requirement to point at `version`, keeping its style
+///
+/// `^1.2.3`, `~1.2.3`, `>=1.2.3`, `=1.2.3` and exact `1.2.3` keep their operator. Partial
+/// (`^1.2`) and wildcard (`1.2.x`, `1.*`) requirements keep their precision, unless `version`
+/// is a prerelease, which a partial requirement could not match. The pnpm `workspace:`
+/// protocol and NuGet `[1.2.3]` / `[1.2.3,)` brackets are preserved as well.
+///
+/// Returns `None` for requirements that should be left untouched: `*`, `latest`,
+/// `workspace:^`, compound ranges such as `>=1.0.0 <2.0.0` or `1.x || 2.x`, and
+/// anything else that doesn't name a single version.
+#[must_use]
+pub fn rewrite_version_range(range: &str, version: &str) -> Option<String> {
+    let range = range.trim();
+    if let Some(rest) = range.strip_prefix("workspace:") {
+        return rewrite_version_range(rest, version).map(|rest| format!("workspace:{rest}"));
+    }
+    if let Some(inner) = range.strip_prefix('[') {
+        // NuGet: exact `[1.2.3]` or minimum-inclusive `[1.2.3,)`
+        let (inner, suffix) = inner
+            .strip_suffix(",)")
+            .map(|inner| (inner, ",)"))
+            .or_else(|| inner.strip_suffix(']').map(|inner| (inner, "]")))?;
+        return rewrite_exact(inner, version).map(|inner| format!("[{inner}{suffix}"));
+    }
+    if range.contains(|c: char| c.is_whitespace() || matches!(c, '|' | ',' | '<' | '(' | ')')) {
+        return None;
+    }
+
+    let (prefix, current) = split_version(range).ok()?;
+    let prefix = prefix.unwrap_or_default();
+    if !OPERATORS.contains(&prefix.as_str()) {
+        return None;
+    }
+    let core = current.split(['-', '+']).next().unwrap_or_default();
+    let parts: Vec<&str> = core.split('.').collect();
+    let concrete = parts
+        .iter()
+        .take_while(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+        .count();
+    let wildcards = &parts[concrete..];
+    if concrete == 0
+        || parts.len() > 3
+        || !wildcards
+            .iter()
+            .all(|part| matches!(*part, "x" | "X" | "*"))
+    {
+        return None;
+    }
+    let release = version.split('+').next().unwrap_or_default();
+    if (parts.len() == 3 && wildcards.is_empty()) || release.contains('-') {
+        return Some(format!("{prefix}{version}"));
+    }
+    if current.contains('-') {
+        // `1.2-beta` isn't a version any tool understands
+        return None;
+    }
+
+    let mut rewritten: Vec<&str> = release.split('.').take(concrete).collect();
+    if rewritten.len() < concrete {
+        return None;
+    }
+    rewritten.extend_from_slice(wildcards);
+    Some(format!("{prefix}{}", rewritten.join(".")))
+}
+
+/// [`rewrite_version_range`] with the operator chosen by `strategy`
+///
+/// With a strategy other than `Preserve`, a requirement that names a single version becomes
+/// `^version`, `~version` or `version`, keeping the `workspace:` protocol. Requirements
+/// [`rewrite_version_range`] leaves untouched are left untouched here too.
+#[must_use]
+pub fn rewrite_dependency_range(
+    range: &str,
+    version: &str,
+    strategy: RangeStrategy,
+) -> Option<String> {
+    let rewritten = rewrite_version_range(range, version)?;
+    let operator = match strategy {
+        RangeStrategy::Preserve => return Some(rewritten),
+        RangeStrategy::Caret => "^",
+        RangeStrategy::Tilde => "~",
+        RangeStrategy::Exact => "",
+    };
+    let protocol = if rewritten.starts_with("workspace:") {
+        "workspace:"
+    } else {
+        ""
+    };
+    Some(format!("{protocol}{operator}{version}"))
+}
+
+/// Whether `version` satisfies a single-version npm requirement such as `^1.2.0`, `~1.2`,
+/// `>=1.0.0` or `1.x`
+///
+/// Returns `None` for the requirements [`rewrite_version_range`] leaves untouched, and for
+/// versions that aren't plain `MAJOR.MINOR.PATCH`. A prerelease `version` satisfies nothing,
+/// as in npm.
+#[must_use]
+pub fn range_allows(range: &str, version: &str) -> Option<bool> {
+    let range = range.trim();
+    let range = range.strip_prefix("workspace:").unwrap_or(range);
+    if range.contains(|c: char| c.is_whitespace() || matches!(c, '|' | ',' | '<' | '(' | ')' | '['))
+    {
+        return None;
+    }
+    let (prefix, current) = split_version(range).ok()?;
+    let prefix = prefix.unwrap_or_default();
+    if !OPERATORS.contains(&prefix.as_str()) || current.contains(['-', '+']) {
+        return None;
+    }
+    let bound: Vec<u64> = current
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect();
+    let parts = current.split('.').count();
+    if bound.is_empty()
+        || parts > 3
+        || !current
+            .split('.')
+            .skip(bound.len())
+            .all(|part| matches!(part, "x" | "X" | "*"))
+    {
+        return None;
+    }
+    let release = version.split('+').next().unwrap_or_default();
+    if release.contains('-') {
+        return Some(false);
+    }
+    let version: Vec<u64> = release
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    let [major, minor, patch] = version[..] else {
+        return None;
+    };
+    let lower = [
+        bound[0],
+        bound.get(1).copied().unwrap_or(0),
+        bound.get(2).copied().unwrap_or(0),
+    ];
+    let at_least = [major, minor, patch] >= lower;
+    // Components of the bound that must match exactly
+    let fixed = match prefix.as_str() {
+        ">=" => 0,
+        "~" => bound.len().min(2),
+        "^" => bound
+            .iter()
+            .position(|part| *part != 0)
+            .map_or(bound.len(), |position| position + 1),
+        _ => bound.len(),
+    };
+    Some(at_least && [major, minor, patch][..fixed] == bound[..fixed])
+}
+
+/// Rewrite a bare `MAJOR.MINOR.PATCH` requirement, rejecting anything with an operator
+fn rewrite_exact(range: &str, version: &str) -> Option<String> {
+    range
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| rewrite_version_range(range, version))
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1.2.3", "1.3.0", Some("1.3.0"))]
+    #[case("^1.2.3", "1.3.0", Some("^1.3.0"))]
+    #[case("~1.2.3", "1.3.0", Some("~1.3.0"))]
+    #[case(">=1.2.3", "2.0.0", Some(">=2.0.0"))]
+    #[case("=1.2.3", "1.2.4", Some("=1.2.4"))]
+    #[case("v1.2.3", "1.2.4", Some("v1.2.4"))]
+    #[case(" ^1.2.3 ", "1.2.4", Some("^1.2.4"))]
+    #[case("^1.2.3-beta.1", "1.2.3", Some("^1.2.3"))]
+    #[case("1.2.3+build.1", "1.2.4", Some("1.2.4"))]
+    #[case("^1.2", "1.3.0", Some("^1.3"))]
+    #[case("~1", "2.0.0", Some("~2"))]
+    #[case("1.2.x", "1.3.0", Some("1.3.x"))]
+    #[case("1.x", "2.0.0", Some("2.x"))]
+    #[case("1.X.X", "2.0.0", Some("2.X.X"))]
+    #[case("1.*", "2.1.0", Some("2.*"))]
+    #[case("1.2.x", "1.3.0-rc.1", Some("1.3.0-rc.1"))]
+    #[case("^1.2", "2.0.0-rc.1", Some("^2.0.0-rc.1"))]
+    #[case("1.2.x", "1.3.0+build.5", Some("1.3.x"))]
+    #[case("workspace:^1.2.3", "1.3.0", Some("workspace:^1.3.0"))]
+    #[case("workspace:1.2.3", "1.3.0", Some("workspace:1.3.0"))]
+    #[case("[1.2.3]", "1.3.0", Some("[1.3.0]"))]
+    #[case("[1.2.3,)", "1.3.0", Some("[1.3.0,)"))]
+    #[case("*", "1.3.0", None)]
+    #[case("x", "1.3.0", None)]
+    #[case("latest", "1.3.0", None)]
+    #[case("any", "1.3.0", None)]
+    #[case("workspace:*", "1.3.0", None)]
+    #[case("workspace:^", "1.3.0", None)]
+    #[case(">=1.0.0 <2.0.0", "1.3.0", None)]
+    #[case("1.x || 2.x", "1.3.0", None)]
+    #[case(">1.2.3", "1.3.0", None)]
+    #[case("<2.0.0", "1.3.0", None)]
+    #[case("[1.0,2.0)", "1.3.0", None)]
+    #[case("[^1.2.3]", "1.3.0", None)]
+    #[case("(1.2.3,)", "1.3.0", None)]
+    #[case("1.x.2", "1.3.0", None)]
+    #[case("1.2.3.4", "1.3.0", None)]
+    #[case("1.2-beta", "1.3.0", None)]
+    #[case("helloworld-1.0.2", "1.3.0", None)]
+    #[case("file:../core", "1.3.0", None)]
+    #[case("^1.2", "1", None)]
+    fn test_rewrite_version_range(
+        #[case] range: &str,
+        #[case] version: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            rewrite_version_range(range, version).as_deref(),
+            expected,
+            "{range} -> {version}"
+        );
+    }
+
+    #[rstest]
+    #[case("^1.2.0", "1.9.3", Some(true))]
+    #[case("^1.2.0", "1.1.9", Some(false))]
+    #[case("^1.2.0", "2.0.0", Some(false))]
+    #[case("^0.2.1", "0.2.5", Some(true))]
+    #[case("^0.2.1", "0.3.0", Some(false))]
+    #[case("^0.0.3", "0.0.4", Some(false))]
+    #[case("^1", "1.5.0", Some(true))]
+    #[case("~1.2.0", "1.2.7", Some(true))]
+    #[case("~1.2.0", "1.3.0", Some(false))]
+    #[case("~1", "1.9.0", Some(true))]
+    #[case(">=1.2.0", "3.0.0", Some(true))]
+    #[case(">=1.2.0", "1.1.0", Some(false))]
+    #[case("1.2.3", "1.2.3", Some(true))]
+    #[case("=1.2.3", "1.2.4", Some(false))]
+    #[case("1.x", "1.4.0", Some(true))]
+    #[case("1.2.x", "1.3.0", Some(false))]
+    #[case("workspace:^1.0.0", "1.3.0", Some(true))]
+    #[case("^1.0.0", "1.3.0-rc.1", Some(false))]
+    #[case("^1.0.0", "1.3.0+build.5", Some(true))]
+    #[case("*", "1.3.0", None)]
+    #[case(">=1.0.0 <2.0.0", "1.3.0", None)]
+    #[case("^1.0.0-beta.1", "1.3.0", None)]
+    fn test_range_allows(
+        #[case] range: &str,
+        #[case] version: &str,
+        #[case] expected: Option<bool>,
+    ) {
+        assert_eq!(range_allows(range, version), expected, "{range} {version}");
+    }
+
+    #[rstest]
+    #[case("^1.2.3", RangeStrategy::Preserve, Some("^1.3.0"))]
+    #[case("1.2.x", RangeStrategy::Preserve, Some("1.3.x"))]
+    #[case("1.2.3", RangeStrategy::Caret, Some("^1.3.0"))]
+    #[case("^1.2", RangeStrategy::Tilde, Some("~1.3.0"))]
+    #[case("~1.2.3", RangeStrategy::Exact, Some("1.3.0"))]
+    #[case("workspace:^1.2.3", RangeStrategy::Tilde, Some("workspace:~1.3.0"))]
+    #[case("workspace:*", RangeStrategy::Caret, None)]
+    #[case(">=1.0.0 <2.0.0", RangeStrategy::Exact, None)]
+    fn test_rewrite_dependency_range(
+        #[case] range: &str,
+        #[case] strategy: RangeStrategy,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            rewrite_dependency_range(range, "1.3.0", strategy).as_deref(),
+            expected
+        );
+    }
+}
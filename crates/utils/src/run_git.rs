@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use tokio::process::Command;
+
+/// Run `git` with `args` in `repo_root` and return its trimmed standard output
+///
+/// Only for what gix cannot do (staging, committing, fetching and pushing); read local
+/// repository state through gix instead.
+///
+/// # Errors
+/// Returns error if `git` cannot be run or exits unsuccessfully; the message includes git's stderr.
+pub async fn run_git(repo_root: &Path, args: &[&str]) -> Result<String> {
+    let stdout = run_git_output(repo_root, args).await?;
+    Ok(String::from_utf8_lossy(&stdout).trim().to_string())
+}
+
+/// Run `git` like [`run_git`], returning its standard output byte for byte
+///
+/// # Errors
+/// Returns error if `git` cannot be run or exits unsuccessfully; the message includes git's stderr.
+pub async fn run_git_output(repo_root: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .await
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_run_git() {
+        let temp_dir = TempDir::new().unwrap();
+        run_git(temp_dir.path(), &["init", "-q", "-b", "main"])
+            .await
+            .unwrap();
+        assert_eq!(
+            run_git(temp_dir.path(), &["symbolic-ref", "--short", "HEAD"])
+                .await
+                .unwrap(),
+            "main"
+        );
+        std::fs::write(temp_dir.path().join("a.txt"), "a\n").unwrap();
+        run_git(temp_dir.path(), &["add", "a.txt"]).await.unwrap();
+        assert_eq!(
+            run_git_output(temp_dir.path(), &["show", ":a.txt"])
+                .await
+                .unwrap(),
+            b"a\n"
+        );
+
+        let err = run_git(temp_dir.path(), &["rev-parse", "--verify", "missing"])
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("git rev-parse --verify missing failed")
+        );
+    }
+}
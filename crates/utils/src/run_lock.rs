@@ -0,0 +1,255 @@
+use std::{
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+
+/// Lock file held in the changepacks directory while `update` or `publish` runs
+pub const RUN_LOCK_FILE: &str = "run.lock";
+
+/// Age after which a lock whose process can't be checked is treated as abandoned
+const STALE_AFTER: Duration = Duration::hours(6);
+
+/// Advisory lock serializing runs that write to the changepacks directory
+///
+/// Acquired by `update` and `publish` so overlapping invocations (e.g., concurrent CI jobs)
+/// can't interleave version writes and changepack log deletions. The lock file is removed
+/// when the guard is dropped, unless another run has since taken it over.
+#[derive(Debug)]
+pub struct RunLock {
+    path: PathBuf,
+    token: String,
+}
+
+impl RunLock {
+    /// Take the lock in `changepacks_dir` for `command`
+    ///
+    /// The lock file is written in full under a temporary name and then linked into place,
+    /// so other runs never see it half written. A lock left behind by a process that no
+    /// longer runs (or, where processes can't be checked, one older than six hours) is stale
+    /// and taken over.
+    ///
+    /// # Errors
+    /// Returns error naming the holder when another run holds the lock, or if the lock file
+    /// cannot be written.
+    pub fn acquire(changepacks_dir: &Path, command: &str) -> Result<Self> {
+        let path = changepacks_dir.join(RUN_LOCK_FILE);
+        // One retry: the first attempt may find a stale lock that is then removed
+        for _ in 0..2 {
+            let mut file = tempfile::Builder::new()
+                .prefix(".run.lock.")
+                .tempfile_in(changepacks_dir)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            // The random temporary name identifies this run's lock
+            let token = file
+                .path()
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let contents = serde_json::json!({
+                "pid": std::process::id(),
+                "command": command,
+                "startedAt": Utc::now().to_rfc3339(),
+                "token": token,
+            })
+            .to_string();
+            file.write_all(contents.as_bytes())?;
+            match file.persist_noclobber(&path) {
+                Ok(_) => return Ok(Self { path, token }),
+                Err(err) if err.error.kind() == ErrorKind::AlreadyExists => {
+                    let holder = std::fs::read_to_string(&path).unwrap_or_default();
+                    if !is_stale(&holder, Utc::now()) {
+                        bail!(
+                            "Another changepacks run holds {} ({}); wait for it to finish, or remove the file if no run is active",
+                            path.display(),
+                            describe_holder(&holder)
+                        );
+                    }
+                    // Leave the lock alone if another run took it over in the meantime
+                    if std::fs::read_to_string(&path).ok().as_deref() != Some(holder.as_str()) {
+                        continue;
+                    }
+                    match std::fs::remove_file(&path) {
+                        Ok(()) => {}
+                        Err(err) if err.kind() == ErrorKind::NotFound => {}
+                        Err(err) => {
+                            return Err(err).with_context(|| {
+                                format!("Failed to remove stale lock {}", path.display())
+                            });
+                        }
+                    }
+                }
+                Err(err) => {
+                    return Err(err.error)
+                        .with_context(|| format!("Failed to create {}", path.display()));
+                }
+            }
+        }
+        bail!("Failed to acquire {}", path.display())
+    }
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        // A run that found this lock stale may have replaced it; only remove our own
+        let owned = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|holder| serde_json::from_str::<serde_json::Value>(&holder).ok())
+            .is_some_and(|holder| holder["token"].as_str() == Some(self.token.as_str()));
+        if owned {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Whether the lock file `holder` was left behind by a run that is gone
+///
+/// Lock files are only ever created complete, so contents that don't parse were edited by
+/// hand and are left for the user to remove.
+fn is_stale(holder: &str, now: DateTime<Utc>) -> bool {
+    let Ok(holder) = serde_json::from_str::<serde_json::Value>(holder) else {
+        return false;
+    };
+    if let Some(alive) = holder["pid"]
+        .as_u64()
+        .and_then(|pid| u32::try_from(pid).ok())
+        .and_then(process_alive)
+    {
+        return !alive;
+    }
+    holder["startedAt"]
+        .as_str()
+        .and_then(|started| DateTime::parse_from_rfc3339(started).ok())
+        .is_none_or(|started| now - started.with_timezone(&Utc) > STALE_AFTER)
+}
+
+/// Whether process `pid` is running, when the platform lets us tell
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> Option<bool> {
+    Some(Path::new("/proc").join(pid.to_string()).exists())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_alive(_pid: u32) -> Option<bool> {
+    None
+}
+
+/// `update (pid 42) since <time>` summary of the lock holder for error messages
+fn describe_holder(holder: &str) -> String {
+    let holder: serde_json::Value = serde_json::from_str(holder).unwrap_or_default();
+    format!(
+        "{} (pid {}) since {}",
+        holder["command"].as_str().unwrap_or("unknown command"),
+        holder["pid"],
+        holder["startedAt"].as_str().unwrap_or("unknown time")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn holder(pid: u32, started_at: DateTime<Utc>) -> String {
+        serde_json::json!({
+            "pid": pid,
+            "command": "publish",
+            "startedAt": started_at.to_rfc3339(),
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_run_lock_excludes_concurrent_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = RunLock::acquire(temp_dir.path(), "update").unwrap();
+        assert!(temp_dir.path().join(RUN_LOCK_FILE).exists());
+
+        let err = RunLock::acquire(temp_dir.path(), "publish")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Another changepacks run holds"));
+        assert!(err.contains(&format!("update (pid {})", std::process::id())));
+
+        drop(lock);
+        assert!(!temp_dir.path().join(RUN_LOCK_FILE).exists());
+        RunLock::acquire(temp_dir.path(), "publish").unwrap();
+    }
+
+    #[test]
+    fn test_run_lock_takes_over_stale_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(RUN_LOCK_FILE);
+        std::fs::write(
+            &path,
+            serde_json::json!({ "startedAt": (Utc::now() - Duration::hours(7)).to_rfc3339() })
+                .to_string(),
+        )
+        .unwrap();
+
+        let lock = RunLock::acquire(temp_dir.path(), "update").unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains(r#""command":"update""#));
+        drop(lock);
+        assert!(!path.exists());
+        // No temporary lock files are left behind
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_run_lock_keeps_unreadable_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(RUN_LOCK_FILE), "").unwrap();
+
+        assert!(RunLock::acquire(temp_dir.path(), "update").is_err());
+    }
+
+    #[test]
+    fn test_run_lock_drop_keeps_lock_taken_over() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(RUN_LOCK_FILE);
+        let lock = RunLock::acquire(temp_dir.path(), "update").unwrap();
+        // Another run found the lock stale and replaced it
+        let other = holder(std::process::id(), Utc::now());
+        std::fs::write(&path, &other).unwrap();
+
+        drop(lock);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), other);
+    }
+
+    #[tokio::test]
+    async fn test_run_lock_survives_clearing_logs() {
+        let temp_dir = TempDir::new().unwrap();
+        let _lock = RunLock::acquire(temp_dir.path(), "update").unwrap();
+        std::fs::write(temp_dir.path().join("changepack_log_a.json"), "{}").unwrap();
+
+        crate::clear_update_logs(&temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        assert!(temp_dir.path().join(RUN_LOCK_FILE).exists());
+        assert!(!temp_dir.path().join("changepack_log_a.json").exists());
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let now = Utc::now();
+        assert!(!is_stale("", now));
+        assert!(!is_stale("not json", now));
+        assert!(!is_stale(&holder(std::process::id(), now), now));
+        // Without a usable pid, only the age decides
+        let no_pid = |started: DateTime<Utc>| {
+            serde_json::json!({ "startedAt": started.to_rfc3339() }).to_string()
+        };
+        assert!(!is_stale(&no_pid(now - Duration::hours(1)), now));
+        assert!(is_stale(&no_pid(now - Duration::hours(7)), now));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_stale_dead_process() {
+        // pid_max is at most 2^22 on Linux, so this pid never exists
+        assert!(is_stale(&holder(u32::MAX, Utc::now()), Utc::now()));
+    }
+}
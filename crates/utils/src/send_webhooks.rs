@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use changepacks_core::{Webhook, WebhookPayload};
+
+use crate::format_webhook_body;
+
+/// Attempts per webhook before giving up
+const WEBHOOK_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled after each failed attempt
+const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// POST `payload` to every webhook in its configured format, retrying failed deliveries
+///
+/// All webhooks are notified concurrently. Non-2xx responses count as failures.
+///
+/// # Errors
+/// Returns error listing every URL that still failed after all attempts.
+pub async fn send_webhooks(webhooks: &[Webhook], payload: &WebhookPayload) -> Result<()> {
+    send_webhooks_with_retry(webhooks, payload, WEBHOOK_ATTEMPTS, WEBHOOK_RETRY_DELAY).await
+}
+
+async fn send_webhooks_with_retry(
+    webhooks: &[Webhook],
+    payload: &WebhookPayload,
+    attempts: u32,
+    delay: Duration,
+) -> Result<()> {
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+    let bodies = webhooks
+        .iter()
+        .map(|webhook| format_webhook_body(webhook.format(), payload))
+        .collect::<Result<Vec<_>>>()?;
+    let results = futures::future::join_all(
+        webhooks
+            .iter()
+            .zip(&bodies)
+            .map(|(webhook, body)| post_with_retry(webhook.url(), body, attempts, delay)),
+    )
+    .await;
+
+    let failures: Vec<String> = results
+        .into_iter()
+        .filter_map(|result| result.err().map(|e| format!("{e:#}")))
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Failed to notify {} webhook(s): {}",
+            failures.len(),
+            failures.join("; ")
+        ))
+    }
+}
+
+async fn post_with_retry(url: &str, body: &[u8], attempts: u32, delay: Duration) -> Result<()> {
+    let mut delay = delay;
+    let mut attempt = 1;
+    loop {
+        let owned_url = url.to_string();
+        let owned_body = body.to_vec();
+        let result = tokio::task::spawn_blocking(move || post(&owned_url, &owned_body)).await?;
+        match result {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= attempts => {
+                return Err(e).with_context(|| format!("{url} (after {attempts} attempts)"));
+            }
+            Err(_) => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+fn post(url: &str, body: &[u8]) -> Result<()> {
+    ureq::post(url)
+        .header("Content-Type", "application/json")
+        .send(body)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_core::{WebhookEvent, WebhookFormat, WebhookPackage, WebhookStatus};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    fn payload() -> WebhookPayload {
+        WebhookPayload::new(
+            WebhookEvent::Update,
+            vec![WebhookPackage::new(
+                Some("pkg".to_string()),
+                PathBuf::from("package.json"),
+                Some("1.1.0".to_string()),
+                vec!["Add feature".to_string()],
+                WebhookStatus::Updated,
+            )],
+        )
+    }
+
+    /// Serve one response per status in `statuses`, recording each request body
+    fn serve(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&bodies);
+        std::thread::spawn(move || {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 4096];
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            received.lock().unwrap().push(body.to_string());
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .unwrap();
+            }
+        });
+        (url, bodies)
+    }
+
+    #[tokio::test]
+    async fn test_send_webhooks_no_urls() {
+        assert!(send_webhooks(&[], &payload()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_webhooks_retries_until_success() {
+        let (url, bodies) = serve(vec![500, 200]);
+        send_webhooks_with_retry(
+            &[Webhook::Url(url)],
+            &payload(),
+            3,
+            Duration::from_millis(1),
+        )
+        .await
+        .unwrap();
+
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 2);
+        let json: serde_json::Value = serde_json::from_str(&bodies[1]).unwrap();
+        assert_eq!(json["event"], "update");
+        assert_eq!(json["packages"][0]["version"], "1.1.0");
+        assert_eq!(json["packages"][0]["notes"][0], "Add feature");
+    }
+
+    #[tokio::test]
+    async fn test_send_webhooks_slack_format() {
+        let (url, bodies) = serve(vec![200]);
+        let webhook = Webhook::Target {
+            url,
+            format: WebhookFormat::Slack,
+        };
+        send_webhooks_with_retry(&[webhook], &payload(), 1, Duration::from_millis(1))
+            .await
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&bodies.lock().unwrap()[0]).unwrap();
+        assert!(json["text"].as_str().unwrap().contains("pkg v1.1.0"));
+    }
+
+    #[tokio::test]
+    async fn test_send_webhooks_gives_up_after_attempts() {
+        let (url, bodies) = serve(vec![503, 503]);
+        let err = send_webhooks_with_retry(
+            &[Webhook::Url(url.clone())],
+            &payload(),
+            2,
+            Duration::from_millis(1),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(bodies.lock().unwrap().len(), 2);
+        let message = err.to_string();
+        assert!(message.contains("Failed to notify 1 webhook(s)"));
+        assert!(message.contains(&url));
+    }
+}
@@ -84,6 +84,37 @@ pub fn sort_by_dependencies(projects: Vec<&Project>) -> Vec<&Project> {
     sorted_indices.iter().map(|&idx| projects[idx]).collect()
 }
 
+/// Group projects already in [`sort_by_dependencies`] order into levels that can run at once.
+///
+/// A project lands one level after the last project it depends on, so every level only
+/// depends on earlier levels. Dependencies placed later in `projects` (cycles) are ignored.
+/// Projects keep their relative order within a level.
+#[must_use]
+pub fn dependency_levels<'a>(projects: &[&'a Project]) -> Vec<Vec<&'a Project>> {
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut levels: Vec<Vec<&Project>> = Vec::new();
+    let mut project_levels: Vec<usize> = Vec::with_capacity(projects.len());
+    for (idx, project) in projects.iter().enumerate() {
+        let level = project
+            .dependencies()
+            .iter()
+            .filter_map(|dep| index.get(dep))
+            .map(|&dep_idx| project_levels[dep_idx] + 1)
+            .max()
+            .unwrap_or(0);
+        project_levels.push(level);
+        if level == levels.len() {
+            levels.push(Vec::new());
+        }
+        levels[level].push(project);
+        index.insert(project.relative_path().to_string_lossy().into_owned(), idx);
+        if let Some(name) = project.name() {
+            index.insert(name.to_string(), idx);
+        }
+    }
+    levels
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,4 +347,22 @@ mod tests {
         // p1 should come last
         assert_eq!(names[4], Some("p1"));
     }
+
+    #[test]
+    fn test_dependency_levels() {
+        // p1 -> p2 -> p3, p4 -> p3, p5 independent
+        let p3 = create_project("p3", vec![]);
+        let p2 = create_project("p2", vec!["p3"]);
+        let p1 = create_project("p1", vec!["p2"]);
+        let p4 = create_project("p4", vec!["p3"]);
+        let p5 = create_project("p5", vec![]);
+
+        let sorted = sort_by_dependencies(vec![&p1, &p2, &p3, &p4, &p5]);
+        let levels: Vec<Vec<&str>> = dependency_levels(&sorted)
+            .iter()
+            .map(|level| level.iter().map(|p| p.name().unwrap()).collect())
+            .collect();
+        assert_eq!(levels, [vec!["p3", "p5"], vec!["p2", "p4"], vec!["p1"]]);
+        assert!(dependency_levels(&[]).is_empty());
+    }
 }
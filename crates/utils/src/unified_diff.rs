@@ -0,0 +1,188 @@
+use std::fmt::Write;
+
+/// Number of unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Render a unified diff (`diff -u` style) between two texts.
+///
+/// Returns an empty string when both texts are identical.
+#[must_use]
+pub fn unified_diff(label: &str, original: &str, updated: &str) -> String {
+    if original == updated {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = original.lines().collect();
+    let new_lines: Vec<&str> = updated.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut out = format!("--- a/{label}\n+++ b/{label}\n");
+    let mut start = 0;
+    while let Some(first_change) = ops[start..]
+        .iter()
+        .position(|op| !matches!(op, DiffOp::Equal(..)))
+        .map(|offset| start + offset)
+    {
+        // Extend the hunk until a run of unchanged lines long enough to split on
+        let hunk_start = first_change.saturating_sub(CONTEXT_LINES);
+        let mut hunk_end = first_change;
+        let mut equal_run = 0;
+        for (idx, op) in ops.iter().enumerate().skip(first_change) {
+            if matches!(op, DiffOp::Equal(..)) {
+                equal_run += 1;
+                if equal_run > CONTEXT_LINES * 2 {
+                    break;
+                }
+            } else {
+                equal_run = 0;
+                hunk_end = idx;
+            }
+        }
+        let hunk_end = (hunk_end + CONTEXT_LINES + 1).min(ops.len());
+        write_hunk(&mut out, &ops[hunk_start..hunk_end], &old_lines, &new_lines);
+        start = hunk_end;
+    }
+    out
+}
+
+fn write_hunk(out: &mut String, ops: &[DiffOp], old_lines: &[&str], new_lines: &[&str]) {
+    let (old_start, new_start) = hunk_origin(ops, old_lines.len(), new_lines.len());
+    let old_len = ops
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Insert(_)))
+        .count();
+    let new_len = ops
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Delete(_)))
+        .count();
+    let _ = writeln!(
+        out,
+        "@@ -{},{old_len} +{},{new_len} @@",
+        if old_len == 0 {
+            old_start
+        } else {
+            old_start + 1
+        },
+        if new_len == 0 {
+            new_start
+        } else {
+            new_start + 1
+        },
+    );
+    for op in ops {
+        let _ = match *op {
+            DiffOp::Equal(old, _) => writeln!(out, " {}", old_lines[old]),
+            DiffOp::Delete(old) => writeln!(out, "-{}", old_lines[old]),
+            DiffOp::Insert(new) => writeln!(out, "+{}", new_lines[new]),
+        };
+    }
+}
+
+/// Zero-based line offsets in the old and new text where a hunk begins
+fn hunk_origin(ops: &[DiffOp], old_total: usize, new_total: usize) -> (usize, usize) {
+    let old = ops.iter().find_map(|op| match *op {
+        DiffOp::Equal(old, _) | DiffOp::Delete(old) => Some(old),
+        DiffOp::Insert(_) => None,
+    });
+    let new = ops.iter().find_map(|op| match *op {
+        DiffOp::Equal(_, new) | DiffOp::Insert(new) => Some(new),
+        DiffOp::Delete(_) => None,
+    });
+    (old.unwrap_or(old_total), new.unwrap_or(new_total))
+}
+
+/// Line-level diff via longest common subsequence.
+///
+/// Manifests are small, so the quadratic table is cheap and keeps the output
+/// identical to what reviewers expect from `diff -u`.
+fn diff_lines(old_lines: &[&str], new_lines: &[&str]) -> Vec<DiffOp> {
+    let (n, m) = (old_lines.len(), new_lines.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_lines[i] == new_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_lines[i] == new_lines[j] {
+            ops.push(DiffOp::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Delete(i));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(j));
+            j += 1;
+        }
+    }
+    ops.extend((i..n).map(DiffOp::Delete));
+    ops.extend((j..m).map(DiffOp::Insert));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_identical() {
+        assert_eq!(unified_diff("a.txt", "same\n", "same\n"), "");
+    }
+
+    #[test]
+    fn test_unified_diff_single_line_change() {
+        let original = "{\n  \"name\": \"pkg\",\n  \"version\": \"1.0.0\"\n}\n";
+        let updated = "{\n  \"name\": \"pkg\",\n  \"version\": \"1.0.1\"\n}\n";
+        assert_eq!(
+            unified_diff("package.json", original, updated),
+            "--- a/package.json\n+++ b/package.json\n@@ -1,4 +1,4 @@\n {\n   \"name\": \"pkg\",\n-  \"version\": \"1.0.0\"\n+  \"version\": \"1.0.1\"\n }\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_trims_context() {
+        let original: String = (1..=20).map(|i| format!("line{i}\n")).collect();
+        let updated = original.replace("line10\n", "changed\n");
+        let diff = unified_diff("f", &original, &updated);
+        assert!(diff.contains("@@ -7,7 +7,7 @@\n"));
+        assert!(diff.contains(" line7\n"));
+        assert!(!diff.contains("line6\n"));
+        assert!(diff.contains("-line10\n+changed\n"));
+        assert!(diff.contains(" line13\n"));
+        assert!(!diff.contains("line14\n"));
+    }
+
+    #[test]
+    fn test_unified_diff_splits_distant_hunks() {
+        let original: String = (1..=30).map(|i| format!("line{i}\n")).collect();
+        let updated = original
+            .replace("line2\n", "two\n")
+            .replace("line28\n", "twenty-eight\n");
+        let diff = unified_diff("f", &original, &updated);
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,5 +1,5 @@\n"));
+        assert!(diff.contains("@@ -25,6 +25,6 @@\n"));
+    }
+
+    #[test]
+    fn test_unified_diff_new_file() {
+        assert_eq!(
+            unified_diff("new.md", "", "hello\n"),
+            "--- a/new.md\n+++ b/new.md\n@@ -0,0 +1,1 @@\n+hello\n"
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+/// GitHub rejects API requests without a User-Agent
+const USER_AGENT: &str = concat!("changepacks/", env!("CARGO_PKG_VERSION"));
+
+/// Open a pull request from `head` into `base` on GitHub, or refresh the open one
+///
+/// `repo` is `owner/name` and `api_url` the REST root (`https://api.github.com`, or the
+/// `/api/v3` root of a GitHub Enterprise Server). An open pull request from the same branch
+/// gets the new title and body instead of a duplicate. Returns the pull request's web URL.
+///
+/// # Errors
+/// Returns error if the API cannot be reached, rejects the token, or answers unexpectedly.
+pub async fn upsert_pull_request(
+    api_url: &str,
+    token: &str,
+    repo: &str,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+) -> Result<String> {
+    let api_url = api_url.trim_end_matches('/');
+    let owner = repo.split('/').next().unwrap_or_default();
+    let list_url =
+        format!("{api_url}/repos/{repo}/pulls?state=open&head={owner}:{head}&base={base}");
+    let token = token.to_string();
+    let pulls = call(Method::Get, list_url, token.clone(), Value::Null)
+        .await
+        .with_context(|| format!("Failed to list pull requests of {repo}"))?;
+    let existing = pulls
+        .as_array()
+        .and_then(|pulls| pulls.first())
+        .and_then(|pull| pull["number"].as_u64());
+    let pull = match existing {
+        Some(number) => call(
+            Method::Patch,
+            format!("{api_url}/repos/{repo}/pulls/{number}"),
+            token,
+            json!({ "title": title, "body": body }),
+        )
+        .await
+        .with_context(|| format!("Failed to update pull request #{number} of {repo}"))?,
+        None => call(
+            Method::Post,
+            format!("{api_url}/repos/{repo}/pulls"),
+            token,
+            json!({ "title": title, "head": head, "base": base, "body": body }),
+        )
+        .await
+        .with_context(|| format!("Failed to open a pull request on {repo}"))?,
+    };
+    pull["html_url"]
+        .as_str()
+        .map(str::to_string)
+        .context("GitHub answered without a pull request URL")
+}
+
+/// Title and description (empty when unset) of pull request `number` on GitHub
+///
+/// `repo` and `api_url` are as for [`upsert_pull_request`].
+///
+/// # Errors
+/// Returns error if the API cannot be reached, rejects the token, or answers unexpectedly.
+pub async fn fetch_pull_request(
+    api_url: &str,
+    token: &str,
+    repo: &str,
+    number: u64,
+) -> Result<(String, String)> {
+    let url = format!(
+        "{}/repos/{repo}/pulls/{number}",
+        api_url.trim_end_matches('/')
+    );
+    let pull = call(Method::Get, url, token.to_string(), Value::Null)
+        .await
+        .with_context(|| format!("Failed to fetch pull request #{number} of {repo}"))?;
+    let title = pull["title"]
+        .as_str()
+        .context("GitHub answered without a pull request title")?;
+    Ok((
+        title.to_string(),
+        pull["body"].as_str().unwrap_or_default().to_string(),
+    ))
+}
+
+#[derive(Clone, Copy)]
+enum Method {
+    Get,
+    Post,
+    Patch,
+}
+
+/// Send `body` (ignored for `GET`) to `url` and parse the JSON answer
+async fn call(method: Method, url: String, token: String, body: Value) -> Result<Value> {
+    tokio::task::spawn_blocking(move || {
+        let authorization = format!("Bearer {token}");
+        let mut response = match method {
+            Method::Get => ureq::get(&url)
+                .header("Accept", "application/vnd.github+json")
+                .header("Authorization", &authorization)
+                .header("User-Agent", USER_AGENT)
+                .call()?,
+            Method::Post | Method::Patch => {
+                let request = if matches!(method, Method::Patch) {
+                    ureq::patch(&url)
+                } else {
+                    ureq::post(&url)
+                };
+                request
+                    .header("Accept", "application/vnd.github+json")
+                    .header("Authorization", &authorization)
+                    .header("User-Agent", USER_AGENT)
+                    .header("Content-Type", "application/json")
+                    .send(serde_json::to_vec(&body)?)?
+            }
+        };
+        let text = response.body_mut().read_to_string()?;
+        Ok(serde_json::from_str(&text)?)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    /// Answer one request per body in `responses`, recording each request line and body
+    fn serve(responses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 4096];
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            let line = head.lines().next().unwrap_or_default();
+                            received.lock().unwrap().push(format!("{line} {body}"));
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                    response.len()
+                )
+                .unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn test_upsert_pull_request_opens_new() {
+        let (url, requests) = serve(vec![
+            "[]",
+            r#"{"number": 7, "html_url": "https://github.com/org/repo/pull/7"}"#,
+        ]);
+        let pull_url = upsert_pull_request(
+            &url,
+            "token",
+            "org/repo",
+            "changepacks/release",
+            "main",
+            "Version Packages",
+            "body",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(pull_url, "https://github.com/org/repo/pull/7");
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with(
+            "GET /repos/org/repo/pulls?state=open&head=org:changepacks/release&base=main "
+        ));
+        assert!(requests[1].starts_with("POST /repos/org/repo/pulls "));
+        assert!(requests[1].contains(r#""head":"changepacks/release""#));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_pull_request_refreshes_open() {
+        let (url, requests) = serve(vec![
+            r#"[{"number": 3}]"#,
+            r#"{"number": 3, "html_url": "https://github.com/org/repo/pull/3"}"#,
+        ]);
+        let pull_url = upsert_pull_request(
+            &url,
+            "token",
+            "org/repo",
+            "changepacks/release",
+            "main",
+            "Version Packages",
+            "new body",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(pull_url, "https://github.com/org/repo/pull/3");
+        let requests = requests.lock().unwrap();
+        assert!(requests[1].starts_with("PATCH /repos/org/repo/pulls/3 "));
+        assert!(requests[1].contains(r#""body":"new body""#));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pull_request() {
+        let (url, requests) = serve(vec![
+            r#"{"number": 5, "title": "Add parser", "body": "Parses things"}"#,
+            r#"{"number": 6, "title": "No body", "body": null}"#,
+        ]);
+        let pull = fetch_pull_request(&url, "token", "org/repo", 5)
+            .await
+            .unwrap();
+        assert_eq!(
+            pull,
+            ("Add parser".to_string(), "Parses things".to_string())
+        );
+        let pull = fetch_pull_request(&url, "token", "org/repo", 6)
+            .await
+            .unwrap();
+        assert_eq!(pull, ("No body".to_string(), String::new()));
+        assert!(requests.lock().unwrap()[0].starts_with("GET /repos/org/repo/pulls/5 "));
+    }
+}
@@ -0,0 +1,354 @@
+use std::fmt;
+
+use changepacks_core::{Config, RegistryEndpoint};
+use glob::Pattern;
+use serde_json::{Map, Value};
+
+/// Problem found in the settings of a config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Setting the problem is in, e.g. `git.tagTemplate` or `ignore[1]`
+    pub field: String,
+
+    /// 1-based line of the setting in the config file, when it can be located
+    pub line: Option<usize>,
+
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        if self.field.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "`{}`: {}", self.field, self.message)
+        }
+    }
+}
+
+/// Every problem in the raw settings `value` of a config file
+///
+/// Reports unknown keys, settings of the wrong type, invalid glob patterns and invalid
+/// branch names together, so they can be fixed before a command fails on the first one.
+/// Lines are looked up in `content`, the text the settings were parsed from.
+#[must_use]
+pub fn validate_config(value: &Value, content: &str) -> Vec<ConfigIssue> {
+    let Value::Object(settings) = value else {
+        return vec![ConfigIssue {
+            field: String::new(),
+            line: None,
+            message: "config must be a table of settings".to_string(),
+        }];
+    };
+    let mut found = Vec::new();
+    let known = default_value(&Config::default());
+    check_keys(settings, &known, "", &mut found);
+
+    for (key, setting) in settings {
+        if !known.contains_key(key) {
+            continue;
+        }
+        // Deserialize each setting alone so a type error names the setting it is in
+        let single = Value::Object(Map::from_iter([(key.clone(), setting.clone())]));
+        match serde_json::from_value::<Config>(single) {
+            Ok(_) => check_setting(key, setting, &mut found),
+            Err(err) => found.push(Found::key(key, key, err.to_string())),
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|found| ConfigIssue {
+            line: find_line(content, &found.needle),
+            field: found.field,
+            message: found.message,
+        })
+        .collect()
+}
+
+/// Issue before its line is looked up by `needle`, the key or value it is about
+struct Found {
+    field: String,
+    needle: String,
+    message: String,
+}
+
+impl Found {
+    fn key(field: &str, key: &str, message: String) -> Self {
+        Self {
+            field: field.to_string(),
+            needle: key.to_string(),
+            message,
+        }
+    }
+}
+
+fn default_value<T: serde::Serialize>(default: &T) -> Map<String, Value> {
+    match serde_json::to_value(default) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Flag keys of `settings` missing from `known`, descending into nested sections
+///
+/// A section whose default is an empty table is a map keyed by the user (e.g., `publish`),
+/// so only sections with default keys of their own are checked.
+fn check_keys(
+    settings: &Map<String, Value>,
+    known: &Map<String, Value>,
+    prefix: &str,
+    found: &mut Vec<Found>,
+) {
+    for (key, setting) in settings {
+        let field = format!("{prefix}{key}");
+        match known.get(key) {
+            None => {
+                let message = match suggest_key(key, known) {
+                    Some(suggestion) => format!("unknown setting (did you mean `{suggestion}`?)"),
+                    None => "unknown setting".to_string(),
+                };
+                found.push(Found::key(&field, key, message));
+            }
+            Some(Value::Object(known)) if !known.is_empty() => {
+                if let Value::Object(setting) = setting {
+                    check_keys(setting, known, &format!("{field}."), found);
+                }
+            }
+            Some(_) if field == "registries" => {
+                let known = default_value(&RegistryEndpoint::default());
+                for (registry, endpoint) in setting.as_object().into_iter().flatten() {
+                    if let Value::Object(endpoint) = endpoint {
+                        check_keys(endpoint, &known, &format!("{field}.{registry}."), found);
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// Known key spelled the same apart from case and separators (e.g., `base_branch`)
+fn suggest_key<'a>(key: &str, known: &'a Map<String, Value>) -> Option<&'a str> {
+    let normalize = |key: &str| {
+        key.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase()
+    };
+    let key = normalize(key);
+    known
+        .keys()
+        .find(|known| normalize(known) == key)
+        .map(String::as_str)
+}
+
+/// Check the values of a setting that deserialized fine
+fn check_setting(key: &str, setting: &Value, found: &mut Vec<Found>) {
+    match key {
+        "ignore" | "changeIgnore" => {
+            for (index, pattern) in strings(setting) {
+                check_glob(&format!("{key}[{index}]"), pattern, found);
+            }
+        }
+        "changeFiles" => {
+            for (project, patterns) in setting.as_object().into_iter().flatten() {
+                for (index, pattern) in strings(patterns) {
+                    check_glob(&format!("{key}.{project}[{index}]"), pattern, found);
+                }
+            }
+        }
+        "updateOn" => {
+            for trigger in setting.as_object().into_iter().flatten().map(|(k, _)| k) {
+                check_glob(&format!("{key}.{trigger}"), trigger, found);
+            }
+        }
+        "baseBranch" => {
+            if let Some(branch) = setting.as_str()
+                && let Err(message) = check_branch_name(branch, false)
+            {
+                found.push(value_issue(key, branch, message));
+            }
+        }
+        "protectedBranches" => {
+            for (index, branch) in strings(setting) {
+                let field = format!("{key}[{index}]");
+                if let Err(message) = check_branch_name(branch, true) {
+                    found.push(value_issue(&field, branch, message));
+                } else {
+                    check_glob(&field, branch, found);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn strings(value: &Value) -> impl Iterator<Item = (usize, &str)> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(index, item)| Some((index, item.as_str()?)))
+}
+
+fn value_issue(field: &str, value: &str, message: String) -> Found {
+    Found {
+        field: field.to_string(),
+        needle: value.to_string(),
+        message,
+    }
+}
+
+fn check_glob(field: &str, pattern: &str, found: &mut Vec<Found>) {
+    if let Err(err) = Pattern::new(pattern) {
+        found.push(value_issue(
+            field,
+            pattern,
+            format!("invalid glob pattern \"{pattern}\": {}", err.msg),
+        ));
+    }
+}
+
+/// Whether `branch` is a valid git branch name, following `git check-ref-format --branch`
+///
+/// With `allow_glob`, the glob characters `*`, `?` and `[` are accepted.
+fn check_branch_name(branch: &str, allow_glob: bool) -> Result<(), String> {
+    let invalid = |reason: &str| Err(format!("invalid branch name \"{branch}\": {reason}"));
+    if branch.is_empty() {
+        return invalid("it is empty");
+    }
+    if branch == "@" {
+        return invalid("\"@\" is not a branch name");
+    }
+    if branch.starts_with('-') {
+        return invalid("it starts with \"-\"");
+    }
+    if let Some(c) = branch.chars().find(|c| {
+        c.is_ascii_control()
+            || matches!(c, ' ' | '~' | '^' | ':' | '\\')
+            || (!allow_glob && matches!(c, '*' | '?' | '['))
+    }) {
+        return invalid(&format!("it contains {c:?}"));
+    }
+    if branch.contains("..") || branch.contains("@{") || branch.contains("//") {
+        return invalid("it contains \"..\", \"@{\" or \"//\"");
+    }
+    if branch.starts_with('/') || branch.ends_with('/') || branch.ends_with('.') {
+        return invalid("it starts or ends with \"/\", or ends with \".\"");
+    }
+    if branch
+        .split('/')
+        .any(|part| part.starts_with('.') || part.ends_with(".lock"))
+    {
+        return invalid("a component starts with \".\" or ends with \".lock\"");
+    }
+    Ok(())
+}
+
+/// 1-based line of the first standalone occurrence of `needle` in `content`
+fn find_line(content: &str, needle: &str) -> Option<usize> {
+    if needle.is_empty() {
+        return None;
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    content
+        .lines()
+        .position(|line| {
+            line.match_indices(needle).any(|(start, _)| {
+                let before = line[..start].chars().next_back();
+                let after = line[start + needle.len()..].chars().next();
+                !before.is_some_and(is_word) && !after.is_some_and(is_word)
+            })
+        })
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn validate(content: &str) -> Vec<ConfigIssue> {
+        let value: Value = serde_json::from_str(content).unwrap();
+        validate_config(&value, content)
+    }
+
+    #[test]
+    fn test_validate_config_valid() {
+        let content = r#"{
+  "baseBranch": "release/1.x",
+  "ignore": ["examples/**"],
+  "protectedBranches": ["main", "release/*"],
+  "publish": {"node": "npm publish", "crates/cli/Cargo.toml": false},
+  "git": {"tag": true},
+  "registries": {"npm": {"url": "https://npm.example.com"}}
+}"#;
+        assert_eq!(validate(content), []);
+    }
+
+    #[test]
+    fn test_validate_config_reports_every_problem() {
+        let content = r#"{
+  "base_branch": "main",
+  "baseBranch": "feature..x",
+  "ignore": ["docs/**", "src/[*"],
+  "git": {"tag": true, "tagPrefx": "v"},
+  "registries": {"npm": {"url": "https://npm.example.com", "token": "x"}},
+  "publishRetries": "three"
+}"#;
+        let issues: Vec<String> = validate(content).iter().map(ToString::to_string).collect();
+        assert_eq!(issues.len(), 6, "{issues:#?}");
+        assert!(issues.contains(
+            &"line 2: `base_branch`: unknown setting (did you mean `baseBranch`?)".to_string()
+        ));
+        assert!(issues.contains(&"line 5: `git.tagPrefx`: unknown setting".to_string()));
+        assert!(issues.contains(&"line 6: `registries.npm.token`: unknown setting".to_string()));
+        assert!(issues.iter().any(|issue| {
+            issue.starts_with("line 3: `baseBranch`: invalid branch name \"feature..x\"")
+        }));
+        assert!(issues.iter().any(|issue| {
+            issue.starts_with("line 4: `ignore[1]`: invalid glob pattern \"src/[*\"")
+        }));
+        assert!(
+            issues
+                .iter()
+                .any(|issue| issue.starts_with("line 7: `publishRetries`: invalid type"))
+        );
+    }
+
+    #[test]
+    fn test_validate_config_not_a_table() {
+        let issues = validate("[1, 2]");
+        assert_eq!(issues[0].to_string(), "config must be a table of settings");
+    }
+
+    #[rstest]
+    #[case("main", false, true)]
+    #[case("release/1.x", false, true)]
+    #[case("release/*", false, false)]
+    #[case("release/*", true, true)]
+    #[case("", false, false)]
+    #[case("my branch", false, false)]
+    #[case("-main", false, false)]
+    #[case("feature/", false, false)]
+    #[case("feature/.hidden", false, false)]
+    #[case("main.lock", false, false)]
+    #[case("a@{b", false, false)]
+    #[case("@", false, false)]
+    fn test_check_branch_name(#[case] branch: &str, #[case] allow_glob: bool, #[case] ok: bool) {
+        assert_eq!(check_branch_name(branch, allow_glob).is_ok(), ok);
+    }
+
+    #[rstest]
+    #[case("baseBranch = \"main\"\nbase = 1\n", "base", Some(2))]
+    #[case("ignore:\n  - src/[*\n", "src/[*", Some(2))]
+    #[case("{}", "ignore", None)]
+    fn test_find_line(#[case] content: &str, #[case] needle: &str, #[case] line: Option<usize>) {
+        assert_eq!(find_line(content, needle), line);
+    }
+}
@@ -0,0 +1,206 @@
+use std::path::Path;
+
+use anyhow::Result;
+use changepacks_core::{Config, ProjectFinder, changepacks_dir_name};
+use ignore::WalkBuilder;
+
+use crate::filter_project_dirs::{
+    build_ignore_matcher, set_fallback_names, visit_linked_dirs, visit_project_file,
+};
+
+/// Find project directories by walking the filesystem, for trees that are not git repositories
+///
+/// `.gitignore` and `.ignore` files are honored the same way git would, and the config `ignore`
+/// patterns apply on top. Subdirectories holding their own changepacks directory are separate
+/// release roots and are skipped. Symlinked directories are followed as in
+/// [`find_project_dirs`](crate::find_project_dirs). Without git history there is nothing to diff against, so every
+/// project is reported as unchanged.
+///
+/// # Errors
+/// Returns error if walking the directory, ignore pattern parsing, or project visiting fails.
+pub async fn walk_project_dirs(
+    root: &Path,
+    project_finders: &mut [Box<dyn ProjectFinder>],
+    config: &Config,
+) -> Result<()> {
+    let gitignore = build_ignore_matcher(root, config)?;
+    let changepacks_dir = changepacks_dir_name();
+
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            entry.file_name() != ".git"
+                && (entry.depth() == 0
+                    || !entry.file_type().is_some_and(|ty| ty.is_dir())
+                    || !entry.path().join(&changepacks_dir).is_dir())
+        })
+        .sort_by_file_name(Ord::cmp)
+        .build();
+    let mut links = Vec::new();
+    for entry in walker {
+        let entry = entry?;
+        if entry.path_is_symlink() {
+            links.push(entry.into_path());
+            continue;
+        }
+        if !entry.file_type().is_some_and(|ty| ty.is_file()) {
+            continue;
+        }
+        visit_project_file(project_finders, gitignore.as_ref(), root, entry.path()).await?;
+    }
+    visit_linked_dirs(project_finders, gitignore.as_ref(), root, links).await?;
+
+    for finder in project_finders.iter_mut() {
+        finder.finalize().await?;
+    }
+
+    if let Some(dir_name) = root.file_name().and_then(|n| n.to_str()) {
+        set_fallback_names(project_finders, dir_name);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_node::finder::NodeProjectFinder;
+    use tempfile::TempDir;
+    use tokio::fs;
+
+    #[tokio::test]
+    async fn test_walk_project_dirs_without_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        for name in ["core", "ignored", "excluded"] {
+            fs::create_dir_all(temp_path.join(format!("packages/{name}")))
+                .await
+                .unwrap();
+            fs::write(
+                temp_path.join(format!("packages/{name}/package.json")),
+                format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+            )
+            .await
+            .unwrap();
+        }
+        fs::write(temp_path.join(".gitignore"), "packages/ignored/\n")
+            .await
+            .unwrap();
+
+        let config = Config {
+            ignore: vec!["packages/excluded/**".to_string()],
+            ..Default::default()
+        };
+        let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
+
+        walk_project_dirs(temp_path, &mut finders, &config)
+            .await
+            .unwrap();
+
+        let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name(), Some("core"));
+        assert!(!projects[0].is_changed());
+    }
+
+    #[tokio::test]
+    async fn test_walk_project_dirs_fallback_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("my-project");
+        fs::create_dir_all(&root).await.unwrap();
+        fs::write(root.join("package.json"), r#"{"version": "1.0.0"}"#)
+            .await
+            .unwrap();
+
+        let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
+        walk_project_dirs(&root, &mut finders, &Config::default())
+            .await
+            .unwrap();
+
+        let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name(), Some("my-project"));
+    }
+
+    #[tokio::test]
+    async fn test_walk_project_dirs_skips_nested_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+        for name in ["core", "web"] {
+            fs::create_dir_all(temp_path.join(format!("packages/{name}")))
+                .await
+                .unwrap();
+            fs::write(
+                temp_path.join(format!("packages/{name}/package.json")),
+                format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+            )
+            .await
+            .unwrap();
+        }
+        fs::create_dir_all(temp_path.join("packages/web/.changepacks"))
+            .await
+            .unwrap();
+
+        let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
+        walk_project_dirs(temp_path, &mut finders, &Config::default())
+            .await
+            .unwrap();
+
+        let projects: Vec<_> = finders.iter().flat_map(|f| f.projects()).collect();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name(), Some("core"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_walk_project_dirs_follows_symlinked_dirs_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("repo");
+        let vendor = temp_dir.path().join("vendor/ext");
+        fs::create_dir_all(root.join("packages/core"))
+            .await
+            .unwrap();
+        fs::create_dir_all(&vendor).await.unwrap();
+        fs::write(
+            root.join("packages/core/package.json"),
+            r#"{"name": "core", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+        fs::write(
+            vendor.join("package.json"),
+            r#"{"name": "ext", "version": "1.0.0"}"#,
+        )
+        .await
+        .unwrap();
+        // A link back into the tree and two links to the same vendored package
+        std::os::unix::fs::symlink(root.join("packages/core"), root.join("core-link")).unwrap();
+        std::os::unix::fs::symlink(&vendor, root.join("packages/ext")).unwrap();
+        std::os::unix::fs::symlink(&vendor, root.join("packages/ext-again")).unwrap();
+
+        let mut finders: Vec<Box<dyn ProjectFinder>> = vec![Box::new(NodeProjectFinder::new())];
+        walk_project_dirs(&root, &mut finders, &Config::default())
+            .await
+            .unwrap();
+
+        let mut projects: Vec<(String, String)> = finders
+            .iter()
+            .flat_map(|f| f.projects())
+            .map(|project| {
+                (
+                    project.name().unwrap().to_string(),
+                    project.relative_path().display().to_string(),
+                )
+            })
+            .collect();
+        projects.sort();
+        assert_eq!(
+            projects,
+            [
+                ("core".to_string(), "packages/core/package.json".to_string()),
+                ("ext".to_string(), "packages/ext/package.json".to_string()),
+            ]
+        );
+    }
+}
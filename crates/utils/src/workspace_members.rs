@@ -0,0 +1,257 @@
+use std::path::{Component, Path, PathBuf};
+
+use changepacks_core::Project;
+use glob::{MatchOptions, Pattern};
+
+/// Workspace a package belongs to
+#[derive(Debug, Clone, Copy)]
+pub enum Membership<'a> {
+    /// Member of the workspace
+    Member(&'a Project),
+    /// Inside the directory of the workspace, but none of its member globs match
+    Orphan(&'a Project),
+    /// Not inside any workspace of its language
+    Standalone,
+}
+
+/// Workspace among `projects` that `package` belongs to
+///
+/// Workspaces of the package's language whose directory contains the package are tried from
+/// the nearest out. One that declares member globs claims the package when a glob matches
+/// the package directory and no `!` glob does; one without globs claims every package below
+/// it. A package claimed by none of them is an orphan of the nearest workspace with globs.
+#[must_use]
+pub fn workspace_membership<'a>(package: &Project, projects: &[&'a Project]) -> Membership<'a> {
+    let package_dir = dir(package);
+    let mut candidates: Vec<&Project> = projects
+        .iter()
+        .copied()
+        .filter(|project| {
+            matches!(project, Project::Workspace(_))
+                && project.language() == package.language()
+                && package_dir.starts_with(dir(project))
+        })
+        .collect();
+    candidates.sort_by_key(|workspace| std::cmp::Reverse(dir(workspace).components().count()));
+
+    let mut orphan_of = None;
+    for workspace in candidates {
+        let member_dir = package_dir
+            .strip_prefix(dir(workspace))
+            .unwrap_or(package_dir);
+        match member_patterns(workspace) {
+            Some(patterns) if !member_dir.as_os_str().is_empty() => {
+                if matches_members(&patterns, member_dir) {
+                    return Membership::Member(workspace);
+                }
+                orphan_of.get_or_insert(workspace);
+            }
+            _ => return Membership::Member(workspace),
+        }
+    }
+    orphan_of.map_or(Membership::Standalone, Membership::Orphan)
+}
+
+fn dir(project: &Project) -> &Path {
+    project.relative_path().parent().unwrap_or(Path::new(""))
+}
+
+/// Manifests named `manifest` of the members that `patterns` select below the directory of
+/// the workspace manifest at `workspace_path`, sorted
+///
+/// Empty when `patterns` lists no members.
+#[must_use]
+pub fn member_manifests(
+    workspace_path: &Path,
+    patterns: &[String],
+    manifest: &str,
+) -> Vec<PathBuf> {
+    let Some(patterns) = normalize_patterns(patterns) else {
+        return Vec::new();
+    };
+    let dir = workspace_path.parent().unwrap_or(Path::new(""));
+    let mut manifests: Vec<PathBuf> = patterns
+        .iter()
+        .filter(|(exclude, _)| !exclude)
+        .filter_map(|(_, pattern)| {
+            let pattern = format!(
+                "{}/{pattern}/{manifest}",
+                Pattern::escape(&dir.to_string_lossy())
+            );
+            glob::glob(&pattern).ok()
+        })
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|path| {
+            path.parent()
+                .and_then(|member_dir| member_dir.strip_prefix(dir).ok())
+                .is_some_and(|member_dir| {
+                    !member_dir.as_os_str().is_empty() && matches_members(&patterns, member_dir)
+                })
+        })
+        .collect();
+    manifests.sort();
+    manifests.dedup();
+    manifests
+}
+
+/// Normalized member globs of `workspace`, or `None` when it lists no members
+fn member_patterns(workspace: &Project) -> Option<Vec<(bool, String)>> {
+    normalize_patterns(workspace.member_patterns()?)
+}
+
+fn normalize_patterns(patterns: &[String]) -> Option<Vec<(bool, String)>> {
+    let patterns: Vec<(bool, String)> = patterns
+        .iter()
+        .map(|pattern| {
+            let (exclude, pattern) = match pattern.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, pattern.as_str()),
+            };
+            let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+            (exclude, pattern.to_string())
+        })
+        .collect();
+    // `[workspace]` alone (Cargo takes path dependencies as members) or a pnpm file without
+    // `packages` says nothing about membership
+    patterns
+        .iter()
+        .any(|(exclude, _)| !exclude)
+        .then_some(patterns)
+}
+
+fn matches_members(patterns: &[(bool, String)], member_dir: &Path) -> bool {
+    let member_dir = member_dir
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+    let matches = |exclude: bool| {
+        patterns.iter().any(|(is_exclude, pattern)| {
+            *is_exclude == exclude
+                && Pattern::new(pattern)
+                    .is_ok_and(|pattern| pattern.matches_with(&member_dir, options))
+        })
+    };
+    matches(false) && !matches(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use changepacks_node::{package::NodePackage, workspace::NodeWorkspace};
+    use changepacks_rust::{package::RustPackage, workspace::RustWorkspace};
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(ToString::to_string).collect()
+    }
+
+    fn owner(membership: Membership) -> String {
+        match membership {
+            Membership::Member(workspace) => format!("member of {}", workspace.name().unwrap()),
+            Membership::Orphan(workspace) => format!("orphan of {}", workspace.name().unwrap()),
+            Membership::Standalone => "standalone".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_workspace_membership() {
+        let node_workspace = |name: &str, relative_path: &str| {
+            NodeWorkspace::new(
+                Some(name.to_string()),
+                None,
+                PathBuf::from("/repo").join(relative_path),
+                PathBuf::from(relative_path),
+            )
+        };
+        let rust_workspace = |name: &str, relative_path: &str| {
+            RustWorkspace::new(
+                Some(name.to_string()),
+                None,
+                PathBuf::from("/repo").join(relative_path),
+                PathBuf::from(relative_path),
+            )
+        };
+        let projects = [
+            Project::Workspace(Box::new(
+                node_workspace("root", "package.json").with_member_patterns(patterns(&[
+                    "./packages/*",
+                    "apps/**/",
+                    "!packages/legacy",
+                ])),
+            )),
+            Project::Workspace(Box::new(
+                rust_workspace("rust", "rust/Cargo.toml")
+                    .with_member_patterns(patterns(&["crates/*", "!crates/old"])),
+            )),
+            Project::Workspace(Box::new(
+                rust_workspace("tools", "rust/tools/Cargo.toml").with_member_patterns(vec![]),
+            )),
+        ];
+        let refs: Vec<&Project> = projects.iter().collect();
+        let node = |relative_path: &str| {
+            let package = Project::Package(Box::new(NodePackage::new(
+                None,
+                None,
+                PathBuf::from("/repo").join(relative_path),
+                PathBuf::from(relative_path),
+            )));
+            owner(workspace_membership(&package, &refs))
+        };
+        let rust = |relative_path: &str| {
+            let package = Project::Package(Box::new(RustPackage::new(
+                None,
+                None,
+                PathBuf::from("/repo").join(relative_path),
+                PathBuf::from(relative_path),
+            )));
+            owner(workspace_membership(&package, &refs))
+        };
+
+        assert_eq!(node("packages/ui/package.json"), "member of root");
+        assert_eq!(node("apps/web/admin/package.json"), "member of root");
+        assert_eq!(node("packages/legacy/package.json"), "orphan of root");
+        assert_eq!(node("packages/ui/fixtures/package.json"), "orphan of root");
+        assert_eq!(rust("rust/crates/core/Cargo.toml"), "member of rust");
+        assert_eq!(rust("rust/crates/old/Cargo.toml"), "orphan of rust");
+        // A workspace without member globs claims everything below it
+        assert_eq!(rust("rust/tools/gen/Cargo.toml"), "member of tools");
+        assert_eq!(rust("scripts/Cargo.toml"), "standalone");
+    }
+
+    #[test]
+    fn test_member_manifests() {
+        let dir = tempfile::TempDir::new().unwrap();
+        for member in [
+            "packages/ui",
+            "packages/legacy",
+            "packages/empty",
+            "apps/web",
+        ] {
+            std::fs::create_dir_all(dir.path().join(member)).unwrap();
+        }
+        for member in ["packages/ui", "packages/legacy", "apps/web"] {
+            std::fs::write(dir.path().join(member).join("package.json"), "{}").unwrap();
+        }
+        let manifests = member_manifests(
+            &dir.path().join("package.json"),
+            &patterns(&["packages/*", "./apps/web/", "!packages/legacy"]),
+            "package.json",
+        );
+        assert_eq!(
+            manifests,
+            [
+                dir.path().join("apps/web/package.json"),
+                dir.path().join("packages/ui/package.json"),
+            ]
+        );
+        assert!(member_manifests(&dir.path().join("package.json"), &[], "package.json").is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::{cell::RefCell, collections::BTreeMap, future::Future, path::Path, path::PathBuf};
+
+tokio::task_local! {
+    static CAPTURED_WRITES: RefCell<BTreeMap<PathBuf, FileChange>>;
+}
+
+/// A file write recorded while running inside [`capture_writes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path of the file that would be written
+    pub path: PathBuf,
+    /// Content on disk before any write
+    pub original: String,
+    /// Content after the last write
+    pub updated: String,
+}
+
+/// Read a file, seeing writes captured by an enclosing [`capture_writes`] scope.
+///
+/// # Errors
+/// Returns error if the file cannot be read from disk.
+pub async fn read_file(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let captured = CAPTURED_WRITES
+        .try_with(|writes| {
+            writes
+                .borrow()
+                .get(path)
+                .map(|change| change.updated.clone())
+        })
+        .ok()
+        .flatten();
+    match captured {
+        Some(content) => Ok(content),
+        None => Ok(tokio::fs::read_to_string(path).await?),
+    }
+}
+
+/// Write a file to disk, or record the write when running inside [`capture_writes`].
+///
+/// # Errors
+/// Returns error if the file cannot be written (or, in capture mode, its original content cannot be read).
+pub async fn write_file(path: impl AsRef<Path>, contents: impl Into<String>) -> Result<()> {
+    let path = path.as_ref();
+    let contents = contents.into();
+    if CAPTURED_WRITES.try_with(|_| ()).is_err() {
+        tokio::fs::write(path, contents).await?;
+        return Ok(());
+    }
+
+    let already_captured = CAPTURED_WRITES.with(|writes| writes.borrow().contains_key(path));
+    let original = if already_captured {
+        None
+    } else if tokio::fs::try_exists(path).await? {
+        Some(tokio::fs::read_to_string(path).await?)
+    } else {
+        Some(String::new())
+    };
+    CAPTURED_WRITES.with(|writes| {
+        let mut writes = writes.borrow_mut();
+        if let Some(change) = writes.get_mut(path) {
+            change.updated = contents;
+        } else {
+            writes.insert(
+                path.to_path_buf(),
+                FileChange {
+                    path: path.to_path_buf(),
+                    original: original.unwrap_or_default(),
+                    updated: contents,
+                },
+            );
+        }
+    });
+    Ok(())
+}
+
+/// Serialize `value` as two-space indented JSON with a trailing newline and write it via [`write_file`]
+///
+/// # Errors
+/// Returns error if serialization or the write fails.
+pub async fn write_json_file(path: impl AsRef<Path>, value: &impl Serialize) -> Result<()> {
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"  ");
+    let mut ser = serde_json::Serializer::with_formatter(Vec::new(), formatter);
+    value.serialize(&mut ser)?;
+    write_file(path, format!("{}\n", String::from_utf8(ser.into_inner())?)).await
+}
+
+/// Run `future` with every [`write_file`] redirected into memory instead of disk.
+///
+/// Returns the future's output together with the captured changes, sorted by path.
+/// Files whose final content equals their original content are omitted.
+pub async fn capture_writes<F: Future>(future: F) -> (F::Output, Vec<FileChange>) {
+    let (output, writes) = CAPTURED_WRITES
+        .scope(RefCell::new(BTreeMap::new()), async {
+            let output = future.await;
+            (output, CAPTURED_WRITES.with(|writes| writes.take()))
+        })
+        .await;
+    let changes = writes
+        .into_values()
+        .filter(|change| change.original != change.updated)
+        .collect();
+    (output, changes)
+}
+
+/// Write changes recorded by [`capture_writes`] to disk, e.g. once the user approved them
+///
+/// # Errors
+/// Returns error if any file cannot be written.
+pub async fn apply_file_changes(changes: &[FileChange]) -> Result<()> {
+    for change in changes {
+        write_file(&change.path, change.updated.as_str()).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_write_file_without_capture_writes_to_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("file.txt");
+
+        write_file(&path, "hello").await.unwrap();
+
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "hello");
+        assert_eq!(read_file(&path).await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_capture_writes_does_not_touch_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("file.txt");
+        tokio::fs::write(&path, "before\n").await.unwrap();
+
+        let (result, changes) = capture_writes(async {
+            write_file(&path, "after\n").await?;
+            read_file(&path).await
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "after\n");
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "before\n");
+        assert_eq!(
+            changes,
+            vec![FileChange {
+                path: path.clone(),
+                original: "before\n".to_string(),
+                updated: "after\n".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capture_writes_keeps_first_original_and_last_update() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("file.txt");
+        tokio::fs::write(&path, "one").await.unwrap();
+
+        let (_, changes) = capture_writes(async {
+            write_file(&path, "two").await.unwrap();
+            write_file(&path, "three").await.unwrap();
+        })
+        .await;
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].original, "one");
+        assert_eq!(changes[0].updated, "three");
+    }
+
+    #[tokio::test]
+    async fn test_capture_writes_skips_unchanged_and_handles_new_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let same = temp_dir.path().join("same.txt");
+        let new = temp_dir.path().join("new.txt");
+        tokio::fs::write(&same, "same").await.unwrap();
+
+        let (_, changes) = capture_writes(async {
+            write_file(&same, "same").await.unwrap();
+            write_file(&new, "created").await.unwrap();
+        })
+        .await;
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, new);
+        assert_eq!(changes[0].original, "");
+        assert!(!new.exists());
+    }
+
+    #[tokio::test]
+    async fn test_apply_file_changes_writes_captured_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("file.txt");
+        tokio::fs::write(&path, "one").await.unwrap();
+
+        let (_, changes) = capture_writes(async {
+            write_file(&path, "two").await.unwrap();
+        })
+        .await;
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "one");
+
+        apply_file_changes(&changes).await.unwrap();
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "two");
+    }
+}